@@ -0,0 +1,112 @@
+// Shared by the benchmarks: a global allocator counting heap allocations,
+// a typical WebRTC offer and a simple timing loop.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+pub struct CountingAllocator;
+
+pub static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+pub const ITERATIONS: usize = 10_000;
+
+pub const OFFER_SDP: &str = "v=0\r
+o=- 4611731400430051336 2 IN IP4 127.0.0.1\r
+s=-\r
+t=0 0\r
+a=group:BUNDLE 0 1 2\r
+a=extmap-allow-mixed\r
+a=msid-semantic: WMS 3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc\r
+m=audio 9 UDP/TLS/RTP/SAVPF 111 63 9 0 8 13 110 126\r
+c=IN IP4 0.0.0.0\r
+a=rtcp:9 IN IP4 0.0.0.0\r
+a=ice-ufrag:Gh2c\r
+a=ice-pwd:3zUiRr5gHbS0aBkPQVvvDbQy\r
+a=ice-options:trickle\r
+a=fingerprint:sha-256 8F:5A:1C:0E:65:3B:7D:14:0C:57:3A:31:DD:5D:80:D7:1E:70:F5:87:6C:5C:31:E2:0A:EE:D8:B8:6B:63:17:3E\r
+a=setup:actpass\r
+a=mid:0\r
+a=extmap:1 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r
+a=extmap:2 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time\r
+a=extmap:3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01\r
+a=sendrecv\r
+a=msid:3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc 6e4ab2a4-a1a3-4a66-90c5-9b1c5d7b5ed1\r
+a=rtcp-mux\r
+a=rtpmap:111 opus/48000/2\r
+a=rtcp-fb:111 transport-cc\r
+a=fmtp:111 minptime=10;useinbandfec=1\r
+a=rtpmap:9 G722/8000\r
+a=rtpmap:0 PCMU/8000\r
+a=rtpmap:8 PCMA/8000\r
+a=ssrc:1001286040 cname:2VZ7vUaxZQxyB6sR\r
+m=video 9 UDP/TLS/RTP/SAVPF 96 97 102 103\r
+c=IN IP4 0.0.0.0\r
+a=rtcp:9 IN IP4 0.0.0.0\r
+a=ice-ufrag:Gh2c\r
+a=ice-pwd:3zUiRr5gHbS0aBkPQVvvDbQy\r
+a=ice-options:trickle\r
+a=fingerprint:sha-256 8F:5A:1C:0E:65:3B:7D:14:0C:57:3A:31:DD:5D:80:D7:1E:70:F5:87:6C:5C:31:E2:0A:EE:D8:B8:6B:63:17:3E\r
+a=setup:actpass\r
+a=mid:1\r
+a=sendrecv\r
+a=rtcp-mux\r
+a=rtcp-rsize\r
+a=rtpmap:96 VP8/90000\r
+a=rtcp-fb:96 goog-remb\r
+a=rtcp-fb:96 transport-cc\r
+a=rtcp-fb:96 nack\r
+a=rtcp-fb:96 nack pli\r
+a=rtpmap:97 rtx/90000\r
+a=fmtp:97 apt=96\r
+a=rtpmap:102 H264/90000\r
+a=fmtp:102 level-asymmetry-allowed=1;packetization-mode=1;profile-level-id=42001f\r
+a=rtpmap:103 rtx/90000\r
+a=fmtp:103 apt=102\r
+a=ssrc-group:FID 2231627014 632943048\r
+a=ssrc:2231627014 cname:2VZ7vUaxZQxyB6sR\r
+a=ssrc:632943048 cname:2VZ7vUaxZQxyB6sR\r
+m=application 9 UDP/DTLS/SCTP webrtc-datachannel\r
+c=IN IP4 0.0.0.0\r
+a=ice-ufrag:Gh2c\r
+a=ice-pwd:3zUiRr5gHbS0aBkPQVvvDbQy\r
+a=setup:actpass\r
+a=mid:2\r
+a=sctp-port:5000\r
+";
+
+// run prints the time and heap allocations per call of f.
+pub fn run(name: &str, unit: &str, f: impl Fn()) {
+    f();
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+
+    println!(
+        "{:<10} {:>8.2} us/{} {:>8} allocations/{}",
+        name,
+        elapsed.as_secs_f64() * 1e6 / ITERATIONS as f64,
+        unit,
+        allocations / ITERATIONS,
+        unit,
+    );
+}
@@ -0,0 +1,31 @@
+// Compares Display based marshaling with marshal_into on a typical WebRTC
+// offer, reporting time and heap allocations per marshal. to_string grows
+// its buffer field by field, marshal_into reserves marshal_len_hint first.
+//
+//     cargo bench --bench marshal
+
+mod common;
+
+use std::cell::RefCell;
+
+use common::{run, OFFER_SDP};
+use webrtc_rs_sdp::session_description::SessionDescription;
+
+fn main() {
+    let sd = OFFER_SDP.parse::<SessionDescription>().unwrap();
+    assert_eq!(sd.marshal(), OFFER_SDP);
+
+    run("to_string", "marshal", || {
+        sd.to_string();
+    });
+    run("marshal", "marshal", || {
+        let mut buf = String::new();
+        sd.marshal_into(&mut buf);
+    });
+    let buf = RefCell::new(String::new());
+    run("reused", "marshal", || {
+        let mut buf = buf.borrow_mut();
+        buf.clear();
+        sd.marshal_into(&mut buf);
+    });
+}
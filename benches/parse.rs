@@ -0,0 +1,133 @@
+// Compares SessionDescription::unmarshal with Parser and the borrowed parser
+// on a typical WebRTC offer and on a larger simulcast offer, reporting time
+// and heap allocations per parse.
+//
+//     cargo bench --bench parse
+
+mod common;
+
+use std::io::Cursor;
+
+use common::{run, OFFER_SDP};
+use webrtc_rs_sdp::parser::Parser;
+use webrtc_rs_sdp::session_description::SessionDescription;
+
+// simulcast_offer is an offer with an audio section and three video
+// sections sending three simulcast layers each, about 200 lines.
+fn simulcast_offer() -> String {
+    let mut sdp = String::from(
+        "v=0\r
+o=- 4611731400430051336 2 IN IP4 127.0.0.1\r
+s=-\r
+t=0 0\r
+a=group:BUNDLE 0 1 2 3\r
+a=extmap-allow-mixed\r
+a=msid-semantic: WMS 3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc\r
+m=audio 9 UDP/TLS/RTP/SAVPF 111 63 9 0 8\r
+c=IN IP4 0.0.0.0\r
+a=rtcp:9 IN IP4 0.0.0.0\r
+a=candidate:1467250027 1 udp 2122260223 192.168.0.196 46243 typ host generation 0\r
+a=candidate:1853887674 1 udp 1518280447 47.61.61.61 36768 typ srflx raddr 192.168.0.196 rport 36768 generation 0\r
+a=ice-ufrag:Gh2c\r
+a=ice-pwd:3zUiRr5gHbS0aBkPQVvvDbQy\r
+a=ice-options:trickle\r
+a=fingerprint:sha-256 8F:5A:1C:0E:65:3B:7D:14:0C:57:3A:31:DD:5D:80:D7:1E:70:F5:87:6C:5C:31:E2:0A:EE:D8:B8:6B:63:17:3E\r
+a=setup:actpass\r
+a=mid:0\r
+a=extmap:1 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r
+a=extmap:3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01\r
+a=sendrecv\r
+a=msid:3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc 6e4ab2a4-a1a3-4a66-90c5-9b1c5d7b5ed1\r
+a=rtcp-mux\r
+a=rtpmap:111 opus/48000/2\r
+a=rtcp-fb:111 transport-cc\r
+a=fmtp:111 minptime=10;useinbandfec=1\r
+a=rtpmap:63 red/48000/2\r
+a=fmtp:63 111/111\r
+a=rtpmap:9 G722/8000\r
+a=rtpmap:0 PCMU/8000\r
+a=rtpmap:8 PCMA/8000\r
+a=ssrc:1001286040 cname:2VZ7vUaxZQxyB6sR\r
+",
+    );
+    for mid in 1..=3 {
+        sdp.push_str(&format!(
+            "m=video 9 UDP/TLS/RTP/SAVPF 96 97 98 99 100 101 102 103 104 105\r
+c=IN IP4 0.0.0.0\r
+a=rtcp:9 IN IP4 0.0.0.0\r
+a=ice-ufrag:Gh2c\r
+a=ice-pwd:3zUiRr5gHbS0aBkPQVvvDbQy\r
+a=ice-options:trickle\r
+a=fingerprint:sha-256 8F:5A:1C:0E:65:3B:7D:14:0C:57:3A:31:DD:5D:80:D7:1E:70:F5:87:6C:5C:31:E2:0A:EE:D8:B8:6B:63:17:3E\r
+a=setup:actpass\r
+a=mid:{mid}\r
+a=extmap:2 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time\r
+a=extmap:3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01\r
+a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r
+a=extmap:10 urn:ietf:params:rtp-hdrext:sdes:rtp-stream-id\r
+a=extmap:11 urn:ietf:params:rtp-hdrext:sdes:repaired-rtp-stream-id\r
+a=sendonly\r
+a=msid:3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc 0c7d2a8e-video-{mid}\r
+a=rtcp-mux\r
+a=rtcp-rsize\r
+a=rtpmap:96 VP8/90000\r
+a=rtcp-fb:96 goog-remb\r
+a=rtcp-fb:96 transport-cc\r
+a=rtcp-fb:96 ccm fir\r
+a=rtcp-fb:96 nack\r
+a=rtcp-fb:96 nack pli\r
+a=rtpmap:97 rtx/90000\r
+a=fmtp:97 apt=96\r
+a=rtpmap:98 VP9/90000\r
+a=rtcp-fb:98 goog-remb\r
+a=rtcp-fb:98 transport-cc\r
+a=rtcp-fb:98 ccm fir\r
+a=rtcp-fb:98 nack\r
+a=rtcp-fb:98 nack pli\r
+a=fmtp:98 profile-id=0\r
+a=rtpmap:99 rtx/90000\r
+a=fmtp:99 apt=98\r
+a=rtpmap:100 H264/90000\r
+a=rtcp-fb:100 goog-remb\r
+a=rtcp-fb:100 transport-cc\r
+a=rtcp-fb:100 ccm fir\r
+a=rtcp-fb:100 nack\r
+a=rtcp-fb:100 nack pli\r
+a=fmtp:100 level-asymmetry-allowed=1;packetization-mode=1;profile-level-id=42e01f\r
+a=rtpmap:101 rtx/90000\r
+a=fmtp:101 apt=100\r
+a=rtpmap:102 AV1/90000\r
+a=rtcp-fb:102 transport-cc\r
+a=rtcp-fb:102 nack\r
+a=rtpmap:103 rtx/90000\r
+a=fmtp:103 apt=102\r
+a=rtpmap:104 red/90000\r
+a=rtpmap:105 ulpfec/90000\r
+a=rid:q send\r
+a=rid:h send\r
+a=rid:f send\r
+a=simulcast:send q;h;f\r
+"
+        ));
+    }
+    sdp
+}
+
+fn main() {
+    for (name, sdp) in [
+        ("offer", OFFER_SDP.to_owned()),
+        ("simulcast", simulcast_offer()),
+    ] {
+        println!("{} ({} lines)", name, sdp.lines().count());
+        run("owned", "parse", || {
+            let mut reader = Cursor::new(sdp.as_bytes());
+            SessionDescription::unmarshal(&mut reader).unwrap();
+        });
+        run("parser", "parse", || {
+            Parser::new().parse(sdp.as_bytes()).unwrap();
+        });
+        run("borrowed", "parse", || {
+            SessionDescription::parse_borrowed(&sdp).unwrap();
+        });
+    }
+}
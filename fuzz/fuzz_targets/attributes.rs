@@ -0,0 +1,44 @@
+// Parses arbitrary input as each typed attribute value, which must not panic,
+// and checks that parsed values survive a round trip.
+//
+//     cargo fuzz run attributes
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use webrtc_rs_sdp::prelude::*;
+
+macro_rules! check {
+    ($input:expr, $($ty:ty),+ $(,)?) => {
+        $(
+            if let Ok(value) = $input.parse::<$ty>() {
+                assert_eq!(value.to_string().parse::<$ty>().ok(), Some(value));
+            }
+        )+
+    };
+}
+
+fuzz_target!(|input: &str| {
+    check!(
+        input,
+        Attribute,
+        Candidate,
+        ExtMap,
+        RtpMap,
+        Fmtp,
+        Fingerprint,
+        Ssrc,
+        SsrcGroup,
+        Msid,
+        MsidSemantic,
+        RtcpFeedback,
+        Rid,
+        Simulcast,
+        SctpMap,
+        Rtcp,
+        Group,
+        MaxPRate,
+        PTime,
+        IceOptions,
+    );
+});
@@ -0,0 +1,15 @@
+// Parses arbitrary input as Bandwidth, which must not panic, and checks that a
+// parsed value survives a round trip.
+//
+//     cargo fuzz run bandwidth
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use webrtc_rs_sdp::prelude::*;
+
+fuzz_target!(|input: &str| {
+    if let Ok(value) = input.parse::<Bandwidth>() {
+        assert_eq!(value.to_string().parse::<Bandwidth>().ok(), Some(value));
+    }
+});
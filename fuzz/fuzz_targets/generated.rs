@@ -0,0 +1,15 @@
+// Builds valid session descriptions from the fuzzer input with the
+// "arbitrary" feature and checks that they survive marshal and parse, which
+// reaches deeper into the parser than mutating text does.
+//
+//     cargo fuzz run generated
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use webrtc_rs_sdp::SessionDescription;
+
+fuzz_target!(|sd: SessionDescription| {
+    let text = sd.marshal();
+    assert_eq!(text.parse::<SessionDescription>().ok(), Some(sd));
+});
@@ -0,0 +1,29 @@
+// Parses arbitrary input with every session description parser. They must
+// agree, and a parsed description must survive a round trip.
+//
+//     cargo fuzz run session_description
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use webrtc_rs_sdp::session_description::SessionDescription;
+
+fuzz_target!(|data: &[u8]| {
+    let streamed = SessionDescription::from_reader(data).ok();
+
+    let input = match std::str::from_utf8(data) {
+        Ok(input) => input,
+        Err(_) => return,
+    };
+    let owned = input.parse::<SessionDescription>().ok();
+    let borrowed = SessionDescription::parse_borrowed(input)
+        .ok()
+        .map(|sd| sd.to_owned());
+    assert_eq!(borrowed, owned);
+    assert_eq!(streamed, owned);
+
+    if let Some(sd) = owned {
+        let text = sd.marshal();
+        assert_eq!(text.parse::<SessionDescription>().ok(), Some(sd));
+    }
+});
@@ -0,0 +1,15 @@
+// Parses arbitrary input as Timing, which must not panic, and checks that a
+// parsed value survives a round trip.
+//
+//     cargo fuzz run timing
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use webrtc_rs_sdp::prelude::*;
+
+fuzz_target!(|input: &str| {
+    if let Ok(value) = input.parse::<Timing>() {
+        assert_eq!(value.to_string().parse::<Timing>().ok(), Some(value));
+    }
+});
@@ -0,0 +1,348 @@
+use core::fmt::Write;
+
+use super::alloc_prelude::*;
+use super::basic::{ConferenceType, FrameRate, Lang, Orient, Quality, SdpLang, Tool};
+use super::candidate::{Candidate, RemoteCandidates};
+use super::charset::Charset;
+use super::common_description::{
+    Attribute, Bandwidth, BandwidthKind, ConnectionInformation, EncryptionKey, TypedAttribute,
+};
+use super::content::{Content, Label};
+use super::crypto::Crypto;
+use super::diagnostic::{Diagnostic, Severity};
+use super::direction::Direction;
+use super::extmap::{ExtMap, ExtmapAllowMixed};
+use super::fingerprint::Fingerprint;
+use super::fmtp::Fmtp;
+use super::group::{Group, Mid};
+use super::ice::{IceOptions, IcePwd, IceUfrag};
+use super::keymgmt::KeyMgmt;
+use super::maxprate::MaxPRate;
+use super::media_description::{MediaDescription, MediaName};
+use super::msid::{Msid, MsidSemantic};
+use super::ptime::{MaxPTime, PTime};
+use super::rtcp::{Rtcp, RtcpMux, RtcpMuxOnly};
+use super::rtcpfb::RtcpFeedback;
+use super::rtpmap::RtpMap;
+use super::sctp::{MaxMessageSize, SctpMap, SctpPort};
+use super::session_description::{SessionDescription, Timing};
+use super::setup::{Connection, Setup};
+use super::simulcast::{Rid, Simulcast};
+use super::ssrc::{Ssrc, SsrcGroup};
+
+#[cfg(test)]
+mod annotate_test;
+
+/// COMMENT_COLUMN is the column at which the comments of annotated lines
+/// start, unless the line is longer.
+const COMMENT_COLUMN: usize = 40;
+
+const REDACTED: &str = "REDACTED";
+
+/// Describe returns None if an attribute is not of its type, and the
+/// description of the attribute otherwise.
+type Describe = fn(&Attribute) -> Option<Option<String>>;
+
+/// TYPED_ATTRIBUTES describes the attributes of every typed attribute, see
+/// TypedAttribute::describe. Attributes matched by none of them are flagged
+/// as unknown.
+const TYPED_ATTRIBUTES: &[Describe] = &[
+    describe::<Candidate>,
+    describe::<Charset>,
+    describe::<ConferenceType>,
+    describe::<Connection>,
+    describe::<Content>,
+    describe::<Crypto>,
+    describe::<Direction>,
+    describe::<ExtMap>,
+    describe::<ExtmapAllowMixed>,
+    describe::<Fingerprint>,
+    describe::<FrameRate>,
+    describe::<Fmtp>,
+    describe::<Group>,
+    describe::<IceOptions>,
+    describe::<IcePwd>,
+    describe::<IceUfrag>,
+    describe::<KeyMgmt>,
+    describe::<Label>,
+    describe::<Lang>,
+    describe::<MaxMessageSize>,
+    describe::<MaxPRate>,
+    describe::<MaxPTime>,
+    describe::<Mid>,
+    describe::<Msid>,
+    describe::<MsidSemantic>,
+    describe::<Orient>,
+    describe::<PTime>,
+    describe::<Quality>,
+    describe::<RemoteCandidates>,
+    describe::<Rid>,
+    describe::<Rtcp>,
+    describe::<RtcpFeedback>,
+    describe::<RtcpMux>,
+    describe::<RtcpMuxOnly>,
+    describe::<RtpMap>,
+    describe::<SctpMap>,
+    describe::<SctpPort>,
+    describe::<SdpLang>,
+    describe::<Setup>,
+    describe::<Simulcast>,
+    describe::<Ssrc>,
+    describe::<SsrcGroup>,
+    describe::<Tool>,
+];
+
+/// is_typed returns true if the attribute is of one of the typed attributes.
+pub(crate) fn is_typed(attribute: &Attribute) -> bool {
+    TYPED_ATTRIBUTES.iter().any(|f| f(attribute).is_some())
+}
+
+impl SessionDescription {
+    /// annotate returns the description as SDP text for humans: each line is
+    /// followed by a comment explaining it, and the findings of validate are
+    /// inserted after the line they concern. Keys and passwords are
+    /// redacted, so the text is not meant to be parsed back.
+    pub fn annotate(&self) -> String {
+        annotate(&self.marshal(), &self.validate())
+    }
+}
+
+impl MediaDescription {
+    /// annotate is SessionDescription::annotate for a single media
+    /// description.
+    pub fn annotate(&self) -> String {
+        let diagnostics = self
+            .validate()
+            .into_iter()
+            .map(|d| d.in_media(0))
+            .collect::<Vec<_>>();
+        annotate(&self.to_string(), &diagnostics)
+    }
+}
+
+fn annotate(text: &str, diagnostics: &[Diagnostic]) -> String {
+    let lines = text
+        .split("\r\n")
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+
+    // The media description of each line, None for the session level.
+    let mut media_index = None;
+    let sections = lines
+        .iter()
+        .map(|line| {
+            if line.starts_with("m=") {
+                media_index = Some(media_index.map_or(0, |i| i + 1));
+            }
+            media_index
+        })
+        .collect::<Vec<_>>();
+
+    // Diagnostics go after the first line of their field in their section,
+    // or after the first line of the section.
+    let mut placed = vec![vec![]; lines.len()];
+    for diagnostic in diagnostics {
+        let mut candidates = (0..lines.len()).filter(|&i| sections[i] == diagnostic.media_index);
+        let first = candidates.clone().next();
+        if let Some(i) = candidates
+            .find(|&i| is_field(lines[i], &diagnostic.field))
+            .or(first)
+        {
+            placed[i].push(diagnostic);
+        }
+    }
+
+    let mut annotated = String::new();
+    for (line, diagnostics) in lines.iter().zip(&placed) {
+        let redacted = redact(line);
+        match describe_line(line) {
+            Some(comment) => {
+                let _ = writeln!(
+                    annotated,
+                    "{:<width$} ; {}",
+                    redacted,
+                    comment,
+                    width = COMMENT_COLUMN - 1
+                );
+            }
+            None => {
+                let _ = writeln!(annotated, "{}", redacted);
+            }
+        }
+        for diagnostic in diagnostics {
+            let severity = match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            };
+            let _ = writeln!(
+                annotated,
+                "{:<width$} ; {}: {}",
+                "",
+                severity,
+                diagnostic.message,
+                width = COMMENT_COLUMN - 1
+            );
+        }
+    }
+    annotated
+}
+
+/// is_field reports whether the line is of the field of a diagnostic, e.g.
+/// "m=" or "a=ptime".
+fn is_field(line: &str, field: &str) -> bool {
+    if field.is_empty() || !line.starts_with(field) {
+        return false;
+    }
+    field.ends_with('=') || matches!(line.as_bytes().get(field.len()), None | Some(b':'))
+}
+
+fn describe_line(line: &str) -> Option<String> {
+    let value = match line.get(1..2) {
+        Some("=") => &line[2..],
+        _ => return Some("malformed line".to_owned()),
+    };
+    let description = match line.as_bytes()[0] {
+        b'v' => "protocol version".to_owned(),
+        b'o' => match value.split(' ').nth(2) {
+            Some(version) => format!("origin, session version {}", version),
+            None => "origin".to_owned(),
+        },
+        b's' if value == "-" => "no session name".to_owned(),
+        b's' => "session name".to_owned(),
+        b'i' => "information".to_owned(),
+        b'u' => "description URI".to_owned(),
+        b'e' => "email address".to_owned(),
+        b'p' => "phone number".to_owned(),
+        b'c' => describe_connection(&value.parse().ok()?),
+        b'b' => describe_bandwidth(&value.parse().ok()?),
+        b't' => describe_timing(value)?,
+        b'r' => "repeat times".to_owned(),
+        b'z' => "time zone adjustments".to_owned(),
+        b'k' => format!(
+            "{} encryption key",
+            value.parse::<EncryptionKey>().ok()?.method()
+        ),
+        b'm' => {
+            let media_name = value.parse::<MediaName>().ok()?;
+            if media_name.port.value == 0 {
+                format!("rejected {} media", media_name.media)
+            } else {
+                format!("{} media", media_name.media)
+            }
+        }
+        b'a' => {
+            let attribute = value.parse::<Attribute>().ok()?;
+            match TYPED_ATTRIBUTES.iter().find_map(|f| f(&attribute)) {
+                Some(description) => description?,
+                None => "unknown attribute".to_owned(),
+            }
+        }
+        _ => "unknown line type".to_owned(),
+    };
+    Some(description)
+}
+
+fn describe<T: TypedAttribute>(attribute: &Attribute) -> Option<Option<String>> {
+    if !T::matches(attribute) {
+        return None;
+    }
+    Some(match T::from_attribute(attribute) {
+        Ok(value) => value.describe(),
+        Err(_) => Some(format!("malformed {} attribute", T::NAME)),
+    })
+}
+
+fn describe_connection(connection_information: &ConnectionInformation) -> String {
+    match &connection_information.address {
+        Some(address) if address.is_multicast() => match address.ttl {
+            Some(ttl) => format!("multicast address, ttl {}", ttl),
+            None => "multicast address".to_owned(),
+        },
+        Some(_) => "connection address".to_owned(),
+        None => "no connection address".to_owned(),
+    }
+}
+
+fn describe_bandwidth(bandwidth: &Bandwidth) -> String {
+    let (per_second, kind) = match bandwidth.kind() {
+        // b=TIAS is in bits per second, the others in kilobits per second.
+        Some(BandwidthKind::Tias) if bandwidth.bandwidth.is_multiple_of(1000) => (
+            format!("{} kbps", bandwidth.bandwidth / 1000),
+            "transport-independent",
+        ),
+        Some(BandwidthKind::Tias) => (
+            format!("{} bps", bandwidth.bandwidth),
+            "transport-independent",
+        ),
+        Some(BandwidthKind::As) => (
+            format!("{} kbps", bandwidth.bandwidth),
+            "application specific",
+        ),
+        Some(BandwidthKind::Ct) => (format!("{} kbps", bandwidth.bandwidth), "conference total"),
+        Some(BandwidthKind::Rr) => (format!("{} bps", bandwidth.bandwidth), "RTCP receivers"),
+        Some(BandwidthKind::Rs) => (format!("{} bps", bandwidth.bandwidth), "RTCP senders"),
+        None if bandwidth.experimental => return "experimental bandwidth".to_owned(),
+        None => return "unknown bandwidth modifier".to_owned(),
+    };
+    format!("{} {}", per_second, kind)
+}
+
+fn describe_timing(value: &str) -> Option<String> {
+    let timing = value.parse::<Timing>().ok()?;
+    Some(if timing.is_permanent() {
+        "permanent session".to_owned()
+    } else if timing.is_unbounded() {
+        "unbounded session".to_owned()
+    } else {
+        format!("session of {} seconds", timing.duration()?)
+    })
+}
+
+/// redact replaces the keys of "k=", "a=crypto" and "a=key-mgmt" and the
+/// ICE password with REDACTED.
+fn redact(line: &str) -> String {
+    if let Some(key) = line.strip_prefix("k=") {
+        return match key.split_once(':') {
+            Some((method, _)) if method != "uri" => format!("k={}:{}", method, REDACTED),
+            _ => line.to_owned(),
+        };
+    }
+    let attribute = match line.strip_prefix("a=") {
+        Some(attribute) => attribute,
+        None => return line.to_owned(),
+    };
+    let (name, value) = match attribute.split_once(':') {
+        Some(split) => split,
+        None => return line.to_owned(),
+    };
+    if name == IcePwd::NAME {
+        format!("a={}:{}", name, REDACTED)
+    } else if name == KeyMgmt::NAME {
+        let protocol_id = value.split(' ').next().unwrap_or_default();
+        format!("a={}:{} {}", name, protocol_id, REDACTED)
+    } else if name == Crypto::NAME {
+        let fields = value
+            .split(' ')
+            .enumerate()
+            .map(|(i, field)| {
+                if i != 2 {
+                    return field.to_owned();
+                }
+                field
+                    .split(';')
+                    .map(|key_param| match key_param.strip_prefix("inline:") {
+                        Some(key_info) => {
+                            let rest = key_info.find('|').map_or("", |i| &key_info[i..]);
+                            format!("inline:{}{}", REDACTED, rest)
+                        }
+                        None => key_param.to_owned(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(";")
+            })
+            .collect::<Vec<_>>();
+        format!("a={}:{}", name, fields.join(" "))
+    } else {
+        line.to_owned()
+    }
+}
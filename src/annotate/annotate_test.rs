@@ -0,0 +1,151 @@
+use super::*;
+use crate::error::Error;
+
+const OFFER_SDP: &str = "v=0\r\n\
+o=- 4611731400430051336 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0 1\r\n\
+a=extmap-allow-mixed\r\n\
+a=msid-semantic: WMS stream\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+c=IN IP4 0.0.0.0\r\n\
+b=TIAS:256000\r\n\
+a=rtcp:9 IN IP4 0.0.0.0\r\n\
+a=candidate:1 1 udp 2122260223 192.168.1.20 50000 typ host\r\n\
+a=ice-ufrag:EsAw\r\n\
+a=ice-pwd:P2uYro0UCOQ4zxjKXaWCBui1\r\n\
+a=ice-options:trickle\r\n\
+a=fingerprint:sha-256 D2:FA:0E:C3:22:59:5E:14:95:69:92:3D:13:B4:84:24:2C:C2:A2:C0:3E:FD:34:8E:5E:EA:6F:AF:52:CE:E6:0F\r\n\
+a=setup:actpass\r\n\
+a=mid:0\r\n\
+a=extmap:3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01\r\n\
+a=extmap:16 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r\n\
+a=sendrecv\r\n\
+a=msid:stream track\r\n\
+a=rtcp-mux\r\n\
+a=rtpmap:111 opus/48000/2\r\n\
+a=rtcp-fb:111 transport-cc\r\n\
+a=fmtp:111 minptime=10;useinbandfec=1\r\n\
+a=ptime:120\r\n\
+a=maxptime:60\r\n\
+a=ssrc:3735928559 cname:4TOk42mSjXCkVIa6\r\n\
+a=x-google-flag:conference\r\n\
+m=video 9 RTP/SAVP 96\r\n\
+c=IN IP4 0.0.0.0\r\n\
+k=base64:c2VjcmV0\r\n\
+a=crypto:1 AES_CM_128_HMAC_SHA1_80 inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR|2^20|1:4\r\n\
+a=mid:1\r\n\
+a=recvonly\r\n\
+a=rtpmap:96 VP8/90000\r\n";
+
+const ANNOTATED: &str = "\
+v=0                                     ; protocol version
+o=- 4611731400430051336 2 IN IP4 127.0.0.1 ; origin, session version 2
+s=-                                     ; no session name
+t=0 0                                   ; permanent session
+a=group:BUNDLE 0 1                      ; BUNDLE group of mids 0, 1
+a=extmap-allow-mixed
+a=msid-semantic: WMS stream
+m=audio 9 UDP/TLS/RTP/SAVPF 111         ; audio media
+c=IN IP4 0.0.0.0                        ; connection address
+b=TIAS:256000                           ; 256 kbps transport-independent
+a=rtcp:9 IN IP4 0.0.0.0                 ; RTCP port 9
+a=candidate:1 1 udp 2122260223 192.168.1.20 50000 typ host ; udp host candidate, component 1
+a=ice-ufrag:EsAw                        ; ICE username fragment
+a=ice-pwd:REDACTED                      ; ICE password
+a=ice-options:trickle                   ; ICE options: trickle
+a=fingerprint:sha-256 D2:FA:0E:C3:22:59:5E:14:95:69:92:3D:13:B4:84:24:2C:C2:A2:C0:3E:FD:34:8E:5E:EA:6F:AF:52:CE:E6:0F ; sha-256 certificate fingerprint
+a=setup:actpass                         ; DTLS client or server
+a=mid:0                                 ; media id `0`
+a=extmap:3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01 ; transport-wide CC, one-byte id
+a=extmap:16 urn:ietf:params:rtp-hdrext:ssrc-audio-level ; audio level, two-byte id
+a=sendrecv                              ; send and receive
+a=msid:stream track                     ; track `track` of stream `stream`
+a=rtcp-mux
+a=rtpmap:111 opus/48000/2               ; payload type 111: opus, 48000 Hz, 2 channels
+a=rtcp-fb:111 transport-cc              ; RTCP feedback for payload type 111
+a=fmtp:111 minptime=10;useinbandfec=1   ; parameters of payload type 111
+a=ptime:120
+                                        ; warning: ptime 120 exceeds maxptime 60
+a=maxptime:60
+a=ssrc:3735928559 cname:4TOk42mSjXCkVIa6 ; cname of SSRC 3735928559
+a=x-google-flag:conference              ; unknown attribute
+m=video 9 RTP/SAVP 96                   ; video media
+c=IN IP4 0.0.0.0                        ; connection address
+k=base64:REDACTED                       ; base64 encryption key
+                                        ; warning: the base64 key method is NOT RECOMMENDED
+a=crypto:1 AES_CM_128_HMAC_SHA1_80 inline:REDACTED|2^20|1:4 ; SRTP AES_CM_128_HMAC_SHA1_80 keys, tag 1
+a=mid:1                                 ; media id `1`
+a=recvonly                              ; receive only
+a=rtpmap:96 VP8/90000                   ; payload type 96: VP8, 90000 Hz
+";
+
+#[test]
+fn test_annotate() -> Result<(), Error> {
+    let sd = OFFER_SDP.parse::<SessionDescription>()?;
+    let annotated = sd.annotate();
+    assert_eq!(annotated, ANNOTATED, "{}", annotated);
+
+    Ok(())
+}
+
+#[test]
+fn test_annotate_redacts_secrets() -> Result<(), Error> {
+    let sd = OFFER_SDP.parse::<SessionDescription>()?;
+    let annotated = sd.annotate();
+    for secret in [
+        "P2uYro0UCOQ4zxjKXaWCBui1",
+        "c2VjcmV0",
+        "PS1uQCVeeCFCanVmcjkp",
+    ]
+    .iter()
+    {
+        assert!(!annotated.contains(secret), "{}", annotated);
+    }
+
+    let key_mgmt =
+        "a=key-mgmt:mikey AQAFgM0XflABAAAAAAAAAAAAAAsAyONQ6gAAAAAJAAAQbWlrZXlAZXhhbXBsZS5jb20=";
+    assert_eq!(redact(key_mgmt), "a=key-mgmt:mikey REDACTED");
+    assert_eq!(
+        redact("a=crypto:1 AES_CM_128_HMAC_SHA1_32 inline:MTIz|2^20|1:4;inline:QUJj|2:4 FEC_ORDER=FEC_SRTP"),
+        "a=crypto:1 AES_CM_128_HMAC_SHA1_32 inline:REDACTED|2^20|1:4;inline:REDACTED|2:4 FEC_ORDER=FEC_SRTP"
+    );
+    assert_eq!(redact("k=prompt"), "k=prompt");
+    assert_eq!(redact("k=clear:secret"), "k=clear:REDACTED");
+
+    Ok(())
+}
+
+#[test]
+fn test_annotate_media_description() -> Result<(), Error> {
+    let sd = OFFER_SDP.parse::<SessionDescription>()?;
+    let annotated = sd.media_descriptions[1].annotate();
+    assert!(annotated.starts_with("m=video 9 RTP/SAVP 96                   ; video media\n"));
+    assert!(annotated.contains("k=base64:REDACTED"));
+    assert!(annotated.contains("; warning: the base64 key method is NOT RECOMMENDED\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_annotate_malformed() -> Result<(), Error> {
+    let sd = "v=0\r\n\
+o=- 1 1 IN IP4 127.0.0.1\r\n\
+s=Talk\r\n\
+c=IN IP4 224.2.1.1/127\r\n\
+t=2873397496 2873404696\r\n\
+m=audio 0 RTP/AVP 0\r\n\
+a=ptime:fast\r\n"
+        .parse::<SessionDescription>()?;
+    let annotated = sd.annotate();
+    assert!(annotated.contains("s=Talk                                  ; session name\n"));
+    assert!(annotated.contains("; multicast address, ttl 127\n"));
+    assert!(annotated.contains("; session of 7200 seconds\n"));
+    assert!(annotated.contains("m=audio 0 RTP/AVP 0                     ; rejected audio media\n"));
+    assert!(
+        annotated.contains("a=ptime:fast                            ; malformed ptime attribute\n")
+    );
+
+    Ok(())
+}
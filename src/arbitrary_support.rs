@@ -0,0 +1,1034 @@
+// Arbitrary implementations, enabled by the "arbitrary" feature.
+//
+// The arbitrary crate is used rather than proptest because it is what
+// cargo-fuzz and libfuzzer-sys build on, so the fuzz targets in fuzz/ and
+// downstream fuzzers can take a SessionDescription or any field type as
+// input directly.
+//
+// Every implementation only produces values which the parser accepts and
+// which survive a round trip through Display, i.e. for each type T
+// `x.to_string().parse::<T>() == Ok(x)`. Blind derives would not do that,
+// since most types have rules beyond their field types: no whitespace in
+// usernames, payload types up to 127, key lengths matching the hash
+// function, and so on. Values are built from the public constructors where
+// there are any, so that their checks apply too. Secrets, i.e. "k=" and the
+// keys of "a=crypto:", are random bytes, which makes them dummy ones.
+
+use std::net::IpAddr;
+use std::num::NonZeroU16;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use super::basic::{ConferenceType, FrameRate, Lang, LanguageTag, Orient, Quality, SdpLang, Tool};
+use super::candidate::{
+    Candidate, CandidateAddress, CandidateType, RemoteCandidate, RemoteCandidates, TcpType,
+    Transport,
+};
+use super::charset::Charset;
+use super::common_description::*;
+use super::content::{Content, ContentValue, Label};
+use super::crypto::{Crypto, CryptoSuite, KeyParam, Lifetime, Mki};
+use super::direction::Direction;
+use super::extmap::{ExtMap, ExtMapId, ExtmapAllowMixed};
+use super::fingerprint::{Fingerprint, HashFunction};
+use super::fmtp::Fmtp;
+use super::group::{Group, GroupSemantics, Mid};
+use super::hdrext::KnownExtension;
+use super::ice::{IceOptions, IcePwd, IceUfrag};
+use super::keymgmt::KeyMgmt;
+use super::maxprate::MaxPRate;
+use super::media_description::*;
+use super::msid::{Msid, MsidSemantic, MSID_SEMANTIC_WMS};
+use super::ptime::{MaxPTime, PTime};
+use super::rtcp::{Rtcp, RtcpMux, RtcpMuxOnly};
+use super::rtcpfb::{PayloadTypeRef, RtcpFeedback, RtcpFeedbackType};
+use super::rtpmap::{RtpMap, MAX_PAYLOAD_TYPE};
+use super::sctp::{MaxMessageSize, SctpMap, SctpPort, DATACHANNEL_FORMAT};
+use super::session_description::*;
+use super::setup::{Connection, Setup};
+use super::simulcast::{Rid, RidDirection, Simulcast, SimulcastRid};
+use super::ssrc::{Ssrc, SsrcGroup, SsrcGroupSemantics};
+use super::util::LineEnding;
+
+#[cfg(test)]
+mod arbitrary_support_test;
+
+const ALPHA: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const ALNUM: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+const DIGITS: &[u8] = b"0123456789";
+// ice-char = ALPHA / DIGIT / "+" / "/"
+const ICE_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789+/";
+
+/// string_of returns a string of characters from alphabet.
+fn string_of(
+    u: &mut Unstructured<'_>,
+    alphabet: &[u8],
+    len: RangeInclusive<usize>,
+) -> Result<String> {
+    let len = u.int_in_range(len)?;
+    let mut value = String::with_capacity(len);
+    for _ in 0..len {
+        value.push(char::from(*u.choose(alphabet)?));
+    }
+    Ok(value)
+}
+
+/// word returns up to max letters and digits, which make a valid token.
+fn word(u: &mut Unstructured<'_>, max: usize) -> Result<String> {
+    string_of(u, ALNUM, 1..=max)
+}
+
+/// extension returns a word which doesn't collide with any registered
+/// value, for the Other variants of the enums.
+fn extension(u: &mut Unstructured<'_>) -> Result<String> {
+    Ok(format!("x-{}", word(u, 8)?))
+}
+
+/// text returns a few words separated by single spaces, as used by the
+/// free-form fields. Leading and trailing whitespace would be trimmed.
+fn text(u: &mut Unstructured<'_>) -> Result<String> {
+    Ok(vec_of(u, 1..=4, |u| word(u, 8))?.join(" "))
+}
+
+fn vec_of<'a, T, F>(
+    u: &mut Unstructured<'a>,
+    len: RangeInclusive<usize>,
+    mut f: F,
+) -> Result<Vec<T>>
+where
+    F: FnMut(&mut Unstructured<'a>) -> Result<T>,
+{
+    let len = u.int_in_range(len)?;
+    (0..len).map(|_| f(u)).collect()
+}
+
+fn option_of<'a, T, F>(u: &mut Unstructured<'a>, f: F) -> Result<Option<T>>
+where
+    F: FnOnce(&mut Unstructured<'a>) -> Result<T>,
+{
+    Ok(if u.arbitrary()? { Some(f(u)?) } else { None })
+}
+
+/// valid turns the result of a constructor into a generator result. The
+/// inputs are valid by construction, so an error is a bug in the generator
+/// or the constructor; IncorrectFormat tells the fuzzer to move on.
+fn valid<T, E>(result: core::result::Result<T, E>) -> Result<T> {
+    result.map_err(|_| arbitrary::Error::IncorrectFormat)
+}
+
+/// parsed is valid for the types without a constructor for their parts.
+fn parsed<T: FromStr>(value: &str) -> Result<T> {
+    valid(value.parse())
+}
+
+fn payload_type(u: &mut Unstructured<'_>) -> Result<u8> {
+    u.int_in_range(0..=MAX_PAYLOAD_TYPE)
+}
+
+fn unicast_address(u: &mut Unstructured<'_>) -> Result<IpAddr> {
+    let ip: IpAddr = u.arbitrary()?;
+    Ok(if ip.is_multicast() {
+        IpAddr::from([192, 0, 2, 1])
+    } else {
+        ip
+    })
+}
+
+fn fqdn(u: &mut Unstructured<'_>) -> Result<String> {
+    Ok(format!("{}.example.com", word(u, 16)?.to_lowercase()))
+}
+
+// impl_arbitrary_choice implements Arbitrary for a type with a fixed set of
+// values.
+macro_rules! impl_arbitrary_choice {
+    ($ty:ty, [$($value:expr),+ $(,)?]) => {
+        impl<'a> Arbitrary<'a> for $ty {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                Ok(u.choose(&[$($value),+])?.clone())
+            }
+        }
+    };
+}
+
+impl_arbitrary_choice!(
+    Direction,
+    [
+        Direction::SendRecv,
+        Direction::SendOnly,
+        Direction::RecvOnly,
+        Direction::Inactive,
+    ]
+);
+impl_arbitrary_choice!(
+    Orient,
+    [Orient::Portrait, Orient::Landscape, Orient::Seascape]
+);
+impl_arbitrary_choice!(
+    Setup,
+    [
+        Setup::Active,
+        Setup::Passive,
+        Setup::ActPass,
+        Setup::HoldConn
+    ]
+);
+impl_arbitrary_choice!(Connection, [Connection::New, Connection::Existing]);
+impl_arbitrary_choice!(RidDirection, [RidDirection::Send, RidDirection::Recv]);
+impl_arbitrary_choice!(
+    CandidateType,
+    [
+        CandidateType::Host,
+        CandidateType::ServerReflexive,
+        CandidateType::PeerReflexive,
+        CandidateType::Relayed,
+    ]
+);
+impl_arbitrary_choice!(
+    TcpType,
+    [TcpType::Active, TcpType::Passive, TcpType::SimultaneousOpen]
+);
+impl_arbitrary_choice!(ExtmapAllowMixed, [ExtmapAllowMixed]);
+impl_arbitrary_choice!(RtcpMux, [RtcpMux]);
+impl_arbitrary_choice!(RtcpMuxOnly, [RtcpMuxOnly]);
+impl_arbitrary_choice!(Version, [Version::V0]);
+
+impl<'a> Arbitrary<'a> for Username {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        if u.ratio(1, 4)? {
+            return valid(Username::try_new("-"));
+        }
+        valid(Username::try_new(&word(u, 16)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Origin {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let username = u.arbitrary()?;
+        let session_id = u.arbitrary()?;
+        let session_version = u.arbitrary()?;
+        let connection = if u.ratio(1, 4)? {
+            ConnectionInformation {
+                network_type: NetType::In,
+                address_type: ADDRESS_TYPE_IP4.to_owned(),
+                address: Some(valid(Address::fqdn(ADDRESS_TYPE_IP4, &fqdn(u)?))?),
+            }
+        } else {
+            ConnectionInformation::from(unicast_address(u)?)
+        };
+        Ok(Origin {
+            username,
+            session_id,
+            session_version,
+            network_type: connection.network_type,
+            address_type: connection.address_type,
+            unicast_address: connection.address.map(|a| a.address).unwrap_or_default(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for SessionName {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        if u.ratio(1, 2)? {
+            return valid(SessionName::try_new("-"));
+        }
+        valid(SessionName::try_new(&text(u)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Information {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        valid(Information::try_new(&text(u)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Uri {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let scheme = u.choose(&["http", "https"])?;
+        parsed(&format!("{}://{}/{}", scheme, fqdn(u)?, word(u, 16)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for EmailAddress {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let address = format!("{}@{}", word(u, 16)?, fqdn(u)?);
+        let display_name = option_of(u, text)?;
+        valid(EmailAddress::try_new(&address, display_name.as_deref()))
+    }
+}
+
+impl<'a> Arbitrary<'a> for PhoneNumber {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut number = format!("+{}", string_of(u, DIGITS, 1..=3)?);
+        for _ in 0..u.int_in_range(0..=3)? {
+            number.push(*u.choose(&[' ', '-'])?);
+            number.push_str(&string_of(u, DIGITS, 1..=4)?);
+        }
+        let display_name = option_of(u, text)?;
+        valid(PhoneNumber::new(&number, display_name.as_deref()))
+    }
+}
+
+impl<'a> Arbitrary<'a> for ConnectionInformation {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut connection = ConnectionInformation::from(unicast_address(u)?);
+        match u.int_in_range(0..=3)? {
+            // IPv4 multicast addresses need a TTL and may give a range.
+            0 => {
+                let ip = IpAddr::from([224, u.arbitrary()?, u.arbitrary()?, u.arbitrary()?]);
+                connection = ConnectionInformation::from(ip);
+                if let Some(address) = &mut connection.address {
+                    address.ttl = Some(u.arbitrary()?);
+                    address.range = option_of(u, |u| u.int_in_range(1..=255))?;
+                }
+            }
+            1 => {
+                connection.address =
+                    Some(valid(Address::fqdn(&connection.address_type, &fqdn(u)?))?);
+            }
+            _ => {}
+        }
+        Ok(connection)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Bandwidth {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let bandwidth_type = if u.ratio(3, 4)? {
+            (*u.choose(&[
+                BANDWIDTH_TYPE_CT,
+                BANDWIDTH_TYPE_AS,
+                BANDWIDTH_TYPE_TIAS,
+                BANDWIDTH_TYPE_RR,
+                BANDWIDTH_TYPE_RS,
+            ])?)
+            .to_owned()
+        } else {
+            format!("X-{}", string_of(u, ALPHA, 1..=4)?)
+        };
+        // Only TIAS may exceed 32 bits.
+        let bandwidth = if bandwidth_type == BANDWIDTH_TYPE_TIAS {
+            u.arbitrary()?
+        } else {
+            u64::from(u.arbitrary::<u32>()?)
+        };
+        valid(Bandwidth::new(&bandwidth_type, bandwidth))
+    }
+}
+
+impl<'a> Arbitrary<'a> for EncryptionKey {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match u.int_in_range(0..=3)? {
+            0 => valid(EncryptionKey::try_new("prompt")),
+            1 => valid(EncryptionKey::try_new(&format!("clear:{}", word(u, 32)?))),
+            2 => {
+                let key = vec_of(u, 1..=32, |u| u.arbitrary::<u8>())?;
+                Ok(EncryptionKey::base64_from_bytes(&key))
+            }
+            _ => valid(EncryptionKey::try_new(&format!(
+                "uri:https://{}/{}",
+                fqdn(u)?,
+                word(u, 16)?
+            ))),
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for Attribute {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let key = extension(u)?;
+        let value = option_of(u, text)?;
+        valid(Attribute::try_new(&key, value.as_deref()))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Timing {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let start_time = u64::from(u.arbitrary::<u32>()?);
+        let stop_time = if u.ratio(1, 2)? {
+            0
+        } else {
+            start_time + u64::from(u.arbitrary::<u32>()?)
+        };
+        valid(Timing::new(start_time, stop_time))
+    }
+}
+
+impl<'a> Arbitrary<'a> for RepeatTime {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let interval = u.int_in_range(1..=31_536_000)?;
+        let duration = u.int_in_range(0..=interval)?;
+        let offsets = vec_of(u, 1..=3, |u| u.int_in_range(0..=interval))?;
+        valid(RepeatTime::new(interval, duration, offsets))
+    }
+}
+
+impl<'a> Arbitrary<'a> for TimeZone {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(TimeZone::new(
+            u64::from(u.arbitrary::<u32>()?),
+            u.int_in_range(-86_400..=86_400)?,
+        ))
+    }
+}
+
+impl<'a> Arbitrary<'a> for TimeZones {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(TimeZones(vec_of(u, 1..=3, TimeZone::arbitrary)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for TimeDescription {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(TimeDescription {
+            timing: u.arbitrary()?,
+            repeat_times: vec_of(u, 0..=2, RepeatTime::arbitrary)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for MediaName {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let media = (*u.choose(&["audio", "video", "text", "application", "message"])?).to_owned();
+        let port = Port {
+            value: u.arbitrary()?,
+            count: option_of(u, |u| u.int_in_range(1..=4).map(NonZeroU16::new))?.flatten(),
+        };
+        let (protos, formats) = if media == "application" && u.arbitrary()? {
+            (
+                vec!["UDP".to_owned(), "DTLS".to_owned(), "SCTP".to_owned()],
+                vec![DATACHANNEL_FORMAT.to_owned()],
+            )
+        } else {
+            let proto = u.choose(&["RTP/AVP", "RTP/SAVPF", "UDP/TLS/RTP/SAVPF"])?;
+            let formats = vec_of(u, 1..=4, |u| payload_type(u).map(|pt| pt.to_string()))?;
+            (proto.split('/').map(str::to_owned).collect(), formats)
+        };
+        Ok(MediaName {
+            media,
+            port,
+            protos,
+            formats,
+        })
+    }
+}
+
+/// typed_attribute returns one of the typed attributes, or an untyped one.
+fn typed_attribute(u: &mut Unstructured<'_>) -> Result<Attribute> {
+    Ok(match u.int_in_range(0..=14)? {
+        0 => Direction::arbitrary(u)?.to_attribute(),
+        1 => Mid::arbitrary(u)?.to_attribute(),
+        2 => RtpMap::arbitrary(u)?.to_attribute(),
+        3 => Fmtp::arbitrary(u)?.to_attribute(),
+        4 => RtcpFeedback::arbitrary(u)?.to_attribute(),
+        5 => ExtMap::arbitrary(u)?.to_attribute(),
+        6 => Candidate::arbitrary(u)?.to_attribute(),
+        7 => Ssrc::arbitrary(u)?.to_attribute(),
+        8 => Msid::arbitrary(u)?.to_attribute(),
+        9 => Fingerprint::arbitrary(u)?.to_attribute(),
+        10 => Setup::arbitrary(u)?.to_attribute(),
+        11 => IceUfrag::arbitrary(u)?.to_attribute(),
+        12 => Rid::arbitrary(u)?.to_attribute(),
+        13 => RtcpMux.to_attribute(),
+        _ => Attribute::arbitrary(u)?,
+    })
+}
+
+impl<'a> Arbitrary<'a> for MediaDescription {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(MediaDescription {
+            media_name: u.arbitrary()?,
+            media_title: u.arbitrary()?,
+            connection_information: u.arbitrary()?,
+            bandwidth: vec_of(u, 0..=2, Bandwidth::arbitrary)?,
+            encryption_key: u.arbitrary()?,
+            attributes: Attributes(vec_of(u, 0..=6, typed_attribute)?),
+            unknown_lines: vec![],
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for SessionDescription {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let attributes = vec_of(u, 0..=3, |u| {
+            Ok(match u.int_in_range(0..=4)? {
+                0 => Group::arbitrary(u)?.to_attribute(),
+                1 => MsidSemantic::arbitrary(u)?.to_attribute(),
+                2 => IceOptions::arbitrary(u)?.to_attribute(),
+                3 => Tool::arbitrary(u)?.to_attribute(),
+                _ => Attribute::arbitrary(u)?,
+            })
+        })?;
+        Ok(SessionDescription {
+            version: u.arbitrary()?,
+            origin: u.arbitrary()?,
+            session_name: u.arbitrary()?,
+            session_information: u.arbitrary()?,
+            uri: u.arbitrary()?,
+            email_address: u.arbitrary()?,
+            phone_number: u.arbitrary()?,
+            connection_information: u.arbitrary()?,
+            bandwidth: vec_of(u, 0..=2, Bandwidth::arbitrary)?,
+            time_descriptions: vec_of(u, 1..=2, TimeDescription::arbitrary)?,
+            time_zones: if u.arbitrary()? {
+                u.arbitrary()?
+            } else {
+                TimeZones::default()
+            },
+            encryption_key: u.arbitrary()?,
+            attributes: Attributes(attributes),
+            media_descriptions: vec_of(u, 0..=3, MediaDescription::arbitrary)?,
+            unknown_lines: vec![],
+            line_ending: LineEnding::CrLf,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Tool {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        valid(Tool::new(&text(u)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for ConferenceType {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        if u.ratio(1, 4)? {
+            return Ok(ConferenceType::Other(extension(u)?));
+        }
+        Ok(u.choose(&ConferenceType::ALL)?.clone())
+    }
+}
+
+impl<'a> Arbitrary<'a> for FrameRate {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let integer = u.arbitrary::<u32>()?;
+        if u.arbitrary()? {
+            return Ok(FrameRate::from_fps(integer));
+        }
+        parsed(&format!("{}.{}", integer, string_of(u, DIGITS, 1..=9)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Quality {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        valid(Quality::new(u.int_in_range(0..=Quality::MAX.get())?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for LanguageTag {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut tag = string_of(u, ALPHA, 2..=3)?.to_lowercase();
+        if u.arbitrary()? {
+            tag.push('-');
+            tag.push_str(&string_of(u, ALPHA, 2..=2)?.to_uppercase());
+        }
+        valid(LanguageTag::new(&tag))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Lang {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Lang(u.arbitrary()?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for SdpLang {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(SdpLang(u.arbitrary()?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Transport {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(if u.arbitrary()? {
+            Transport::Udp
+        } else {
+            Transport::Tcp
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for CandidateAddress {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        if u.ratio(1, 4)? {
+            return Ok(CandidateAddress::Fqdn(format!(
+                "{}.local",
+                word(u, 32)?.to_lowercase()
+            )));
+        }
+        Ok(CandidateAddress::Ip(u.arbitrary()?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Candidate {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let transport: Transport = u.arbitrary()?;
+        let typ: CandidateType = u.arbitrary()?;
+        let (related_address, related_port) = if typ == CandidateType::Host {
+            (None, None)
+        } else {
+            (Some(u.arbitrary()?), Some(u.arbitrary()?))
+        };
+        let tcp_type = if transport == Transport::Tcp {
+            Some(u.arbitrary()?)
+        } else {
+            None
+        };
+        let extensions = vec_of(u, 0..=2, |u| {
+            let name = u.choose(&["generation", "network-id", "network-cost", "ufrag"])?;
+            Ok(((*name).to_owned(), word(u, 8)?))
+        })?;
+        Ok(Candidate {
+            foundation: string_of(u, ICE_CHARS, 1..=32)?,
+            component: u.int_in_range(1..=2)?,
+            transport,
+            priority: u.int_in_range(1..=u32::MAX)?,
+            address: u.arbitrary()?,
+            port: u.arbitrary()?,
+            typ,
+            related_address,
+            related_port,
+            tcp_type,
+            extensions,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for RemoteCandidate {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(RemoteCandidate {
+            component: u.int_in_range(1..=256)?,
+            address: u.arbitrary()?,
+            port: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for RemoteCandidates {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(RemoteCandidates {
+            candidates: vec_of(u, 1..=2, RemoteCandidate::arbitrary)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Charset {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=2)? {
+            0 => Charset::Utf8,
+            1 => Charset::Iso8859_1,
+            _ => Charset::Other(extension(u)?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Label {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        valid(Label::try_new(&word(u, 16)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for ContentValue {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=5)? {
+            0 => ContentValue::Slides,
+            1 => ContentValue::Speaker,
+            2 => ContentValue::Sl,
+            3 => ContentValue::Main,
+            4 => ContentValue::Alt,
+            _ => ContentValue::Other(extension(u)?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Content {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Content(vec_of(u, 1..=3, ContentValue::arbitrary)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for CryptoSuite {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=4)? {
+            0 => CryptoSuite::AesCm128HmacSha1_80,
+            1 => CryptoSuite::AesCm128HmacSha1_32,
+            2 => CryptoSuite::Aes256CmHmacSha1_80,
+            3 => CryptoSuite::AeadAes128Gcm,
+            _ => CryptoSuite::Other(extension(u)?.to_uppercase().replace('-', "_")),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Lifetime {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(if u.arbitrary()? {
+            Lifetime::Power(u.int_in_range(0..=63)?)
+        } else {
+            Lifetime::Decimal(u.arbitrary()?)
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Mki {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let length = u.int_in_range(1..=128)?;
+        let value = if length < 8 {
+            u.int_in_range(0..=(1u64 << (u32::from(length) * 8)) - 1)?
+        } else {
+            u.arbitrary()?
+        };
+        Ok(Mki { value, length })
+    }
+}
+
+impl<'a> Arbitrary<'a> for KeyParam {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut key_param = KeyParam::inline(vec_of(u, 1..=46, |u| u.arbitrary::<u8>())?);
+        key_param.lifetime = u.arbitrary()?;
+        key_param.mki = u.arbitrary()?;
+        Ok(key_param)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Crypto {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Crypto {
+            tag: u.int_in_range(1..=999_999_999)?,
+            suite: u.arbitrary()?,
+            key_params: vec_of(u, 1..=2, KeyParam::arbitrary)?,
+            session_params: vec_of(u, 0..=2, |u| {
+                Ok((*u.choose(&["KDR=1", "UNENCRYPTED_SRTP", "UNAUTHENTICATED_SRTP"])?).to_owned())
+            })?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for ExtMapId {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let id = match u.int_in_range(0..=2)? {
+            0 => u.int_in_range(1..=14)?,
+            1 => u.int_in_range(16..=255)?,
+            _ => u.int_in_range(4096..=4351)?,
+        };
+        valid(ExtMapId::try_new(id))
+    }
+}
+
+impl<'a> Arbitrary<'a> for ExtMap {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let uri = if u.ratio(3, 4)? {
+            u.choose(&KnownExtension::ALL)?.to_url()
+        } else {
+            parsed(&format!("urn:example:{}", word(u, 16)?))?
+        };
+        Ok(ExtMap {
+            id: u.arbitrary()?,
+            direction: u.arbitrary()?,
+            uri,
+            attributes: option_of(u, |u| word(u, 8))?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for HashFunction {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=7)? {
+            0 => HashFunction::Sha1,
+            1 => HashFunction::Sha224,
+            2 => HashFunction::Sha256,
+            3 => HashFunction::Sha384,
+            4 => HashFunction::Sha512,
+            5 => HashFunction::Md5,
+            6 => HashFunction::Md2,
+            _ => HashFunction::Other(extension(u)?.to_lowercase()),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Fingerprint {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let hash_function: HashFunction = u.arbitrary()?;
+        let len = match hash_function {
+            HashFunction::Sha1 => 20,
+            HashFunction::Sha224 => 28,
+            HashFunction::Sha256 => 32,
+            HashFunction::Sha384 => 48,
+            HashFunction::Sha512 => 64,
+            HashFunction::Md5 | HashFunction::Md2 => 16,
+            HashFunction::Other(_) => u.int_in_range(1..=64)?,
+        };
+        let value = vec_of(u, len..=len, |u| u.arbitrary::<u8>())?;
+        valid(Fingerprint::try_new(hash_function, value))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Fmtp {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let params = vec_of(u, 1..=3, |u| {
+            Ok(format!("{}={}", word(u, 8)?, string_of(u, DIGITS, 1..=6)?))
+        })?;
+        valid(Fmtp::try_new(
+            &payload_type(u)?.to_string(),
+            &params.join(";"),
+        ))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Mid {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        valid(Mid::try_new(&word(u, 16)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for GroupSemantics {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=4)? {
+            0 => GroupSemantics::Bundle,
+            1 => GroupSemantics::Ls,
+            2 => GroupSemantics::Fid,
+            3 => GroupSemantics::Fec,
+            _ => GroupSemantics::Other(extension(u)?.to_uppercase()),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Group {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Group {
+            semantics: u.arbitrary()?,
+            mids: vec_of(u, 1..=3, Mid::arbitrary)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for IceUfrag {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        valid(IceUfrag::try_new(&string_of(u, ICE_CHARS, 4..=32)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for IcePwd {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        valid(IcePwd::try_new(&string_of(u, ICE_CHARS, 22..=32)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for IceOptions {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let options = vec_of(u, 1..=3, |u| {
+            Ok(if u.arbitrary()? {
+                (*u.choose(&["trickle", "renomination", "ice2"])?).to_owned()
+            } else {
+                word(u, 8)?
+            })
+        })?;
+        parsed(&options.join(" "))
+    }
+}
+
+impl<'a> Arbitrary<'a> for KeyMgmt {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let protocol_id = if u.arbitrary()? {
+            "mikey".to_owned()
+        } else {
+            word(u, 8)?
+        };
+        let data = vec_of(u, 1..=32, |u| u.arbitrary::<u8>())?;
+        valid(KeyMgmt::new(&protocol_id, data))
+    }
+}
+
+impl<'a> Arbitrary<'a> for MaxPRate {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        valid(MaxPRate::new(
+            u64::from(u.arbitrary::<u32>()?),
+            u.int_in_range(0..=3)?,
+        ))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Msid {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Msid {
+            stream_id: option_of(u, |u| word(u, 36))?,
+            track_id: option_of(u, |u| word(u, 36))?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for MsidSemantic {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let stream_ids = if u.ratio(1, 4)? {
+            vec!["*".to_owned()]
+        } else {
+            vec_of(u, 0..=3, |u| word(u, 36))?
+        };
+        Ok(MsidSemantic {
+            semantic: MSID_SEMANTIC_WMS.to_owned(),
+            stream_ids,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for PTime {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(PTime(u.int_in_range(1..=1000)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for MaxPTime {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(MaxPTime(u.int_in_range(1..=1000)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Rtcp {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Rtcp {
+            port: u.arbitrary()?,
+            connection_information: option_of(u, |u| {
+                Ok(ConnectionInformation::from(unicast_address(u)?))
+            })?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for PayloadTypeRef {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        if u.ratio(1, 4)? {
+            return Ok(PayloadTypeRef::All);
+        }
+        Ok(PayloadTypeRef::Pt(payload_type(u)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for RtcpFeedbackType {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=5)? {
+            0 => RtcpFeedbackType::Nack,
+            1 => RtcpFeedbackType::NackPli,
+            2 => RtcpFeedbackType::CcmFir,
+            3 => RtcpFeedbackType::GoogRemb,
+            4 => RtcpFeedbackType::TransportCc,
+            _ => RtcpFeedbackType::Other {
+                typ: extension(u)?,
+                param: option_of(u, |u| word(u, 8))?,
+            },
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for RtcpFeedback {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(RtcpFeedback {
+            payload_type: u.arbitrary()?,
+            feedback: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for RtpMap {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let encoding_name = if u.ratio(3, 4)? {
+            (*u.choose(&["opus", "VP8", "VP9", "H264", "AV1", "PCMU", "red", "rtx"])?).to_owned()
+        } else {
+            extension(u)?
+        };
+        Ok(RtpMap {
+            payload_type: payload_type(u)?,
+            encoding_name,
+            clock_rate: u.int_in_range(1..=192_000)?,
+            channels: option_of(u, |u| u.int_in_range(1..=8))?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for SctpPort {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        valid(SctpPort::new(u.int_in_range(1..=u16::MAX)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for MaxMessageSize {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(MaxMessageSize(u.arbitrary()?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for SctpMap {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(SctpMap {
+            port: u.arbitrary()?,
+            app: DATACHANNEL_FORMAT.to_owned(),
+            max_streams: option_of(u, |u| u.int_in_range(1..=65535))?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Rid {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let restrictions = vec_of(u, 0..=2, |u| {
+            let name = u.choose(&["max-width", "max-height", "max-fps", "max-br"])?;
+            Ok(((*name).to_owned(), Some(string_of(u, DIGITS, 1..=5)?)))
+        })?;
+        Ok(Rid {
+            id: word(u, 16)?,
+            direction: u.arbitrary()?,
+            payload_types: vec_of(u, 0..=2, payload_type)?,
+            restrictions,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for SimulcastRid {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(SimulcastRid {
+            id: word(u, 16)?,
+            paused: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Simulcast {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut streams = || vec_of(u, 0..=2, |u| vec_of(u, 1..=2, SimulcastRid::arbitrary));
+        let mut send = streams()?;
+        let recv = streams()?;
+        if send.is_empty() && recv.is_empty() {
+            send.push(vec![SimulcastRid::arbitrary(u)?]);
+        }
+        Ok(Simulcast { send, recv })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Ssrc {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Ssrc {
+            id: u.arbitrary()?,
+            attribute: (*u.choose(&["cname", "msid", "label", "mslabel"])?).to_owned(),
+            value: option_of(u, text)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for SsrcGroupSemantics {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=4)? {
+            0 => SsrcGroupSemantics::Fid,
+            1 => SsrcGroupSemantics::Fec,
+            2 => SsrcGroupSemantics::FecFr,
+            3 => SsrcGroupSemantics::Sim,
+            _ => SsrcGroupSemantics::Other(extension(u)?.to_uppercase()),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for SsrcGroup {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(SsrcGroup {
+            semantics: u.arbitrary()?,
+            ssrcs: vec_of(u, 1..=3, |u| u.arbitrary())?,
+        })
+    }
+}
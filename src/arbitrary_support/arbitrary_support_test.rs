@@ -0,0 +1,128 @@
+use super::*;
+
+use std::fmt::{Debug, Display};
+use std::io::Cursor;
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+use crate::error::Error;
+
+// These tests check the generators against the parser: every generated value
+// has to come back unchanged from parsing its text form. They use a fixed
+// seed, so failures are reproducible.
+
+const ITERATIONS: usize = 500;
+
+/// generate returns ITERATIONS values of T, failing if the generator ever
+/// gives up on its input.
+fn generate<T: for<'a> Arbitrary<'a>>() -> Vec<T> {
+    let mut rng = StdRng::seed_from_u64(0x5d9);
+    let mut data = vec![0u8; 4096];
+    (0..ITERATIONS)
+        .map(|_| {
+            rng.fill_bytes(&mut data);
+            let mut u = Unstructured::new(&data);
+            match T::arbitrary(&mut u) {
+                Ok(value) => value,
+                Err(err) => panic!("generator failed: {}", err),
+            }
+        })
+        .collect()
+}
+
+fn check_roundtrip<T>()
+where
+    T: for<'a> Arbitrary<'a> + Display + FromStr<Err = Error> + PartialEq + Debug,
+{
+    for value in generate::<T>() {
+        let text = value.to_string();
+        match text.parse::<T>() {
+            Ok(parsed) => assert_eq!(parsed, value, "`{}` changed", text),
+            Err(err) => panic!("`{}` didn't parse: {}", text, err),
+        }
+    }
+}
+
+#[test]
+fn test_session_fields() {
+    check_roundtrip::<Version>();
+    check_roundtrip::<Origin>();
+    check_roundtrip::<SessionName>();
+    check_roundtrip::<Information>();
+    check_roundtrip::<Uri>();
+    check_roundtrip::<EmailAddress>();
+    check_roundtrip::<PhoneNumber>();
+    check_roundtrip::<Timing>();
+    check_roundtrip::<RepeatTime>();
+    check_roundtrip::<TimeZones>();
+}
+
+#[test]
+fn test_common_fields() {
+    check_roundtrip::<ConnectionInformation>();
+    check_roundtrip::<Bandwidth>();
+    check_roundtrip::<EncryptionKey>();
+    check_roundtrip::<Attribute>();
+    check_roundtrip::<MediaName>();
+}
+
+#[test]
+fn test_attributes() {
+    check_roundtrip::<Direction>();
+    check_roundtrip::<Orient>();
+    check_roundtrip::<Setup>();
+    check_roundtrip::<Connection>();
+    check_roundtrip::<Tool>();
+    check_roundtrip::<ConferenceType>();
+    check_roundtrip::<FrameRate>();
+    check_roundtrip::<Quality>();
+    check_roundtrip::<Lang>();
+    check_roundtrip::<SdpLang>();
+    check_roundtrip::<Candidate>();
+    check_roundtrip::<RemoteCandidates>();
+    check_roundtrip::<Charset>();
+    check_roundtrip::<Label>();
+    check_roundtrip::<Content>();
+    check_roundtrip::<Crypto>();
+    check_roundtrip::<ExtMap>();
+    check_roundtrip::<Fingerprint>();
+    check_roundtrip::<Fmtp>();
+    check_roundtrip::<Mid>();
+    check_roundtrip::<Group>();
+    check_roundtrip::<IceUfrag>();
+    check_roundtrip::<IcePwd>();
+    check_roundtrip::<IceOptions>();
+    check_roundtrip::<KeyMgmt>();
+    check_roundtrip::<MaxPRate>();
+    check_roundtrip::<Msid>();
+    check_roundtrip::<MsidSemantic>();
+    check_roundtrip::<PTime>();
+    check_roundtrip::<MaxPTime>();
+    check_roundtrip::<Rtcp>();
+    check_roundtrip::<RtcpFeedback>();
+    check_roundtrip::<RtpMap>();
+    check_roundtrip::<SctpPort>();
+    check_roundtrip::<MaxMessageSize>();
+    check_roundtrip::<SctpMap>();
+    check_roundtrip::<Rid>();
+    check_roundtrip::<Simulcast>();
+    check_roundtrip::<Ssrc>();
+    check_roundtrip::<SsrcGroup>();
+}
+
+#[test]
+fn test_session_description() -> Result<(), Error> {
+    for sd in generate::<SessionDescription>() {
+        let text = sd.marshal();
+        let parsed: SessionDescription = text.parse()?;
+        assert_eq!(parsed, sd, "{}", text);
+
+        let unmarshaled = SessionDescription::unmarshal(&mut Cursor::new(text.as_bytes()))?;
+        assert_eq!(unmarshaled, sd, "{}", text);
+
+        let borrowed = SessionDescription::parse_borrowed(&text)?;
+        assert_eq!(borrowed.to_owned(), sd, "{}", text);
+    }
+    Ok(())
+}
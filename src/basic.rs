@@ -0,0 +1,548 @@
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::{Attributes, TypedAttribute};
+use super::diagnostic::{Diagnostic, DiagnosticKind};
+use super::error::Error;
+use super::util::impl_string_newtype;
+use super::util::token::is_token;
+
+#[cfg(test)]
+mod basic_test;
+
+/// Tool is the "a=tool:" attribute, naming the tool that created the
+/// description. It is free text, usually a name and a version.
+/// `a=tool:<name and version of tool>`
+/// <https://tools.ietf.org/html/rfc4566#section-6>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::basic::Tool;
+/// use webrtc_rs_sdp::common_description::TypedAttribute;
+///
+/// # fn main() -> Result<(), Error> {
+/// let tool = "libsdp 1.2".parse::<Tool>()?;
+/// assert_eq!(tool.as_str(), "libsdp 1.2");
+/// assert_eq!(Tool::new("libsdp 1.2")?.to_attribute().to_string(), "tool:libsdp 1.2");
+/// assert!(Tool::new("").is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Tool(String);
+
+impl Tool {
+    /// new creates the attribute, rejecting empty values and line breaks.
+    pub fn new(value: &str) -> Result<Self, Error> {
+        validate_tool(value)?;
+        Ok(Tool(value.to_owned()))
+    }
+
+    /// as_str returns the name and version of the tool.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl_string_newtype!(Tool(String), validate_tool);
+
+fn validate_tool(value: &str) -> Result<(), Error> {
+    if value.is_empty() || value.contains(['\r', '\n', '\0']) {
+        return Err(Error::SdpInvalidTool(value.to_owned()));
+    }
+    Ok(())
+}
+
+impl TypedAttribute for Tool {
+    const NAME: &'static str = "tool";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("created by {}", self.0))
+    }
+}
+
+/// ConferenceType is the "a=type:" attribute, the type of the conference.
+/// Types without a variant are kept as Other.
+/// `a=type:<conference type>`
+/// <https://tools.ietf.org/html/rfc4566#section-6>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::basic::ConferenceType;
+///
+/// # fn main() -> Result<(), Error> {
+/// assert_eq!("meeting".parse::<ConferenceType>()?, ConferenceType::Meeting);
+/// assert_eq!(ConferenceType::Moderated.to_string(), "moderated");
+/// assert_eq!("x-lecture".parse::<ConferenceType>()?.as_str(), "x-lecture");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ConferenceType {
+    /// Broadcast is a broadcast session, with "a=recvonly" as default.
+    Broadcast,
+    /// Meeting is a meeting, with "a=sendrecv" as default.
+    Meeting,
+    /// Moderated is a moderated conference.
+    Moderated,
+    /// Test is a test session, not to be shown to users.
+    Test,
+    /// H332 is an H.332 loosely coupled conference.
+    H332,
+    /// Other is any other conference type, kept as written.
+    Other(String),
+}
+
+impl ConferenceType {
+    /// ALL lists every conference type with a variant.
+    pub const ALL: [ConferenceType; 5] = [
+        ConferenceType::Broadcast,
+        ConferenceType::Meeting,
+        ConferenceType::Moderated,
+        ConferenceType::Test,
+        ConferenceType::H332,
+    ];
+
+    /// as_str returns the value of the attribute.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ConferenceType::Broadcast => "broadcast",
+            ConferenceType::Meeting => "meeting",
+            ConferenceType::Moderated => "moderated",
+            ConferenceType::Test => "test",
+            ConferenceType::H332 => "H332",
+            ConferenceType::Other(value) => value,
+        }
+    }
+}
+
+impl fmt::Display for ConferenceType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl FromStr for ConferenceType {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Some(conference_type) = ConferenceType::ALL.iter().find(|t| t.as_str() == value) {
+            return Ok(conference_type.clone());
+        }
+        if !is_token(value) {
+            return Err(Error::SdpInvalidConferenceType(value.to_owned()));
+        }
+        Ok(ConferenceType::Other(value.to_owned()))
+    }
+}
+
+impl TypedAttribute for ConferenceType {
+    const NAME: &'static str = "type";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("{} conference", self))
+    }
+}
+
+/// Orient is the "a=orient:" attribute, the orientation of a whiteboard or
+/// presentation tool. Seascape is landscape turned upside down.
+/// `a=orient:<orientation>`
+/// <https://tools.ietf.org/html/rfc4566#section-6>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::basic::Orient;
+///
+/// # fn main() -> Result<(), Error> {
+/// assert_eq!("seascape".parse::<Orient>()?, Orient::Seascape);
+/// assert_eq!(Orient::Portrait.to_string(), "portrait");
+/// assert!("Portrait".parse::<Orient>().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orient {
+    /// Portrait is "portrait".
+    Portrait,
+    /// Landscape is "landscape".
+    Landscape,
+    /// Seascape is "seascape".
+    Seascape,
+}
+
+impl Orient {
+    /// ALL lists every orientation.
+    pub const ALL: [Orient; 3] = [Orient::Portrait, Orient::Landscape, Orient::Seascape];
+
+    /// as_str returns the value of the attribute.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Orient::Portrait => "portrait",
+            Orient::Landscape => "landscape",
+            Orient::Seascape => "seascape",
+        }
+    }
+}
+
+impl fmt::Display for Orient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl FromStr for Orient {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Orient::ALL
+            .iter()
+            .copied()
+            .find(|o| o.as_str() == value)
+            .ok_or_else(|| Error::SdpInvalidOrient(value.to_owned()))
+    }
+}
+
+impl TypedAttribute for Orient {
+    const NAME: &'static str = "orient";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("{} orientation", self))
+    }
+}
+
+/// FrameRate is the "a=framerate:" attribute, the maximum video frame rate in
+/// frames per second. The integer and fractional parts are kept separately so
+/// that the value formats exactly as parsed, e.g. "29.970".
+/// `a=framerate:<frame rate>`
+/// <https://tools.ietf.org/html/rfc4566#section-6>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FrameRate {
+    integer: u32,
+    fraction: u32,
+    fraction_digits: u8,
+}
+
+/// More fractional digits than this do not fit into u32.
+const MAX_FRACTION_DIGITS: usize = 9;
+
+impl FrameRate {
+    /// from_fps creates a whole number frame rate.
+    pub const fn from_fps(fps: u32) -> Self {
+        FrameRate {
+            integer: fps,
+            fraction: 0,
+            fraction_digits: 0,
+        }
+    }
+
+    /// integer returns the whole frames per second.
+    pub fn integer(&self) -> u32 {
+        self.integer
+    }
+
+    /// fraction returns the fractional part and its number of digits, e.g.
+    /// (970, 3) for "29.970".
+    pub fn fraction(&self) -> (u32, u8) {
+        (self.fraction, self.fraction_digits)
+    }
+
+    /// fps returns the frame rate as a float.
+    pub fn fps(&self) -> f64 {
+        f64::from(self.integer)
+            + f64::from(self.fraction) / f64::from(10u32.pow(u32::from(self.fraction_digits)))
+    }
+}
+
+impl fmt::Display for FrameRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.fraction_digits == 0 {
+            return write!(f, "{}", self.integer);
+        }
+        write!(
+            f,
+            "{}.{:0width$}",
+            self.integer,
+            self.fraction,
+            width = usize::from(self.fraction_digits)
+        )
+    }
+}
+
+impl FromStr for FrameRate {
+    type Err = Error;
+
+    /// framerate-value = 1*DIGIT ["." 1*DIGIT]
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::SdpInvalidFrameRate(value.to_owned());
+
+        let (integer, fraction) = match value.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (value, None),
+        };
+        let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        if !is_digits(integer) || !fraction.is_none_or(is_digits) {
+            return Err(invalid());
+        }
+        let fraction = fraction.unwrap_or_default();
+        if fraction.len() > MAX_FRACTION_DIGITS {
+            return Err(invalid());
+        }
+
+        Ok(FrameRate {
+            integer: integer.parse().map_err(|_| invalid())?,
+            fraction: fraction.parse().unwrap_or(0),
+            fraction_digits: fraction.len() as u8,
+        })
+    }
+}
+
+impl TypedAttribute for FrameRate {
+    const NAME: &'static str = "framerate";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("up to {} frames per second", self))
+    }
+}
+
+/// Quality is the "a=quality:" attribute, a hint from 0 (worst) to 10 (best)
+/// for trading frame rate against still image quality.
+/// `a=quality:<quality>`
+/// <https://tools.ietf.org/html/rfc4566#section-6>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::basic::Quality;
+///
+/// # fn main() -> Result<(), Error> {
+/// assert_eq!("7".parse::<Quality>()?.get(), 7);
+/// assert_eq!(Quality::new(10)?, Quality::MAX);
+/// assert_eq!(Quality::DEFAULT.to_string(), "5");
+/// assert!(Quality::new(11).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Quality(u8);
+
+impl Quality {
+    /// MAX is the best quality.
+    pub const MAX: Quality = Quality(10);
+    /// DEFAULT is the quality of video codecs without the attribute.
+    pub const DEFAULT: Quality = Quality(5);
+
+    /// new creates a quality, rejecting values above 10.
+    pub fn new(value: u8) -> Result<Self, Error> {
+        if value > Quality::MAX.0 {
+            return Err(Error::SdpInvalidQuality(value.to_string()));
+        }
+        Ok(Quality(value))
+    }
+
+    /// get returns the quality.
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl fmt::Display for Quality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Quality {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        // Leading zeros and signs would not survive a round trip.
+        let canonical =
+            value.bytes().all(|b| b.is_ascii_digit()) && (value == "0" || !value.starts_with('0'));
+        match value.parse::<u8>() {
+            Ok(quality) if canonical => Quality::new(quality),
+            _ => Err(Error::SdpInvalidQuality(value.to_owned())),
+        }
+    }
+}
+
+impl TypedAttribute for Quality {
+    const NAME: &'static str = "quality";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("quality {} of 10", self.0))
+    }
+}
+
+/// LanguageTag is an RFC 5646 language tag such as "en", "de-CH" or
+/// "zh-Hant-TW". Only its shape is checked: subtags of 1 to 8 letters and
+/// digits separated by "-", starting with a language of 2 to 8 letters, or
+/// with "x" for private use or "i" for the grandfathered tags. Whether the
+/// subtags are registered is not checked, and the case is kept as given.
+/// <https://tools.ietf.org/html/rfc5646#section-2.1>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::basic::LanguageTag;
+///
+/// # fn main() -> Result<(), Error> {
+/// let tag = "de-CH".parse::<LanguageTag>()?;
+/// assert_eq!(tag.primary_language(), "de");
+/// assert_eq!(LanguageTag::new("zh-Hant-TW")?.to_string(), "zh-Hant-TW");
+/// assert!(LanguageTag::new("en_US").is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LanguageTag(String);
+
+impl LanguageTag {
+    /// new creates a language tag, checking its shape.
+    pub fn new(value: &str) -> Result<Self, Error> {
+        validate_language_tag(value)?;
+        Ok(LanguageTag(value.to_owned()))
+    }
+
+    /// as_str returns the language tag.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// primary_language returns the first subtag, e.g. "de" for "de-CH".
+    pub fn primary_language(&self) -> &str {
+        self.0.split('-').next().unwrap_or_default()
+    }
+}
+
+impl_string_newtype!(LanguageTag(String), validate_language_tag);
+
+fn validate_language_tag(value: &str) -> Result<(), Error> {
+    if !is_language_tag(value) {
+        return Err(Error::SdpInvalidLanguageTag(value.to_owned()));
+    }
+    Ok(())
+}
+
+fn is_language_tag(value: &str) -> bool {
+    let mut subtags = value.split('-');
+    let first = subtags.next().unwrap_or_default();
+    let is_subtag =
+        |s: &str| (1..=8).contains(&s.len()) && s.bytes().all(|b| b.is_ascii_alphanumeric());
+    let rest: Vec<&str> = subtags.collect();
+    if !rest.iter().all(|s| is_subtag(s)) {
+        return false;
+    }
+
+    if first.eq_ignore_ascii_case("x") || first.eq_ignore_ascii_case("i") {
+        return !rest.is_empty();
+    }
+    if !(2..=8).contains(&first.len()) || !first.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return false;
+    }
+    // An extension singleton is followed by subtags of 2 to 8 characters;
+    // after the private use singleton "x" anything goes.
+    let mut singleton = false;
+    for subtag in rest {
+        if singleton && subtag.len() < 2 {
+            return false;
+        }
+        if subtag.eq_ignore_ascii_case("x") {
+            return true;
+        }
+        singleton = subtag.len() == 1;
+    }
+    !singleton
+}
+
+/// Lang is the "a=lang:" attribute, the language of the session or media.
+/// `a=lang:<language tag>`
+/// <https://tools.ietf.org/html/rfc4566#section-6>
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Lang(pub LanguageTag);
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for Lang {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(Lang(value.parse()?))
+    }
+}
+
+impl TypedAttribute for Lang {
+    const NAME: &'static str = "lang";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("language {}", self.0))
+    }
+}
+
+/// SdpLang is the "a=sdplang:" attribute, the language of the description
+/// itself, i.e. of the "s=" and "i=" fields.
+/// `a=sdplang:<language tag>`
+/// <https://tools.ietf.org/html/rfc4566#section-6>
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SdpLang(pub LanguageTag);
+
+impl fmt::Display for SdpLang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for SdpLang {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(SdpLang(value.parse()?))
+    }
+}
+
+impl TypedAttribute for SdpLang {
+    const NAME: &'static str = "sdplang";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("description in language {}", self.0))
+    }
+}
+
+/// check_basic_attributes reports the attributes of this module which fail to
+/// parse. They are informational, so a malformed one is only a warning.
+pub(crate) fn check_basic_attributes(attributes: &Attributes) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    check_malformed::<Tool>(attributes, &mut diagnostics);
+    check_malformed::<ConferenceType>(attributes, &mut diagnostics);
+    check_malformed::<Orient>(attributes, &mut diagnostics);
+    check_malformed::<FrameRate>(attributes, &mut diagnostics);
+    check_malformed::<Quality>(attributes, &mut diagnostics);
+    check_malformed::<Lang>(attributes, &mut diagnostics);
+    check_malformed::<SdpLang>(attributes, &mut diagnostics);
+    diagnostics
+}
+
+fn check_malformed<T: TypedAttribute<Err = Error>>(
+    attributes: &Attributes,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for err in attributes.get::<T>().filter_map(Result::err) {
+        diagnostics.push(Diagnostic::warning(
+            DiagnosticKind::MalformedAttribute,
+            &format!("a={}", T::NAME),
+            err.to_string(),
+        ));
+    }
+}
@@ -0,0 +1,276 @@
+use super::*;
+use crate::diagnostic::Severity;
+use crate::session_description::SessionDescription;
+
+#[test]
+fn test_tool() -> Result<(), Error> {
+    let tool = "libsdp 1.2 (linux)".parse::<Tool>()?;
+    assert_eq!(tool.as_str(), "libsdp 1.2 (linux)");
+    assert_eq!(tool.to_attribute().to_string(), "tool:libsdp 1.2 (linux)");
+
+    for value in ["", "libsdp\r\ns=evil", "libsdp\0"].iter() {
+        assert!(
+            matches!(value.parse::<Tool>(), Err(Error::SdpInvalidTool(_))),
+            "{:?}",
+            value
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_conference_type() -> Result<(), Error> {
+    let tests = [
+        ("broadcast", ConferenceType::Broadcast),
+        ("meeting", ConferenceType::Meeting),
+        ("moderated", ConferenceType::Moderated),
+        ("test", ConferenceType::Test),
+        ("H332", ConferenceType::H332),
+        ("x-lecture", ConferenceType::Other("x-lecture".to_owned())),
+        // Values are case-sensitive.
+        ("Meeting", ConferenceType::Other("Meeting".to_owned())),
+    ];
+    for (value, expected) in tests.iter() {
+        let conference_type = value.parse::<ConferenceType>()?;
+        assert_eq!(&conference_type, expected, "{}", value);
+        assert_eq!(conference_type.to_string(), *value);
+    }
+    for conference_type in ConferenceType::ALL.iter() {
+        assert_eq!(
+            &conference_type.as_str().parse::<ConferenceType>()?,
+            conference_type
+        );
+    }
+
+    for value in ["", "a b", "a:b"].iter() {
+        assert!(
+            matches!(
+                value.parse::<ConferenceType>(),
+                Err(Error::SdpInvalidConferenceType(_))
+            ),
+            "{:?}",
+            value
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_orient() -> Result<(), Error> {
+    let tests = [
+        ("portrait", Orient::Portrait),
+        ("landscape", Orient::Landscape),
+        ("seascape", Orient::Seascape),
+    ];
+    for (value, expected) in tests.iter() {
+        let orient = value.parse::<Orient>()?;
+        assert_eq!(orient, *expected);
+        assert_eq!(orient.to_string(), *value);
+    }
+    assert_eq!(Orient::ALL.len(), tests.len());
+
+    for value in ["", "Portrait", "upside-down"].iter() {
+        assert!(
+            matches!(value.parse::<Orient>(), Err(Error::SdpInvalidOrient(_))),
+            "{:?}",
+            value
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_framerate() -> Result<(), Error> {
+    let passingtests = [
+        ("0", 0, (0, 0)),
+        ("30", 30, (0, 0)),
+        ("29.97", 29, (97, 2)),
+        ("29.970", 29, (970, 3)),
+        ("0.5", 0, (5, 1)),
+        ("1.05", 1, (5, 2)),
+        ("4294967295", u32::MAX, (0, 0)),
+        ("1.999999999", 1, (999999999, 9)),
+    ];
+    for (value, integer, fraction) in passingtests.iter() {
+        let framerate = value.parse::<FrameRate>()?;
+        assert_eq!(framerate.integer(), *integer, "{}", value);
+        assert_eq!(framerate.fraction(), *fraction, "{}", value);
+        assert_eq!(framerate.to_string(), *value);
+    }
+    assert_eq!("30".parse::<FrameRate>()?, FrameRate::from_fps(30));
+    assert!(("29.97".parse::<FrameRate>()?.fps() - 29.97).abs() < 1e-9);
+    // Equal rates spelled differently are kept apart.
+    assert_ne!("30".parse::<FrameRate>()?, "30.0".parse::<FrameRate>()?);
+
+    let failingtests = [
+        "",
+        ".",
+        "30.",
+        ".5",
+        "-1",
+        "+1",
+        "1e3",
+        " 30",
+        "29,97",
+        "4294967296",
+        "1.0000000000",
+    ];
+    for value in failingtests.iter() {
+        assert!(
+            matches!(
+                value.parse::<FrameRate>(),
+                Err(Error::SdpInvalidFrameRate(_))
+            ),
+            "{:?}",
+            value
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_quality() -> Result<(), Error> {
+    for value in 0..=10 {
+        let quality = value.to_string().parse::<Quality>()?;
+        assert_eq!(quality.get(), value);
+        assert_eq!(quality.to_string(), value.to_string());
+        assert_eq!(Quality::new(value)?, quality);
+    }
+    assert_eq!(Quality::MAX.get(), 10);
+    assert!(matches!(Quality::new(11), Err(Error::SdpInvalidQuality(_))));
+
+    for value in ["", "11", "255", "256", "-1", "+5", "05", "00", "5.0"].iter() {
+        assert!(
+            matches!(value.parse::<Quality>(), Err(Error::SdpInvalidQuality(_))),
+            "{:?}",
+            value
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_language_tag() -> Result<(), Error> {
+    let passingtests = [
+        "en",
+        "de-CH",
+        "zh-Hant-TW",
+        "sr-Latn-RS",
+        "es-419",
+        "en-US-x-twain",
+        "de-DE-u-co-phonebk",
+        "sl-rozaj-biske",
+        "x-whatever",
+        "i-klingon",
+        "haw",
+    ];
+    for value in passingtests.iter() {
+        let tag = value.parse::<LanguageTag>()?;
+        assert_eq!(tag.to_string(), *value);
+    }
+    assert_eq!("de-CH".parse::<LanguageTag>()?.primary_language(), "de");
+
+    let failingtests = [
+        "",
+        "e",
+        "abcdefghi",
+        "en_US",
+        "en-",
+        "-en",
+        "en--US",
+        "1en",
+        "en-abcdefghi",
+        "en-u",
+        "en-u-x",
+        "x",
+        "en US",
+        "fr\r\n",
+    ];
+    for value in failingtests.iter() {
+        assert!(
+            matches!(
+                value.parse::<LanguageTag>(),
+                Err(Error::SdpInvalidLanguageTag(_))
+            ),
+            "{:?}",
+            value
+        );
+    }
+
+    assert_eq!("en".parse::<Lang>()?.to_attribute().to_string(), "lang:en");
+    assert_eq!(
+        "de-CH".parse::<SdpLang>()?.to_attribute().to_string(),
+        "sdplang:de-CH"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_basic_attributes_in_description() -> Result<(), Error> {
+    let sdp = "v=0\r\n\
+o=- 0 0 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=tool:libsdp 1.2\r\n\
+a=type:moderated\r\n\
+a=sdplang:en\r\n\
+a=lang:xx_YY\r\n\
+m=video 9 RTP/AVP 31\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=orient:portrait\r\n\
+a=framerate:29.97\r\n\
+a=quality:11\r\n"
+        .parse::<SessionDescription>()?;
+
+    assert_eq!(
+        sdp.attributes.get::<Tool>().next().transpose()?,
+        Some(Tool::new("libsdp 1.2")?)
+    );
+    assert_eq!(
+        sdp.attributes.get::<ConferenceType>().next().transpose()?,
+        Some(ConferenceType::Moderated)
+    );
+    let media = &sdp.media_descriptions[0];
+    assert_eq!(
+        media.attributes.get::<FrameRate>().next().transpose()?,
+        Some("29.97".parse()?)
+    );
+
+    let malformed: Vec<(Option<usize>, String)> = sdp
+        .validate()
+        .into_iter()
+        .filter(|d| d.kind == DiagnosticKind::MalformedAttribute)
+        .inspect(|d| assert_eq!(d.severity, Severity::Warning))
+        .map(|d| (d.media_index, d.field))
+        .collect();
+    assert_eq!(
+        malformed,
+        vec![
+            (None, "a=lang".to_owned()),
+            (Some(0), "a=quality".to_owned())
+        ]
+    );
+
+    let annotated = sdp.annotate();
+    for expected in [
+        "created by libsdp 1.2",
+        "moderated conference",
+        "description in language en",
+        "malformed lang attribute",
+        "portrait orientation",
+        "up to 29.97 frames per second",
+        "malformed quality attribute",
+    ]
+    .iter()
+    {
+        assert!(annotated.contains(expected), "{}\n{}", expected, annotated);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,223 @@
+use super::alloc_prelude::*;
+use super::common_description::{
+    Attribute, Attributes, Bandwidth, ConnectionInformation, EncryptionKey, Information,
+    TypedAttribute,
+};
+use super::error::Error;
+use super::media_description::{MediaDescription, MediaName};
+use super::session_description::{
+    Origin, SessionDescription, SessionName, TimeDescription, Timing, Version,
+};
+
+#[cfg(test)]
+mod builder_test;
+
+/// SessionDescriptionBuilder builds a SessionDescription field by field. The
+/// mandatory "o=" and "t=" fields are checked by build, and the "s=" field
+/// defaults to a single space as recommended for sessions without a
+/// meaningful name.
+/// <https://tools.ietf.org/html/rfc4566#section-5.3>
+#[derive(Debug, Default, Clone)]
+pub struct SessionDescriptionBuilder {
+    origin: Option<Origin>,
+    session_name: Option<SessionName>,
+    session_information: Option<Information>,
+    connection_information: Option<ConnectionInformation>,
+    bandwidth: Vec<Bandwidth>,
+    time_descriptions: Vec<TimeDescription>,
+    encryption_key: Option<EncryptionKey>,
+    attributes: Attributes,
+    media_descriptions: Vec<MediaDescription>,
+}
+
+impl SessionDescription {
+    /// builder returns an empty SessionDescriptionBuilder.
+    pub fn builder() -> SessionDescriptionBuilder {
+        SessionDescriptionBuilder::default()
+    }
+}
+
+impl SessionDescriptionBuilder {
+    /// new creates a builder without any fields set.
+    pub fn new() -> Self {
+        SessionDescriptionBuilder::default()
+    }
+
+    /// origin sets the "o=" field.
+    pub fn origin(mut self, origin: Origin) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    /// name sets the "s=" field.
+    pub fn name(mut self, session_name: SessionName) -> Self {
+        self.session_name = Some(session_name);
+        self
+    }
+
+    /// info sets the "i=" field.
+    pub fn info(mut self, session_information: Information) -> Self {
+        self.session_information = Some(session_information);
+        self
+    }
+
+    /// connection sets the "c=" field.
+    pub fn connection(mut self, connection_information: ConnectionInformation) -> Self {
+        self.connection_information = Some(connection_information);
+        self
+    }
+
+    /// bandwidth appends a "b=" field.
+    pub fn bandwidth(mut self, bandwidth: Bandwidth) -> Self {
+        self.bandwidth.push(bandwidth);
+        self
+    }
+
+    /// timing appends a "t=" field without repeat times.
+    pub fn timing(self, timing: Timing) -> Self {
+        self.time_description(TimeDescription {
+            timing,
+            repeat_times: vec![],
+        })
+    }
+
+    /// time_description appends a "t=" field with its "r=" fields.
+    pub fn time_description(mut self, time_description: TimeDescription) -> Self {
+        self.time_descriptions.push(time_description);
+        self
+    }
+
+    /// key sets the "k=" field.
+    pub fn key(mut self, encryption_key: EncryptionKey) -> Self {
+        self.encryption_key = Some(encryption_key);
+        self
+    }
+
+    /// attribute appends a session-level attribute.
+    pub fn attribute(mut self, attribute: Attribute) -> Self {
+        self.attributes.push(attribute);
+        self
+    }
+
+    /// typed_attribute appends a session-level attribute of type T.
+    pub fn typed_attribute<T: TypedAttribute>(mut self, value: T) -> Self {
+        self.attributes.insert(value);
+        self
+    }
+
+    /// media appends a media description built by f.
+    pub fn media<F>(mut self, media_name: MediaName, f: F) -> Self
+    where
+        F: FnOnce(MediaDescriptionBuilder) -> MediaDescriptionBuilder,
+    {
+        self.media_descriptions
+            .push(f(MediaDescriptionBuilder::new(media_name)).build());
+        self
+    }
+
+    /// build returns an error naming the first mandatory field which is
+    /// missing. Media descriptions without a "c=" field inherit the session
+    /// one, so either the session or every media description must have one.
+    /// <https://tools.ietf.org/html/rfc4566#section-5.7>
+    pub fn build(self) -> Result<SessionDescription, Error> {
+        let origin = self
+            .origin
+            .ok_or_else(|| Error::SdpMissingField("o=".to_owned()))?;
+        if self.time_descriptions.is_empty() {
+            return Err(Error::SdpMissingField("t=".to_owned()));
+        }
+        if self.connection_information.is_none()
+            && self
+                .media_descriptions
+                .iter()
+                .any(|md| md.connection_information.is_none())
+        {
+            return Err(Error::SdpMissingField("c=".to_owned()));
+        }
+
+        let session_name = match self.session_name {
+            Some(session_name) => session_name,
+            None => SessionName::try_new(" ")?,
+        };
+
+        Ok(SessionDescription {
+            version: Version::V0,
+            origin,
+            session_name,
+            session_information: self.session_information,
+            connection_information: self.connection_information,
+            bandwidth: self.bandwidth,
+            time_descriptions: self.time_descriptions,
+            encryption_key: self.encryption_key,
+            attributes: self.attributes,
+            media_descriptions: self.media_descriptions,
+            ..Default::default()
+        })
+    }
+}
+
+/// MediaDescriptionBuilder builds a MediaDescription for
+/// SessionDescriptionBuilder::media.
+#[derive(Debug, Clone)]
+pub struct MediaDescriptionBuilder {
+    media_description: MediaDescription,
+}
+
+impl MediaDescriptionBuilder {
+    /// new creates a builder for a media description with the given "m="
+    /// field.
+    pub fn new(media_name: MediaName) -> Self {
+        MediaDescriptionBuilder {
+            media_description: MediaDescription {
+                media_name,
+                media_title: None,
+                connection_information: None,
+                bandwidth: vec![],
+                encryption_key: None,
+                attributes: Attributes::default(),
+                unknown_lines: vec![],
+            },
+        }
+    }
+
+    /// title sets the "i=" field.
+    pub fn title(mut self, media_title: Information) -> Self {
+        self.media_description.media_title = Some(media_title);
+        self
+    }
+
+    /// connection overrides the session-level "c=" field.
+    pub fn connection(mut self, connection_information: ConnectionInformation) -> Self {
+        self.media_description.connection_information = Some(connection_information);
+        self
+    }
+
+    /// bandwidth appends a "b=" field.
+    pub fn bandwidth(mut self, bandwidth: Bandwidth) -> Self {
+        self.media_description.bandwidth.push(bandwidth);
+        self
+    }
+
+    /// key sets the "k=" field.
+    pub fn key(mut self, encryption_key: EncryptionKey) -> Self {
+        self.media_description.encryption_key = Some(encryption_key);
+        self
+    }
+
+    /// attribute appends a media-level attribute.
+    pub fn attribute(mut self, attribute: Attribute) -> Self {
+        self.media_description.attributes.push(attribute);
+        self
+    }
+
+    /// typed_attribute appends a media-level attribute of type T.
+    pub fn typed_attribute<T: TypedAttribute>(mut self, value: T) -> Self {
+        self.media_description.attributes.insert(value);
+        self
+    }
+
+    /// build returns the media description.
+    pub fn build(self) -> MediaDescription {
+        self.media_description
+    }
+}
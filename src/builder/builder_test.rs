@@ -0,0 +1,140 @@
+use super::*;
+
+use crate::media_description::MediaType;
+use crate::sctp::SctpPort;
+
+fn origin() -> Result<Origin, Error> {
+    "jdoe 2890844526 2890842807 IN IP4 10.47.16.5".parse()
+}
+
+fn connection(value: &str) -> Result<ConnectionInformation, Error> {
+    value.parse()
+}
+
+#[test]
+fn test_builder() -> Result<(), Error> {
+    let sd = SessionDescription::builder()
+        .origin(origin()?)
+        .name("SDP Seminar".parse()?)
+        .info("A Seminar".parse()?)
+        .connection(connection("IN IP4 224.2.17.12/127")?)
+        .bandwidth(Bandwidth::new("CT", 1000)?)
+        .bandwidth(Bandwidth::new("AS", 128)?)
+        .timing(Timing::new(2873397496, 2873404696)?)
+        .timing(Timing::new(2873404696, 2873411896)?)
+        .key("prompt".parse()?)
+        .attribute(Attribute::try_new("recvonly", None)?)
+        .media("audio 49170 RTP/AVP 0".parse()?, |m| m)
+        .media("video 51372 RTP/AVP 99".parse()?, |m| {
+            m.title("Video".parse().expect("title"))
+                .connection(connection("IN IP4 224.2.17.14/127").expect("connection"))
+                .bandwidth(Bandwidth::new("AS", 256).expect("bandwidth"))
+                .attribute(
+                    Attribute::try_new("rtpmap", Some("99 h263-1998/90000")).expect("rtpmap"),
+                )
+        })
+        .build()?;
+
+    let expected = "v=0\r\n\
+o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
+s=SDP Seminar\r\n\
+i=A Seminar\r\n\
+c=IN IP4 224.2.17.12/127\r\n\
+b=CT:1000\r\n\
+b=AS:128\r\n\
+t=2873397496 2873404696\r\n\
+t=2873404696 2873411896\r\n\
+k=prompt\r\n\
+a=recvonly\r\n\
+m=audio 49170 RTP/AVP 0\r\n\
+m=video 51372 RTP/AVP 99\r\n\
+i=Video\r\n\
+c=IN IP4 224.2.17.14/127\r\n\
+b=AS:256\r\n\
+a=rtpmap:99 h263-1998/90000\r\n";
+    assert_eq!(sd.to_string(), expected);
+    assert_eq!(expected.parse::<SessionDescription>()?, sd);
+
+    Ok(())
+}
+
+#[test]
+fn test_builder_defaults() -> Result<(), Error> {
+    let sd = SessionDescription::builder()
+        .origin(origin()?)
+        .timing(Timing::new(0, 0)?)
+        .build()?;
+
+    assert_eq!(sd.version, Version::V0);
+    assert_eq!(sd.session_name.as_str(), Some(" "));
+    assert_eq!(
+        sd.to_string(),
+        "v=0\r\n\
+o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
+s= \r\n\
+t=0 0\r\n"
+    );
+    sd.to_string().parse::<SessionDescription>()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_builder_typed_attributes() -> Result<(), Error> {
+    let sd = SessionDescription::builder()
+        .origin(origin()?)
+        .timing(Timing::new(0, 0)?)
+        .connection(connection("IN IP4 0.0.0.0")?)
+        .media(
+            "application 9 UDP/DTLS/SCTP webrtc-datachannel".parse()?,
+            |m| m.typed_attribute(SctpPort(5000)),
+        )
+        .build()?;
+
+    let md = &sd.media_descriptions[0];
+    assert_eq!(md.media_name.media_type()?, MediaType::Application);
+    assert_eq!(md.connection_information, None);
+    let sctp_port = md.attributes.get::<SctpPort>().next().expect("sctp-port")?;
+    assert_eq!(sctp_port, SctpPort(5000));
+
+    Ok(())
+}
+
+#[test]
+fn test_builder_missing_field() -> Result<(), Error> {
+    let audio: MediaName = "audio 49170 RTP/AVP 0".parse()?;
+    let tests = vec![
+        (
+            "origin",
+            SessionDescription::builder().timing(Timing::new(0, 0)?),
+            "o=",
+        ),
+        (
+            "timing",
+            SessionDescription::builder().origin(origin()?),
+            "t=",
+        ),
+        (
+            "media connection",
+            SessionDescription::builder()
+                .origin(origin()?)
+                .timing(Timing::new(0, 0)?)
+                .media(audio.clone(), |m| {
+                    m.connection(connection("IN IP4 10.47.16.5").expect("connection"))
+                })
+                .media(audio.clone(), |m| m),
+            "c=",
+        ),
+    ];
+
+    for (name, builder, field) in tests {
+        match builder.build() {
+            Err(Error::SdpMissingField(value)) => {
+                assert_eq!(value, field, "{}: unexpected field", name)
+            }
+            result => panic!("{}: expected missing `{}`, got {:?}", name, field, result),
+        }
+    }
+
+    Ok(())
+}
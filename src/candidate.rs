@@ -0,0 +1,503 @@
+use core::fmt;
+use core::net::IpAddr;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::TypedAttribute;
+use super::error::Error;
+
+#[cfg(test)]
+mod candidate_test;
+
+/// Transport is the transport protocol of a candidate. It is compared
+/// case-insensitively and written in lower case.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Transport {
+    /// Udp is "udp".
+    Udp,
+    /// Tcp is "tcp".
+    Tcp,
+    /// Extension is any other transport, kept as written.
+    Extension(String),
+}
+
+impl fmt::Display for Transport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Transport::Udp => write!(f, "udp"),
+            Transport::Tcp => write!(f, "tcp"),
+            Transport::Extension(transport) => write!(f, "{}", transport),
+        }
+    }
+}
+
+impl FromStr for Transport {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.eq_ignore_ascii_case("udp") {
+            Ok(Transport::Udp)
+        } else if value.eq_ignore_ascii_case("tcp") {
+            Ok(Transport::Tcp)
+        } else if !value.is_empty() && value.chars().all(is_ice_char) {
+            Ok(Transport::Extension(value.to_owned()))
+        } else {
+            Err(Error::SdpInvalidCandidate(value.to_owned()))
+        }
+    }
+}
+
+/// CandidateType is the origin of a candidate.
+/// <https://tools.ietf.org/html/rfc8445#section-5.1.1>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CandidateType {
+    /// Host is a candidate of a local interface, "host".
+    Host,
+    /// ServerReflexive is an address learned from a STUN server, "srflx".
+    ServerReflexive,
+    /// PeerReflexive is an address learned from the peer, "prflx".
+    PeerReflexive,
+    /// Relayed is an address of a TURN server, "relay".
+    Relayed,
+}
+
+impl fmt::Display for CandidateType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            CandidateType::Host => "host",
+            CandidateType::ServerReflexive => "srflx",
+            CandidateType::PeerReflexive => "prflx",
+            CandidateType::Relayed => "relay",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for CandidateType {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "host" => Ok(CandidateType::Host),
+            "srflx" => Ok(CandidateType::ServerReflexive),
+            "prflx" => Ok(CandidateType::PeerReflexive),
+            "relay" => Ok(CandidateType::Relayed),
+            _ => Err(Error::SdpInvalidCandidate(value.to_owned())),
+        }
+    }
+}
+
+/// TcpType is the role of a TCP candidate.
+/// <https://tools.ietf.org/html/rfc6544#section-4.5>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TcpType {
+    /// Active opens outgoing connections only, "active".
+    Active,
+    /// Passive accepts incoming connections only, "passive".
+    Passive,
+    /// SimultaneousOpen attempts a TCP simultaneous open, "so".
+    SimultaneousOpen,
+}
+
+impl fmt::Display for TcpType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            TcpType::Active => "active",
+            TcpType::Passive => "passive",
+            TcpType::SimultaneousOpen => "so",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for TcpType {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "active" => Ok(TcpType::Active),
+            "passive" => Ok(TcpType::Passive),
+            "so" => Ok(TcpType::SimultaneousOpen),
+            _ => Err(Error::SdpInvalidCandidate(value.to_owned())),
+        }
+    }
+}
+
+/// CandidateAddress is the connection address of a candidate: an IP address
+/// or a fully qualified domain name, e.g. the mDNS names used to hide local
+/// addresses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CandidateAddress {
+    /// Ip is an IP address.
+    Ip(IpAddr),
+    /// Fqdn is a domain name.
+    Fqdn(String),
+}
+
+impl fmt::Display for CandidateAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CandidateAddress::Ip(ip) => write!(f, "{}", ip),
+            CandidateAddress::Fqdn(fqdn) => write!(f, "{}", fqdn),
+        }
+    }
+}
+
+impl FromStr for CandidateAddress {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Ok(ip) = value.parse::<IpAddr>() {
+            return Ok(CandidateAddress::Ip(ip));
+        }
+        let is_fqdn = !value.is_empty()
+            && value
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'.');
+        if !is_fqdn {
+            return Err(Error::SdpInvalidCandidate(value.to_owned()));
+        }
+        Ok(CandidateAddress::Fqdn(value.to_owned()))
+    }
+}
+
+/// PriorityComponents are the parts of a candidate priority.
+/// priority = (2^24)*(type preference) + (2^8)*(local preference) +
+///            (2^0)*(256 - component ID)
+/// <https://tools.ietf.org/html/rfc8445#section-5.1.2.1>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriorityComponents {
+    /// type_preference is the preference of the candidate type, 126 for host
+    /// candidates in the recommended scheme.
+    pub type_preference: u8,
+    /// local_preference is the preference of the IP address.
+    pub local_preference: u16,
+    /// component is the component id.
+    pub component: u16,
+}
+
+/// Candidate is an ICE candidate of an "a=candidate:" attribute.
+/// candidate-attribute = "candidate" ":" foundation SP component-id SP
+///                       transport SP priority SP connection-address SP
+///                       port SP cand-type [SP rel-addr] [SP rel-port]
+///                       *(SP extension-att-name SP extension-att-value)
+/// <https://tools.ietf.org/html/rfc8839#section-5.1>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::candidate::{Candidate, CandidateType, Transport};
+///
+/// # fn main() -> Result<(), Error> {
+/// let text = "1 1 UDP 2130706431 10.0.1.1 8998 typ host generation 0";
+/// let candidate = text.parse::<Candidate>()?;
+/// assert_eq!(candidate.transport, Transport::Udp);
+/// assert_eq!(candidate.typ, CandidateType::Host);
+/// assert_eq!(candidate.priority_components().type_preference, 126);
+/// assert_eq!(candidate.extension("generation"), Some("0"));
+/// assert_eq!(
+///     candidate.to_string(),
+///     "1 1 udp 2130706431 10.0.1.1 8998 typ host generation 0"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    /// foundation tells candidates apart which share the same type, base and
+    /// server.
+    pub foundation: String,
+    /// component is the component id, 1 for RTP and 2 for RTCP.
+    pub component: u16,
+    /// transport is the transport protocol.
+    pub transport: Transport,
+    /// priority is the priority, see priority_components.
+    pub priority: u32,
+    /// address is the connection address.
+    pub address: CandidateAddress,
+    /// port is the connection port.
+    pub port: u16,
+    /// typ is the candidate type.
+    pub typ: CandidateType,
+    /// related_address is the "raddr", the address the candidate was derived
+    /// from.
+    pub related_address: Option<CandidateAddress>,
+    /// related_port is the "rport", the port the candidate was derived from.
+    pub related_port: Option<u16>,
+    /// tcp_type is the "tcptype" of a TCP candidate.
+    pub tcp_type: Option<TcpType>,
+    /// extensions are the remaining name/value pairs, e.g. "generation 0",
+    /// in their original order.
+    pub extensions: Vec<(String, String)>,
+}
+
+impl Candidate {
+    /// priority_components splits the priority into its parts.
+    pub fn priority_components(&self) -> PriorityComponents {
+        PriorityComponents {
+            type_preference: (self.priority >> 24) as u8,
+            local_preference: (self.priority >> 8) as u16,
+            component: 256 - (self.priority & 0xff) as u16,
+        }
+    }
+
+    /// extension returns the value of the first extension attribute with the
+    /// given name.
+    pub fn extension(&self, name: &str) -> Option<&str> {
+        self.extensions
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl fmt::Display for Candidate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {} typ {}",
+            self.foundation,
+            self.component,
+            self.transport,
+            self.priority,
+            self.address,
+            self.port,
+            self.typ
+        )?;
+        if let Some(related_address) = &self.related_address {
+            write!(f, " raddr {}", related_address)?;
+        }
+        if let Some(related_port) = self.related_port {
+            write!(f, " rport {}", related_port)?;
+        }
+        if let Some(tcp_type) = self.tcp_type {
+            write!(f, " tcptype {}", tcp_type)?;
+        }
+        for (name, value) in &self.extensions {
+            write!(f, " {} {}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Candidate {
+    type Err = Error;
+
+    /// from_str parses the value of an "a=candidate:" attribute, i.e. without
+    /// the "candidate:" prefix.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::SdpInvalidCandidate(value.to_owned());
+        let mut fields = value.split_whitespace();
+        let mut next = || fields.next().ok_or_else(invalid);
+
+        let foundation = next()?;
+        if foundation.len() > 32 || !foundation.chars().all(is_ice_char) {
+            return Err(invalid());
+        }
+        let component = next()?.parse::<u16>().map_err(|_| invalid())?;
+        if !(1..=256).contains(&component) {
+            return Err(Error::SdpInvalidCandidateComponent(component));
+        }
+        let transport = next()?.parse()?;
+        let priority = next()?.parse::<u32>().map_err(|_| invalid())?;
+        if priority == 0 {
+            return Err(Error::SdpInvalidCandidatePriority);
+        }
+        let address = next()?.parse()?;
+        let port = next()?.parse::<u16>().map_err(|_| invalid())?;
+        if next()? != "typ" {
+            return Err(invalid());
+        }
+        let typ = next()?.parse()?;
+
+        let mut candidate = Candidate {
+            foundation: foundation.to_owned(),
+            component,
+            transport,
+            priority,
+            address,
+            port,
+            typ,
+            related_address: None,
+            related_port: None,
+            tcp_type: None,
+            extensions: vec![],
+        };
+
+        while let Some(name) = fields.next() {
+            let value = fields.next().ok_or_else(invalid)?;
+            match name {
+                "raddr" if candidate.related_address.is_none() => {
+                    candidate.related_address = Some(value.parse()?);
+                }
+                "rport" if candidate.related_port.is_none() => {
+                    candidate.related_port = Some(value.parse().map_err(|_| invalid())?);
+                }
+                "tcptype" if candidate.tcp_type.is_none() => {
+                    candidate.tcp_type = Some(value.parse()?);
+                }
+                _ => candidate
+                    .extensions
+                    .push((name.to_owned(), value.to_owned())),
+            }
+        }
+
+        Ok(candidate)
+    }
+}
+
+impl TypedAttribute for Candidate {
+    const NAME: &'static str = "candidate";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!(
+            "{} {} candidate, component {}",
+            self.transport, self.typ, self.component
+        ))
+    }
+}
+
+/// RemoteCandidate is one candidate of an "a=remote-candidates:" attribute,
+/// identified by its component id, address and port.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteCandidate {
+    /// component is the component id, 1 for RTP and 2 for RTCP.
+    pub component: u16,
+    /// address is the connection address.
+    pub address: CandidateAddress,
+    /// port is the connection port.
+    pub port: u16,
+}
+
+impl From<&Candidate> for RemoteCandidate {
+    fn from(candidate: &Candidate) -> Self {
+        RemoteCandidate {
+            component: candidate.component,
+            address: candidate.address.clone(),
+            port: candidate.port,
+        }
+    }
+}
+
+impl fmt::Display for RemoteCandidate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.component, self.address, self.port)
+    }
+}
+
+impl FromStr for RemoteCandidate {
+    type Err = Error;
+
+    /// `<component-id> <connection-address> <port>`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let triplet: Vec<&str> = value.split(' ').collect();
+        parse_remote_candidate(&triplet, || {
+            Error::SdpInvalidRemoteCandidates(value.to_owned())
+        })
+    }
+}
+
+/// RemoteCandidates is the "a=remote-candidates:" attribute, with which the
+/// controlling agent names the remote candidates of the pairs selected by
+/// ICE, one for each component.
+/// `a=remote-candidates:<component-id> <connection-address> <port> ...`
+/// <https://tools.ietf.org/html/rfc8839#section-5.2>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::candidate::RemoteCandidates;
+///
+/// # fn main() -> Result<(), Error> {
+/// let remote = "1 192.0.2.3 45664 2 192.0.2.3 45665".parse::<RemoteCandidates>()?;
+/// assert_eq!(remote.candidates.len(), 2);
+/// assert_eq!(remote.candidates[1].port, 45665);
+/// assert_eq!(remote.to_string(), "1 192.0.2.3 45664 2 192.0.2.3 45665");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteCandidates {
+    /// candidates are the remote candidates, in order.
+    pub candidates: Vec<RemoteCandidate>,
+}
+
+impl fmt::Display for RemoteCandidates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, candidate) in self.candidates.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}", candidate)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RemoteCandidates {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::SdpInvalidRemoteCandidates(value.to_owned());
+        let fields: Vec<&str> = value.split(' ').collect();
+        if value.is_empty() || !fields.len().is_multiple_of(3) {
+            return Err(invalid());
+        }
+
+        let candidates = fields
+            .chunks(3)
+            .map(|triplet| parse_remote_candidate(triplet, invalid))
+            .collect::<Result<_, _>>()?;
+
+        Ok(RemoteCandidates { candidates })
+    }
+}
+
+/// parse_remote_candidate reads a `<component-id> <connection-address>
+/// <port>` triplet, returning invalid() if it is malformed.
+fn parse_remote_candidate(
+    triplet: &[&str],
+    invalid: impl Fn() -> Error,
+) -> Result<RemoteCandidate, Error> {
+    let (component, address, port) = match triplet {
+        [component, address, port] => (component, address, port),
+        _ => return Err(invalid()),
+    };
+    let component = component.parse::<u16>().map_err(|_| invalid())?;
+    if !(1..=256).contains(&component) {
+        return Err(Error::SdpInvalidCandidateComponent(component));
+    }
+    Ok(RemoteCandidate {
+        component,
+        address: address.parse().map_err(|_| invalid())?,
+        port: port.parse::<u16>().map_err(|_| invalid())?,
+    })
+}
+
+impl TypedAttribute for RemoteCandidates {
+    const NAME: &'static str = "remote-candidates";
+
+    fn describe(&self) -> Option<String> {
+        let components: Vec<String> = self
+            .candidates
+            .iter()
+            .map(|c| {
+                format!(
+                    "{} port {} for component {}",
+                    c.address, c.port, c.component
+                )
+            })
+            .collect();
+        Some(format!(
+            "selected remote candidates {}",
+            components.join(", ")
+        ))
+    }
+}
+
+/// ice-char = ALPHA / DIGIT / "+" / "/"
+pub(crate) fn is_ice_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '+' || c == '/'
+}
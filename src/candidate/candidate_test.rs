@@ -0,0 +1,155 @@
+use super::*;
+
+const CHROME_CANDIDATES: [&str; 5] = [
+    "1467250027 1 udp 2122260223 192.168.0.196 46243 typ host generation 0 ufrag q0ly network-id 1",
+    "1853887674 1 udp 1518280447 47.61.61.61 36768 typ srflx raddr 192.168.0.196 rport 36768 generation 0 ufrag q0ly network-id 1",
+    "1467250027 1 tcp 1518280447 192.168.0.196 9 typ host tcptype active generation 0 ufrag q0ly network-id 1",
+    "2157334355 1 udp 33562367 203.0.113.5 61143 typ relay raddr 47.61.61.61 rport 54321 generation 0 ufrag q0ly network-id 1 network-cost 10",
+    "3885250869 1 udp 2122260223 4d5a3b82-4c5b-4b1f-9f3d-7b2d6b8b7a3e.local 56143 typ host generation 0 network-cost 999",
+];
+
+#[test]
+fn test_candidate_round_trip() -> Result<(), Error> {
+    for input in CHROME_CANDIDATES.iter() {
+        let candidate = input.parse::<Candidate>()?;
+        assert_eq!(candidate.to_string(), *input);
+    }
+
+    let srflx = CHROME_CANDIDATES[1].parse::<Candidate>()?;
+    assert_eq!(srflx.foundation, "1853887674");
+    assert_eq!(srflx.component, 1);
+    assert_eq!(srflx.transport, Transport::Udp);
+    assert_eq!(srflx.typ, CandidateType::ServerReflexive);
+    assert_eq!(srflx.address, "47.61.61.61".parse::<CandidateAddress>()?);
+    assert_eq!(
+        srflx.related_address,
+        Some("192.168.0.196".parse::<CandidateAddress>()?)
+    );
+    assert!(matches!(srflx.address, CandidateAddress::Ip(IpAddr::V4(_))));
+    assert_eq!(srflx.related_port, Some(36768));
+    assert_eq!(srflx.extension("ufrag"), Some("q0ly"));
+    assert_eq!(
+        srflx.extensions,
+        vec![
+            ("generation".to_owned(), "0".to_owned()),
+            ("ufrag".to_owned(), "q0ly".to_owned()),
+            ("network-id".to_owned(), "1".to_owned()),
+        ]
+    );
+
+    let tcp = CHROME_CANDIDATES[2].parse::<Candidate>()?;
+    assert_eq!(tcp.tcp_type, Some(TcpType::Active));
+
+    let mdns = CHROME_CANDIDATES[4].parse::<Candidate>()?;
+    assert!(matches!(mdns.address, CandidateAddress::Fqdn(_)));
+
+    // Transports are case-insensitive.
+    let firefox = "0 1 UDP 2122252543 192.168.1.2 49203 typ host".parse::<Candidate>()?;
+    assert_eq!(firefox.transport, Transport::Udp);
+
+    Ok(())
+}
+
+#[test]
+fn test_candidate_priority_components() -> Result<(), Error> {
+    let host = CHROME_CANDIDATES[0].parse::<Candidate>()?;
+    assert_eq!(
+        host.priority_components(),
+        PriorityComponents {
+            type_preference: 126,
+            local_preference: 32542,
+            component: 1,
+        }
+    );
+
+    let relay = CHROME_CANDIDATES[3].parse::<Candidate>()?;
+    assert_eq!(relay.priority_components().type_preference, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_candidate_errors() {
+    assert!(matches!(
+        "1 0 udp 2122260223 192.168.0.196 46243 typ host".parse::<Candidate>(),
+        Err(Error::SdpInvalidCandidateComponent(0))
+    ));
+    assert!(matches!(
+        "1 257 udp 2122260223 192.168.0.196 46243 typ host".parse::<Candidate>(),
+        Err(Error::SdpInvalidCandidateComponent(257))
+    ));
+    assert!(matches!(
+        "1 1 udp 0 192.168.0.196 46243 typ host".parse::<Candidate>(),
+        Err(Error::SdpInvalidCandidatePriority)
+    ));
+
+    let failingtests = [
+        "",
+        "1 1 udp 2122260223 192.168.0.196 46243",
+        "1 1 udp 2122260223 192.168.0.196 46243 type host",
+        "1 1 udp 2122260223 192.168.0.196 46243 typ bogus",
+        "1 1 udp 2122260223 192.168.0.196 70000 typ host",
+        "1 1 udp 2122260223 192.168.0.196 46243 typ host generation",
+        "1 1 udp 2122260223 192.168.0.196 46243 typ host tcptype sideways",
+        "1 1 udp 2122260223 192.168.0.196 46243 typ srflx raddr 1.2.3.4 rport x",
+        "a_b 1 udp 2122260223 192.168.0.196 46243 typ host",
+        "1 1 udp 2122260223 bad_host 46243 typ host",
+    ];
+    for input in failingtests {
+        assert!(
+            input.parse::<Candidate>().is_err(),
+            "expected `{}` to be rejected",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_remote_candidates() -> Result<(), Error> {
+    let passingtests = [
+        "1 192.0.2.3 45664",
+        "1 192.0.2.3 45664 2 192.0.2.3 45665",
+        "1 2001:db8::1 9 2 4d5a3b82-4c5b-4b1f-9f3d-7b2d6b8b7a3e.local 10",
+    ];
+    for input in passingtests.iter() {
+        let remote = input.parse::<RemoteCandidates>()?;
+        assert_eq!(remote.to_string(), *input);
+    }
+
+    let candidate = CHROME_CANDIDATES[1].parse::<Candidate>()?;
+    let remote = RemoteCandidates {
+        candidates: vec![RemoteCandidate::from(&candidate)],
+    };
+    assert_eq!(remote.to_string(), "1 47.61.61.61 36768");
+    assert_eq!(
+        remote.to_attribute().to_string(),
+        "remote-candidates:1 47.61.61.61 36768"
+    );
+
+    assert!(matches!(
+        "0 192.0.2.3 45664".parse::<RemoteCandidates>(),
+        Err(Error::SdpInvalidCandidateComponent(0))
+    ));
+    let failingtests = [
+        "",
+        "1",
+        "1 192.0.2.3",
+        "1 192.0.2.3 45664 2",
+        "1 192.0.2.3 70000",
+        "x 192.0.2.3 45664",
+        "1 bad_host 45664",
+        "1  192.0.2.3 45664",
+    ];
+    for input in failingtests.iter() {
+        assert!(
+            matches!(
+                input.parse::<RemoteCandidates>(),
+                Err(Error::SdpInvalidRemoteCandidates(_))
+            ),
+            "{:?}",
+            input
+        );
+    }
+
+    Ok(())
+}
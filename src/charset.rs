@@ -0,0 +1,69 @@
+use alloc::borrow::Cow;
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::TypedAttribute;
+use super::error::Error;
+use super::util::token::is_token;
+
+#[cfg(test)]
+mod charset_test;
+
+/// Charset is the "a=charset:" attribute, giving the character set of the
+/// "s=" and "i=" fields. Without it they are UTF-8. Charset names are
+/// compared case-insensitively.
+/// <https://tools.ietf.org/html/rfc4566#section-6>
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Charset {
+    /// Utf8 is "UTF-8", the default.
+    #[default]
+    Utf8,
+    /// Iso8859_1 is "ISO-8859-1".
+    Iso8859_1,
+    /// Other is any other charset, whose text is kept undecoded.
+    Other(String),
+}
+
+impl Charset {
+    /// decode decodes text in this charset. UTF-8 text is borrowed, invalid
+    /// sequences are replaced. ISO-8859-1 maps every byte to the code point
+    /// of the same value. Other charsets are decoded as UTF-8.
+    pub fn decode<'a>(&self, value: &'a [u8]) -> Cow<'a, str> {
+        match self {
+            Charset::Iso8859_1 if !value.is_ascii() => {
+                Cow::Owned(value.iter().map(|&b| char::from(b)).collect())
+            }
+            _ => String::from_utf8_lossy(value),
+        }
+    }
+}
+
+impl fmt::Display for Charset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Charset::Utf8 => "UTF-8",
+            Charset::Iso8859_1 => "ISO-8859-1",
+            Charset::Other(name) => name,
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for Charset {
+    type Err = Error;
+
+    /// charset-value = token
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(match value {
+            _ if value.eq_ignore_ascii_case("UTF-8") => Charset::Utf8,
+            _ if value.eq_ignore_ascii_case("ISO-8859-1") => Charset::Iso8859_1,
+            _ if is_token(value) => Charset::Other(value.to_owned()),
+            _ => return Err(Error::SdpInvalidCharset(value.to_owned())),
+        })
+    }
+}
+
+impl TypedAttribute for Charset {
+    const NAME: &'static str = "charset";
+}
@@ -0,0 +1,147 @@
+use super::*;
+
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::common_description::Information;
+use crate::session_description::{SessionDescription, SessionName};
+
+// "Café Crème" in ISO-8859-1
+const LATIN1_NAME: &[u8] = b"Caf\xe9 Cr\xe8me";
+
+#[cfg(feature = "std")]
+fn latin1_sdp() -> Vec<u8> {
+    let mut sdp = b"v=0\r\n\
+o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
+s="
+    .to_vec();
+    sdp.extend_from_slice(LATIN1_NAME);
+    sdp.extend_from_slice(b"\r\ni=");
+    sdp.extend_from_slice(LATIN1_NAME);
+    sdp.extend_from_slice(
+        b"\r\n\
+t=0 0\r\n\
+a=charset:ISO-8859-1\r\n",
+    );
+    sdp
+}
+
+#[test]
+fn test_charset() -> Result<(), Error> {
+    let tests = vec![
+        ("UTF-8", Charset::Utf8, "UTF-8"),
+        ("utf-8", Charset::Utf8, "UTF-8"),
+        ("ISO-8859-1", Charset::Iso8859_1, "ISO-8859-1"),
+        ("iso-8859-1", Charset::Iso8859_1, "ISO-8859-1"),
+        ("KOI8-R", Charset::Other("KOI8-R".to_owned()), "KOI8-R"),
+    ];
+
+    for (input, expected, output) in tests {
+        let charset = input.parse::<Charset>()?;
+        assert_eq!(charset, expected, "{}", input);
+        assert_eq!(charset.to_string(), output);
+    }
+
+    for input in ["", "UTF 8", "ISO-8859-1:1987"] {
+        assert!(
+            matches!(input.parse::<Charset>(), Err(Error::SdpInvalidCharset(_))),
+            "{}",
+            input
+        );
+    }
+    assert_eq!(Charset::default(), Charset::Utf8);
+
+    Ok(())
+}
+
+#[test]
+fn test_charset_decode() {
+    let tests: Vec<(Charset, &[u8], &str, bool)> = vec![
+        (Charset::Utf8, b"Caf\xc3\xa9", "Caf\u{e9}", true),
+        (
+            Charset::Utf8,
+            LATIN1_NAME,
+            "Caf\u{fffd} Cr\u{fffd}me",
+            false,
+        ),
+        (Charset::Iso8859_1, b"Cafe", "Cafe", true),
+        (
+            Charset::Iso8859_1,
+            LATIN1_NAME,
+            "Caf\u{e9} Cr\u{e8}me",
+            false,
+        ),
+        (Charset::Iso8859_1, b"\x80\xff", "\u{80}\u{ff}", false),
+        (Charset::Other("KOI8-R".to_owned()), b"abc", "abc", true),
+    ];
+
+    for (charset, input, expected, borrowed) in tests {
+        let decoded = charset.decode(input);
+        assert_eq!(decoded, expected, "{:?}", input);
+        assert_eq!(matches!(decoded, Cow::Borrowed(_)), borrowed, "{:?}", input);
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_latin1_session_name() -> Result<(), Error> {
+    let sdp = latin1_sdp();
+    assert!(String::from_utf8(sdp.clone()).is_err());
+
+    let parsed = vec![
+        SessionDescription::unmarshal(&mut io::Cursor::new(&sdp))?,
+        SessionDescription::from_reader(sdp.as_slice())?,
+    ];
+
+    for sd in parsed {
+        assert_eq!(sd.charset(), Charset::Iso8859_1);
+        assert_eq!(sd.session_name.as_bytes(), LATIN1_NAME);
+        assert_eq!(sd.session_name.as_str(), None);
+        assert_eq!(
+            sd.session_name.decode_with(&sd.charset()),
+            "Caf\u{e9} Cr\u{e8}me"
+        );
+        let information = sd.session_information.as_ref().expect("i=");
+        assert_eq!(information.as_bytes(), LATIN1_NAME);
+        assert_eq!(
+            information.decode_with(&Charset::Iso8859_1),
+            "Caf\u{e9} Cr\u{e8}me"
+        );
+    }
+
+    // Other fields must still be UTF-8.
+    let mut sdp = latin1_sdp();
+    sdp.extend_from_slice(b"a=tool:\xe9\r\n");
+    assert!(SessionDescription::unmarshal(&mut io::Cursor::new(&sdp)).is_err());
+    assert!(SessionDescription::from_reader(sdp.as_slice()).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_text_fields_from_bytes() -> Result<(), Error> {
+    let name = SessionName::from_bytes(LATIN1_NAME)?;
+    assert_eq!(name.to_string(), "Caf\u{fffd} Cr\u{fffd}me");
+    assert_eq!(
+        format!("{:?}", name),
+        "SessionName(\"Caf\u{fffd} Cr\u{fffd}me\")"
+    );
+    assert_eq!(
+        SessionName::try_new("Caf\u{e9}")?.as_str(),
+        Some("Caf\u{e9}")
+    );
+
+    for input in [&b"a\rb"[..], b"a\nb", b"a\0b", b"\xe9\n"] {
+        assert!(
+            matches!(
+                SessionName::from_bytes(input),
+                Err(Error::SdpInvalidCharacter(_))
+            ),
+            "{:?}",
+            input
+        );
+        assert!(Information::from_bytes(input).is_err(), "{:?}", input);
+    }
+
+    Ok(())
+}
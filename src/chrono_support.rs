@@ -0,0 +1,132 @@
+use core::convert::TryFrom;
+
+use chrono::{DateTime, Utc};
+
+use super::alloc_prelude::*;
+use super::error::Error;
+use super::session_description::{Offset, SessionDescription, Time, TimeZone, TimeZones, Timing};
+use super::util::NTP_UNIX_OFFSET;
+
+#[cfg(test)]
+mod chrono_support_test;
+
+impl Time {
+    /// to_datetime converts the NTP time into a UTC date and time, or None if
+    /// it is out of the range of DateTime.
+    pub fn to_datetime(self) -> Option<DateTime<Utc>> {
+        let unix_secs = i64::try_from(self.0).ok()? - NTP_UNIX_OFFSET as i64;
+        DateTime::from_timestamp(unix_secs, 0)
+    }
+
+    /// from_datetime converts a UTC date and time into an NTP time,
+    /// truncating any sub-second part. Returns None before the NTP epoch
+    /// (1900-01-01).
+    pub fn from_datetime(datetime: DateTime<Utc>) -> Option<Time> {
+        u64::try_from(ntp_secs(datetime)).ok().map(Time)
+    }
+}
+
+/// ntp_secs returns the NTP seconds of datetime, which may be negative.
+fn ntp_secs(datetime: DateTime<Utc>) -> i128 {
+    i128::from(datetime.timestamp()) + i128::from(NTP_UNIX_OFFSET)
+}
+
+/// to_datetime converts NTP seconds which are known to come from a
+/// DateTime.
+fn to_datetime(secs: u64) -> DateTime<Utc> {
+    Time(secs).to_datetime().unwrap_or_default()
+}
+
+impl SessionDescription {
+    /// occurrences_between returns the activation intervals of the session
+    /// which overlap from..to, clipped to it, in chronological order. They
+    /// are those of TimeDescription::occurrences for every "t=" line, with
+    /// the "z=" adjustments applied.
+    pub fn occurrences_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+        // Clamping to the range of NTP times keeps the conversions back
+        // exact.
+        let clamp = |secs: i128| u64::try_from(secs.max(0)).unwrap_or(u64::MAX);
+        let (from, to) = (clamp(ntp_secs(from)), clamp(ntp_secs(to)));
+
+        let mut occurrences = vec![];
+        for time_description in &self.time_descriptions {
+            for (start, end) in time_description.occurrences(&self.time_zones) {
+                if start >= to {
+                    break;
+                }
+                if end > from {
+                    occurrences.push((start.max(from), end.min(to)));
+                }
+            }
+        }
+        occurrences.sort_unstable();
+
+        occurrences
+            .into_iter()
+            .map(|(start, end)| (to_datetime(start), to_datetime(end)))
+            .collect()
+    }
+}
+
+impl TimeZones {
+    /// from_transitions builds the "z=" adjustments of a session from the
+    /// transitions of a civil time zone, given as the times the zone changes
+    /// and its UTC offsets from then on. The offset in effect at the start
+    /// of the session, from the latest transition not after it, is the base
+    /// the repeat times are in; each later change within the session becomes
+    /// an adjustment relative to it.
+    ///
+    /// An unbounded session can't be covered by a finite list, and returns
+    /// Error::SdpUnboundedSession.
+    /// <https://tools.ietf.org/html/rfc4566#section-5.11>
+    pub fn from_transitions(
+        timing: &Timing,
+        transitions: &[(DateTime<Utc>, Offset)],
+    ) -> Result<TimeZones, Error> {
+        if timing.is_unbounded() {
+            return Err(Error::SdpUnboundedSession);
+        }
+        let start = i128::from(timing.start_time);
+        let stop = i128::from(timing.stop_time);
+
+        let mut transitions = transitions
+            .iter()
+            .map(|(datetime, offset)| (ntp_secs(*datetime), *offset))
+            .collect::<Vec<_>>();
+        transitions.sort_by_key(|(secs, _)| *secs);
+
+        let base = transitions
+            .iter()
+            .rev()
+            .find(|(secs, _)| *secs <= start)
+            .map(|(_, offset)| *offset)
+            .ok_or_else(|| {
+                Error::SdpInvalidValue(format!(
+                    "no UTC offset in effect at the start time {}",
+                    timing.start_time
+                ))
+            })?;
+
+        let mut time_zones = TimeZones::default();
+        let mut adjustment = Offset::ZERO;
+        for (secs, offset) in transitions {
+            if secs <= start || secs > stop {
+                continue;
+            }
+            let next = base
+                .checked_sub(offset)
+                .ok_or_else(|| Error::SdpInvalidValue(format!("{:?}", offset)))?;
+            if next != adjustment {
+                adjustment = next;
+                // secs is within the "t=" times, which are u64.
+                time_zones.push(TimeZone::new(secs as u64, adjustment.as_secs()));
+            }
+        }
+
+        Ok(time_zones)
+    }
+}
@@ -0,0 +1,99 @@
+use super::*;
+
+use chrono::TimeZone as _;
+
+fn utc(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, hour, 0, 0)
+        .single()
+        .expect("valid date")
+}
+
+#[test]
+fn test_time_datetime() {
+    assert_eq!(Time(0).to_datetime(), Some(utc(1900, 1, 1, 0)));
+    assert_eq!(
+        Time(NTP_UNIX_OFFSET).to_datetime(),
+        Some(utc(1970, 1, 1, 0))
+    );
+    assert_eq!(Time(u64::MAX).to_datetime(), None);
+
+    assert_eq!(Time::from_datetime(utc(1900, 1, 1, 0)), Some(Time(0)));
+    assert_eq!(Time::from_datetime(utc(1899, 12, 31, 23)), None);
+    let datetime = utc(1996, 2, 13, 12) + chrono::Duration::milliseconds(1500);
+    assert_eq!(
+        Time::from_datetime(datetime).and_then(Time::to_datetime),
+        Some(utc(1996, 2, 13, 12) + chrono::Duration::seconds(1))
+    );
+}
+
+#[test]
+fn test_occurrences_between() -> Result<(), Error> {
+    let sd = "v=0\r\no=- 0 0 IN IP4 127.0.0.1\r\ns=-\r\nt=0 0\r\n".parse::<SessionDescription>()?;
+    // A permanent session is clipped to the range.
+    assert_eq!(
+        sd.occurrences_between(utc(2020, 1, 1, 0), utc(2020, 1, 2, 0)),
+        vec![(utc(2020, 1, 1, 0), utc(2020, 1, 2, 0))]
+    );
+    assert!(sd
+        .occurrences_between(utc(1800, 1, 1, 0), utc(1850, 1, 1, 0))
+        .is_empty());
+
+    // Daily at 1h for one hour, from two "t=" lines.
+    let start = |day| Time::from_datetime(utc(2020, 1, day, 1)).map_or(0, Time::as_secs);
+    let sd = format!(
+        "v=0\r\no=- 0 0 IN IP4 127.0.0.1\r\ns=-\r\n\
+         t={} {}\r\nr=1d 1h 0\r\nt={} {}\r\nr=1d 1h 0\r\n",
+        start(10),
+        start(12),
+        start(1),
+        start(3)
+    )
+    .parse::<SessionDescription>()?;
+    assert_eq!(
+        sd.occurrences_between(utc(2020, 1, 2, 0), utc(2020, 1, 11, 0)),
+        vec![
+            (utc(2020, 1, 2, 1), utc(2020, 1, 2, 2)),
+            (utc(2020, 1, 10, 1), utc(2020, 1, 10, 2)),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_from_transitions() -> Result<(), Error> {
+    let hour = Offset::from_secs(3600);
+    let secs = |datetime| Time::from_datetime(datetime).map_or(0, Time::as_secs);
+    let timing = Timing::new(secs(utc(2020, 1, 1, 0)), secs(utc(2020, 12, 31, 0)))?;
+
+    // Central European time, with transitions outside the session and one
+    // which doesn't change the offset.
+    let transitions = [
+        (utc(2021, 3, 28, 1), hour * 2),
+        (utc(2020, 10, 25, 1), hour),
+        (utc(2020, 6, 1, 0), hour * 2),
+        (utc(2020, 3, 29, 1), hour * 2),
+        (utc(2019, 10, 27, 1), hour),
+    ];
+    let time_zones = TimeZones::from_transitions(&timing, &transitions)?;
+    assert_eq!(
+        time_zones.to_compact_string(),
+        format!(
+            "{} -1h {} 0",
+            secs(utc(2020, 3, 29, 1)),
+            secs(utc(2020, 10, 25, 1))
+        )
+    );
+
+    // Without a known offset at the start time.
+    assert!(matches!(
+        TimeZones::from_transitions(&timing, &transitions[..4]),
+        Err(Error::SdpInvalidValue(_))
+    ));
+    assert!(matches!(
+        TimeZones::from_transitions(&Timing::new(1, 0)?, &transitions),
+        Err(Error::SdpUnboundedSession)
+    ));
+
+    Ok(())
+}
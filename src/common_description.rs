@@ -1,18 +1,349 @@
-use std::fmt;
+use core::convert::TryFrom;
+use core::fmt;
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use core::str::FromStr;
 
-// Information describes the "i=" field which provides textual information
-// about the session.
-pub type Information = String;
+use url::Url;
 
-// ConnectionInformation defines the representation for the "c=" field
-// containing connection data.
-#[derive(Debug, Default)]
+use alloc::borrow::Cow;
+
+use super::alloc_prelude::*;
+use super::annotate::is_typed;
+use super::charset::Charset;
+use super::direction::Direction;
+use super::error::Error;
+use super::intern::Istr;
+use super::util::token::{is_token, validate_token};
+use super::util::{
+    base64_decode, base64_encode, decimal_len, impl_string_newtype, validate_no_line_breaks,
+    validate_text, END_LINE,
+};
+
+#[cfg(test)]
+mod common_description_test;
+
+/// Information describes the "i=" field which provides textual information
+/// about the session or media. It may be any text without line breaks, in
+/// the charset given by the "a=charset:" attribute, so the raw bytes are
+/// kept and decoded on demand.
+/// <https://tools.ietf.org/html/rfc4566#section-5.4>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::common_description::Information;
+///
+/// # fn main() -> Result<(), Error> {
+/// let information = "A Seminar on the session description protocol".parse::<Information>()?;
+/// assert_eq!(
+///     information.as_str(),
+///     Some("A Seminar on the session description protocol")
+/// );
+/// assert_eq!(Information::try_new("Audio")?.to_string(), "Audio");
+/// assert!(Information::try_new("Audio\r\nb=AS:64").is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default, Clone, PartialEq, Eq, Hash)]
+pub struct Information(pub(crate) Vec<u8>);
+
+impl Information {
+    /// try_new creates the information from UTF-8 text, rejecting line breaks
+    /// and NUL characters.
+    pub fn try_new(value: &str) -> Result<Self, Error> {
+        Information::from_bytes(value.as_bytes())
+    }
+
+    /// from_bytes creates the information from text in any charset.
+    pub fn from_bytes(value: &[u8]) -> Result<Self, Error> {
+        validate_text(value)?;
+        Ok(Information(value.to_vec()))
+    }
+
+    /// as_str returns the text if it is UTF-8.
+    pub fn as_str(&self) -> Option<&str> {
+        core::str::from_utf8(&self.0).ok()
+    }
+
+    /// as_bytes returns the text in its charset.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// decode_with decodes the text in the given charset.
+    pub fn decode_with(&self, charset: &Charset) -> Cow<'_, str> {
+        charset.decode(&self.0)
+    }
+}
+
+impl_string_newtype!(Information(Vec<u8>), validate_text);
+
+/// ConnectionInformation defines the representation for the "c=" field
+/// containing connection data.
+/// <https://tools.ietf.org/html/rfc4566#section-5.7>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::common_description::{ConnectionInformation, NetType};
+///
+/// # fn main() -> Result<(), Error> {
+/// let connection = "IN IP4 224.2.36.42/127/3".parse::<ConnectionInformation>()?;
+/// assert_eq!(connection.network_type, NetType::In);
+/// assert_eq!(connection.address_type, "IP4");
+/// let address = connection.address.as_ref().expect("address");
+/// assert_eq!((address.ttl, address.range), (Some(127), Some(3)));
+/// assert_eq!(connection.to_string(), "IN IP4 224.2.36.42/127/3");
+///
+/// assert!("IN IP6 10.47.16.5".parse::<ConnectionInformation>().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct ConnectionInformation {
-    pub network_type: String,
+    /// network_type is the `<nettype>`, "IN" for the Internet.
+    pub network_type: NetType,
+    /// address_type is the `<addrtype>`, "IP4" or "IP6" for the Internet.
     pub address_type: String,
+    /// address is the `<connection-address>`. It is optional so that lines
+    /// which only give the types can be represented.
     pub address: Option<Address>,
 }
 
+/// Network and address types currently registered with IANA
+/// <https://tools.ietf.org/html/rfc4566#section-8.2.6>
+/// <https://tools.ietf.org/html/rfc4566#section-8.2.7>
+pub const NETWORK_TYPE_IN: &str = "IN";
+/// ADDRESS_TYPE_IP4 is the address type of IPv4 addresses.
+pub const ADDRESS_TYPE_IP4: &str = "IP4";
+/// ADDRESS_TYPE_IP6 is the address type of IPv6 addresses.
+pub const ADDRESS_TYPE_IP6: &str = "IP6";
+
+/// NetType is the `<nettype>` of the "o=" and "c=" fields. Only "IN" is
+/// registered, other tokens are kept as written so that future registrations
+/// (e.g. "ATM" or "PSTN") do not need a breaking change.
+/// <https://tools.ietf.org/html/rfc4566#section-8.2.6>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::common_description::NetType;
+///
+/// # fn main() -> Result<(), Error> {
+/// assert_eq!("IN".parse::<NetType>()?, NetType::In);
+/// assert_eq!("ATM".parse::<NetType>()?, NetType::Custom("ATM".to_owned()));
+/// assert_eq!(NetType::In.to_string(), "IN");
+/// assert!("I N".parse::<NetType>().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub enum NetType {
+    /// In is the Internet.
+    #[default]
+    In,
+    /// Custom is any other network type, which must be a token.
+    Custom(String),
+}
+
+impl NetType {
+    /// as_str returns the network type as written in the description.
+    pub fn as_str(&self) -> &str {
+        match self {
+            NetType::In => NETWORK_TYPE_IN,
+            NetType::Custom(token) => token,
+        }
+    }
+
+    /// from_token creates the network type of an already validated token.
+    pub(crate) fn from_token(token: &str) -> Self {
+        match token {
+            NETWORK_TYPE_IN => NetType::In,
+            _ => NetType::Custom(token.to_owned()),
+        }
+    }
+}
+
+impl fmt::Display for NetType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for NetType {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        validate_token(value)?;
+        Ok(NetType::from_token(value))
+    }
+}
+
+/// validate_network_address checks a `<nettype>` `<addrtype>` `<address>` triple as
+/// used by the "o=" and "c=" fields. Network types other than "IN" are
+/// accepted as long as they are tokens, so that future registrations (e.g.
+/// "ATM" or "PSTN") do not need a breaking change; the address type is only
+/// checked for "IN". An address which is an IP literal must agree with the
+/// address type, while anything else must be a domain name, see
+/// validate_fqdn.
+pub(crate) fn validate_network_address(
+    network_type: &str,
+    address_type: &str,
+    address: Option<&str>,
+) -> Result<(), Error> {
+    validate_token(network_type)?;
+    validate_token(address_type)?;
+    if network_type != NETWORK_TYPE_IN {
+        return Ok(());
+    }
+
+    let is_ip6 = match address_type {
+        ADDRESS_TYPE_IP4 => false,
+        ADDRESS_TYPE_IP6 => true,
+        _ => return Err(Error::SdpInvalidValue(address_type.to_owned())),
+    };
+
+    if let Some(address) = address {
+        if let Ok(ip) = address.parse::<IpAddr>() {
+            if ip.is_ipv6() != is_ip6 {
+                return Err(Error::SdpAddressTypeMismatch {
+                    address_type: address_type.to_owned(),
+                    address: address.to_owned(),
+                });
+            }
+        } else {
+            validate_fqdn(address)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// MAX_FQDN_LEN is the longest domain name, not counting a trailing dot.
+/// <https://tools.ietf.org/html/rfc1035#section-2.3.4>
+pub const MAX_FQDN_LEN: usize = 253;
+
+/// MAX_FQDN_LABEL_LEN is the longest label of a domain name.
+pub const MAX_FQDN_LABEL_LEN: usize = 63;
+
+/// validate_fqdn checks that domain is a host name as used for `<unicast-address>`
+/// and `<connection-address>`: dot separated labels of 1 to 63 letters, digits
+/// and hyphens that neither start nor end with a hyphen, and 253 characters at
+/// most. A single trailing dot is allowed.
+/// Internationalized names must be given in their "xn--" form; non-ASCII
+/// characters are rejected.
+/// <https://tools.ietf.org/html/rfc4566#section-9>
+/// <https://tools.ietf.org/html/rfc1123#section-2.1>
+pub fn validate_fqdn(domain: &str) -> Result<(), Error> {
+    let name = domain.strip_suffix('.').unwrap_or(domain);
+    let is_label = |label: &str| {
+        (1..=MAX_FQDN_LABEL_LEN).contains(&label.len())
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+    };
+    if name.len() > MAX_FQDN_LEN || !name.split('.').all(is_label) {
+        return Err(Error::SdpInvalidFqdn(domain.to_owned()));
+    }
+    Ok(())
+}
+
+impl From<IpAddr> for ConnectionInformation {
+    /// from creates "IN IP4" or "IN IP6" connection information for a
+    /// unicast address.
+    fn from(ip: IpAddr) -> Self {
+        let address_type = match ip {
+            IpAddr::V4(_) => ADDRESS_TYPE_IP4,
+            IpAddr::V6(_) => ADDRESS_TYPE_IP6,
+        };
+        ConnectionInformation {
+            network_type: NetType::In,
+            address_type: address_type.to_owned(),
+            address: Some(Address::from(ip)),
+        }
+    }
+}
+
+impl FromStr for ConnectionInformation {
+    type Err = Error;
+
+    /// `c=<nettype> <addrtype> <connection-address>`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(ConnectionInformationRef::parse(value)?.to_owned())
+    }
+}
+
+/// ConnectionInformationRef is the borrowed form of ConnectionInformation,
+/// pointing into the text it was parsed from.
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::common_description::ConnectionInformationRef;
+///
+/// # fn main() -> Result<(), Error> {
+/// let connection = ConnectionInformationRef::parse("IN IP6 ff15::103/3")?;
+/// assert_eq!(connection.address.as_ref().map(|a| a.address), Some("ff15::103"));
+/// assert_eq!(connection.to_owned().to_string(), "IN IP6 ff15::103/3");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ConnectionInformationRef<'a> {
+    /// network_type is the `<nettype>`, see ConnectionInformation::network_type.
+    pub network_type: &'a str,
+    /// address_type is the `<addrtype>`, see ConnectionInformation::address_type.
+    pub address_type: &'a str,
+    /// address is the `<connection-address>`, see
+    /// ConnectionInformation::address.
+    pub address: Option<AddressRef<'a>>,
+}
+
+impl<'a> ConnectionInformationRef<'a> {
+    /// `c=<nettype> <addrtype> <connection-address>`
+    pub fn parse(value: &'a str) -> Result<Self, Error> {
+        let mut fields = value.split_whitespace();
+        let (network_type, address_type, address) =
+            match (fields.next(), fields.next(), fields.next(), fields.next()) {
+                (Some(network_type), Some(address_type), address, None) => {
+                    (network_type, address_type, address)
+                }
+                _ => return Err(Error::SdpInvalidSyntax(format!("`c={}`", value))),
+            };
+
+        let address = match address {
+            Some(address) => Some(AddressRef::parse(address_type, address)?),
+            None => None,
+        };
+        validate_network_address(
+            network_type,
+            address_type,
+            address.as_ref().map(|a| a.address),
+        )?;
+
+        Ok(ConnectionInformationRef {
+            network_type,
+            address_type,
+            address,
+        })
+    }
+
+    /// to_owned copies the connection data into a ConnectionInformation.
+    pub fn to_owned(&self) -> ConnectionInformation {
+        ConnectionInformation {
+            network_type: NetType::from_token(self.network_type),
+            address_type: self.address_type.to_owned(),
+            address: self.address.as_ref().map(|a| a.to_owned()),
+        }
+    }
+}
+
 impl fmt::Display for ConnectionInformation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(address) = &self.address {
@@ -23,36 +354,371 @@ impl fmt::Display for ConnectionInformation {
     }
 }
 
-// Address desribes a structured address token from within the "c=" field.
-#[derive(Debug, Default)]
+impl ConnectionInformation {
+    /// try_new creates connection data, checking the types and the address
+    /// like parsing a "c=" line does.
+    ///
+    /// ```
+    /// # use webrtc_rs_sdp::Error;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    /// use webrtc_rs_sdp::common_description::{Address, ConnectionInformation, NetType};
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let address = Address::from(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)));
+    /// let connection = ConnectionInformation::try_new(NetType::In, "IP4", Some(address))?;
+    /// assert_eq!(connection.to_string(), "IN IP4 192.0.2.1");
+    ///
+    /// let address = Address::from(IpAddr::V4(Ipv4Addr::new(224, 2, 1, 1)));
+    /// assert!(ConnectionInformation::try_new(NetType::In, "IP4", Some(address)).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_new(
+        network_type: NetType,
+        address_type: &str,
+        address: Option<Address>,
+    ) -> Result<Self, Error> {
+        if let Some(address) = &address {
+            AddressRef::parse(address_type, &address.to_string())?;
+        }
+        validate_network_address(
+            network_type.as_str(),
+            address_type,
+            address.as_ref().map(|a| a.address.as_str()),
+        )?;
+
+        Ok(ConnectionInformation {
+            network_type,
+            address_type: address_type.to_owned(),
+            address,
+        })
+    }
+
+    /// marshal_len_hint returns the length of the marshaled connection data.
+    pub fn marshal_len_hint(&self) -> usize {
+        self.network_type.as_str().len()
+            + 1
+            + self.address_type.len()
+            + self
+                .address
+                .as_ref()
+                .map_or(0, |address| 1 + address.marshal_len_hint())
+    }
+}
+
+/// Address desribes a structured address token from within the "c=" field.
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::common_description::Address;
+///
+/// # fn main() -> Result<(), Error> {
+/// let address = Address::parse("IP4", "224.2.1.1/127/3")?;
+/// assert!(address.is_multicast());
+/// assert_eq!(address.to_string(), "224.2.1.1/127/3");
+///
+/// let address = Address::fqdn("IP4", "Host.Example.com.")?;
+/// assert_eq!(address.as_domain(), Some("host.example.com"));
+/// assert_eq!(address.to_string(), "host.example.com");
+///
+/// assert!(Address::parse("IP4", "224.2.1.1").is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct Address {
+    /// address is the IP address or domain name.
     pub address: String,
-    pub ttl: Option<isize>,
-    pub range: Option<isize>,
+    /// ttl is the time to live of an IPv4 multicast address.
+    pub ttl: Option<u8>,
+    /// range is the number of consecutive multicast addresses, starting at
+    /// address.
+    pub range: Option<u32>,
 }
 
 impl fmt::Display for Address {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut parts = vec![self.address.to_owned()];
+        write!(f, "{}", self.address)?;
         if let Some(t) = &self.ttl {
-            parts.push(t.to_string());
+            write!(f, "/{}", t)?;
         }
         if let Some(r) = &self.range {
-            parts.push(r.to_string());
+            write!(f, "/{}", r)?;
+        }
+        Ok(())
+    }
+}
+
+impl Address {
+    /// marshal_len_hint returns the length of the marshaled address.
+    pub fn marshal_len_hint(&self) -> usize {
+        self.address.len()
+            + self.ttl.map_or(0, |ttl| 1 + decimal_len(u64::from(ttl)))
+            + self
+                .range
+                .map_or(0, |range| 1 + decimal_len(u64::from(range)))
+    }
+}
+
+impl From<IpAddr> for Address {
+    /// The address carries no TTL or range, so an IPv4 multicast address
+    /// needs a TTL set before it can be written into a "c=" line.
+    fn from(ip: IpAddr) -> Self {
+        Address {
+            address: ip.to_string(),
+            ttl: None,
+            range: None,
         }
-        write!(f, "{}", parts.join("/"))
     }
 }
 
-// Bandwidth describes an optional field which denotes the proposed bandwidth
-// to be used by the session or media.
-#[derive(Debug, Default)]
+impl FromStr for Address {
+    type Err = Error;
+
+    /// The `<addrtype>` is taken to be "IP6" if the address contains a ":",
+    /// and "IP4" otherwise. Use Address::parse when it is known.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let address = value.split('/').next().unwrap_or_default();
+        let address_type = if address.contains(':') {
+            ADDRESS_TYPE_IP6
+        } else {
+            ADDRESS_TYPE_IP4
+        };
+        Address::parse(address_type, value)
+    }
+}
+
+impl Address {
+    /// parse reads a `<connection-address>` for the given `<addrtype>`, see
+    /// AddressRef::parse.
+    pub fn parse(address_type: &str, value: &str) -> Result<Self, Error> {
+        Ok(AddressRef::parse(address_type, value)?.to_owned())
+    }
+
+    /// fqdn creates a unicast Address holding a domain name for the "IP4" or
+    /// "IP6" address type. The name is checked with validate_fqdn and stored
+    /// normalized: in lowercase, as domain names are case-insensitive, and
+    /// without a trailing dot, which is therefore not written back out.
+    /// Parsed addresses keep the text as written.
+    pub fn fqdn(address_type: &str, domain: &str) -> Result<Self, Error> {
+        if address_type != ADDRESS_TYPE_IP4 && address_type != ADDRESS_TYPE_IP6 {
+            return Err(Error::SdpInvalidValue(address_type.to_owned()));
+        }
+        if domain.parse::<IpAddr>().is_ok() {
+            return Err(Error::SdpInvalidFqdn(domain.to_owned()));
+        }
+        validate_fqdn(domain)?;
+        let domain = domain.strip_suffix('.').unwrap_or(domain);
+        Ok(Address {
+            address: domain.to_ascii_lowercase(),
+            ttl: None,
+            range: None,
+        })
+    }
+
+    /// as_ip returns the address as an IP address, or None for a domain name.
+    pub fn as_ip(&self) -> Option<IpAddr> {
+        self.address.parse().ok()
+    }
+
+    /// as_domain returns the domain name of the address without any trailing
+    /// dot, or None for an IP address.
+    pub fn as_domain(&self) -> Option<&str> {
+        match self.as_ip() {
+            Some(_) => None,
+            None => Some(self.address.strip_suffix('.').unwrap_or(&self.address)),
+        }
+    }
+
+    /// is_multicast reports whether the address is an IP multicast address.
+    pub fn is_multicast(&self) -> bool {
+        self.address
+            .parse::<IpAddr>()
+            .map(|ip| ip.is_multicast())
+            .unwrap_or(false)
+    }
+}
+
+/// AddressRef is the borrowed form of Address.
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::common_description::AddressRef;
+///
+/// # fn main() -> Result<(), Error> {
+/// let address = AddressRef::parse("IP6", "ff15::103/3")?;
+/// assert_eq!((address.address, address.range), ("ff15::103", Some(3)));
+/// assert_eq!(address.to_owned().to_string(), "ff15::103/3");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AddressRef<'a> {
+    /// address is the IP address or domain name, see Address::address.
+    pub address: &'a str,
+    /// ttl is the time to live, see Address::ttl.
+    pub ttl: Option<u8>,
+    /// range is the number of addresses, see Address::range.
+    pub range: Option<u32>,
+}
+
+impl<'a> AddressRef<'a> {
+    /// parse reads a `<connection-address>` for the given `<addrtype>`. IPv4
+    /// multicast addresses carry a mandatory TTL and an optional number of
+    /// addresses; IPv6 multicast addresses only carry the optional number of
+    /// addresses. Unicast addresses carry neither.
+    /// <https://tools.ietf.org/html/rfc4566#section-5.7>
+    pub fn parse(address_type: &str, value: &'a str) -> Result<Self, Error> {
+        validate_no_line_breaks(value)?;
+        let invalid = || Error::SdpInvalidConnectionAddress(value.to_owned());
+        let mut parts = value.split('/');
+        let address = parts.next().unwrap_or_default();
+        let (second, third) = (parts.next(), parts.next());
+        if address.is_empty() || parts.next().is_some() {
+            return Err(invalid());
+        }
+
+        let parse_range = |range: &str| match range.parse::<u32>() {
+            Ok(range) if range >= 1 => Ok(range),
+            _ => Err(invalid()),
+        };
+
+        let (ttl, range) = match address_type {
+            "IP4" => {
+                let multicast = address
+                    .parse::<Ipv4Addr>()
+                    .map(|ip| ip.is_multicast())
+                    .unwrap_or(false);
+                if multicast != second.is_some() {
+                    return Err(invalid());
+                }
+                let ttl = match second {
+                    Some(ttl) => Some(ttl.parse::<u8>().map_err(|_| invalid())?),
+                    None => None,
+                };
+                let range = match third {
+                    Some(range) => Some(parse_range(range)?),
+                    None => None,
+                };
+                (ttl, range)
+            }
+            "IP6" => {
+                let multicast = address
+                    .parse::<Ipv6Addr>()
+                    .map(|ip| ip.is_multicast())
+                    .unwrap_or(false);
+                if third.is_some() || (!multicast && second.is_some()) {
+                    return Err(invalid());
+                }
+                let range = match second {
+                    Some(range) => Some(parse_range(range)?),
+                    None => None,
+                };
+                (None, range)
+            }
+            _ => {
+                if second.is_some() {
+                    return Err(invalid());
+                }
+                (None, None)
+            }
+        };
+
+        Ok(AddressRef {
+            address,
+            ttl,
+            range,
+        })
+    }
+
+    /// to_owned copies the address into an Address.
+    pub fn to_owned(&self) -> Address {
+        Address {
+            address: self.address.to_owned(),
+            ttl: self.ttl,
+            range: self.range,
+        }
+    }
+}
+
+/// Bandwidth describes an optional field which denotes the proposed bandwidth
+/// to be used by the session or media.
+/// <https://tools.ietf.org/html/rfc4566#section-5.8>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::common_description::{Bandwidth, BandwidthKind};
+///
+/// # fn main() -> Result<(), Error> {
+/// let bandwidth = "AS:128".parse::<Bandwidth>()?;
+/// assert_eq!(bandwidth.kind(), Some(BandwidthKind::As));
+/// assert_eq!(bandwidth.bits_per_second(), Some(128_000));
+/// assert_eq!(bandwidth.to_string(), "AS:128");
+///
+/// let bandwidth = Bandwidth::new("X-YZ", 64)?;
+/// assert!(bandwidth.experimental);
+/// assert_eq!(bandwidth.to_string(), "X-YZ:64");
+/// assert_eq!(Bandwidth::tias_from_bps(96_000).to_string(), "TIAS:96000");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct Bandwidth {
+    /// experimental reports whether the `<bwtype>` has the "X-" prefix.
     pub experimental: bool,
-    pub bandwidth_type: String,
+    /// bandwidth_type is the `<bwtype>`, without any "X-" prefix.
+    pub bandwidth_type: BandwidthType,
+    /// bandwidth is the `<bandwidth>`, in a unit depending on the type.
     pub bandwidth: u64,
 }
 
+/// BandwidthType is the `<bwtype>` of a "b=" field, without the "X-" prefix of
+/// experimental types. It must be a token; see util::token::is_token_char.
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::common_description::BandwidthType;
+///
+/// # fn main() -> Result<(), Error> {
+/// assert_eq!("TIAS".parse::<BandwidthType>()?.as_str(), "TIAS");
+/// assert_eq!(BandwidthType::try_new("CT")?.to_string(), "CT");
+/// assert!(BandwidthType::try_new("A S").is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct BandwidthType(String);
+
+impl BandwidthType {
+    /// try_new creates a bandwidth type, rejecting values which are not tokens.
+    pub fn try_new(value: &str) -> Result<Self, Error> {
+        validate_bandwidth_type(value)?;
+        Ok(BandwidthType(value.to_owned()))
+    }
+
+    /// as_str returns the bandwidth type.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+fn validate_bandwidth_type(value: &str) -> Result<(), Error> {
+    if !is_token(value) {
+        return Err(Error::BandwidthInvalidType(value.to_owned()));
+    }
+    Ok(())
+}
+
+impl_string_newtype!(BandwidthType(String), validate_bandwidth_type);
+
 impl fmt::Display for Bandwidth {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let output = if self.experimental { "X-" } else { "" };
@@ -60,15 +726,451 @@ impl fmt::Display for Bandwidth {
     }
 }
 
-// EncryptionKey describes the "k=" which conveys encryption key information.
-pub type EncryptionKey = String;
+/// Bandwidth types currently registered with IANA
+/// <https://tools.ietf.org/html/rfc4566#section-5.8>
+/// <https://tools.ietf.org/html/rfc3890#section-6.2>
+/// <https://tools.ietf.org/html/rfc3556#section-2>
+pub const BANDWIDTH_TYPE_CT: &str = "CT";
+/// BANDWIDTH_TYPE_AS is the application specific maximum, in kilobits per
+/// second.
+pub const BANDWIDTH_TYPE_AS: &str = "AS";
+/// BANDWIDTH_TYPE_TIAS is the transport independent application specific
+/// maximum, in bits per second.
+pub const BANDWIDTH_TYPE_TIAS: &str = "TIAS";
+/// BANDWIDTH_TYPE_RR is the RTCP bandwidth of receivers, in bits per second.
+pub const BANDWIDTH_TYPE_RR: &str = "RR";
+/// BANDWIDTH_TYPE_RS is the RTCP bandwidth of senders, in bits per second.
+pub const BANDWIDTH_TYPE_RS: &str = "RS";
+
+/// BandwidthKind is one of the registered bandwidth modifiers, for looking
+/// up "b=" fields regardless of how they are spelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BandwidthKind {
+    /// Ct is the conference total, "CT".
+    Ct,
+    /// As is the application specific maximum, "AS".
+    As,
+    /// Tias is the transport independent application specific maximum, "TIAS".
+    Tias,
+    /// Rr and Rs are the RTCP bandwidths of receivers and senders. Unlike AS
+    /// and CT, they are given in bits per second.
+    Rr,
+    /// Rs is the RTCP bandwidth of senders, "RS".
+    Rs,
+}
+
+impl BandwidthKind {
+    /// ALL lists every registered modifier.
+    pub const ALL: [BandwidthKind; 5] = [
+        BandwidthKind::Ct,
+        BandwidthKind::As,
+        BandwidthKind::Tias,
+        BandwidthKind::Rr,
+        BandwidthKind::Rs,
+    ];
+
+    /// as_str returns the `<bwtype>` of the modifier.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BandwidthKind::Ct => BANDWIDTH_TYPE_CT,
+            BandwidthKind::As => BANDWIDTH_TYPE_AS,
+            BandwidthKind::Tias => BANDWIDTH_TYPE_TIAS,
+            BandwidthKind::Rr => BANDWIDTH_TYPE_RR,
+            BandwidthKind::Rs => BANDWIDTH_TYPE_RS,
+        }
+    }
+}
+
+impl fmt::Display for BandwidthKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl FromStr for BandwidthKind {
+    type Err = Error;
+
+    /// Only the registered modifiers are accepted, without an "X-" prefix.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        BandwidthKind::ALL
+            .iter()
+            .copied()
+            .find(|kind| kind.as_str() == value)
+            .ok_or_else(|| Error::BandwidthInvalidType(value.to_owned()))
+    }
+}
+
+impl Bandwidth {
+    /// new creates a Bandwidth after validating the bwtype. A leading "X-"
+    /// marks the bandwidth as experimental. Types other than the registered
+    /// ones are accepted as long as they are valid tokens, since RFC 4566
+    /// requires unknown modifiers to be ignored rather than rejected.
+    pub fn new(bandwidth_type: &str, bandwidth: u64) -> Result<Self, Error> {
+        let (experimental, bandwidth_type) = match bandwidth_type.strip_prefix("X-") {
+            Some(stripped) => (true, stripped),
+            None => (false, bandwidth_type),
+        };
+
+        let bandwidth_type = BandwidthType::try_new(bandwidth_type)?;
+        let bandwidth = Bandwidth {
+            experimental,
+            bandwidth_type,
+            bandwidth,
+        };
+        if bandwidth.kind() != Some(BandwidthKind::Tias)
+            && bandwidth.bandwidth > u64::from(u32::MAX)
+        {
+            return Err(Error::BandwidthOverflow(bandwidth.bandwidth.to_string()));
+        }
+
+        Ok(bandwidth)
+    }
+
+    /// marshal_len_hint returns the length of the marshaled bandwidth.
+    pub fn marshal_len_hint(&self) -> usize {
+        let prefix = if self.experimental { 2 } else { 0 };
+        prefix + self.bandwidth_type.0.len() + 1 + decimal_len(self.bandwidth)
+    }
+
+    /// as_from_bps creates an "AS" bandwidth from bits per second, rounding
+    /// up to whole kilobits.
+    pub fn as_from_bps(bps: u64) -> Result<Self, Error> {
+        Bandwidth::new(BANDWIDTH_TYPE_AS, bps.div_ceil(1000))
+    }
+
+    /// tias_from_bps creates a "TIAS" bandwidth. TIAS is given in bits per
+    /// second and, unlike the other types, may exceed u32::MAX.
+    pub fn tias_from_bps(bps: u64) -> Self {
+        Bandwidth {
+            experimental: false,
+            bandwidth_type: BandwidthType(BANDWIDTH_TYPE_TIAS.to_owned()),
+            bandwidth: bps,
+        }
+    }
+
+    /// bits_per_second returns the bandwidth in bits per second. AS and CT
+    /// are given in kilobits per second, and TIAS, RR and RS in bits per
+    /// second.
+    /// <https://tools.ietf.org/html/rfc3890#section-6.2>
+    /// <https://tools.ietf.org/html/rfc3556#section-2>
+    /// Returns None for other types, whose unit is unknown, or on overflow.
+    pub fn bits_per_second(&self) -> Option<u64> {
+        if self.is_bps() {
+            Some(self.bandwidth)
+        } else if self.is_kbps() {
+            self.bandwidth.checked_mul(1000)
+        } else {
+            None
+        }
+    }
+
+    /// kilobits_per_second returns the bandwidth in kilobits per second,
+    /// rounding TIAS, RR and RS values up. Returns None for types with an
+    /// unknown unit.
+    pub fn kilobits_per_second(&self) -> Option<u64> {
+        if self.is_bps() {
+            Some(self.bandwidth.div_ceil(1000))
+        } else if self.is_kbps() {
+            Some(self.bandwidth)
+        } else {
+            None
+        }
+    }
+
+    /// kind returns the registered modifier of the bandwidth, or None for
+    /// experimental and unknown ones.
+    pub fn kind(&self) -> Option<BandwidthKind> {
+        if self.experimental {
+            return None;
+        }
+        BandwidthKind::ALL
+            .iter()
+            .copied()
+            .find(|kind| self.bandwidth_type == kind.as_str())
+    }
+
+    /// has_same_modifier returns true if both bandwidths have the same
+    /// `<bwtype>`, experimental or not.
+    pub fn has_same_modifier(&self, other: &Bandwidth) -> bool {
+        self.experimental == other.experimental && self.bandwidth_type == other.bandwidth_type
+    }
+
+    fn is_bps(&self) -> bool {
+        matches!(
+            self.kind(),
+            Some(BandwidthKind::Tias | BandwidthKind::Rr | BandwidthKind::Rs)
+        )
+    }
+
+    fn is_kbps(&self) -> bool {
+        matches!(self.kind(), Some(BandwidthKind::As | BandwidthKind::Ct))
+    }
+}
+
+/// RtcpBandwidth is the RTCP bandwidth of a media description in bits per
+/// second, for senders and receivers. None means the bandwidth is unknown,
+/// and zero that RTCP is disabled.
+/// <https://tools.ietf.org/html/rfc3556#section-2>
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RtcpBandwidth {
+    /// rr_bps is the RTCP bandwidth of receivers.
+    pub rr_bps: Option<u64>,
+    /// rs_bps is the RTCP bandwidth of senders.
+    pub rs_bps: Option<u64>,
+}
+
+impl RtcpBandwidth {
+    /// is_disabled reports whether both bandwidths are zero, which turns RTCP
+    /// off.
+    pub fn is_disabled(&self) -> bool {
+        self.rr_bps == Some(0) && self.rs_bps == Some(0)
+    }
+}
+
+impl FromStr for Bandwidth {
+    type Err = Error;
+
+    /// `b=<bwtype>:<bandwidth>`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (bandwidth_type, bandwidth) = match value.find(':') {
+            Some(i) => (&value[..i], &value[i + 1..]),
+            None => return Err(Error::BandwidthMissingSeparator(value.to_owned())),
+        };
+
+        if bandwidth.is_empty() || !bandwidth.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(Error::BandwidthInvalidValue(value.to_owned()));
+        }
+        let bandwidth = match bandwidth.parse::<u64>() {
+            Ok(bandwidth) => bandwidth,
+            Err(_) => return Err(Error::BandwidthOverflow(value.to_owned())),
+        };
+
+        Bandwidth::new(bandwidth_type, bandwidth).map_err(|err| match err {
+            Error::BandwidthOverflow(_) => Error::BandwidthOverflow(value.to_owned()),
+            _ => Error::BandwidthInvalidType(value.to_owned()),
+        })
+    }
+}
+
+/// KEY_METHOD_CLEAR is the method of an untransformed key.
+pub const KEY_METHOD_CLEAR: &str = "clear";
+/// KEY_METHOD_BASE64 is the method of a base64 encoded key.
+pub const KEY_METHOD_BASE64: &str = "base64";
+/// KEY_METHOD_URI is the method of a key obtained from a URI.
+pub const KEY_METHOD_URI: &str = "uri";
+/// KEY_METHOD_PROMPT is the method of a key the user is prompted for.
+pub const KEY_METHOD_PROMPT: &str = "prompt";
+
+/// EncryptionKey describes the "k=" which conveys encryption key information,
+/// i.e. "`<method>`" or "`<method>`:`<encryption key>`".
+/// <https://tools.ietf.org/html/rfc4566#section-5.12>
+///
+/// Display emits the real key as it is needed for serialization, while Debug
+/// redacts it so that logging a description does not print key material. Use
+/// expose() to deliberately access the key.
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::common_description::EncryptionKey;
+///
+/// # fn main() -> Result<(), Error> {
+/// let key = "base64:c2VjcmV0".parse::<EncryptionKey>()?;
+/// assert_eq!(key.method(), "base64");
+/// assert_eq!(key.decoded_bytes()?, b"secret");
+/// assert_eq!(key.to_string(), "base64:c2VjcmV0");
+/// assert_eq!(format!("{:?}", key), "EncryptionKey::Base64(REDACTED)");
+///
+/// assert_eq!(EncryptionKey::base64_from_bytes(b"secret"), key);
+/// assert_eq!(EncryptionKey::try_new("prompt")?, EncryptionKey::Prompt);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub enum EncryptionKey {
+    /// Clear is a key which is included untransformed.
+    Clear(String),
+    /// Base64 is a base64 encoded key.
+    Base64(String),
+    /// Uri is the URI the key can be obtained from.
+    Uri(Url),
+    /// Prompt asks the user for the key.
+    Prompt,
+}
+
+impl EncryptionKey {
+    /// try_new parses a "k=" value, like FromStr.
+    pub fn try_new(value: &str) -> Result<Self, Error> {
+        value.parse()
+    }
+
+    /// method returns the method part of the "k=" line.
+    pub fn method(&self) -> &'static str {
+        match self {
+            EncryptionKey::Clear(_) => KEY_METHOD_CLEAR,
+            EncryptionKey::Base64(_) => KEY_METHOD_BASE64,
+            EncryptionKey::Uri(_) => KEY_METHOD_URI,
+            EncryptionKey::Prompt => KEY_METHOD_PROMPT,
+        }
+    }
+
+    /// marshal_len_hint returns the length of the marshaled key.
+    pub fn marshal_len_hint(&self) -> usize {
+        let material = match self {
+            EncryptionKey::Clear(key) | EncryptionKey::Base64(key) => key.len(),
+            EncryptionKey::Uri(uri) => uri.as_str().len(),
+            EncryptionKey::Prompt => return self.method().len(),
+        };
+        self.method().len() + 1 + material
+    }
 
-// Attribute describes the "a=" field which represents the primary means for
-// extending SDP.
-#[derive(Debug, Default)]
+    /// base64_from_bytes creates a "base64:" key from raw key bytes.
+    pub fn base64_from_bytes(key: &[u8]) -> Self {
+        EncryptionKey::Base64(base64_encode(key))
+    }
+
+    /// decoded_bytes returns the raw bytes of a "base64:" key.
+    pub fn decoded_bytes(&self) -> Result<Vec<u8>, Error> {
+        match self {
+            EncryptionKey::Base64(key) => base64_decode(key).ok_or(Error::SdpInvalidBase64Key),
+            key => Err(Error::SdpKeyNotBase64(key.method().to_owned())),
+        }
+    }
+
+    /// expose gives access to the key material, which Debug redacts.
+    pub fn expose(&self) -> ExposedEncryptionKey<'_> {
+        ExposedEncryptionKey(self)
+    }
+}
+
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncryptionKey::Clear(_) => write!(f, "EncryptionKey::Clear(REDACTED)"),
+            EncryptionKey::Base64(_) => write!(f, "EncryptionKey::Base64(REDACTED)"),
+            EncryptionKey::Uri(uri) => write!(f, "EncryptionKey::Uri({:?})", uri.as_str()),
+            EncryptionKey::Prompt => write!(f, "EncryptionKey::Prompt"),
+        }
+    }
+}
+
+impl fmt::Display for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.expose().material() {
+            Some(key) => write!(f, "{}:{}", self.method(), key),
+            None => write!(f, "{}", self.method()),
+        }
+    }
+}
+
+impl FromStr for EncryptionKey {
+    type Err = Error;
+
+    /// Errors never include the key itself.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (method, key) = match value.split_once(':') {
+            Some((method, key)) => (method, Some(key)),
+            None => (value, None),
+        };
+
+        match (method, key) {
+            (KEY_METHOD_PROMPT, None) => Ok(EncryptionKey::Prompt),
+            (KEY_METHOD_CLEAR, Some(key)) => {
+                if key.contains(['\r', '\n', '\0']) {
+                    return Err(Error::SdpInvalidCharacter(format!("{}:", method)));
+                }
+                Ok(EncryptionKey::Clear(key.to_owned()))
+            }
+            (KEY_METHOD_BASE64, Some(key)) => {
+                if key.is_empty() || base64_decode(key).is_none() {
+                    return Err(Error::SdpInvalidBase64Key);
+                }
+                Ok(EncryptionKey::Base64(key.to_owned()))
+            }
+            (KEY_METHOD_URI, Some(key)) => {
+                validate_no_line_breaks(key)?;
+                Ok(EncryptionKey::Uri(Url::parse(key)?))
+            }
+            _ => Err(Error::SdpUnknownKeyMethod(
+                method.chars().filter(|c| !c.is_control()).collect(),
+            )),
+        }
+    }
+}
+
+impl TryFrom<&str> for EncryptionKey {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<String> for EncryptionKey {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// ExposedEncryptionKey is an explicit view of the key material of an
+/// EncryptionKey, whose Debug output is not redacted.
+#[derive(Clone, Copy)]
+pub struct ExposedEncryptionKey<'a>(&'a EncryptionKey);
+
+impl<'a> ExposedEncryptionKey<'a> {
+    /// material returns the key, or the URI it can be obtained from. It is
+    /// None for the "prompt" method.
+    pub fn material(&self) -> Option<&'a str> {
+        match self.0 {
+            EncryptionKey::Clear(key) | EncryptionKey::Base64(key) => Some(key),
+            EncryptionKey::Uri(uri) => Some(uri.as_str()),
+            EncryptionKey::Prompt => None,
+        }
+    }
+}
+
+impl fmt::Debug for ExposedEncryptionKey<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            EncryptionKey::Clear(key) => write!(f, "EncryptionKey::Clear({:?})", key),
+            EncryptionKey::Base64(key) => write!(f, "EncryptionKey::Base64({:?})", key),
+            key => write!(f, "{:?}", key),
+        }
+    }
+}
+
+/// Attribute describes the "a=" field which represents the primary means for
+/// extending SDP. An attribute is either a property attribute ("a=recvonly")
+/// without a value, or a value attribute ("a=fmtp:96 ...").
+/// <https://tools.ietf.org/html/rfc4566#section-5.13>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::common_description::Attribute;
+///
+/// # fn main() -> Result<(), Error> {
+/// let attribute = "rtpmap:111 opus/48000/2".parse::<Attribute>()?;
+/// assert_eq!(attribute.name(), "rtpmap");
+/// assert_eq!(attribute.value(), Some("111 opus/48000/2"));
+/// assert_eq!(attribute.to_string(), "rtpmap:111 opus/48000/2");
+///
+/// let attribute = Attribute::try_new("recvonly", None)?;
+/// assert!(attribute.is_property());
+/// assert!(Attribute::try_new("bad name", None).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct Attribute {
-    pub key: String,
-    pub value: Option<String>,
+    /// key is the attribute name.
+    pub key: Istr,
+    /// value is the attribute value, or None for property attributes.
+    pub value: Option<Istr>,
 }
 
 impl fmt::Display for Attribute {
@@ -81,14 +1183,400 @@ impl fmt::Display for Attribute {
     }
 }
 
+impl FromStr for Attribute {
+    type Err = Error;
+
+    /// `a=<attribute>`
+    /// `a=<attribute>:<value>`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(AttributeRef::parse(value)?.to_owned())
+    }
+}
+
 impl Attribute {
-    // constructs a new attribute
+    /// constructs a new attribute
     pub fn new(key: String, value: Option<String>) -> Self {
-        Attribute { key, value }
+        Attribute {
+            key: key.into(),
+            value: value.map(Istr::from),
+        }
+    }
+
+    /// try_new constructs a new attribute, checking that the name is a
+    /// non-empty token and that the value does not contain a line break.
+    pub fn try_new(key: &str, value: Option<&str>) -> Result<Self, Error> {
+        Ok(AttributeRef::try_new(key, value)?.to_owned())
+    }
+
+    /// marshal_len_hint returns the length of the marshaled attribute.
+    pub fn marshal_len_hint(&self) -> usize {
+        self.key.len() + self.value.as_ref().map_or(0, |value| 1 + value.len())
+    }
+
+    /// name returns the attribute name.
+    pub fn name(&self) -> &str {
+        &self.key
+    }
+
+    /// value returns the attribute value, or None for property attributes.
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+
+    /// is_property returns true for attributes of the "a=`<flag>`" form.
+    pub fn is_property(&self) -> bool {
+        self.value.is_none()
     }
 
-    // IsICECandidate returns true if the attribute key equals "candidate".
+    /// IsICECandidate returns true if the attribute key equals "candidate".
     pub fn is_ice_candidate(&self) -> bool {
         self.key.as_str() == "candidate"
     }
 }
+
+/// AttributeRef is the borrowed form of Attribute.
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::common_description::AttributeRef;
+///
+/// # fn main() -> Result<(), Error> {
+/// let attribute = AttributeRef::parse("mid:0")?;
+/// assert_eq!((attribute.name(), attribute.value()), ("mid", Some("0")));
+/// assert_eq!(attribute.to_string(), "mid:0");
+/// assert_eq!(attribute.to_owned().to_string(), "mid:0");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AttributeRef<'a> {
+    /// key is the attribute name.
+    pub key: &'a str,
+    /// value is the attribute value, or None for property attributes.
+    pub value: Option<&'a str>,
+}
+
+impl fmt::Display for AttributeRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(value) = self.value {
+            write!(f, "{}:{}", self.key, value)
+        } else {
+            write!(f, "{}", self.key)
+        }
+    }
+}
+
+impl<'a> AttributeRef<'a> {
+    /// `a=<attribute>`
+    /// `a=<attribute>:<value>`
+    pub fn parse(value: &'a str) -> Result<Self, Error> {
+        match value.split_once(':') {
+            Some((key, v)) => AttributeRef::try_new(key, Some(v)),
+            None => AttributeRef::try_new(value, None),
+        }
+    }
+
+    /// parse_line parses the text of an "a=" line after the "=", without its
+    /// line break. It is trimmed like the other fields, except for the value
+    /// of an attribute which has no type in this crate: that is kept as
+    /// written, trailing whitespace included, so that it is forwarded byte
+    /// for byte.
+    pub(crate) fn parse_line(line: &'a str) -> Result<Self, Error> {
+        let attribute = AttributeRef::parse(line.trim())?;
+        if let (Some(value), Some((_, raw))) = (attribute.value, line.trim_start().split_once(':'))
+        {
+            if raw.len() != value.len() && !is_typed(&attribute.to_owned()) {
+                return AttributeRef::try_new(attribute.key, Some(raw));
+            }
+        }
+        Ok(attribute)
+    }
+
+    /// try_new checks that the name is a non-empty token and that the value
+    /// does not contain a line break.
+    pub fn try_new(key: &'a str, value: Option<&'a str>) -> Result<Self, Error> {
+        let attribute = AttributeRef { key, value };
+
+        if !is_token(key) {
+            return Err(Error::SdpInvalidAttributeName(attribute.to_string()));
+        }
+        if let Some(value) = value {
+            validate_no_line_breaks(value)?;
+        }
+
+        Ok(attribute)
+    }
+
+    /// name returns the attribute name.
+    pub fn name(&self) -> &'a str {
+        self.key
+    }
+
+    /// value returns the attribute value, or None for property attributes.
+    pub fn value(&self) -> Option<&'a str> {
+        self.value
+    }
+
+    /// to_owned copies the attribute into an Attribute.
+    pub fn to_owned(&self) -> Attribute {
+        Attribute {
+            key: self.key.into(),
+            value: self.value.map(Istr::from),
+        }
+    }
+}
+
+/// Attributes is the ordered list of "a=" lines of a session or media
+/// description. The same attribute name may occur several times (e.g.
+/// "rtpmap" or "candidate"), so lookups come in a first-match and an
+/// all-matches flavour.
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::common_description::{Attribute, Attributes};
+/// use webrtc_rs_sdp::direction::Direction;
+///
+/// # fn main() -> Result<(), Error> {
+/// let mut attributes = Attributes::default();
+/// attributes.push("mid:0".parse::<Attribute>()?);
+/// attributes.insert(Direction::SendOnly);
+/// attributes.set_direction(Direction::RecvOnly);
+/// assert_eq!(attributes.len(), 2);
+/// assert_eq!(attributes.direction(), Some(Direction::RecvOnly));
+/// assert_eq!(attributes.get_first("mid").and_then(|a| a.value()), Some("0"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Attributes(pub Vec<Attribute>);
+
+impl From<Vec<Attribute>> for Attributes {
+    fn from(attributes: Vec<Attribute>) -> Self {
+        Attributes(attributes)
+    }
+}
+
+impl Attributes {
+    /// len returns the number of attributes.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// is_empty reports whether there are no attributes.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// iter iterates over the attributes in order.
+    pub fn iter(&self) -> core::slice::Iter<'_, Attribute> {
+        self.0.iter()
+    }
+
+    /// push appends an attribute.
+    pub fn push(&mut self, attribute: Attribute) {
+        self.0.push(attribute);
+    }
+
+    /// get_first returns the first attribute with the given name.
+    pub fn get_first(&self, name: &str) -> Option<&Attribute> {
+        self.0.iter().find(|a| a.key == name)
+    }
+
+    /// get_all returns every attribute with the given name, in the order they
+    /// appear in the description.
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Attribute> + 'a {
+        self.0.iter().filter(move |a| a.key == name)
+    }
+
+    /// has returns true if at least one attribute with the given name exists.
+    pub fn has(&self, name: &str) -> bool {
+        self.get_first(name).is_some()
+    }
+
+    /// get parses every attribute of type T, in order. Malformed attributes
+    /// are returned as errors so the caller can decide whether they matter.
+    pub fn get<'a, T: TypedAttribute + 'a>(
+        &'a self,
+    ) -> impl Iterator<Item = Result<T, T::Err>> + 'a {
+        self.0
+            .iter()
+            .filter(|a| T::matches(a))
+            .map(T::from_attribute)
+    }
+
+    /// insert appends an attribute of type T.
+    pub fn insert<T: TypedAttribute>(&mut self, value: T) {
+        self.0.push(value.to_attribute());
+    }
+
+    /// remove_all removes every attribute of type T, well-formed or not.
+    pub fn remove_all<T: TypedAttribute>(&mut self) {
+        self.0.retain(|a| !T::matches(a));
+    }
+
+    /// replace replaces every attribute of type T with a single one, kept at
+    /// the position of the first one found.
+    pub fn replace<T: TypedAttribute>(&mut self, value: T) {
+        let position = self.0.iter().position(T::matches);
+        self.remove_all::<T>();
+        let attribute = value.to_attribute();
+        match position {
+            Some(position) => self.0.insert(position, attribute),
+            None => self.0.push(attribute),
+        }
+    }
+
+    /// direction returns the last direction property attribute, if any.
+    pub fn direction(&self) -> Option<Direction> {
+        self.get::<Direction>().filter_map(Result::ok).last()
+    }
+
+    /// set_direction replaces every direction property attribute with a single
+    /// one, kept at the position of the first one found.
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.replace(direction);
+    }
+}
+
+/// TypedAttribute is implemented by the types of well-known attributes so
+/// that they can be read from and written to Attributes. NAME is the
+/// attribute name, and the value is parsed with FromStr and written with
+/// Display. Attributes which don't follow the "a=`<name>`:`<value>`" form, like
+/// the direction property attributes, override the provided methods.
+pub trait TypedAttribute: FromStr + fmt::Display {
+    /// NAME is the name of the attribute.
+    const NAME: &'static str;
+
+    /// describe returns a short human-readable explanation of the value, used
+    /// by SessionDescription::annotate. It never includes secrets.
+    fn describe(&self) -> Option<String> {
+        None
+    }
+
+    /// matches returns true if the attribute is of this type.
+    fn matches(attribute: &Attribute) -> bool {
+        attribute.key == Self::NAME
+    }
+
+    /// from_attribute parses the attribute, which has to match.
+    fn from_attribute(attribute: &Attribute) -> Result<Self, Self::Err> {
+        attribute.value().unwrap_or_default().parse()
+    }
+
+    /// to_attribute writes the value as an attribute.
+    fn to_attribute(&self) -> Attribute {
+        Attribute::new(Self::NAME.to_owned(), Some(self.to_string()))
+    }
+}
+
+impl Extend<Attribute> for Attributes {
+    fn extend<I: IntoIterator<Item = Attribute>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+
+impl IntoIterator for Attributes {
+    type Item = Attribute;
+    type IntoIter = alloc::vec::IntoIter<Attribute>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Attributes {
+    type Item = &'a Attribute;
+    type IntoIter = core::slice::Iter<'a, Attribute>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// UnknownLine is a line of a type not defined by RFC 4566, e.g. "y=", as
+/// kept by Parser with UnknownLinePolicy::Keep.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnknownLine {
+    /// line_type is the type of the line, e.g. 'y'.
+    pub line_type: char,
+    /// value is the text after the "=".
+    pub value: String,
+    /// position is the number of other lines of its session or media
+    /// description it was found after, so that it is written back in the
+    /// same place. Positions past the last line, e.g. usize::MAX, put it at
+    /// the end.
+    pub position: usize,
+}
+
+impl fmt::Display for UnknownLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.line_type, self.value)
+    }
+}
+
+impl UnknownLine {
+    /// marshal_len_hint returns the length of the marshaled line, without
+    /// its line break.
+    pub fn marshal_len_hint(&self) -> usize {
+        self.line_type.len_utf8() + 1 + self.value.len()
+    }
+}
+
+/// UnknownLineWriter passes the lines of a session or media description
+/// through to w, writing each of its unknown lines before the line at its
+/// position. The unknown lines must be ordered by position.
+pub(crate) struct UnknownLineWriter<'a, W> {
+    w: &'a mut W,
+    unknown_lines: &'a [UnknownLine],
+    lines: usize,
+    line_start: bool,
+}
+
+impl<'a, W: fmt::Write> UnknownLineWriter<'a, W> {
+    pub(crate) fn new(w: &'a mut W, unknown_lines: &'a [UnknownLine]) -> Self {
+        UnknownLineWriter {
+            w,
+            unknown_lines,
+            lines: 0,
+            line_start: true,
+        }
+    }
+
+    /// finish writes the unknown lines positioned after the last line.
+    pub(crate) fn finish(mut self) -> fmt::Result {
+        self.write_unknown_lines(usize::MAX)
+    }
+
+    fn write_unknown_lines(&mut self, position: usize) -> fmt::Result {
+        while let Some((unknown_line, rest)) = self.unknown_lines.split_first() {
+            if unknown_line.position > position {
+                break;
+            }
+            write!(self.w, "{}{}", unknown_line, END_LINE)?;
+            self.unknown_lines = rest;
+        }
+        Ok(())
+    }
+}
+
+impl<W: fmt::Write> fmt::Write for UnknownLineWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.is_empty() {
+            return Ok(());
+        }
+        if self.line_start {
+            self.write_unknown_lines(self.lines)?;
+            self.line_start = false;
+        }
+        self.w.write_str(s)?;
+        if s.ends_with('\n') {
+            self.lines += 1;
+            self.line_start = true;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,657 @@
+use super::*;
+
+use core::convert::TryFrom;
+
+#[test]
+fn test_bandwidth_from_str() -> Result<(), Error> {
+    let passingtests = [
+        ("AS:128", false, "AS", 128),
+        ("CT:1000", false, "CT", 1000),
+        ("TIAS:256000", false, "TIAS", 256000),
+        ("X-YZ:300", true, "YZ", 300),
+        ("RR:0", false, "RR", 0),
+        ("AS:4294967295", false, "AS", 4294967295),
+        ("TIAS:10000000000", false, "TIAS", 10000000000),
+    ];
+
+    for (input, experimental, bandwidth_type, bandwidth) in passingtests.iter() {
+        let b = input.parse::<Bandwidth>()?;
+        assert_eq!(b.experimental, *experimental, "{}", input);
+        assert_eq!(b.bandwidth_type, *bandwidth_type, "{}", input);
+        assert_eq!(b.bandwidth, *bandwidth, "{}", input);
+        assert_eq!(b.to_string(), *input);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_bandwidth_from_str_errors() {
+    assert!(matches!(
+        "AS128".parse::<Bandwidth>(),
+        Err(Error::BandwidthMissingSeparator(_))
+    ));
+    assert!(matches!(
+        ":128".parse::<Bandwidth>(),
+        Err(Error::BandwidthInvalidType(_))
+    ));
+    assert!(matches!(
+        "X-:128".parse::<Bandwidth>(),
+        Err(Error::BandwidthInvalidType(_))
+    ));
+    assert!(matches!(
+        "A S:128".parse::<Bandwidth>(),
+        Err(Error::BandwidthInvalidType(_))
+    ));
+    assert!(matches!(
+        "AS:".parse::<Bandwidth>(),
+        Err(Error::BandwidthInvalidValue(_))
+    ));
+    assert!(matches!(
+        "AS:12k".parse::<Bandwidth>(),
+        Err(Error::BandwidthInvalidValue(_))
+    ));
+    assert!(matches!(
+        "AS:-1".parse::<Bandwidth>(),
+        Err(Error::BandwidthInvalidValue(_))
+    ));
+    assert!(matches!(
+        "AS:4294967296".parse::<Bandwidth>(),
+        Err(Error::BandwidthOverflow(_))
+    ));
+    assert!(matches!(
+        "X-TIAS:4294967296".parse::<Bandwidth>(),
+        Err(Error::BandwidthOverflow(_))
+    ));
+    assert!(matches!(
+        "TIAS:18446744073709551616".parse::<Bandwidth>(),
+        Err(Error::BandwidthOverflow(_))
+    ));
+}
+
+#[test]
+fn test_bandwidth_units() -> Result<(), Error> {
+    // Examples from https://tools.ietf.org/html/rfc3890#section-6
+    let tias = "TIAS:62200".parse::<Bandwidth>()?;
+    assert_eq!(tias.bits_per_second(), Some(62200));
+    assert_eq!(tias.kilobits_per_second(), Some(63));
+
+    let as_ = "AS:64".parse::<Bandwidth>()?;
+    assert_eq!(as_.bits_per_second(), Some(64000));
+    assert_eq!(as_.kilobits_per_second(), Some(64));
+
+    let ct = "CT:1000".parse::<Bandwidth>()?;
+    assert_eq!(ct.bits_per_second(), Some(1_000_000));
+
+    // RR and RS are in bits per second, unlike AS.
+    // https://tools.ietf.org/html/rfc3556#section-2
+    let rr = "RR:800".parse::<Bandwidth>()?;
+    assert_eq!(rr.kind(), Some(BandwidthKind::Rr));
+    assert_eq!(rr.bits_per_second(), Some(800));
+    assert_eq!(rr.kilobits_per_second(), Some(1));
+    let rs = "RS:0".parse::<Bandwidth>()?;
+    assert_eq!(rs.kind(), Some(BandwidthKind::Rs));
+    assert_eq!(rs.bits_per_second(), Some(0));
+    assert!(matches!(
+        "RS:4294967296".parse::<Bandwidth>(),
+        Err(Error::BandwidthOverflow(_))
+    ));
+
+    for unknown in ["X-RR:800", "X-YZ:300", "X-TIAS:300"] {
+        let b = unknown.parse::<Bandwidth>()?;
+        assert_eq!(b.bits_per_second(), None, "{}", unknown);
+        assert_eq!(b.kilobits_per_second(), None, "{}", unknown);
+    }
+
+    // 10 Gbps does not fit into u32 bits per second.
+    let ten_gbps = 10_000_000_000;
+    let tias = Bandwidth::tias_from_bps(ten_gbps);
+    assert_eq!(tias.to_string(), "TIAS:10000000000");
+    assert_eq!(tias.bits_per_second(), Some(ten_gbps));
+    assert_eq!(tias.kilobits_per_second(), Some(10_000_000));
+
+    let as_ = Bandwidth::as_from_bps(ten_gbps)?;
+    assert_eq!(as_.to_string(), "AS:10000000");
+    assert_eq!(Bandwidth::as_from_bps(62200)?.to_string(), "AS:63");
+    assert!(matches!(
+        Bandwidth::as_from_bps(u64::MAX),
+        Err(Error::BandwidthOverflow(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_bandwidth_new() -> Result<(), Error> {
+    let b = Bandwidth::new("X-cap-max", 64)?;
+    assert!(b.experimental);
+    assert_eq!(b.to_string(), "X-cap-max:64");
+
+    assert!(Bandwidth::new("", 64).is_err());
+    assert!(Bandwidth::new("AS", u64::from(u32::MAX) + 1).is_err());
+    assert!(Bandwidth::new("TIAS", u64::from(u32::MAX) + 1).is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_address_parse() -> Result<(), Error> {
+    let passingtests = [
+        ("IP4", "224.2.36.42/127", Some(127), None, true),
+        ("IP4", "224.2.36.42/127/3", Some(127), Some(3), true),
+        ("IP4", "203.0.113.1", None, None, false),
+        ("IP6", "FF15::101", None, None, true),
+        ("IP6", "FF15::101/3", None, Some(3), true),
+        ("IP6", "2001:db8::1", None, None, false),
+        ("IP4", "example.com", None, None, false),
+    ];
+
+    for (address_type, input, ttl, range, multicast) in passingtests.iter() {
+        let address = Address::parse(address_type, input)?;
+        assert_eq!(address.ttl, *ttl, "{}", input);
+        assert_eq!(address.range, *range, "{}", input);
+        assert_eq!(address.is_multicast(), *multicast, "{}", input);
+        assert_eq!(address.to_string(), *input);
+    }
+
+    let failingtests = [
+        ("IP4", "224.2.36.42"),
+        ("IP4", "224.2.36.42/256"),
+        ("IP4", "224.2.36.42/127/0"),
+        ("IP4", "224.2.36.42/127/3/1"),
+        ("IP4", "203.0.113.1/127"),
+        ("IP6", "FF15::101/127/3"),
+        ("IP6", "FF15::101/0"),
+        ("IP6", "2001:db8::1/3"),
+        ("IP4", ""),
+    ];
+    for (address_type, input) in failingtests.iter() {
+        assert!(Address::parse(address_type, input).is_err(), "{}", input);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_connection_information_from_str() -> Result<(), Error> {
+    let passingtests = [
+        "IN IP4 10.47.16.5",
+        "IN IP6 2001:db8::1",
+        "IN IP6 example.com",
+        "IN IP4 224.2.36.42/127/3",
+        "IN IP4",
+        "ATM NSAP 47.0005.80.ffe100.0000.f21a.6e4c.0020480e60c4.00",
+    ];
+
+    for input in passingtests.iter() {
+        let ci = input.parse::<ConnectionInformation>()?;
+        assert_eq!(ci.to_string(), *input);
+    }
+
+    let ci = "ATM NSAP 47.0005.80.ffe100.0000.f21a.6e4c.0020480e60c4.00"
+        .parse::<ConnectionInformation>()?;
+    assert_eq!(ci.network_type, NetType::Custom("ATM".to_owned()));
+
+    let ci = "IN IP6 example.com".parse::<ConnectionInformation>()?;
+    assert_eq!(ci.network_type, NetType::In);
+    assert_eq!(ci.address_type, ADDRESS_TYPE_IP6);
+    assert_eq!(ci.address.unwrap().address, "example.com");
+
+    assert!(matches!(
+        "IN IP4 ::1".parse::<ConnectionInformation>(),
+        Err(Error::SdpAddressTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        "IN IP6 10.47.16.5".parse::<ConnectionInformation>(),
+        Err(Error::SdpAddressTypeMismatch { .. })
+    ));
+    assert!("IN IP5 10.47.16.5"
+        .parse::<ConnectionInformation>()
+        .is_err());
+    assert!("IN".parse::<ConnectionInformation>().is_err());
+    assert!("IN IP4 10.47.16.5 extra"
+        .parse::<ConnectionInformation>()
+        .is_err());
+    // <nettype> and <addrtype> must be tokens, not just printable
+    assert!("A(M) NSAP 47.0005"
+        .parse::<ConnectionInformation>()
+        .is_err());
+    assert!("ATM NSAP 47.0005".parse::<ConnectionInformation>().is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_fqdn() {
+    let label63 = "a".repeat(63);
+    let label64 = "a".repeat(64);
+    let longest = format!("{0}.{0}.{0}.{1}", label63, "a".repeat(61));
+    assert_eq!(longest.len(), MAX_FQDN_LEN);
+    let too_long = format!("a{}", longest);
+
+    let passingtests = [
+        "localhost",
+        "host.example.com",
+        "host.example.com.",
+        "HOST.Example.COM",
+        "a-b.c0.example",
+        "xn--bcher-kva.example",
+        &label63,
+        &format!("{}.example.com", label63),
+        &longest,
+        &format!("{}.", longest),
+    ];
+    for input in passingtests.iter() {
+        assert!(validate_fqdn(input).is_ok(), "{:?}", input);
+    }
+
+    let failingtests = [
+        "",
+        ".",
+        "host..example.com",
+        "host.example.com..",
+        ".example.com",
+        "-host.example.com",
+        "host-.example.com",
+        "host.-example.com",
+        "host_name.example.com",
+        "host name.example.com",
+        "b\u{fc}cher.example",
+        "host.example.com\r\n",
+        &label64,
+        &format!("{}.example.com", label64),
+        &too_long,
+    ];
+    for input in failingtests.iter() {
+        assert!(
+            matches!(validate_fqdn(input), Err(Error::SdpInvalidFqdn(_))),
+            "{:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_address_fqdn() -> Result<(), Error> {
+    let address = Address::fqdn("IP4", "Host.Example.COM.")?;
+    assert_eq!(address.address, "host.example.com");
+    assert_eq!(address.to_string(), "host.example.com");
+    assert_eq!(address.as_domain(), Some("host.example.com"));
+    assert_eq!(address.as_ip(), None);
+    assert!(Address::fqdn("IP6", "xn--bcher-kva.example").is_ok());
+
+    assert!(matches!(
+        Address::fqdn("IP4", "-host.example.com"),
+        Err(Error::SdpInvalidFqdn(_))
+    ));
+    assert!(matches!(
+        Address::fqdn("IP4", "10.0.0.1"),
+        Err(Error::SdpInvalidFqdn(_))
+    ));
+    assert!(matches!(
+        Address::fqdn("NSAP", "host.example.com"),
+        Err(Error::SdpInvalidValue(_))
+    ));
+
+    // parsed addresses keep their spelling
+    let connection = "IN IP4 Host.Example.COM.".parse::<ConnectionInformation>()?;
+    let address = connection.address.as_ref().unwrap();
+    assert_eq!(connection.to_string(), "IN IP4 Host.Example.COM.");
+    assert_eq!(address.as_domain(), Some("Host.Example.COM"));
+
+    let address = Address::parse("IP6", "FF15::101/3")?;
+    assert_eq!(address.as_ip(), Some("ff15::101".parse().unwrap()));
+    assert_eq!(address.as_domain(), None);
+
+    for input in [
+        "IN IP4 host_name.example.com",
+        "IN IP6 -host",
+        "IN IP4 b\u{fc}cher",
+    ]
+    .iter()
+    {
+        assert!(
+            matches!(
+                input.parse::<ConnectionInformation>(),
+                Err(Error::SdpInvalidFqdn(_))
+            ),
+            "{:?}",
+            input
+        );
+    }
+    // other network types are not checked
+    assert!("ATM NSAP 47.0005.80ffe1"
+        .parse::<ConnectionInformation>()
+        .is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_attribute_from_str() -> Result<(), Error> {
+    let passingtests = [
+        ("recvonly", "recvonly", None),
+        (
+            "fmtp:96 profile-level-id=42e01f",
+            "fmtp",
+            Some("96 profile-level-id=42e01f"),
+        ),
+        (
+            "fingerprint:sha-256 AB:CD:EF",
+            "fingerprint",
+            Some("sha-256 AB:CD:EF"),
+        ),
+        ("tool:", "tool", Some("")),
+    ];
+
+    for (input, name, value) in passingtests.iter() {
+        let attribute = input.parse::<Attribute>()?;
+        assert_eq!(attribute.name(), *name);
+        assert_eq!(attribute.value(), *value);
+        assert_eq!(attribute.is_property(), value.is_none());
+        assert_eq!(attribute.to_string(), *input);
+    }
+
+    let failingtests = [
+        ":value",
+        "",
+        "rec vonly",
+        "bad\u{e9}name",
+        "tool:a\r\nb",
+        "tool:a\nb",
+    ];
+    for input in failingtests.iter() {
+        assert!(
+            input.parse::<Attribute>().is_err(),
+            "expected `{}` to be rejected",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_attributes() -> Result<(), Error> {
+    let mut attributes = Attributes::default();
+    assert!(attributes.is_empty());
+
+    for line in [
+        "rtpmap:111 opus/48000/2",
+        "sendrecv",
+        "rtpmap:96 VP8/90000",
+        "rtcp-fb:96 nack",
+        "rtpmap:97 rtx/90000",
+    ] {
+        attributes.push(line.parse()?);
+    }
+
+    assert_eq!(attributes.len(), 5);
+    assert!(attributes.has("sendrecv"));
+    assert!(!attributes.has("recvonly"));
+    assert_eq!(
+        attributes.get_first("rtpmap").and_then(|a| a.value()),
+        Some("111 opus/48000/2")
+    );
+    assert!(attributes.get_first("fmtp").is_none());
+
+    let rtpmaps: Vec<&str> = attributes
+        .get_all("rtpmap")
+        .filter_map(|a| a.value())
+        .collect();
+    assert_eq!(
+        rtpmaps,
+        vec!["111 opus/48000/2", "96 VP8/90000", "97 rtx/90000"]
+    );
+    assert_eq!(attributes.get_all("fmtp").count(), 0);
+
+    let names: Vec<&str> = attributes.iter().map(|a| a.name()).collect();
+    assert_eq!(
+        names,
+        vec!["rtpmap", "sendrecv", "rtpmap", "rtcp-fb", "rtpmap"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_bandwidth_type() -> Result<(), Error> {
+    let bandwidth_type = "TIAS".parse::<BandwidthType>()?;
+    assert_eq!(bandwidth_type, BANDWIDTH_TYPE_TIAS);
+    assert_eq!(BandwidthType::try_from("AS")?.as_str(), "AS");
+    assert_eq!(BandwidthType::try_from("CT".to_owned())?.to_string(), "CT");
+
+    // Any token is a valid bwtype, including ones with characters beyond
+    // letters, digits, "-", "_" and "." which used to be rejected.
+    for input in ["cap-max", "v1.0", "a!b", "{~}", "x'y"].iter() {
+        assert_eq!(input.parse::<BandwidthType>()?, *input);
+        let b = format!("X-{}:64", input).parse::<Bandwidth>()?;
+        assert!(b.experimental);
+        assert_eq!(b.to_string(), format!("X-{}:64", input));
+    }
+
+    let failingtests = ["", "AS:", "AS\r\nk=clear:secret", "A S", "AS\n"];
+    for input in failingtests.iter() {
+        assert!(
+            matches!(
+                input.parse::<BandwidthType>(),
+                Err(Error::BandwidthInvalidType(_))
+            ),
+            "{:?}",
+            input
+        );
+        assert!(BandwidthType::try_from(*input).is_err(), "{:?}", input);
+        assert!(
+            BandwidthType::try_from(input.to_string()).is_err(),
+            "{:?}",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_string_types_reject_line_breaks() {
+    for c in ['\r', '\n', '\0'].iter() {
+        let value = format!("96 VP8/90000{}c=IN IP4 10.0.0.1", c);
+        assert!(
+            matches!(
+                Attribute::try_new("rtpmap", Some(&value)),
+                Err(Error::SdpInvalidCharacter(_))
+            ),
+            "{:?}",
+            value
+        );
+        assert!(format!("rtpmap:{}", value).parse::<Attribute>().is_err());
+        assert!(Attribute::try_new(&format!("rtp{}map", c), None).is_err());
+
+        let bandwidth_type = format!("AS{}k=prompt", c);
+        assert!(bandwidth_type.parse::<BandwidthType>().is_err());
+
+        let domain = format!("example{}.com", c);
+        assert!(matches!(
+            Address::parse("IP4", &domain),
+            Err(Error::SdpInvalidCharacter(_))
+        ));
+        assert!(format!("IN IP4 {}", domain)
+            .parse::<ConnectionInformation>()
+            .is_err());
+    }
+}
+
+#[test]
+fn test_encryption_key_from_str() -> Result<(), Error> {
+    let passingtests = [
+        "prompt",
+        "clear:correct horse",
+        "base64:c2VjcmV0a2V5",
+        "base64:c2VjcmV0IGtleQ==",
+        "uri:https://example.com/keys/1",
+    ];
+
+    for input in passingtests {
+        let key = input.parse::<EncryptionKey>()?;
+        assert_eq!(key.to_string(), input);
+    }
+
+    assert_eq!(
+        "clear:s3cret".parse::<EncryptionKey>()?.expose().material(),
+        Some("s3cret")
+    );
+    assert_eq!("prompt".parse::<EncryptionKey>()?.expose().material(), None);
+
+    for input in ["rot13:secret", "prompt:secret", "clear", "", "CLEAR:secret"] {
+        assert!(
+            matches!(
+                input.parse::<EncryptionKey>(),
+                Err(Error::SdpUnknownKeyMethod(_))
+            ),
+            "{}",
+            input
+        );
+    }
+
+    for input in ["base64:", "base64:c2VjcmV0!!", "base64:abc", "base64:a==="] {
+        assert!(
+            matches!(
+                input.parse::<EncryptionKey>(),
+                Err(Error::SdpInvalidBase64Key)
+            ),
+            "{}",
+            input
+        );
+    }
+
+    assert!("uri:not a uri".parse::<EncryptionKey>().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_encryption_key_debug_is_redacted() -> Result<(), Error> {
+    let clear = "clear:hunter2".parse::<EncryptionKey>()?;
+    assert_eq!(format!("{:?}", clear), "EncryptionKey::Clear(REDACTED)");
+    assert_eq!(
+        format!("{:?}", Some(&clear)),
+        "Some(EncryptionKey::Clear(REDACTED))"
+    );
+    assert_eq!(
+        format!("{:?}", clear.expose()),
+        "EncryptionKey::Clear(\"hunter2\")"
+    );
+
+    let base64 = "base64:c2VjcmV0a2V5".parse::<EncryptionKey>()?;
+    let debug = format!("{:?}", base64);
+    assert_eq!(debug, "EncryptionKey::Base64(REDACTED)");
+    assert!(!debug.contains("c2VjcmV0a2V5"));
+
+    let sdp = "v=0\r\no=- 0 0 IN IP4 0.0.0.0\r\ns=-\r\nt=0 0\r\nk=clear:hunter2\r\n\
+               m=audio 9 RTP/AVP 0\r\nk=base64:c2VjcmV0a2V5\r\n";
+    let sd = sdp.parse::<crate::session_description::SessionDescription>()?;
+    let debug = format!("{:?}", sd);
+    assert!(!debug.contains("hunter2"), "{}", debug);
+    assert!(!debug.contains("c2VjcmV0a2V5"), "{}", debug);
+    assert!(sd.marshal().contains("k=clear:hunter2\r\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_encryption_key_base64_bytes() -> Result<(), Error> {
+    let keys: [&[u8]; 4] = [
+        &[0x00, 0x01, 0xfe, 0xff, 0x80, 0x7f],
+        &[0xde, 0xad, 0xbe, 0xef],
+        &[0xde, 0xad, 0xbe, 0xef, 0x00],
+        &[0xff],
+    ];
+
+    for key in keys.iter() {
+        let encoded = EncryptionKey::base64_from_bytes(key);
+        let parsed = encoded.to_string().parse::<EncryptionKey>()?;
+        assert_eq!(parsed, encoded);
+        assert_eq!(parsed.decoded_bytes()?, key.to_vec());
+    }
+
+    // One and two padding characters.
+    assert_eq!(
+        EncryptionKey::base64_from_bytes(&[0xde, 0xad, 0xbe, 0xef, 0x00]).to_string(),
+        "base64:3q2+7wA="
+    );
+    assert_eq!(
+        EncryptionKey::base64_from_bytes(&[0xde, 0xad, 0xbe, 0xef]).to_string(),
+        "base64:3q2+7w=="
+    );
+
+    // Non-canonical: the unused bits of the last character are set.
+    assert!(matches!(
+        "base64:3q2+7x==".parse::<EncryptionKey>(),
+        Err(Error::SdpInvalidBase64Key)
+    ));
+    assert!(matches!(
+        "clear:secret".parse::<EncryptionKey>()?.decoded_bytes(),
+        Err(Error::SdpKeyNotBase64(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_typed_attributes() -> Result<(), Error> {
+    use crate::direction::Direction;
+    use crate::extmap::ExtMap;
+    use crate::fmtp::Fmtp;
+    use crate::maxprate::MaxPRate;
+    use crate::rtpmap::RtpMap;
+
+    let mut attributes = Attributes::default();
+    for line in [
+        "mid:0",
+        "extmap:1 urn:ietf:params:rtp-hdrext:sdes:mid",
+        "sendonly",
+        "rtpmap:111 opus/48000/2",
+        "rtpmap:200 broken/8000",
+        "fmtp:111 minptime=10;useinbandfec=1",
+        "x-proprietary:whatever",
+        "rtpmap:126 telephone-event/8000",
+        "recvonly",
+    ] {
+        attributes.push(line.parse()?);
+    }
+
+    let rtpmaps: Vec<Result<RtpMap, Error>> = attributes.get::<RtpMap>().collect();
+    assert_eq!(rtpmaps.len(), 3);
+    assert_eq!(rtpmaps[0].as_ref().map(|r| r.payload_type).ok(), Some(111));
+    assert!(matches!(rtpmaps[1], Err(Error::SdpInvalidRtpMap(_))));
+    assert_eq!(rtpmaps[2].as_ref().map(|r| r.payload_type).ok(), Some(126));
+
+    assert_eq!(attributes.get::<ExtMap>().count(), 1);
+    assert_eq!(attributes.get::<Fmtp>().count(), 1);
+    assert_eq!(attributes.get::<MaxPRate>().count(), 0);
+    let directions: Vec<Direction> = attributes.get::<Direction>().collect::<Result<_, _>>()?;
+    assert_eq!(directions, vec![Direction::SendOnly, Direction::RecvOnly]);
+
+    attributes.insert("20".parse::<MaxPRate>()?);
+    assert_eq!(
+        attributes.iter().last().map(|a| a.to_string()),
+        Some("maxprate:20".to_owned())
+    );
+
+    attributes.replace("96 VP8/90000".parse::<RtpMap>()?);
+    attributes.replace(Direction::Inactive);
+    attributes.remove_all::<Fmtp>();
+    let lines: Vec<String> = attributes.iter().map(|a| a.to_string()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "mid:0",
+            "extmap:1 urn:ietf:params:rtp-hdrext:sdes:mid",
+            "inactive",
+            "rtpmap:96 VP8/90000",
+            "x-proprietary:whatever",
+            "maxprate:20",
+        ]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use super::alloc_prelude::*;
+use super::common_description::TypedAttribute;
+use super::direction::Direction;
+use super::rtcpfb::{RtcpFeedback, RtcpFeedbackType};
+use super::session_description::SessionDescription;
+use super::ssrc::Ssrc;
+use super::util::LenCounter;
+
+#[cfg(test)]
+mod compact_test;
+
+/// CompactOptions selects the size reductions of SessionDescription::compact.
+/// Each of them keeps the description valid and doesn't change what is
+/// negotiated; they only drop lines which peers can do without.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompactOptions {
+    /// ssrc_cname_only drops the "a=ssrc:" attributes other than cname, such
+    /// as the legacy msid, mslabel and label ones.
+    pub ssrc_cname_only: bool,
+    /// rtcp_feedback, if set, keeps only the "a=rtcp-fb:" attributes of these
+    /// feedback types. Malformed ones are kept.
+    pub rtcp_feedback: Option<Vec<RtcpFeedbackType>>,
+    /// dedup_connection_information drops the "c=" fields of media
+    /// descriptions which are the same as the session level one.
+    pub dedup_connection_information: bool,
+    /// omit_default_direction drops "a=sendrecv", which is the default,
+    /// wherever it doesn't override another direction.
+    pub omit_default_direction: bool,
+}
+
+impl SessionDescription {
+    /// serialized_len returns the exact length of the marshaled session
+    /// description, by counting what would be written.
+    pub fn serialized_len(&self) -> usize {
+        let mut counter = LenCounter::default();
+        // Counting never fails.
+        let _ = self.write_to(&mut counter);
+        counter.0
+    }
+
+    /// compact applies the size reductions selected in options.
+    pub fn compact(&mut self, options: &CompactOptions) {
+        let session_connection_information = self.connection_information.clone();
+        let session_direction = self.attributes.direction();
+        let omit_media_direction = options.omit_default_direction
+            && session_direction.unwrap_or_default() == Direction::SendRecv;
+
+        if options.omit_default_direction && session_direction == Some(Direction::SendRecv) {
+            self.attributes.remove_all::<Direction>();
+        }
+
+        for md in &mut self.media_descriptions {
+            if options.ssrc_cname_only {
+                md.attributes.0.retain(|a| {
+                    !Ssrc::matches(a)
+                        || Ssrc::from_attribute(a).map_or(true, |s| s.attribute == "cname")
+                });
+            }
+            if let Some(allowed) = &options.rtcp_feedback {
+                md.attributes.0.retain(|a| {
+                    !RtcpFeedback::matches(a)
+                        || RtcpFeedback::from_attribute(a)
+                            .map_or(true, |fb| allowed.contains(&fb.feedback))
+                });
+            }
+            if options.dedup_connection_information
+                && session_connection_information.is_some()
+                && md.connection_information == session_connection_information
+            {
+                md.connection_information = None;
+            }
+            if omit_media_direction && md.direction() == Some(Direction::SendRecv) {
+                md.attributes.remove_all::<Direction>();
+            }
+        }
+    }
+
+    /// marshal_compact marshals a copy of the session description compacted
+    /// with the given options.
+    pub fn marshal_compact(&self, options: &CompactOptions) -> String {
+        let mut compacted = self.clone();
+        compacted.compact(options);
+        compacted.marshal()
+    }
+}
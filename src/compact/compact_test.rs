@@ -0,0 +1,249 @@
+use super::*;
+use crate::diagnostic::Severity;
+use crate::error::Error;
+use crate::session_description::SessionName;
+
+const SIMULCAST_OFFER_SDP: &str = "v=0\r\n\
+o=- 8137256412834467390 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+c=IN IP4 0.0.0.0\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0 1\r\n\
+a=extmap-allow-mixed\r\n\
+a=msid-semantic: WMS stream\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111 63 9 0 8 13 110 126\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=rtcp:9 IN IP4 0.0.0.0\r\n\
+a=ice-ufrag:Gh2c\r\n\
+a=ice-pwd:3zUiRr5gHbS0aBkPQVvvDbQy\r\n\
+a=ice-options:trickle\r\n\
+a=fingerprint:sha-256 8F:5A:1C:0E:65:3B:7D:14:0C:57:3A:31:DD:5D:80:D7:1E:70:F5:87:6C:5C:31:E2:0A:EE:D8:B8:6B:63:17:3E\r\n\
+a=setup:actpass\r\n\
+a=mid:0\r\n\
+a=extmap:1 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r\n\
+a=extmap:2 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time\r\n\
+a=extmap:3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01\r\n\
+a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=sendrecv\r\n\
+a=msid:stream track-audio\r\n\
+a=rtcp-mux\r\n\
+a=rtpmap:111 opus/48000/2\r\n\
+a=rtcp-fb:111 transport-cc\r\n\
+a=fmtp:111 minptime=10;useinbandfec=1\r\n\
+a=rtpmap:63 red/48000/2\r\n\
+a=fmtp:63 111/111\r\n\
+a=rtpmap:9 G722/8000\r\n\
+a=rtpmap:0 PCMU/8000\r\n\
+a=rtpmap:8 PCMA/8000\r\n\
+a=rtpmap:13 CN/8000\r\n\
+a=rtpmap:110 telephone-event/48000\r\n\
+a=rtpmap:126 telephone-event/8000\r\n\
+a=ssrc:1001 cname:4TOk42mSjXCkVIa6\r\n\
+a=ssrc:1001 msid:stream track-audio\r\n\
+a=ssrc:1001 mslabel:stream\r\n\
+a=ssrc:1001 label:track-audio\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96 97 98 99 100 101 102 103 104 105\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=rtcp:9 IN IP4 0.0.0.0\r\n\
+a=ice-ufrag:Gh2c\r\n\
+a=ice-pwd:3zUiRr5gHbS0aBkPQVvvDbQy\r\n\
+a=ice-options:trickle\r\n\
+a=fingerprint:sha-256 8F:5A:1C:0E:65:3B:7D:14:0C:57:3A:31:DD:5D:80:D7:1E:70:F5:87:6C:5C:31:E2:0A:EE:D8:B8:6B:63:17:3E\r\n\
+a=setup:actpass\r\n\
+a=mid:1\r\n\
+a=extmap:14 urn:ietf:params:rtp-hdrext:toffset\r\n\
+a=extmap:2 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time\r\n\
+a=extmap:13 urn:3gpp:video-orientation\r\n\
+a=extmap:3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01\r\n\
+a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=extmap:10 urn:ietf:params:rtp-hdrext:sdes:rtp-stream-id\r\n\
+a=extmap:11 urn:ietf:params:rtp-hdrext:sdes:repaired-rtp-stream-id\r\n\
+a=sendrecv\r\n\
+a=msid:stream track-video\r\n\
+a=rtcp-mux\r\n\
+a=rtcp-rsize\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+a=rtcp-fb:96 goog-remb\r\n\
+a=rtcp-fb:96 transport-cc\r\n\
+a=rtcp-fb:96 ccm fir\r\n\
+a=rtcp-fb:96 nack\r\n\
+a=rtcp-fb:96 nack pli\r\n\
+a=rtpmap:97 rtx/90000\r\n\
+a=fmtp:97 apt=96\r\n\
+a=rtpmap:98 VP9/90000\r\n\
+a=rtcp-fb:98 goog-remb\r\n\
+a=rtcp-fb:98 transport-cc\r\n\
+a=rtcp-fb:98 ccm fir\r\n\
+a=rtcp-fb:98 nack\r\n\
+a=rtcp-fb:98 nack pli\r\n\
+a=fmtp:98 profile-id=0\r\n\
+a=rtpmap:99 rtx/90000\r\n\
+a=fmtp:99 apt=98\r\n\
+a=rtpmap:100 H264/90000\r\n\
+a=rtcp-fb:100 goog-remb\r\n\
+a=rtcp-fb:100 transport-cc\r\n\
+a=rtcp-fb:100 ccm fir\r\n\
+a=rtcp-fb:100 nack\r\n\
+a=rtcp-fb:100 nack pli\r\n\
+a=fmtp:100 level-asymmetry-allowed=1;packetization-mode=1;profile-level-id=42e01f\r\n\
+a=rtpmap:101 rtx/90000\r\n\
+a=fmtp:101 apt=100\r\n\
+a=rtpmap:102 AV1/90000\r\n\
+a=rtcp-fb:102 goog-remb\r\n\
+a=rtcp-fb:102 transport-cc\r\n\
+a=rtcp-fb:102 ccm fir\r\n\
+a=rtcp-fb:102 nack\r\n\
+a=rtcp-fb:102 nack pli\r\n\
+a=rtpmap:103 rtx/90000\r\n\
+a=fmtp:103 apt=102\r\n\
+a=rtpmap:104 red/90000\r\n\
+a=rtpmap:105 ulpfec/90000\r\n\
+a=rid:q send\r\n\
+a=rid:h send\r\n\
+a=rid:f send\r\n\
+a=simulcast:send q;h;f\r\n";
+
+fn offer() -> Result<SessionDescription, Error> {
+    SIMULCAST_OFFER_SDP.parse()
+}
+
+fn all_options() -> CompactOptions {
+    CompactOptions {
+        ssrc_cname_only: true,
+        rtcp_feedback: Some(vec![
+            RtcpFeedbackType::TransportCc,
+            RtcpFeedbackType::Nack,
+            RtcpFeedbackType::NackPli,
+        ]),
+        dedup_connection_information: true,
+        omit_default_direction: true,
+    }
+}
+
+#[test]
+fn test_serialized_len() -> Result<(), Error> {
+    let offer = offer()?;
+    assert_eq!(offer.serialized_len(), SIMULCAST_OFFER_SDP.len());
+
+    // Unlike marshal_len_hint, it is exact for text which isn't UTF-8.
+    let mut sd = offer.clone();
+    sd.session_name = SessionName::from_bytes(b"caf\xe9")?;
+    assert_eq!(sd.serialized_len(), sd.marshal().len());
+    assert_ne!(sd.marshal_len_hint(), sd.serialized_len());
+
+    Ok(())
+}
+
+#[test]
+fn test_compact_options() -> Result<(), Error> {
+    let offer = offer()?;
+    let size = offer.serialized_len();
+
+    // (options, removed lines)
+    let tests = [
+        (
+            CompactOptions {
+                ssrc_cname_only: true,
+                ..Default::default()
+            },
+            vec![
+                "a=ssrc:1001 msid:stream track-audio",
+                "a=ssrc:1001 mslabel:stream",
+                "a=ssrc:1001 label:track-audio",
+            ],
+        ),
+        (
+            CompactOptions {
+                rtcp_feedback: all_options().rtcp_feedback,
+                ..Default::default()
+            },
+            vec![
+                "a=rtcp-fb:96 goog-remb",
+                "a=rtcp-fb:96 ccm fir",
+                "a=rtcp-fb:98 goog-remb",
+                "a=rtcp-fb:98 ccm fir",
+                "a=rtcp-fb:100 goog-remb",
+                "a=rtcp-fb:100 ccm fir",
+                "a=rtcp-fb:102 goog-remb",
+                "a=rtcp-fb:102 ccm fir",
+            ],
+        ),
+        (
+            CompactOptions {
+                dedup_connection_information: true,
+                ..Default::default()
+            },
+            vec!["c=IN IP4 0.0.0.0", "c=IN IP4 0.0.0.0"],
+        ),
+        (
+            CompactOptions {
+                omit_default_direction: true,
+                ..Default::default()
+            },
+            vec!["a=sendrecv", "a=sendrecv"],
+        ),
+    ];
+
+    for (options, removed) in tests.iter() {
+        let compacted = offer.marshal_compact(options);
+        let removed_len: usize = removed.iter().map(|line| line.len() + 2).sum();
+        assert_eq!(compacted.len(), size - removed_len, "{:?}", options);
+
+        let parsed = compacted.parse::<SessionDescription>()?;
+        assert!(offer.diff(&parsed).is_empty(), "{:?}", options);
+        assert!(parsed.validate().is_empty(), "{:?}", parsed.validate());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_compact_simulcast_offer() -> Result<(), Error> {
+    let offer = offer()?;
+    let compacted = offer.marshal_compact(&all_options());
+    assert!(
+        compacted.len() * 10 < offer.serialized_len() * 9,
+        "{} of {}",
+        compacted.len(),
+        offer.serialized_len()
+    );
+
+    let parsed = compacted.parse::<SessionDescription>()?;
+    assert!(offer.diff(&parsed).is_empty());
+    assert!(parsed.validate().is_empty(), "{:?}", parsed.validate());
+    assert_eq!(parsed.media_descriptions[0].ssrcs().len(), 1);
+    assert_eq!(parsed.media_descriptions[1].rtcp_feedback_for(96).len(), 3);
+    assert_eq!(parsed.media_descriptions[1].rids().len(), 3);
+
+    // Compacting again changes nothing.
+    let mut again = parsed.clone();
+    again.compact(&all_options());
+    assert_eq!(again, parsed);
+
+    // Without options nothing is removed.
+    assert_eq!(
+        offer.marshal_compact(&CompactOptions::default()),
+        SIMULCAST_OFFER_SDP
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_compact_keeps_overriding_direction() -> Result<(), Error> {
+    let mut sd = offer()?;
+    sd.attributes.set_direction(Direction::RecvOnly);
+    sd.media_descriptions[1].connection_information = Some("IN IP4 192.0.2.1".parse()?);
+    sd.compact(&all_options());
+
+    assert_eq!(sd.attributes.direction(), Some(Direction::RecvOnly));
+    assert_eq!(
+        sd.media_descriptions[0].direction(),
+        Some(Direction::SendRecv)
+    );
+    assert!(sd.media_descriptions[0].connection_information.is_none());
+    assert!(sd.media_descriptions[1].connection_information.is_some());
+    assert!(!sd.validate().iter().any(|d| d.severity == Severity::Error));
+
+    Ok(())
+}
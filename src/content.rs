@@ -0,0 +1,150 @@
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::TypedAttribute;
+use super::error::Error;
+use super::util::impl_string_newtype;
+use super::util::token::is_token;
+
+#[cfg(test)]
+mod content_test;
+
+/// Label is the "a=label:" attribute, an identifier of a media description
+/// which other protocols can refer to. Unlike mids, labels are not required
+/// to be unique.
+/// <https://tools.ietf.org/html/rfc4574#section-4>
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Label(String);
+
+impl Label {
+    /// pointer = token
+    pub fn try_new(value: &str) -> Result<Self, Error> {
+        validate_label(value)?;
+        Ok(Label(value.to_owned()))
+    }
+
+    /// as_str returns the label.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl_string_newtype!(Label(String), validate_label);
+
+fn validate_label(value: &str) -> Result<(), Error> {
+    if !is_token(value) {
+        return Err(Error::SdpInvalidLabel(value.to_owned()));
+    }
+    Ok(())
+}
+
+impl TypedAttribute for Label {
+    const NAME: &'static str = "label";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("label `{}`", self.0))
+    }
+}
+
+/// ContentValue is one of the values of an "a=content:" attribute. Values
+/// without a variant are kept as Other.
+/// <https://tools.ietf.org/html/rfc4796#section-5>
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ContentValue {
+    /// Slides is a presentation.
+    Slides,
+    /// Speaker is the image of the speaker.
+    Speaker,
+    /// Sl is sign language.
+    Sl,
+    /// Main is the main media, e.g. the camera of a room.
+    Main,
+    /// Alt is an alternative to the main media.
+    Alt,
+    /// Other is any other content, kept as written.
+    Other(String),
+}
+
+impl fmt::Display for ContentValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ContentValue::Slides => "slides",
+            ContentValue::Speaker => "speaker",
+            ContentValue::Sl => "sl",
+            ContentValue::Main => "main",
+            ContentValue::Alt => "alt",
+            ContentValue::Other(value) => value,
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for ContentValue {
+    type Err = Error;
+
+    /// mediacnt = "slides" / "speaker" / "sl" / "main" / "alt" / mediacnt-ext
+    /// mediacnt-ext = token
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(match value {
+            "slides" => ContentValue::Slides,
+            "speaker" => ContentValue::Speaker,
+            "sl" => ContentValue::Sl,
+            "main" => ContentValue::Main,
+            "alt" => ContentValue::Alt,
+            _ if is_token(value) => ContentValue::Other(value.to_owned()),
+            _ => return Err(Error::SdpInvalidContent(value.to_owned())),
+        })
+    }
+}
+
+/// Content is the "a=content:" attribute, telling what a media description
+/// carries. It has at least one value.
+/// `a=content:<mediacnt>[,<mediacnt>]*`
+/// <https://tools.ietf.org/html/rfc4796#section-5>
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Content(pub Vec<ContentValue>);
+
+impl Content {
+    /// contains reports whether the content includes value.
+    pub fn contains(&self, value: &ContentValue) -> bool {
+        self.0.contains(value)
+    }
+}
+
+impl fmt::Display for Content {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, value) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Content {
+    type Err = Error;
+
+    /// Values are separated by commas only; whitespace makes a value
+    /// invalid, as it isn't a token character.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        value
+            .split(',')
+            .map(|v| {
+                v.parse()
+                    .map_err(|_| Error::SdpInvalidContent(value.to_owned()))
+            })
+            .collect::<Result<_, _>>()
+            .map(Content)
+    }
+}
+
+impl TypedAttribute for Content {
+    const NAME: &'static str = "content";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("content: {}", self))
+    }
+}
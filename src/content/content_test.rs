@@ -0,0 +1,116 @@
+use super::*;
+use crate::session_description::SessionDescription;
+
+#[test]
+fn test_label() -> Result<(), Error> {
+    let label = "1".parse::<Label>()?;
+    assert_eq!(label.as_str(), "1");
+    assert_eq!(label.to_string(), "1");
+    assert_eq!("a-b.c".parse::<Label>()?.as_str(), "a-b.c");
+
+    for value in ["", "a b", "a,b:c", "a\tb"].iter() {
+        assert!(value.parse::<Label>().is_err(), "{:?}", value);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_content() -> Result<(), Error> {
+    let tests = [
+        ("slides", vec![ContentValue::Slides]),
+        ("speaker", vec![ContentValue::Speaker]),
+        (
+            "main,alt,sl",
+            vec![ContentValue::Main, ContentValue::Alt, ContentValue::Sl],
+        ),
+        (
+            "slides,x-whiteboard",
+            vec![
+                ContentValue::Slides,
+                ContentValue::Other("x-whiteboard".to_owned()),
+            ],
+        ),
+        // Values are case-sensitive.
+        ("Main", vec![ContentValue::Other("Main".to_owned())]),
+    ];
+    for (value, expected) in tests.iter() {
+        let content = value.parse::<Content>()?;
+        assert_eq!(&content.0, expected, "{}", value);
+        assert_eq!(content.to_string(), *value);
+    }
+
+    for value in [
+        "",
+        ",",
+        "slides,",
+        ",slides",
+        "slides,,main",
+        "slides, main",
+        " slides",
+    ]
+    .iter()
+    {
+        assert!(value.parse::<Content>().is_err(), "{:?}", value);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_media_by_label_and_content() -> Result<(), Error> {
+    // The RFC 4796 example, with the label of the second video reused.
+    // https://tools.ietf.org/html/rfc4796#section-6
+    let sd = "v=0\r\n\
+o=Alice 292742730 29277831 IN IP4 131.163.72.4\r\n\
+s=The Funky Flow\r\n\
+c=IN IP4 131.164.74.2\r\n\
+t=0 0\r\n\
+m=video 6000 RTP/AVP 31\r\n\
+b=AS:1000\r\n\
+a=content:main\r\n\
+a=label:1\r\n\
+m=video 6002 RTP/AVP 31\r\n\
+b=AS:1000\r\n\
+a=content:slides,speaker\r\n\
+a=label:2\r\n\
+m=audio 6004 RTP/AVP 0\r\n\
+a=content:speaker\r\n\
+a=label:2\r\n\
+m=audio 6006 RTP/AVP 0\r\n\
+a=content:sl,\r\n"
+        .parse::<SessionDescription>()?;
+
+    let ports = |media: Vec<&crate::media_description::MediaDescription>| {
+        media
+            .iter()
+            .map(|md| md.media_name.port.value)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(ports(sd.media_by_label("1").collect()), vec![6000]);
+    assert_eq!(ports(sd.media_by_label("2").collect()), vec![6002, 6004]);
+    assert_eq!(ports(sd.media_by_label("3").collect()), Vec::<u16>::new());
+
+    assert_eq!(
+        ports(sd.media_with_content(ContentValue::Speaker).collect()),
+        vec![6002, 6004]
+    );
+    assert_eq!(
+        ports(sd.media_with_content(ContentValue::Main).collect()),
+        vec![6000]
+    );
+    // The malformed attribute of the last media description is skipped.
+    assert_eq!(
+        ports(sd.media_with_content(ContentValue::Sl).collect()),
+        Vec::<u16>::new()
+    );
+
+    assert_eq!(sd.media_descriptions[0].label(), Some(Label::try_new("1")?));
+    assert_eq!(
+        sd.media_descriptions[1].content(),
+        Some(Content(vec![ContentValue::Slides, ContentValue::Speaker]))
+    );
+
+    Ok(())
+}
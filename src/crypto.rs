@@ -0,0 +1,359 @@
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::TypedAttribute;
+use super::error::Error;
+use super::util::{base64_decode, base64_encode};
+
+#[cfg(test)]
+mod crypto_test;
+
+/// CryptoSuite is the SRTP crypto suite of an "a=crypto:" attribute. Suites
+/// without a variant are kept as Other.
+/// <https://tools.ietf.org/html/rfc4568#section-6.2>
+/// <https://tools.ietf.org/html/rfc7714#section-14.2>
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CryptoSuite {
+    /// AesCm128HmacSha1_80 is "AES_CM_128_HMAC_SHA1_80".
+    AesCm128HmacSha1_80,
+    /// AesCm128HmacSha1_32 is "AES_CM_128_HMAC_SHA1_32".
+    AesCm128HmacSha1_32,
+    /// Aes256CmHmacSha1_80 is "AES_256_CM_HMAC_SHA1_80".
+    Aes256CmHmacSha1_80,
+    /// AeadAes128Gcm is "AEAD_AES_128_GCM".
+    AeadAes128Gcm,
+    /// Other is any other suite, kept as written.
+    Other(String),
+}
+
+impl CryptoSuite {
+    /// key_salt_len returns the length in bytes of the master key and salt
+    /// which make up the inline key of the suite, or None when unknown.
+    pub fn key_salt_len(&self) -> Option<usize> {
+        match self {
+            CryptoSuite::AesCm128HmacSha1_80 | CryptoSuite::AesCm128HmacSha1_32 => Some(16 + 14),
+            CryptoSuite::Aes256CmHmacSha1_80 => Some(32 + 14),
+            CryptoSuite::AeadAes128Gcm => Some(16 + 12),
+            CryptoSuite::Other(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for CryptoSuite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            CryptoSuite::AesCm128HmacSha1_80 => "AES_CM_128_HMAC_SHA1_80",
+            CryptoSuite::AesCm128HmacSha1_32 => "AES_CM_128_HMAC_SHA1_32",
+            CryptoSuite::Aes256CmHmacSha1_80 => "AES_256_CM_HMAC_SHA1_80",
+            CryptoSuite::AeadAes128Gcm => "AEAD_AES_128_GCM",
+            CryptoSuite::Other(suite) => suite,
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for CryptoSuite {
+    type Err = Error;
+
+    /// srtp-crypto-suite = 1*(ALPHA / DIGIT / "_")
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(match value {
+            "AES_CM_128_HMAC_SHA1_80" => CryptoSuite::AesCm128HmacSha1_80,
+            "AES_CM_128_HMAC_SHA1_32" => CryptoSuite::AesCm128HmacSha1_32,
+            "AES_256_CM_HMAC_SHA1_80" => CryptoSuite::Aes256CmHmacSha1_80,
+            "AEAD_AES_128_GCM" => CryptoSuite::AeadAes128Gcm,
+            _ if !value.is_empty()
+                && value
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'_') =>
+            {
+                CryptoSuite::Other(value.to_owned())
+            }
+            _ => return Err(Error::SdpInvalidCrypto(format!("suite `{}`", value))),
+        })
+    }
+}
+
+/// Lifetime is the master key lifetime of a key parameter, in packets. It
+/// keeps the notation it was written in.
+/// lifetime = ["2^"] 1*(DIGIT)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lifetime {
+    /// Power is 2 to the power of the value, e.g. "2^20".
+    Power(u8),
+    /// Decimal is the number of packets, e.g. "1048576".
+    Decimal(u64),
+}
+
+impl Lifetime {
+    /// packets returns the lifetime in packets.
+    pub fn packets(self) -> u64 {
+        match self {
+            Lifetime::Power(exponent) => 1 << exponent,
+            Lifetime::Decimal(packets) => packets,
+        }
+    }
+}
+
+impl fmt::Display for Lifetime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Lifetime::Power(exponent) => write!(f, "2^{}", exponent),
+            Lifetime::Decimal(packets) => write!(f, "{}", packets),
+        }
+    }
+}
+
+impl FromStr for Lifetime {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::SdpInvalidCrypto(format!("lifetime `{}`", value));
+        let (power, digits) = match value.strip_prefix("2^") {
+            Some(exponent) => (true, exponent),
+            None => (false, value),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        if power {
+            match digits.parse::<u8>() {
+                Ok(exponent) if exponent < 64 => Ok(Lifetime::Power(exponent)),
+                _ => Err(invalid()),
+            }
+        } else {
+            digits.parse().map(Lifetime::Decimal).map_err(|_| invalid())
+        }
+    }
+}
+
+/// Mki is the master key identifier of a key parameter: its value and the
+/// length in bytes of the MKI field of SRTP packets, 1 to 128. Values are
+/// limited to u64.
+/// mki = mki-value ":" mki-length
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Mki {
+    /// value is the MKI value.
+    pub value: u64,
+    /// length is the length in bytes of the MKI field.
+    pub length: u8,
+}
+
+impl fmt::Display for Mki {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.value, self.length)
+    }
+}
+
+impl FromStr for Mki {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::SdpInvalidCrypto(format!("mki `{}`", value));
+        let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        let (mki_value, length) = value.split_once(':').ok_or_else(invalid)?;
+        if !is_digits(mki_value) || !is_digits(length) || length.len() > 3 {
+            return Err(invalid());
+        }
+        let mki_value = mki_value.parse::<u64>().map_err(|_| invalid())?;
+        let length = match length.parse::<u8>() {
+            Ok(length) if (1..=128).contains(&length) => length,
+            _ => return Err(invalid()),
+        };
+        // The value has to fit into the MKI field.
+        if length < 8 && mki_value >> (u32::from(length) * 8) != 0 {
+            return Err(invalid());
+        }
+        Ok(Mki {
+            value: mki_value,
+            length,
+        })
+    }
+}
+
+/// KeyParam is an "inline" key parameter of an "a=crypto:" attribute. The
+/// concatenated master key and salt are kept decoded; like EncryptionKey,
+/// Debug redacts them and expose_key_salt() gives access to them.
+/// key-param = "inline:" key-salt ["|" lifetime] ["|" mki]
+/// <https://tools.ietf.org/html/rfc4568#section-6.1>
+#[derive(Clone, PartialEq, Eq)]
+pub struct KeyParam {
+    key_salt: Vec<u8>,
+    /// lifetime is the master key lifetime, if given.
+    pub lifetime: Option<Lifetime>,
+    /// mki is the master key identifier, if given.
+    pub mki: Option<Mki>,
+}
+
+impl KeyParam {
+    /// inline creates a key parameter without lifetime and MKI.
+    pub fn inline(key_salt: Vec<u8>) -> Self {
+        KeyParam {
+            key_salt,
+            lifetime: None,
+            mki: None,
+        }
+    }
+
+    /// expose_key_salt returns the concatenated master key and salt, which
+    /// Debug redacts.
+    pub fn expose_key_salt(&self) -> &[u8] {
+        &self.key_salt
+    }
+}
+
+impl fmt::Debug for KeyParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyParam")
+            .field("key_salt", &format_args!("REDACTED"))
+            .field("lifetime", &self.lifetime)
+            .field("mki", &self.mki)
+            .finish()
+    }
+}
+
+impl fmt::Display for KeyParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "inline:{}", base64_encode(&self.key_salt))?;
+        if let Some(lifetime) = &self.lifetime {
+            write!(f, "|{}", lifetime)?;
+        }
+        if let Some(mki) = &self.mki {
+            write!(f, "|{}", mki)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for KeyParam {
+    type Err = Error;
+
+    /// Errors never include the key itself. Only canonical base64 is
+    /// accepted, so that Display gives back the same text.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let key_info = value
+            .strip_prefix("inline:")
+            .ok_or_else(|| Error::SdpInvalidCrypto("key method".to_owned()))?;
+        let mut fields = key_info.split('|');
+        let key_salt = fields
+            .next()
+            .and_then(base64_decode)
+            .filter(|key_salt| !key_salt.is_empty())
+            .ok_or_else(|| Error::SdpInvalidCrypto("inline key".to_owned()))?;
+
+        let mut key_param = KeyParam::inline(key_salt);
+        for field in fields {
+            if key_param.mki.is_some() {
+                return Err(Error::SdpInvalidCrypto(format!(
+                    "field `{}` after mki",
+                    field
+                )));
+            }
+            if field.contains(':') {
+                key_param.mki = Some(field.parse()?);
+            } else if key_param.lifetime.is_none() {
+                key_param.lifetime = Some(field.parse()?);
+            } else {
+                return Err(Error::SdpInvalidCrypto(format!("lifetime `{}`", field)));
+            }
+        }
+        Ok(key_param)
+    }
+}
+
+/// Crypto is an "a=crypto:" attribute of SDP security descriptions, offering
+/// SRTP keys for a media description.
+/// `a=crypto:<tag> <crypto-suite> <key-params> [<session-params>]`
+/// <https://tools.ietf.org/html/rfc4568#section-9.1>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crypto {
+    /// tag identifies the attribute among the offered ones, from 1.
+    pub tag: u32,
+    /// suite is the crypto suite the keys are for.
+    pub suite: CryptoSuite,
+    /// key_params are separated by ";" and hold at least one key.
+    pub key_params: Vec<KeyParam>,
+    /// session_params are the optional SRTP session parameters, e.g.
+    /// "KDR=1", kept as written.
+    pub session_params: Vec<String>,
+}
+
+impl Crypto {
+    /// select_answer picks the first offered attribute with a suite in
+    /// supported, and answers it with the same tag and suite and a single
+    /// fresh key, filled in by fill_random. Suites whose key length isn't
+    /// known are skipped.
+    /// <https://tools.ietf.org/html/rfc4568#section-7.1.2>
+    pub fn select_answer<F>(
+        offers: &[Crypto],
+        supported: &[CryptoSuite],
+        mut fill_random: F,
+    ) -> Option<Crypto>
+    where
+        F: FnMut(&mut [u8]),
+    {
+        let offer = offers.iter().find(|offer| {
+            supported.contains(&offer.suite) && offer.suite.key_salt_len().is_some()
+        })?;
+        let mut key_salt = vec![0; offer.suite.key_salt_len()?];
+        fill_random(&mut key_salt);
+        Some(Crypto {
+            tag: offer.tag,
+            suite: offer.suite.clone(),
+            key_params: vec![KeyParam::inline(key_salt)],
+            session_params: vec![],
+        })
+    }
+}
+
+impl fmt::Display for Crypto {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} ", self.tag, self.suite)?;
+        for (i, key_param) in self.key_params.iter().enumerate() {
+            if i > 0 {
+                write!(f, ";")?;
+            }
+            write!(f, "{}", key_param)?;
+        }
+        for session_param in &self.session_params {
+            write!(f, " {}", session_param)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Crypto {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut fields = value.split_whitespace();
+        let tag = fields.next().unwrap_or_default();
+        let tag = match tag.parse::<u32>() {
+            Ok(tag_value) if tag_value > 0 && tag.len() <= 9 && !tag.starts_with('+') => tag_value,
+            _ => return Err(Error::SdpInvalidCrypto(format!("tag `{}`", tag))),
+        };
+        let suite = fields.next().unwrap_or_default().parse()?;
+        let key_params = fields
+            .next()
+            .unwrap_or_default()
+            .split(';')
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Crypto {
+            tag,
+            suite,
+            key_params,
+            session_params: fields.map(str::to_owned).collect(),
+        })
+    }
+}
+
+impl TypedAttribute for Crypto {
+    const NAME: &'static str = "crypto";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("SRTP {} keys, tag {}", self.suite, self.tag))
+    }
+}
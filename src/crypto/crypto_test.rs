@@ -0,0 +1,249 @@
+use super::*;
+use crate::common_description::Attribute;
+use crate::media_description::MediaDescription;
+
+// The "a=crypto:" examples of RFC 4568.
+// https://tools.ietf.org/html/rfc4568#section-8.1
+const RFC_EXAMPLES: &[&str] = &[
+    "1 AES_CM_128_HMAC_SHA1_80 inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR|2^20|1:32",
+    "1 AES_CM_128_HMAC_SHA1_80 inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR|2^20|1:4",
+    "1 AES_CM_128_HMAC_SHA1_80 inline:WVNfX19zZW1jdGwgKCkgewkyMjA7fQp9CnVubGVz|2^20|1:4 FEC_ORDER=FEC_SRTP",
+    "1 AES_CM_128_HMAC_SHA1_32 inline:MTIzNDU2Nzg5QUJDREUwMTIzNDU2Nzg5QUJjZGVm|2^20|1:4;inline:QUJjZGVmMTIzNDU2Nzg5QUJDREUwMTIzNDU2Nzg5|2^20|2:4 FEC_ORDER=FEC_SRTP",
+    "2 F8_128_HMAC_SHA1_80 inline:MTIzNDU2Nzg5QUJDREUwMTIzNDU2Nzg5QUJjZGVm|2^20|1:4;inline:QUJjZGVmMTIzNDU2Nzg5QUJDREUwMTIzNDU2Nzg5|2^20|2:4 FEC_ORDER=FEC_SRTP",
+];
+
+#[test]
+fn test_crypto_rfc_examples() -> Result<(), Error> {
+    for &example in RFC_EXAMPLES {
+        let crypto = example.parse::<Crypto>()?;
+        assert_eq!(crypto.to_string(), example);
+        for key_param in &crypto.key_params {
+            assert_eq!(key_param.expose_key_salt().len(), 30);
+            assert_eq!(key_param.lifetime, Some(Lifetime::Power(20)));
+        }
+    }
+
+    let crypto = RFC_EXAMPLES[3].parse::<Crypto>()?;
+    assert_eq!(crypto.tag, 1);
+    assert_eq!(crypto.suite, CryptoSuite::AesCm128HmacSha1_32);
+    assert_eq!(crypto.key_params.len(), 2);
+    assert_eq!(
+        crypto.key_params[0].expose_key_salt(),
+        b"123456789ABCDE0123456789ABcdef"
+    );
+    assert_eq!(
+        crypto.key_params[1].mki,
+        Some(Mki {
+            value: 2,
+            length: 4
+        })
+    );
+    assert_eq!(crypto.session_params, vec!["FEC_ORDER=FEC_SRTP"]);
+
+    let crypto = RFC_EXAMPLES[4].parse::<Crypto>()?;
+    assert_eq!(crypto.tag, 2);
+    assert_eq!(
+        crypto.suite,
+        CryptoSuite::Other("F8_128_HMAC_SHA1_80".to_owned())
+    );
+    assert_eq!(crypto.suite.key_salt_len(), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_crypto_suite() -> Result<(), Error> {
+    let tests = [
+        (
+            "AES_CM_128_HMAC_SHA1_80",
+            CryptoSuite::AesCm128HmacSha1_80,
+            Some(30),
+        ),
+        (
+            "AES_CM_128_HMAC_SHA1_32",
+            CryptoSuite::AesCm128HmacSha1_32,
+            Some(30),
+        ),
+        (
+            "AES_256_CM_HMAC_SHA1_80",
+            CryptoSuite::Aes256CmHmacSha1_80,
+            Some(46),
+        ),
+        ("AEAD_AES_128_GCM", CryptoSuite::AeadAes128Gcm, Some(28)),
+    ];
+    for (value, suite, key_salt_len) in tests.iter() {
+        assert_eq!(value.parse::<CryptoSuite>()?, *suite);
+        assert_eq!(suite.to_string(), *value);
+        assert_eq!(suite.key_salt_len(), *key_salt_len);
+    }
+
+    for value in ["", "AES-CM", "AES CM"].iter() {
+        assert!(value.parse::<CryptoSuite>().is_err(), "{:?}", value);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_key_param_lifetime() -> Result<(), Error> {
+    let key = "inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR";
+
+    let key_param = format!("{}|2^20", key).parse::<KeyParam>()?;
+    assert_eq!(key_param.lifetime, Some(Lifetime::Power(20)));
+    assert_eq!(key_param.lifetime.map(Lifetime::packets), Some(1_048_576));
+    assert_eq!(key_param.to_string(), format!("{}|2^20", key));
+
+    // The same lifetime in decimal notation keeps its notation.
+    let key_param = format!("{}|1048576", key).parse::<KeyParam>()?;
+    assert_eq!(key_param.lifetime, Some(Lifetime::Decimal(1_048_576)));
+    assert_eq!(key_param.lifetime.map(Lifetime::packets), Some(1_048_576));
+    assert_eq!(key_param.to_string(), format!("{}|1048576", key));
+
+    // MKI without lifetime.
+    let key_param = format!("{}|1:4", key).parse::<KeyParam>()?;
+    assert_eq!(key_param.lifetime, None);
+    assert_eq!(
+        key_param.mki,
+        Some(Mki {
+            value: 1,
+            length: 4
+        })
+    );
+    assert_eq!(key_param.to_string(), format!("{}|1:4", key));
+
+    assert_eq!(
+        format!("{:?}", key_param),
+        "KeyParam { key_salt: REDACTED, lifetime: None, mki: Some(Mki { value: 1, length: 4 }) }"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_mki() -> Result<(), Error> {
+    let tests = [
+        ("1:1", true),
+        ("255:1", true),
+        ("256:1", false),
+        ("65535:2", true),
+        ("65536:2", false),
+        ("18446744073709551615:8", true),
+        ("1:128", true),
+        ("1:0", false),
+        ("1:129", false),
+        ("1:", false),
+        (":4", false),
+        ("+1:4", false),
+        ("1:4:4", false),
+        ("18446744073709551616:128", false),
+    ];
+    for (value, valid) in tests.iter() {
+        let mki = value.parse::<Mki>();
+        assert_eq!(mki.is_ok(), *valid, "{:?}", value);
+        if let Ok(mki) = mki {
+            assert_eq!(mki.to_string(), *value);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_crypto_invalid() {
+    let key = "inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR";
+    let tests = [
+        String::new(),
+        "1".to_owned(),
+        "1 AES_CM_128_HMAC_SHA1_80".to_owned(),
+        format!("0 AES_CM_128_HMAC_SHA1_80 {}", key),
+        format!("-1 AES_CM_128_HMAC_SHA1_80 {}", key),
+        format!("1234567890 AES_CM_128_HMAC_SHA1_80 {}", key),
+        format!("1 AES-CM {}", key),
+        "1 AES_CM_128_HMAC_SHA1_80 uri:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR".to_owned(),
+        "1 AES_CM_128_HMAC_SHA1_80 inline:".to_owned(),
+        "1 AES_CM_128_HMAC_SHA1_80 inline:PS1uQCVeeCFCanVmcjkp!".to_owned(),
+        // Not canonical: the unused bits of the last character are set.
+        "1 AES_CM_128_HMAC_SHA1_80 inline:AQJ=".to_owned(),
+        format!("1 AES_CM_128_HMAC_SHA1_80 {};", key),
+        format!("1 AES_CM_128_HMAC_SHA1_80 {}|2^64", key),
+        format!("1 AES_CM_128_HMAC_SHA1_80 {}|2^", key),
+        format!("1 AES_CM_128_HMAC_SHA1_80 {}|2^20|2^20", key),
+        format!("1 AES_CM_128_HMAC_SHA1_80 {}|1:4|2^20", key),
+        format!("1 AES_CM_128_HMAC_SHA1_80 {}|1:4|1:4", key),
+        format!("1 AES_CM_128_HMAC_SHA1_80 {}|", key),
+        format!("1 AES_CM_128_HMAC_SHA1_80 {}|1:0", key),
+    ];
+    for value in tests.iter() {
+        let err = value.parse::<Crypto>().unwrap_err();
+        // The key material never makes it into the error.
+        assert!(!err.to_string().contains("PS1uQ"), "{}", err);
+    }
+}
+
+#[test]
+fn test_crypto_select_answer() -> Result<(), Error> {
+    let offers = [
+        "1 F8_128_HMAC_SHA1_80 inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR",
+        "2 AES_CM_128_HMAC_SHA1_32 inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR|2^20|1:4",
+        "3 AES_CM_128_HMAC_SHA1_80 inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR FEC_ORDER=FEC_SRTP",
+    ]
+    .iter()
+    .map(|offer| offer.parse())
+    .collect::<Result<Vec<Crypto>, _>>()?;
+
+    let mut calls = 0;
+    let answer = Crypto::select_answer(
+        &offers,
+        &[
+            CryptoSuite::AesCm128HmacSha1_80,
+            CryptoSuite::AesCm128HmacSha1_32,
+        ],
+        |buf| {
+            calls += 1;
+            buf.iter_mut().for_each(|b| *b = 0x41);
+        },
+    )
+    .expect("a mutually supported suite");
+    assert_eq!(calls, 1);
+    // The first offer in the offerer's order wins.
+    assert_eq!(
+        answer.to_string(),
+        "2 AES_CM_128_HMAC_SHA1_32 inline:QUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFBQUFB"
+    );
+    assert_eq!(answer.key_params[0].expose_key_salt(), &[0x41; 30][..]);
+
+    let answer = Crypto::select_answer(&offers, &[CryptoSuite::AesCm128HmacSha1_80], |_| {})
+        .expect("a mutually supported suite");
+    assert_eq!(answer.tag, 3);
+    assert!(answer.session_params.is_empty());
+
+    // Unknown suites have no known key length to answer with.
+    let f8 = CryptoSuite::Other("F8_128_HMAC_SHA1_80".to_owned());
+    assert_eq!(Crypto::select_answer(&offers, &[f8], |_| {}), None);
+    assert_eq!(
+        Crypto::select_answer(&offers, &[CryptoSuite::AeadAes128Gcm], |_| {}),
+        None
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_media_description_crypto() -> Result<(), Error> {
+    let media = MediaDescription::new("audio".to_owned(), vec![])
+        .with_value_attribute("crypto".to_owned(), RFC_EXAMPLES[1].to_owned())
+        .with_value_attribute("crypto".to_owned(), "0 AES_CM_128_HMAC_SHA1_80".to_owned())
+        .with_value_attribute("crypto".to_owned(), RFC_EXAMPLES[3].to_owned());
+
+    let crypto = media.crypto();
+    assert_eq!(crypto.len(), 2);
+    assert_eq!(crypto[1].suite, CryptoSuite::AesCm128HmacSha1_32);
+
+    let attribute = crypto[0].to_attribute();
+    assert_eq!(
+        attribute,
+        Attribute::new("crypto".to_owned(), Some(RFC_EXAMPLES[1].to_owned()))
+    );
+
+    Ok(())
+}
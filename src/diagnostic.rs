@@ -0,0 +1,144 @@
+use core::fmt;
+
+use super::alloc_prelude::*;
+
+/// Severity tells whether a diagnostic makes a description invalid, or only
+/// points at something which some endpoints may not handle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// Error is a violation of the specification.
+    Error,
+    /// Warning is something which some endpoints may not handle.
+    Warning,
+}
+
+/// DiagnosticKind identifies the check which produced a diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticKind {
+    /// A field contains a line break and would corrupt the marshaled text.
+    UnexpectedLineBreak,
+    /// Neither the session nor a media description has a "c=" field.
+    /// <https://tools.ietf.org/html/rfc4566#section-5.7>
+    MissingConnectionInformation,
+    /// There is no "t=" field.
+    /// <https://tools.ietf.org/html/rfc4566#section-5.9>
+    MissingTiming,
+    /// "b=CT" appears more than once at session level.
+    DuplicateConferenceTotalBandwidth,
+    /// A bandwidth modifier other than the session level "b=CT" appears more
+    /// than once at the same level.
+    /// <https://tools.ietf.org/html/rfc4566#section-5.8>
+    DuplicateBandwidth,
+    /// A "k=clear:" or "k=base64:" key is sent, which is NOT RECOMMENDED.
+    /// <https://tools.ietf.org/html/rfc4566#section-5.12>
+    InsecureEncryptionKey,
+    /// A "k=" field with a method other than "clear" or "base64" is sent. The
+    /// mechanism as a whole is NOT RECOMMENDED and kept for compatibility only.
+    /// <https://tools.ietf.org/html/rfc8866#section-5.12>
+    DeprecatedEncryptionKey,
+    /// A dynamic payload type of the "m=" line has no "a=rtpmap".
+    /// <https://tools.ietf.org/html/rfc4566#section-6>
+    MissingRtpMap,
+    /// An RTP "m=" line with several ports starts at an odd port.
+    /// <https://tools.ietf.org/html/rfc4566#section-5.14>
+    OddRtpPort,
+    /// "a=ptime" is greater than "a=maxptime".
+    PTimeExceedsMaxPTime,
+    /// "b=RR" and "b=RS" add up to more than the "b=AS" bandwidth.
+    /// <https://tools.ietf.org/html/rfc3556#section-2>
+    RtcpBandwidthExceedsSession,
+    /// "a=mid" is longer than recommended for the RTP header extension.
+    /// <https://tools.ietf.org/html/rfc9143#section-9.1>
+    LongMid,
+    /// "a=group" refers to a mid no media description has.
+    /// <https://tools.ietf.org/html/rfc5888#section-5>
+    UnknownGroupMid,
+    /// A media description has a mid but is in no group, although the
+    /// session uses grouping.
+    UngroupedMid,
+    /// "a=rtcp-mux-only" is present without "a=rtcp-mux".
+    /// <https://tools.ietf.org/html/rfc8858#section-4>
+    RtcpMuxOnlyWithoutRtcpMux,
+    /// The "a=msid" stream of a media description is not listed by the
+    /// legacy session level "a=msid-semantic:WMS".
+    MsidStreamNotInWms,
+    /// "a=simulcast" refers to a RID without an "a=rid" line.
+    /// <https://tools.ietf.org/html/rfc8853#section-5.2>
+    UnknownSimulcastRid,
+    /// "a=simulcast" lists a RID in the direction opposite to its "a=rid".
+    SimulcastRidDirectionMismatch,
+    /// Both "k=" and "a=key-mgmt" apply to a media description.
+    /// <https://tools.ietf.org/html/rfc4567#section-3.1>
+    EncryptionKeyWithKeyMgmt,
+    /// An "a=extmap" id needs a two-byte header, but "a=extmap-allow-mixed"
+    /// is present at neither session nor media level.
+    /// <https://tools.ietf.org/html/rfc8285#section-6>
+    TwoByteExtMapWithoutAllowMixed,
+    /// An informational attribute like "a=framerate" or "a=lang" does not
+    /// parse.
+    /// <https://tools.ietf.org/html/rfc4566#section-6>
+    MalformedAttribute,
+}
+
+/// Diagnostic is a single finding of a validation pass. Unlike parse errors,
+/// diagnostics don't stop processing, and it is up to the caller to decide
+/// which ones matter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// severity tells whether the description is invalid.
+    pub severity: Severity,
+    /// kind tells what the diagnostic is about.
+    pub kind: DiagnosticKind,
+    /// field is the field or attribute concerned, e.g. "a=ptime".
+    pub field: String,
+    /// media_index is the index of the media description concerned, or None
+    /// for the session level.
+    pub media_index: Option<usize>,
+    /// message explains the diagnostic.
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// warning creates a session level warning.
+    pub fn warning(kind: DiagnosticKind, field: &str, message: String) -> Self {
+        Diagnostic {
+            severity: Severity::Warning,
+            kind,
+            field: field.to_owned(),
+            media_index: None,
+            message,
+        }
+    }
+
+    /// error creates a session level error.
+    pub fn error(kind: DiagnosticKind, field: &str, message: String) -> Self {
+        Diagnostic {
+            severity: Severity::Error,
+            kind,
+            field: field.to_owned(),
+            media_index: None,
+            message,
+        }
+    }
+
+    /// in_media marks the diagnostic as concerning the media description with
+    /// the given index.
+    pub fn in_media(mut self, index: usize) -> Self {
+        self.media_index = Some(index);
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}: ", severity)?;
+        if let Some(index) = self.media_index {
+            write!(f, "media {}: ", index)?;
+        }
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
@@ -0,0 +1,192 @@
+use super::alloc_prelude::*;
+use super::common_description::Attributes;
+use super::direction::Direction;
+use super::group::Mid;
+use super::media_description::MediaDescription;
+use super::session_description::SessionDescription;
+use super::util::LineEnding;
+
+#[cfg(test)]
+mod diff_test;
+
+/// MediaId identifies a media description across two versions of a session
+/// description: by its mid, or by its index if it has none.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MediaId {
+    /// Mid is the mid of the media description.
+    Mid(Mid),
+    /// Index is the position of a media description without a mid.
+    Index(usize),
+}
+
+/// SdpChange is a change between two session descriptions which matters for
+/// renegotiation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SdpChange {
+    /// OriginChanged means the "o=" fields identifying the session changed,
+    /// i.e. it is a different session.
+    OriginChanged,
+    /// MediaAdded means a media description was added.
+    MediaAdded(MediaId),
+    /// MediaRemoved means a media description was removed.
+    MediaRemoved(MediaId),
+    /// DirectionChanged compares the directions in effect, sendrecv if there
+    /// is no direction attribute.
+    DirectionChanged {
+        /// media is the media description whose direction changed.
+        media: MediaId,
+        /// from is the old direction.
+        from: Direction,
+        /// to is the new direction.
+        to: Direction,
+    },
+    /// IceRestart means the ICE credentials in effect for the media changed.
+    /// <https://tools.ietf.org/html/rfc8839#section-4.4.1.1.1>
+    IceRestart(MediaId),
+    /// CodecsChanged lists the codecs by their "a=rtpmap:" value, or by their
+    /// format if they have none, e.g. "111 opus/48000/2" or "0".
+    CodecsChanged {
+        /// media is the media description whose codecs changed.
+        media: MediaId,
+        /// added are the codecs only in the new description.
+        added: Vec<String>,
+        /// removed are the codecs only in the old description.
+        removed: Vec<String>,
+    },
+}
+
+/// SdpDiff is the result of SessionDescription::diff. A new `<sess-version>`
+/// alone isn't a change, it is only noted in session_version.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SdpDiff {
+    /// session_version holds the old and new `<sess-version>` if they differ.
+    pub session_version: Option<(u64, u64)>,
+    /// changes are the changes, in the order of the media descriptions.
+    pub changes: Vec<SdpChange>,
+}
+
+impl SdpDiff {
+    /// is_empty reports whether nothing changed that matters for renegotiation.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl SessionDescription {
+    /// semantically_eq compares two session descriptions, ignoring the
+    /// `<sess-version>`, the line endings and the order of attributes within
+    /// the session and each media description. The order of media
+    /// descriptions and formats is significant.
+    pub fn semantically_eq(&self, other: &SessionDescription) -> bool {
+        normalized(self) == normalized(other)
+    }
+
+    /// diff lists the changes from self to other. Media descriptions are
+    /// matched by mid, or by index if they have none.
+    pub fn diff(&self, other: &SessionDescription) -> SdpDiff {
+        let mut diff = SdpDiff::default();
+        if self.origin.session_version != other.origin.session_version {
+            diff.session_version =
+                Some((self.origin.session_version, other.origin.session_version));
+        }
+        if !self.origin.is_same_session(&other.origin) {
+            diff.changes.push(SdpChange::OriginChanged);
+        }
+
+        let ids = media_ids(self);
+        let other_ids = media_ids(other);
+        for (index, id) in ids.iter().enumerate() {
+            let other_index = match other_ids.iter().position(|other_id| other_id == id) {
+                Some(other_index) => other_index,
+                None => {
+                    diff.changes.push(SdpChange::MediaRemoved(id.clone()));
+                    continue;
+                }
+            };
+            let (md, other_md) = (
+                &self.media_descriptions[index],
+                &other.media_descriptions[other_index],
+            );
+
+            let direction = md.direction().unwrap_or_default();
+            let other_direction = other_md.direction().unwrap_or_default();
+            if direction != other_direction {
+                diff.changes.push(SdpChange::DirectionChanged {
+                    media: id.clone(),
+                    from: direction,
+                    to: other_direction,
+                });
+            }
+
+            if self.ice_credentials_for(index) != other.ice_credentials_for(other_index) {
+                diff.changes.push(SdpChange::IceRestart(id.clone()));
+            }
+
+            let (codecs, other_codecs) = (codecs(md), codecs(other_md));
+            let added: Vec<String> = other_codecs
+                .iter()
+                .filter(|codec| !codecs.contains(codec))
+                .cloned()
+                .collect();
+            let removed: Vec<String> = codecs
+                .iter()
+                .filter(|codec| !other_codecs.contains(codec))
+                .cloned()
+                .collect();
+            if !added.is_empty() || !removed.is_empty() {
+                diff.changes.push(SdpChange::CodecsChanged {
+                    media: id.clone(),
+                    added,
+                    removed,
+                });
+            }
+        }
+
+        for id in other_ids {
+            if !ids.contains(&id) {
+                diff.changes.push(SdpChange::MediaAdded(id));
+            }
+        }
+
+        diff
+    }
+}
+
+fn normalized(sd: &SessionDescription) -> SessionDescription {
+    let mut sd = sd.clone();
+    sd.origin.session_version = 0;
+    sd.line_ending = LineEnding::CrLf;
+    sort_attributes(&mut sd.attributes);
+    for md in &mut sd.media_descriptions {
+        sort_attributes(&mut md.attributes);
+    }
+    sd
+}
+
+fn sort_attributes(attributes: &mut Attributes) {
+    attributes
+        .0
+        .sort_by(|a, b| (&a.key, &a.value).cmp(&(&b.key, &b.value)));
+}
+
+fn media_ids(sd: &SessionDescription) -> Vec<MediaId> {
+    sd.media_descriptions
+        .iter()
+        .enumerate()
+        .map(|(index, md)| md.mid().map_or(MediaId::Index(index), MediaId::Mid))
+        .collect()
+}
+
+fn codecs(md: &MediaDescription) -> Vec<String> {
+    md.media_name
+        .formats
+        .iter()
+        .map(|format| {
+            format
+                .parse::<u8>()
+                .ok()
+                .and_then(|payload_type| md.rtpmap_for(payload_type))
+                .map_or_else(|| format.clone(), |rtpmap| rtpmap.to_string())
+        })
+        .collect()
+}
@@ -0,0 +1,203 @@
+use super::*;
+
+use crate::error::Error;
+use crate::group::Mid;
+use crate::ice::{IcePwd, IceUfrag};
+
+const OFFER_SDP: &str = "v=0\r\n\
+o=- 4611731400430051336 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0 1\r\n\
+a=msid-semantic: WMS stream\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111 0\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=ice-ufrag:Gh2c\r\n\
+a=ice-pwd:3zUiRr5gHbS0aBkPQVvvDbQy\r\n\
+a=mid:0\r\n\
+a=sendrecv\r\n\
+a=rtpmap:111 opus/48000/2\r\n\
+a=fmtp:111 minptime=10;useinbandfec=1\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96 97\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=ice-ufrag:Gh2c\r\n\
+a=ice-pwd:3zUiRr5gHbS0aBkPQVvvDbQy\r\n\
+a=mid:1\r\n\
+a=sendonly\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+a=rtpmap:97 rtx/90000\r\n\
+a=fmtp:97 apt=96\r\n";
+
+fn mid(value: &str) -> MediaId {
+    MediaId::Mid(Mid::try_new(value).expect("mid"))
+}
+
+#[test]
+fn test_version_bump() -> Result<(), Error> {
+    let offer = OFFER_SDP.parse::<SessionDescription>()?;
+    let mut reoffer = offer.clone();
+    reoffer.prepare_reoffer()?;
+
+    let diff = offer.diff(&reoffer);
+    assert!(diff.is_empty(), "{:?}", diff);
+    assert_eq!(diff.session_version, Some((2, 3)));
+    assert!(offer.semantically_eq(&reoffer));
+    assert_eq!(offer.diff(&offer), SdpDiff::default());
+
+    Ok(())
+}
+
+#[test]
+fn test_ice_restart() -> Result<(), Error> {
+    let offer = OFFER_SDP.parse::<SessionDescription>()?;
+    let mut reoffer = offer.clone();
+    reoffer.rotate_ice_credentials(
+        IceUfrag::try_new("Xy7q")?,
+        IcePwd::try_new("n3wPasswordn3wPasswordxx")?,
+    );
+
+    let diff = offer.diff(&reoffer);
+    assert_eq!(
+        diff.changes,
+        vec![
+            SdpChange::IceRestart(mid("0")),
+            SdpChange::IceRestart(mid("1"))
+        ]
+    );
+    assert!(!offer.semantically_eq(&reoffer));
+
+    Ok(())
+}
+
+#[test]
+fn test_diff() -> Result<(), Error> {
+    let offer = OFFER_SDP.parse::<SessionDescription>()?;
+    let reoffer = OFFER_SDP
+        .replace("o=- 4611731400430051336", "o=- 1")
+        .replace(
+            "m=audio 9 UDP/TLS/RTP/SAVPF 111 0",
+            "m=audio 9 UDP/TLS/RTP/SAVPF 111 8",
+        )
+        .replace("a=sendonly", "a=inactive")
+        .replace("a=mid:1", "a=mid:2")
+        .parse::<SessionDescription>()?;
+
+    let diff = offer.diff(&reoffer);
+    assert_eq!(diff.session_version, None);
+    assert_eq!(
+        diff.changes,
+        vec![
+            SdpChange::OriginChanged,
+            SdpChange::CodecsChanged {
+                media: mid("0"),
+                added: vec!["8".to_owned()],
+                removed: vec!["0".to_owned()],
+            },
+            SdpChange::MediaRemoved(mid("1")),
+            SdpChange::MediaAdded(mid("2")),
+        ]
+    );
+
+    let reoffer = OFFER_SDP
+        .replace("a=sendrecv\r\n", "")
+        .replace("a=sendonly", "a=inactive")
+        .replace("a=rtpmap:111 opus/48000/2", "a=rtpmap:111 opus/48000/1")
+        .parse::<SessionDescription>()?;
+    assert_eq!(
+        offer.diff(&reoffer).changes,
+        vec![
+            SdpChange::CodecsChanged {
+                media: mid("0"),
+                added: vec!["111 opus/48000/1".to_owned()],
+                removed: vec!["111 opus/48000/2".to_owned()],
+            },
+            SdpChange::DirectionChanged {
+                media: mid("1"),
+                from: Direction::SendOnly,
+                to: Direction::Inactive,
+            },
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_diff_by_index() -> Result<(), Error> {
+    let offer = "v=0\r\n\
+o=- 0 0 IN IP4 0.0.0.0\r\n\
+s=-\r\n\
+t=0 0\r\n\
+m=audio 9 RTP/AVP 0\r\n"
+        .parse::<SessionDescription>()?;
+    let mut reoffer = offer.clone();
+    reoffer
+        .media_descriptions
+        .push(reoffer.media_descriptions[0].clone());
+    reoffer.media_descriptions[0].set_direction(Direction::RecvOnly);
+
+    assert_eq!(
+        offer.diff(&reoffer).changes,
+        vec![
+            SdpChange::DirectionChanged {
+                media: MediaId::Index(0),
+                from: Direction::SendRecv,
+                to: Direction::RecvOnly,
+            },
+            SdpChange::MediaAdded(MediaId::Index(1)),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_semantically_eq() -> Result<(), Error> {
+    let offer = OFFER_SDP.parse::<SessionDescription>()?;
+    let tests = vec![
+        (
+            "attribute order",
+            OFFER_SDP.replace(
+                "a=group:BUNDLE 0 1\r\na=msid-semantic: WMS stream\r\n",
+                "a=msid-semantic: WMS stream\r\na=group:BUNDLE 0 1\r\n",
+            ),
+            true,
+        ),
+        (
+            "media attribute order",
+            OFFER_SDP.replace(
+                "a=rtpmap:96 VP8/90000\r\na=rtpmap:97 rtx/90000\r\n",
+                "a=rtpmap:97 rtx/90000\r\na=rtpmap:96 VP8/90000\r\n",
+            ),
+            true,
+        ),
+        ("whitespace", OFFER_SDP.replace("\r\n", "  \n"), true),
+        (
+            "sess-version",
+            OFFER_SDP.replace(" 2 IN IP4", " 7 IN IP4"),
+            true,
+        ),
+        (
+            "format order",
+            OFFER_SDP.replace("SAVPF 96 97", "SAVPF 97 96"),
+            false,
+        ),
+        (
+            "attribute value",
+            OFFER_SDP.replace("a=mid:1", "a=mid:2"),
+            false,
+        ),
+        (
+            "sess-id",
+            OFFER_SDP.replace("o=- 4611731400430051336", "o=- 1"),
+            false,
+        ),
+    ];
+
+    for (name, input, expected) in tests {
+        let other = input.parse::<SessionDescription>()?;
+        assert_eq!(offer.semantically_eq(&other), expected, "{}", name);
+    }
+
+    Ok(())
+}
@@ -1,55 +1,126 @@
-use std::fmt;
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::{Attribute, TypedAttribute};
+use super::error::Error;
 
 #[cfg(test)]
 mod direction_test;
 
-//Direction is a marker for transmission direction of an endpoint
-#[derive(Debug, PartialEq, Clone)]
+///Direction is a marker for transmission direction of an endpoint
+#[derive(Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
 pub enum Direction {
-    DirectionUnknown = 0,
-    //DirectionSendRecv is for bidirectional communication
-    DirectionSendRecv = 1,
-    //DirectionSendOnly is for outgoing communication
-    DirectionSendOnly = 2,
-    //DirectionRecvOnly is for incoming communication
-    DirectionRecvOnly = 3,
-    //DirectionInactive is for no communication
-    DirectionInactive = 4,
+    ///SendRecv is for bidirectional communication, and the default when no
+    ///direction attribute is present
+    ///<https://tools.ietf.org/html/rfc4566#section-6>
+    #[default]
+    SendRecv,
+    ///SendOnly is for outgoing communication
+    SendOnly,
+    ///RecvOnly is for incoming communication
+    RecvOnly,
+    ///Inactive is for no communication
+    Inactive,
 }
 
+/// DIRECTION_SEND_RECV_STR is the attribute of Direction::SendRecv.
 pub const DIRECTION_SEND_RECV_STR: &str = "sendrecv";
+/// DIRECTION_SEND_ONLY_STR is the attribute of Direction::SendOnly.
 pub const DIRECTION_SEND_ONLY_STR: &str = "sendonly";
+/// DIRECTION_RECV_ONLY_STR is the attribute of Direction::RecvOnly.
 pub const DIRECTION_RECV_ONLY_STR: &str = "recvonly";
+/// DIRECTION_INACTIVE_STR is the attribute of Direction::Inactive.
 pub const DIRECTION_INACTIVE_STR: &str = "inactive";
-pub const DIRECTION_UNKNOWN_STR: &str = "";
+
+impl Direction {
+    /// reversed returns the direction as seen from the remote endpoint, e.g.
+    /// the answer to a sendonly offer is recvonly.
+    pub fn reversed(self) -> Self {
+        match self {
+            Direction::SendOnly => Direction::RecvOnly,
+            Direction::RecvOnly => Direction::SendOnly,
+            other => other,
+        }
+    }
+
+    /// is_send returns true if media is sent in this direction.
+    pub const fn is_send(self) -> bool {
+        matches!(self, Direction::SendRecv | Direction::SendOnly)
+    }
+
+    /// is_recv returns true if media is received in this direction.
+    pub const fn is_recv(self) -> bool {
+        matches!(self, Direction::SendRecv | Direction::RecvOnly)
+    }
+
+    /// intersection returns the direction allowed by both, e.g. sendrecv and
+    /// recvonly give recvonly.
+    pub fn intersection(self, other: Direction) -> Self {
+        match (
+            self.is_send() && other.is_send(),
+            self.is_recv() && other.is_recv(),
+        ) {
+            (true, true) => Direction::SendRecv,
+            (true, false) => Direction::SendOnly,
+            (false, true) => Direction::RecvOnly,
+            (false, false) => Direction::Inactive,
+        }
+    }
+}
 
 impl fmt::Display for Direction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
-            Direction::DirectionSendRecv => DIRECTION_SEND_RECV_STR,
-            Direction::DirectionSendOnly => DIRECTION_SEND_ONLY_STR,
-            Direction::DirectionRecvOnly => DIRECTION_RECV_ONLY_STR,
-            Direction::DirectionInactive => DIRECTION_INACTIVE_STR,
-            _ => DIRECTION_UNKNOWN_STR,
+            Direction::SendRecv => DIRECTION_SEND_RECV_STR,
+            Direction::SendOnly => DIRECTION_SEND_ONLY_STR,
+            Direction::RecvOnly => DIRECTION_RECV_ONLY_STR,
+            Direction::Inactive => DIRECTION_INACTIVE_STR,
         };
         write!(f, "{}", s)
     }
 }
 
-impl Default for Direction {
-    fn default() -> Direction {
-        Direction::DirectionUnknown
-    }
-}
-// NewDirection defines a procedure for creating a new direction from a raw string.
-impl Direction {
-    pub fn new(raw: &str) -> Self {
+impl FromStr for Direction {
+    type Err = Error;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
         match raw {
-            DIRECTION_SEND_RECV_STR => Direction::DirectionSendRecv,
-            DIRECTION_SEND_ONLY_STR => Direction::DirectionSendOnly,
-            DIRECTION_RECV_ONLY_STR => Direction::DirectionRecvOnly,
-            DIRECTION_INACTIVE_STR => Direction::DirectionInactive,
-            _ => Direction::DirectionUnknown,
+            DIRECTION_SEND_RECV_STR => Ok(Direction::SendRecv),
+            DIRECTION_SEND_ONLY_STR => Ok(Direction::SendOnly),
+            DIRECTION_RECV_ONLY_STR => Ok(Direction::RecvOnly),
+            DIRECTION_INACTIVE_STR => Ok(Direction::Inactive),
+            _ => Err(Error::SdpInvalidDirection(raw.to_owned())),
         }
     }
 }
+
+/// Direction is a property attribute: "a=sendrecv" rather than a value
+/// attribute named "direction".
+impl TypedAttribute for Direction {
+    const NAME: &'static str = "direction";
+
+    fn matches(attribute: &Attribute) -> bool {
+        attribute.value.is_none() && attribute.key.parse::<Direction>().is_ok()
+    }
+
+    fn from_attribute(attribute: &Attribute) -> Result<Self, Self::Err> {
+        attribute.key.parse()
+    }
+
+    fn to_attribute(&self) -> Attribute {
+        Attribute::new(self.to_string(), None)
+    }
+
+    fn describe(&self) -> Option<String> {
+        Some(
+            match self {
+                Direction::SendRecv => "send and receive",
+                Direction::SendOnly => "send only",
+                Direction::RecvOnly => "receive only",
+                Direction::Inactive => "inactive",
+            }
+            .to_owned(),
+        )
+    }
+}
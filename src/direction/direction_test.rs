@@ -1,38 +1,60 @@
 use super::*;
-use std::iter::Iterator;
+use core::iter::Iterator;
 
 #[test]
 fn test_new_direction() {
-    let passingtests = vec![
-        ("sendrecv", Direction::DirectionSendRecv),
-        ("sendonly", Direction::DirectionSendOnly),
-        ("recvonly", Direction::DirectionRecvOnly),
-        ("inactive", Direction::DirectionInactive),
+    let passingtests = [
+        ("sendrecv", Direction::SendRecv),
+        ("sendonly", Direction::SendOnly),
+        ("recvonly", Direction::RecvOnly),
+        ("inactive", Direction::Inactive),
     ];
 
-    let failingtests = vec!["", "notadirection"];
+    let failingtests = ["", "notadirection", "SENDRECV"];
 
     for (i, u) in passingtests.iter().enumerate() {
-        let dir = Direction::new(u.0);
-        assert!(u.1 == dir, "{}: {}", i, u.0);
+        let dir = u.0.parse::<Direction>();
+        assert!(matches!(dir, Ok(d) if d == u.1), "{}: {}", i, u.0);
     }
-    for (_, &u) in failingtests.iter().enumerate() {
-        let dir = Direction::new(u);
-        assert!(dir == Direction::DirectionUnknown);
+    for &u in failingtests.iter() {
+        let dir = u.parse::<Direction>();
+        assert!(
+            matches!(dir, Err(Error::SdpInvalidDirection(ref s)) if s == u),
+            "{}",
+            u
+        );
     }
 }
 
 #[test]
 fn test_direction_string() {
-    let tests = vec![
-        (Direction::DirectionUnknown, DIRECTION_UNKNOWN_STR),
-        (Direction::DirectionSendRecv, "sendrecv"),
-        (Direction::DirectionSendOnly, "sendonly"),
-        (Direction::DirectionRecvOnly, "recvonly"),
-        (Direction::DirectionInactive, "inactive"),
+    let tests = [
+        (Direction::SendRecv, "sendrecv"),
+        (Direction::SendOnly, "sendonly"),
+        (Direction::RecvOnly, "recvonly"),
+        (Direction::Inactive, "inactive"),
     ];
 
     for (i, u) in tests.iter().enumerate() {
         assert!(u.1 == u.0.to_string(), "{}: {}", i, u.1);
     }
 }
+
+#[test]
+fn test_direction_intersection() {
+    use Direction::*;
+
+    let tests = [
+        (SendRecv, SendRecv, SendRecv),
+        (SendRecv, RecvOnly, RecvOnly),
+        (SendOnly, SendRecv, SendOnly),
+        (SendOnly, RecvOnly, Inactive),
+        (RecvOnly, RecvOnly, RecvOnly),
+        (Inactive, SendRecv, Inactive),
+    ];
+
+    for (a, b, expected) in tests.iter() {
+        assert_eq!(a.intersection(*b), *expected, "{} {}", a, b);
+        assert_eq!(b.intersection(*a), *expected, "{} {}", b, a);
+    }
+}
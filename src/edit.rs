@@ -0,0 +1,270 @@
+use super::alloc_prelude::*;
+use super::common_description::{Attributes, TypedAttribute};
+use super::diagnostic::{Diagnostic, Severity};
+use super::error::Error;
+use super::group::{Group, GroupSemantics, Mid};
+use super::media_description::MediaDescription;
+use super::msid::{MsidSemantic, MSID_SEMANTIC_WMS};
+use super::session_description::SessionDescription;
+
+#[cfg(test)]
+mod edit_test;
+
+/// Editor changes a session description as a transaction, keeping the parts
+/// which depend on the media descriptions consistent, see Editor::commit.
+/// Changes are made in place; the editor holds a copy of the description
+/// from before the edit to roll back to. Dropping an editor without
+/// committing it rolls back like abort.
+#[must_use = "an Editor rolls back unless it is committed"]
+pub struct Editor<'a> {
+    description: &'a mut SessionDescription,
+    snapshot: Option<SessionDescription>,
+}
+
+/// EditSummary tells what a committed edit changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditSummary {
+    /// added_mids are the mids of the media descriptions added by the edit.
+    pub added_mids: Vec<Mid>,
+    /// removed_mids are the mids of the media descriptions removed by the
+    /// edit.
+    pub removed_mids: Vec<Mid>,
+    /// session_version is the `<sess-version>` of "o=" after the edit. It is
+    /// only bumped if the description changed.
+    pub session_version: u64,
+}
+
+impl SessionDescription {
+    /// edit starts a transactional edit of the description, see Editor.
+    pub fn edit(&mut self) -> Editor<'_> {
+        let snapshot = self.clone();
+        Editor {
+            description: self,
+            snapshot: Some(snapshot),
+        }
+    }
+}
+
+impl<'a> Editor<'a> {
+    /// add_media appends a media description and returns it for further
+    /// changes. If it has a mid by the time the edit is committed, and is not
+    /// rejected, the mid is added to the first BUNDLE group.
+    pub fn add_media(&mut self, media_description: MediaDescription) -> &mut MediaDescription {
+        self.description.media_descriptions.push(media_description);
+        let index = self.description.media_descriptions.len() - 1;
+        &mut self.description.media_descriptions[index]
+    }
+
+    /// remove_media removes the media description with the given mid and
+    /// returns it. Its mid is dropped from every group when the edit is
+    /// committed. A re-offer must not have fewer "m=" lines than the previous
+    /// offer; use SessionDescription::reject_media there instead.
+    /// <https://tools.ietf.org/html/rfc3264#section-8>
+    pub fn remove_media(&mut self, mid: &str) -> Result<MediaDescription, Error> {
+        let index = self
+            .description
+            .media_descriptions
+            .iter()
+            .position(|md| md.mid().is_some_and(|m| m.as_str() == mid))
+            .ok_or_else(|| Error::SdpMidNotFound(mid.to_owned()))?;
+        Ok(self.description.media_descriptions.remove(index))
+    }
+
+    /// media_mut returns the media description with the given mid.
+    pub fn media_mut(&mut self, mid: &str) -> Option<&mut MediaDescription> {
+        self.description
+            .media_descriptions
+            .iter_mut()
+            .find(|md| md.mid().is_some_and(|m| m.as_str() == mid))
+    }
+
+    /// attributes_mut returns the session level attributes.
+    pub fn attributes_mut(&mut self) -> &mut Attributes {
+        &mut self.description.attributes
+    }
+
+    /// commit finishes the edit. The mids of added and removed media
+    /// descriptions are added to or dropped from the groups, the stream ids
+    /// of "a=msid-semantic:WMS" follow the "a=msid" attributes, and the
+    /// session version is bumped if anything changed. The result has to have
+    /// unique mids and must not fail validate with errors that the original
+    /// description didn't have; otherwise the edit is rolled back and
+    /// SdpInvalidEdit lists the problems.
+    pub fn commit(mut self) -> Result<EditSummary, Error> {
+        let snapshot = match self.snapshot.take() {
+            Some(snapshot) => snapshot,
+            None => unreachable!("an Editor is only finished by consuming it"),
+        };
+        match finish(self.description, &snapshot) {
+            Ok(summary) => Ok(summary),
+            Err(err) => {
+                *self.description = snapshot;
+                Err(err)
+            }
+        }
+    }
+
+    /// abort rolls the description back to how it was before the edit.
+    pub fn abort(self) {}
+}
+
+impl Drop for Editor<'_> {
+    fn drop(&mut self) {
+        if let Some(snapshot) = self.snapshot.take() {
+            *self.description = snapshot;
+        }
+    }
+}
+
+fn finish(
+    description: &mut SessionDescription,
+    snapshot: &SessionDescription,
+) -> Result<EditSummary, Error> {
+    let before = mids(snapshot);
+    let after = mids(description);
+    let is_new = |mid: &Mid| !before.iter().any(|(m, _)| m == mid);
+    let added_mids: Vec<Mid> = after
+        .iter()
+        .filter(|(mid, _)| is_new(mid))
+        .map(|(mid, _)| mid.clone())
+        .collect();
+    let bundled_mids: Vec<Mid> = after
+        .iter()
+        .filter(|(mid, rejected)| is_new(mid) && !rejected)
+        .map(|(mid, _)| mid.clone())
+        .collect();
+    let removed_mids: Vec<Mid> = before
+        .iter()
+        .filter(|(mid, _)| !after.iter().any(|(m, _)| m == mid))
+        .map(|(mid, _)| mid.clone())
+        .collect();
+
+    update_groups(&mut description.attributes, &bundled_mids, &removed_mids);
+    update_wms(description, snapshot);
+
+    let mut problems = vec![];
+    for (index, (mid, _)) in after.iter().enumerate() {
+        if after[..index].iter().any(|(m, _)| m == mid) {
+            problems.push(format!("mid `{}` is used more than once", mid));
+        }
+    }
+    // Errors the description already had are not the edit's fault. Media
+    // indices may have shifted, so they are not compared.
+    let known: Vec<Diagnostic> = errors(snapshot.validate()).collect();
+    problems.extend(
+        errors(description.validate())
+            .filter(|d| {
+                !known
+                    .iter()
+                    .any(|k| k.kind == d.kind && k.message == d.message)
+            })
+            .map(|d| d.to_string()),
+    );
+    if !problems.is_empty() {
+        return Err(Error::SdpInvalidEdit(problems));
+    }
+
+    if description != snapshot {
+        description.origin.bump_version()?;
+    }
+    Ok(EditSummary {
+        added_mids,
+        removed_mids,
+        session_version: description.origin.session_version,
+    })
+}
+
+/// mids returns the mid of every media description which has one, and
+/// whether it is rejected.
+fn mids(description: &SessionDescription) -> Vec<(Mid, bool)> {
+    description
+        .media_descriptions
+        .iter()
+        .filter_map(|md| md.mid().map(|mid| (mid, md.is_rejected())))
+        .collect()
+}
+
+fn errors(diagnostics: Vec<Diagnostic>) -> impl Iterator<Item = Diagnostic> {
+    diagnostics
+        .into_iter()
+        .filter(|d| d.severity == Severity::Error)
+}
+
+/// update_groups drops removed mids from every group, removing groups left
+/// empty, and appends added mids to the first BUNDLE group, if there is one.
+fn update_groups(attributes: &mut Attributes, added_mids: &[Mid], removed_mids: &[Mid]) {
+    let mut bundled = false;
+    attributes.0.retain_mut(|attribute| {
+        if !Group::matches(attribute) {
+            return true;
+        }
+        let mut group = match Group::from_attribute(attribute) {
+            Ok(group) => group,
+            Err(_) => return true,
+        };
+        let original = group.clone();
+        group.mids.retain(|m| !removed_mids.contains(m));
+        if group.semantics == GroupSemantics::Bundle && !bundled {
+            bundled = true;
+            for mid in added_mids {
+                if !group.mids.contains(mid) {
+                    group.mids.push(mid.clone());
+                }
+            }
+        }
+        // Untouched groups keep their original spelling.
+        if group != original {
+            *attribute = group.to_attribute();
+        }
+        !group.mids.is_empty()
+    });
+}
+
+/// update_wms makes the stream ids of "a=msid-semantic:WMS" follow the
+/// changes to the "a=msid" stream ids: ids no longer used are removed and
+/// new ones appended. Lists using the "*" wildcard are left alone.
+fn update_wms(description: &mut SessionDescription, snapshot: &SessionDescription) {
+    let before = stream_ids(snapshot);
+    let after = stream_ids(description);
+    if before == after {
+        return;
+    }
+    for attribute in description.attributes.0.iter_mut() {
+        if !MsidSemantic::matches(attribute) {
+            continue;
+        }
+        let mut semantic = match MsidSemantic::from_attribute(attribute) {
+            Ok(semantic) if semantic.semantic == MSID_SEMANTIC_WMS => semantic,
+            _ => continue,
+        };
+        if semantic.stream_ids.iter().any(|id| id == "*") {
+            continue;
+        }
+        semantic
+            .stream_ids
+            .retain(|id| after.contains(id) || !before.contains(id));
+        for id in &after {
+            if !before.contains(id) && !semantic.stream_ids.contains(id) {
+                semantic.stream_ids.push(id.clone());
+            }
+        }
+        *attribute = semantic.to_attribute();
+    }
+}
+
+/// stream_ids returns the distinct "a=msid" stream ids of the media
+/// descriptions which are not rejected, in order.
+fn stream_ids(description: &SessionDescription) -> Vec<String> {
+    let mut ids: Vec<String> = vec![];
+    for md in &description.media_descriptions {
+        if md.is_rejected() {
+            continue;
+        }
+        if let Some(id) = md.msid().and_then(|msid| msid.stream_id) {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+    }
+    ids
+}
@@ -0,0 +1,171 @@
+use super::*;
+use crate::common_description::Attribute;
+use crate::diagnostic::DiagnosticKind;
+use crate::msid::Msid;
+
+const AUDIO_OFFER: &str = "v=0\r\n\
+o=- 4611731400430051336 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0\r\n\
+a=msid-semantic: WMS stream\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=mid:0\r\n\
+a=msid:stream audio\r\n\
+a=rtpmap:111 opus/48000/2\r\n";
+
+fn video(mid: &str, stream_id: &str) -> Result<MediaDescription, Error> {
+    let mut md = MediaDescription::new("video".to_owned(), vec![]).with_codec(
+        96,
+        "VP8".to_owned(),
+        90000,
+        0,
+        String::new(),
+    );
+    md.set_mid(Mid::try_new(mid)?);
+    md.attributes.insert(Msid {
+        stream_id: Some(stream_id.to_owned()),
+        track_id: Some("video".to_owned()),
+    });
+    Ok(md)
+}
+
+#[test]
+fn test_edit_add_media() -> Result<(), Error> {
+    let mut sd = AUDIO_OFFER.parse::<SessionDescription>()?;
+
+    let mut editor = sd.edit();
+    editor.add_media(video("1", "screen")?);
+    let summary = editor.commit()?;
+
+    assert_eq!(summary.added_mids, vec![Mid::try_new("1")?]);
+    assert!(summary.removed_mids.is_empty());
+    assert_eq!(summary.session_version, 3);
+    assert_eq!(sd.origin.session_version, 3);
+    let text = sd.marshal();
+    assert!(text.contains("a=group:BUNDLE 0 1\r\n"), "{}", text);
+    assert!(
+        text.contains("a=msid-semantic:WMS stream screen\r\n"),
+        "{}",
+        text
+    );
+    assert!(sd.validate().is_empty(), "{:?}", sd.validate());
+
+    Ok(())
+}
+
+#[test]
+fn test_edit_remove_media() -> Result<(), Error> {
+    let mut sd = AUDIO_OFFER.parse::<SessionDescription>()?;
+    let mut editor = sd.edit();
+    editor.add_media(video("1", "stream")?);
+    editor.commit()?;
+
+    let mut editor = sd.edit();
+    let removed = editor.remove_media("0")?;
+    assert_eq!(removed.media_name.media, "audio");
+    assert!(matches!(
+        editor.remove_media("0"),
+        Err(Error::SdpMidNotFound(_))
+    ));
+    let summary = editor.commit()?;
+
+    assert_eq!(summary.removed_mids, vec![Mid::try_new("0")?]);
+    assert_eq!(summary.session_version, 4);
+    let text = sd.marshal();
+    assert!(text.contains("a=group:BUNDLE 1\r\n"), "{}", text);
+    // The stream is still used by the video.
+    assert!(text.contains("a=msid-semantic: WMS stream\r\n"), "{}", text);
+
+    let mut editor = sd.edit();
+    editor.remove_media("1")?;
+    editor.commit()?;
+    assert!(sd.groups().is_empty());
+    assert_eq!(sd.wms_stream_ids(), Some(vec![]));
+
+    Ok(())
+}
+
+#[test]
+fn test_edit_media_mut() -> Result<(), Error> {
+    let mut sd = AUDIO_OFFER.parse::<SessionDescription>()?;
+
+    let mut editor = sd.edit();
+    assert!(editor.media_mut("1").is_none());
+    if let Some(md) = editor.media_mut("0") {
+        md.attributes
+            .push(Attribute::new("ptime".to_owned(), Some("20".to_owned())));
+    }
+    let summary = editor.commit()?;
+    assert!(summary.added_mids.is_empty());
+    assert_eq!(summary.session_version, 3);
+    // Untouched lines keep their spelling.
+    assert!(sd.marshal().contains("a=msid-semantic: WMS stream\r\n"));
+
+    // Nothing changed, nothing to bump.
+    let summary = sd.edit().commit()?;
+    assert_eq!(summary.session_version, 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_edit_abort() -> Result<(), Error> {
+    let original = AUDIO_OFFER.parse::<SessionDescription>()?;
+    let mut sd = original.clone();
+
+    let mut editor = sd.edit();
+    editor.add_media(video("1", "screen")?);
+    editor.remove_media("0")?;
+    editor.attributes_mut().0.clear();
+    editor.abort();
+    assert_eq!(sd, original);
+
+    {
+        let mut editor = sd.edit();
+        editor.remove_media("0")?;
+    }
+    assert_eq!(sd, original);
+
+    Ok(())
+}
+
+#[test]
+fn test_edit_invalid() -> Result<(), Error> {
+    let original = AUDIO_OFFER.parse::<SessionDescription>()?;
+    let mut sd = original.clone();
+
+    let mut editor = sd.edit();
+    editor.add_media(video("0", "stream")?);
+    match editor.commit() {
+        Err(Error::SdpInvalidEdit(problems)) => {
+            assert_eq!(problems, vec!["mid `0` is used more than once".to_owned()]);
+        }
+        result => panic!("{:?}", result),
+    }
+    assert_eq!(sd, original);
+
+    // A dynamic payload type without rtpmap fails validate.
+    let mut editor = sd.edit();
+    editor
+        .add_media(video("1", "stream")?)
+        .media_name
+        .formats
+        .push("97".to_owned());
+    assert!(matches!(editor.commit(), Err(Error::SdpInvalidEdit(_))));
+    assert_eq!(sd, original);
+
+    // Errors which were there before the edit don't count.
+    let mut sd = original.clone();
+    sd.time_descriptions.clear();
+    assert!(sd
+        .validate()
+        .iter()
+        .any(|d| d.kind == DiagnosticKind::MissingTiming));
+    let mut editor = sd.edit();
+    editor.add_media(video("1", "stream")?);
+    assert_eq!(editor.commit()?.session_version, 3);
+
+    Ok(())
+}
@@ -1,33 +1,558 @@
-use std::{num::ParseIntError, string::FromUtf8Error};
+use alloc::boxed::Box;
+use alloc::string::{FromUtf8Error, String};
+use alloc::vec::Vec;
+use core::fmt;
+use core::num::ParseIntError;
 
-use thiserror::Error;
+#[cfg(test)]
+mod error_test;
 
-#[derive(Debug, Error)]
+/// Error is the error of every fallible function of the crate. Messages
+/// start with "sdp: ", are lowercase and don't end with a period, and wrapped
+/// errors are available through core::error::Error::source.
+#[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
-    #[error("codec not found")]
+    /// CodecNotFound is returned when no codec matches a payload type.
     CodecNotFound,
-    #[error("could not extract codec from rtcp-fb")]
+    /// RtcpFb is returned when the payload type of an "a=rtcp-fb" attribute
+    /// can't be read.
     RtcpFb,
-    #[error("could not extract codec from fmtp")]
+    /// FmtpParse is returned when the payload type of an "a=fmtp" attribute
+    /// can't be read.
     FmtpParse,
-    #[error("could not extract codec from rtpmap")]
+    /// RtpmapParse is returned when an "a=rtpmap" attribute can't be read.
     RtpmapParse,
-    #[error("payload type not found")]
+    /// PayloadTypeNotFound is returned when a payload type is not among the
+    /// formats of a media description.
     PayloadTypeNotFound,
-    #[error("SyntaxError: {0}")]
+    /// ExtMapParse is returned for an "a=extmap" attribute with invalid syntax.
     ExtMapParse(String),
-    #[error("sdp: empty time_descriptions")]
+    /// SdpInvalidDirection is returned for an unknown direction attribute.
+    SdpInvalidDirection(String),
+    /// SdpInvalidExtMapId is returned for an "a=extmap" id outside the allowed
+    /// ranges.
+    SdpInvalidExtMapId(u16),
+    /// SdpInvalidRtpMap is returned for a malformed "a=rtpmap" value.
+    SdpInvalidRtpMap(String),
+    /// SdpInvalidFmtp is returned for a malformed "a=fmtp" value.
+    SdpInvalidFmtp(String),
+    /// SdpUnknownKeyMethod is returned for a "k=" method other than clear,
+    /// base64, uri and prompt.
+    SdpUnknownKeyMethod(String),
+    /// SdpInvalidBase64Key is returned for a "k=base64:" key which is not valid
+    /// base64.
+    SdpInvalidBase64Key,
+    /// SdpKeyNotBase64 is returned when a key of the given method is not base64
+    /// encoded.
+    SdpKeyNotBase64(String),
+    /// SdpSessionVersionOverflow is returned when the `<sess-version>` of "o="
+    /// can't be bumped any further.
+    SdpSessionVersionOverflow,
+    /// SdpUnsupportedVersion is returned for a "v=" other than 0.
+    SdpUnsupportedVersion {
+        /// found is the version of the description.
+        found: u64,
+    },
+    /// SdpInvalidMaxPRate is returned for a malformed "a=maxprate" value.
+    SdpInvalidMaxPRate(String),
+    /// SdpInvalidTool is returned for an empty or malformed "a=tool" value.
+    SdpInvalidTool(String),
+    /// SdpInvalidConferenceType is returned for an "a=type" value which is not
+    /// a token.
+    SdpInvalidConferenceType(String),
+    /// SdpInvalidOrient is returned for an unknown "a=orient" value.
+    SdpInvalidOrient(String),
+    /// SdpInvalidFrameRate is returned for a malformed "a=framerate" value.
+    SdpInvalidFrameRate(String),
+    /// SdpInvalidQuality is returned for an "a=quality" value outside 0 to 10.
+    SdpInvalidQuality(String),
+    /// SdpInvalidLanguageTag is returned for a malformed language tag.
+    SdpInvalidLanguageTag(String),
+    /// SdpInvalidPacketTime is returned for a malformed "a=ptime" or
+    /// "a=maxptime" value.
+    SdpInvalidPacketTime(String),
+    /// SdpInvalidCandidate is returned for a malformed "a=candidate" value.
+    SdpInvalidCandidate(String),
+    /// SdpInvalidCandidateComponent is returned for a candidate component id
+    /// outside 1 to 256.
+    SdpInvalidCandidateComponent(u16),
+    /// SdpInvalidCandidatePriority is returned for a candidate priority of 0.
+    SdpInvalidCandidatePriority,
+    /// SdpInvalidRemoteCandidates is returned for a malformed
+    /// "a=remote-candidates" value.
+    SdpInvalidRemoteCandidates(String),
+    /// SdpInvalidIceUfrag is returned for a malformed "a=ice-ufrag" value.
+    SdpInvalidIceUfrag(String),
+    /// SdpInvalidIcePwd is returned for a malformed "a=ice-pwd" value. The
+    /// password itself is left out of the error.
+    SdpInvalidIcePwd,
+    /// SdpInvalidIceOptions is returned for a malformed "a=ice-options" value.
+    SdpInvalidIceOptions(String),
+    /// SdpInvalidFingerprint is returned for a malformed "a=fingerprint" value.
+    SdpInvalidFingerprint(String),
+    /// SdpFingerprintLengthMismatch is returned when a fingerprint has the
+    /// wrong length for its hash function.
+    SdpFingerprintLengthMismatch {
+        /// hash_function is the hash function of the fingerprint.
+        hash_function: String,
+        /// expected is the length in bytes of a digest of the hash function.
+        expected: usize,
+        /// found is the length in bytes of the fingerprint.
+        found: usize,
+    },
+    /// SdpInvalidSetup is returned for an unknown "a=setup" value.
+    SdpInvalidSetup(String),
+    /// SdpInvalidConnection is returned for an unknown "a=connection" value.
+    SdpInvalidConnection(String),
+    /// SdpIncompatibleSetup is returned when an answer's "a=setup" role doesn't
+    /// fit the offered one.
+    SdpIncompatibleSetup {
+        /// offer is the offered role.
+        offer: String,
+        /// answer is the answered role.
+        answer: String,
+    },
+    /// SdpInvalidMid is returned for an empty or malformed "a=mid" value.
+    SdpInvalidMid(String),
+    /// SdpInvalidGroup is returned for a malformed "a=group" value.
+    SdpInvalidGroup(String),
+    /// SdpInvalidRtcp is returned for a malformed "a=rtcp" value.
+    SdpInvalidRtcp(String),
+    /// SdpInvalidSsrc is returned for a malformed "a=ssrc" value.
+    SdpInvalidSsrc(String),
+    /// SdpInvalidSsrcGroup is returned for a malformed "a=ssrc-group" value.
+    SdpInvalidSsrcGroup(String),
+    /// SdpInvalidMsid is returned for a malformed "a=msid" value.
+    SdpInvalidMsid(String),
+    /// SdpInvalidMsidSemantic is returned for a malformed "a=msid-semantic"
+    /// value.
+    SdpInvalidMsidSemantic(String),
+    /// SdpInvalidRtcpFeedback is returned for a malformed "a=rtcp-fb" value.
+    SdpInvalidRtcpFeedback(String),
+    /// SdpInvalidRid is returned for a malformed "a=rid" value.
+    SdpInvalidRid(String),
+    /// SdpInvalidSimulcast is returned for a malformed "a=simulcast" value.
+    SdpInvalidSimulcast(String),
+    /// SdpInvalidSctpPort is returned for a malformed "a=sctp-port" value.
+    SdpInvalidSctpPort(String),
+    /// SdpInvalidMaxMessageSize is returned for a malformed
+    /// "a=max-message-size" value.
+    SdpInvalidMaxMessageSize(String),
+    /// SdpInvalidSctpMap is returned for a malformed "a=sctpmap" value.
+    SdpInvalidSctpMap(String),
+    /// SdpInvalidKeyMgmt is returned for a malformed "a=key-mgmt" value.
+    SdpInvalidKeyMgmt(String),
+    /// SdpInvalidCrypto is returned for a malformed "a=crypto" value.
+    SdpInvalidCrypto(String),
+    /// SdpInvalidOffer is returned when an answer can't be made for an offer.
+    SdpInvalidOffer(String),
+    /// SdpPayloadTypeNotFound is returned when a payload type is not among the
+    /// formats of a media description.
+    SdpPayloadTypeNotFound(u8),
+    /// SdpPayloadTypeInUse is returned when a payload type is already taken by
+    /// another codec.
+    SdpPayloadTypeInUse(u8),
+    /// SdpPayloadTypesExhausted is returned when a payload type has to be
+    /// renumbered but no dynamic payload type is left.
+    SdpPayloadTypesExhausted(u8),
+    /// SdpExtMapIdNotFound is returned when no "a=extmap" of a media
+    /// description has the given id.
+    SdpExtMapIdNotFound(u16),
+    /// SdpExtMapIdInUse is returned when an extmap id is already taken by
+    /// another header extension.
+    SdpExtMapIdInUse(u16),
+    /// SdpExtMapIdsExhausted is returned when an extmap id has to be
+    /// renumbered but no id of the same header size is left.
+    SdpExtMapIdsExhausted(u16),
+    /// SdpDuplicateMid is returned when media descriptions to be put into one
+    /// session description share a mid.
+    SdpDuplicateMid(String),
+    /// SdpMidNotFound is returned when no media description has the given mid.
+    SdpMidNotFound(String),
+    /// SdpHoldStateMismatch is returned when held media descriptions are no
+    /// longer in the description they are resumed in.
+    SdpHoldStateMismatch(Vec<String>),
+    /// SdpInvalidEdit is returned when a transactional edit was rolled back and
+    /// lists why.
+    SdpInvalidEdit(Vec<String>),
+    /// SdpLineTooLong is returned for a line longer than the limit of the
+    /// parse options.
+    SdpLineTooLong(usize),
+    /// SdpTooLarge is returned for a description larger than the limit of the
+    /// parse options.
+    SdpTooLarge(usize),
+    /// SdpTooManyMediaSections is returned for more media descriptions than the
+    /// limit of the parse options.
+    SdpTooManyMediaSections(usize),
+    /// SdpMissingField is returned when a mandatory field is missing.
+    SdpMissingField(String),
+    /// SdpDuplicateField is returned for a field which may appear at most
+    /// once per level, like "k=", but appears again.
+    SdpDuplicateField(String),
+    /// SdpInvalidCharset is returned for a malformed "a=charset" value.
+    SdpInvalidCharset(String),
+    /// SdpInvalidLabel is returned for a malformed "a=label" value.
+    SdpInvalidLabel(String),
+    /// SdpInvalidContent is returned for a malformed "a=content" value.
+    SdpInvalidContent(String),
+    /// SdpEmptyTimeDescription is returned when a description has no "t=" line.
     SdpEmptyTimeDescription,
-    #[error("SdpInvalidSyntax: {0}")]
+    /// SdpInvalidSyntax is returned for a line which doesn't follow the SDP
+    /// grammar.
     SdpInvalidSyntax(String),
-    #[error("SdpInvalidValue: {0}")]
+    /// SdpInvalidValue is returned for a field with an invalid value.
     SdpInvalidValue(String),
-    #[error("FromUtf8Error: {0}")]
-    Utf8Error(#[from] FromUtf8Error),
-    #[error("ParseIntError: {0}")]
-    ParseIntError(#[from] ParseIntError),
-    #[error("UrlParseError: {0}")]
-    UrlParseError(#[from] url::ParseError),
-    #[error("IoError: {0}")]
-    Io(#[from] std::io::Error),
+    /// BandwidthMissingSeparator is returned for a "b=" line without ":".
+    BandwidthMissingSeparator(String),
+    /// BandwidthInvalidType is returned for a "b=" line with an empty or
+    /// invalid `<bwtype>`.
+    BandwidthInvalidType(String),
+    /// BandwidthInvalidValue is returned for a "b=" line whose `<bandwidth>` is
+    /// not a number.
+    BandwidthInvalidValue(String),
+    /// BandwidthOverflow is returned for a "b=" line whose `<bandwidth>` doesn't
+    /// fit in 64 bits.
+    BandwidthOverflow(String),
+    /// SdpInvalidTiming is returned for a "t=" line which stops before it
+    /// starts.
+    SdpInvalidTiming {
+        /// start is the `<start-time>` of the line.
+        start: u64,
+        /// stop is the `<stop-time>` of the line.
+        stop: u64,
+    },
+    /// SdpUnboundedSession is returned for a computation which needs the
+    /// session to have a stop time.
+    SdpUnboundedSession,
+    /// SdpNonWholeSeconds is returned for a duration which is not a whole
+    /// number of seconds.
+    SdpNonWholeSeconds(String),
+    /// SdpInvalidConnectionAddress is returned for a malformed "c=" address.
+    SdpInvalidConnectionAddress(String),
+    /// SdpAddressTypeMismatch is returned when an address doesn't match its
+    /// `<addrtype>`.
+    SdpAddressTypeMismatch {
+        /// address_type is the `<addrtype>` of the line.
+        address_type: String,
+        /// address is the mismatched address.
+        address: String,
+    },
+    /// SdpInvalidFqdn is returned for a malformed domain name.
+    SdpInvalidFqdn(String),
+    /// SdpInvalidRepeatTime is returned for a malformed "r=" line.
+    SdpInvalidRepeatTime(String),
+    /// SdpInvalidPhoneNumber is returned for a malformed "p=" line.
+    SdpInvalidPhoneNumber(String),
+    /// SdpEmailMissingAt is returned for an "e=" address without "@".
+    SdpEmailMissingAt(String),
+    /// SdpEmailInvalidChar is returned for an "e=" address containing
+    /// whitespace or line breaks.
+    SdpEmailInvalidChar(String),
+    /// SdpInvalidUsername is returned for an empty "o=" `<username>` or one
+    /// containing whitespace or control characters.
+    SdpInvalidUsername(String),
+    /// SdpInvalidAttributeName is returned for an attribute name which is not a
+    /// token.
+    SdpInvalidAttributeName(String),
+    /// SdpInvalidCharacter is returned for a value containing a line break or
+    /// NUL character.
+    SdpInvalidCharacter(String),
+    /// SdpUnexpectedLineBreak is returned for a field containing CR or LF.
+    SdpUnexpectedLineBreak(String),
+    /// SdpUnbalancedDelimiters is returned for a field with unbalanced brackets
+    /// or quotes.
+    SdpUnbalancedDelimiters(String),
+    /// SdpParse wraps an error with the line it was found on.
+    SdpParse {
+        /// line is the 1-based number of the line.
+        line: usize,
+        /// line_type is the type of the line, if it has one.
+        line_type: Option<char>,
+        /// source is the error found on the line.
+        source: Box<Error>,
+    },
+    /// Utf8Error is returned for a description which is not valid UTF-8.
+    Utf8Error(FromUtf8Error),
+    /// ParseIntError is returned for a malformed number.
+    ParseIntError(ParseIntError),
+    /// UrlParseError is returned for a malformed URI.
+    UrlParseError(url::ParseError),
+    /// Io is returned when reading or writing a description fails.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::CodecNotFound => write!(f, "sdp: codec not found"),
+            Error::RtcpFb => write!(f, "sdp: could not extract codec from rtcp-fb"),
+            Error::FmtpParse => write!(f, "sdp: could not extract codec from fmtp"),
+            Error::RtpmapParse => write!(f, "sdp: could not extract codec from rtpmap"),
+            Error::PayloadTypeNotFound => write!(f, "sdp: payload type not found"),
+            Error::ExtMapParse(value) => write!(f, "sdp: invalid extmap syntax: {}", value),
+            Error::SdpInvalidDirection(value) => write!(f, "sdp: unknown direction `{}`", value),
+            Error::SdpInvalidExtMapId(value) => write!(
+                f,
+                "sdp: extmap id {} is outside the ranges 1-14, 16-255 and 4096-4351",
+                value
+            ),
+            Error::SdpInvalidRtpMap(value) => write!(f, "sdp: invalid rtpmap `{}`", value),
+            Error::SdpInvalidFmtp(value) => write!(f, "sdp: invalid fmtp `{}`", value),
+            Error::SdpUnknownKeyMethod(value) => {
+                write!(f, "sdp: unknown encryption key method `{}`", value)
+            }
+            Error::SdpInvalidBase64Key => write!(f, "sdp: encryption key is not valid base64"),
+            Error::SdpKeyNotBase64(value) => {
+                write!(f, "sdp: `{}` encryption key is not base64 encoded", value)
+            }
+            Error::SdpSessionVersionOverflow => write!(f, "sdp: session version overflow"),
+            Error::SdpUnsupportedVersion { found } => {
+                write!(f, "sdp: unsupported protocol version {}", found)
+            }
+            Error::SdpInvalidMaxPRate(value) => write!(f, "sdp: invalid maxprate `{}`", value),
+            Error::SdpInvalidTool(value) => write!(f, "sdp: invalid tool `{}`", value),
+            Error::SdpInvalidConferenceType(value) => {
+                write!(f, "sdp: invalid conference type `{}`", value)
+            }
+            Error::SdpInvalidOrient(value) => write!(f, "sdp: invalid orientation `{}`", value),
+            Error::SdpInvalidFrameRate(value) => write!(f, "sdp: invalid frame rate `{}`", value),
+            Error::SdpInvalidQuality(value) => {
+                write!(f, "sdp: invalid quality `{}`, expected 0 to 10", value)
+            }
+            Error::SdpInvalidLanguageTag(value) => {
+                write!(f, "sdp: invalid language tag `{}`", value)
+            }
+            Error::SdpInvalidPacketTime(value) => write!(f, "sdp: invalid packet time `{}`", value),
+            Error::SdpInvalidCandidate(value) => write!(f, "sdp: invalid candidate `{}`", value),
+            Error::SdpInvalidCandidateComponent(value) => {
+                write!(f, "sdp: candidate component id {} is outside 1-256", value)
+            }
+            Error::SdpInvalidCandidatePriority => {
+                write!(f, "sdp: candidate priority must not be 0")
+            }
+            Error::SdpInvalidRemoteCandidates(value) => {
+                write!(f, "sdp: invalid remote-candidates `{}`", value)
+            }
+            Error::SdpInvalidIceUfrag(value) => {
+                write!(f, "sdp: ice-ufrag `{}` must be 4 to 256 ice-chars", value)
+            }
+            Error::SdpInvalidIcePwd => write!(f, "sdp: ice-pwd must be 22 to 256 ice-chars"),
+            Error::SdpInvalidIceOptions(value) => write!(f, "sdp: invalid ice-options `{}`", value),
+            Error::SdpInvalidFingerprint(value) => {
+                write!(f, "sdp: invalid fingerprint `{}`", value)
+            }
+            Error::SdpFingerprintLengthMismatch {
+                hash_function,
+                expected,
+                found,
+            } => write!(
+                f,
+                "sdp: {} fingerprint must be {} bytes, found {}",
+                hash_function, expected, found
+            ),
+            Error::SdpInvalidSetup(value) => write!(f, "sdp: invalid setup `{}`", value),
+            Error::SdpInvalidConnection(value) => write!(f, "sdp: invalid connection `{}`", value),
+            Error::SdpIncompatibleSetup { offer, answer } => write!(
+                f,
+                "sdp: setup `{}` is not a valid answer to `{}`",
+                answer, offer
+            ),
+            Error::SdpInvalidMid(value) => write!(f, "sdp: invalid mid `{}`", value),
+            Error::SdpInvalidGroup(value) => write!(f, "sdp: invalid group `{}`", value),
+            Error::SdpInvalidRtcp(value) => write!(f, "sdp: invalid rtcp `{}`", value),
+            Error::SdpInvalidSsrc(value) => write!(f, "sdp: invalid ssrc `{}`", value),
+            Error::SdpInvalidSsrcGroup(value) => write!(f, "sdp: invalid ssrc-group `{}`", value),
+            Error::SdpInvalidMsid(value) => write!(f, "sdp: invalid msid `{}`", value),
+            Error::SdpInvalidMsidSemantic(value) => {
+                write!(f, "sdp: invalid msid-semantic `{}`", value)
+            }
+            Error::SdpInvalidRtcpFeedback(value) => write!(f, "sdp: invalid rtcp-fb `{}`", value),
+            Error::SdpInvalidRid(value) => write!(f, "sdp: invalid rid `{}`", value),
+            Error::SdpInvalidSimulcast(value) => write!(f, "sdp: invalid simulcast `{}`", value),
+            Error::SdpInvalidSctpPort(value) => write!(f, "sdp: invalid sctp-port `{}`", value),
+            Error::SdpInvalidMaxMessageSize(value) => {
+                write!(f, "sdp: invalid max-message-size `{}`", value)
+            }
+            Error::SdpInvalidSctpMap(value) => write!(f, "sdp: invalid sctpmap `{}`", value),
+            Error::SdpInvalidKeyMgmt(value) => {
+                write!(f, "sdp: invalid key-mgmt for protocol `{}`", value)
+            }
+            Error::SdpInvalidCrypto(value) => write!(f, "sdp: invalid crypto `{}`", value),
+            Error::SdpInvalidOffer(value) => write!(f, "sdp: invalid offer: {}", value),
+            Error::SdpPayloadTypeNotFound(value) => write!(
+                f,
+                "sdp: payload type {} is not in the media description",
+                value
+            ),
+            Error::SdpPayloadTypeInUse(value) => {
+                write!(f, "sdp: payload type {} is already in use", value)
+            }
+            Error::SdpPayloadTypesExhausted(value) => write!(
+                f,
+                "sdp: no free dynamic payload type left to renumber payload type {}",
+                value
+            ),
+            Error::SdpExtMapIdNotFound(value) => {
+                write!(
+                    f,
+                    "sdp: extmap id {} is not in the media description",
+                    value
+                )
+            }
+            Error::SdpExtMapIdInUse(value) => {
+                write!(f, "sdp: extmap id {} is already in use", value)
+            }
+            Error::SdpExtMapIdsExhausted(value) => {
+                write!(
+                    f,
+                    "sdp: no free extmap id left to renumber extmap id {}",
+                    value
+                )
+            }
+            Error::SdpDuplicateMid(value) => write!(f, "sdp: duplicate mid `{}`", value),
+            Error::SdpMidNotFound(value) => {
+                write!(f, "sdp: no media description with mid `{}`", value)
+            }
+            Error::SdpHoldStateMismatch(values) => write!(
+                f,
+                "sdp: cannot resume media descriptions no longer present: {}",
+                values.join(", ")
+            ),
+            Error::SdpInvalidEdit(problems) => {
+                write!(f, "sdp: edit rolled back: {}", problems.join("; "))
+            }
+            Error::SdpLineTooLong(value) => write!(f, "sdp: line is longer than {} bytes", value),
+            Error::SdpTooLarge(value) => {
+                write!(f, "sdp: session description is larger than {} bytes", value)
+            }
+            Error::SdpTooManyMediaSections(value) => {
+                write!(f, "sdp: more than {} media descriptions", value)
+            }
+            Error::SdpMissingField(value) => write!(f, "sdp: missing mandatory `{}` field", value),
+            Error::SdpDuplicateField(value) => write!(f, "sdp: duplicate `{}` field", value),
+            Error::SdpInvalidCharset(value) => write!(f, "sdp: invalid charset `{}`", value),
+            Error::SdpInvalidLabel(value) => write!(f, "sdp: invalid label `{}`", value),
+            Error::SdpInvalidContent(value) => write!(f, "sdp: invalid content `{}`", value),
+            Error::SdpEmptyTimeDescription => write!(f, "sdp: empty time_descriptions"),
+            Error::SdpInvalidSyntax(value) => write!(f, "sdp: invalid syntax: {}", value),
+            Error::SdpInvalidValue(value) => write!(f, "sdp: invalid value: {}", value),
+            Error::BandwidthMissingSeparator(value) => {
+                write!(f, "sdp: bandwidth `{}` is missing the `:` separator", value)
+            }
+            Error::BandwidthInvalidType(value) => write!(
+                f,
+                "sdp: bandwidth `{}` has an empty or invalid bwtype",
+                value
+            ),
+            Error::BandwidthInvalidValue(value) => {
+                write!(f, "sdp: bandwidth `{}` has a non-numeric value", value)
+            }
+            Error::BandwidthOverflow(value) => {
+                write!(f, "sdp: bandwidth `{}` exceeds the maximum value", value)
+            }
+            Error::SdpInvalidTiming { start, stop } => {
+                write!(f, "sdp: stop time {} is before start time {}", stop, start)
+            }
+            Error::SdpUnboundedSession => write!(f, "sdp: the session has no stop time"),
+            Error::SdpNonWholeSeconds(value) => {
+                write!(f, "sdp: {} is not a whole number of seconds", value)
+            }
+            Error::SdpInvalidConnectionAddress(value) => {
+                write!(f, "sdp: invalid connection address `{}`", value)
+            }
+            Error::SdpAddressTypeMismatch {
+                address_type,
+                address,
+            } => write!(
+                f,
+                "sdp: address `{}` does not match address type {}",
+                address, address_type
+            ),
+            Error::SdpInvalidFqdn(value) => write!(f, "sdp: invalid domain name `{}`", value),
+            Error::SdpInvalidRepeatTime(value) => write!(f, "sdp: invalid repeat time: {}", value),
+            Error::SdpInvalidPhoneNumber(value) => {
+                write!(f, "sdp: invalid phone number `{}`", value)
+            }
+            Error::SdpEmailMissingAt(value) => write!(
+                f,
+                "sdp: email address `{}` must contain exactly one `@`",
+                value
+            ),
+            Error::SdpEmailInvalidChar(value) => write!(
+                f,
+                "sdp: email address `{}` contains whitespace or line breaks",
+                value
+            ),
+            Error::SdpInvalidUsername(value) => write!(f, "sdp: invalid username {:?}", value),
+            Error::SdpInvalidAttributeName(value) => {
+                write!(f, "sdp: invalid attribute name in `{}`", value)
+            }
+            Error::SdpInvalidCharacter(value) => {
+                write!(f, "sdp: {:?} contains a line break or NUL character", value)
+            }
+            Error::SdpUnexpectedLineBreak(value) => {
+                write!(f, "sdp: `{}` field contains a line break", value)
+            }
+            Error::SdpUnbalancedDelimiters(value) => {
+                write!(f, "sdp: unbalanced delimiters in `{}`", value)
+            }
+            Error::SdpParse {
+                line,
+                line_type,
+                source,
+            } => {
+                write!(f, "sdp: line {}", line)?;
+                if let Some(line_type) = line_type {
+                    write!(f, " ({}=)", line_type)?;
+                }
+                write!(f, ": {}", source)
+            }
+            Error::Utf8Error(err) => write!(f, "sdp: invalid utf-8: {}", err),
+            Error::ParseIntError(err) => write!(f, "sdp: invalid integer: {}", err),
+            Error::UrlParseError(err) => write!(f, "sdp: invalid url: {}", err),
+            #[cfg(feature = "std")]
+            Error::Io(err) => write!(f, "sdp: i/o error: {}", err),
+        }
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Error::SdpParse { source, .. } => Some(&**source),
+            Error::Utf8Error(err) => Some(err),
+            Error::ParseIntError(err) => Some(err),
+            Error::UrlParseError(err) => Some(err),
+            #[cfg(feature = "std")]
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<FromUtf8Error> for Error {
+    fn from(err: FromUtf8Error) -> Self {
+        Error::Utf8Error(err)
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(err: ParseIntError) -> Self {
+        Error::ParseIntError(err)
+    }
+}
+
+impl From<url::ParseError> for Error {
+    fn from(err: url::ParseError) -> Self {
+        Error::UrlParseError(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
 }
@@ -0,0 +1,163 @@
+use super::*;
+
+use core::error::Error as _;
+
+use crate::alloc_prelude::*;
+
+fn all_errors() -> Vec<Error> {
+    let s = || "x".to_owned();
+    #[allow(unused_mut)]
+    let mut errors = vec![
+        Error::CodecNotFound,
+        Error::RtcpFb,
+        Error::FmtpParse,
+        Error::RtpmapParse,
+        Error::PayloadTypeNotFound,
+        Error::ExtMapParse(s()),
+        Error::SdpInvalidDirection(s()),
+        Error::SdpInvalidExtMapId(0),
+        Error::SdpInvalidRtpMap(s()),
+        Error::SdpInvalidFmtp(s()),
+        Error::SdpUnknownKeyMethod(s()),
+        Error::SdpInvalidBase64Key,
+        Error::SdpKeyNotBase64(s()),
+        Error::SdpSessionVersionOverflow,
+        Error::SdpUnsupportedVersion { found: 1 },
+        Error::SdpInvalidMaxPRate(s()),
+        Error::SdpInvalidTool(s()),
+        Error::SdpInvalidConferenceType(s()),
+        Error::SdpInvalidOrient(s()),
+        Error::SdpInvalidFrameRate(s()),
+        Error::SdpInvalidQuality(s()),
+        Error::SdpInvalidLanguageTag(s()),
+        Error::SdpInvalidPacketTime(s()),
+        Error::SdpInvalidCandidate(s()),
+        Error::SdpInvalidCandidateComponent(0),
+        Error::SdpInvalidCandidatePriority,
+        Error::SdpInvalidRemoteCandidates(s()),
+        Error::SdpInvalidIceUfrag(s()),
+        Error::SdpInvalidIcePwd,
+        Error::SdpInvalidIceOptions(s()),
+        Error::SdpInvalidFingerprint(s()),
+        Error::SdpFingerprintLengthMismatch {
+            hash_function: s(),
+            expected: 20,
+            found: 1,
+        },
+        Error::SdpInvalidSetup(s()),
+        Error::SdpInvalidConnection(s()),
+        Error::SdpIncompatibleSetup {
+            offer: s(),
+            answer: s(),
+        },
+        Error::SdpInvalidMid(s()),
+        Error::SdpInvalidGroup(s()),
+        Error::SdpInvalidRtcp(s()),
+        Error::SdpInvalidSsrc(s()),
+        Error::SdpInvalidSsrcGroup(s()),
+        Error::SdpInvalidMsid(s()),
+        Error::SdpInvalidMsidSemantic(s()),
+        Error::SdpInvalidRtcpFeedback(s()),
+        Error::SdpInvalidRid(s()),
+        Error::SdpInvalidSimulcast(s()),
+        Error::SdpInvalidSctpPort(s()),
+        Error::SdpInvalidMaxMessageSize(s()),
+        Error::SdpInvalidSctpMap(s()),
+        Error::SdpInvalidKeyMgmt(s()),
+        Error::SdpInvalidCrypto(s()),
+        Error::SdpHoldStateMismatch(vec![s(), s()]),
+        Error::SdpInvalidEdit(vec![s(), s()]),
+        Error::SdpInvalidOffer(s()),
+        Error::SdpPayloadTypeNotFound(96),
+        Error::SdpPayloadTypeInUse(96),
+        Error::SdpPayloadTypesExhausted(96),
+        Error::SdpExtMapIdNotFound(1),
+        Error::SdpExtMapIdInUse(1),
+        Error::SdpExtMapIdsExhausted(1),
+        Error::SdpDuplicateMid(s()),
+        Error::SdpMidNotFound(s()),
+        Error::SdpLineTooLong(8192),
+        Error::SdpTooLarge(65536),
+        Error::SdpTooManyMediaSections(32),
+        Error::SdpMissingField(s()),
+        Error::SdpDuplicateField(s()),
+        Error::SdpInvalidCharset(s()),
+        Error::SdpInvalidLabel(s()),
+        Error::SdpInvalidContent(s()),
+        Error::SdpEmptyTimeDescription,
+        Error::SdpInvalidSyntax(s()),
+        Error::SdpInvalidValue(s()),
+        Error::BandwidthMissingSeparator(s()),
+        Error::BandwidthInvalidType(s()),
+        Error::BandwidthInvalidValue(s()),
+        Error::BandwidthOverflow(s()),
+        Error::SdpInvalidTiming { start: 2, stop: 1 },
+        Error::SdpUnboundedSession,
+        Error::SdpNonWholeSeconds(s()),
+        Error::SdpInvalidConnectionAddress(s()),
+        Error::SdpAddressTypeMismatch {
+            address_type: s(),
+            address: s(),
+        },
+        Error::SdpInvalidFqdn(s()),
+        Error::SdpInvalidRepeatTime(s()),
+        Error::SdpInvalidPhoneNumber(s()),
+        Error::SdpEmailMissingAt(s()),
+        Error::SdpEmailInvalidChar(s()),
+        Error::SdpInvalidUsername(s()),
+        Error::SdpInvalidAttributeName(s()),
+        Error::SdpInvalidCharacter(s()),
+        Error::SdpUnexpectedLineBreak(s()),
+        Error::SdpUnbalancedDelimiters(s()),
+        Error::SdpParse {
+            line: 1,
+            line_type: Some('v'),
+            source: Box::new(Error::SdpInvalidValue(s())),
+        },
+        Error::Utf8Error(String::from_utf8(vec![0xff]).unwrap_err()),
+        Error::ParseIntError("x".parse::<u8>().unwrap_err()),
+        Error::UrlParseError(url::ParseError::EmptyHost),
+    ];
+    #[cfg(feature = "std")]
+    errors.push(Error::Io(std::io::Error::other("x")));
+    errors
+}
+
+#[test]
+fn test_error_display() {
+    for err in all_errors() {
+        let message = err.to_string();
+        assert!(!message.is_empty(), "{:?}", err);
+        assert!(message.starts_with("sdp: "), "{:?}: {}", err, message);
+        assert!(!message.ends_with('.'), "{:?}: {}", err, message);
+        assert!(
+            !message.starts_with(|c: char| c.is_uppercase()),
+            "{:?}: {}",
+            err,
+            message
+        );
+    }
+}
+
+#[test]
+fn test_error_source() {
+    // The chain of a parse error leads to the cause of the field error.
+    let err = "v=0\r\no=- x 0 IN IP4 0.0.0.0\r\n"
+        .parse::<crate::session_description::SessionDescription>()
+        .expect_err("invalid session id");
+    assert!(matches!(err, Error::SdpParse { line: 2, .. }));
+    let source = err.source().expect("source");
+    assert!(matches!(
+        source.downcast_ref::<Error>(),
+        Some(Error::ParseIntError(_))
+    ));
+    assert!(source.source().expect("source").is::<ParseIntError>());
+
+    let err = Error::from("x".parse::<u8>().unwrap_err());
+    assert!(err.source().expect("source").is::<ParseIntError>());
+
+    let err = Error::from(url::Url::parse("http://").unwrap_err());
+    assert!(err.source().expect("source").is::<url::ParseError>());
+
+    assert!(Error::CodecNotFound.source().is_none());
+}
@@ -1,117 +1,240 @@
-use std::fmt;
-use std::io;
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
 
 use url::Url;
 
+use super::alloc_prelude::*;
 use super::common_description::*;
 use super::direction::*;
 use super::error::Error;
+use super::hdrext::KnownExtension;
+use super::rtcp::impl_flag_attribute;
 
 #[cfg(test)]
 mod extmap_test;
 
-// Default ext values
+/// Default ext values
 pub const DEF_EXT_MAP_VALUE_ABS_SEND_TIME: usize = 1;
+/// DEF_EXT_MAP_VALUE_TRANSPORT_CC is the default id of transport-cc.
 pub const DEF_EXT_MAP_VALUE_TRANSPORT_CC: usize = 2;
+/// DEF_EXT_MAP_VALUE_SDES_MID is the default id of the mid extension.
 pub const DEF_EXT_MAP_VALUE_SDES_MID: usize = 3;
+/// DEF_EXT_MAP_VALUE_SDES_RTP_STREAM_ID is the default id of the
+/// rtp-stream-id extension.
 pub const DEF_EXT_MAP_VALUE_SDES_RTP_STREAM_ID: usize = 4;
 
+/// ABS_SEND_TIME_URI is the URI of the absolute send time extension.
 pub const ABS_SEND_TIME_URI: &str = "http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time";
+/// TRANSPORT_CC_URI is the URI of the transport-wide congestion control
+/// extension.
 pub const TRANSPORT_CC_URI: &str =
     "http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01";
+/// SDES_MID_URI is the URI of the mid extension.
+/// <https://tools.ietf.org/html/rfc8843#section-15.1>
 pub const SDES_MID_URI: &str = "urn:ietf:params:rtp-hdrext:sdes:mid";
+/// SDES_RTP_STREAM_ID_URI is the URI of the rtp-stream-id extension.
+/// <https://tools.ietf.org/html/rfc8852#section-3.1>
 pub const SDES_RTP_STREAM_ID_URI: &str = "urn:ietf:params:rtp-hdrext:sdes:rtp-stream-id";
 
-//ExtMap represents the activation of a single RTP header extension
-#[derive(Debug, Clone, Default)]
-pub struct ExtMap {
-    pub(crate) value: isize,
-    pub(crate) direction: Direction,
-    pub(crate) uri: Option<Url>,
-    pub(crate) ext_attr: Option<String>,
-}
-
-impl fmt::Display for ExtMap {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut output = format!("{}", self.value);
-        let dirstring = self.direction.to_string();
-        if dirstring != DIRECTION_UNKNOWN_STR {
-            output += format!("/{}", dirstring).as_str();
+/// ExtMapId is the local identifier of an RTP header extension. One-byte
+/// headers use 1-14, two-byte headers 1-255, and 4096-4351 is reserved for
+/// use in negotiation. 15 is reserved and 0 is padding.
+/// <https://tools.ietf.org/html/rfc8285#section-5>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::extmap::ExtMapId;
+///
+/// # fn main() -> Result<(), Error> {
+/// let id = "16".parse::<ExtMapId>()?;
+/// assert!(id.requires_two_byte_header());
+/// assert_eq!(ExtMapId::try_new(1)?.to_string(), "1");
+/// assert!(ExtMapId::try_new(15).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExtMapId(pub(crate) u16);
+
+impl ExtMapId {
+    /// try_new creates an id, rejecting ids outside the ranges above.
+    pub fn try_new(id: u16) -> Result<Self, Error> {
+        if (1..=14).contains(&id) || (16..=255).contains(&id) || (4096..=4351).contains(&id) {
+            Ok(ExtMapId(id))
+        } else {
+            Err(Error::SdpInvalidExtMapId(id))
         }
+    }
 
-        if let Some(uri) = &self.uri {
-            output += format!(" {}", uri).as_str();
-        }
+    /// new_unchecked creates an id without checking the ranges above, which
+    /// the caller has to ensure. An id outside of them is written as is and
+    /// rejected by peers.
+    pub const fn new_unchecked(id: u16) -> Self {
+        ExtMapId(id)
+    }
 
-        if let Some(ext_attr) = &self.ext_attr {
-            output += format!(" {}", ext_attr).as_str();
-        }
+    /// get returns the id.
+    pub const fn get(&self) -> u16 {
+        self.0
+    }
 
-        write!(f, "{}", output)
+    /// requires_two_byte_header returns true if the id doesn't fit into a
+    /// one-byte header, so that mixing it with one-byte extensions needs
+    /// "a=extmap-allow-mixed".
+    /// <https://tools.ietf.org/html/rfc8285#section-6>
+    pub const fn requires_two_byte_header(&self) -> bool {
+        self.0 > 14
     }
 }
 
-impl ExtMap {
-    //Clone converts this object to an Attribute
-    pub fn convert(&self) -> Attribute {
-        Attribute {
-            key: "extmap".to_string(),
-            value: Some(self.to_string()),
-        }
+impl fmt::Display for ExtMapId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
     }
+}
 
-    //Unmarshal creates an Extmap from a string
-    pub fn unmarshal<R: io::BufRead>(reader: &mut R) -> Result<Self, Error> {
-        let mut line = String::new();
-        reader.read_line(&mut line)?;
-        let parts: Vec<&str> = line.trim().splitn(2, ':').collect();
-        if parts.len() != 2 {
-            return Err(Error::ExtMapParse(line));
-        }
+impl FromStr for ExtMapId {
+    type Err = Error;
 
-        let fields: Vec<&str> = parts[1].split_whitespace().collect();
-        if fields.len() < 2 {
-            return Err(Error::ExtMapParse(line));
-        }
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ExtMapId::try_new(value.parse::<u16>()?)
+    }
+}
 
-        let valdir: Vec<&str> = fields[0].split('/').collect();
-        let value = valdir[0].parse::<isize>()?;
-        if value < 1 || value > 246 {
-            return Err(Error::ExtMapParse(format!(
-                "{} -- extmap key must be in the range 1-256",
-                valdir[0]
-            )));
-        }
+impl TryFrom<u16> for ExtMapId {
+    type Error = Error;
+
+    fn try_from(id: u16) -> Result<Self, Self::Error> {
+        ExtMapId::try_new(id)
+    }
+}
+
+///ExtMap represents the activation of a single RTP header extension
+///`a=extmap:<value>["/"<direction>] <URI> <extensionattributes>`
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::direction::Direction;
+/// use webrtc_rs_sdp::extmap::ExtMap;
+///
+/// # fn main() -> Result<(), Error> {
+/// let extmap = "2/sendonly urn:ietf:params:rtp-hdrext:sdes:mid".parse::<ExtMap>()?;
+/// assert_eq!(extmap.id.get(), 2);
+/// assert_eq!(extmap.direction, Some(Direction::SendOnly));
+/// assert_eq!(
+///     extmap.to_string(),
+///     "2/sendonly urn:ietf:params:rtp-hdrext:sdes:mid"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtMap {
+    /// id is the local identifier of the extension.
+    pub id: ExtMapId,
+    /// direction limits the direction the extension is used in.
+    pub direction: Option<Direction>,
+    /// uri identifies the extension.
+    pub uri: Url,
+    /// attributes are the extension attributes, kept as written.
+    pub attributes: Option<String>,
+}
 
-        let mut direction = Direction::DirectionUnknown;
-        if valdir.len() == 2 {
-            direction = Direction::new(valdir[1]);
-            if direction == Direction::DirectionUnknown {
-                return Err(Error::ExtMapParse(format!(
-                    "unknown direction from {}",
-                    valdir[1]
-                )));
-            }
+impl fmt::Display for ExtMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id)?;
+        if let Some(direction) = &self.direction {
+            write!(f, "/{}", direction)?;
+        }
+        write!(f, " {}", self.uri)?;
+        if let Some(attributes) = &self.attributes {
+            write!(f, " {}", attributes)?;
         }
+        Ok(())
+    }
+}
 
-        let uri = Some(Url::parse(fields[1])?);
+impl FromStr for ExtMap {
+    type Err = Error;
+
+    /// from_str parses the value of an "a=extmap:" attribute, i.e. without
+    /// the "extmap:" prefix.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let (valdir, rest) = value
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| Error::ExtMapParse(value.to_owned()))?;
+        let rest = rest.trim_start();
+        let (uri, attributes) = match rest.split_once(char::is_whitespace) {
+            Some((uri, attributes)) => (uri, Some(attributes.trim_start())),
+            None => (rest, None),
+        };
 
-        let ext_attr = if fields.len() == 3 {
-            Some(fields[2].to_owned())
-        } else {
-            None
+        let (id, direction) = match valdir.split_once('/') {
+            Some((id, direction)) => (id, Some(direction.parse::<Direction>()?)),
+            None => (valdir, None),
         };
 
         Ok(ExtMap {
-            value,
+            id: id.parse()?,
             direction,
-            uri,
-            ext_attr,
+            uri: Url::parse(uri)?,
+            attributes: attributes.map(str::to_owned),
         })
     }
+}
 
-    //Marshal creates a string from an ExtMap
-    pub fn marshal(&self) -> String {
-        "extmap:".to_string() + self.to_string().as_str()
+impl TypedAttribute for ExtMap {
+    const NAME: &'static str = "extmap";
+
+    fn describe(&self) -> Option<String> {
+        let size = if self.id.requires_two_byte_header() {
+            "two-byte"
+        } else {
+            "one-byte"
+        };
+        Some(match self.known() {
+            Some(known) => format!("{}, {} id", known.name(), size),
+            None => format!("header extension, {} id", size),
+        })
+    }
+}
+
+/// ExtmapAllowMixed is the "a=extmap-allow-mixed" attribute, allowing one-byte
+/// and two-byte header extensions in the same RTP stream. It can appear at
+/// session and media level.
+/// <https://tools.ietf.org/html/rfc8285#section-6>
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ExtmapAllowMixed;
+
+impl_flag_attribute!(ExtmapAllowMixed, "extmap-allow-mixed");
+
+impl ExtMap {
+    /// for_known creates the extmap of a well-known header extension.
+    pub fn for_known(id: ExtMapId, known: KnownExtension) -> Self {
+        ExtMap {
+            id,
+            direction: None,
+            uri: known.to_url(),
+            attributes: None,
+        }
+    }
+
+    /// known returns the well-known header extension this extmap activates.
+    pub fn known(&self) -> Option<KnownExtension> {
+        KnownExtension::from_uri(self.uri.as_str())
+    }
+
+    ///Clone converts this object to an Attribute
+    pub fn convert(&self) -> Attribute {
+        Attribute {
+            key: "extmap".into(),
+            value: Some(self.to_string().into()),
+        }
     }
 }
@@ -1,53 +1,97 @@
 use super::*;
-use crate::util::{ATTRIBUTE_KEY, END_LINE};
 
-use std::io::BufReader;
-use std::iter::Iterator;
+use core::iter::Iterator;
 
-const EXAMPLE_ATTR_EXTMAP1: &'static str = "extmap:1 http://example.com/082005/ext.htm#ttime";
-const EXAMPLE_ATTR_EXTMAP2: &'static str =
-    "extmap:2/sendrecv http://example.com/082005/ext.htm#xmeta short";
-const FAILING_ATTR_EXTMAP1: &'static str =
-    "extmap:257/sendrecv http://example.com/082005/ext.htm#xmeta short";
-const FAILING_ATTR_EXTMAP2: &'static str =
-    "extmap:2/blorg http://example.com/082005/ext.htm#xmeta short";
+const EXAMPLE_ATTR_EXTMAP1: &str = "1 http://example.com/082005/ext.htm#ttime";
+const EXAMPLE_ATTR_EXTMAP2: &str = "2/sendrecv http://example.com/082005/ext.htm#xmeta short";
+const EXAMPLE_ATTR_EXTMAP3: &str = "4096/recvonly urn:ietf:params:rtp-hdrext:sdes:mid a b";
+const FAILING_ATTR_EXTMAP1: &str = "257/sendrecv http://example.com/082005/ext.htm#xmeta short";
+const FAILING_ATTR_EXTMAP2: &str = "2/blorg http://example.com/082005/ext.htm#xmeta short";
+const FAILING_ATTR_EXTMAP3: &str = "2/sendrecv";
+const FAILING_ATTR_EXTMAP4: &str = "0 urn:ietf:params:rtp-hdrext:sdes:mid";
 
 #[test]
 fn test_extmap() -> Result<(), Error> {
-    let example_attr_extmap1_line = EXAMPLE_ATTR_EXTMAP1;
-    let example_attr_extmap2_line = EXAMPLE_ATTR_EXTMAP2;
-    let failing_attr_extmap1_line =
-        format!("{}{}{}", ATTRIBUTE_KEY, FAILING_ATTR_EXTMAP1, END_LINE);
-    let failing_attr_extmap2_line =
-        format!("{}{}{}", ATTRIBUTE_KEY, FAILING_ATTR_EXTMAP2, END_LINE);
-    let passingtests = vec![
-        (EXAMPLE_ATTR_EXTMAP1, example_attr_extmap1_line),
-        (EXAMPLE_ATTR_EXTMAP2, example_attr_extmap2_line),
+    let passingtests = [
+        EXAMPLE_ATTR_EXTMAP1,
+        EXAMPLE_ATTR_EXTMAP2,
+        EXAMPLE_ATTR_EXTMAP3,
     ];
-    let failingtests = vec![
-        (FAILING_ATTR_EXTMAP1, failing_attr_extmap1_line),
-        (FAILING_ATTR_EXTMAP2, failing_attr_extmap2_line),
+    let failingtests = [
+        FAILING_ATTR_EXTMAP1,
+        FAILING_ATTR_EXTMAP2,
+        FAILING_ATTR_EXTMAP3,
+        FAILING_ATTR_EXTMAP4,
     ];
 
-    for (i, u) in passingtests.iter().enumerate() {
-        let mut reader = BufReader::new(u.1.as_bytes());
-        let actual = ExtMap::unmarshal(&mut reader)?;
-        assert_eq!(
-            u.1,
-            actual.marshal(),
-            "{}: {} vs {}",
-            i,
-            u.1,
-            actual.marshal()
-        );
+    for (i, &u) in passingtests.iter().enumerate() {
+        let actual = u.parse::<ExtMap>()?;
+        assert_eq!(u, actual.to_string(), "{}: {}", i, u);
     }
 
-    for u in failingtests {
-        let mut reader = BufReader::new(u.1.as_bytes());
-        let actual = ExtMap::unmarshal(&mut reader);
-        assert!(actual.is_err());
+    for &u in failingtests.iter() {
+        assert!(u.parse::<ExtMap>().is_err(), "{}", u);
     }
 
+    let extmap = EXAMPLE_ATTR_EXTMAP2.parse::<ExtMap>()?;
+    assert_eq!(extmap.id.get(), 2);
+    assert_eq!(extmap.direction, Some(Direction::SendRecv));
+    assert_eq!(extmap.attributes.as_deref(), Some("short"));
+
+    assert!(matches!(
+        FAILING_ATTR_EXTMAP1.parse::<ExtMap>(),
+        Err(Error::SdpInvalidExtMapId(257))
+    ));
+    assert!(matches!(
+        FAILING_ATTR_EXTMAP2.parse::<ExtMap>(),
+        Err(Error::SdpInvalidDirection(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_extmap_id() -> Result<(), Error> {
+    // (id, requires a two-byte header)
+    let passingtests = [
+        (1, false),
+        (14, false),
+        (16, true),
+        (255, true),
+        (4096, true),
+        (4351, true),
+    ];
+    let failingtests = [0, 15, 256, 4095, 4352];
+
+    for &(u, two_byte) in passingtests.iter() {
+        let id = ExtMapId::try_new(u)?;
+        assert_eq!(id.requires_two_byte_header(), two_byte, "{}", u);
+        let extmap = format!("{} urn:ietf:params:rtp-hdrext:sdes:mid", u);
+        assert_eq!(extmap.parse::<ExtMap>()?.id, id);
+    }
+    for &u in failingtests.iter() {
+        assert!(
+            matches!(ExtMapId::try_new(u), Err(Error::SdpInvalidExtMapId(id)) if id == u),
+            "{}",
+            u
+        );
+        let extmap = format!("{} urn:ietf:params:rtp-hdrext:sdes:mid", u);
+        assert!(extmap.parse::<ExtMap>().is_err(), "{}", u);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_extmap_allow_mixed() -> Result<(), Error> {
+    let attribute = ExtmapAllowMixed.to_attribute();
+    assert_eq!(attribute.to_string(), "extmap-allow-mixed");
+    assert!(ExtmapAllowMixed::matches(&attribute));
+    assert_eq!(
+        ExtmapAllowMixed::from_attribute(&attribute)?,
+        ExtmapAllowMixed
+    );
+
     Ok(())
 }
 
@@ -55,25 +99,19 @@ fn test_extmap() -> Result<(), Error> {
 fn test_transport_cc_extmap() -> Result<(), Error> {
     //a=extmap:<value>["/"<direction>] <URI> <extensionattributes>
     //a=extmap:3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01
-    let uri = Some(Url::parse(
-        "http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01",
-    )?);
+    let uri =
+        Url::parse("http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01")?;
     let e = ExtMap {
-        value: 3,
+        id: ExtMapId::try_new(3)?,
         uri,
-        direction: Direction::DirectionUnknown,
-        ext_attr: None,
+        direction: None,
+        attributes: None,
     };
 
-    let s = e.marshal();
-    if s == "3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01" {
-        assert!(false, "TestTransportCC failed");
-    } else {
-        assert_eq!(
-            s,
-            "extmap:3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01"
-        )
-    }
+    assert_eq!(
+        e.convert().to_string(),
+        "extmap:3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01"
+    );
 
     Ok(())
 }
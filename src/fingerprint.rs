@@ -0,0 +1,199 @@
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::TypedAttribute;
+use super::error::Error;
+use super::util::token::is_token;
+
+#[cfg(feature = "fingerprint-verify")]
+mod digest;
+
+#[cfg(test)]
+mod fingerprint_test;
+
+/// HashFunction is the hash-func of an "a=fingerprint:" attribute, from the
+/// IANA "Hash Function Textual Names" registry. Unregistered tokens are kept
+/// as Other.
+/// <https://tools.ietf.org/html/rfc8122#section-5>
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HashFunction {
+    /// Sha1 is "sha-1".
+    Sha1,
+    /// Sha224 is "sha-224".
+    Sha224,
+    /// Sha256 is "sha-256".
+    Sha256,
+    /// Sha384 is "sha-384".
+    Sha384,
+    /// Sha512 is "sha-512".
+    Sha512,
+    /// Md5 is "md5".
+    Md5,
+    /// Md2 is "md2".
+    Md2,
+    /// Other is any other hash function, kept as written.
+    Other(String),
+}
+
+impl HashFunction {
+    /// digest_len returns the number of bytes a fingerprint made with this
+    /// hash function has, or None when the function is unknown.
+    pub fn digest_len(&self) -> Option<usize> {
+        match self {
+            HashFunction::Sha1 => Some(20),
+            HashFunction::Sha224 => Some(28),
+            HashFunction::Sha256 => Some(32),
+            HashFunction::Sha384 => Some(48),
+            HashFunction::Sha512 => Some(64),
+            HashFunction::Md5 | HashFunction::Md2 => Some(16),
+            HashFunction::Other(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for HashFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            HashFunction::Sha1 => "sha-1",
+            HashFunction::Sha224 => "sha-224",
+            HashFunction::Sha256 => "sha-256",
+            HashFunction::Sha384 => "sha-384",
+            HashFunction::Sha512 => "sha-512",
+            HashFunction::Md5 => "md5",
+            HashFunction::Md2 => "md2",
+            HashFunction::Other(token) => token,
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for HashFunction {
+    type Err = Error;
+
+    /// Hash function names are case-insensitive.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(match value.to_ascii_lowercase().as_str() {
+            "sha-1" => HashFunction::Sha1,
+            "sha-224" => HashFunction::Sha224,
+            "sha-256" => HashFunction::Sha256,
+            "sha-384" => HashFunction::Sha384,
+            "sha-512" => HashFunction::Sha512,
+            "md5" => HashFunction::Md5,
+            "md2" => HashFunction::Md2,
+            _ if is_token(value) => HashFunction::Other(value.to_owned()),
+            _ => return Err(Error::SdpInvalidFingerprint(value.to_owned())),
+        })
+    }
+}
+
+/// Fingerprint is the certificate fingerprint of an "a=fingerprint:"
+/// attribute. The digest is kept as bytes and always has the length of the
+/// declared hash function.
+/// <https://tools.ietf.org/html/rfc8122#section-5>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::fingerprint::{Fingerprint, HashFunction};
+///
+/// # fn main() -> Result<(), Error> {
+/// let fingerprint = Fingerprint::try_new(HashFunction::Sha1, vec![0xab; 20])?;
+/// assert_eq!(fingerprint.value().len(), 20);
+/// let text = fingerprint.to_string();
+/// assert!(text.starts_with("sha-1 AB:AB:"));
+/// assert_eq!(text.parse::<Fingerprint>()?, fingerprint);
+/// assert!(Fingerprint::try_new(HashFunction::Sha256, vec![0xab; 20]).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fingerprint {
+    hash_function: HashFunction,
+    value: Vec<u8>,
+}
+
+impl Fingerprint {
+    /// try_new creates a fingerprint, checking the length of the digest for the
+    /// known hash functions.
+    pub fn try_new(hash_function: HashFunction, value: Vec<u8>) -> Result<Self, Error> {
+        if let Some(expected) = hash_function.digest_len() {
+            if value.len() != expected {
+                return Err(Error::SdpFingerprintLengthMismatch {
+                    hash_function: hash_function.to_string(),
+                    expected,
+                    found: value.len(),
+                });
+            }
+        } else if value.is_empty() {
+            return Err(Error::SdpInvalidFingerprint(hash_function.to_string()));
+        }
+        Ok(Fingerprint {
+            hash_function,
+            value,
+        })
+    }
+
+    /// hash_function returns the hash function of the fingerprint.
+    pub fn hash_function(&self) -> &HashFunction {
+        &self.hash_function
+    }
+
+    /// value returns the digest.
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+
+    /// matches hashes a DER encoded certificate with the declared hash
+    /// function and compares it with the fingerprint. MD5, MD2 and unknown
+    /// hash functions never match.
+    #[cfg(feature = "fingerprint-verify")]
+    pub fn matches(&self, der_certificate: &[u8]) -> bool {
+        digest::digest(&self.hash_function, der_certificate).as_ref() == Some(&self.value)
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ", self.hash_function)?;
+        for (i, b) in self.value.iter().enumerate() {
+            if i > 0 {
+                write!(f, ":")?;
+            }
+            write!(f, "{:02X}", b)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Fingerprint {
+    type Err = Error;
+
+    /// fingerprint-attribute = "fingerprint" ":" hash-func SP fingerprint
+    /// fingerprint = 2UHEX *(":" 2UHEX)
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::SdpInvalidFingerprint(value.to_owned());
+
+        let (hash_function, fingerprint) = value.split_once(' ').ok_or_else(invalid)?;
+        let hash_function = hash_function.parse::<HashFunction>()?;
+
+        let mut bytes = vec![];
+        for pair in fingerprint.split(':') {
+            if pair.len() != 2 || !pair.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(invalid());
+            }
+            bytes.push(u8::from_str_radix(pair, 16).map_err(|_| invalid())?);
+        }
+
+        Fingerprint::try_new(hash_function, bytes)
+    }
+}
+
+impl TypedAttribute for Fingerprint {
+    const NAME: &'static str = "fingerprint";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("{} certificate fingerprint", self.hash_function))
+    }
+}
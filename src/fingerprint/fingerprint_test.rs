@@ -0,0 +1,146 @@
+use super::*;
+use crate::common_description::Attributes;
+
+#[test]
+fn test_fingerprint() -> Result<(), Error> {
+    let tests = vec![
+        (
+            "sha-256 2F:4C:56:91:41:55:B4:1F:6E:6A:BF:64:4F:C5:1B:5A:30:23:98:36:E3:11:7E:58:04:A8:E6:73:7A:E4:D7:83",
+            HashFunction::Sha256,
+            "sha-256 2F:4C:56:91:41:55:B4:1F:6E:6A:BF:64:4F:C5:1B:5A:30:23:98:36:E3:11:7E:58:04:A8:E6:73:7A:E4:D7:83",
+        ),
+        (
+            "SHA-256 8f:5a:1c:0e:65:3b:7d:14:0c:57:3a:31:dd:5d:80:d7:1e:70:f5:87:6c:5c:31:e2:0a:ee:d8:b8:6b:63:17:3e",
+            HashFunction::Sha256,
+            "sha-256 8F:5A:1C:0E:65:3B:7D:14:0C:57:3A:31:DD:5D:80:D7:1E:70:F5:87:6C:5C:31:E2:0A:EE:D8:B8:6B:63:17:3E",
+        ),
+        (
+            "sha-1 A9:99:3E:36:47:06:81:6A:BA:3E:25:71:78:50:C2:6C:9C:D0:D8:9D",
+            HashFunction::Sha1,
+            "sha-1 A9:99:3E:36:47:06:81:6A:BA:3E:25:71:78:50:C2:6C:9C:D0:D8:9D",
+        ),
+        (
+            "blake2 01:02:03",
+            HashFunction::Other("blake2".to_owned()),
+            "blake2 01:02:03",
+        ),
+    ];
+
+    for (input, hash_function, expected) in tests {
+        let fingerprint = input.parse::<Fingerprint>()?;
+        assert_eq!(fingerprint.hash_function(), &hash_function, "{}", input);
+        assert_eq!(
+            fingerprint.value().len(),
+            hash_function.digest_len().unwrap_or(3)
+        );
+        assert_eq!(fingerprint.to_string(), expected);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_fingerprint_invalid() {
+    let sha256 = "2F:4C:56:91:41:55:B4:1F:6E:6A:BF:64:4F:C5:1B:5A:30:23:98:36:E3:11:7E:58:04:A8:E6:73:7A:E4:D7:83";
+
+    let tests = vec![
+        format!("sha-1 {}", sha256),
+        format!("sha-512 {}", sha256),
+        format!("sha-256 {}:00", sha256),
+        "sha-256 2F:4C".to_owned(),
+    ];
+    for input in tests {
+        assert!(
+            matches!(
+                input.parse::<Fingerprint>(),
+                Err(Error::SdpFingerprintLengthMismatch { .. })
+            ),
+            "{}",
+            input
+        );
+    }
+
+    let tests = vec![
+        "sha-256".to_owned(),
+        format!("sha-256  {}", sha256),
+        format!("sha-256 {}:", sha256),
+        format!("sha-256 {}:F", &sha256[3..]),
+        "sha-256 2F4C5691".to_owned(),
+        "sha-256 2F:4G".to_owned(),
+        "sha-256 2F::4C".to_owned(),
+        format!("sha/256 {}", sha256),
+    ];
+    for input in tests {
+        assert!(
+            matches!(
+                input.parse::<Fingerprint>(),
+                Err(Error::SdpInvalidFingerprint(_))
+            ),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_fingerprint_attribute() -> Result<(), Error> {
+    let mut attributes = Attributes::default();
+    let fingerprint = "sha-256 2F:4C:56:91:41:55:B4:1F:6E:6A:BF:64:4F:C5:1B:5A:30:23:98:36:E3:11:7E:58:04:A8:E6:73:7A:E4:D7:83"
+        .parse::<Fingerprint>()?;
+    attributes.insert(fingerprint.clone());
+
+    assert_eq!(
+        attributes
+            .get::<Fingerprint>()
+            .collect::<Result<Vec<_>, _>>()?,
+        vec![fingerprint]
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "fingerprint-verify")]
+#[test]
+fn test_fingerprint_matches() -> Result<(), Error> {
+    let tests = vec![
+        (
+            "sha-1",
+            "A9:99:3E:36:47:06:81:6A:BA:3E:25:71:78:50:C2:6C:9C:D0:D8:9D",
+        ),
+        (
+            "sha-224",
+            "23:09:7D:22:34:05:D8:22:86:42:A4:77:BD:A2:55:B3:2A:AD:BC:E4:BD:A0:B3:F7:E3:6C:9D:A7",
+        ),
+        (
+            "sha-256",
+            "BA:78:16:BF:8F:01:CF:EA:41:41:40:DE:5D:AE:22:23:B0:03:61:A3:96:17:7A:9C:B4:10:FF:61:F2:00:15:AD",
+        ),
+        (
+            "sha-384",
+            "CB:00:75:3F:45:A3:5E:8B:B5:A0:3D:69:9A:C6:50:07:27:2C:32:AB:0E:DE:D1:63:1A:8B:60:5A:43:FF:5B:ED:\
+             80:86:07:2B:A1:E7:CC:23:58:BA:EC:A1:34:C8:25:A7",
+        ),
+        (
+            "sha-512",
+            "DD:AF:35:A1:93:61:7A:BA:CC:41:73:49:AE:20:41:31:12:E6:FA:4E:89:A9:7E:A2:0A:9E:EE:E6:4B:55:D3:9A:\
+             21:92:99:2A:27:4F:C1:A8:36:BA:3C:23:A3:FE:EB:BD:45:4D:44:23:64:3C:E8:0E:2A:9A:C9:4F:A5:4C:A4:9F",
+        ),
+    ];
+
+    for (hash_function, value) in tests {
+        let fingerprint = format!("{} {}", hash_function, value).parse::<Fingerprint>()?;
+        assert!(fingerprint.matches(b"abc"), "{}", hash_function);
+        assert!(!fingerprint.matches(b"abd"), "{}", hash_function);
+    }
+
+    // Exercises the multi-block padding paths.
+    let fingerprint = "sha-256 24:8D:6A:61:D2:06:38:B8:E5:C0:26:93:0C:3E:60:39:A3:3C:E4:59:64:FF:21:67:F6:EC:ED:D4:19:DB:06:C1"
+        .parse::<Fingerprint>()?;
+    assert!(fingerprint.matches(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"));
+
+    let fingerprint =
+        "md5 90:01:50:98:3C:D2:4F:B0:D6:96:3F:7D:28:E1:7F:72".parse::<Fingerprint>()?;
+    assert!(!fingerprint.matches(b"abc"));
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::TypedAttribute;
+use super::error::Error;
+use super::util::token::is_token;
+use super::util::validate_no_line_breaks;
+
+#[cfg(test)]
+mod fmtp_test;
+
+/// Fmtp carries format specific parameters for one of the formats of a
+/// media section.
+/// `a=fmtp:<format> <format specific parameters>`
+/// <https://tools.ietf.org/html/rfc4566#section-6>
+///
+/// The parameters are kept as the raw string so that serialization is
+/// byte-for-byte identical to the input until a parameter is changed; some
+/// formats give meaning to the position of their parameters.
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::fmtp::Fmtp;
+///
+/// # fn main() -> Result<(), Error> {
+/// let fmtp = "111 minptime=10;useinbandfec=1".parse::<Fmtp>()?;
+/// assert_eq!(fmtp.format, "111");
+/// assert_eq!(fmtp.to_string(), "111 minptime=10;useinbandfec=1");
+/// assert_eq!(Fmtp::try_new("96", "apt=100")?.to_string(), "96 apt=100");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fmtp {
+    /// format is the `<format>` the parameters are for.
+    pub format: String,
+    params: String,
+}
+
+impl Fmtp {
+    /// try_new creates the attribute from a format and its parameters.
+    pub fn try_new(format: &str, params: &str) -> Result<Self, Error> {
+        format!("{} {}", format, params).parse()
+    }
+
+    /// params returns the raw format specific parameters.
+    pub fn params(&self) -> &str {
+        &self.params
+    }
+
+    /// parameters parses the common "key=value;key=value" convention. Tokens
+    /// without a "=" are kept as flags with no value. The order of the input
+    /// is preserved.
+    pub fn parameters(&self) -> Vec<(&str, Option<&str>)> {
+        self.params
+            .split(';')
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .map(|token| match token.split_once('=') {
+                Some((key, value)) => (key.trim(), Some(value.trim())),
+                None => (token, None),
+            })
+            .collect()
+    }
+
+    /// parameter returns the value of the first parameter with the given key.
+    pub fn parameter(&self, key: &str) -> Option<&str> {
+        self.parameters()
+            .into_iter()
+            .find(|(k, _)| *k == key)
+            .and_then(|(_, value)| value)
+    }
+
+    /// set_parameter replaces the value of the given key in place, or appends
+    /// it if absent. Other parameters are left untouched.
+    pub fn set_parameter(&mut self, key: &str, value: &str) {
+        let mut found = false;
+        let segments: Vec<String> = self
+            .params
+            .split(';')
+            .map(|segment| {
+                let name = segment.split_once('=').map_or(segment, |(k, _)| k);
+                if !found && name.trim() == key {
+                    found = true;
+                    let indent = &segment[..segment.len() - segment.trim_start().len()];
+                    format!("{}{}={}", indent, key, value)
+                } else {
+                    segment.to_owned()
+                }
+            })
+            .collect();
+
+        self.params = segments.join(";");
+        if !found {
+            if !self.params.trim().is_empty() {
+                self.params.push(';');
+            } else {
+                self.params.clear();
+            }
+            self.params += &format!("{}={}", key, value);
+        }
+    }
+}
+
+impl fmt::Display for Fmtp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.format, self.params)
+    }
+}
+
+impl FromStr for Fmtp {
+    type Err = Error;
+
+    /// from_str parses the value of an "a=fmtp:" attribute, i.e. without
+    /// the "fmtp:" prefix.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (format, params) = value
+            .split_once(' ')
+            .ok_or_else(|| Error::SdpInvalidFmtp(value.to_owned()))?;
+        if !is_token(format) {
+            return Err(Error::SdpInvalidFmtp(value.to_owned()));
+        }
+        validate_no_line_breaks(params)?;
+
+        Ok(Fmtp {
+            format: format.to_owned(),
+            params: params.to_owned(),
+        })
+    }
+}
+
+impl TypedAttribute for Fmtp {
+    const NAME: &'static str = "fmtp";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("parameters of payload type {}", self.format))
+    }
+}
@@ -0,0 +1,93 @@
+use super::*;
+
+#[test]
+fn test_fmtp_round_trip() -> Result<(), Error> {
+    let tests = [
+        "102 profile-level-id=42e01f;packetization-mode=1",
+        "102 level-asymmetry-allowed=1; packetization-mode=1;profile-level-id=42e01f",
+        "111 minptime=10;useinbandfec=1",
+        "101 0-15",
+        "5000 webrtc-datachannel 1024",
+    ];
+
+    for input in tests {
+        let fmtp = input.parse::<Fmtp>()?;
+        assert_eq!(fmtp.to_string(), input);
+    }
+
+    for input in [
+        "102",
+        " profile-level-id=42e01f",
+        "1:2 x=1",
+        "102 a=1\r\nb=2",
+    ] {
+        assert!(
+            input.parse::<Fmtp>().is_err(),
+            "expected `{}` to be rejected",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_fmtp_parameters() -> Result<(), Error> {
+    let h264 = "102 profile-level-id=42e01f;packetization-mode=1".parse::<Fmtp>()?;
+    assert_eq!(h264.format, "102");
+    assert_eq!(
+        h264.parameters(),
+        vec![
+            ("profile-level-id", Some("42e01f")),
+            ("packetization-mode", Some("1")),
+        ]
+    );
+    assert_eq!(h264.parameter("packetization-mode"), Some("1"));
+    assert_eq!(h264.parameter("level-asymmetry-allowed"), None);
+
+    let opus = "111 minptime=10; useinbandfec=1; stereo".parse::<Fmtp>()?;
+    assert_eq!(
+        opus.parameters(),
+        vec![
+            ("minptime", Some("10")),
+            ("useinbandfec", Some("1")),
+            ("stereo", None),
+        ]
+    );
+
+    let datachannel = "5000 webrtc-datachannel 1024".parse::<Fmtp>()?;
+    assert_eq!(
+        datachannel.parameters(),
+        vec![("webrtc-datachannel 1024", None)]
+    );
+    assert_eq!(datachannel.params(), "webrtc-datachannel 1024");
+
+    Ok(())
+}
+
+#[test]
+fn test_fmtp_set_parameter() -> Result<(), Error> {
+    let mut fmtp = "102 level-asymmetry-allowed=1; packetization-mode=1;profile-level-id=42e01f"
+        .parse::<Fmtp>()?;
+    fmtp.set_parameter("packetization-mode", "0");
+    assert_eq!(
+        fmtp.to_string(),
+        "102 level-asymmetry-allowed=1; packetization-mode=0;profile-level-id=42e01f"
+    );
+
+    fmtp.set_parameter("max-fs", "8160");
+    assert_eq!(
+        fmtp.to_string(),
+        "102 level-asymmetry-allowed=1; packetization-mode=0;profile-level-id=42e01f;max-fs=8160"
+    );
+
+    let mut opus = "111 minptime=10;stereo".parse::<Fmtp>()?;
+    opus.set_parameter("stereo", "1");
+    assert_eq!(opus.to_string(), "111 minptime=10;stereo=1");
+
+    let mut empty = Fmtp::try_new("111", "")?;
+    empty.set_parameter("useinbandfec", "1");
+    assert_eq!(empty.to_string(), "111 useinbandfec=1");
+
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::TypedAttribute;
+use super::error::Error;
+use super::util::impl_string_newtype;
+use super::util::token::is_token;
+
+#[cfg(test)]
+mod group_test;
+
+/// MAX_RECOMMENDED_MID_LEN is the length a mid should not exceed so that it
+/// fits the RTP header extension carrying it. Longer mids are only reported
+/// by validation.
+/// <https://tools.ietf.org/html/rfc9143#section-9.1>
+pub const MAX_RECOMMENDED_MID_LEN: usize = 16;
+
+/// Mid is the identification-tag of an "a=mid:" attribute, labelling a media
+/// description so that "a=group:" can refer to it.
+/// <https://tools.ietf.org/html/rfc5888#section-4>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::group::Mid;
+///
+/// # fn main() -> Result<(), Error> {
+/// let mid = "audio0".parse::<Mid>()?;
+/// assert_eq!(mid.as_str(), "audio0");
+/// assert_eq!(Mid::try_new("1")?.to_string(), "1");
+/// assert!(Mid::try_new("a b").is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Mid(String);
+
+impl Mid {
+    /// try_new creates a mid, rejecting values which are not tokens.
+    pub fn try_new(value: &str) -> Result<Self, Error> {
+        validate_mid(value)?;
+        Ok(Mid(value.to_owned()))
+    }
+
+    /// as_str returns the mid.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl_string_newtype!(Mid(String), validate_mid);
+
+fn validate_mid(value: &str) -> Result<(), Error> {
+    if !is_token(value) {
+        return Err(Error::SdpInvalidMid(value.to_owned()));
+    }
+    Ok(())
+}
+
+impl TypedAttribute for Mid {
+    const NAME: &'static str = "mid";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("media id `{}`", self))
+    }
+}
+
+/// GroupSemantics tells how the media descriptions of a group relate.
+/// <https://tools.ietf.org/html/rfc5888#section-5>
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GroupSemantics {
+    /// Bundle shares one transport between the media descriptions.
+    /// <https://tools.ietf.org/html/rfc9143>
+    Bundle,
+    /// Ls is lip synchronization.
+    Ls,
+    /// Fid is flow identification.
+    Fid,
+    /// Fec is forward error correction.
+    /// <https://tools.ietf.org/html/rfc5956>
+    Fec,
+    /// Other is any other semantics, kept as written.
+    Other(String),
+}
+
+impl fmt::Display for GroupSemantics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            GroupSemantics::Bundle => "BUNDLE",
+            GroupSemantics::Ls => "LS",
+            GroupSemantics::Fid => "FID",
+            GroupSemantics::Fec => "FEC",
+            GroupSemantics::Other(token) => token,
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for GroupSemantics {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(match value {
+            "BUNDLE" => GroupSemantics::Bundle,
+            "LS" => GroupSemantics::Ls,
+            "FID" => GroupSemantics::Fid,
+            "FEC" => GroupSemantics::Fec,
+            _ if is_token(value) => GroupSemantics::Other(value.to_owned()),
+            _ => return Err(Error::SdpInvalidGroup(value.to_owned())),
+        })
+    }
+}
+
+/// Group is an "a=group:" attribute, listing the mids of the media
+/// descriptions grouped with the given semantics.
+/// <https://tools.ietf.org/html/rfc5888#section-5>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::group::{Group, GroupSemantics};
+///
+/// # fn main() -> Result<(), Error> {
+/// let group = "BUNDLE 0 1".parse::<Group>()?;
+/// assert_eq!(group.semantics, GroupSemantics::Bundle);
+/// assert!(group.contains("1"));
+/// assert_eq!(group.to_string(), "BUNDLE 0 1");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    /// semantics tells how the media descriptions relate.
+    pub semantics: GroupSemantics,
+    /// mids are the mids of the media descriptions.
+    pub mids: Vec<Mid>,
+}
+
+impl Group {
+    /// contains reports whether the group has the mid.
+    pub fn contains(&self, mid: &str) -> bool {
+        self.mids.iter().any(|m| m.as_str() == mid)
+    }
+}
+
+impl fmt::Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.semantics)?;
+        for mid in &self.mids {
+            write!(f, " {}", mid)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Group {
+    type Err = Error;
+
+    /// group-attribute = "a=group:" semantics *(SP identification-tag)
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut fields = value.split(' ');
+        let semantics = fields.next().unwrap_or_default().parse()?;
+        let mids = fields
+            .map(Mid::try_new)
+            .collect::<Result<_, _>>()
+            .map_err(|_| Error::SdpInvalidGroup(value.to_owned()))?;
+        Ok(Group { semantics, mids })
+    }
+}
+
+impl TypedAttribute for Group {
+    const NAME: &'static str = "group";
+
+    fn describe(&self) -> Option<String> {
+        let mids = self.mids.iter().map(Mid::as_str).collect::<Vec<_>>();
+        Some(format!(
+            "{} group of mids {}",
+            self.semantics,
+            mids.join(", ")
+        ))
+    }
+}
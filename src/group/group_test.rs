@@ -0,0 +1,50 @@
+use super::*;
+
+#[test]
+fn test_mid() -> Result<(), Error> {
+    for input in ["0", "audio", "a.b-c_d", &"m".repeat(32)] {
+        assert_eq!(input.parse::<Mid>()?.as_str(), input);
+    }
+    for input in ["", "a b", "mid:0", "caf\u{e9}"] {
+        assert!(
+            matches!(input.parse::<Mid>(), Err(Error::SdpInvalidMid(_))),
+            "{}",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_group() -> Result<(), Error> {
+    let tests = vec![
+        ("BUNDLE 0 1 2", GroupSemantics::Bundle, vec!["0", "1", "2"]),
+        ("LS audio video", GroupSemantics::Ls, vec!["audio", "video"]),
+        ("FID 1 2", GroupSemantics::Fid, vec!["1", "2"]),
+        ("FEC 1 2", GroupSemantics::Fec, vec!["1", "2"]),
+        ("BUNDLE", GroupSemantics::Bundle, vec![]),
+        ("DDP 1", GroupSemantics::Other("DDP".to_owned()), vec!["1"]),
+    ];
+
+    for (input, semantics, mids) in tests {
+        let group = input.parse::<Group>()?;
+        assert_eq!(group.semantics, semantics, "{}", input);
+        assert_eq!(group.mids.iter().map(Mid::as_str).collect::<Vec<_>>(), mids);
+        assert_eq!(group.to_string(), input);
+    }
+
+    let group = "BUNDLE 0 1".parse::<Group>()?;
+    assert!(group.contains("1"));
+    assert!(!group.contains("2"));
+
+    for input in ["", " 0 1", "BUNDLE 0  1", "BUNDLE 0 1 ", "BUN:DLE 0"] {
+        assert!(
+            matches!(input.parse::<Group>(), Err(Error::SdpInvalidGroup(_))),
+            "{:?}",
+            input
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+
+use url::Url;
+
+use super::alloc_prelude::*;
+use super::error::Error;
+use super::extmap::{ABS_SEND_TIME_URI, SDES_MID_URI, SDES_RTP_STREAM_ID_URI, TRANSPORT_CC_URI};
+
+#[cfg(test)]
+mod hdrext_test;
+
+/// AUDIO_LEVEL_URI is the URI of the client-to-mixer audio level
+/// extension.
+pub const AUDIO_LEVEL_URI: &str = "urn:ietf:params:rtp-hdrext:ssrc-audio-level";
+/// SDES_REPAIRED_RTP_STREAM_ID_URI is the URI of the repaired-rtp-stream-id
+/// extension.
+pub const SDES_REPAIRED_RTP_STREAM_ID_URI: &str =
+    "urn:ietf:params:rtp-hdrext:sdes:repaired-rtp-stream-id";
+/// VIDEO_ORIENTATION_URI is the URI of the coordination of video
+/// orientation extension.
+pub const VIDEO_ORIENTATION_URI: &str = "urn:3gpp:video-orientation";
+/// FRAME_MARKING_URI is the URI of the frame marking extension.
+pub const FRAME_MARKING_URI: &str = "urn:ietf:params:rtp-hdrext:framemarking";
+/// ABS_CAPTURE_TIME_URI is the URI of the absolute capture time extension.
+pub const ABS_CAPTURE_TIME_URI: &str =
+    "http://www.webrtc.org/experiments/rtp-hdrext/abs-capture-time";
+
+/// KnownExtension is an RTP header extension commonly negotiated by WebRTC
+/// endpoints, identified by the URI of its "a=extmap:" attribute.
+/// <https://tools.ietf.org/html/rfc8285#section-8>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KnownExtension {
+    /// <https://tools.ietf.org/html/rfc6464>
+    AudioLevel,
+    /// <https://webrtc.googlesource.com/src/+/refs/heads/main/docs/native-code/rtp-hdrext/abs-send-time>
+    AbsSendTime,
+    /// <https://tools.ietf.org/html/draft-holmer-rmcat-transport-wide-cc-extensions-01>
+    TransportCc,
+    /// <https://tools.ietf.org/html/rfc8843#section-15.1>
+    SdesMid,
+    /// <https://tools.ietf.org/html/rfc8852#section-3.1>
+    SdesRtpStreamId,
+    /// <https://tools.ietf.org/html/rfc8852#section-3.2>
+    SdesRepairedRtpStreamId,
+    /// 3GPP TS 26.114, section 7.4.5
+    VideoOrientation,
+    /// <https://tools.ietf.org/html/draft-ietf-avtext-framemarking-13>
+    FrameMarking,
+    /// <https://webrtc.googlesource.com/src/+/refs/heads/main/docs/native-code/rtp-hdrext/abs-capture-time>
+    AbsCaptureTime,
+}
+
+impl KnownExtension {
+    /// ALL lists every known extension.
+    pub const ALL: [KnownExtension; 9] = [
+        KnownExtension::AudioLevel,
+        KnownExtension::AbsSendTime,
+        KnownExtension::TransportCc,
+        KnownExtension::SdesMid,
+        KnownExtension::SdesRtpStreamId,
+        KnownExtension::SdesRepairedRtpStreamId,
+        KnownExtension::VideoOrientation,
+        KnownExtension::FrameMarking,
+        KnownExtension::AbsCaptureTime,
+    ];
+
+    /// uri returns the URI of the extension.
+    pub const fn uri(self) -> &'static str {
+        match self {
+            KnownExtension::AudioLevel => AUDIO_LEVEL_URI,
+            KnownExtension::AbsSendTime => ABS_SEND_TIME_URI,
+            KnownExtension::TransportCc => TRANSPORT_CC_URI,
+            KnownExtension::SdesMid => SDES_MID_URI,
+            KnownExtension::SdesRtpStreamId => SDES_RTP_STREAM_ID_URI,
+            KnownExtension::SdesRepairedRtpStreamId => SDES_REPAIRED_RTP_STREAM_ID_URI,
+            KnownExtension::VideoOrientation => VIDEO_ORIENTATION_URI,
+            KnownExtension::FrameMarking => FRAME_MARKING_URI,
+            KnownExtension::AbsCaptureTime => ABS_CAPTURE_TIME_URI,
+        }
+    }
+
+    /// from_uri returns the extension identified by the URI. URIs are
+    /// compared exactly, as RFC 8285 doesn't define any normalization.
+    pub fn from_uri(uri: &str) -> Option<Self> {
+        KnownExtension::ALL
+            .iter()
+            .copied()
+            .find(|known| known.uri() == uri)
+    }
+
+    /// name returns a short human-readable name of the extension.
+    pub const fn name(self) -> &'static str {
+        match self {
+            KnownExtension::AudioLevel => "audio level",
+            KnownExtension::AbsSendTime => "absolute send time",
+            KnownExtension::TransportCc => "transport-wide CC",
+            KnownExtension::SdesMid => "media id",
+            KnownExtension::SdesRtpStreamId => "RTP stream id",
+            KnownExtension::SdesRepairedRtpStreamId => "repaired RTP stream id",
+            KnownExtension::VideoOrientation => "video orientation",
+            KnownExtension::FrameMarking => "frame marking",
+            KnownExtension::AbsCaptureTime => "absolute capture time",
+        }
+    }
+
+    /// to_url returns the URI of the extension as a Url.
+    pub fn to_url(self) -> Url {
+        Url::parse(self.uri()).expect("valid header extension uri")
+    }
+}
+
+impl fmt::Display for KnownExtension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.uri())
+    }
+}
+
+impl FromStr for KnownExtension {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        KnownExtension::from_uri(value).ok_or_else(|| Error::SdpInvalidValue(value.to_owned()))
+    }
+}
+
+impl From<KnownExtension> for Url {
+    fn from(known: KnownExtension) -> Self {
+        known.to_url()
+    }
+}
+
+impl TryFrom<&Url> for KnownExtension {
+    type Error = Error;
+
+    fn try_from(url: &Url) -> Result<Self, Self::Error> {
+        KnownExtension::from_uri(url.as_str())
+            .ok_or_else(|| Error::SdpInvalidValue(url.to_string()))
+    }
+}
@@ -0,0 +1,43 @@
+use super::*;
+
+use crate::extmap::{ExtMap, ExtMapId};
+
+#[test]
+fn test_known_extension() -> Result<(), Error> {
+    for known in KnownExtension::ALL.iter().copied() {
+        let url = Url::from(known);
+        assert_eq!(url.as_str(), known.uri());
+        assert_eq!(KnownExtension::try_from(&url)?, known);
+        assert_eq!(KnownExtension::from_uri(&known.to_string()), Some(known));
+    }
+
+    let unknown = Url::parse("urn:example:unknown")?;
+    assert!(matches!(
+        KnownExtension::try_from(&unknown),
+        Err(Error::SdpInvalidValue(_))
+    ));
+    assert_eq!(KnownExtension::from_uri("urn:3gpp:VIDEO-orientation"), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_extmap_known() -> Result<(), Error> {
+    let extmap = "1 urn:ietf:params:rtp-hdrext:ssrc-audio-level".parse::<ExtMap>()?;
+    assert_eq!(extmap.known(), Some(KnownExtension::AudioLevel));
+    assert_eq!(
+        ExtMap::for_known(ExtMapId::try_new(1)?, KnownExtension::AudioLevel),
+        extmap
+    );
+
+    let extmap = ExtMap::for_known(ExtMapId::try_new(3)?, KnownExtension::TransportCc);
+    assert_eq!(
+        extmap.to_string(),
+        "3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01"
+    );
+
+    let extmap = "2 urn:example:unknown".parse::<ExtMap>()?;
+    assert_eq!(extmap.known(), None);
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use super::alloc_prelude::*;
+use super::direction::Direction;
+use super::error::Error;
+use super::group::Mid;
+use super::media_description::MediaDescription;
+use super::session_description::SessionDescription;
+
+#[cfg(test)]
+mod hold_test;
+
+/// HoldStyle is how media is put on hold.
+/// <https://tools.ietf.org/html/rfc6337#section-5.3>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HoldStyle {
+    /// SendOnly keeps sending, e.g. music on hold, and stops receiving:
+    /// sendrecv becomes sendonly and recvonly becomes inactive.
+    SendOnly,
+    /// Inactive stops media in both directions.
+    Inactive,
+}
+
+/// HoldState records the directions of the media descriptions put on hold,
+/// so that SessionDescription::resume can restore them. Media descriptions
+/// are identified by their mid, or by their index when they have none.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HoldState {
+    media: Vec<HeldMedia>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HeldMedia {
+    index: usize,
+    mid: Option<Mid>,
+    /// direction is the effective direction before the hold.
+    direction: Direction,
+    /// attribute is the media level direction attribute before the hold,
+    /// restored as is so that resuming doesn't add one.
+    attribute: Option<Direction>,
+}
+
+impl HoldState {
+    /// directions returns the mid and the effective direction before the
+    /// hold of each held media description.
+    pub fn directions(&self) -> impl Iterator<Item = (Option<&Mid>, Direction)> {
+        self.media.iter().map(|m| (m.mid.as_ref(), m.direction))
+    }
+
+    /// is_empty reports whether no media description was put on hold.
+    pub fn is_empty(&self) -> bool {
+        self.media.is_empty()
+    }
+}
+
+impl SessionDescription {
+    /// hold puts every media description on hold in the given style, and
+    /// bumps the origin version. The effective directions, taking the session
+    /// level direction attribute into account, are returned to resume from.
+    /// Rejected media descriptions are skipped.
+    /// <https://tools.ietf.org/html/rfc3264#section-8.4>
+    pub fn hold(&mut self, style: HoldStyle) -> Result<HoldState, Error> {
+        let session_direction = self.attributes.direction();
+        let mut media = vec![];
+        for (index, md) in self.media_descriptions.iter_mut().enumerate() {
+            if md.is_rejected() {
+                continue;
+            }
+            let attribute = md.direction();
+            let direction = attribute.or(session_direction).unwrap_or_default();
+            md.set_direction(match style {
+                HoldStyle::SendOnly => direction.intersection(Direction::SendOnly),
+                HoldStyle::Inactive => Direction::Inactive,
+            });
+            media.push(HeldMedia {
+                index,
+                mid: md.mid(),
+                direction,
+                attribute,
+            });
+        }
+        self.origin.bump_version()?;
+        Ok(HoldState { media })
+    }
+
+    /// resume restores the directions recorded by hold, and bumps the origin
+    /// version. Media descriptions which are gone or rejected since are
+    /// reported in Error::SdpHoldStateMismatch, after the others have been
+    /// restored.
+    pub fn resume(&mut self, state: HoldState) -> Result<(), Error> {
+        let mut missing = vec![];
+        for held in state.media {
+            match self.held_media_mut(&held) {
+                Some(md) => match held.attribute {
+                    Some(direction) => md.set_direction(direction),
+                    None => md.attributes.remove_all::<Direction>(),
+                },
+                None => missing.push(match held.mid {
+                    Some(mid) => mid.to_string(),
+                    None => format!("#{}", held.index),
+                }),
+            }
+        }
+        self.origin.bump_version()?;
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::SdpHoldStateMismatch(missing))
+        }
+    }
+
+    fn held_media_mut(&mut self, held: &HeldMedia) -> Option<&mut MediaDescription> {
+        let md = match &held.mid {
+            Some(mid) => self
+                .media_descriptions
+                .iter_mut()
+                .find(|md| md.mid().as_ref() == Some(mid))?,
+            None => self
+                .media_descriptions
+                .get_mut(held.index)
+                .filter(|md| md.mid().is_none())?,
+        };
+        Some(md).filter(|md| !md.is_rejected())
+    }
+}
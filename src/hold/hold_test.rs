@@ -0,0 +1,140 @@
+use super::*;
+
+const OFFER_SDP: &str = "v=0\r\n\
+o=- 4611731400430051336 2 IN IP4 192.168.1.20\r\n\
+s=-\r\n\
+c=IN IP4 192.168.1.20\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0 1 2\r\n\
+m=audio 50000 RTP/AVP 0\r\n\
+a=mid:0\r\n\
+a=rtpmap:0 PCMU/8000\r\n\
+m=video 50002 RTP/AVP 96\r\n\
+a=mid:1\r\n\
+a=recvonly\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+m=video 0 RTP/AVP 96\r\n\
+a=mid:2\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+m=audio 50004 RTP/AVP 0\r\n\
+a=mid:3\r\n\
+a=sendrecv\r\n\
+a=ptime:20\r\n";
+
+#[test]
+fn test_hold_resume() -> Result<(), Error> {
+    let mut sd = OFFER_SDP.parse::<SessionDescription>()?;
+
+    let state = sd.hold(HoldStyle::SendOnly)?;
+    assert_eq!(sd.origin.session_version, 3);
+    let directions = state.directions().collect::<Vec<_>>();
+    assert_eq!(directions.len(), 3);
+    assert_eq!(directions[0].0.map(Mid::as_str), Some("0"));
+    assert_eq!(directions[0].1, Direction::SendRecv);
+    assert_eq!(directions[1].1, Direction::RecvOnly);
+    assert_eq!(directions[2].1, Direction::SendRecv);
+
+    let held = sd
+        .media_descriptions
+        .iter()
+        .map(MediaDescription::direction)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        held,
+        vec![
+            Some(Direction::SendOnly),
+            Some(Direction::Inactive),
+            None,
+            Some(Direction::SendOnly),
+        ]
+    );
+    // The attribute is replaced in place.
+    assert!(sd
+        .marshal()
+        .contains("a=mid:3\r\na=sendonly\r\na=ptime:20\r\n"));
+
+    sd.resume(state)?;
+    assert_eq!(sd.origin.session_version, 4);
+    let original = OFFER_SDP.replace(" 2 IN IP4", " 4 IN IP4");
+    assert_eq!(sd.marshal(), original);
+
+    Ok(())
+}
+
+#[test]
+fn test_hold_inactive_session_direction() -> Result<(), Error> {
+    let offer = OFFER_SDP.replace("t=0 0\r\n", "t=0 0\r\na=sendonly\r\n");
+    let mut sd = offer.parse::<SessionDescription>()?;
+
+    let state = sd.hold(HoldStyle::Inactive)?;
+    let directions = state.directions().map(|(_, d)| d).collect::<Vec<_>>();
+    assert_eq!(
+        directions,
+        vec![
+            Direction::SendOnly,
+            Direction::RecvOnly,
+            Direction::SendRecv
+        ]
+    );
+    assert!(sd
+        .media_descriptions
+        .iter()
+        .filter(|md| !md.is_rejected())
+        .all(|md| md.direction() == Some(Direction::Inactive)));
+    // The session level attribute is left alone.
+    assert_eq!(sd.attributes.direction(), Some(Direction::SendOnly));
+
+    sd.resume(state)?;
+    assert_eq!(sd.marshal(), offer.replace(" 2 IN IP4", " 4 IN IP4"));
+
+    Ok(())
+}
+
+#[test]
+fn test_resume_partial() -> Result<(), Error> {
+    let mut sd = OFFER_SDP.parse::<SessionDescription>()?;
+    let state = sd.hold(HoldStyle::SendOnly)?;
+
+    // A renegotiation removes the first media description and rejects the
+    // last one.
+    sd.media_descriptions.remove(0);
+    sd.media_descriptions[2].reject();
+
+    match sd.resume(state) {
+        Err(Error::SdpHoldStateMismatch(missing)) => assert_eq!(missing, vec!["0", "3"]),
+        other => panic!("unexpected {:?}", other),
+    }
+    // What could be restored was.
+    assert_eq!(
+        sd.media_descriptions[0].direction(),
+        Some(Direction::RecvOnly)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_hold_without_mids() -> Result<(), Error> {
+    let offer = "v=0\r\n\
+o=- 1 1 IN IP4 192.0.2.1\r\n\
+s=-\r\n\
+c=IN IP4 192.0.2.1\r\n\
+t=0 0\r\n\
+m=audio 49170 RTP/AVP 0\r\n\
+m=video 51372 RTP/AVP 31\r\n\
+a=recvonly\r\n";
+    let mut sd = offer.parse::<SessionDescription>()?;
+
+    let state = sd.hold(HoldStyle::SendOnly)?;
+    assert!(state.directions().all(|(mid, _)| mid.is_none()));
+    sd.resume(state.clone())?;
+    assert_eq!(sd.marshal(), offer.replace(" 1 1 IN", " 1 3 IN"));
+
+    sd.media_descriptions.truncate(1);
+    match sd.resume(state) {
+        Err(Error::SdpHoldStateMismatch(missing)) => assert_eq!(missing, vec!["#1"]),
+        other => panic!("unexpected {:?}", other),
+    }
+
+    Ok(())
+}
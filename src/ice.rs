@@ -0,0 +1,180 @@
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::candidate::is_ice_char;
+use super::common_description::TypedAttribute;
+use super::error::Error;
+use super::util::impl_string_newtype;
+
+#[cfg(test)]
+mod ice_test;
+
+/// IceUfrag is the ICE username fragment of an "a=ice-ufrag:" attribute,
+/// made of 4 to 256 ice-chars.
+/// <https://tools.ietf.org/html/rfc8839#section-5.4>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::ice::IceUfrag;
+///
+/// # fn main() -> Result<(), Error> {
+/// let ufrag = "8hhY".parse::<IceUfrag>()?;
+/// assert_eq!(ufrag.as_str(), "8hhY");
+/// assert_eq!(IceUfrag::try_new("8hhY")?.to_string(), "8hhY");
+/// assert!(IceUfrag::try_new("8hh").is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IceUfrag(String);
+
+impl IceUfrag {
+    /// try_new creates a username fragment, rejecting ones which are too short
+    /// or too long, or contain characters other than ice-chars.
+    pub fn try_new(value: &str) -> Result<Self, Error> {
+        validate_ice_ufrag(value)?;
+        Ok(IceUfrag(value.to_owned()))
+    }
+
+    /// as_str returns the username fragment.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl_string_newtype!(IceUfrag(String), validate_ice_ufrag);
+
+fn validate_ice_ufrag(value: &str) -> Result<(), Error> {
+    if !is_ice_string(value, 4) {
+        return Err(Error::SdpInvalidIceUfrag(value.to_owned()));
+    }
+    Ok(())
+}
+
+impl TypedAttribute for IceUfrag {
+    const NAME: &'static str = "ice-ufrag";
+
+    fn describe(&self) -> Option<String> {
+        Some("ICE username fragment".to_owned())
+    }
+}
+
+/// IcePwd is the ICE password of an "a=ice-pwd:" attribute, made of 22 to
+/// 256 ice-chars. Like EncryptionKey, Debug redacts it and Display emits it
+/// for serialization.
+/// <https://tools.ietf.org/html/rfc8839#section-5.4>
+#[derive(Clone, PartialEq, Eq)]
+pub struct IcePwd(String);
+
+impl IcePwd {
+    /// Errors never include the password itself.
+    pub fn try_new(value: &str) -> Result<Self, Error> {
+        validate_ice_pwd(value)?;
+        Ok(IcePwd(value.to_owned()))
+    }
+
+    /// expose deliberately returns the password.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for IcePwd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "IcePwd(REDACTED)")
+    }
+}
+
+impl_string_newtype!(secret IcePwd(String), validate_ice_pwd);
+
+fn validate_ice_pwd(value: &str) -> Result<(), Error> {
+    if !is_ice_string(value, 22) {
+        return Err(Error::SdpInvalidIcePwd);
+    }
+    Ok(())
+}
+
+impl TypedAttribute for IcePwd {
+    const NAME: &'static str = "ice-pwd";
+
+    fn describe(&self) -> Option<String> {
+        Some("ICE password".to_owned())
+    }
+}
+
+/// IceOptions are the option tags of an "a=ice-options:" attribute, e.g.
+/// "trickle", in their original order and without duplicates.
+/// <https://tools.ietf.org/html/rfc8839#section-5.6>
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct IceOptions(Vec<String>);
+
+/// ICE_OPTION_TRICKLE is the option of endpoints supporting trickle ICE.
+/// <https://tools.ietf.org/html/rfc8840#section-4.1.1>
+pub const ICE_OPTION_TRICKLE: &str = "trickle";
+/// ICE_OPTION_RENOMINATION is the option of endpoints supporting ICE
+/// renomination.
+pub const ICE_OPTION_RENOMINATION: &str = "renomination";
+
+impl IceOptions {
+    /// has reports whether the option is present.
+    pub fn has(&self, option: &str) -> bool {
+        self.0.iter().any(|o| o == option)
+    }
+
+    /// insert adds an option tag unless it is already present.
+    pub fn insert(&mut self, option: &str) -> Result<(), Error> {
+        if option.is_empty() || !option.chars().all(is_ice_char) {
+            return Err(Error::SdpInvalidIceOptions(option.to_owned()));
+        }
+        if !self.has(option) {
+            self.0.push(option.to_owned());
+        }
+        Ok(())
+    }
+
+    /// remove removes the option.
+    pub fn remove(&mut self, option: &str) {
+        self.0.retain(|o| o != option);
+    }
+
+    /// iter iterates over the options in order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+impl fmt::Display for IceOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.join(" "))
+    }
+}
+
+impl FromStr for IceOptions {
+    type Err = Error;
+
+    /// ice-options = "ice-options:" ice-option-tag *(SP ice-option-tag)
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut options = IceOptions::default();
+        for option in value.split(' ') {
+            options
+                .insert(option)
+                .map_err(|_| Error::SdpInvalidIceOptions(value.to_owned()))?;
+        }
+        Ok(options)
+    }
+}
+
+impl TypedAttribute for IceOptions {
+    const NAME: &'static str = "ice-options";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("ICE options: {}", self.0.join(", ")))
+    }
+}
+
+fn is_ice_string(value: &str, min_len: usize) -> bool {
+    (min_len..=256).contains(&value.len()) && value.chars().all(is_ice_char)
+}
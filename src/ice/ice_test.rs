@@ -0,0 +1,62 @@
+use super::*;
+
+#[test]
+fn test_ice_ufrag() -> Result<(), Error> {
+    for input in ["Gh2c", "q0ly+/AZ", &"a".repeat(256)] {
+        assert_eq!(input.parse::<IceUfrag>()?.as_str(), input);
+    }
+    for input in ["", "abc", "ab-cd", "ab cd", &"a".repeat(257)] {
+        assert!(
+            matches!(input.parse::<IceUfrag>(), Err(Error::SdpInvalidIceUfrag(_))),
+            "{}",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_ice_pwd() -> Result<(), Error> {
+    let pwd = "3zUiRr5gHbS0aBkPQVvvDbQy".parse::<IcePwd>()?;
+    assert_eq!(pwd.to_string(), "3zUiRr5gHbS0aBkPQVvvDbQy");
+    assert_eq!(pwd.expose(), "3zUiRr5gHbS0aBkPQVvvDbQy");
+    assert_eq!(format!("{:?}", pwd), "IcePwd(REDACTED)");
+
+    for input in ["", "tooshortpassword12345", "3zUiRr5gHbS0aBkPQVvvDb-y"] {
+        let err = input.parse::<IcePwd>();
+        assert!(matches!(err, Err(Error::SdpInvalidIcePwd)), "{}", input);
+    }
+    assert!("3zUiRr5gHbS0aBkPQVvv\r\n"
+        .parse::<IcePwd>()
+        .unwrap_err()
+        .to_string()
+        .find("3zUi")
+        .is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_ice_options() -> Result<(), Error> {
+    let mut options = "trickle".parse::<IceOptions>()?;
+    assert!(options.has(ICE_OPTION_TRICKLE));
+    assert!(!options.has(ICE_OPTION_RENOMINATION));
+
+    options.insert(ICE_OPTION_RENOMINATION)?;
+    options.insert(ICE_OPTION_TRICKLE)?;
+    assert_eq!(options.to_string(), "trickle renomination");
+    assert_eq!(
+        options.iter().collect::<Vec<_>>(),
+        vec!["trickle", "renomination"]
+    );
+
+    options.remove(ICE_OPTION_TRICKLE);
+    assert_eq!(options.to_string(), "renomination");
+
+    for input in ["", "trickle  ice2", "trick-le"] {
+        assert!(input.parse::<IceOptions>().is_err(), "{:?}", input);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,276 @@
+use alloc::sync::Arc;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+
+use super::alloc_prelude::*;
+#[cfg(feature = "std")]
+use super::session_description::SessionDescription;
+
+#[cfg(all(test, feature = "std"))]
+mod intern_test;
+
+/// Istr is the string of an attribute name or value. It is either owned, as
+/// after parsing without an Interner, or shared with every other equal
+/// string interned by the same Interner. Both behave the same: Istr derefs
+/// to str and compares, hashes and orders by content.
+#[derive(Clone)]
+pub struct Istr(Repr);
+
+#[derive(Clone)]
+enum Repr {
+    Owned(String),
+    Shared(Arc<str>),
+}
+
+impl Istr {
+    /// as_str returns the string.
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            Repr::Owned(s) => s,
+            Repr::Shared(s) => s,
+        }
+    }
+
+    /// is_interned reports whether the string is shared through an Interner.
+    pub fn is_interned(&self) -> bool {
+        matches!(self.0, Repr::Shared(_))
+    }
+}
+
+impl Default for Istr {
+    fn default() -> Self {
+        Istr(Repr::Owned(String::new()))
+    }
+}
+
+impl Deref for Istr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for Istr {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for Istr {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for Istr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for Istr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Interned strings are compared by pointer first, which is all it takes
+/// when both come from the same Interner.
+impl PartialEq for Istr {
+    fn eq(&self, other: &Istr) -> bool {
+        match (&self.0, &other.0) {
+            (Repr::Shared(a), Repr::Shared(b)) if Arc::ptr_eq(a, b) => true,
+            _ => self.as_str() == other.as_str(),
+        }
+    }
+}
+
+impl Eq for Istr {}
+
+impl PartialEq<str> for Istr {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Istr {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for Istr {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<Istr> for str {
+    fn eq(&self, other: &Istr) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<Istr> for &str {
+    fn eq(&self, other: &Istr) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl PartialOrd for Istr {
+    fn partial_cmp(&self, other: &Istr) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Istr {
+    fn cmp(&self, other: &Istr) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Hash for Istr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl From<String> for Istr {
+    fn from(s: String) -> Self {
+        Istr(Repr::Owned(s))
+    }
+}
+
+impl From<&str> for Istr {
+    fn from(s: &str) -> Self {
+        Istr(Repr::Owned(s.to_owned()))
+    }
+}
+
+impl From<Istr> for String {
+    fn from(s: Istr) -> Self {
+        match s.0 {
+            Repr::Owned(s) => s,
+            Repr::Shared(s) => s.as_ref().to_owned(),
+        }
+    }
+}
+
+/// Interner shares equal strings between session descriptions, to save the
+/// memory of the attribute names and values which repeat across them, like
+/// "rtpmap:111 opus/48000/2" or the extmap URIs. Clones share the same
+/// strings, and an Interner can be used from several threads. Strings stay
+/// interned for as long as the Interner lives.
+#[cfg(feature = "std")]
+#[derive(Clone, Default)]
+pub struct Interner(Arc<Mutex<HashSet<Arc<str>>>>);
+
+/// INTERNED_VALUES are the attributes whose values are interned, as they
+/// tend to be the same in every description of a deployment. Other values,
+/// like candidates and ICE credentials, are unique and are left owned.
+#[cfg(feature = "std")]
+const INTERNED_VALUES: &[&str] = &[
+    "extmap",
+    "fmtp",
+    "group",
+    "ice-options",
+    "msid-semantic",
+    "rtcp",
+    "rtcp-fb",
+    "rtpmap",
+    "setup",
+];
+
+#[cfg(feature = "std")]
+impl Interner {
+    /// new creates an empty interner.
+    pub fn new() -> Self {
+        Interner::default()
+    }
+
+    /// intern returns the shared copy of the string, adding it if needed.
+    pub fn intern(&self, s: &str) -> Istr {
+        let mut strings = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(shared) = strings.get(s) {
+            return Istr(Repr::Shared(Arc::clone(shared)));
+        }
+        let shared: Arc<str> = Arc::from(s);
+        strings.insert(Arc::clone(&shared));
+        Istr(Repr::Shared(shared))
+    }
+
+    /// len returns the number of distinct strings interned.
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// is_empty reports whether no string has been interned.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Debug for Interner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interner")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// Interners are equal if they share the same strings.
+#[cfg(feature = "std")]
+impl PartialEq for Interner {
+    fn eq(&self, other: &Interner) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Eq for Interner {}
+
+#[cfg(feature = "std")]
+impl Hash for Interner {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.0).hash(state)
+    }
+}
+
+#[cfg(feature = "std")]
+impl SessionDescription {
+    /// intern replaces the attribute names, and the values of the attributes
+    /// in INTERNED_VALUES, with their copies shared through the interner.
+    /// Parser does this itself when ParseOptions has an interner.
+    pub fn intern(&mut self, interner: &Interner) {
+        self.attributes.0.shrink_to_fit();
+        for md in &mut self.media_descriptions {
+            md.attributes.0.shrink_to_fit();
+        }
+        let attributes = self.attributes.0.iter_mut().chain(
+            self.media_descriptions
+                .iter_mut()
+                .flat_map(|md| md.attributes.0.iter_mut()),
+        );
+        for attribute in attributes {
+            if !attribute.key.is_interned() {
+                attribute.key = interner.intern(&attribute.key);
+            }
+            if INTERNED_VALUES.contains(&attribute.key.as_str()) {
+                if let Some(value) = &mut attribute.value {
+                    if !value.is_interned() {
+                        *value = interner.intern(value);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use super::*;
+use crate::common_description::Attribute;
+use crate::error::Error;
+use crate::parser::{ParseOptions, Parser};
+
+use std::collections::hash_map::DefaultHasher;
+
+// Istr takes no more room than the String it replaces.
+const _: () = assert!(core::mem::size_of::<Istr>() == core::mem::size_of::<String>());
+const _: () =
+    assert!(core::mem::size_of::<Option<Istr>>() == core::mem::size_of::<Option<String>>());
+
+const OFFER_SDP: &str = "v=0\r\n\
+o=- 4611731400430051336 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=ice-ufrag:EsAw\r\n\
+a=ice-pwd:P2uYro0UCOQ4zxjKXaWCBui1\r\n\
+a=mid:0\r\n\
+a=extmap:1 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r\n\
+a=sendrecv\r\n\
+a=rtcp-mux\r\n\
+a=rtpmap:111 opus/48000/2\r\n\
+a=fmtp:111 minptime=10;useinbandfec=1\r\n";
+
+fn hash(s: &Istr) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    s.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_istr() {
+    let interner = Interner::new();
+    let owned = Istr::from("rtpmap");
+    let shared = interner.intern("rtpmap");
+    assert!(!owned.is_interned());
+    assert!(shared.is_interned());
+
+    // Both forms behave the same.
+    assert_eq!(owned, shared);
+    assert_eq!(hash(&owned), hash(&shared));
+    assert_eq!(owned.cmp(&shared), Ordering::Equal);
+    assert_eq!(&*shared, "rtpmap");
+    assert_eq!(shared, "rtpmap");
+    assert_eq!("rtpmap", shared);
+    assert_eq!(shared.to_string(), "rtpmap");
+    assert_eq!(format!("{:?}", shared), "\"rtpmap\"");
+    assert_eq!(String::from(shared), "rtpmap");
+    assert_eq!(Istr::from("fmtp").cmp(&owned), Ordering::Less);
+}
+
+#[test]
+fn test_interner() {
+    let interner = Interner::new();
+    assert!(interner.is_empty());
+
+    let a = interner.intern("opus/48000/2");
+    let b = interner.clone().intern("opus/48000/2");
+    assert_eq!(interner.len(), 1);
+    match (&a.0, &b.0) {
+        (Repr::Shared(a), Repr::Shared(b)) => assert!(Arc::ptr_eq(a, b)),
+        _ => panic!("not interned"),
+    }
+
+    interner.intern("VP8/90000");
+    assert_eq!(interner.len(), 2);
+    assert_eq!(interner, interner.clone());
+    assert_ne!(interner, Interner::new());
+
+    // Interners can be shared between threads.
+    let handles = (0..4)
+        .map(|_| {
+            let interner = interner.clone();
+            std::thread::spawn(move || interner.intern("H264/90000"))
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        assert_eq!(handle.join().expect("thread panicked"), "H264/90000");
+    }
+    assert_eq!(interner.len(), 3);
+}
+
+#[test]
+fn test_parse_interned() -> Result<(), Error> {
+    let interner = Interner::new();
+    let mut parser = Parser::new().with_options(ParseOptions {
+        interner: Some(interner.clone()),
+        ..Default::default()
+    });
+
+    let first = parser.parse(OFFER_SDP.as_bytes())?;
+    let len = interner.len();
+    let second = parser.parse(OFFER_SDP.as_bytes())?;
+    assert_eq!(interner.len(), len);
+    assert_eq!(first, second);
+    assert_eq!(second.marshal(), OFFER_SDP);
+
+    let md = &second.media_descriptions[0];
+    assert!(md.attributes.0.iter().all(|a| a.key.is_interned()));
+    let interned_values = md
+        .attributes
+        .0
+        .iter()
+        .filter(|a| a.value.as_ref().is_some_and(Istr::is_interned))
+        .map(|a| a.key.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(interned_values, vec!["extmap", "rtpmap", "fmtp"]);
+
+    // The unique values are left owned.
+    let pwd = md
+        .attributes
+        .get_first("ice-pwd")
+        .and_then(|a| a.value.as_ref());
+    assert!(!pwd.is_some_and(Istr::is_interned));
+
+    // Without an interner, nothing is shared.
+    let sd = OFFER_SDP.parse::<SessionDescription>()?;
+    assert!(sd.attributes.0.iter().all(|a| !a.key.is_interned()));
+    assert_eq!(sd, first);
+
+    Ok(())
+}
+
+#[test]
+fn test_intern_modified() -> Result<(), Error> {
+    let interner = Interner::new();
+    let mut sd = OFFER_SDP.parse::<SessionDescription>()?;
+    sd.intern(&interner);
+
+    // Interned attributes can be replaced and compared like the others.
+    sd.media_descriptions[0].attributes.push(Attribute::new(
+        "rtpmap".to_owned(),
+        Some("0 PCMU/8000".to_owned()),
+    ));
+    assert_eq!(sd.media_descriptions[0].rtpmaps().count(), 2);
+    sd.intern(&interner);
+    assert!(sd.marshal().ends_with("a=rtpmap:0 PCMU/8000\r\n"));
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::TypedAttribute;
+use super::error::Error;
+use super::util::token::is_token;
+use super::util::{base64_decode, base64_encode};
+
+#[cfg(test)]
+mod keymgmt_test;
+
+/// KEY_MGMT_PROTOCOL_MIKEY is the `<prtcl-id>` of MIKEY key management.
+/// <https://tools.ietf.org/html/rfc4567#section-3.1>
+pub const KEY_MGMT_PROTOCOL_MIKEY: &str = "mikey";
+
+/// KeyMgmt is an "a=key-mgmt:" attribute, carrying the message of a key
+/// management protocol. It can appear at session and media level, where the
+/// media level ones replace the session level ones, and several of them are
+/// listed in order of preference.
+/// `a=key-mgmt:<prtcl-id> <keymgmt-data>`
+/// <https://tools.ietf.org/html/rfc4567#section-3.1>
+///
+/// The data is kept decoded. Like EncryptionKey, Debug redacts it and
+/// Display emits it for serialization; use expose_data() to access it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct KeyMgmt {
+    protocol_id: String,
+    data: Vec<u8>,
+}
+
+impl KeyMgmt {
+    /// new creates the attribute, rejecting a protocol id which is not a
+    /// token.
+    pub fn new(protocol_id: &str, data: Vec<u8>) -> Result<Self, Error> {
+        if !is_token(protocol_id) {
+            return Err(Error::SdpInvalidKeyMgmt(
+                protocol_id.chars().filter(|c| !c.is_control()).collect(),
+            ));
+        }
+        Ok(KeyMgmt {
+            protocol_id: protocol_id.to_owned(),
+            data,
+        })
+    }
+
+    /// protocol_id returns the key management protocol, e.g. "mikey".
+    pub fn protocol_id(&self) -> &str {
+        &self.protocol_id
+    }
+
+    /// is_mikey reports whether the protocol is MIKEY.
+    pub fn is_mikey(&self) -> bool {
+        self.protocol_id == KEY_MGMT_PROTOCOL_MIKEY
+    }
+
+    /// expose_data returns the decoded key management message.
+    pub fn expose_data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl fmt::Debug for KeyMgmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyMgmt")
+            .field("protocol_id", &self.protocol_id)
+            .field("data", &format_args!("REDACTED"))
+            .finish()
+    }
+}
+
+impl fmt::Display for KeyMgmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.protocol_id, base64_encode(&self.data))
+    }
+}
+
+impl FromStr for KeyMgmt {
+    type Err = Error;
+
+    /// Errors never include the data itself. Only canonical base64 is
+    /// accepted, so that Display gives back the same text.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (protocol_id, data) = value.split_once(' ').unwrap_or((value, ""));
+        let mut key_mgmt = KeyMgmt::new(protocol_id, vec![])?;
+        key_mgmt.data = match base64_decode(data) {
+            Some(data) if !data.is_empty() => data,
+            _ => return Err(Error::SdpInvalidKeyMgmt(protocol_id.to_owned())),
+        };
+        Ok(key_mgmt)
+    }
+}
+
+impl TypedAttribute for KeyMgmt {
+    const NAME: &'static str = "key-mgmt";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("{} key management", self.protocol_id))
+    }
+}
@@ -0,0 +1,98 @@
+use super::*;
+use crate::diagnostic::DiagnosticKind;
+use crate::session_description::SessionDescription;
+
+// A MIKEY message in the style of the RFC 4567 examples: a common header,
+// a timestamp and the initiator's identity.
+// https://tools.ietf.org/html/rfc4567#section-3.1
+const MIKEY_DATA: &str = "AQAFgM0XflABAAAAAAAAAAAAAAsAyONQ6gAAAAAJAAAQbWlrZXlAZXhhbXBsZS5jb20=";
+
+#[test]
+fn test_key_mgmt() -> Result<(), Error> {
+    let value = format!("mikey {}", MIKEY_DATA);
+    let key_mgmt = value.parse::<KeyMgmt>()?;
+    assert!(key_mgmt.is_mikey());
+    assert_eq!(key_mgmt.protocol_id(), "mikey");
+    assert_eq!(key_mgmt.expose_data().len(), 50);
+    assert!(key_mgmt.expose_data().ends_with(b"mikey@example.com"));
+    assert_eq!(key_mgmt.to_string(), value);
+    assert_eq!(
+        format!("{:?}", key_mgmt),
+        "KeyMgmt { protocol_id: \"mikey\", data: REDACTED }"
+    );
+
+    let key_mgmt = KeyMgmt::new("other-protocol", vec![1, 2, 3])?;
+    assert_eq!(key_mgmt.to_string(), "other-protocol AQID");
+    assert!(!key_mgmt.is_mikey());
+
+    Ok(())
+}
+
+#[test]
+fn test_key_mgmt_invalid() {
+    let tests = [
+        "mikey",
+        "mikey ",
+        "mikey AQID!",
+        // Not canonical: the unused bits of the last character are set.
+        "mikey AQJ=",
+        "mikey AQI",
+        " AQID",
+        "mi/key AQID",
+    ];
+    for value in tests.iter() {
+        let err = value.parse::<KeyMgmt>().unwrap_err();
+        assert!(matches!(err, Error::SdpInvalidKeyMgmt(_)), "{}", value);
+        assert!(!err.to_string().contains("AQ"), "{}", err);
+    }
+}
+
+#[test]
+fn test_effective_key_mgmt() -> Result<(), Error> {
+    let sdp = format!(
+        "v=0\r\n\
+         o=- 0 0 IN IP4 127.0.0.1\r\n\
+         s=-\r\n\
+         c=IN IP4 127.0.0.1\r\n\
+         t=0 0\r\n\
+         a=key-mgmt:mikey {}\r\n\
+         a=key-mgmt:other AQID\r\n\
+         m=audio 5004 RTP/SAVP 0\r\n\
+         m=video 5006 RTP/SAVP 31\r\n\
+         a=key-mgmt:mikey AQIDBA==\r\n",
+        MIKEY_DATA
+    );
+    let mut sd = sdp.parse::<SessionDescription>()?;
+    assert_eq!(sd.marshal(), sdp);
+
+    let protocols = |key_mgmt: Vec<KeyMgmt>| -> Vec<String> {
+        key_mgmt.iter().map(|k| k.to_string()).collect()
+    };
+    assert_eq!(protocols(sd.key_mgmt()).len(), 2);
+    assert_eq!(
+        protocols(sd.effective_key_mgmt(0)),
+        protocols(sd.key_mgmt())
+    );
+    assert_eq!(protocols(sd.effective_key_mgmt(1)), vec!["mikey AQIDBA=="]);
+    assert!(sd.effective_key_mgmt(2).is_empty());
+    assert!(sd.validate().is_empty(), "{:?}", sd.validate());
+
+    // k= along with key-mgmt in effect, at either level.
+    let key_mgmt_warnings = |sd: &SessionDescription| -> Vec<Option<usize>> {
+        sd.validate()
+            .into_iter()
+            .filter(|d| d.kind == DiagnosticKind::EncryptionKeyWithKeyMgmt)
+            .map(|d| d.media_index)
+            .collect()
+    };
+    sd.media_descriptions[0].encryption_key = Some("prompt".parse()?);
+    assert_eq!(key_mgmt_warnings(&sd), vec![Some(0)]);
+    sd.media_descriptions[0].encryption_key = None;
+    sd.media_descriptions[1].encryption_key = Some("prompt".parse()?);
+    assert_eq!(key_mgmt_warnings(&sd), vec![Some(1)]);
+    sd.media_descriptions[1].encryption_key = None;
+    sd.encryption_key = Some("prompt".parse()?);
+    assert_eq!(key_mgmt_warnings(&sd), vec![None, Some(1)]);
+
+    Ok(())
+}
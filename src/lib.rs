@@ -1,10 +1,160 @@
+//! webrtc-rs-sdp is a pure Rust implementation of the Session Description
+//! Protocol of RFC 4566 and RFC 8866, with the extensions used by WebRTC.
+//!
+//! ```
+//! use webrtc_rs_sdp::{Error, SessionDescription};
+//!
+//! # fn main() -> Result<(), Error> {
+//! let sd = "v=0\r\n\
+//! o=- 4611731400430051336 2 IN IP4 127.0.0.1\r\n\
+//! s=-\r\n\
+//! t=0 0\r\n\
+//! m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+//! c=IN IP4 0.0.0.0\r\n\
+//! a=rtpmap:111 opus/48000/2\r\n"
+//!     .parse::<SessionDescription>()?;
+//!
+//! assert_eq!(sd.origin.session_version, 2);
+//! assert_eq!(sd.media_descriptions[0].media_name.media, "audio");
+//! assert!(sd.marshal().starts_with("v=0\r\no=- 4611731400430051336 2 IN IP4"));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The minimum supported Rust version is 1.87.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(rust_2018_idioms)]
+#![deny(missing_docs)]
+#![deny(rustdoc::broken_intra_doc_links)]
 #![allow(dead_code)]
+// Some imports are only used by the std parts.
+#![cfg_attr(not(feature = "std"), allow(unused_imports))]
+
+#[cfg(not(feature = "alloc"))]
+compile_error!("webrtc-rs-sdp needs the `alloc` feature");
+
+extern crate alloc;
 
+/// alloc_prelude brings the parts of the std prelude which come from alloc
+/// into scope without std.
+mod alloc_prelude {
+    pub(crate) use alloc::borrow::ToOwned;
+    pub(crate) use alloc::boxed::Box;
+    pub(crate) use alloc::string::{String, ToString};
+    pub(crate) use alloc::vec::Vec;
+    pub(crate) use alloc::{format, vec};
+}
+
+/// annotate explains a session description line by line in plain words.
+pub mod annotate;
+/// basic has the typed attributes of RFC 4566 such as "a=tool", "a=type"
+/// and "a=framerate".
+pub mod basic;
+/// builder builds session descriptions field by field.
+pub mod builder;
+/// candidate has the ICE "a=candidate" attribute.
+pub mod candidate;
+/// charset has the "a=charset" attribute and decodes text in other
+/// character sets.
+pub mod charset;
+/// common_description has the fields shared by the session and media
+/// levels: "c=", "b=", "k=" and "a=".
 pub mod common_description;
+/// compact writes session descriptions with as few bytes as possible.
+pub mod compact;
+/// content has the "a=content" and "a=label" attributes of RFC 4796.
+pub mod content;
+/// crypto has the SDES "a=crypto" attribute of RFC 4568.
+pub mod crypto;
+/// diagnostic has the findings of SessionDescription::validate.
+pub mod diagnostic;
+/// diff compares two session descriptions.
+pub mod diff;
+/// direction has the "a=sendrecv", "a=sendonly", "a=recvonly" and
+/// "a=inactive" attributes.
 pub mod direction;
+/// edit changes session descriptions as transactions.
+pub mod edit;
+/// error has the Error of the crate.
 pub mod error;
+/// extmap has the "a=extmap" attribute of RFC 8285.
 pub mod extmap;
+/// fingerprint has the "a=fingerprint" attribute of RFC 8122.
+pub mod fingerprint;
+/// fmtp has the "a=fmtp" attribute and its format parameters.
+pub mod fmtp;
+/// group has the "a=group" and "a=mid" attributes of RFC 5888.
+pub mod group;
+/// hdrext knows the RTP header extensions commonly used with WebRTC.
+pub mod hdrext;
+/// hold puts media descriptions on hold and resumes them.
+pub mod hold;
+/// ice has the "a=ice-ufrag", "a=ice-pwd", "a=ice-options" and
+/// "a=ice-lite" attributes.
+pub mod ice;
+/// intern shares the memory of repeated attribute names and values.
+pub mod intern;
+/// keymgmt has the "a=key-mgmt" attribute of RFC 4567.
+pub mod keymgmt;
+/// maxprate has the "a=maxprate" attribute of RFC 3890.
+pub mod maxprate;
+/// media_description has the media descriptions, the "m=" sections of
+/// a session description.
 pub mod media_description;
+/// msid has the "a=msid" and "a=msid-semantic" attributes.
+pub mod msid;
+/// offer_answer makes answers to offers following RFC 3264.
+pub mod offer_answer;
+#[cfg(feature = "std")]
+/// parser reads session descriptions with configurable strictness and
+/// limits.
+pub mod parser;
+pub mod prelude;
+/// ptime has the "a=ptime" and "a=maxptime" attributes.
+pub mod ptime;
+/// resolve has the view of a media description with the session level
+/// fields and attributes it inherits.
+pub mod resolve;
+/// rewrite changes the addresses of a session description, such as for
+/// NAT traversal.
+pub mod rewrite;
+/// rtcp has the "a=rtcp" and "a=rtcp-mux" attributes.
+pub mod rtcp;
+/// rtcpfb has the "a=rtcp-fb" attribute of RFC 4585.
+pub mod rtcpfb;
+/// rtpmap has the "a=rtpmap" attribute.
+pub mod rtpmap;
+/// sanitize removes or replaces the sensitive values of a session
+/// description, e.g. for logging.
+pub mod sanitize;
+/// sctp has the "a=sctp-port", "a=max-message-size" and "a=sctpmap"
+/// attributes.
+pub mod sctp;
+/// session_description has the session description and its session
+/// level fields.
 pub mod session_description;
+/// setup has the "a=setup" and "a=connection" attributes of RFC 4145.
+pub mod setup;
+/// simulcast has the "a=rid" and "a=simulcast" attributes.
+pub mod simulcast;
+/// split takes media descriptions out of session descriptions and puts
+/// them together again.
+pub mod split;
+/// ssrc has the "a=ssrc" and "a=ssrc-group" attributes of RFC 5576.
+pub mod ssrc;
+/// util has helpers shared by the other modules.
 pub mod util;
+/// visit walks the lines of a session description with a visitor.
+pub mod visit;
+
+pub use prelude::*;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_support;
+#[cfg(feature = "chrono")]
+mod chrono_support;
+#[cfg(feature = "webrtc-compat")]
+mod sdp_support;
+#[cfg(feature = "serde")]
+mod serde_support;
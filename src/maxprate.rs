@@ -0,0 +1,111 @@
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::TypedAttribute;
+use super::error::Error;
+
+#[cfg(test)]
+mod maxprate_test;
+
+/// MaxPRate is the maximum packet rate of the "a=maxprate:" attribute, in
+/// packets per second. It is a decimal number which may have a fractional
+/// part; it is stored as fixed-point so that it formats exactly as parsed.
+/// `a=maxprate:<packet rate>`
+/// <https://tools.ietf.org/html/rfc3890#section-6.3>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MaxPRate {
+    /// value is the packet rate multiplied by 10^decimals.
+    value: u64,
+    decimals: u8,
+}
+
+/// More fractional digits than this cannot be scaled within u64.
+const MAX_DECIMALS: usize = 18;
+
+impl MaxPRate {
+    /// new creates a packet rate of value / 10^decimals packets per second.
+    pub fn new(value: u64, decimals: u8) -> Result<Self, Error> {
+        if usize::from(decimals) > MAX_DECIMALS {
+            return Err(Error::SdpInvalidMaxPRate(format!(
+                "{}e-{}",
+                value, decimals
+            )));
+        }
+        Ok(MaxPRate { value, decimals })
+    }
+
+    /// from_packets_per_second creates a whole number packet rate.
+    pub const fn from_packets_per_second(rate: u64) -> Self {
+        MaxPRate {
+            value: rate,
+            decimals: 0,
+        }
+    }
+
+    fn scale(&self) -> u64 {
+        10u64.pow(u32::from(self.decimals))
+    }
+}
+
+impl fmt::Display for MaxPRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.decimals == 0 {
+            return write!(f, "{}", self.value);
+        }
+        write!(
+            f,
+            "{}.{:0width$}",
+            self.value / self.scale(),
+            self.value % self.scale(),
+            width = usize::from(self.decimals)
+        )
+    }
+}
+
+impl FromStr for MaxPRate {
+    type Err = Error;
+
+    /// maxprate-value = integer ["." 1*DIGIT]
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::SdpInvalidMaxPRate(value.to_owned());
+
+        let (integer, fraction) = match value.split_once('.') {
+            Some((integer, fraction)) => (integer, fraction),
+            None => (value, ""),
+        };
+        let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        if !is_digits(integer)
+            || (value.contains('.') && !is_digits(fraction))
+            || fraction.len() > MAX_DECIMALS
+        {
+            return Err(invalid());
+        }
+
+        let digits = format!("{}{}", integer, fraction);
+        Ok(MaxPRate {
+            value: digits.parse().map_err(|_| invalid())?,
+            decimals: fraction.len() as u8,
+        })
+    }
+}
+
+impl TypedAttribute for MaxPRate {
+    const NAME: &'static str = "maxprate";
+}
+
+/// transport_bandwidth computes the transport dependent bandwidth in bits per
+/// second from a TIAS value and the maximum packet rate, given the per packet
+/// overhead of the transport, e.g. 40 bytes for IPv4/UDP/RTP. The result is
+/// rounded up.
+/// <https://tools.ietf.org/html/rfc3890#section-6.4>
+pub fn transport_bandwidth(
+    tias_bps: u64,
+    maxprate: &MaxPRate,
+    per_packet_overhead_bytes: u32,
+) -> u64 {
+    let overhead_bits = u128::from(maxprate.value) * u128::from(per_packet_overhead_bytes) * 8;
+    let overhead_bps = overhead_bits.div_ceil(u128::from(maxprate.scale()));
+    u64::try_from(u128::from(tias_bps) + overhead_bps).unwrap_or(u64::MAX)
+}
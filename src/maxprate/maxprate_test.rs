@@ -0,0 +1,66 @@
+use super::*;
+
+#[test]
+fn test_maxprate_from_str() -> Result<(), Error> {
+    let passingtests = ["50", "28.0", "0.2", "10.25", "0", "0.05"];
+
+    for input in passingtests {
+        let maxprate = input.parse::<MaxPRate>()?;
+        assert_eq!(maxprate.to_string(), input);
+    }
+
+    assert_eq!("0.2".parse::<MaxPRate>()?, MaxPRate::new(2, 1)?);
+    assert_eq!(
+        "50".parse::<MaxPRate>()?,
+        MaxPRate::from_packets_per_second(50)
+    );
+
+    let failingtests = [
+        "",
+        ".",
+        "1.",
+        ".5",
+        "-1",
+        "1.2.3",
+        "1e3",
+        " 5",
+        "0.1234567890123456789",
+    ];
+    for input in failingtests {
+        assert!(
+            matches!(input.parse::<MaxPRate>(), Err(Error::SdpInvalidMaxPRate(_))),
+            "expected `{}` to be rejected",
+            input
+        );
+    }
+    assert!(MaxPRate::new(1, 19).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_transport_bandwidth() -> Result<(), Error> {
+    // IPv4/UDP/RTP: 20 + 8 + 12 bytes per packet.
+    const OVERHEAD: u32 = 40;
+
+    // The example of RFC 3890: b=TIAS:8480 with a=maxprate:10.0 is
+    // announced as b=AS:12, and the session level b=TIAS:50780 with
+    // a=maxprate:28.0 as b=AS:60.
+    let audio = transport_bandwidth(8480, &"10.0".parse()?, OVERHEAD);
+    assert_eq!(audio, 11680);
+    assert_eq!(audio.div_ceil(1000), 12);
+    let session = transport_bandwidth(50780, &"28.0".parse()?, OVERHEAD);
+    assert_eq!(session, 59740);
+    assert_eq!(session.div_ceil(1000), 60);
+
+    // Fractional rates are rounded up to whole bits per second.
+    assert_eq!(transport_bandwidth(1000, &"0.2".parse()?, OVERHEAD), 1064);
+    assert_eq!(transport_bandwidth(0, &"0.3".parse()?, 1), 3);
+
+    assert_eq!(
+        transport_bandwidth(u64::MAX, &"1".parse()?, OVERHEAD),
+        u64::MAX
+    );
+
+    Ok(())
+}
@@ -1,69 +1,762 @@
-use std::fmt;
+use core::fmt;
+use core::net::IpAddr;
+use core::num::NonZeroU16;
+use core::str::FromStr;
 use url::Url;
 
+use super::alloc_prelude::*;
+use super::basic::check_basic_attributes;
+use super::candidate::RemoteCandidates;
 use super::common_description::*;
+use super::content::{Content, Label};
+use super::crypto::Crypto;
+use super::diagnostic::{Diagnostic, DiagnosticKind};
+use super::direction::Direction;
+use super::error::Error;
 use super::extmap::*;
+use super::fmtp::Fmtp;
+use super::group::{Mid, MAX_RECOMMENDED_MID_LEN};
+use super::hdrext::KnownExtension;
+use super::keymgmt::KeyMgmt;
+use super::msid::Msid;
+use super::ptime::{MaxPTime, PTime};
+use super::rtcp::{Rtcp, RtcpMux, RtcpMuxOnly};
+use super::rtcpfb::{PayloadTypeRef, RtcpFeedback};
+use super::rtpmap::{RtpMap, MAX_PAYLOAD_TYPE};
+use super::sctp::{SctpMap, SctpPort, DATACHANNEL_FORMAT};
+use super::setup::Setup;
+use super::simulcast::{Rid, Simulcast};
+use super::ssrc::{Ssrc, SsrcGroup, SsrcGroupSemantics, SsrcSource};
+use super::util::token::{is_token, validate_token};
+use super::util::{decimal_len, line_len, END_LINE};
 
-// Constants for extmap key
-const EXT_MAP_VALUE_TRANSPORT_CC_KEY: isize = 3;
+#[cfg(test)]
+mod media_description_test;
+
+/// Constants for extmap key
+const EXT_MAP_VALUE_TRANSPORT_CC_KEY: u16 = 3;
 const EXT_MAP_VALUE_TRANSPORT_CC_URI: &str =
     "http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01";
 
-// MediaDescription represents a media type.
-// https://tools.ietf.org/html/rfc4566#section-5.14
-#[derive(Debug, Default)]
+/// MediaDescription represents a media type.
+/// <https://tools.ietf.org/html/rfc4566#section-5.14>
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct MediaDescription {
-    // m=<media> <port>/<number of ports> <proto> <fmt> ...
-    // https://tools.ietf.org/html/rfc4566#section-5.14
+    /// `m=<media> <port>/<number of ports> <proto> <fmt> ...`
+    /// <https://tools.ietf.org/html/rfc4566#section-5.14>
     pub media_name: MediaName,
 
-    // i=<session description>
-    // https://tools.ietf.org/html/rfc4566#section-5.4
+    /// `i=<session description>`
+    /// <https://tools.ietf.org/html/rfc4566#section-5.4>
     pub media_title: Option<Information>,
 
-    // c=<nettype> <addrtype> <connection-address>
-    // https://tools.ietf.org/html/rfc4566#section-5.7
+    /// `c=<nettype> <addrtype> <connection-address>`
+    /// <https://tools.ietf.org/html/rfc4566#section-5.7>
     pub connection_information: Option<ConnectionInformation>,
 
-    // b=<bwtype>:<bandwidth>
-    // https://tools.ietf.org/html/rfc4566#section-5.8
+    /// `b=<bwtype>:<bandwidth>`
+    /// <https://tools.ietf.org/html/rfc4566#section-5.8>
     pub bandwidth: Vec<Bandwidth>,
 
-    // k=<method>
-    // k=<method>:<encryption key>
-    // https://tools.ietf.org/html/rfc4566#section-5.12
+    /// `k=<method>`
+    /// `k=<method>:<encryption key>`
+    /// <https://tools.ietf.org/html/rfc4566#section-5.12>
     pub encryption_key: Option<EncryptionKey>,
 
-    // Attributes are the primary means for extending SDP.  Attributes may
-    // be defined to be used as "session-level" attributes, "media-level"
-    // attributes, or both.
-    // https://tools.ietf.org/html/rfc4566#section-5.12
-    pub attributes: Vec<Attribute>,
+    /// Attributes are the primary means for extending SDP.  Attributes may
+    /// be defined to be used as "session-level" attributes, "media-level"
+    /// attributes, or both.
+    /// <https://tools.ietf.org/html/rfc4566#section-5.12>
+    pub attributes: Attributes,
+
+    /// unknown_lines are the lines of unknown types following the "m=" field,
+    /// if they were kept by the parser.
+    pub unknown_lines: Vec<UnknownLine>,
+}
+
+/// A media description is displayed as its lines, see write_to.
+impl fmt::Display for MediaDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_to(f)
+    }
 }
 
 impl MediaDescription {
-    // Attribute returns the value of an attribute and if it exists
-    pub fn attribute(&self, key: &str) -> Option<&String> {
-        for a in &self.attributes {
-            if a.key == key {
-                return a.value.as_ref();
+    /// Attribute returns the value of an attribute and if it exists
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes.get_first(key)?.value()
+    }
+
+    /// direction returns the last direction attribute of the media section.
+    /// Callers should treat None as sendrecv.
+    pub fn direction(&self) -> Option<Direction> {
+        self.attributes.direction()
+    }
+
+    /// set_direction replaces any direction attributes with the given one.
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.attributes.set_direction(direction);
+    }
+
+    /// bandwidth returns the value of the first "b=" field of the given
+    /// modifier, in the unit of the modifier.
+    pub fn bandwidth(&self, kind: BandwidthKind) -> Option<u64> {
+        self.bandwidth
+            .iter()
+            .find(|b| b.kind() == Some(kind))
+            .map(|b| b.bandwidth)
+    }
+
+    /// rtcp_bandwidth returns the RTCP bandwidth in bits per second, from
+    /// "b=RR" and "b=RS". A missing one defaults to its share of 5% of the
+    /// "b=AS" bandwidth: 1/4 for senders and 3/4 for receivers. Only the
+    /// media level fields are considered.
+    /// <https://tools.ietf.org/html/rfc3556#section-2>
+    pub fn rtcp_bandwidth(&self) -> RtcpBandwidth {
+        let session_bps = self.bandwidth(BandwidthKind::As).map(|kbps| kbps * 1000);
+        RtcpBandwidth {
+            rr_bps: self
+                .bandwidth(BandwidthKind::Rr)
+                .or_else(|| session_bps.map(|bps| bps * 3 / 80)),
+            rs_bps: self
+                .bandwidth(BandwidthKind::Rs)
+                .or_else(|| session_bps.map(|bps| bps / 80)),
+        }
+    }
+
+    /// set_bandwidth replaces the "b=" fields with the modifier of the given
+    /// one, kept at the position of the first one found, as there may be at
+    /// most one per modifier.
+    /// <https://tools.ietf.org/html/rfc4566#section-5.8>
+    pub fn set_bandwidth(&mut self, bandwidth: Bandwidth) {
+        match self
+            .bandwidth
+            .iter()
+            .position(|b| b.has_same_modifier(&bandwidth))
+        {
+            Some(position) => {
+                self.bandwidth.retain(|b| !b.has_same_modifier(&bandwidth));
+                self.bandwidth.insert(position, bandwidth);
+            }
+            None => self.bandwidth.push(bandwidth),
+        }
+    }
+
+    /// rtpmaps returns the well-formed "a=rtpmap:" attributes of the media
+    /// section, in order. Malformed ones are skipped.
+    pub fn rtpmaps(&self) -> impl Iterator<Item = RtpMap> + '_ {
+        self.attributes.get::<RtpMap>().filter_map(Result::ok)
+    }
+
+    /// rtpmap_for returns the rtpmap describing the given payload type.
+    pub fn rtpmap_for(&self, payload_type: u8) -> Option<RtpMap> {
+        self.rtpmaps().find(|r| r.payload_type == payload_type)
+    }
+
+    /// fmtps returns the well-formed "a=fmtp:" attributes of the media
+    /// section, in order. Malformed ones are skipped.
+    pub fn fmtps(&self) -> impl Iterator<Item = Fmtp> + '_ {
+        self.attributes.get::<Fmtp>().filter_map(Result::ok)
+    }
+
+    /// fmtp_for returns the fmtp of the given format, if it is one of the
+    /// formats listed in the "m=" line.
+    pub fn fmtp_for(&self, format: &str) -> Option<Fmtp> {
+        if !self.media_name.formats.iter().any(|f| f == format) {
+            return None;
+        }
+        self.fmtps().find(|f| f.format == format)
+    }
+
+    /// remap_payload_type renumbers a payload type throughout the media
+    /// description: the "m=" format list, "a=rtpmap:", "a=fmtp:" and
+    /// "a=rtcp-fb:", along with the payload types embedded in the fmtp of RTX
+    /// ("apt=") and RED ("`<pt>`/`<pt>`") formats. The new payload type must not
+    /// be in use yet.
+    pub fn remap_payload_type(&mut self, from: u8, to: u8) -> Result<(), Error> {
+        let (from_format, to_format) = (from.to_string(), to.to_string());
+        if !self.media_name.formats.contains(&from_format) {
+            return Err(Error::SdpPayloadTypeNotFound(from));
+        }
+        if from == to {
+            return Ok(());
+        }
+        if to > MAX_PAYLOAD_TYPE
+            || self.media_name.formats.contains(&to_format)
+            || self.rtpmap_for(to).is_some()
+        {
+            return Err(Error::SdpPayloadTypeInUse(to));
+        }
+
+        let rtpmaps: Vec<RtpMap> = self.rtpmaps().collect();
+        let is_encoding = |format: &str, name: &str| {
+            rtpmaps
+                .iter()
+                .any(|r| r.payload_type.to_string() == format && r.is_encoding(name))
+        };
+
+        for format in self.media_name.formats.iter_mut() {
+            if *format == from_format {
+                *format = to_format.clone();
+            }
+        }
+
+        for attribute in self.attributes.0.iter_mut() {
+            if RtpMap::matches(attribute) {
+                if let Ok(mut rtpmap) = RtpMap::from_attribute(attribute) {
+                    if rtpmap.payload_type == from {
+                        rtpmap.payload_type = to;
+                        *attribute = rtpmap.to_attribute();
+                    }
+                }
+            } else if Fmtp::matches(attribute) {
+                if let Ok(mut fmtp) = Fmtp::from_attribute(attribute) {
+                    if is_encoding(&fmtp.format, "rtx")
+                        && fmtp.parameter("apt") == Some(&from_format)
+                    {
+                        fmtp.set_parameter("apt", &to_format);
+                    } else if is_encoding(&fmtp.format, "red") {
+                        let params: Vec<_> = fmtp
+                            .params()
+                            .split('/')
+                            .map(|pt| {
+                                if pt == from_format {
+                                    to_format.as_str()
+                                } else {
+                                    pt
+                                }
+                            })
+                            .collect();
+                        fmtp = Fmtp::try_new(&fmtp.format, &params.join("/"))?;
+                    }
+                    if fmtp.format == from_format {
+                        fmtp.format = to_format.clone();
+                    }
+                    *attribute = fmtp.to_attribute();
+                }
+            } else if RtcpFeedback::matches(attribute) {
+                if let Ok(mut feedback) = RtcpFeedback::from_attribute(attribute) {
+                    if feedback.payload_type == PayloadTypeRef::Pt(from) {
+                        feedback.payload_type = PayloadTypeRef::Pt(to);
+                        *attribute = feedback.to_attribute();
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// free_dynamic_payload_types lists the payload types not used by the
+    /// media description, from the dynamic range 96-127 first, then from the
+    /// unassigned range 35-63.
+    /// <https://tools.ietf.org/html/rfc3551#section-6>
+    pub fn free_dynamic_payload_types(&self) -> impl Iterator<Item = u8> + '_ {
+        (96..=MAX_PAYLOAD_TYPE).chain(35..=63).filter(move |pt| {
+            !self.media_name.formats.contains(&pt.to_string()) && self.rtpmap_for(*pt).is_none()
+        })
+    }
+
+    /// remap_extmap_id renumbers the "a=extmap:" attributes with the given
+    /// id. The new id must not be in use yet.
+    /// <https://tools.ietf.org/html/rfc8285#section-5>
+    pub fn remap_extmap_id(&mut self, from: ExtMapId, to: ExtMapId) -> Result<(), Error> {
+        if !self.extmaps().any(|e| e.id == from) {
+            return Err(Error::SdpExtMapIdNotFound(from.get()));
+        }
+        if from == to {
+            return Ok(());
+        }
+        if self.extmaps().any(|e| e.id == to) {
+            return Err(Error::SdpExtMapIdInUse(to.get()));
+        }
+
+        for attribute in self.attributes.0.iter_mut() {
+            if ExtMap::matches(attribute) {
+                if let Ok(mut extmap) = ExtMap::from_attribute(attribute) {
+                    if extmap.id == from {
+                        extmap.id = to;
+                        *attribute = extmap.to_attribute();
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// ptime returns the first well-formed "a=ptime:" attribute.
+    pub fn ptime(&self) -> Option<PTime> {
+        self.attributes.get::<PTime>().find_map(Result::ok)
+    }
+
+    /// set_ptime replaces any "a=ptime:" attributes with the given one.
+    pub fn set_ptime(&mut self, ptime: PTime) {
+        self.attributes.replace(ptime);
+    }
+
+    /// maxptime returns the first well-formed "a=maxptime:" attribute.
+    pub fn maxptime(&self) -> Option<MaxPTime> {
+        self.attributes.get::<MaxPTime>().find_map(Result::ok)
+    }
+
+    /// set_maxptime replaces any "a=maxptime:" attributes with the given one.
+    pub fn set_maxptime(&mut self, maxptime: MaxPTime) {
+        self.attributes.replace(maxptime);
+    }
+
+    /// mid returns the first well-formed "a=mid:" attribute.
+    pub fn mid(&self) -> Option<Mid> {
+        self.attributes.get::<Mid>().find_map(Result::ok)
+    }
+
+    /// set_mid replaces any "a=mid:" attributes with the given one.
+    pub fn set_mid(&mut self, mid: Mid) {
+        self.attributes.replace(mid);
+    }
+
+    /// msid returns the first well-formed "a=msid:" attribute.
+    pub fn msid(&self) -> Option<Msid> {
+        self.attributes.get::<Msid>().find_map(Result::ok)
+    }
+
+    /// rtcp_feedback_for returns the well-formed "a=rtcp-fb:" attributes
+    /// which apply to the payload type, both its own and the "*" ones, in
+    /// their original order and without duplicate feedback types.
+    pub fn rtcp_feedback_for(&self, payload_type: u8) -> Vec<RtcpFeedback> {
+        let mut feedback: Vec<RtcpFeedback> = vec![];
+        for fb in self.attributes.get::<RtcpFeedback>().filter_map(Result::ok) {
+            if fb.payload_type.applies_to(payload_type)
+                && !feedback.iter().any(|f| f.feedback == fb.feedback)
+            {
+                feedback.push(fb);
+            }
+        }
+        feedback
+    }
+
+    /// rtcp_address resolves where RTCP is sent: to the RTP port with
+    /// "a=rtcp-mux", else to the "a=rtcp:" port and address, else to the RTP
+    /// port plus one. The connection information is the media level one
+    /// unless "a=rtcp:" gives an address, so None means that the session
+    /// level "c=" applies. Returns None if the port is zero or unusable.
+    /// <https://tools.ietf.org/html/rfc3605#section-2.1>
+    pub fn rtcp_address(&self) -> Option<Rtcp> {
+        let rtp_port = self.media_name.port.value;
+        if rtp_port == 0 {
+            return None;
+        }
+
+        let (port, connection_information) = if self.attributes.get::<RtcpMux>().next().is_some() {
+            (rtp_port, None)
+        } else if let Some(rtcp) = self.attributes.get::<Rtcp>().find_map(Result::ok) {
+            (rtcp.port, rtcp.connection_information)
+        } else {
+            (self.media_name.port.rtcp_port()?, None)
+        };
+
+        Some(Rtcp {
+            port,
+            connection_information: connection_information
+                .or_else(|| self.connection_information.clone()),
+        })
+    }
+
+    /// set_default_destination points the media description at the address
+    /// and ports of the candidates selected by ICE, as needed by endpoints
+    /// which don't do ICE themselves: the "m=" port becomes rtp_port and the
+    /// media level "c=" the address, added if missing. "a=rtcp:" is set when
+    /// rtcp_port isn't the RTP port plus one, and removed otherwise. See
+    /// SessionDescription::set_default_destination for bundled media.
+    /// <https://tools.ietf.org/html/rfc8839#section-4.2.1.2>
+    pub fn set_default_destination(&mut self, addr: IpAddr, rtp_port: u16, rtcp_port: Option<u16>) {
+        self.rewrite_default_destination(addr, rtp_port, rtcp_port, None);
+    }
+
+    /// rewrite_default_destination is set_default_destination, leaving out
+    /// the media level "c=" if there is none and the session level one
+    /// already matches.
+    pub(crate) fn rewrite_default_destination(
+        &mut self,
+        addr: IpAddr,
+        rtp_port: u16,
+        rtcp_port: Option<u16>,
+        session_connection: Option<&ConnectionInformation>,
+    ) {
+        self.media_name.port.value = rtp_port;
+
+        let connection_information = ConnectionInformation::from(addr);
+        if self.connection_information.is_some()
+            || session_connection != Some(&connection_information)
+        {
+            self.connection_information = Some(connection_information);
+        }
+
+        match rtcp_port {
+            Some(port) if Some(port) != rtp_port.checked_add(1) => self.attributes.replace(Rtcp {
+                port,
+                connection_information: None,
+            }),
+            _ => self.attributes.remove_all::<Rtcp>(),
+        }
+    }
+
+    /// remote_candidates returns the first well-formed
+    /// "a=remote-candidates:" attribute.
+    pub fn remote_candidates(&self) -> Option<RemoteCandidates> {
+        self.attributes
+            .get::<RemoteCandidates>()
+            .find_map(Result::ok)
+    }
+
+    /// extmaps returns the well-formed "a=extmap:" attributes, in order.
+    /// Malformed ones are skipped.
+    pub fn extmaps(&self) -> impl Iterator<Item = ExtMap> + '_ {
+        self.attributes.get::<ExtMap>().filter_map(Result::ok)
+    }
+
+    /// extmap_id_for returns the id the well-known header extension is
+    /// negotiated with.
+    pub fn extmap_id_for(&self, known: KnownExtension) -> Option<ExtMapId> {
+        self.extmaps()
+            .find(|e| e.known() == Some(known))
+            .map(|e| e.id)
+    }
+
+    /// negotiate_extmaps returns the offered extmaps whose URI is also in
+    /// the local ones, in the offered order. The ids are the offerer's, as
+    /// the answer has to use the same ones; the local ids only matter for
+    /// the offers made locally. Unless the offer allows mixed extmaps, those
+    /// needing a two-byte header are dropped.
+    /// <https://tools.ietf.org/html/rfc8285#section-6>
+    pub fn negotiate_extmaps(
+        offer: &[ExtMap],
+        local_supported: &[ExtMap],
+        allow_mixed: bool,
+    ) -> Vec<ExtMap> {
+        offer
+            .iter()
+            .filter(|e| allow_mixed || !e.id.requires_two_byte_header())
+            .filter(|e| local_supported.iter().any(|local| local.uri == e.uri))
+            .cloned()
+            .collect()
+    }
+
+    /// extmap_allow_mixed returns true if "a=extmap-allow-mixed" is present at
+    /// media level.
+    pub fn extmap_allow_mixed(&self) -> bool {
+        self.attributes.get::<ExtmapAllowMixed>().any(|a| a.is_ok())
+    }
+
+    /// rids returns the well-formed "a=rid:" attributes.
+    pub fn rids(&self) -> Vec<Rid> {
+        self.attributes
+            .get::<Rid>()
+            .filter_map(Result::ok)
+            .collect()
+    }
+
+    /// simulcast returns the first well-formed "a=simulcast:" attribute.
+    pub fn simulcast(&self) -> Option<Simulcast> {
+        self.attributes.get::<Simulcast>().find_map(Result::ok)
+    }
+
+    /// ssrcs returns the well-formed "a=ssrc:" attributes grouped by source,
+    /// in the order the sources first appear.
+    pub fn ssrcs(&self) -> Vec<SsrcSource> {
+        let mut sources: Vec<SsrcSource> = vec![];
+        for ssrc in self.attributes.get::<Ssrc>().filter_map(Result::ok) {
+            let Ssrc {
+                id,
+                attribute,
+                value,
+            } = ssrc;
+            match sources.iter_mut().find(|s| s.id == id) {
+                Some(source) => source.attributes.push((attribute, value)),
+                None => sources.push(SsrcSource {
+                    id,
+                    attributes: vec![(attribute, value)],
+                }),
             }
         }
-        None
+        sources
+    }
+
+    /// ssrc_groups returns the well-formed "a=ssrc-group:" attributes.
+    pub fn ssrc_groups(&self) -> Vec<SsrcGroup> {
+        self.attributes
+            .get::<SsrcGroup>()
+            .filter_map(Result::ok)
+            .collect()
     }
 
-    // New JSEPMediaDescription creates a new MediaName with
-    // some settings that are required by the JSEP spec.
-    // TODO: use codec_prefs
+    /// primary_ssrc returns the first source which is not the secondary one
+    /// of a FID group, e.g. the media source rather than its RTX source.
+    pub fn primary_ssrc(&self) -> Option<u32> {
+        let groups = self.ssrc_groups();
+        let is_secondary = |id: u32| {
+            groups
+                .iter()
+                .filter(|g| g.semantics == SsrcGroupSemantics::Fid)
+                .any(|g| g.ssrcs.iter().skip(1).any(|&ssrc| ssrc == id))
+        };
+        self.ssrcs()
+            .into_iter()
+            .map(|s| s.id)
+            .find(|&id| !is_secondary(id))
+    }
+
+    /// is_datachannel returns true for a WebRTC datachannel media
+    /// description, either in the current form with the "webrtc-datachannel"
+    /// format, or in the legacy "DTLS/SCTP" form described by "a=sctpmap:".
+    /// <https://tools.ietf.org/html/rfc8841#section-4.1>
+    pub fn is_datachannel(&self) -> bool {
+        match self.media_name.protos.join("/").as_str() {
+            "UDP/DTLS/SCTP" | "TCP/DTLS/SCTP" => {}
+            "DTLS/SCTP" => {
+                if self
+                    .attributes
+                    .get::<SctpMap>()
+                    .filter_map(Result::ok)
+                    .any(|m| m.app == DATACHANNEL_FORMAT)
+                {
+                    return true;
+                }
+            }
+            _ => return false,
+        }
+        self.media_name
+            .formats
+            .iter()
+            .any(|f| f == DATACHANNEL_FORMAT)
+    }
+
+    /// label returns the first well-formed "a=label:" attribute.
+    pub fn label(&self) -> Option<Label> {
+        self.attributes.get::<Label>().find_map(Result::ok)
+    }
+
+    /// content returns the first well-formed "a=content:" attribute.
+    pub fn content(&self) -> Option<Content> {
+        self.attributes.get::<Content>().find_map(Result::ok)
+    }
+
+    /// crypto returns the well-formed "a=crypto:" attributes, in order of
+    /// preference.
+    pub fn crypto(&self) -> Vec<Crypto> {
+        self.attributes
+            .get::<Crypto>()
+            .filter_map(Result::ok)
+            .collect()
+    }
+
+    /// key_mgmt returns the well-formed media level "a=key-mgmt:" attributes,
+    /// in order of preference. See SessionDescription::effective_key_mgmt for
+    /// the ones in effect.
+    pub fn key_mgmt(&self) -> Vec<KeyMgmt> {
+        self.attributes
+            .get::<KeyMgmt>()
+            .filter_map(Result::ok)
+            .collect()
+    }
+
+    /// sctp_port returns the SCTP port of a datachannel media description
+    /// from "a=sctp-port:", falling back for legacy offers to "a=sctpmap:"
+    /// and then to the "m=" format, which is the port there.
+    pub fn sctp_port(&self) -> Option<u16> {
+        if !self.is_datachannel() {
+            return None;
+        }
+        if let Some(SctpPort(port)) = self.attributes.get::<SctpPort>().find_map(Result::ok) {
+            return Some(port);
+        }
+        if let Some(sctpmap) = self.attributes.get::<SctpMap>().find_map(Result::ok) {
+            return Some(sctpmap.port);
+        }
+        self.media_name
+            .formats
+            .iter()
+            .find_map(|f| f.parse::<SctpPort>().ok())
+            .map(|SctpPort(port)| port)
+    }
+
+    /// setup returns the first well-formed "a=setup:" attribute.
+    pub fn setup(&self) -> Option<Setup> {
+        self.attributes.get::<Setup>().find_map(Result::ok)
+    }
+
+    /// set_setup replaces any "a=setup:" attributes with the given one.
+    pub fn set_setup(&mut self, setup: Setup) {
+        self.attributes.replace(setup);
+    }
+
+    /// validate runs semantic checks which parsing doesn't enforce, because
+    /// endpoints are known to violate them. It never fails; the findings are
+    /// returned for the caller to act on. Checks which need the session level,
+    /// like the presence of "c=", are done by SessionDescription::validate.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+
+        diagnostics.extend(check_duplicate_bandwidth(&self.bandwidth, false));
+        diagnostics.extend(check_basic_attributes(&self.attributes));
+
+        if let Some(encryption_key) = &self.encryption_key {
+            diagnostics.push(check_encryption_key(encryption_key));
+            if self.attributes.has(KeyMgmt::NAME) {
+                diagnostics.push(encryption_key_with_key_mgmt());
+            }
+        }
+
+        // A rejected media description keeps a format only to stay
+        // well-formed.
+        if !self.is_rejected() && self.media_name.protos.iter().any(|p| p == "RTP") {
+            for format in &self.media_name.formats {
+                let payload_type = match format.parse::<u8>() {
+                    Ok(payload_type) if (96..=127).contains(&payload_type) => payload_type,
+                    _ => continue,
+                };
+                if self.rtpmap_for(payload_type).is_none() {
+                    diagnostics.push(Diagnostic::error(
+                        DiagnosticKind::MissingRtpMap,
+                        "m=",
+                        format!("dynamic payload type {} has no rtpmap", payload_type),
+                    ));
+                }
+            }
+        }
+
+        // Each RTP port has its RTCP port next to it, so a range of them has
+        // to start at an even port.
+        // https://tools.ietf.org/html/rfc4566#section-5.14
+        let port = &self.media_name.port;
+        if port.count.is_some_and(|count| count.get() > 1)
+            && port.value % 2 == 1
+            && self.media_name.protos.iter().any(|p| p == "RTP")
+        {
+            diagnostics.push(Diagnostic::error(
+                DiagnosticKind::OddRtpPort,
+                "m=",
+                format!("port range {} starts at an odd port", port),
+            ));
+        }
+
+        if let (Some(ptime), Some(maxptime)) = (self.ptime(), self.maxptime()) {
+            if ptime.as_millis() > maxptime.as_millis() {
+                diagnostics.push(Diagnostic::warning(
+                    DiagnosticKind::PTimeExceedsMaxPTime,
+                    "a=ptime",
+                    format!("ptime {} exceeds maxptime {}", ptime, maxptime),
+                ));
+            }
+        }
+
+        if let Some(as_kbps) = self.bandwidth(BandwidthKind::As) {
+            let rr_bps = self.bandwidth(BandwidthKind::Rr).unwrap_or_default();
+            let rs_bps = self.bandwidth(BandwidthKind::Rs).unwrap_or_default();
+            if rr_bps + rs_bps > as_kbps * 1000 {
+                diagnostics.push(Diagnostic::warning(
+                    DiagnosticKind::RtcpBandwidthExceedsSession,
+                    "b=",
+                    format!(
+                        "RTCP bandwidth of {} bps exceeds b=AS:{} ({} bps)",
+                        rr_bps + rs_bps,
+                        as_kbps,
+                        as_kbps * 1000
+                    ),
+                ));
+            }
+        }
+
+        if self.attributes.get::<RtcpMuxOnly>().next().is_some()
+            && self.attributes.get::<RtcpMux>().next().is_none()
+        {
+            diagnostics.push(Diagnostic::warning(
+                DiagnosticKind::RtcpMuxOnlyWithoutRtcpMux,
+                "a=rtcp-mux-only",
+                "rtcp-mux-only without rtcp-mux".to_owned(),
+            ));
+        }
+
+        if let Some(simulcast) = self.simulcast() {
+            let rids = self.rids();
+            for (direction, simulcast_rid) in simulcast.rids() {
+                match rids.iter().find(|rid| rid.id == simulcast_rid.id) {
+                    None => diagnostics.push(Diagnostic::error(
+                        DiagnosticKind::UnknownSimulcastRid,
+                        "a=simulcast",
+                        format!("rid `{}` has no a=rid line", simulcast_rid.id),
+                    )),
+                    Some(rid) if rid.direction != direction => diagnostics.push(Diagnostic::error(
+                        DiagnosticKind::SimulcastRidDirectionMismatch,
+                        "a=simulcast",
+                        format!(
+                            "rid `{}` is listed as {} but declared as {}",
+                            rid.id, direction, rid.direction
+                        ),
+                    )),
+                    Some(_) => {}
+                }
+            }
+        }
+
+        if let Some(mid) = self.mid() {
+            if mid.as_str().len() > MAX_RECOMMENDED_MID_LEN {
+                diagnostics.push(Diagnostic::warning(
+                    DiagnosticKind::LongMid,
+                    "a=mid",
+                    format!(
+                        "mid `{}` is longer than {} bytes",
+                        mid, MAX_RECOMMENDED_MID_LEN
+                    ),
+                ));
+            }
+        }
+
+        diagnostics
+    }
+
+    /// is_rejected returns true if the media stream has been rejected or
+    /// disabled by setting its port to zero.
+    /// <https://tools.ietf.org/html/rfc3264#section-6>
+    pub fn is_rejected(&self) -> bool {
+        self.media_name.port.value == 0
+    }
+
+    /// reject disables the media stream as in an answer rejecting it: the
+    /// port becomes zero, the protocol and the first format are kept so that
+    /// the "m=" line stays well-formed, and the direction becomes inactive.
+    /// Only "a=mid:" and the rtpmap and fmtp of the kept format remain, so a
+    /// later offer can bring the media stream back.
+    /// <https://tools.ietf.org/html/rfc3264#section-6>
+    pub fn reject(&mut self) {
+        self.media_name.port = Port::new(0);
+        self.media_name.formats.truncate(1);
+        if self.media_name.formats.is_empty() {
+            self.media_name.formats.push("0".to_owned());
+        }
+
+        let format = self.media_name.formats[0].clone();
+        self.bandwidth.clear();
+        self.attributes.0.retain(|attribute| {
+            Mid::matches(attribute)
+                || (RtpMap::matches(attribute)
+                    && RtpMap::from_attribute(attribute)
+                        .is_ok_and(|r| r.payload_type.to_string() == format))
+                || (Fmtp::matches(attribute)
+                    && Fmtp::from_attribute(attribute).is_ok_and(|f| f.format == format))
+        });
+        self.set_direction(Direction::Inactive);
+    }
+
+    /// New JSEPMediaDescription creates a new MediaName with
+    /// some settings that are required by the JSEP spec.
+    /// TODO: use codec_prefs
     pub fn new(codec_type: String, _codec_prefs: Vec<&str>) -> Self {
         // TODO: handle codec_prefs
         MediaDescription {
             media_name: MediaName {
                 media: codec_type,
-                port: RangedPort {
-                    value: 9,
-                    range: None,
-                },
+                port: Port::new(9),
                 protos: vec![
                     "UDP".to_string(),
                     "TLS".to_string(),
@@ -74,7 +767,7 @@ impl MediaDescription {
             },
             media_title: None,
             connection_information: Some(ConnectionInformation {
-                network_type: "IN".to_string(),
+                network_type: NetType::In,
                 address_type: "IP4".to_string(),
                 address: Some(Address {
                     address: "0.0.0.0".to_string(),
@@ -84,29 +777,30 @@ impl MediaDescription {
             }),
             bandwidth: vec![],
             encryption_key: None,
-            attributes: vec![],
+            attributes: Attributes::default(),
+            unknown_lines: vec![],
         }
     }
 
-    // WithPropertyAttribute adds a property attribute 'a=key' to the media description
+    /// WithPropertyAttribute adds a property attribute 'a=key' to the media description
     pub fn with_property_attribute(mut self, key: String) -> Self {
         self.attributes.push(Attribute::new(key, None));
         self
     }
 
-    // WithValueAttribute adds a value attribute 'a=key:value' to the media description
+    /// WithValueAttribute adds a value attribute 'a=key:value' to the media description
     pub fn with_value_attribute(mut self, key: String, value: String) -> Self {
         self.attributes.push(Attribute::new(key, Some(value)));
         self
     }
 
-    // WithICECredentials adds ICE credentials to the media description
+    /// WithICECredentials adds ICE credentials to the media description
     pub fn with_ice_credentials(self, username: String, password: String) -> Self {
         self.with_value_attribute("ice-ufrag".to_string(), username)
             .with_value_attribute("ice-pwd".to_string(), password)
     }
 
-    // WithCodec adds codec information to the media description
+    /// WithCodec adds codec information to the media description
     pub fn with_codec(
         mut self,
         payload_type: u8,
@@ -121,7 +815,7 @@ impl MediaDescription {
             rtpmap += format!("/{}", channels).as_str();
         }
 
-        if &fmtp != "" {
+        if !fmtp.is_empty() {
             self.with_value_attribute("rtpmap".to_string(), rtpmap)
                 .with_value_attribute("fmtp".to_string(), format!("{} {}", payload_type, fmtp))
         } else {
@@ -129,7 +823,7 @@ impl MediaDescription {
         }
     }
 
-    // WithMediaSource adds media source information to the media description
+    /// WithMediaSource adds media source information to the media description
     pub fn with_media_source(
         self,
         ssrc: u32,
@@ -145,70 +839,713 @@ impl MediaDescription {
         // Deprecated but not phased out?
     }
 
-    // WithCandidate adds an ICE candidate to the media description
-    // Deprecated: use WithICECandidate instead
+    /// WithCandidate adds an ICE candidate to the media description
+    /// Deprecated: use WithICECandidate instead
     pub fn with_candidate(self, value: String) -> Self {
         self.with_value_attribute("candidate".to_string(), value)
     }
 
+    /// with_extmap adds an "a=extmap" attribute to the media description.
     pub fn with_extmap(self, e: ExtMap) -> Self {
-        self.with_property_attribute(e.marshal())
+        self.with_value_attribute("extmap".to_string(), e.to_string())
     }
 
-    // WithTransportCCExtMap adds an extmap to the media description
+    /// WithTransportCCExtMap adds an extmap to the media description
     pub fn with_transport_cc_extmap(self) -> Self {
-        let uri = match Url::parse(EXT_MAP_VALUE_TRANSPORT_CC_URI) {
-            Ok(uri) => Some(uri),
-            Err(_) => None,
-        };
-
         let e = ExtMap {
-            value: EXT_MAP_VALUE_TRANSPORT_CC_KEY,
-            uri,
-            ..Default::default()
+            id: ExtMapId(EXT_MAP_VALUE_TRANSPORT_CC_KEY),
+            direction: None,
+            uri: Url::parse(EXT_MAP_VALUE_TRANSPORT_CC_URI).expect("valid transport-cc uri"),
+            attributes: None,
         };
 
         self.with_extmap(e)
     }
+
+    /// marshal_into appends the marshaled media description to buf, see
+    /// SessionDescription::marshal_into.
+    pub fn marshal_into(&self, buf: &mut String) {
+        buf.reserve(self.marshal_len_hint());
+        // Writing to a String never fails.
+        let _ = self.write_to(buf);
+    }
+
+    /// write_to writes the lines of the media description to w, starting
+    /// with "m=".
+    pub fn write_to<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        let mut w = UnknownLineWriter::new(w, &self.unknown_lines);
+        self.write_fields_to(&mut w)?;
+        w.finish()
+    }
+
+    fn write_fields_to<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        write!(w, "m={}{}", self.media_name, END_LINE)?;
+        if let Some(media_title) = &self.media_title {
+            write!(w, "i={}{}", media_title, END_LINE)?;
+        }
+        if let Some(connection_information) = &self.connection_information {
+            write!(w, "c={}{}", connection_information, END_LINE)?;
+        }
+        for bandwidth in &self.bandwidth {
+            write!(w, "b={}{}", bandwidth, END_LINE)?;
+        }
+        if let Some(encryption_key) = &self.encryption_key {
+            write!(w, "k={}{}", encryption_key, END_LINE)?;
+        }
+        for attribute in &self.attributes {
+            write!(w, "a={}{}", attribute, END_LINE)?;
+        }
+        Ok(())
+    }
+
+    /// line_count returns the number of lines written by write_to, without
+    /// the unknown lines.
+    pub(crate) fn line_count(&self) -> usize {
+        1 + usize::from(self.media_title.is_some())
+            + usize::from(self.connection_information.is_some())
+            + self.bandwidth.len()
+            + usize::from(self.encryption_key.is_some())
+            + self.attributes.len()
+    }
+
+    /// marshal_len_hint returns the length of the lines written by write_to.
+    pub fn marshal_len_hint(&self) -> usize {
+        line_len(self.media_name.marshal_len_hint())
+            + self
+                .media_title
+                .as_ref()
+                .map_or(0, |media_title| line_len(media_title.as_bytes().len()))
+            + self
+                .connection_information
+                .as_ref()
+                .map_or(0, |c| line_len(c.marshal_len_hint()))
+            + self
+                .bandwidth
+                .iter()
+                .map(|b| line_len(b.marshal_len_hint()))
+                .sum::<usize>()
+            + self
+                .encryption_key
+                .as_ref()
+                .map_or(0, |k| line_len(k.marshal_len_hint()))
+            + self
+                .attributes
+                .iter()
+                .map(|a| line_len(a.marshal_len_hint()))
+                .sum::<usize>()
+            + self
+                .unknown_lines
+                .iter()
+                .map(|l| l.marshal_len_hint() + 2)
+                .sum::<usize>()
+    }
 }
 
-// RangedPort supports special format for the media field "m=" port value. If
-// it may be necessary to specify multiple transport ports, the protocol allows
-// to write it as: <port>/<number of ports> where number of ports is a an
-// offsetting range.
-#[derive(Debug, Default)]
-pub struct RangedPort {
-    pub value: isize,
-    pub range: Option<isize>,
+/// check_duplicate_bandwidth reports every modifier with more than one "b="
+/// field. Duplicate "b=CT" fields at session level have their own kind, so
+/// they can be left out with except_ct.
+pub(crate) fn check_duplicate_bandwidth(
+    bandwidth: &[Bandwidth],
+    except_ct: bool,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    for (index, b) in bandwidth.iter().enumerate() {
+        if except_ct && b.kind() == Some(BandwidthKind::Ct) {
+            continue;
+        }
+        // Only the first of the duplicates reports them.
+        if bandwidth[..index]
+            .iter()
+            .any(|other| other.has_same_modifier(b))
+        {
+            continue;
+        }
+        let count = bandwidth
+            .iter()
+            .filter(|other| other.has_same_modifier(b))
+            .count();
+        if count > 1 {
+            let prefix = if b.experimental { "X-" } else { "" };
+            diagnostics.push(Diagnostic::error(
+                DiagnosticKind::DuplicateBandwidth,
+                "b=",
+                format!("b={}{} appears {} times", prefix, b.bandwidth_type, count),
+            ));
+        }
+    }
+    diagnostics
+}
+
+/// check_encryption_key warns about any "k=" field, as the mechanism is NOT
+/// RECOMMENDED. Keys sent in the description itself, which is only safe over
+/// a secure channel, get the more specific InsecureEncryptionKey.
+pub(crate) fn check_encryption_key(encryption_key: &EncryptionKey) -> Diagnostic {
+    match encryption_key {
+        EncryptionKey::Clear(_) | EncryptionKey::Base64(_) => Diagnostic::warning(
+            DiagnosticKind::InsecureEncryptionKey,
+            "k=",
+            format!(
+                "the {} key method is NOT RECOMMENDED",
+                encryption_key.method()
+            ),
+        ),
+        _ => Diagnostic::warning(
+            DiagnosticKind::DeprecatedEncryptionKey,
+            "k=",
+            "k= is NOT RECOMMENDED".to_owned(),
+        ),
+    }
+}
+
+pub(crate) fn encryption_key_with_key_mgmt() -> Diagnostic {
+    Diagnostic::warning(
+        DiagnosticKind::EncryptionKeyWithKeyMgmt,
+        "k=",
+        "k= is used along with a=key-mgmt".to_owned(),
+    )
+}
+
+/// MediaDescriptionRef is the borrowed form of MediaDescription. Numeric
+/// fields and the rarely used "b=" lines are kept in their owned form.
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::session_description::SessionDescription;
+///
+/// # fn main() -> Result<(), Error> {
+/// let sd = SessionDescription::parse_borrowed(
+///     "v=0\r\n\
+/// o=- 0 1 IN IP4 127.0.0.1\r\n\
+/// s=-\r\n\
+/// t=0 0\r\n\
+/// m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+/// a=mid:0\r\n",
+/// )?;
+/// let media = &sd.media_descriptions[0];
+/// assert_eq!(media.media_name.media, "audio");
+/// assert_eq!(
+///     media.to_owned().media_name.to_string(),
+///     "audio 9 UDP/TLS/RTP/SAVPF 111"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MediaDescriptionRef<'a> {
+    /// media_name is the "m=" field, see MediaDescription::media_name.
+    pub media_name: MediaNameRef<'a>,
+    /// media_title is the "i=" field, see MediaDescription::media_title.
+    pub media_title: Option<&'a str>,
+    /// connection_information is the "c=" field, see
+    /// MediaDescription::connection_information.
+    pub connection_information: Option<ConnectionInformationRef<'a>>,
+    /// bandwidth are the "b=" fields, see MediaDescription::bandwidth.
+    pub bandwidth: Vec<Bandwidth>,
+    /// encryption_key is the "k=" field, see MediaDescription::encryption_key.
+    pub encryption_key: Option<EncryptionKey>,
+    /// attributes are the "a=" fields, see MediaDescription::attributes.
+    pub attributes: Vec<AttributeRef<'a>>,
 }
 
-impl fmt::Display for RangedPort {
+impl MediaDescriptionRef<'_> {
+    /// to_owned copies the media description into a MediaDescription.
+    pub fn to_owned(&self) -> MediaDescription {
+        MediaDescription {
+            media_name: self.media_name.to_owned(),
+            media_title: self.media_title.map(|t| Information(t.as_bytes().to_vec())),
+            connection_information: self.connection_information.as_ref().map(|c| c.to_owned()),
+            bandwidth: self.bandwidth.clone(),
+            encryption_key: self.encryption_key.clone(),
+            attributes: self
+                .attributes
+                .iter()
+                .map(|a| a.to_owned())
+                .collect::<Vec<_>>()
+                .into(),
+            unknown_lines: vec![],
+        }
+    }
+}
+
+/// MediaType is the `<media>` field of the "m=" line. The types currently
+/// registered with IANA get their own variant, anything else which is a valid
+/// token is kept as-is.
+/// <https://tools.ietf.org/html/rfc4566#section-8.2.1>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::media_description::MediaType;
+///
+/// # fn main() -> Result<(), Error> {
+/// assert_eq!("video".parse::<MediaType>()?, MediaType::Video);
+/// assert_eq!("image".parse::<MediaType>()?.to_string(), "image");
+/// assert!("vid eo".parse::<MediaType>().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MediaType {
+    /// Audio is "audio".
+    Audio,
+    /// Video is "video".
+    Video,
+    /// Text is "text".
+    Text,
+    /// Application is "application", e.g. for data channels.
+    Application,
+    /// Message is "message".
+    Message,
+    /// Other is any other media type, kept as written.
+    Other(String),
+}
+
+impl fmt::Display for MediaType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if let Some(range) = self.range {
-            write!(f, "{}/{}", self.value, range)
+        let s = match self {
+            MediaType::Audio => "audio",
+            MediaType::Video => "video",
+            MediaType::Text => "text",
+            MediaType::Application => "application",
+            MediaType::Message => "message",
+            MediaType::Other(media) => media,
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for MediaType {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(match value {
+            "audio" => MediaType::Audio,
+            "video" => MediaType::Video,
+            "text" => MediaType::Text,
+            "application" => MediaType::Application,
+            "message" => MediaType::Message,
+            _ => {
+                validate_token(value)?;
+                MediaType::Other(value.to_owned())
+            }
+        })
+    }
+}
+
+/// Protocol is the `<proto>` field of the "m=" line.
+/// <https://tools.ietf.org/html/rfc4566#section-5.14>
+/// <https://tools.ietf.org/html/rfc8839#section-5.1>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::media_description::Protocol;
+///
+/// # fn main() -> Result<(), Error> {
+/// assert_eq!("UDP/TLS/RTP/SAVPF".parse::<Protocol>()?, Protocol::UdpTlsRtpSavpf);
+/// assert_eq!("TCP/MRCPv2".parse::<Protocol>()?.to_string(), "TCP/MRCPv2");
+/// assert!("RTP//AVP".parse::<Protocol>().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Protocol {
+    /// UDP/TLS/RTP/SAVPF, the profile mandated by JSEP
+    UdpTlsRtpSavpf,
+    /// RTP/AVP
+    RtpAvp,
+    /// RTP/SAVPF
+    RtpSavpf,
+    /// DTLS/SCTP, the legacy data channel profile
+    DtlsSctp,
+    /// UDP/DTLS/SCTP
+    UdpDtlsSctp,
+    /// Other is any other protocol, kept as written.
+    Other(String),
+}
+
+/// JSEP_RTP_PROFILES are the `<proto>` values of RTP media descriptions
+/// which a JSEP implementation accepts in an offer and treats as
+/// UDP/TLS/RTP/SAVPF or TCP/DTLS/RTP/SAVPF.
+/// <https://tools.ietf.org/html/rfc8829#section-5.1.3>
+const JSEP_RTP_PROFILES: &[&str] = &[
+    "UDP/TLS/RTP/SAVPF",
+    "TCP/DTLS/RTP/SAVPF",
+    "UDP/TLS/RTP/SAVP",
+    "TCP/DTLS/RTP/SAVP",
+    "RTP/SAVPF",
+    "RTP/SAVP",
+    "RTP/AVPF",
+    "RTP/AVP",
+];
+
+/// JSEP_SCTP_PROFILES are the `<proto>` values of data media descriptions
+/// which a JSEP implementation accepts in an offer and treats as
+/// UDP/DTLS/SCTP or TCP/DTLS/SCTP.
+/// <https://tools.ietf.org/html/rfc8829#section-5.1.3>
+const JSEP_SCTP_PROFILES: &[&str] = &["UDP/DTLS/SCTP", "TCP/DTLS/SCTP", "DTLS/SCTP"];
+
+/// ANSWER_COMPATIBILITY lists, for each local profile, the offered profiles
+/// besides itself it can answer. The answer then repeats the offered
+/// `<proto>` exactly. Profiles not listed only answer themselves.
+/// <https://tools.ietf.org/html/rfc8829#section-5.3.1>
+const ANSWER_COMPATIBILITY: &[(&str, &[&str])] = &[
+    ("UDP/TLS/RTP/SAVPF", JSEP_RTP_PROFILES),
+    ("TCP/DTLS/RTP/SAVPF", JSEP_RTP_PROFILES),
+    ("UDP/DTLS/SCTP", JSEP_SCTP_PROFILES),
+    ("TCP/DTLS/SCTP", JSEP_SCTP_PROFILES),
+];
+
+impl Protocol {
+    /// as_str returns the `<proto>` as written in the "m=" line.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Protocol::UdpTlsRtpSavpf => "UDP/TLS/RTP/SAVPF",
+            Protocol::RtpAvp => "RTP/AVP",
+            Protocol::RtpSavpf => "RTP/SAVPF",
+            Protocol::DtlsSctp => "DTLS/SCTP",
+            Protocol::UdpDtlsSctp => "UDP/DTLS/SCTP",
+            Protocol::Other(proto) => proto,
+        }
+    }
+
+    fn has_token(&self, token: &str) -> bool {
+        self.as_str().split('/').any(|t| t == token)
+    }
+
+    /// is_rtp returns true for RTP profiles, e.g. RTP/AVP or
+    /// UDP/TLS/RTP/SAVPF.
+    pub fn is_rtp(&self) -> bool {
+        self.has_token("RTP")
+    }
+
+    /// is_secure returns true if the media is protected, with SRTP (SAVP and
+    /// SAVPF), or with TLS or DTLS as in UDP/TLS/RTP/SAVPF and DTLS/SCTP.
+    /// <https://tools.ietf.org/html/rfc3711>
+    /// <https://tools.ietf.org/html/rfc5764#section-8>
+    pub fn is_secure(&self) -> bool {
+        ["SAVP", "SAVPF", "TLS", "DTLS"]
+            .iter()
+            .any(|token| self.has_token(token))
+    }
+
+    /// supports_feedback returns true for the RTP profiles with RTCP
+    /// feedback, AVPF and SAVPF.
+    /// <https://tools.ietf.org/html/rfc4585>
+    pub fn supports_feedback(&self) -> bool {
+        self.is_rtp() && (self.has_token("AVPF") || self.has_token("SAVPF"))
+    }
+
+    /// is_sctp returns true for SCTP based profiles, e.g. UDP/DTLS/SCTP.
+    /// <https://tools.ietf.org/html/rfc8841#section-4.1>
+    pub fn is_sctp(&self) -> bool {
+        self.has_token("SCTP")
+    }
+
+    /// answer_compatible reports whether an answerer using this profile can
+    /// answer a media description offered with the given one, following
+    /// ANSWER_COMPATIBILITY: a JSEP profile answers any profile JSEP accepts
+    /// for the same kind of media, e.g. UDP/TLS/RTP/SAVPF answers RTP/SAVPF
+    /// and RTP/AVP offers. Any other profile only answers itself.
+    /// <https://tools.ietf.org/html/rfc8829#section-5.1.3>
+    pub fn answer_compatible(&self, offered: &Protocol) -> bool {
+        self == offered
+            || ANSWER_COMPATIBILITY.iter().any(|(local, accepted)| {
+                *local == self.as_str() && accepted.contains(&offered.as_str())
+            })
+    }
+}
+
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Protocol {
+    type Err = Error;
+
+    /// `<proto>` is a list of tokens separated by "/"
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(match value {
+            "UDP/TLS/RTP/SAVPF" => Protocol::UdpTlsRtpSavpf,
+            "RTP/AVP" => Protocol::RtpAvp,
+            "RTP/SAVPF" => Protocol::RtpSavpf,
+            "DTLS/SCTP" => Protocol::DtlsSctp,
+            "UDP/DTLS/SCTP" => Protocol::UdpDtlsSctp,
+            _ => {
+                validate_protocol(value)?;
+                Protocol::Other(value.to_owned())
+            }
+        })
+    }
+}
+
+/// validate_protocol checks that `<proto>` is a list of tokens separated by "/".
+pub(crate) fn validate_protocol(value: &str) -> Result<(), Error> {
+    if !value.split('/').all(is_token) {
+        return Err(Error::SdpInvalidValue(value.to_owned()));
+    }
+    Ok(())
+}
+
+/// Port is the `<port>`/`<number of ports>` part of the "m=" field. The number
+/// of ports is only present if the media stream uses several consecutive
+/// transport ports; for RTP each of them is paired with its RTCP port.
+/// <https://tools.ietf.org/html/rfc4566#section-5.14>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::media_description::Port;
+///
+/// # fn main() -> Result<(), Error> {
+/// let port = "49170/2".parse::<Port>()?;
+/// assert_eq!(port.ports().collect::<Vec<_>>(), vec![49170, 49171]);
+/// assert_eq!(port.to_string(), "49170/2");
+/// assert_eq!(Port::new(9).to_string(), "9");
+/// assert_eq!(Port::new(9).rtcp_port(), Some(10));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Port {
+    /// value is the base `<port>`.
+    pub value: u16,
+    /// count is the `<number of ports>`, if given.
+    pub count: Option<NonZeroU16>,
+}
+
+impl Port {
+    /// new creates a single port.
+    pub const fn new(value: u16) -> Self {
+        Port { value, count: None }
+    }
+
+    /// rtcp_port returns the port of the RTCP stream implied by RTP on this
+    /// port, which is the next higher one, or None if there isn't one.
+    /// <https://tools.ietf.org/html/rfc3550#section-11>
+    pub const fn rtcp_port(&self) -> Option<u16> {
+        self.value.checked_add(1)
+    }
+
+    /// ports iterates over the consecutive transport ports starting at the
+    /// base port, one of them without a number of ports.
+    pub fn ports(&self) -> impl Iterator<Item = u16> {
+        let (value, count) = (self.value, self.count.map_or(1, NonZeroU16::get));
+        (0..count).map_while(move |i| value.checked_add(i))
+    }
+}
+
+impl FromStr for Port {
+    type Err = Error;
+
+    /// `<port>`/`<number of ports>`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (port, count) = match value.split_once('/') {
+            Some((port, count)) => (port, Some(count)),
+            None => (value, None),
+        };
+
+        let port_value = port.parse::<u16>()?;
+        let port_count = match count {
+            Some(count) => {
+                let count = NonZeroU16::new(count.parse::<u16>()?)
+                    .ok_or_else(|| Error::SdpInvalidValue(value.to_owned()))?;
+                // The last port has to be a valid port too.
+                if port_value.checked_add(count.get() - 1).is_none() {
+                    return Err(Error::SdpInvalidValue(value.to_owned()));
+                }
+                Some(count)
+            }
+            None => None,
+        };
+
+        Ok(Port {
+            value: port_value,
+            count: port_count,
+        })
+    }
+}
+
+impl fmt::Display for Port {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(count) = self.count {
+            write!(f, "{}/{}", self.value, count)
         } else {
             write!(f, "{}", self.value)
         }
     }
 }
 
-// MediaName describes the "m=" field storage structure.
-#[derive(Debug, Default)]
+/// MediaName describes the "m=" field storage structure.
+/// <https://tools.ietf.org/html/rfc4566#section-5.14>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::media_description::{MediaName, MediaType, Port};
+///
+/// # fn main() -> Result<(), Error> {
+/// let media_name = "audio 49170 RTP/AVP 0 8".parse::<MediaName>()?;
+/// assert_eq!(media_name.media_type()?, MediaType::Audio);
+/// assert_eq!(media_name.formats, vec!["0", "8"]);
+/// assert_eq!(media_name.to_string(), "audio 49170 RTP/AVP 0 8");
+///
+/// let media_name = MediaName {
+///     media: "video".to_owned(),
+///     port: Port::new(9),
+///     protos: vec!["RTP".to_owned(), "AVP".to_owned()],
+///     formats: vec!["31".to_owned()],
+/// };
+/// assert_eq!(media_name.to_string(), "video 9 RTP/AVP 31");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct MediaName {
+    /// media is the `<media>` type, see media_type.
     pub media: String,
-    pub port: RangedPort,
+    /// port is the `<port>` and `<number of ports>`.
+    pub port: Port,
+    /// protos are the parts of the `<proto>`, which are separated by "/".
     pub protos: Vec<String>,
+    /// formats are the `<fmt>` values, e.g. the RTP payload types.
     pub formats: Vec<String>,
 }
 
 impl fmt::Display for MediaName {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let s = vec![
-            self.media.clone(),
-            self.port.to_string(),
-            self.protos.join("/"),
-            self.formats.join(" "),
-        ];
-        write!(f, "{}", s.join(" "))
+        write!(f, "{} {} ", self.media, self.port)?;
+        for (i, proto) in self.protos.iter().enumerate() {
+            let separator = if i == 0 { "" } else { "/" };
+            write!(f, "{}{}", separator, proto)?;
+        }
+        f.write_str(" ")?;
+        for (i, format) in self.formats.iter().enumerate() {
+            let separator = if i == 0 { "" } else { " " };
+            write!(f, "{}{}", separator, format)?;
+        }
+        Ok(())
+    }
+}
+
+impl MediaName {
+    /// marshal_len_hint returns the length of the marshaled media name.
+    pub fn marshal_len_hint(&self) -> usize {
+        let joined_len = |values: &[String]| {
+            values.iter().map(String::len).sum::<usize>() + values.len().saturating_sub(1)
+        };
+        self.media.len()
+            + 1
+            + decimal_len(u64::from(self.port.value))
+            + self
+                .port
+                .count
+                .map_or(0, |count| 1 + decimal_len(u64::from(count.get())))
+            + 1
+            + joined_len(&self.protos)
+            + 1
+            + joined_len(&self.formats)
+    }
+}
+
+impl FromStr for MediaName {
+    type Err = Error;
+
+    /// `m=<media> <port>/<number of ports> <proto> <fmt> ...`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(MediaNameRef::parse(value)?.to_owned())
+    }
+}
+
+/// MediaNameRef is the borrowed form of MediaName.
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::media_description::MediaNameRef;
+///
+/// # fn main() -> Result<(), Error> {
+/// let media_name = MediaNameRef::parse("application 9 UDP/DTLS/SCTP webrtc-datachannel")?;
+/// assert_eq!(media_name.protos, vec!["UDP", "DTLS", "SCTP"]);
+/// assert_eq!(
+///     media_name.to_owned().to_string(),
+///     "application 9 UDP/DTLS/SCTP webrtc-datachannel"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MediaNameRef<'a> {
+    /// media is the `<media>` type, see MediaName::media.
+    pub media: &'a str,
+    /// port is the `<port>` and `<number of ports>`, see MediaName::port.
+    pub port: Port,
+    /// protos are the parts of the `<proto>`, see MediaName::protos.
+    pub protos: Vec<&'a str>,
+    /// formats are the `<fmt>` values, see MediaName::formats.
+    pub formats: Vec<&'a str>,
+}
+
+impl<'a> MediaNameRef<'a> {
+    /// `m=<media> <port>/<number of ports> <proto> <fmt> ...`
+    pub fn parse(value: &'a str) -> Result<Self, Error> {
+        let mut fields = value.split_whitespace();
+        let (media, port, proto) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(media), Some(port), Some(proto)) => (media, port, proto),
+            _ => return Err(Error::SdpInvalidSyntax(format!("`m={}`", value))),
+        };
+
+        // <fmt>...
+        let formats: Vec<&'a str> = fields.collect();
+        if formats.is_empty() {
+            return Err(Error::SdpInvalidSyntax(format!("`m={}`", value)));
+        }
+
+        // <media>
+        validate_token(media)?;
+
+        // <port>
+        let port = port.parse::<Port>()?;
+
+        // <proto>
+        validate_protocol(proto)?;
+
+        Ok(MediaNameRef {
+            media,
+            port,
+            protos: proto.split('/').collect(),
+            formats,
+        })
+    }
+
+    /// to_owned copies the media name into a MediaName.
+    pub fn to_owned(&self) -> MediaName {
+        MediaName {
+            media: self.media.to_owned(),
+            port: self.port,
+            protos: self.protos.iter().map(|p| p.to_string()).collect(),
+            formats: self.formats.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+}
+
+impl MediaName {
+    /// media_type returns the typed `<media>` field.
+    pub fn media_type(&self) -> Result<MediaType, Error> {
+        self.media.parse()
+    }
+
+    /// protocol returns the typed `<proto>` field.
+    pub fn protocol(&self) -> Result<Protocol, Error> {
+        self.protos.join("/").parse()
     }
 }
@@ -0,0 +1,826 @@
+use super::*;
+
+#[test]
+fn test_protocol_predicates() -> Result<(), Error> {
+    // proto, is_rtp, is_secure, supports_feedback, is_sctp
+    let tests = [
+        ("UDP/TLS/RTP/SAVPF", true, true, true, false),
+        ("TCP/DTLS/RTP/SAVPF", true, true, true, false),
+        ("UDP/TLS/RTP/SAVP", true, true, false, false),
+        ("RTP/SAVPF", true, true, true, false),
+        ("RTP/SAVP", true, true, false, false),
+        ("RTP/AVPF", true, false, true, false),
+        ("RTP/AVP", true, false, false, false),
+        ("UDP/DTLS/SCTP", false, true, false, true),
+        ("TCP/DTLS/SCTP", false, true, false, true),
+        ("DTLS/SCTP", false, true, false, true),
+        ("TCP/TLS/MSRP", false, true, false, false),
+        ("TCP/MSRP", false, false, false, false),
+        ("udptl", false, false, false, false),
+    ];
+
+    for &(input, rtp, secure, feedback, sctp) in tests.iter() {
+        let protocol = input.parse::<Protocol>()?;
+        assert_eq!(protocol.is_rtp(), rtp, "{}", input);
+        assert_eq!(protocol.is_secure(), secure, "{}", input);
+        assert_eq!(protocol.supports_feedback(), feedback, "{}", input);
+        assert_eq!(protocol.is_sctp(), sctp, "{}", input);
+        // Unknown profiles are kept as written.
+        assert_eq!(protocol.as_str(), input);
+        assert_eq!(protocol.to_string(), input);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_protocol_answer_compatible() -> Result<(), Error> {
+    let profiles = [
+        "UDP/TLS/RTP/SAVPF",
+        "TCP/DTLS/RTP/SAVPF",
+        "UDP/TLS/RTP/SAVP",
+        "TCP/DTLS/RTP/SAVP",
+        "RTP/SAVPF",
+        "RTP/SAVP",
+        "RTP/AVPF",
+        "RTP/AVP",
+        "UDP/DTLS/SCTP",
+        "TCP/DTLS/SCTP",
+        "DTLS/SCTP",
+        "TCP/MSRP",
+    ];
+    // One row per local profile, one column per offered profile, in the
+    // order above.
+    let matrix = [
+        "xxxxxxxx....",
+        "xxxxxxxx....",
+        "..x.........",
+        "...x........",
+        "....x.......",
+        ".....x......",
+        "......x.....",
+        ".......x....",
+        "........xxx.",
+        "........xxx.",
+        "..........x.",
+        "...........x",
+    ];
+
+    for (local, row) in profiles.iter().zip(matrix.iter()) {
+        let local = local.parse::<Protocol>()?;
+        for (offered, cell) in profiles.iter().zip(row.chars()) {
+            let offered = offered.parse::<Protocol>()?;
+            assert_eq!(
+                local.answer_compatible(&offered),
+                cell == 'x',
+                "{} answering {}",
+                local,
+                offered
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_media_name_from_str() -> Result<(), Error> {
+    let passingtests = [
+        (
+            "audio 9 UDP/TLS/RTP/SAVPF 111 103 104 9 0 8",
+            MediaType::Audio,
+            Protocol::UdpTlsRtpSavpf,
+        ),
+        ("video 51372 RTP/AVP 99", MediaType::Video, Protocol::RtpAvp),
+        (
+            "video 49170/2 RTP/SAVPF 96 97",
+            MediaType::Video,
+            Protocol::RtpSavpf,
+        ),
+        (
+            "application 9 UDP/DTLS/SCTP webrtc-datachannel",
+            MediaType::Application,
+            Protocol::UdpDtlsSctp,
+        ),
+        (
+            "application 5000 DTLS/SCTP 5000",
+            MediaType::Application,
+            Protocol::DtlsSctp,
+        ),
+        (
+            "image 54111 udptl t38",
+            MediaType::Other("image".to_owned()),
+            Protocol::Other("udptl".to_owned()),
+        ),
+        (
+            "message 0 TCP/MSRP *",
+            MediaType::Message,
+            Protocol::Other("TCP/MSRP".to_owned()),
+        ),
+    ];
+
+    for (input, media_type, protocol) in passingtests.iter() {
+        let media_name = input.parse::<MediaName>()?;
+        assert_eq!(media_name.media_type()?, *media_type);
+        assert_eq!(media_name.protocol()?, *protocol);
+        assert_eq!(media_name.to_string(), *input);
+    }
+
+    let media_name = "video 49170/2 RTP/SAVPF 96 97".parse::<MediaName>()?;
+    assert_eq!(media_name.port.value, 49170);
+    assert_eq!(media_name.port.count, NonZeroU16::new(2));
+    assert_eq!(media_name.formats, vec!["96", "97"]);
+
+    let failingtests = [
+        "audio 9 UDP/TLS/RTP/SAVPF",
+        "audio 70000 RTP/AVP 0",
+        "audio 9/0 RTP/AVP 0",
+        "audio 9/x RTP/AVP 0",
+        "audio 65535/2 RTP/AVP 0",
+        "au\u{e9}dio 9 RTP/AVP 0",
+        "audio 9 RTP//AVP 0",
+    ];
+    for input in failingtests.iter() {
+        assert!(
+            input.parse::<MediaName>().is_err(),
+            "expected `{}` to be rejected",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_rejected_media() -> Result<(), Error> {
+    let md = MediaDescription {
+        media_name: "audio 0 UDP/TLS/RTP/SAVPF 0".parse()?,
+        ..Default::default()
+    };
+    assert!(md.is_rejected());
+
+    let md = MediaDescription::new("audio".to_owned(), vec![]);
+    assert!(!md.is_rejected());
+
+    Ok(())
+}
+
+#[test]
+fn test_direction() -> Result<(), Error> {
+    let mut md = MediaDescription::new("audio".to_owned(), vec![]).with_codec(
+        111,
+        "opus".to_owned(),
+        48000,
+        2,
+        String::new(),
+    );
+    assert_eq!(md.direction(), None);
+    assert_eq!(md.direction().unwrap_or_default(), Direction::SendRecv);
+
+    md.set_direction(Direction::RecvOnly);
+    assert_eq!(md.direction(), Some(Direction::RecvOnly));
+    assert_eq!(md.attributes.get_all("recvonly").count(), 1);
+
+    // Conflicting duplicates: the last one wins, and the setter collapses
+    // them into a single attribute at the position of the first.
+    let mut md = MediaDescription::new("video".to_owned(), vec![])
+        .with_property_attribute("sendonly".to_owned())
+        .with_value_attribute("mid".to_owned(), "0".to_owned())
+        .with_property_attribute("inactive".to_owned());
+    assert_eq!(md.direction(), Some(Direction::Inactive));
+
+    md.set_direction(Direction::SendOnly.reversed());
+    let names: Vec<&str> = md.attributes.iter().map(|a| a.name()).collect();
+    assert_eq!(names, vec!["recvonly", "mid"]);
+    assert_eq!(md.direction(), Some(Direction::RecvOnly));
+
+    Ok(())
+}
+
+#[test]
+fn test_direction_helpers() {
+    let tests = [
+        (Direction::SendRecv, Direction::SendRecv, true, true),
+        (Direction::SendOnly, Direction::RecvOnly, true, false),
+        (Direction::RecvOnly, Direction::SendOnly, false, true),
+        (Direction::Inactive, Direction::Inactive, false, false),
+    ];
+
+    for (direction, reversed, is_send, is_recv) in tests {
+        assert_eq!(direction.reversed(), reversed, "{}", direction);
+        assert_eq!(direction.is_send(), is_send, "{}", direction);
+        assert_eq!(direction.is_recv(), is_recv, "{}", direction);
+    }
+}
+
+#[test]
+fn test_rtpmaps() {
+    let md = MediaDescription::new("audio".to_owned(), vec![])
+        .with_codec(111, "opus".to_owned(), 48000, 2, String::new())
+        .with_value_attribute("rtpmap".to_owned(), "200 broken/8000".to_owned())
+        .with_codec(126, "telephone-event".to_owned(), 8000, 0, String::new());
+
+    let payload_types: Vec<u8> = md.rtpmaps().map(|r| r.payload_type).collect();
+    assert_eq!(payload_types, vec![111, 126]);
+
+    let opus = md.rtpmap_for(111).expect("opus rtpmap");
+    assert!(opus.is_encoding("OPUS"));
+    assert_eq!(opus.clock_rate, 48000);
+    assert_eq!(opus.channels, Some(2));
+    assert_eq!(md.rtpmap_for(126).and_then(|r| r.channels), None);
+    assert!(md.rtpmap_for(200).is_none());
+}
+
+#[test]
+fn test_fmtp_for() {
+    let md = MediaDescription::new("video".to_owned(), vec![])
+        .with_codec(
+            102,
+            "H264".to_owned(),
+            90000,
+            0,
+            "profile-level-id=42e01f;packetization-mode=1".to_owned(),
+        )
+        .with_value_attribute("fmtp".to_owned(), "103 apt=102".to_owned());
+
+    let fmtp = md.fmtp_for("102").expect("h264 fmtp");
+    assert_eq!(fmtp.parameter("profile-level-id"), Some("42e01f"));
+    assert_eq!(md.fmtps().count(), 2);
+    // 103 is not one of the formats of the "m=" line.
+    assert!(md.fmtp_for("103").is_none());
+}
+
+#[test]
+fn test_ptime_validation() -> Result<(), Error> {
+    use crate::diagnostic::Severity;
+
+    let mut md = MediaDescription::new("audio".to_owned(), vec![]).with_codec(
+        111,
+        "opus".to_owned(),
+        48000,
+        2,
+        String::new(),
+    );
+    assert_eq!(md.ptime(), None);
+    assert!(md.validate().is_empty());
+
+    md.set_ptime(PTime(20));
+    md.set_maxptime(MaxPTime(120));
+    assert_eq!(md.ptime(), Some(PTime(20)));
+    assert_eq!(md.maxptime(), Some(MaxPTime(120)));
+    assert!(md.validate().is_empty());
+
+    md.set_ptime(PTime(60));
+    assert_eq!(md.attributes.get::<PTime>().count(), 1);
+    md.set_maxptime(MaxPTime(40));
+    let diagnostics = md.validate();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(diagnostics[0].kind, DiagnosticKind::PTimeExceedsMaxPTime);
+
+    // Equal values are fine, and malformed ones are ignored.
+    md.set_ptime(PTime(40));
+    assert!(md.validate().is_empty());
+    md.attributes
+        .push(Attribute::new("ptime".to_owned(), Some("20.5".to_owned())));
+    assert_eq!(md.ptime(), Some(PTime(40)));
+
+    Ok(())
+}
+
+#[test]
+fn test_setup() -> Result<(), Error> {
+    let mut md = MediaDescription::new("audio".to_owned(), vec![]);
+    assert_eq!(md.setup(), None);
+
+    md.set_setup(Setup::ActPass);
+    md.set_setup(Setup::answer_for(Setup::ActPass)?);
+    assert_eq!(md.setup(), Some(Setup::Active));
+    assert_eq!(md.attributes.get::<Setup>().count(), 1);
+    assert_eq!(
+        md.attributes.get_first("setup").and_then(Attribute::value),
+        Some("active")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_port() -> Result<(), Error> {
+    let port = "49170/2".parse::<Port>()?;
+    assert_eq!(port.value, 49170);
+    assert_eq!(port.count, NonZeroU16::new(2));
+    assert_eq!(port.rtcp_port(), Some(49171));
+    assert_eq!(port.ports().collect::<Vec<_>>(), vec![49170, 49171]);
+    assert_eq!(port.to_string(), "49170/2");
+
+    // The WebRTC placeholder port.
+    let port = "9".parse::<Port>()?;
+    assert_eq!(port, Port::new(9));
+    assert_eq!(port.ports().collect::<Vec<_>>(), vec![9]);
+    assert_eq!(port.to_string(), "9");
+
+    assert_eq!("0".parse::<Port>()?, Port::new(0));
+    assert_eq!("65535/1".parse::<Port>()?.rtcp_port(), None);
+    assert!("65535/2".parse::<Port>().is_err());
+    assert!("9/0".parse::<Port>().is_err());
+
+    let mut md = MediaDescription::new("audio".to_owned(), vec![]);
+    md.media_name.formats.push("0".to_owned());
+    md.media_name.port = "49170/2".parse()?;
+    assert!(md.validate().is_empty());
+    md.media_name.port = "49171/2".parse()?;
+    let diagnostics = md.validate();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].kind, DiagnosticKind::OddRtpPort);
+    md.media_name.port = "49171".parse()?;
+    assert!(md.validate().is_empty());
+    md.media_name.protos = vec!["UDP".to_owned(), "DTLS".to_owned(), "SCTP".to_owned()];
+    md.media_name.port = "49171/2".parse()?;
+    assert!(md.validate().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_rtcp_address() -> Result<(), Error> {
+    use crate::diagnostic::Severity;
+
+    let mut md = MediaDescription::new("audio".to_owned(), vec![]);
+    md.media_name.port.value = 49170;
+    md.connection_information = Some("IN IP4 192.0.2.1".parse()?);
+    let rtp_address = md.connection_information.clone();
+
+    // No attributes: RTP port plus one.
+    let rtcp = md.rtcp_address().unwrap();
+    assert_eq!(rtcp.port, 49171);
+    assert_eq!(rtcp.connection_information, rtp_address);
+
+    // Port only.
+    md.attributes.insert("53020".parse::<Rtcp>()?);
+    let rtcp = md.rtcp_address().unwrap();
+    assert_eq!(rtcp.port, 53020);
+    assert_eq!(rtcp.connection_information, rtp_address);
+
+    // Port and address.
+    md.attributes
+        .replace("53020 IN IP4 198.51.100.7".parse::<Rtcp>()?);
+    let rtcp = md.rtcp_address().unwrap();
+    assert_eq!(rtcp.to_string(), "53020 IN IP4 198.51.100.7");
+
+    // rtcp-mux wins over a=rtcp.
+    md.attributes.insert(RtcpMux);
+    let rtcp = md.rtcp_address().unwrap();
+    assert_eq!(rtcp.port, 49170);
+    assert_eq!(rtcp.connection_information, rtp_address);
+
+    md.media_name.port.value = 0;
+    assert_eq!(md.rtcp_address(), None);
+    md.media_name.port.value = 65535;
+    md.attributes.remove_all::<RtcpMux>();
+    md.attributes.remove_all::<Rtcp>();
+    assert_eq!(md.rtcp_address(), None);
+
+    assert!(md.validate().is_empty());
+    md.attributes.insert(RtcpMuxOnly);
+    let diagnostics = md.validate();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(
+        diagnostics[0].kind,
+        DiagnosticKind::RtcpMuxOnlyWithoutRtcpMux
+    );
+    md.attributes.insert(RtcpMux);
+    assert!(md.validate().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_ssrcs() -> Result<(), Error> {
+    const SSRC_LINES: [&str; 9] = [
+        "ssrc-group:FID 2231627014 632943048",
+        "ssrc:2231627014 cname:2VZ7vUaxZQxyB6sR",
+        "ssrc:2231627014 msid:3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc 1c4d8e2b",
+        "ssrc:2231627014 mslabel:3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc",
+        "ssrc:2231627014 label:1c4d8e2b",
+        "ssrc:632943048 cname:2VZ7vUaxZQxyB6sR",
+        "ssrc:632943048 msid:3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc 1c4d8e2b",
+        "ssrc:632943048 mslabel:3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc",
+        "ssrc:632943048 label:1c4d8e2b",
+    ];
+
+    let mut md = MediaDescription::new("video".to_owned(), vec![]);
+    assert_eq!(md.primary_ssrc(), None);
+    for line in SSRC_LINES.iter() {
+        md.attributes.push(line.parse::<Attribute>()?);
+    }
+
+    let sources = md.ssrcs();
+    assert_eq!(
+        sources.iter().map(|s| s.id).collect::<Vec<_>>(),
+        vec![2231627014, 632943048]
+    );
+    assert_eq!(sources[0].cname(), Some("2VZ7vUaxZQxyB6sR"));
+    assert_eq!(
+        sources[0].msid(),
+        Some("3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc 1c4d8e2b")
+    );
+    assert_eq!(
+        sources[1].mslabel(),
+        Some("3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc")
+    );
+    assert_eq!(sources[1].label(), Some("1c4d8e2b"));
+    assert_eq!(md.primary_ssrc(), Some(2231627014));
+
+    let reconstructed: Vec<_> = sources
+        .iter()
+        .flat_map(SsrcSource::to_ssrcs)
+        .map(|ssrc| format!("ssrc:{}", ssrc))
+        .collect();
+    assert_eq!(reconstructed, SSRC_LINES[1..].to_vec());
+
+    // The RTX source listed first is still not the primary one.
+    md.attributes.remove_all::<Ssrc>();
+    md.attributes
+        .push("ssrc:632943048 cname:2VZ7vUaxZQxyB6sR".parse::<Attribute>()?);
+    md.attributes
+        .push("ssrc:2231627014 cname:2VZ7vUaxZQxyB6sR".parse::<Attribute>()?);
+    assert_eq!(md.primary_ssrc(), Some(2231627014));
+
+    Ok(())
+}
+
+#[test]
+fn test_rtcp_feedback_for() -> Result<(), Error> {
+    use crate::rtcpfb::{PayloadTypeRef, RtcpFeedbackType};
+
+    let mut md = MediaDescription::new("video".to_owned(), vec![]);
+    for line in [
+        "rtpmap:96 VP8/90000",
+        "rtcp-fb:96 goog-remb",
+        "rtcp-fb:96 transport-cc",
+        "rtcp-fb:96 ccm fir",
+        "rtcp-fb:96 nack",
+        "rtcp-fb:96 nack pli",
+        "rtpmap:97 rtx/90000",
+        "rtcp-fb:* transport-cc",
+        "rtcp-fb:* trr-int 100",
+    ] {
+        md.attributes.push(line.parse::<Attribute>()?);
+    }
+
+    let feedback: Vec<_> = md
+        .rtcp_feedback_for(96)
+        .into_iter()
+        .map(|fb| fb.feedback)
+        .collect();
+    assert_eq!(
+        feedback,
+        vec![
+            RtcpFeedbackType::GoogRemb,
+            RtcpFeedbackType::TransportCc,
+            RtcpFeedbackType::CcmFir,
+            RtcpFeedbackType::Nack,
+            RtcpFeedbackType::NackPli,
+            RtcpFeedbackType::Other {
+                typ: "trr-int".to_owned(),
+                param: Some("100".to_owned()),
+            },
+        ]
+    );
+
+    let feedback = md.rtcp_feedback_for(97);
+    assert_eq!(feedback.len(), 2);
+    assert!(feedback
+        .iter()
+        .all(|fb| fb.payload_type == PayloadTypeRef::All));
+    assert_eq!(feedback[0].to_string(), "* transport-cc");
+
+    Ok(())
+}
+
+#[test]
+fn test_simulcast_validation() -> Result<(), Error> {
+    use crate::simulcast::RidDirection;
+
+    let mut md = MediaDescription::new("video".to_owned(), vec![]);
+    for line in [
+        "rtpmap:96 VP8/90000",
+        "rid:f send",
+        "rid:h send",
+        "rid:q send",
+        "simulcast:send f;h;q",
+    ] {
+        md.attributes.push(line.parse::<Attribute>()?);
+    }
+
+    assert_eq!(
+        md.rids().iter().map(|r| r.id.as_str()).collect::<Vec<_>>(),
+        vec!["f", "h", "q"]
+    );
+    assert!(md.rids().iter().all(|r| r.direction == RidDirection::Send));
+    assert_eq!(md.simulcast().unwrap().to_string(), "send f;h;q");
+    assert!(md.validate().is_empty());
+
+    md.attributes
+        .replace("send f;h;q recv q,x".parse::<Simulcast>()?);
+    let kinds: Vec<_> = md.validate().into_iter().map(|d| d.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            DiagnosticKind::SimulcastRidDirectionMismatch,
+            DiagnosticKind::UnknownSimulcastRid,
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_remap_payload_type() -> Result<(), Error> {
+    let mut md = MediaDescription::new("video".to_owned(), vec![]);
+    md.media_name.formats = vec!["96".to_owned(), "97".to_owned(), "63".to_owned()];
+    for line in [
+        "rtpmap:96 VP8/90000",
+        "rtcp-fb:96 nack",
+        "rtcp-fb:* transport-cc",
+        "rtpmap:97 rtx/90000",
+        "fmtp:97 apt=96",
+        "rtpmap:63 red/90000",
+        "fmtp:63 96/96",
+    ] {
+        md.attributes.push(line.parse::<Attribute>()?);
+    }
+
+    md.remap_payload_type(96, 100)?;
+    md.remap_payload_type(97, 101)?;
+    assert_eq!(md.media_name.formats, vec!["100", "101", "63"]);
+
+    let lines: Vec<_> = md.attributes.iter().map(|a| a.to_string()).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "rtpmap:100 VP8/90000",
+            "rtcp-fb:100 nack",
+            "rtcp-fb:* transport-cc",
+            "rtpmap:101 rtx/90000",
+            "fmtp:101 apt=100",
+            "rtpmap:63 red/90000",
+            "fmtp:63 100/100",
+        ]
+    );
+    assert!(md.validate().is_empty());
+
+    assert!(matches!(
+        md.remap_payload_type(96, 102),
+        Err(Error::SdpPayloadTypeNotFound(96))
+    ));
+    assert!(matches!(
+        md.remap_payload_type(100, 101),
+        Err(Error::SdpPayloadTypeInUse(101))
+    ));
+    assert!(matches!(
+        md.remap_payload_type(100, 128),
+        Err(Error::SdpPayloadTypeInUse(128))
+    ));
+    md.remap_payload_type(100, 100)?;
+    assert_eq!(md.media_name.formats, vec!["100", "101", "63"]);
+
+    let free: Vec<_> = md.free_dynamic_payload_types().collect();
+    assert_eq!(free.len(), 32 + 29 - 3);
+    assert_eq!(&free[..5], &[96, 97, 98, 99, 102]);
+    assert_eq!(free.last(), Some(&62));
+
+    Ok(())
+}
+
+#[test]
+fn test_remap_extmap_id() -> Result<(), Error> {
+    let mut md = MediaDescription::new("audio".to_owned(), vec![])
+        .with_extmap("1/sendonly urn:ietf:params:rtp-hdrext:ssrc-audio-level".parse()?)
+        .with_extmap("4 urn:ietf:params:rtp-hdrext:sdes:mid".parse()?);
+    let (one, four, five) = (
+        ExtMapId::try_new(1)?,
+        ExtMapId::try_new(4)?,
+        ExtMapId::try_new(5)?,
+    );
+
+    md.remap_extmap_id(one, five)?;
+    let extmaps: Vec<_> = md.extmaps().map(|e| e.to_string()).collect();
+    assert_eq!(
+        extmaps,
+        vec![
+            "5/sendonly urn:ietf:params:rtp-hdrext:ssrc-audio-level",
+            "4 urn:ietf:params:rtp-hdrext:sdes:mid",
+        ]
+    );
+
+    assert!(matches!(
+        md.remap_extmap_id(one, five),
+        Err(Error::SdpExtMapIdNotFound(1))
+    ));
+    assert!(matches!(
+        md.remap_extmap_id(four, five),
+        Err(Error::SdpExtMapIdInUse(5))
+    ));
+    md.remap_extmap_id(four, four)?;
+    assert_eq!(md.extmap_id_for(KnownExtension::SdesMid), Some(four));
+
+    Ok(())
+}
+
+#[test]
+fn test_negotiate_extmaps() -> Result<(), Error> {
+    let offer = MediaDescription {
+        media_name: "audio 9 UDP/TLS/RTP/SAVPF 111".parse()?,
+        ..Default::default()
+    }
+    .with_extmap("1 urn:ietf:params:rtp-hdrext:ssrc-audio-level".parse()?)
+    .with_extmap("2 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time".parse()?)
+    .with_extmap("4 urn:ietf:params:rtp-hdrext:sdes:mid".parse()?)
+    .with_extmap("5 urn:example:proprietary".parse()?);
+    assert_eq!(
+        offer.extmap_id_for(KnownExtension::SdesMid),
+        Some(ExtMapId::try_new(4)?)
+    );
+    assert_eq!(offer.extmap_id_for(KnownExtension::TransportCc), None);
+
+    // The local configuration numbers the same extensions differently.
+    let local = [
+        ExtMap::for_known(ExtMapId::try_new(3)?, KnownExtension::SdesMid),
+        ExtMap::for_known(ExtMapId::try_new(5)?, KnownExtension::TransportCc),
+        ExtMap::for_known(ExtMapId::try_new(9)?, KnownExtension::AudioLevel),
+    ];
+    let offered: Vec<_> = offer.extmaps().collect();
+    let negotiated = MediaDescription::negotiate_extmaps(&offered, &local, false);
+    let negotiated: Vec<_> = negotiated.iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        negotiated,
+        vec![
+            "1 urn:ietf:params:rtp-hdrext:ssrc-audio-level",
+            "4 urn:ietf:params:rtp-hdrext:sdes:mid",
+        ]
+    );
+
+    assert!(MediaDescription::negotiate_extmaps(&offered, &[], false).is_empty());
+
+    // Two-byte ids are only kept if the offer allows mixed extmaps.
+    let offered = [
+        "1 urn:ietf:params:rtp-hdrext:ssrc-audio-level".parse::<ExtMap>()?,
+        "16 urn:ietf:params:rtp-hdrext:sdes:mid".parse::<ExtMap>()?,
+    ];
+    assert_eq!(
+        MediaDescription::negotiate_extmaps(&offered, &local, false),
+        &offered[..1]
+    );
+    assert_eq!(
+        MediaDescription::negotiate_extmaps(&offered, &local, true),
+        &offered[..]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_bandwidth() -> Result<(), Error> {
+    let mut md = MediaDescription {
+        media_name: "video 9 RTP/AVP 31".parse()?,
+        bandwidth: vec![
+            "AS:512".parse()?,
+            "X-AS:100".parse()?,
+            "TIAS:480000".parse()?,
+        ],
+        ..Default::default()
+    };
+    assert_eq!(md.bandwidth(BandwidthKind::As), Some(512));
+    assert_eq!(md.bandwidth(BandwidthKind::Tias), Some(480000));
+    assert_eq!(md.bandwidth(BandwidthKind::Ct), None);
+
+    // An existing entry is replaced in place, experimental ones are a
+    // modifier of their own.
+    md.set_bandwidth(Bandwidth::as_from_bps(1_000_000)?);
+    md.set_bandwidth(Bandwidth::new("CT", 2000)?);
+    let bandwidth: Vec<_> = md.bandwidth.iter().map(|b| b.to_string()).collect();
+    assert_eq!(
+        bandwidth,
+        vec!["AS:1000", "X-AS:100", "TIAS:480000", "CT:2000"]
+    );
+    assert!(md.validate().is_empty());
+
+    // Duplicates are reduced to one.
+    md.bandwidth.push("AS:64".parse()?);
+    assert_eq!(md.validate().len(), 1);
+    md.set_bandwidth(Bandwidth::new("AS", 128)?);
+    let bandwidth: Vec<_> = md.bandwidth.iter().map(|b| b.to_string()).collect();
+    assert_eq!(
+        bandwidth,
+        vec!["AS:128", "X-AS:100", "TIAS:480000", "CT:2000"]
+    );
+    assert!(md.validate().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_rtcp_bandwidth() -> Result<(), Error> {
+    let media_description = |bandwidth: &[&str]| -> Result<MediaDescription, Error> {
+        Ok(MediaDescription {
+            media_name: "audio 49170 RTP/AVP 0".parse()?,
+            bandwidth: bandwidth
+                .iter()
+                .map(|b| b.parse())
+                .collect::<Result<_, _>>()?,
+            ..Default::default()
+        })
+    };
+
+    // Nothing to derive the bandwidth from.
+    let md = media_description(&[])?;
+    assert_eq!(md.rtcp_bandwidth(), RtcpBandwidth::default());
+    assert!(!md.rtcp_bandwidth().is_disabled());
+
+    // 5% of 64 kbps, 1/4 for senders and 3/4 for receivers.
+    let md = media_description(&["AS:64"])?;
+    assert_eq!(
+        md.rtcp_bandwidth(),
+        RtcpBandwidth {
+            rr_bps: Some(2400),
+            rs_bps: Some(800),
+        }
+    );
+
+    // Explicit values win, and the other one keeps its default.
+    let md = media_description(&["AS:64", "RS:1000"])?;
+    assert_eq!(
+        md.rtcp_bandwidth(),
+        RtcpBandwidth {
+            rr_bps: Some(2400),
+            rs_bps: Some(1000),
+        }
+    );
+
+    // Zero for both turns RTCP off, even with AS present.
+    let md = media_description(&["AS:64", "RR:0", "RS:0"])?;
+    assert_eq!(
+        md.rtcp_bandwidth(),
+        RtcpBandwidth {
+            rr_bps: Some(0),
+            rs_bps: Some(0),
+        }
+    );
+    assert!(md.rtcp_bandwidth().is_disabled());
+    assert!(md.validate().is_empty());
+
+    let md = media_description(&["RR:0"])?;
+    assert_eq!(
+        md.rtcp_bandwidth(),
+        RtcpBandwidth {
+            rr_bps: Some(0),
+            rs_bps: None,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_rtcp_bandwidth_units() -> Result<(), Error> {
+    let md = |bandwidth: &[&str]| -> Result<MediaDescription, Error> {
+        Ok(MediaDescription {
+            media_name: "audio 49170 RTP/AVP 0".parse()?,
+            bandwidth: bandwidth
+                .iter()
+                .map(|b| b.parse())
+                .collect::<Result<_, _>>()?,
+            ..Default::default()
+        })
+    };
+
+    // RR and RS written in kilobits per second, as AS is, are tiny but
+    // valid.
+    let diagnostics = md(&["AS:64", "RR:3", "RS:1"])?.validate();
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+
+    // AS written in bits per second is read as kilobits per second and
+    // hides the overflow of the RTCP bandwidth.
+    let diagnostics = md(&["AS:64000", "RR:48000", "RS:16000"])?.validate();
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+
+    // The RTCP bandwidth exceeds the session bandwidth.
+    let diagnostics = md(&["AS:64", "RR:48000", "RS:17000"])?.validate();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].kind,
+        DiagnosticKind::RtcpBandwidthExceedsSession
+    );
+    assert_eq!(
+        diagnostics[0].message,
+        "RTCP bandwidth of 65000 bps exceeds b=AS:64 (64000 bps)"
+    );
+
+    // Without AS there is nothing to compare with.
+    assert!(md(&["RR:48000", "RS:17000"])?.validate().is_empty());
+
+    Ok(())
+}
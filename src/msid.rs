@@ -0,0 +1,163 @@
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::TypedAttribute;
+use super::error::Error;
+use super::util::token::is_token;
+
+#[cfg(test)]
+mod msid_test;
+
+/// MSID_NO_STREAM is the stream id of a track which belongs to no stream.
+pub const MSID_NO_STREAM: &str = "-";
+
+/// MSID_SEMANTIC_WMS is the WebRTC media stream semantic of the legacy
+/// "a=msid-semantic:" attribute.
+pub const MSID_SEMANTIC_WMS: &str = "WMS";
+
+/// Msid is an "a=msid:" attribute, associating a media description with a
+/// media stream and a track. A stream id of "-" means that the track belongs
+/// to no stream, and is kept as None.
+/// `a=msid:<stream id> [<track id>]`
+/// <https://tools.ietf.org/html/rfc8830#section-2>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::msid::Msid;
+///
+/// # fn main() -> Result<(), Error> {
+/// let msid = "stream track".parse::<Msid>()?;
+/// assert_eq!(msid.stream_id.as_deref(), Some("stream"));
+/// assert_eq!(msid.track_id.as_deref(), Some("track"));
+/// assert_eq!(msid.to_string(), "stream track");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Msid {
+    /// stream_id is the id of the media stream, or None for "-".
+    pub stream_id: Option<String>,
+    /// track_id is the id of the track, if given.
+    pub track_id: Option<String>,
+}
+
+impl fmt::Display for Msid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.stream_id.as_deref().unwrap_or(MSID_NO_STREAM))?;
+        if let Some(track_id) = &self.track_id {
+            write!(f, " {}", track_id)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Msid {
+    type Err = Error;
+
+    /// msid-value = msid-id [ SP msid-appdata ]
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::SdpInvalidMsid(value.to_owned());
+
+        let (stream_id, track_id) = match value.split_once(' ') {
+            Some((stream_id, track_id)) => (stream_id, Some(track_id)),
+            None => (value, None),
+        };
+        if !is_msid_id(stream_id) || !track_id.is_none_or(is_msid_id) {
+            return Err(invalid());
+        }
+
+        Ok(Msid {
+            stream_id: Some(stream_id)
+                .filter(|id| *id != MSID_NO_STREAM)
+                .map(str::to_owned),
+            track_id: track_id.map(str::to_owned),
+        })
+    }
+}
+
+impl TypedAttribute for Msid {
+    const NAME: &'static str = "msid";
+
+    fn describe(&self) -> Option<String> {
+        Some(match (&self.stream_id, &self.track_id) {
+            (Some(stream_id), Some(track_id)) => {
+                format!("track `{}` of stream `{}`", track_id, stream_id)
+            }
+            (Some(stream_id), None) => format!("stream `{}`", stream_id),
+            (None, Some(track_id)) => format!("track `{}` without stream", track_id),
+            (None, None) => "no stream".to_owned(),
+        })
+    }
+}
+
+/// MsidSemantic is the legacy session level "a=msid-semantic:" attribute,
+/// listing the media streams of the session, or "*" for all of them. Chrome
+/// writes it with a space after the colon, which is accepted.
+/// `a=msid-semantic: WMS <stream id> ...`
+/// <https://tools.ietf.org/html/draft-ietf-mmusic-msid-16#section-4>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MsidSemantic {
+    /// semantic is the semantic, e.g. "WMS".
+    pub semantic: String,
+    /// stream_ids are the ids of the media streams, or "*" for all of them.
+    pub stream_ids: Vec<String>,
+}
+
+impl MsidSemantic {
+    /// contains returns true if the stream is listed, or all streams are.
+    pub fn contains(&self, stream_id: &str) -> bool {
+        self.stream_ids
+            .iter()
+            .any(|id| id == stream_id || id == "*")
+    }
+}
+
+impl fmt::Display for MsidSemantic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.semantic)?;
+        for stream_id in &self.stream_ids {
+            write!(f, " {}", stream_id)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for MsidSemantic {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::SdpInvalidMsidSemantic(value.to_owned());
+
+        let mut fields = value.strip_prefix(' ').unwrap_or(value).split(' ');
+        let semantic = fields.next().unwrap_or_default();
+        if !is_token(semantic) {
+            return Err(invalid());
+        }
+        let stream_ids = fields
+            .map(|id| {
+                if is_msid_id(id) {
+                    Ok(id.to_owned())
+                } else {
+                    Err(invalid())
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(MsidSemantic {
+            semantic: semantic.to_owned(),
+            stream_ids,
+        })
+    }
+}
+
+impl TypedAttribute for MsidSemantic {
+    const NAME: &'static str = "msid-semantic";
+}
+
+/// msid-id = 1*64token-char
+fn is_msid_id(value: &str) -> bool {
+    value.len() <= 64 && is_token(value)
+}
@@ -0,0 +1,80 @@
+use super::*;
+
+#[test]
+fn test_msid() -> Result<(), Error> {
+    let tests = vec![
+        (
+            "3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc 6e4ab2a4-a1a3-4a66-90c5-9b1c5d7b5ed1",
+            Some("3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc"),
+            Some("6e4ab2a4-a1a3-4a66-90c5-9b1c5d7b5ed1"),
+        ),
+        (
+            "{1bc87a5a-0e4e-4a31-9d39-3c0a7bd3e1f3} {9a0c4a1e-52f8-4e3c-b0ea-8a7b3c1b2d4f}",
+            Some("{1bc87a5a-0e4e-4a31-9d39-3c0a7bd3e1f3}"),
+            Some("{9a0c4a1e-52f8-4e3c-b0ea-8a7b3c1b2d4f}"),
+        ),
+        ("- track", None, Some("track")),
+        ("stream", Some("stream"), None),
+    ];
+
+    for (input, stream_id, track_id) in tests {
+        let msid = input.parse::<Msid>()?;
+        assert_eq!(msid.stream_id.as_deref(), stream_id, "{}", input);
+        assert_eq!(msid.track_id.as_deref(), track_id);
+        assert_eq!(msid.to_string(), input);
+    }
+
+    for input in [
+        "".to_owned(),
+        " track".to_owned(),
+        "stream ".to_owned(),
+        "stream  track".to_owned(),
+        "stream track extra".to_owned(),
+        "str\"eam track".to_owned(),
+        "s".repeat(65),
+    ] {
+        assert!(
+            matches!(input.parse::<Msid>(), Err(Error::SdpInvalidMsid(_))),
+            "{:?}",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_msid_semantic() -> Result<(), Error> {
+    let tests = vec![
+        (
+            " WMS 3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc",
+            vec!["3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc"],
+        ),
+        ("WMS *", vec!["*"]),
+        ("WMS a b", vec!["a", "b"]),
+        (" WMS", vec![]),
+    ];
+
+    for (input, stream_ids) in tests {
+        let semantic = input.parse::<MsidSemantic>()?;
+        assert_eq!(semantic.semantic, MSID_SEMANTIC_WMS);
+        assert_eq!(semantic.stream_ids, stream_ids);
+        assert_eq!(semantic.to_string(), input.trim_start());
+    }
+
+    assert!("WMS *".parse::<MsidSemantic>()?.contains("any"));
+    assert!(!"WMS a".parse::<MsidSemantic>()?.contains("b"));
+
+    for input in ["", " ", "WMS  a", "WMS a "] {
+        assert!(
+            matches!(
+                input.parse::<MsidSemantic>(),
+                Err(Error::SdpInvalidMsidSemantic(_))
+            ),
+            "{:?}",
+            input
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,289 @@
+use url::Url;
+
+use super::alloc_prelude::*;
+use super::common_description::{Address, Attribute, ConnectionInformation, TypedAttribute};
+use super::common_description::{NetType, ADDRESS_TYPE_IP4};
+use super::diagnostic::Severity;
+use super::direction::Direction;
+use super::error::Error;
+use super::extmap::{ExtMap, ExtmapAllowMixed};
+use super::fmtp::Fmtp;
+use super::group::Group;
+use super::media_description::{MediaDescription, MediaName, MediaType, Port, Protocol};
+use super::rtcp::RtcpMux;
+use super::rtcpfb::RtcpFeedback;
+use super::rtpmap::RtpMap;
+use super::sctp::{MaxMessageSize, SctpMap, SctpPort};
+use super::session_description::SessionDescription;
+
+#[cfg(all(test, feature = "std"))]
+mod offer_answer_test;
+
+/// Codec is a codec supported by the answerer. It is matched against the
+/// offered rtpmaps by encoding name, clock rate and channels, as payload
+/// type numbers are chosen by the offerer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Codec {
+    /// encoding_name is the encoding name, compared case-insensitively.
+    pub encoding_name: String,
+    /// clock_rate is the RTP clock rate in Hz.
+    pub clock_rate: u32,
+    /// channels is the number of audio channels, None meaning one.
+    pub channels: Option<u8>,
+}
+
+impl Codec {
+    /// new creates a codec.
+    pub fn new(encoding_name: &str, clock_rate: u32, channels: Option<u8>) -> Self {
+        Codec {
+            encoding_name: encoding_name.to_owned(),
+            clock_rate,
+            channels,
+        }
+    }
+
+    /// matches reports whether the "a=rtpmap" attribute is for this codec.
+    pub fn matches(&self, rtpmap: &RtpMap) -> bool {
+        rtpmap.is_encoding(&self.encoding_name)
+            && rtpmap.clock_rate == self.clock_rate
+            && rtpmap.channels.unwrap_or(1) == self.channels.unwrap_or(1)
+    }
+}
+
+/// LocalCapabilities describe what the answerer supports.
+#[derive(Debug, Clone, Default)]
+pub struct LocalCapabilities {
+    /// codecs are the supported codecs, in order of preference.
+    pub codecs: Vec<(MediaType, Codec)>,
+    /// header_extensions are the URIs of the supported RTP header extensions.
+    pub header_extensions: Vec<Url>,
+    /// direction limits the directions of the answered media descriptions,
+    /// e.g. recvonly for an endpoint which doesn't send media.
+    pub direction: Direction,
+    /// datachannel tells whether data channels are supported.
+    pub datachannel: bool,
+    /// protocols are the supported transport profiles. Media descriptions
+    /// are rejected unless one of them is Protocol::answer_compatible with
+    /// the offered `<proto>`. If empty, the JSEP profiles UDP/TLS/RTP/SAVPF
+    /// and UDP/DTLS/SCTP are assumed.
+    pub protocols: Vec<Protocol>,
+}
+
+impl LocalCapabilities {
+    /// with_codec adds a supported codec for the media type.
+    pub fn with_codec(mut self, media_type: MediaType, codec: Codec) -> Self {
+        self.codecs.push((media_type, codec));
+        self
+    }
+
+    /// with_header_extension adds a supported RTP header extension.
+    pub fn with_header_extension(mut self, uri: Url) -> Self {
+        self.header_extensions.push(uri);
+        self
+    }
+
+    /// with_protocol adds a supported transport profile.
+    pub fn with_protocol(mut self, protocol: Protocol) -> Self {
+        self.protocols.push(protocol);
+        self
+    }
+
+    fn supports_protocol(&self, offered: &Protocol) -> bool {
+        const JSEP: [Protocol; 2] = [Protocol::UdpTlsRtpSavpf, Protocol::UdpDtlsSctp];
+        let protocols = if self.protocols.is_empty() {
+            &JSEP[..]
+        } else {
+            &self.protocols[..]
+        };
+        protocols.iter().any(|p| p.answer_compatible(offered))
+    }
+
+    fn supports(&self, media_type: &MediaType, rtpmap: &RtpMap) -> bool {
+        self.codecs
+            .iter()
+            .any(|(t, codec)| t == media_type && codec.matches(rtpmap))
+    }
+}
+
+/// answer_skeleton builds the structure of an answer to the offer following
+/// RFC 3264: one media description per offered one and in the same order,
+/// with the same mids, the common formats and the reversed direction.
+/// Unsupported media descriptions, including those whose transport profile
+/// no local one is answer compatible with, are rejected with port zero. ICE
+/// and DTLS attributes are left to the caller, and so are the ports of the
+/// accepted media descriptions, which are set to the placeholder 9.
+/// <https://tools.ietf.org/html/rfc3264#section-6>
+#[cfg(feature = "std")]
+pub fn answer_skeleton(
+    offer: &SessionDescription,
+    caps: &LocalCapabilities,
+) -> Result<SessionDescription, Error> {
+    if let Some(diagnostic) = offer
+        .validate()
+        .into_iter()
+        .find(|d| d.severity == Severity::Error)
+    {
+        return Err(Error::SdpInvalidOffer(diagnostic.to_string()));
+    }
+
+    let mut answer = SessionDescription::new_jsep_session_description(false);
+    answer.media_descriptions = offer
+        .media_descriptions
+        .iter()
+        .map(|md| answer_media_description(md, caps, offer.extmap_allow_mixed()))
+        .collect();
+    if offer.extmap_allow_mixed() {
+        answer.attributes.insert(ExtmapAllowMixed);
+    }
+
+    // Rejected media descriptions leave their BUNDLE groups.
+    for group in offer.bundle_groups() {
+        let mids: Vec<_> = group
+            .mids
+            .into_iter()
+            .filter(|mid| {
+                answer
+                    .media_descriptions
+                    .iter()
+                    .any(|md| !md.is_rejected() && md.mid().as_ref() == Some(mid))
+            })
+            .collect();
+        if !mids.is_empty() {
+            answer.attributes.insert(Group {
+                semantics: group.semantics,
+                mids,
+            });
+        }
+    }
+
+    Ok(answer)
+}
+
+/// Extmaps needing a two-byte header are only answered if the offer allows
+/// mixing them with one-byte ones, at session or media level.
+fn answer_media_description(
+    offer: &MediaDescription,
+    caps: &LocalCapabilities,
+    session_allow_mixed: bool,
+) -> MediaDescription {
+    let allow_mixed = session_allow_mixed || offer.extmap_allow_mixed();
+    let datachannel = offer.is_datachannel();
+    let protocol = offer.media_name.protocol();
+    let formats = if offer.is_rejected()
+        || (datachannel && !caps.datachannel)
+        || !protocol.is_ok_and(|p| caps.supports_protocol(&p))
+    {
+        vec![]
+    } else if datachannel {
+        offer.media_name.formats.clone()
+    } else {
+        answer_formats(offer, caps)
+    };
+
+    let mut answer = MediaDescription {
+        media_name: MediaName {
+            media: offer.media_name.media.clone(),
+            port: Port::new(9),
+            protos: offer.media_name.protos.clone(),
+            formats,
+        },
+        connection_information: Some(ConnectionInformation {
+            network_type: NetType::In,
+            address_type: ADDRESS_TYPE_IP4.to_owned(),
+            address: Some(Address {
+                address: "0.0.0.0".to_owned(),
+                ttl: None,
+                range: None,
+            }),
+        }),
+        ..Default::default()
+    };
+    if let Some(mid) = offer.mid() {
+        answer.set_mid(mid);
+    }
+
+    if answer.media_name.formats.is_empty() {
+        answer.media_name.formats = offer.media_name.formats.clone();
+        answer.reject();
+        return answer;
+    }
+
+    if !datachannel {
+        let direction = offer.direction().unwrap_or_default().reversed();
+        answer.set_direction(direction.intersection(caps.direction));
+    }
+
+    let is_format = |format: &str| answer.media_name.formats.iter().any(|f| f == format);
+    let attributes: Vec<Attribute> = offer
+        .attributes
+        .iter()
+        .filter(|attribute| {
+            if RtpMap::matches(attribute) {
+                RtpMap::from_attribute(attribute)
+                    .is_ok_and(|r| is_format(&r.payload_type.to_string()))
+            } else if Fmtp::matches(attribute) {
+                Fmtp::from_attribute(attribute).is_ok_and(|f| is_format(&f.format))
+            } else if RtcpFeedback::matches(attribute) {
+                RtcpFeedback::from_attribute(attribute).is_ok_and(|fb| {
+                    answer
+                        .media_name
+                        .formats
+                        .iter()
+                        .filter_map(|f| f.parse().ok())
+                        .any(|pt| fb.payload_type.applies_to(pt))
+                })
+            } else if ExtMap::matches(attribute) {
+                ExtMap::from_attribute(attribute).is_ok_and(|e| {
+                    caps.header_extensions.contains(&e.uri)
+                        && (allow_mixed || !e.id.requires_two_byte_header())
+                })
+            } else if ExtmapAllowMixed::matches(attribute) {
+                !datachannel
+            } else if datachannel {
+                SctpPort::matches(attribute)
+                    || MaxMessageSize::matches(attribute)
+                    || SctpMap::matches(attribute)
+            } else {
+                RtcpMux::matches(attribute)
+            }
+        })
+        .cloned()
+        .collect();
+    answer.attributes.extend(attributes);
+
+    answer
+}
+
+/// answer_formats returns the offered formats the answerer supports, in the
+/// offered order. RTX formats are only kept along with the format they
+/// repair.
+fn answer_formats(offer: &MediaDescription, caps: &LocalCapabilities) -> Vec<String> {
+    let media_type = match offer.media_name.media.parse::<MediaType>() {
+        Ok(media_type) => media_type,
+        Err(_) => return vec![],
+    };
+
+    let supported: Vec<_> = offer
+        .media_name
+        .formats
+        .iter()
+        .filter_map(|f| offer.rtpmap_for(f.parse().ok()?))
+        .filter(|rtpmap| caps.supports(&media_type, rtpmap))
+        .collect();
+
+    supported
+        .iter()
+        .filter(|rtpmap| {
+            if !rtpmap.is_encoding("rtx") {
+                return true;
+            }
+            let apt = offer
+                .fmtp_for(&rtpmap.payload_type.to_string())
+                .and_then(|f| f.parameter("apt").and_then(|apt| apt.parse::<u8>().ok()));
+            supported
+                .iter()
+                .any(|r| Some(r.payload_type) == apt && !r.is_encoding("rtx"))
+        })
+        .map(|rtpmap| rtpmap.payload_type.to_string())
+        .collect()
+}
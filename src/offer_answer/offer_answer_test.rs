@@ -0,0 +1,221 @@
+use super::*;
+use crate::direction::Direction;
+
+const BROWSER_OFFER_SDP: &str = "v=0\r\n\
+o=- 4611731400430051336 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0 1 2\r\n\
+a=msid-semantic: WMS stream\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111 0 8\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=rtcp:9 IN IP4 0.0.0.0\r\n\
+a=ice-ufrag:Gh2c\r\n\
+a=ice-pwd:3zUiRr5gHbS0aBkPQVvvDbQy\r\n\
+a=setup:actpass\r\n\
+a=mid:0\r\n\
+a=extmap:1 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r\n\
+a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=sendonly\r\n\
+a=msid:stream track-audio\r\n\
+a=rtcp-mux\r\n\
+a=rtpmap:111 opus/48000/2\r\n\
+a=rtcp-fb:111 transport-cc\r\n\
+a=fmtp:111 minptime=10;useinbandfec=1\r\n\
+a=rtpmap:0 PCMU/8000\r\n\
+a=rtpmap:8 PCMA/8000\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96 97 102 103\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=mid:1\r\n\
+a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=sendrecv\r\n\
+a=rtcp-mux\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+a=rtcp-fb:96 nack\r\n\
+a=rtcp-fb:96 nack pli\r\n\
+a=rtpmap:97 rtx/90000\r\n\
+a=fmtp:97 apt=96\r\n\
+a=rtpmap:102 H264/90000\r\n\
+a=fmtp:102 level-asymmetry-allowed=1;packetization-mode=1;profile-level-id=42001f\r\n\
+a=rtpmap:103 rtx/90000\r\n\
+a=fmtp:103 apt=102\r\n\
+m=application 9 UDP/DTLS/SCTP webrtc-datachannel\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=mid:2\r\n\
+a=sctp-port:5000\r\n\
+a=max-message-size:262144\r\n";
+
+fn capabilities() -> Result<LocalCapabilities, Error> {
+    let mut caps = LocalCapabilities {
+        datachannel: true,
+        ..Default::default()
+    }
+    .with_codec(MediaType::Audio, Codec::new("opus", 48000, Some(2)))
+    .with_codec(MediaType::Audio, Codec::new("PCMA", 8000, None))
+    .with_codec(MediaType::Video, Codec::new("vp8", 90000, None))
+    .with_codec(MediaType::Video, Codec::new("rtx", 90000, None))
+    .with_header_extension(Url::parse("urn:ietf:params:rtp-hdrext:sdes:mid")?);
+    caps.direction = Direction::SendRecv;
+    Ok(caps)
+}
+
+#[test]
+fn test_answer_skeleton() -> Result<(), Error> {
+    let offer = BROWSER_OFFER_SDP.parse::<SessionDescription>()?;
+    let answer = answer_skeleton(&offer, &capabilities()?)?;
+
+    assert!(answer.validate().is_empty(), "{:?}", answer.validate());
+    assert_eq!(
+        answer.media_descriptions.len(),
+        offer.media_descriptions.len()
+    );
+    for (offered, answered) in offer
+        .media_descriptions
+        .iter()
+        .zip(&answer.media_descriptions)
+    {
+        assert_eq!(answered.media_name.media, offered.media_name.media);
+        assert_eq!(answered.media_name.protos, offered.media_name.protos);
+        assert_eq!(answered.mid(), offered.mid());
+        assert!(!answered.is_rejected());
+    }
+
+    let audio = &answer.media_descriptions[0];
+    assert_eq!(audio.media_name.formats, vec!["111", "8"]);
+    assert_eq!(audio.direction(), Some(Direction::RecvOnly));
+    assert_eq!(
+        audio.fmtp_for("111").unwrap().parameter("useinbandfec"),
+        Some("1")
+    );
+    assert_eq!(audio.rtcp_feedback_for(111).len(), 1);
+    assert_eq!(audio.attributes.get::<ExtMap>().count(), 1);
+    assert!(audio.attributes.get::<RtcpMux>().next().is_some());
+    assert!(audio.attribute("ice-ufrag").is_none());
+
+    // H.264 is not supported, and neither is its RTX.
+    let video = &answer.media_descriptions[1];
+    assert_eq!(video.media_name.formats, vec!["96", "97"]);
+    assert_eq!(video.direction(), Some(Direction::SendRecv));
+    assert!(video.rtpmap_for(102).is_none());
+    assert_eq!(video.rtcp_feedback_for(96).len(), 2);
+
+    let datachannel = &answer.media_descriptions[2];
+    assert!(datachannel.is_datachannel());
+    assert_eq!(datachannel.sctp_port(), Some(5000));
+    assert_eq!(datachannel.direction(), None);
+
+    assert_eq!(answer.bundle_groups()[0].to_string(), "BUNDLE 0 1 2");
+
+    Ok(())
+}
+
+#[test]
+fn test_answer_skeleton_rejects() -> Result<(), Error> {
+    let offer = BROWSER_OFFER_SDP.parse::<SessionDescription>()?;
+    let mut caps = capabilities()?;
+    caps.codecs
+        .retain(|(media_type, _)| *media_type == MediaType::Audio);
+    caps.datachannel = false;
+    caps.direction = Direction::SendOnly;
+
+    let answer = answer_skeleton(&offer, &caps)?;
+    assert!(answer.validate().is_empty(), "{:?}", answer.validate());
+
+    // sendonly reversed is recvonly, which a sendonly answerer can't do.
+    assert_eq!(
+        answer.media_descriptions[0].direction(),
+        Some(Direction::Inactive)
+    );
+
+    for (index, format) in [(1, "96"), (2, "webrtc-datachannel")] {
+        let md = &answer.media_descriptions[index];
+        assert!(md.is_rejected(), "{}", index);
+        assert_eq!(md.media_name.formats, vec![format]);
+        assert_eq!(md.mid(), offer.media_descriptions[index].mid());
+        assert_eq!(md.direction(), Some(Direction::Inactive));
+        assert!(md.rtpmaps().next().is_none());
+    }
+    assert_eq!(answer.bundle_groups()[0].to_string(), "BUNDLE 0");
+
+    // A rejected offered media description stays rejected.
+    let mut offer = offer;
+    offer.media_descriptions[0].media_name.port.value = 0;
+    let answer = answer_skeleton(&offer, &capabilities()?)?;
+    assert!(answer.media_descriptions[0].is_rejected());
+
+    Ok(())
+}
+
+#[test]
+fn test_answer_skeleton_protocols() -> Result<(), Error> {
+    // A JSEP answerer takes legacy RTP profiles, and repeats them.
+    let sdp = BROWSER_OFFER_SDP
+        .replace("m=audio 9 UDP/TLS/RTP/SAVPF", "m=audio 9 RTP/SAVPF")
+        .replace("m=video 9 UDP/TLS/RTP/SAVPF", "m=video 9 TCP/MSRP");
+    let offer = sdp.parse::<SessionDescription>()?;
+    let answer = answer_skeleton(&offer, &capabilities()?)?;
+    let audio = &answer.media_descriptions[0];
+    assert!(!audio.is_rejected());
+    assert_eq!(audio.media_name.protocol()?, Protocol::RtpSavpf);
+    let video = &answer.media_descriptions[1];
+    assert!(video.is_rejected());
+    assert_eq!(video.media_name.protos, vec!["TCP", "MSRP"]);
+    assert!(!answer.media_descriptions[2].is_rejected());
+    assert_eq!(answer.bundle_groups()[0].to_string(), "BUNDLE 0 2");
+
+    // An answerer restricted to plain RTP rejects everything else.
+    let caps = capabilities()?.with_protocol(Protocol::RtpAvp);
+    let answer = answer_skeleton(&offer, &caps)?;
+    assert!(answer.media_descriptions.iter().all(|md| md.is_rejected()));
+    let sdp = sdp.replace("m=audio 9 RTP/SAVPF", "m=audio 9 RTP/AVP");
+    let answer = answer_skeleton(&sdp.parse::<SessionDescription>()?, &caps)?;
+    assert!(!answer.media_descriptions[0].is_rejected());
+    assert!(answer.media_descriptions[2].is_rejected());
+
+    Ok(())
+}
+
+#[test]
+fn test_answer_skeleton_two_byte_extmaps() -> Result<(), Error> {
+    let sdp = BROWSER_OFFER_SDP.replace(
+        "a=mid:1\r\na=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid",
+        "a=mid:1\r\na=extmap:16 urn:ietf:params:rtp-hdrext:sdes:mid",
+    );
+    let mut offer = sdp.parse::<SessionDescription>()?;
+
+    let answer = answer_skeleton(&offer, &capabilities()?)?;
+    assert_eq!(answer.media_descriptions[0].extmaps().count(), 1);
+    assert_eq!(answer.media_descriptions[1].extmaps().count(), 0);
+    assert!(!answer.extmap_allow_mixed());
+
+    offer.attributes.insert(ExtmapAllowMixed);
+    let answer = answer_skeleton(&offer, &capabilities()?)?;
+    assert_eq!(answer.media_descriptions[1].extmaps().count(), 1);
+    assert!(answer.extmap_allow_mixed());
+    assert!(answer.validate().is_empty(), "{:?}", answer.validate());
+
+    // At media level, only the media description allowing it keeps it.
+    offer.attributes.remove_all::<ExtmapAllowMixed>();
+    offer.media_descriptions[1]
+        .attributes
+        .insert(ExtmapAllowMixed);
+    let answer = answer_skeleton(&offer, &capabilities()?)?;
+    assert_eq!(answer.media_descriptions[1].extmaps().count(), 1);
+    assert!(answer.media_descriptions[1].extmap_allow_mixed());
+    assert!(!answer.extmap_allow_mixed());
+
+    Ok(())
+}
+
+#[test]
+fn test_answer_skeleton_invalid_offer() -> Result<(), Error> {
+    let mut offer = BROWSER_OFFER_SDP.parse::<SessionDescription>()?;
+    offer.time_descriptions.clear();
+
+    assert!(matches!(
+        answer_skeleton(&offer, &capabilities()?),
+        Err(Error::SdpInvalidOffer(_))
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,454 @@
+use std::io::{self, BufRead, Read};
+
+use super::common_description::{
+    AttributeRef, EncryptionKey, UnknownLine, ADDRESS_TYPE_IP4, ADDRESS_TYPE_IP6, NETWORK_TYPE_IN,
+};
+use super::error::Error;
+use super::intern::Interner;
+use super::session_description::SessionDescription;
+use super::util::LineEnding;
+use lexer::{split_field, trim_line_break};
+
+/// lexer splits the lines of a session description into fields.
+pub(crate) mod lexer;
+#[cfg(test)]
+mod parser_test;
+
+/// DEFAULT_MAX_LINE_LENGTH is the default limit of a single line, without
+/// its line break.
+pub const DEFAULT_MAX_LINE_LENGTH: usize = 8 * 1024;
+
+/// DEFAULT_MAX_SIZE is the default limit of a whole session description.
+pub const DEFAULT_MAX_SIZE: usize = 256 * 1024;
+
+/// LINE_TYPES are the types of lines defined by RFC 4566, anything else is
+/// handled according to UnknownLinePolicy.
+/// <https://tools.ietf.org/html/rfc4566#section-5>
+const LINE_TYPES: &[u8] = b"vosiuepcbtrzkam";
+
+/// Strictness selects how Parser deals with descriptions which break the
+/// grammar of RFC 4566.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strictness {
+    /// Strict stops at the first error.
+    #[default]
+    Strict,
+    /// Lenient works around the mistakes commonly made by implementations.
+    /// Each of these is recorded as a RecoveredError:
+    ///  * optional lines which can't be parsed or are out of order are skipped,
+    ///  * a missing "t=" field is added as "t=0 0",
+    ///  * "a=" lines before the "t=" field are taken as session attributes,
+    ///  * lowercase network and address types, e.g. "IN ip4", are accepted.
+    ///
+    /// Spaces before the "=" and lines ending in a bare "\r" are accepted
+    /// silently.
+    Lenient,
+}
+
+/// UnknownLinePolicy selects what Parser does with lines of types not
+/// defined by RFC 4566, e.g. "x=" or "y=".
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnknownLinePolicy {
+    /// Reject fails parsing, as required by RFC 4566.
+    /// <https://tools.ietf.org/html/rfc4566#section-5>
+    #[default]
+    Reject,
+    /// Keep stores them verbatim in the unknown_lines of the session or
+    /// media description they appear in.
+    Keep,
+    /// Drop ignores them.
+    Drop,
+}
+
+/// ParseOptions configures Parser. The default options are strict.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ParseOptions {
+    /// strictness tells how strictly the grammar is followed.
+    pub strictness: Strictness,
+    /// max_media_sections limits the number of "m=" fields, None means no
+    /// limit.
+    pub max_media_sections: Option<usize>,
+    /// unknown_line_policy tells what happens to lines of unknown types.
+    pub unknown_line_policy: UnknownLinePolicy,
+    /// interner, if any, shares the attribute names and the common attribute
+    /// values of the parsed descriptions, see SessionDescription::intern.
+    pub interner: Option<Interner>,
+    /// normalize_line_endings makes the parsed descriptions end their lines
+    /// in CRLF, instead of with the line break of the first line of the
+    /// input, see SessionDescription::line_ending.
+    pub normalize_line_endings: bool,
+}
+
+/// RecoveredError is an error which a lenient Parser worked around, with the
+/// position of the line it occurred at.
+#[derive(Debug)]
+pub struct RecoveredError {
+    /// line is the 1-based number of the line.
+    pub line: usize,
+    /// line_type is the type of the line, if it has one.
+    pub line_type: Option<char>,
+    /// error is the error which was recovered from.
+    pub error: Error,
+}
+
+/// Parser reads a session description from an io::BufRead line by line, so
+/// that it doesn't have to be collected into a String first, e.g. when it is
+/// read from the network. It walks the same state table as
+/// SessionDescription::unmarshal and, unless ParseOptions make it lenient,
+/// stops at the first error. The length of each line and the total size are
+/// limited to defend against peers sending endless input.
+#[derive(Debug)]
+pub struct Parser {
+    max_line_length: usize,
+    max_size: usize,
+    options: ParseOptions,
+    consumed: usize,
+    recovered: Vec<RecoveredError>,
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Parser {
+            max_line_length: DEFAULT_MAX_LINE_LENGTH,
+            max_size: DEFAULT_MAX_SIZE,
+            options: ParseOptions::default(),
+            consumed: 0,
+            recovered: vec![],
+        }
+    }
+}
+
+/// A clone has the configuration of the parser, but not the errors recovered
+/// by its last call to parse, as errors can't be cloned.
+impl Clone for Parser {
+    fn clone(&self) -> Self {
+        Parser {
+            max_line_length: self.max_line_length,
+            max_size: self.max_size,
+            options: self.options.clone(),
+            consumed: self.consumed,
+            recovered: vec![],
+        }
+    }
+}
+
+impl Parser {
+    /// new creates a parser with the default options.
+    pub fn new() -> Self {
+        Parser::default()
+    }
+
+    /// with_max_line_length sets the limit of a single line, without its line
+    /// break.
+    pub fn with_max_line_length(mut self, max_line_length: usize) -> Self {
+        self.max_line_length = max_line_length;
+        self
+    }
+
+    /// with_max_size sets the limit of the whole session description,
+    /// including line breaks.
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// with_options replaces all options.
+    pub fn with_options(mut self, options: ParseOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// consumed returns the number of bytes the last call to parse read from
+    /// its reader, up to and including the line which failed, if any.
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// recovered returns the errors the last call to parse worked around in
+    /// lenient mode, in the order of their lines.
+    pub fn recovered(&self) -> &[RecoveredError] {
+        &self.recovered
+    }
+
+    /// parse reads a session description until the end of the reader. Errors
+    /// of the reader are returned as Error::Io, while the errors of the
+    /// description are wrapped in Error::SdpParse with their position.
+    pub fn parse<R: BufRead>(&mut self, mut reader: R) -> Result<SessionDescription, Error> {
+        let lenient = self.options.strictness == Strictness::Lenient;
+        let mut desc = SessionDescription::default();
+        let mut state = 1;
+        let mut line = 0;
+        let mut buf = Vec::new();
+        self.consumed = 0;
+        self.recovered.clear();
+
+        loop {
+            line += 1;
+            let at = |err, line_type| Error::SdpParse {
+                line,
+                line_type,
+                source: Box::new(err),
+            };
+
+            // One byte more than allowed is enough to tell that a limit is
+            // exceeded, without reading any further.
+            let remaining = self.max_size - self.consumed.min(self.max_size);
+            let limit = self.max_line_length.saturating_add(2).min(remaining + 1);
+            buf.clear();
+            let num_bytes = read_line(&mut reader.by_ref().take(limit as u64), &mut buf, lenient)?;
+            self.consumed += num_bytes;
+
+            if num_bytes == 0 {
+                if lenient && matches!(state, 4..=8 | 10) {
+                    state = self.add_missing_timing(&mut desc, state, line)?;
+                }
+                return if SessionDescription::is_final_state(state) {
+                    if let Some(interner) = &self.options.interner {
+                        desc.intern(interner);
+                    }
+                    Ok(desc)
+                } else {
+                    Err(at(Error::SdpInvalidSyntax(String::new()), None))
+                };
+            }
+
+            // Like line breaks, stray carriage returns between fields are
+            // skipped.
+            let field = trim_line_break(&buf);
+            let field = &field[field.iter().take_while(|&&b| b == b'\r').count()..];
+            let line_type = field.first().map(|&b| b as char);
+            if self.consumed > self.max_size {
+                return Err(at(Error::SdpTooLarge(self.max_size), line_type));
+            }
+            if field.len() > self.max_line_length {
+                return Err(at(Error::SdpLineTooLong(self.max_line_length), line_type));
+            }
+            if field.is_empty() {
+                continue;
+            }
+            if state == 1 && !self.options.normalize_line_endings {
+                desc.line_ending = LineEnding::of(&buf);
+            }
+
+            // The value is passed on as bytes, as text fields may be in
+            // another charset than UTF-8.
+            let (key, value) = match split_field(field, lenient) {
+                Some(key_value) => key_value,
+                None => {
+                    return Err(at(
+                        Error::SdpInvalidSyntax(format!("{:?}", String::from_utf8_lossy(field))),
+                        line_type,
+                    ))
+                }
+            };
+
+            if key.is_ascii_alphabetic() && !LINE_TYPES.contains(&key) {
+                match self.options.unknown_line_policy {
+                    UnknownLinePolicy::Reject => {}
+                    UnknownLinePolicy::Keep => {
+                        let line_type = key as char;
+                        let value = String::from_utf8_lossy(value).into_owned();
+                        let (unknown_lines, position) = match desc.media_descriptions.last_mut() {
+                            Some(md) if state >= 12 => {
+                                let position = md.line_count();
+                                (&mut md.unknown_lines, position)
+                            }
+                            // Before "s=", only the lines parsed so far
+                            // count, as "v=", "o=" and "s=" are always
+                            // written.
+                            _ => {
+                                let position = match state {
+                                    1..=3 => usize::from(state - 1),
+                                    _ => desc.line_count(),
+                                };
+                                (&mut desc.unknown_lines, position)
+                            }
+                        };
+                        unknown_lines.push(UnknownLine {
+                            line_type,
+                            value,
+                            position,
+                        });
+                        continue;
+                    }
+                    UnknownLinePolicy::Drop => continue,
+                }
+            }
+
+            if key == b'm' {
+                if let Some(max) = self.options.max_media_sections {
+                    if desc.media_descriptions.len() >= max {
+                        return Err(at(Error::SdpTooManyMediaSections(max), line_type));
+                    }
+                }
+            }
+
+            state = match desc.unmarshal_field(state, key, value) {
+                Ok(state) => state,
+                Err(err) if lenient => self
+                    .recover(&mut desc, state, line, key, value, err)
+                    .map_err(|err| at(err, line_type))?,
+                Err(err) => return Err(at(err, line_type)),
+            };
+        }
+    }
+
+    /// recover works around err, which unmarshal_field returned for a field
+    /// in lenient mode, and returns the next state.
+    fn recover(
+        &mut self,
+        desc: &mut SessionDescription,
+        state: u8,
+        line: usize,
+        key: u8,
+        value: &[u8],
+        err: Error,
+    ) -> Result<u8, Error> {
+        let decoded = String::from_utf8_lossy(value);
+        let text = decoded.trim();
+
+        // Attributes before the first "t=" field are taken as session
+        // attributes, as the "t=" field may still follow.
+        if key == b'a' && matches!(state, 2..=8 | 10) {
+            if let Ok(attribute) = AttributeRef::parse_line(&decoded) {
+                desc.attributes.push(attribute.to_owned());
+            }
+            self.recovered.push(RecoveredError {
+                line,
+                line_type: Some('a'),
+                error: err,
+            });
+            return Ok(state);
+        }
+
+        // The last of several "k=" fields at the same level wins.
+        if let Error::SdpDuplicateField(_) = err {
+            if let Ok(encryption_key) = text.parse::<EncryptionKey>() {
+                match desc.media_descriptions.last_mut() {
+                    Some(md) if state >= 12 => md.encryption_key = Some(encryption_key),
+                    _ => desc.encryption_key = Some(encryption_key),
+                }
+            }
+            self.recovered.push(RecoveredError {
+                line,
+                line_type: Some(key as char),
+                error: err,
+            });
+            return Ok(state);
+        }
+
+        // Other fields which must follow the first "t=" field.
+        if matches!(state, 4..=8 | 10)
+            && !SessionDescription::accepts_field(state, key)
+            && SessionDescription::accepts_field(9, key)
+        {
+            let state = self.add_missing_timing(desc, state, line)?;
+            return match desc.unmarshal_field(state, key, value) {
+                Ok(state) => Ok(state),
+                Err(err) => self.recover(desc, state, line, key, value, err),
+            };
+        }
+
+        let next = match key {
+            b'o' | b'c' if SessionDescription::accepts_field(state, key) => {
+                let value = uppercase_address_types(text, if key == b'o' { 3 } else { 0 });
+                match desc.unmarshal_field(state, key, value.as_bytes()) {
+                    Ok(next) => next,
+                    Err(_) if key == b'c' => state,
+                    Err(_) => return Err(err),
+                }
+            }
+            b'i' | b'u' | b'e' | b'p' | b'c' | b'b' | b'r' | b'z' | b'k' | b'a' => state,
+            _ => return Err(err),
+        };
+
+        self.recovered.push(RecoveredError {
+            line,
+            line_type: Some(key as char),
+            error: err,
+        });
+        Ok(next)
+    }
+
+    /// add_missing_timing adds "t=0 0", i.e. an unbounded session, to a
+    /// description which has none, and returns the next state.
+    fn add_missing_timing(
+        &mut self,
+        desc: &mut SessionDescription,
+        state: u8,
+        line: usize,
+    ) -> Result<u8, Error> {
+        let state = desc.unmarshal_field(state, b't', b"0 0")?;
+        self.recovered.push(RecoveredError {
+            line,
+            line_type: Some('t'),
+            error: Error::SdpMissingField("t=".to_owned()),
+        });
+        Ok(state)
+    }
+}
+
+impl SessionDescription {
+    /// from_reader parses a session description from an io::BufRead with the
+    /// default limits of Parser.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, Error> {
+        Parser::new().parse(reader)
+    }
+}
+
+/// read_line reads up to and including the next "\n", like
+/// BufRead::read_until. With bare_cr, a "\r" which isn't followed by "\n"
+/// ends the line as well.
+fn read_line<R: BufRead>(reader: &mut R, buf: &mut Vec<u8>, bare_cr: bool) -> io::Result<usize> {
+    if !bare_cr {
+        return reader.read_until(b'\n', buf);
+    }
+
+    let start = buf.len();
+    loop {
+        let available = match reader.fill_buf() {
+            Ok(available) => available,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        if buf.last() == Some(&b'\r') && buf.len() > start {
+            if available.first() == Some(&b'\n') {
+                buf.push(b'\n');
+                reader.consume(1);
+            }
+            return Ok(buf.len() - start);
+        }
+        if available.is_empty() {
+            return Ok(buf.len() - start);
+        }
+        match available.iter().position(|&b| b == b'\n' || b == b'\r') {
+            Some(i) => {
+                let done = available[i] == b'\n';
+                buf.extend_from_slice(&available[..=i]);
+                reader.consume(i + 1);
+                if done {
+                    return Ok(buf.len() - start);
+                }
+            }
+            None => {
+                let len = available.len();
+                buf.extend_from_slice(available);
+                reader.consume(len);
+            }
+        }
+    }
+}
+
+/// uppercase_address_types uppercases the `<nettype>` and `<addrtype>` found at
+/// the given index of the space-separated fields of "o=" or "c=", if they
+/// are "IN" and "IP4" or "IP6" in another case.
+fn uppercase_address_types(value: &str, index: usize) -> String {
+    let mut fields: Vec<String> = value.split(' ').map(str::to_owned).collect();
+    let known = [NETWORK_TYPE_IN, ADDRESS_TYPE_IP4, ADDRESS_TYPE_IP6];
+    for field in fields.iter_mut().skip(index).take(2) {
+        if let Some(name) = known.iter().find(|name| field.eq_ignore_ascii_case(name)) {
+            *field = (*name).to_owned();
+        }
+    }
+    fields.join(" ")
+}
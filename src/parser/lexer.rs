@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod lexer_test;
+
+/// Field is a field found by Fields, as slices of the input.
+pub(crate) struct Field<'a> {
+    /// line is the 1-based number of the line the field starts on.
+    pub(crate) line: usize,
+    /// key is everything up to and including the first "=", which is the
+    /// type of the field and the "=" unless the field is malformed. Without
+    /// any "=" on the line it is the whole line, without the "\n".
+    pub(crate) key: &'a [u8],
+    /// value is the rest of the line after key, without the "\n".
+    pub(crate) value: &'a [u8],
+}
+
+/// Fields splits a session description held in memory into its fields in a
+/// single pass, without copying or decoding them. Line breaks and stray
+/// carriage returns between fields are skipped.
+pub(crate) struct Fields<'a> {
+    rest: &'a [u8],
+    newlines: usize,
+}
+
+impl<'a> Fields<'a> {
+    /// new starts splitting input at its first field.
+    pub(crate) fn new(input: &'a [u8]) -> Self {
+        Fields {
+            rest: input,
+            newlines: 0,
+        }
+    }
+
+    /// line is the 1-based number of the line where the next field would
+    /// start, which is where the input ends once next returned None.
+    pub(crate) fn line(&self) -> usize {
+        self.newlines + 1
+    }
+}
+
+impl<'a> Iterator for Fields<'a> {
+    type Item = Field<'a>;
+
+    fn next(&mut self) -> Option<Field<'a>> {
+        let skipped = self
+            .rest
+            .iter()
+            .take_while(|&&b| b == b'\r' || b == b'\n')
+            .count();
+        self.newlines += self.rest[..skipped].iter().filter(|&&b| b == b'\n').count();
+        self.rest = &self.rest[skipped..];
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        let line = self.line();
+        let line_len = self
+            .rest
+            .iter()
+            .position(|&b| b == b'\n')
+            .unwrap_or(self.rest.len());
+        let key_len = match self.rest[..line_len].iter().position(|&b| b == b'=') {
+            Some(i) => i + 1,
+            None => line_len,
+        };
+        let (key, rest) = self.rest.split_at(key_len);
+        let (value, rest) = match rest.iter().position(|&b| b == b'\n') {
+            Some(i) => {
+                self.newlines += 1;
+                (&rest[..i], &rest[i + 1..])
+            }
+            None => (rest, &rest[rest.len()..]),
+        };
+        self.rest = rest;
+
+        Some(Field { line, key, value })
+    }
+}
+
+/// trim_line_break removes the "\n" or "\r\n" ending line.
+pub(crate) fn trim_line_break(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+/// split_field splits a line into its type and value. Lenient parsing allows
+/// spaces between the type and the "=".
+pub(crate) fn split_field(field: &[u8], lenient: bool) -> Option<(u8, &[u8])> {
+    let (&key, rest) = field.split_first()?;
+    let rest = if lenient {
+        &rest[rest
+            .iter()
+            .take_while(|&&b| b == b' ' || b == b'\t')
+            .count()..]
+    } else {
+        rest
+    };
+    match rest {
+        [b'=', value @ ..] if key.is_ascii() && key != b'=' => Some((key, value)),
+        _ => None,
+    }
+}
@@ -0,0 +1,67 @@
+use super::*;
+
+fn split(input: &str) -> Vec<(usize, &str, &str)> {
+    Fields::new(input.as_bytes())
+        .map(|field| {
+            (
+                field.line,
+                core::str::from_utf8(field.key).unwrap_or_default(),
+                core::str::from_utf8(field.value).unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_fields() {
+    assert_eq!(
+        split("v=0\r\no=- 1 2 IN IP4 0.0.0.0\r\ns= a=b \r\n"),
+        vec![
+            (1, "v=", "0\r"),
+            (2, "o=", "- 1 2 IN IP4 0.0.0.0\r"),
+            (3, "s=", " a=b \r"),
+        ]
+    );
+}
+
+#[test]
+fn test_fields_line_breaks() {
+    let mut fields = Fields::new(b"\n\r\nv=0\n\n\rt=0 0");
+    let field = fields.next().map(|f| (f.line, f.key, f.value));
+    assert_eq!(field, Some((3, &b"v="[..], &b"0"[..])));
+    let field = fields.next().map(|f| (f.line, f.key, f.value));
+    assert_eq!(field, Some((5, &b"t="[..], &b"0 0"[..])));
+    assert!(fields.next().is_none());
+    assert_eq!(fields.line(), 5);
+
+    let mut fields = Fields::new(b"v=0\r\n\r\n");
+    assert!(fields.next().is_some());
+    assert!(fields.next().is_none());
+    assert_eq!(fields.line(), 3);
+}
+
+#[test]
+fn test_fields_malformed() {
+    // The key runs up to the first "=" on its line.
+    assert_eq!(split("v\nx=1"), vec![(1, "v", ""), (2, "x=", "1")]);
+    assert_eq!(split("v\r\nx=1"), vec![(1, "v\r", ""), (2, "x=", "1")]);
+    assert_eq!(split("abc"), vec![(1, "abc", "")]);
+    assert_eq!(split("=x\n"), vec![(1, "=", "x")]);
+}
+
+#[test]
+fn test_split_field() {
+    assert_eq!(
+        split_field(b"a=rtcp-mux", false),
+        Some((b'a', &b"rtcp-mux"[..]))
+    );
+    assert_eq!(split_field(b"a =rtcp-mux", false), None);
+    assert_eq!(
+        split_field(b"a =rtcp-mux", true),
+        Some((b'a', &b"rtcp-mux"[..]))
+    );
+    assert_eq!(split_field(b"==", false), None);
+    assert_eq!(split_field(b"", false), None);
+    assert_eq!(trim_line_break(b"v=0\r\n"), b"v=0");
+    assert_eq!(trim_line_break(b"v=0\n"), b"v=0");
+}
@@ -0,0 +1,436 @@
+use super::*;
+
+use std::io;
+
+use crate::common_description::UnknownLine;
+
+const CANONICAL_SDP: &str = "v=0\r\n\
+o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
+s=SDP Seminar\r\n\
+i=A Seminar on the session description protocol\r\n\
+u=http://www.example.com/seminars/sdp.pdf\r\n\
+e=j.doe@example.com (Jane Doe)\r\n\
+c=IN IP4 224.2.17.12/127\r\n\
+t=2873397496 2873404696\r\n\
+a=recvonly\r\n\
+m=audio 49170 RTP/AVP 0\r\n\
+m=video 51372 RTP/AVP 99\r\n\
+a=rtpmap:99 h263-1998/90000\r\n";
+
+// OneByteReader returns at most one byte per read, so that every line is
+// split across buffer boundaries.
+struct OneByteReader<'a>(&'a [u8]);
+
+impl io::Read for OneByteReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match (self.0.split_first(), buf.first_mut()) {
+            (Some((&byte, rest)), Some(out)) => {
+                *out = byte;
+                self.0 = rest;
+                Ok(1)
+            }
+            _ => Ok(0),
+        }
+    }
+}
+
+struct FailingReader;
+
+impl io::Read for FailingReader {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::other("connection reset"))
+    }
+}
+
+#[test]
+fn test_parser() -> Result<(), Error> {
+    let expected = CANONICAL_SDP.parse::<SessionDescription>()?;
+
+    let mut parser = Parser::new();
+    let sd = parser.parse(CANONICAL_SDP.as_bytes())?;
+    assert_eq!(sd, expected);
+    assert_eq!(parser.consumed(), CANONICAL_SDP.len());
+
+    let reader = io::BufReader::with_capacity(1, OneByteReader(CANONICAL_SDP.as_bytes()));
+    assert_eq!(SessionDescription::from_reader(reader)?, expected);
+
+    // Bare "\n" line breaks and blank lines are accepted like by FromStr,
+    // and the line breaks are kept unless they are normalized.
+    let input = CANONICAL_SDP
+        .replace("\r\n", "\n")
+        .replace("m=video", "\nm=video");
+    let sd = SessionDescription::from_reader(input.as_bytes())?;
+    assert_eq!(sd.line_ending, LineEnding::Lf);
+    assert_eq!(
+        SessionDescription {
+            line_ending: LineEnding::CrLf,
+            ..sd
+        },
+        expected
+    );
+    let mut parser = Parser::new().with_options(ParseOptions {
+        normalize_line_endings: true,
+        ..Default::default()
+    });
+    assert_eq!(parser.parse(input.as_bytes())?, expected);
+
+    // A carriage return before a field is skipped like by FromStr.
+    let input = CANONICAL_SDP.replace("\r\nm=video", "\r\n\rm=video");
+    assert_eq!(SessionDescription::from_reader(input.as_bytes())?, expected);
+
+    Ok(())
+}
+
+#[test]
+fn test_parser_errors() {
+    let mut parser = Parser::new();
+    let input = "v=0\r\no=- x 0 IN IP4 0.0.0.0\r\ns=-\r\n";
+    match parser.parse(input.as_bytes()) {
+        Err(Error::SdpParse {
+            line: 2,
+            line_type: Some('o'),
+            ..
+        }) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    // Parsing stops after the failing line.
+    assert_eq!(parser.consumed(), input.find("s=").unwrap());
+
+    // Errors of the reader are not parse errors.
+    assert!(matches!(
+        parser.parse(io::BufReader::new(FailingReader)),
+        Err(Error::Io(_))
+    ));
+
+    // Input ending before the first time description.
+    assert!(matches!(
+        parser.parse("v=0\r\n".as_bytes()),
+        Err(Error::SdpParse { line: 2, .. })
+    ));
+}
+
+#[test]
+fn test_parser_limits() {
+    let long_attribute = format!("a={}\r\n", "x".repeat(100));
+    let input = CANONICAL_SDP.to_owned() + &long_attribute;
+
+    let mut parser = Parser::new().with_max_line_length(64);
+    match parser.parse(input.as_bytes()) {
+        Err(Error::SdpParse {
+            line: 13,
+            line_type: Some('a'),
+            source,
+        }) => assert!(matches!(*source, Error::SdpLineTooLong(64))),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    // Reading stops right after the limit.
+    assert_eq!(parser.consumed(), CANONICAL_SDP.len() + 66);
+
+    // A line of exactly the maximum length is accepted.
+    let mut parser = Parser::new().with_max_line_length(102);
+    assert!(parser.parse(input.as_bytes()).is_ok());
+
+    let mut parser = Parser::new().with_max_size(CANONICAL_SDP.len());
+    assert!(parser.parse(CANONICAL_SDP.as_bytes()).is_ok());
+    match parser.parse(input.as_bytes()) {
+        Err(Error::SdpParse { source, .. }) => {
+            assert!(matches!(*source, Error::SdpTooLarge(_)))
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(parser.consumed(), CANONICAL_SDP.len() + 1);
+}
+
+fn lenient() -> Parser {
+    Parser::new().with_options(ParseOptions {
+        strictness: Strictness::Lenient,
+        ..Default::default()
+    })
+}
+
+// BROKEN_SDPS are descriptions as sent by real implementations, with the
+// error strict parsing stops at and the repaired description lenient
+// parsing returns instead.
+const BROKEN_SDPS: &[(&str, &str, usize, char, &str, &str)] = &[
+    (
+        "missing timing",
+        "v=0\r\n\
+o=- 1 1 IN IP4 10.0.0.1\r\n\
+s=-\r\n\
+c=IN IP4 10.0.0.1\r\n\
+m=audio 5004 RTP/AVP 0\r\n",
+        5,
+        'm',
+        "sdp: invalid syntax: m=",
+        "v=0\r\n\
+o=- 1 1 IN IP4 10.0.0.1\r\n\
+s=-\r\n\
+c=IN IP4 10.0.0.1\r\n\
+t=0 0\r\n\
+m=audio 5004 RTP/AVP 0\r\n",
+    ),
+    (
+        "lowercase address type",
+        "v=0\r\n\
+o=- 1 1 IN ip4 10.0.0.1\r\n\
+s=-\r\n\
+c=IN ip4 10.0.0.1\r\n\
+t=0 0\r\n",
+        2,
+        'o',
+        "sdp: invalid value: ip4",
+        "v=0\r\n\
+o=- 1 1 IN IP4 10.0.0.1\r\n\
+s=-\r\n\
+c=IN IP4 10.0.0.1\r\n\
+t=0 0\r\n",
+    ),
+    (
+        "attribute before session name",
+        "v=0\r\n\
+o=- 1 1 IN IP4 10.0.0.1\r\n\
+a=tool:softphone 1.0\r\n\
+s=-\r\n\
+t=0 0\r\n",
+        3,
+        'a',
+        "sdp: invalid syntax: a=",
+        "v=0\r\n\
+o=- 1 1 IN IP4 10.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=tool:softphone 1.0\r\n",
+    ),
+    (
+        "spaces around equals sign",
+        "v=0\r\n\
+o=- 1 1 IN IP4 10.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+m=audio 5004 RTP/AVP 0\r\n\
+a = sendrecv\r\n",
+        6,
+        'a',
+        "sdp: invalid syntax: \"a = sendrecv\"",
+        "v=0\r\n\
+o=- 1 1 IN IP4 10.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+m=audio 5004 RTP/AVP 0\r\n\
+a=sendrecv\r\n",
+    ),
+    (
+        "carriage return line endings",
+        "v=0\ro=- 1 1 IN IP4 10.0.0.1\rs=-\rt=0 0\r",
+        1,
+        'v',
+        "sdp: invalid syntax: `v=0\ro=- 1 1 IN IP4 10.0.0.1\rs=-\rt=0 0`",
+        "v=0\r\n\
+o=- 1 1 IN IP4 10.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n",
+    ),
+    (
+        "unparseable bandwidth",
+        "v=0\r\n\
+o=- 1 1 IN IP4 10.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+m=video 5006 RTP/AVP 96\r\n\
+b=AS:unlimited\r\n\
+a=rtpmap:96 VP8/90000\r\n",
+        6,
+        'b',
+        "sdp: bandwidth `AS:unlimited` has a non-numeric value",
+        "v=0\r\n\
+o=- 1 1 IN IP4 10.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+m=video 5006 RTP/AVP 96\r\n\
+a=rtpmap:96 VP8/90000\r\n",
+    ),
+];
+
+#[test]
+fn test_parser_lenient() -> Result<(), Error> {
+    for &(name, input, line, line_type, error, repaired) in BROKEN_SDPS {
+        match Parser::new().parse(input.as_bytes()) {
+            Err(Error::SdpParse {
+                line: l,
+                line_type: Some(t),
+                source,
+            }) => {
+                assert_eq!((l, t), (line, line_type), "{}: unexpected position", name);
+                assert_eq!(source.to_string(), error, "{}: unexpected error", name);
+            }
+            other => panic!("{}: unexpected strict result: {:?}", name, other),
+        }
+
+        let sd = lenient().parse(input.as_bytes())?;
+        assert_eq!(sd.marshal(), repaired, "{}: unexpected repair", name);
+
+        let reader = io::BufReader::with_capacity(1, OneByteReader(input.as_bytes()));
+        assert_eq!(lenient().parse(reader)?, sd, "{}: split across reads", name);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_parser_lenient_recovered() -> Result<(), Error> {
+    let input = "v=0\r\n\
+o=- 1 1 IN IP4 10.0.0.1\r\n\
+s=-\r\n\
+c=IN ip4 10.0.0.1\r\n\
+m=audio 5004 RTP/AVP 0\r\n\
+b=AS:unlimited\r\n\
+a=sendrecv\r\n";
+    let mut parser = lenient();
+    parser.parse(input.as_bytes())?;
+
+    let recovered: Vec<_> = parser
+        .recovered()
+        .iter()
+        .map(|r| (r.line, r.line_type, r.error.to_string()))
+        .collect();
+    assert_eq!(
+        recovered,
+        vec![
+            (4, Some('c'), "sdp: invalid value: ip4".to_owned()),
+            (5, Some('t'), "sdp: missing mandatory `t=` field".to_owned()),
+            (
+                6,
+                Some('b'),
+                "sdp: bandwidth `AS:unlimited` has a non-numeric value".to_owned()
+            ),
+        ]
+    );
+
+    // The last of duplicate "k=" fields wins at either level.
+    let input = "v=0\r\n\
+o=- 1 1 IN IP4 10.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+k=prompt\r\n\
+k=clear:secret\r\n\
+m=audio 5004 RTP/AVP 0\r\n\
+k=prompt\r\n\
+a=sendrecv\r\n\
+k=uri:https://example.com/key\r\n";
+    let sd = parser.parse(input.as_bytes())?;
+    assert_eq!(sd.encryption_key, Some("clear:secret".parse()?));
+    assert_eq!(
+        sd.media_descriptions[0].encryption_key,
+        Some("uri:https://example.com/key".parse()?)
+    );
+    let recovered: Vec<_> = parser
+        .recovered()
+        .iter()
+        .map(|r| (r.line, r.line_type, r.error.to_string()))
+        .collect();
+    assert_eq!(
+        recovered,
+        vec![
+            (6, Some('k'), "sdp: duplicate `k=` field".to_owned()),
+            (10, Some('k'), "sdp: duplicate `k=` field".to_owned()),
+        ]
+    );
+    assert!(matches!(
+        Parser::new().parse(input.as_bytes()),
+        Err(Error::SdpParse { line: 6, .. })
+    ));
+
+    // Mandatory fields can't be recovered.
+    let input = "v=0\r\no=- x 1 IN IP4 10.0.0.1\r\ns=-\r\nt=0 0\r\n";
+    assert!(matches!(
+        parser.parse(input.as_bytes()),
+        Err(Error::SdpParse { line: 2, .. })
+    ));
+    assert!(parser.recovered().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_parser_unknown_lines() -> Result<(), Error> {
+    let input = "v=0\r\n\
+o=- 1 1 IN IP4 10.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+x=session extension\r\n\
+m=audio 5004 RTP/AVP 0\r\n\
+y=media  extension \r\n\
+a=sendrecv\r\n";
+    let with_policy = |unknown_line_policy| {
+        Parser::new().with_options(ParseOptions {
+            unknown_line_policy,
+            ..Default::default()
+        })
+    };
+
+    match with_policy(UnknownLinePolicy::Reject).parse(input.as_bytes()) {
+        Err(Error::SdpParse {
+            line: 5,
+            line_type: Some('x'),
+            source,
+        }) => assert_eq!(source.to_string(), "sdp: invalid syntax: x="),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let mut sd = with_policy(UnknownLinePolicy::Keep).parse(input.as_bytes())?;
+    assert_eq!(
+        sd.unknown_lines,
+        vec![UnknownLine {
+            line_type: 'x',
+            value: "session extension".to_owned(),
+            position: 4,
+        }]
+    );
+    assert_eq!(
+        sd.media_descriptions[0].unknown_lines,
+        vec![UnknownLine {
+            line_type: 'y',
+            value: "media  extension ".to_owned(),
+            position: 1,
+        }]
+    );
+    assert_eq!(sd.media_descriptions[0].attributes.len(), 1);
+    // They are written back in their places.
+    assert_eq!(sd.marshal(), input);
+    assert_eq!(sd.marshal_len_hint(), input.len());
+
+    sd.unknown_lines.clear();
+    sd.media_descriptions[0].unknown_lines.clear();
+    assert_eq!(
+        with_policy(UnknownLinePolicy::Drop).parse(input.as_bytes())?,
+        sd
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_parser_max_media_sections() -> Result<(), Error> {
+    let options = |max_media_sections| ParseOptions {
+        max_media_sections,
+        ..Default::default()
+    };
+
+    assert!(Parser::new()
+        .with_options(options(Some(2)))
+        .parse(CANONICAL_SDP.as_bytes())
+        .is_ok());
+    match Parser::new()
+        .with_options(options(Some(1)))
+        .parse(CANONICAL_SDP.as_bytes())
+    {
+        Err(Error::SdpParse {
+            line: 11,
+            line_type: Some('m'),
+            source,
+        }) => assert!(matches!(*source, Error::SdpTooManyMediaSections(1))),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    Ok(())
+}
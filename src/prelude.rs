@@ -0,0 +1,249 @@
+//! Commonly used types, re-exported for glob imports.
+//!
+//! ```
+//! use webrtc_rs_sdp::prelude::*;
+//! ```
+//!
+//! # Examples
+//!
+//! [`SessionDescription`] from its mandatory fields:
+//!
+//! ```
+//! # use webrtc_rs_sdp::prelude::*;
+//! # fn main() -> Result<(), Error> {
+//! let sd = SessionDescription::new(
+//!     "jdoe 2890844526 2890842807 IN IP4 10.47.16.5".parse::<Origin>()?,
+//!     "SDP Seminar".parse::<SessionName>()?,
+//!     TimeDescription {
+//!         timing: Timing::new(0, 0)?,
+//!         repeat_times: vec![],
+//!     },
+//! );
+//! assert_eq!(
+//!     sd.to_string(),
+//!     "v=0\r\n\
+//!      o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
+//!      s=SDP Seminar\r\n\
+//!      t=0 0\r\n"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Origin`], [`Username`], [`SessionName`] and [`Information`]:
+//!
+//! ```
+//! # use webrtc_rs_sdp::prelude::*;
+//! # use std::net::{IpAddr, Ipv4Addr};
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() {}
+//! # #[cfg(feature = "std")]
+//! # fn main() -> Result<(), Error> {
+//! let mut origin = Origin::new_for_host(IpAddr::V4(Ipv4Addr::new(10, 47, 16, 5)));
+//! origin.username = Username::try_new("jdoe")?;
+//! origin.session_id = 2890844526;
+//! origin.session_version = 2890842807;
+//! assert_eq!(
+//!     origin.to_string(),
+//!     "jdoe 2890844526 2890842807 IN IP4 10.47.16.5"
+//! );
+//!
+//! assert_eq!(SessionName::try_new("SDP Seminar")?.to_string(), "SDP Seminar");
+//! assert_eq!(Information::try_new("A Seminar")?.to_string(), "A Seminar");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`ConnectionInformation`], [`Bandwidth`], [`Timing`] and
+//! [`EncryptionKey`]:
+//!
+//! ```
+//! # use webrtc_rs_sdp::prelude::*;
+//! # fn main() -> Result<(), Error> {
+//! let connection = "IN IP4 224.2.17.12/127".parse::<ConnectionInformation>()?;
+//! assert_eq!(connection.to_string(), "IN IP4 224.2.17.12/127");
+//!
+//! assert_eq!(Bandwidth::new("AS", 128)?.to_string(), "AS:128");
+//! assert_eq!(Timing::new(2873397496, 2873404696)?.to_string(), "2873397496 2873404696");
+//!
+//! let key = EncryptionKey::base64_from_bytes(b"secret");
+//! assert_eq!(key.to_string(), "base64:c2VjcmV0");
+//! assert_eq!(format!("{:?}", key), "EncryptionKey::Base64(REDACTED)");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`MediaDescription`], [`MediaName`] and typed attributes such as
+//! [`Direction`], [`RtpMap`], [`Fmtp`] and [`ExtMap`]:
+//!
+//! ```
+//! # use webrtc_rs_sdp::prelude::*;
+//! # fn main() -> Result<(), Error> {
+//! let media_name = "audio 49170 RTP/AVP 111".parse::<MediaName>()?;
+//! assert_eq!(media_name.to_string(), "audio 49170 RTP/AVP 111");
+//!
+//! let mut md = MediaDescription {
+//!     media_name,
+//!     ..Default::default()
+//! };
+//! md.attributes.push(Attribute::try_new("rtpmap", Some("111 opus/48000/2"))?);
+//! md.set_direction(Direction::SendOnly);
+//! assert_eq!(md.direction(), Some(Direction::SendOnly));
+//!
+//! let rtpmap = md.rtpmap_for(111).expect("rtpmap");
+//! assert_eq!(rtpmap.to_string(), "111 opus/48000/2");
+//!
+//! let fmtp = "111 minptime=10;useinbandfec=1".parse::<Fmtp>()?;
+//! assert_eq!(fmtp.to_string(), "111 minptime=10;useinbandfec=1");
+//!
+//! let extmap = "1 urn:ietf:params:rtp-hdrext:sdes:mid".parse::<ExtMap>()?;
+//! assert_eq!(extmap.id, ExtMapId::try_new(1)?);
+//! assert_eq!(extmap.to_string(), "1 urn:ietf:params:rtp-hdrext:sdes:mid");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`SessionDescriptionBuilder`] checks the mandatory fields, here for a
+//! minimal WebRTC data channel offer:
+//!
+//! ```
+//! # use webrtc_rs_sdp::prelude::*;
+//! # fn main() -> Result<(), Error> {
+//! let offer = SessionDescription::builder()
+//!     .origin("- 4215775240449105457 2 IN IP4 127.0.0.1".parse()?)
+//!     .timing(Timing::new(0, 0)?)
+//!     .typed_attribute("BUNDLE 0".parse::<Group>()?)
+//!     .media(
+//!         "application 9 UDP/DTLS/SCTP webrtc-datachannel".parse()?,
+//!         |m| {
+//!             m.connection("IN IP4 0.0.0.0".parse().expect("connection"))
+//!                 .typed_attribute(IceUfrag::try_new("EsAw").expect("ufrag"))
+//!                 .typed_attribute(IcePwd::try_new("P2uYro0UCOQ4zxjKXaWCBui1").expect("pwd"))
+//!                 .typed_attribute(Setup::ActPass)
+//!                 .typed_attribute(Mid::try_new("0").expect("mid"))
+//!                 .typed_attribute(SctpPort(5000))
+//!         },
+//!     )
+//!     .build()?;
+//! assert_eq!(
+//!     offer.to_string(),
+//!     "v=0\r\n\
+//!      o=- 4215775240449105457 2 IN IP4 127.0.0.1\r\n\
+//!      s= \r\n\
+//!      t=0 0\r\n\
+//!      a=group:BUNDLE 0\r\n\
+//!      m=application 9 UDP/DTLS/SCTP webrtc-datachannel\r\n\
+//!      c=IN IP4 0.0.0.0\r\n\
+//!      a=ice-ufrag:EsAw\r\n\
+//!      a=ice-pwd:P2uYro0UCOQ4zxjKXaWCBui1\r\n\
+//!      a=setup:actpass\r\n\
+//!      a=mid:0\r\n\
+//!      a=sctp-port:5000\r\n"
+//! );
+//! assert!(offer.to_string().parse::<SessionDescription>().is_ok());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Map keys
+//!
+//! Value types implement [`Hash`] and [`Eq`], so they can be used as
+//! [`HashMap`] keys. The validated string types, e.g. [`Username`],
+//! [`SessionName`], [`BandwidthType`] and [`Mid`], hash like the string they
+//! hold, which is consistent with their comparison to a `str`. Types with a
+//! total order, such as [`Version`], [`Timing`], [`Port`], [`ExtMapId`],
+//! [`PTime`] and [`Direction`], also implement [`Ord`] and can be used as
+//! [`BTreeMap`] keys or be sorted.
+//!
+//! ```
+//! # use webrtc_rs_sdp::prelude::*;
+//! # use std::collections::{BTreeMap, HashMap, HashSet};
+//! # fn main() -> Result<(), Error> {
+//! let mut by_mid = HashMap::new();
+//! by_mid.insert(Mid::try_new("audio")?, Direction::SendRecv);
+//! assert_eq!(by_mid.get(&Mid::try_new("audio")?), Some(&Direction::SendRecv));
+//!
+//! let keys: HashSet<_> = vec![
+//!     Username::try_new("jdoe")?,
+//!     Username::try_new("jdoe")?,
+//! ]
+//! .into_iter()
+//! .collect();
+//! assert_eq!(keys.len(), 1);
+//!
+//! let mut by_direction = BTreeMap::new();
+//! by_direction.insert(Direction::Inactive, "inactive");
+//! by_direction.insert(Direction::SendRecv, "sendrecv");
+//! assert_eq!(by_direction.keys().next(), Some(&Direction::SendRecv));
+//!
+//! let mut timings = vec![Timing::new(3, 4)?, Timing::new(1, 2)?];
+//! timings.sort();
+//! assert_eq!(timings[0], Timing::new(1, 2)?);
+//!
+//! let mut ports = vec!["49172".parse::<Port>()?, "49170/2".parse::<Port>()?];
+//! ports.sort();
+//! assert_eq!(ports[0].value, 49170);
+//!
+//! let _: HashSet<(MediaType, Protocol, Setup, HashFunction, BandwidthType)> = HashSet::new();
+//! let _: BTreeMap<(Version, ExtMapId, PTime, MaxPTime, SctpPort), ()> = BTreeMap::new();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`HashMap`]: std::collections::HashMap
+//! [`BTreeMap`]: std::collections::BTreeMap
+
+pub use crate::basic::{
+    ConferenceType, FrameRate, Lang, LanguageTag, Orient, Quality, SdpLang, Tool,
+};
+pub use crate::builder::{MediaDescriptionBuilder, SessionDescriptionBuilder};
+pub use crate::candidate::{Candidate, RemoteCandidate, RemoteCandidates};
+pub use crate::charset::Charset;
+pub use crate::common_description::{
+    Address, Attribute, Attributes, Bandwidth, BandwidthKind, BandwidthType, ConnectionInformation,
+    EncryptionKey, Information, NetType, RtcpBandwidth, UnknownLine,
+};
+pub use crate::compact::CompactOptions;
+pub use crate::content::{Content, ContentValue, Label};
+pub use crate::crypto::{Crypto, CryptoSuite, KeyParam, Lifetime, Mki};
+pub use crate::diagnostic::{Diagnostic, DiagnosticKind, Severity};
+pub use crate::diff::{MediaId, SdpChange, SdpDiff};
+pub use crate::direction::Direction;
+pub use crate::edit::{EditSummary, Editor};
+pub use crate::error::Error;
+pub use crate::extmap::{ExtMap, ExtMapId, ExtmapAllowMixed};
+pub use crate::fingerprint::{Fingerprint, HashFunction};
+pub use crate::fmtp::Fmtp;
+pub use crate::group::{Group, GroupSemantics, Mid};
+pub use crate::hdrext::KnownExtension;
+pub use crate::hold::{HoldState, HoldStyle};
+pub use crate::ice::{IceOptions, IcePwd, IceUfrag};
+#[cfg(feature = "std")]
+pub use crate::intern::Interner;
+pub use crate::intern::Istr;
+pub use crate::keymgmt::KeyMgmt;
+pub use crate::maxprate::MaxPRate;
+pub use crate::media_description::{MediaDescription, MediaName, MediaType, Port, Protocol};
+pub use crate::msid::{Msid, MsidSemantic};
+#[cfg(feature = "std")]
+pub use crate::offer_answer::answer_skeleton;
+pub use crate::offer_answer::{Codec, LocalCapabilities};
+#[cfg(feature = "std")]
+pub use crate::parser::{ParseOptions, Parser, RecoveredError, Strictness, UnknownLinePolicy};
+pub use crate::ptime::{MaxPTime, PTime};
+pub use crate::resolve::{AttributeScope, ResolvedMedia};
+pub use crate::rtcp::{Rtcp, RtcpMux, RtcpMuxOnly};
+pub use crate::rtcpfb::{PayloadTypeRef, RtcpFeedback, RtcpFeedbackType};
+pub use crate::rtpmap::RtpMap;
+pub use crate::sanitize::{SanitizePolicy, SanitizeStrategy};
+pub use crate::sctp::{MaxMessageSize, SctpMap, SctpPort};
+pub use crate::session_description::{
+    EmailAddress, Occurrences, Offset, Origin, PhoneNumber, RepeatTime, SessionDescription,
+    SessionName, Time, TimeDescription, TimeZone, TimeZones, Timing, Uri, Username, Version,
+};
+pub use crate::setup::{Connection, Setup};
+pub use crate::simulcast::{Rid, RidDirection, Simulcast, SimulcastRid};
+pub use crate::split::{Composition, OriginPolicy, Remapping, SessionTemplate};
+pub use crate::ssrc::{Ssrc, SsrcGroup, SsrcGroupSemantics, SsrcSource};
+pub use crate::util::LineEnding;
+pub use crate::visit::{Level, LineCounter, SecretRedactor, Visitor, VisitorMut};
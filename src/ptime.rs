@@ -0,0 +1,85 @@
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+use core::time::Duration;
+
+use super::alloc_prelude::*;
+use super::common_description::TypedAttribute;
+use super::error::Error;
+
+#[cfg(test)]
+mod ptime_test;
+
+// impl_packet_time implements the conversions shared by the packet time
+// attributes, which hold a whole number of milliseconds.
+macro_rules! impl_packet_time {
+    ($ty:ident, $name:expr) => {
+        impl $ty {
+            /// as_millis returns the packet time in milliseconds.
+            pub const fn as_millis(self) -> u32 {
+                self.0
+            }
+
+            /// to_std converts the packet time into a Duration.
+            pub fn to_std(self) -> Duration {
+                Duration::from_millis(u64::from(self.0))
+            }
+        }
+
+        impl fmt::Display for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl FromStr for $ty {
+            type Err = Error;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(Error::SdpInvalidPacketTime(value.to_owned()));
+                }
+                value
+                    .parse()
+                    .map($ty)
+                    .map_err(|_| Error::SdpInvalidPacketTime(value.to_owned()))
+            }
+        }
+
+        /// Fails if the duration is not a whole number of milliseconds or
+        /// does not fit into u32 milliseconds.
+        impl TryFrom<Duration> for $ty {
+            type Error = Error;
+
+            fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+                if duration.subsec_nanos() % 1_000_000 != 0 {
+                    return Err(Error::SdpInvalidPacketTime(format!("{:?}", duration)));
+                }
+                u32::try_from(duration.as_millis())
+                    .map($ty)
+                    .map_err(|_| Error::SdpInvalidPacketTime(format!("{:?}", duration)))
+            }
+        }
+
+        impl TypedAttribute for $ty {
+            const NAME: &'static str = $name;
+        }
+    };
+}
+
+/// PTime is the length of time in milliseconds represented by the media in
+/// a packet.
+/// `a=ptime:<packet time>`
+/// <https://tools.ietf.org/html/rfc4566#section-6>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PTime(pub u32);
+
+/// MaxPTime is the maximum amount of media in milliseconds that can be
+/// encapsulated in each packet.
+/// `a=maxptime:<maximum packet time>`
+/// <https://tools.ietf.org/html/rfc4566#section-6>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MaxPTime(pub u32);
+
+impl_packet_time!(PTime, "ptime");
+impl_packet_time!(MaxPTime, "maxptime");
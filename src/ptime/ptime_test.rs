@@ -0,0 +1,34 @@
+use super::*;
+
+#[test]
+fn test_ptime_from_str() -> Result<(), Error> {
+    for input in ["20", "0", "120", "4294967295"] {
+        assert_eq!(input.parse::<PTime>()?.to_string(), input);
+        assert_eq!(input.parse::<MaxPTime>()?.to_string(), input);
+    }
+
+    for input in ["", "20.5", "2.0", "-20", "+20", "20ms", "4294967296"] {
+        assert!(
+            matches!(input.parse::<PTime>(), Err(Error::SdpInvalidPacketTime(_))),
+            "expected `{}` to be rejected",
+            input
+        );
+        assert!(input.parse::<MaxPTime>().is_err(), "{}", input);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_ptime_duration() -> Result<(), Error> {
+    let ptime = PTime::try_from(Duration::from_millis(20))?;
+    assert_eq!(ptime, PTime(20));
+    assert_eq!(ptime.to_std(), Duration::from_millis(20));
+    assert_eq!(MaxPTime::try_from(Duration::from_secs(1))?, MaxPTime(1000));
+
+    assert!(PTime::try_from(Duration::from_micros(20500)).is_err());
+    assert!(PTime::try_from(Duration::from_nanos(1)).is_err());
+    assert!(MaxPTime::try_from(Duration::from_millis(u64::from(u32::MAX) + 1)).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,369 @@
+use super::alloc_prelude::*;
+use super::basic::{ConferenceType, FrameRate, Lang, Orient, Quality, SdpLang, Tool};
+use super::candidate::{Candidate, RemoteCandidates};
+use super::charset::Charset;
+use super::common_description::{
+    Attribute, Attributes, Bandwidth, BandwidthKind, ConnectionInformation, EncryptionKey,
+    TypedAttribute,
+};
+use super::content::{Content, Label};
+use super::crypto::Crypto;
+use super::direction::Direction;
+use super::extmap::{ExtMap, ExtmapAllowMixed};
+use super::fingerprint::Fingerprint;
+use super::fmtp::Fmtp;
+use super::group::{Group, Mid};
+use super::ice::{IceOptions, IcePwd, IceUfrag};
+use super::keymgmt::KeyMgmt;
+use super::maxprate::MaxPRate;
+use super::media_description::MediaDescription;
+use super::msid::{Msid, MsidSemantic};
+use super::ptime::{MaxPTime, PTime};
+use super::rtcp::{Rtcp, RtcpMux, RtcpMuxOnly};
+use super::rtcpfb::RtcpFeedback;
+use super::rtpmap::RtpMap;
+use super::sctp::{MaxMessageSize, SctpMap, SctpPort};
+use super::session_description::SessionDescription;
+use super::setup::{Connection, Setup};
+use super::simulcast::{Rid, Simulcast};
+use super::ssrc::{Ssrc, SsrcGroup};
+
+#[cfg(test)]
+mod resolve_test;
+
+/// AttributeScope tells at which levels an attribute may appear, and so
+/// whether media descriptions inherit it from the session level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AttributeScope {
+    /// SessionOnly attributes describe the session as a whole, e.g.
+    /// "a=group:". They are only taken from the session level; misplaced
+    /// media level ones are ignored.
+    SessionOnly,
+    /// MediaOnly attributes describe a single media description, e.g.
+    /// "a=mid:" or "a=candidate:". They are never inherited, even if they
+    /// are misplaced at session level.
+    MediaOnly,
+    /// Inherited attributes may appear at both levels. A media description
+    /// which has none of its own takes the session level ones.
+    Inherited,
+}
+
+/// SCOPES gives the scope of every typed attribute, by name. The levels
+/// are those of the IANA "att-field" registry.
+/// <https://www.iana.org/assignments/sdp-parameters/sdp-parameters.xhtml#sdp-parameters-5>
+const SCOPES: &[(&str, AttributeScope)] = &[
+    (Candidate::NAME, AttributeScope::MediaOnly),
+    (Charset::NAME, AttributeScope::Inherited),
+    (ConferenceType::NAME, AttributeScope::SessionOnly),
+    (Connection::NAME, AttributeScope::Inherited),
+    (Content::NAME, AttributeScope::MediaOnly),
+    (Crypto::NAME, AttributeScope::MediaOnly),
+    (Direction::NAME, AttributeScope::Inherited),
+    (ExtMap::NAME, AttributeScope::Inherited),
+    (ExtmapAllowMixed::NAME, AttributeScope::Inherited),
+    (Fingerprint::NAME, AttributeScope::Inherited),
+    (FrameRate::NAME, AttributeScope::MediaOnly),
+    (Fmtp::NAME, AttributeScope::MediaOnly),
+    (Group::NAME, AttributeScope::SessionOnly),
+    (IceOptions::NAME, AttributeScope::Inherited),
+    (IcePwd::NAME, AttributeScope::Inherited),
+    (IceUfrag::NAME, AttributeScope::Inherited),
+    (KeyMgmt::NAME, AttributeScope::Inherited),
+    (Label::NAME, AttributeScope::MediaOnly),
+    (Lang::NAME, AttributeScope::Inherited),
+    (MaxMessageSize::NAME, AttributeScope::MediaOnly),
+    (MaxPRate::NAME, AttributeScope::Inherited),
+    (MaxPTime::NAME, AttributeScope::MediaOnly),
+    (Mid::NAME, AttributeScope::MediaOnly),
+    (Msid::NAME, AttributeScope::MediaOnly),
+    (MsidSemantic::NAME, AttributeScope::SessionOnly),
+    (Orient::NAME, AttributeScope::MediaOnly),
+    (PTime::NAME, AttributeScope::MediaOnly),
+    (Quality::NAME, AttributeScope::MediaOnly),
+    (RemoteCandidates::NAME, AttributeScope::MediaOnly),
+    (Rid::NAME, AttributeScope::MediaOnly),
+    (Rtcp::NAME, AttributeScope::MediaOnly),
+    (RtcpFeedback::NAME, AttributeScope::MediaOnly),
+    (RtcpMux::NAME, AttributeScope::MediaOnly),
+    (RtcpMuxOnly::NAME, AttributeScope::MediaOnly),
+    (RtpMap::NAME, AttributeScope::MediaOnly),
+    (SctpMap::NAME, AttributeScope::MediaOnly),
+    (SctpPort::NAME, AttributeScope::MediaOnly),
+    (SdpLang::NAME, AttributeScope::Inherited),
+    (Setup::NAME, AttributeScope::Inherited),
+    (Simulcast::NAME, AttributeScope::MediaOnly),
+    (Ssrc::NAME, AttributeScope::MediaOnly),
+    (SsrcGroup::NAME, AttributeScope::MediaOnly),
+    (Tool::NAME, AttributeScope::SessionOnly),
+];
+
+impl AttributeScope {
+    /// of returns the scope of the attribute with the given name. Attributes
+    /// unknown to this crate are taken to be MediaOnly, so nothing is
+    /// inherited that shouldn't be.
+    pub fn of(name: &str) -> Self {
+        SCOPES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map_or(AttributeScope::MediaOnly, |(_, scope)| *scope)
+    }
+}
+
+/// ResolvedMedia is a media description as seen with the session level
+/// fields and attributes it inherits. Each accessor returns the value in
+/// effect: the media level one if there is one, else the session level one
+/// where the field or attribute is inherited. Obtain one with
+/// SessionDescription::resolved_media or SessionDescription::resolve_media.
+#[derive(Debug, Clone, Copy)]
+pub struct ResolvedMedia<'a> {
+    session: &'a SessionDescription,
+    media: &'a MediaDescription,
+    index: usize,
+}
+
+impl SessionDescription {
+    /// resolved_media returns a ResolvedMedia for every media description,
+    /// in order.
+    pub fn resolved_media(&self) -> impl Iterator<Item = ResolvedMedia<'_>> + '_ {
+        self.media_descriptions
+            .iter()
+            .enumerate()
+            .map(move |(index, media)| ResolvedMedia {
+                session: self,
+                media,
+                index,
+            })
+    }
+
+    /// resolve_media returns a ResolvedMedia for the media description with
+    /// the given index, None if there is no such media description.
+    pub fn resolve_media(&self, index: usize) -> Option<ResolvedMedia<'_>> {
+        self.media_descriptions
+            .get(index)
+            .map(|media| ResolvedMedia {
+                session: self,
+                media,
+                index,
+            })
+    }
+
+    /// hoist_common_fields writes inherited "c=" and "b=" fields only once.
+    /// A "c=" field which every media description has, all of them the same,
+    /// becomes the session level one. Media level "c=" and "b=" fields equal
+    /// to the session level ones are dropped. What ResolvedMedia returns is
+    /// unchanged. "b=" fields are never hoisted, as a session level one is a
+    /// limit for the whole session.
+    pub fn hoist_common_fields(&mut self) {
+        let common = match self.media_descriptions.split_first() {
+            Some((first, rest)) => first.connection_information.as_ref().filter(|c| {
+                rest.iter()
+                    .all(|md| md.connection_information.as_ref() == Some(*c))
+            }),
+            None => None,
+        };
+        if let Some(common) = common.cloned() {
+            self.connection_information = Some(common);
+        }
+
+        for md in &mut self.media_descriptions {
+            if md.connection_information.is_some()
+                && md.connection_information == self.connection_information
+            {
+                md.connection_information = None;
+            }
+            for session_bandwidth in first_of_modifier(&self.bandwidth) {
+                let media_bandwidth = md
+                    .bandwidth
+                    .iter()
+                    .find(|b| b.has_same_modifier(session_bandwidth));
+                if media_bandwidth == Some(session_bandwidth) {
+                    md.bandwidth
+                        .retain(|b| !b.has_same_modifier(session_bandwidth));
+                }
+            }
+        }
+    }
+
+    /// push_down_fields is the opposite of hoist_common_fields: the session
+    /// level "c=" and "b=" fields are copied into every media description
+    /// which doesn't override them, and removed from the session level, so
+    /// that media descriptions can be taken out on their own, e.g. to forward
+    /// them to different peers. What ResolvedMedia returns is unchanged.
+    /// Without media descriptions nothing is done.
+    pub fn push_down_fields(&mut self) {
+        if self.media_descriptions.is_empty() {
+            return;
+        }
+        let connection_information = self.connection_information.take();
+        let bandwidth = core::mem::take(&mut self.bandwidth);
+
+        for md in &mut self.media_descriptions {
+            if md.connection_information.is_none() {
+                md.connection_information = connection_information.clone();
+            }
+            for session_bandwidth in first_of_modifier(&bandwidth) {
+                if !md
+                    .bandwidth
+                    .iter()
+                    .any(|b| b.has_same_modifier(session_bandwidth))
+                {
+                    md.bandwidth.push(session_bandwidth.clone());
+                }
+            }
+        }
+    }
+    /// push_down_attributes does for the attributes what push_down_fields
+    /// does for "c=" and "b=": the Inherited session level attributes are
+    /// copied into every media description which has none of their type, and
+    /// removed from the session level. SessionOnly attributes and those
+    /// unknown to this crate stay where they are. What ResolvedMedia returns
+    /// is unchanged. Without media descriptions nothing is done.
+    pub fn push_down_attributes(&mut self) {
+        if self.media_descriptions.is_empty() {
+            return;
+        }
+        let (inherited, kept): (Vec<Attribute>, Vec<Attribute>) =
+            core::mem::take(&mut self.attributes.0)
+                .into_iter()
+                .partition(|a| inherited_name(a).is_some());
+        self.attributes.0 = kept;
+
+        for md in &mut self.media_descriptions {
+            let missing: Vec<Attribute> = inherited
+                .iter()
+                .filter(|a| {
+                    !md.attributes
+                        .iter()
+                        .any(|m| inherited_name(m) == inherited_name(a))
+                })
+                .cloned()
+                .collect();
+            md.attributes.extend(missing);
+        }
+    }
+}
+
+/// inherited_name returns the name an Inherited attribute is listed under in
+/// SCOPES, None for attributes of other scopes. The direction attributes all
+/// go by Direction::NAME.
+pub(crate) fn inherited_name(attribute: &Attribute) -> Option<&str> {
+    let name = if Direction::matches(attribute) {
+        Direction::NAME
+    } else {
+        &attribute.key
+    };
+    (AttributeScope::of(name) == AttributeScope::Inherited).then_some(name)
+}
+
+/// first_of_modifier returns the "b=" fields in effect, the first one of
+/// each modifier.
+fn first_of_modifier(bandwidth: &[Bandwidth]) -> impl Iterator<Item = &Bandwidth> {
+    bandwidth.iter().enumerate().filter_map(move |(index, b)| {
+        let first = bandwidth
+            .iter()
+            .position(|other| other.has_same_modifier(b));
+        (first == Some(index)).then_some(b)
+    })
+}
+
+impl<'a> ResolvedMedia<'a> {
+    /// index returns the index of the media description.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// media returns the media description itself.
+    pub fn media(&self) -> &'a MediaDescription {
+        self.media
+    }
+
+    /// connection returns the "c=" field in effect: the media level one, or
+    /// the session level one.
+    /// <https://tools.ietf.org/html/rfc8866#section-5.7>
+    pub fn connection(&self) -> Option<&'a ConnectionInformation> {
+        self.media
+            .connection_information
+            .as_ref()
+            .or(self.session.connection_information.as_ref())
+    }
+
+    /// direction returns the direction in effect: the media level direction
+    /// attribute, or the session level one, or sendrecv if there is none.
+    /// <https://tools.ietf.org/html/rfc8866#section-6.7>
+    pub fn direction(&self) -> Direction {
+        self.media
+            .direction()
+            .or_else(|| self.session.attributes.direction())
+            .unwrap_or_default()
+    }
+
+    /// bandwidth returns the value of the "b=" field with the given modifier
+    /// in effect: the media level one, or the session level one.
+    /// <https://tools.ietf.org/html/rfc8866#section-5.8>
+    pub fn bandwidth(&self, kind: BandwidthKind) -> Option<u64> {
+        self.media.bandwidth(kind).or_else(|| {
+            self.session
+                .bandwidth
+                .iter()
+                .find(|b| b.kind() == Some(kind))
+                .map(|b| b.bandwidth)
+        })
+    }
+
+    /// key returns the "k=" field in effect: the media level one, or the
+    /// session level one.
+    /// <https://tools.ietf.org/html/rfc4566#section-5.12>
+    pub fn key(&self) -> Option<&'a EncryptionKey> {
+        self.media
+            .encryption_key
+            .as_ref()
+            .or(self.session.encryption_key.as_ref())
+    }
+
+    /// fingerprint returns the first well-formed "a=fingerprint:" in effect.
+    /// Use attributes to get all of them.
+    /// <https://tools.ietf.org/html/rfc8122#section-5>
+    pub fn fingerprint(&self) -> Option<Fingerprint> {
+        self.attribute()
+    }
+
+    /// ice_credentials returns the ICE credentials in effect, see
+    /// SessionDescription::ice_credentials_for.
+    pub fn ice_credentials(&self) -> Option<(IceUfrag, IcePwd)> {
+        self.session.ice_credentials_for(self.index)
+    }
+
+    /// attribute returns the first well-formed attribute of type T in effect,
+    /// see attributes.
+    pub fn attribute<T: TypedAttribute>(&self) -> Option<T> {
+        self.level::<T>().get::<T>().find_map(Result::ok)
+    }
+
+    /// attributes returns the well-formed attributes of type T in effect,
+    /// following the AttributeScope of T: the media level ones if T is
+    /// MediaOnly, the session level ones if T is SessionOnly, and if T is
+    /// Inherited the media level ones or, if there are none, the session
+    /// level ones. The levels are not merged, so media level attributes
+    /// override all session level ones of the same type.
+    pub fn attributes<T: TypedAttribute>(&self) -> Vec<T> {
+        self.level::<T>()
+            .get::<T>()
+            .filter_map(Result::ok)
+            .collect()
+    }
+
+    /// level returns the attributes which T is taken from.
+    fn level<T: TypedAttribute>(&self) -> &'a Attributes {
+        match AttributeScope::of(T::NAME) {
+            AttributeScope::MediaOnly => &self.media.attributes,
+            AttributeScope::SessionOnly => &self.session.attributes,
+            AttributeScope::Inherited => {
+                if self.media.attributes.iter().any(T::matches) {
+                    &self.media.attributes
+                } else {
+                    &self.session.attributes
+                }
+            }
+        }
+    }
+}
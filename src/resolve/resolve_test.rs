@@ -0,0 +1,366 @@
+use super::*;
+use crate::error::Error;
+
+const SDP: &str = "v=0\r\n\
+o=- 4611731400430051336 2 IN IP4 192.168.1.20\r\n\
+s=-\r\n\
+c=IN IP4 192.168.1.20\r\n\
+b=AS:256\r\n\
+t=0 0\r\n\
+k=prompt\r\n\
+a=group:BUNDLE 0 1\r\n\
+a=recvonly\r\n\
+a=ice-ufrag:sess\r\n\
+a=ice-pwd:sessionpasswordsessionpassword\r\n\
+a=fingerprint:sha-1 4A:AD:B9:B1:3F:82:18:3B:54:02:12:DF:3E:5D:49:6B:19:E5:7C:AB\r\n\
+a=setup:actpass\r\n\
+a=extmap-allow-mixed\r\n\
+a=mid:bogus\r\n\
+a=candidate:1 1 udp 2122260223 192.0.2.1 9 typ host\r\n\
+m=audio 50000 RTP/AVP 0\r\n\
+a=mid:0\r\n\
+a=rtpmap:0 PCMU/8000\r\n\
+m=video 50002 RTP/AVP 96\r\n\
+c=IN IP4 203.0.113.1\r\n\
+b=AS:128\r\n\
+k=clear:abc\r\n\
+a=mid:1\r\n\
+a=sendonly\r\n\
+a=ice-ufrag:medi\r\n\
+a=ice-pwd:mediapasswordmediapassword\r\n\
+a=fingerprint:sha-1 00:AD:B9:B1:3F:82:18:3B:54:02:12:DF:3E:5D:49:6B:19:E5:7C:AB\r\n\
+a=setup:active\r\n\
+a=candidate:2 1 udp 2122260223 203.0.113.1 50002 typ host\r\n\
+a=rtpmap:96 VP8/90000\r\n";
+
+#[test]
+fn test_inherit_from_session() -> Result<(), Error> {
+    let sd = SDP.parse::<SessionDescription>()?;
+    let media = sd.resolve_media(0).expect("media 0");
+
+    assert_eq!(media.index(), 0);
+    assert_eq!(
+        media.connection().map(ToString::to_string).as_deref(),
+        Some("IN IP4 192.168.1.20")
+    );
+    assert_eq!(media.direction(), Direction::RecvOnly);
+    assert_eq!(media.bandwidth(BandwidthKind::As), Some(256));
+    assert_eq!(media.bandwidth(BandwidthKind::Ct), None);
+    assert_eq!(media.key(), Some(&EncryptionKey::Prompt));
+    assert_eq!(
+        media.fingerprint().map(|f| f.to_string()),
+        Some("sha-1 4A:AD:B9:B1:3F:82:18:3B:54:02:12:DF:3E:5D:49:6B:19:E5:7C:AB".to_owned())
+    );
+    let (ufrag, _) = media.ice_credentials().expect("ice credentials");
+    assert_eq!(ufrag.as_str(), "sess");
+    assert_eq!(media.attribute::<Setup>(), Some(Setup::ActPass));
+    assert_eq!(
+        media.attribute::<ExtmapAllowMixed>(),
+        Some(ExtmapAllowMixed)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_override_at_media() -> Result<(), Error> {
+    let sd = SDP.parse::<SessionDescription>()?;
+    let media = sd.resolve_media(1).expect("media 1");
+
+    assert_eq!(
+        media.connection().map(ToString::to_string).as_deref(),
+        Some("IN IP4 203.0.113.1")
+    );
+    assert_eq!(media.direction(), Direction::SendOnly);
+    assert_eq!(media.bandwidth(BandwidthKind::As), Some(128));
+    assert_eq!(media.key(), Some(&EncryptionKey::Clear("abc".to_owned())));
+    assert_eq!(
+        media.fingerprint().map(|f| f.to_string()),
+        Some("sha-1 00:AD:B9:B1:3F:82:18:3B:54:02:12:DF:3E:5D:49:6B:19:E5:7C:AB".to_owned())
+    );
+    let (ufrag, _) = media.ice_credentials().expect("ice credentials");
+    assert_eq!(ufrag.as_str(), "medi");
+    assert_eq!(media.attribute::<Setup>(), Some(Setup::Active));
+    assert_eq!(media.attributes::<Setup>(), vec![Setup::Active]);
+
+    Ok(())
+}
+
+#[test]
+fn test_not_inherited() -> Result<(), Error> {
+    let sd = SDP.parse::<SessionDescription>()?;
+    let resolved = sd.resolved_media().collect::<Vec<_>>();
+    assert_eq!(resolved.len(), 2);
+
+    // Media level attributes misplaced at session level are ignored.
+    assert_eq!(
+        resolved[0].attribute::<Mid>().as_ref().map(Mid::as_str),
+        Some("0")
+    );
+    assert!(resolved[0].attributes::<Candidate>().is_empty());
+    assert_eq!(resolved[1].attributes::<Candidate>().len(), 1);
+    assert_eq!(resolved[1].attributes::<RtpMap>().len(), 1);
+
+    // Session level attributes are taken from the session level only.
+    let groups = resolved[1].attributes::<Group>();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].mids.len(), 2);
+
+    assert!(sd.resolve_media(2).is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_attribute_scope() {
+    assert_eq!(AttributeScope::of("mid"), AttributeScope::MediaOnly);
+    assert_eq!(AttributeScope::of("candidate"), AttributeScope::MediaOnly);
+    assert_eq!(AttributeScope::of("group"), AttributeScope::SessionOnly);
+    assert_eq!(AttributeScope::of("fingerprint"), AttributeScope::Inherited);
+    assert_eq!(AttributeScope::of("direction"), AttributeScope::Inherited);
+    assert_eq!(AttributeScope::of("x-unknown"), AttributeScope::MediaOnly);
+}
+
+#[test]
+fn test_default_direction() -> Result<(), Error> {
+    let sd = "v=0\r\n\
+              o=- 0 0 IN IP4 127.0.0.1\r\n\
+              s=-\r\n\
+              t=0 0\r\n\
+              m=audio 9 RTP/AVP 0\r\n"
+        .parse::<SessionDescription>()?;
+    let media = sd.resolve_media(0).expect("media 0");
+    assert_eq!(media.direction(), Direction::SendRecv);
+    assert_eq!(media.connection(), None);
+    assert_eq!(media.key(), None);
+    assert_eq!(media.ice_credentials(), None);
+    Ok(())
+}
+
+const BUILT_SDP: &str = "v=0\r\n\
+o=- 0 0 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+b=AS:512\r\n\
+b=CT:1000\r\n\
+t=0 0\r\n\
+m=audio 9 RTP/AVP 0\r\n\
+c=IN IP4 198.51.100.7\r\n\
+b=AS:512\r\n\
+m=video 9 RTP/AVP 96\r\n\
+c=IN IP4 198.51.100.7\r\n\
+b=AS:512\r\n\
+b=TIAS:400000\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+m=application 9 UDP/DTLS/SCTP webrtc-datachannel\r\n\
+c=IN IP4 198.51.100.7\r\n\
+b=AS:30\r\n";
+
+const MIXED_SDP: &str = "v=0\r\n\
+o=- 0 0 IN IP6 ::1\r\n\
+s=-\r\n\
+c=IN IP6 2001:db8::1\r\n\
+b=AS:256\r\n\
+b=X-YZ:1\r\n\
+t=0 0\r\n\
+m=audio 9 RTP/AVP 0\r\n\
+c=IN IP6 2001:db8::1\r\n\
+b=AS:256\r\n\
+b=AS:128\r\n\
+m=audio 0 RTP/AVP 0\r\n\
+m=video 9 RTP/AVP 96\r\n\
+c=IN IP6 2001:db8::2\r\n\
+b=X-YZ:2\r\n\
+a=rtpmap:96 VP8/90000\r\n";
+
+const NO_MEDIA_SDP: &str = "v=0\r\n\
+o=- 0 0 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+c=IN IP4 192.0.2.1\r\n\
+b=AS:64\r\n\
+t=0 0\r\n";
+
+// resolved returns the connection and bandwidths in effect for every media
+// description.
+fn resolved(sd: &SessionDescription) -> Vec<(Option<ConnectionInformation>, Vec<Option<u64>>)> {
+    sd.resolved_media()
+        .map(|media| {
+            let bandwidth = BandwidthKind::ALL
+                .iter()
+                .map(|&kind| media.bandwidth(kind))
+                .collect();
+            (media.connection().cloned(), bandwidth)
+        })
+        .collect()
+}
+
+#[test]
+fn test_hoist_and_push_down_keep_resolved() -> Result<(), Error> {
+    for input in [SDP, BUILT_SDP, MIXED_SDP, NO_MEDIA_SDP] {
+        let sd = input.parse::<SessionDescription>()?;
+        let expected = resolved(&sd);
+
+        let mut hoisted = sd.clone();
+        hoisted.hoist_common_fields();
+        assert_eq!(resolved(&hoisted), expected, "hoisted {}", input);
+        assert!(hoisted.serialized_len() <= sd.serialized_len());
+
+        let mut pushed = sd.clone();
+        pushed.push_down_fields();
+        assert_eq!(resolved(&pushed), expected, "pushed down {}", input);
+
+        // Either pass undoes the other as far as ResolvedMedia can tell, and
+        // running one twice changes nothing more.
+        let mut round_trip = hoisted.clone();
+        round_trip.push_down_fields();
+        assert_eq!(resolved(&round_trip), expected, "{}", input);
+        round_trip.hoist_common_fields();
+        assert_eq!(resolved(&round_trip), expected, "{}", input);
+        let mut again = hoisted.clone();
+        again.hoist_common_fields();
+        assert_eq!(again, hoisted, "{}", input);
+        let mut again = pushed.clone();
+        again.push_down_fields();
+        assert_eq!(again, pushed, "{}", input);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_hoist_common_fields() -> Result<(), Error> {
+    let mut sd = BUILT_SDP.parse::<SessionDescription>()?;
+    sd.hoist_common_fields();
+    assert_eq!(
+        sd.marshal(),
+        "v=0\r\n\
+o=- 0 0 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+c=IN IP4 198.51.100.7\r\n\
+b=AS:512\r\n\
+b=CT:1000\r\n\
+t=0 0\r\n\
+m=audio 9 RTP/AVP 0\r\n\
+m=video 9 RTP/AVP 96\r\n\
+b=TIAS:400000\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+m=application 9 UDP/DTLS/SCTP webrtc-datachannel\r\n\
+b=AS:30\r\n"
+    );
+
+    // A "c=" missing from one media description is not hoisted, but the
+    // ones equal to the session level one are dropped.
+    let mut sd = MIXED_SDP.parse::<SessionDescription>()?;
+    sd.hoist_common_fields();
+    let connections: Vec<_> = sd
+        .media_descriptions
+        .iter()
+        .map(|md| md.connection_information.as_ref().map(ToString::to_string))
+        .collect();
+    assert_eq!(
+        connections,
+        vec![None, None, Some("IN IP6 2001:db8::2".to_owned())]
+    );
+    // The duplicate "b=AS" is dropped along with the one in effect.
+    assert!(sd.media_descriptions[0].bandwidth.is_empty());
+    assert_eq!(sd.media_descriptions[2].bandwidth.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_push_down_fields() -> Result<(), Error> {
+    let mut sd = SDP.parse::<SessionDescription>()?;
+    sd.push_down_fields();
+    assert_eq!(sd.connection_information, None);
+    assert!(sd.bandwidth.is_empty());
+
+    let audio = &sd.media_descriptions[0];
+    assert_eq!(
+        audio
+            .connection_information
+            .as_ref()
+            .map(ToString::to_string),
+        Some("IN IP4 192.168.1.20".to_owned())
+    );
+    assert_eq!(audio.bandwidth(BandwidthKind::As), Some(256));
+    // Media level fields are kept.
+    let video = &sd.media_descriptions[1];
+    assert_eq!(
+        video
+            .connection_information
+            .as_ref()
+            .map(ToString::to_string),
+        Some("IN IP4 203.0.113.1".to_owned())
+    );
+    assert_eq!(video.bandwidth.len(), 1);
+    assert_eq!(video.bandwidth(BandwidthKind::As), Some(128));
+
+    // Without media descriptions the session level fields stay.
+    let mut sd = NO_MEDIA_SDP.parse::<SessionDescription>()?;
+    sd.push_down_fields();
+    assert_eq!(sd.marshal(), NO_MEDIA_SDP);
+
+    Ok(())
+}
+
+#[test]
+fn test_push_down_attributes() -> Result<(), Error> {
+    let original = SDP.parse::<SessionDescription>()?;
+    let mut sd = original.clone();
+    sd.push_down_attributes();
+
+    // Only the attributes which aren't inherited stay at session level.
+    let session: Vec<_> = sd.attributes.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        session,
+        vec![
+            "group:BUNDLE 0 1",
+            "mid:bogus",
+            "candidate:1 1 udp 2122260223 192.0.2.1 9 typ host",
+        ]
+    );
+    let audio: Vec<_> = sd.media_descriptions[0]
+        .attributes
+        .iter()
+        .map(|a| a.key.to_string())
+        .collect();
+    assert_eq!(
+        audio,
+        vec![
+            "mid",
+            "rtpmap",
+            "recvonly",
+            "ice-ufrag",
+            "ice-pwd",
+            "fingerprint",
+            "setup",
+            "extmap-allow-mixed",
+        ]
+    );
+    // The video media description overrides all but one of them.
+    let video = &sd.media_descriptions[1];
+    assert_eq!(video.attributes.len(), 9);
+    assert!(video.attributes.has(ExtmapAllowMixed::NAME));
+
+    for (before, after) in original.resolved_media().zip(sd.resolved_media()) {
+        assert_eq!(before.direction(), after.direction());
+        assert_eq!(before.ice_credentials(), after.ice_credentials());
+        assert_eq!(before.fingerprint(), after.fingerprint());
+        assert_eq!(before.attribute::<Setup>(), after.attribute::<Setup>());
+        assert_eq!(
+            before.attributes::<ExtmapAllowMixed>(),
+            after.attributes::<ExtmapAllowMixed>()
+        );
+        assert_eq!(
+            before.attributes::<Candidate>(),
+            after.attributes::<Candidate>()
+        );
+    }
+
+    // Without media descriptions the session level attributes stay.
+    let mut sd = NO_MEDIA_SDP.parse::<SessionDescription>()?;
+    sd.push_down_attributes();
+    assert_eq!(sd.marshal(), NO_MEDIA_SDP);
+
+    Ok(())
+}
@@ -0,0 +1,208 @@
+use core::net::IpAddr;
+
+use super::alloc_prelude::*;
+use super::candidate::{Candidate, CandidateAddress, RemoteCandidates};
+use super::common_description::{
+    Address, Attributes, ConnectionInformation, NetType, TypedAttribute, ADDRESS_TYPE_IP4,
+    ADDRESS_TYPE_IP6,
+};
+use super::rtcp::Rtcp;
+use super::session_description::SessionDescription;
+
+#[cfg(test)]
+mod rewrite_test;
+
+impl SessionDescription {
+    /// rewrite_connection_addresses visits every address of the description:
+    /// the "o=" unicast address, the session and media "c=" fields, the
+    /// "a=rtcp:" addresses, the addresses and related addresses of
+    /// "a=candidate:" and the addresses of "a=remote-candidates:". Each is
+    /// passed as connection data, and replaced with
+    /// what f returns, if anything. The address type follows the new address
+    /// when it is an IP literal. Malformed attributes are left alone.
+    pub fn rewrite_connection_addresses<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&ConnectionInformation) -> Option<ConnectionInformation>,
+    {
+        let origin = &mut self.origin;
+        let view = ConnectionInformation {
+            network_type: origin.network_type.clone(),
+            address_type: origin.address_type.clone(),
+            address: Some(Address {
+                address: origin.unicast_address.clone(),
+                ttl: None,
+                range: None,
+            }),
+        };
+        if let Some(new) = rewrite(&view, &mut f) {
+            origin.network_type = new.network_type;
+            origin.address_type = new.address_type;
+            if let Some(address) = new.address {
+                origin.unicast_address = address.address;
+            }
+        }
+
+        rewrite_connection_information(&mut self.connection_information, &mut f);
+        rewrite_attributes(&mut self.attributes, &mut f);
+        for md in &mut self.media_descriptions {
+            rewrite_connection_information(&mut md.connection_information, &mut f);
+            rewrite_attributes(&mut md.attributes, &mut f);
+        }
+    }
+
+    /// set_all_connection_addresses replaces every address visited by
+    /// rewrite_connection_addresses with addr. Multicast TTLs and ranges are
+    /// only kept if addr is a multicast address too.
+    pub fn set_all_connection_addresses(&mut self, addr: IpAddr) {
+        self.rewrite_connection_addresses(|connection_information| {
+            let address = connection_information.address.as_ref()?;
+            let multicast = addr.is_multicast();
+            Some(ConnectionInformation {
+                network_type: NetType::In,
+                address_type: connection_information.address_type.clone(),
+                address: Some(Address {
+                    address: addr.to_string(),
+                    ttl: address.ttl.filter(|_| multicast),
+                    range: address.range.filter(|_| multicast),
+                }),
+            })
+        });
+    }
+
+    /// has_private_addresses reports whether any address visited by
+    /// rewrite_connection_addresses is a private IPv4 address, an IPv6
+    /// unique local address, or a link-local address.
+    /// <https://tools.ietf.org/html/rfc1918#section-3>
+    /// <https://tools.ietf.org/html/rfc4193#section-3>
+    /// <https://tools.ietf.org/html/rfc3927#section-2.1>
+    /// <https://tools.ietf.org/html/rfc4291#section-2.5.6>
+    pub fn has_private_addresses(&self) -> bool {
+        let mut found = false;
+        // Visiting a copy keeps the walk in one place; nothing is rewritten.
+        self.clone()
+            .rewrite_connection_addresses(|connection_information| {
+                found |= connection_information
+                    .address
+                    .as_ref()
+                    .and_then(|a| a.address.parse::<IpAddr>().ok())
+                    .is_some_and(is_private);
+                None
+            });
+        found
+    }
+}
+
+fn is_private(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => ip.is_private() || ip.is_link_local(),
+        IpAddr::V6(ip) => {
+            let first = ip.segments()[0];
+            // fc00::/7 and fe80::/10
+            first & 0xfe00 == 0xfc00 || first & 0xffc0 == 0xfe80
+        }
+    }
+}
+
+/// rewrite calls f and makes the address type of the result follow its
+/// address.
+fn rewrite<F>(
+    connection_information: &ConnectionInformation,
+    f: &mut F,
+) -> Option<ConnectionInformation>
+where
+    F: FnMut(&ConnectionInformation) -> Option<ConnectionInformation>,
+{
+    let mut new = f(connection_information)?;
+    if new.network_type == NetType::In {
+        match new.address.as_ref().map(|a| a.address.parse::<IpAddr>()) {
+            Some(Ok(IpAddr::V4(_))) => new.address_type = ADDRESS_TYPE_IP4.to_owned(),
+            Some(Ok(IpAddr::V6(_))) => new.address_type = ADDRESS_TYPE_IP6.to_owned(),
+            _ => {}
+        }
+    }
+    Some(new)
+}
+
+fn rewrite_connection_information<F>(
+    connection_information: &mut Option<ConnectionInformation>,
+    f: &mut F,
+) where
+    F: FnMut(&ConnectionInformation) -> Option<ConnectionInformation>,
+{
+    if let Some(current) = connection_information {
+        if let Some(new) = rewrite(current, f) {
+            *current = new;
+        }
+    }
+}
+
+fn rewrite_attributes<F>(attributes: &mut Attributes, f: &mut F)
+where
+    F: FnMut(&ConnectionInformation) -> Option<ConnectionInformation>,
+{
+    for attribute in &mut attributes.0 {
+        if Rtcp::matches(attribute) {
+            if let Ok(mut rtcp) = Rtcp::from_attribute(attribute) {
+                if let Some(current) = &rtcp.connection_information {
+                    if let Some(new) = rewrite(current, f) {
+                        rtcp.connection_information = Some(new);
+                        *attribute = rtcp.to_attribute();
+                    }
+                }
+            }
+        } else if Candidate::matches(attribute) {
+            if let Ok(mut candidate) = Candidate::from_attribute(attribute) {
+                let mut changed = rewrite_candidate_address(&mut candidate.address, f);
+                if let Some(related_address) = &mut candidate.related_address {
+                    changed |= rewrite_candidate_address(related_address, f);
+                }
+                if changed {
+                    *attribute = candidate.to_attribute();
+                }
+            }
+        } else if RemoteCandidates::matches(attribute) {
+            if let Ok(mut remote_candidates) = RemoteCandidates::from_attribute(attribute) {
+                let mut changed = false;
+                for remote_candidate in &mut remote_candidates.candidates {
+                    changed |= rewrite_candidate_address(&mut remote_candidate.address, f);
+                }
+                if changed {
+                    *attribute = remote_candidates.to_attribute();
+                }
+            }
+        }
+    }
+}
+
+/// rewrite_candidate_address passes a candidate address as "IN IP4" or
+/// "IN IP6" connection data. Domain names are passed as IP4, the address
+/// type being irrelevant to candidates. Results which are no valid
+/// candidate address are ignored.
+fn rewrite_candidate_address<F>(address: &mut CandidateAddress, f: &mut F) -> bool
+where
+    F: FnMut(&ConnectionInformation) -> Option<ConnectionInformation>,
+{
+    let address_type = match address {
+        CandidateAddress::Ip(IpAddr::V6(_)) => ADDRESS_TYPE_IP6,
+        _ => ADDRESS_TYPE_IP4,
+    };
+    let view = ConnectionInformation {
+        network_type: NetType::In,
+        address_type: address_type.to_owned(),
+        address: Some(Address {
+            address: address.to_string(),
+            ttl: None,
+            range: None,
+        }),
+    };
+    let new = rewrite(&view, f)
+        .and_then(|new| new.address)
+        .and_then(|new| new.address.parse::<CandidateAddress>().ok());
+    match new {
+        Some(new) => {
+            *address = new;
+            true
+        }
+        None => false,
+    }
+}
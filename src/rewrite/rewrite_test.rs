@@ -0,0 +1,123 @@
+use super::*;
+use crate::common_description::Attribute;
+use crate::error::Error;
+
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+const OFFER_SDP: &str = "v=0\r\n\
+o=- 4611731400430051336 2 IN IP4 192.168.1.20\r\n\
+s=-\r\n\
+c=IN IP4 192.168.1.20\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0 1\r\n\
+m=audio 50000 UDP/TLS/RTP/SAVPF 111\r\n\
+c=IN IP4 192.168.1.20\r\n\
+a=rtcp:50001 IN IP4 192.168.1.20\r\n\
+a=candidate:1 1 udp 2122260223 192.168.1.20 50000 typ host generation 0\r\n\
+a=candidate:2 1 udp 1686052607 203.0.113.7 50000 typ srflx raddr 192.168.1.20 rport 50000\r\n\
+a=candidate:3 1 udp 2122260223 4c0ff2bd-3e60-4d9a-8d2e-3d5a2a6c1e8b.local 50002 typ host\r\n\
+a=remote-candidates:1 198.51.100.4 50000\r\n\
+a=mid:0\r\n\
+a=rtpmap:111 opus/48000/2\r\n\
+m=video 50004 UDP/TLS/RTP/SAVPF 96\r\n\
+c=IN IP4 224.2.1.1/127\r\n\
+a=rtcp:50005 IN IP4 192.168.1.20\r\n\
+a=mid:1\r\n\
+a=rtpmap:96 VP8/90000\r\n";
+
+#[test]
+fn test_set_all_connection_addresses() -> Result<(), Error> {
+    let mut sd = OFFER_SDP.parse::<SessionDescription>()?;
+    sd.set_all_connection_addresses(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)));
+
+    let marshaled = sd.marshal();
+    assert!(!marshaled.contains("IP4"), "{}", marshaled);
+    assert!(!marshaled.contains("192.168.1.20"), "{}", marshaled);
+    assert!(!marshaled.contains("203.0.113.7"), "{}", marshaled);
+    assert!(!marshaled.contains(".local"), "{}", marshaled);
+    assert!(!marshaled.contains("198.51.100.4"), "{}", marshaled);
+    assert!(marshaled.contains("o=- 4611731400430051336 2 IN IP6 2001:db8::1\r\n"));
+    assert!(marshaled.contains("a=rtcp:50001 IN IP6 2001:db8::1\r\n"));
+    assert!(marshaled.contains(
+        "a=candidate:2 1 udp 1686052607 2001:db8::1 50000 typ srflx raddr 2001:db8::1 rport 50000\r\n"
+    ));
+    // The TTL only applies to IPv4 multicast.
+    assert_eq!(
+        sd.media_descriptions[1]
+            .connection_information
+            .as_ref()
+            .map(|c| c.to_string()),
+        Some("IN IP6 2001:db8::1".to_owned())
+    );
+
+    let reparsed = marshaled.parse::<SessionDescription>()?;
+    assert_eq!(reparsed, sd);
+    assert!(!reparsed.has_private_addresses());
+
+    Ok(())
+}
+
+#[test]
+fn test_rewrite_connection_addresses() -> Result<(), Error> {
+    let mut sd = OFFER_SDP.parse::<SessionDescription>()?;
+    assert!(sd.has_private_addresses());
+
+    // Replace the private address only, the way a relay would.
+    let mut visited = 0;
+    sd.rewrite_connection_addresses(|connection_information| {
+        visited += 1;
+        let address = connection_information.address.as_ref()?;
+        if address.address != "192.168.1.20" {
+            return None;
+        }
+        Some(ConnectionInformation {
+            address: Some(Address {
+                address: "198.51.100.1".to_owned(),
+                ..address.clone()
+            }),
+            ..connection_information.clone()
+        })
+    });
+    // o=, 3 c=, 2 a=rtcp, 3 candidate addresses, 1 raddr and 1 remote
+    // candidate
+    assert_eq!(visited, 11);
+    assert!(!sd.has_private_addresses());
+
+    let marshaled = sd.marshal();
+    assert!(!marshaled.contains("192.168.1.20"), "{}", marshaled);
+    assert!(marshaled.contains("c=IN IP4 224.2.1.1/127\r\n"));
+    assert!(marshaled.contains("typ srflx raddr 198.51.100.1 rport 50000"));
+
+    Ok(())
+}
+
+#[test]
+fn test_has_private_addresses() -> Result<(), Error> {
+    let tests = [
+        (IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3)), true),
+        (IpAddr::V4(Ipv4Addr::new(172, 16, 0, 1)), true),
+        (IpAddr::V4(Ipv4Addr::new(172, 32, 0, 1)), false),
+        (IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1)), true),
+        (IpAddr::V4(Ipv4Addr::new(169, 254, 1, 1)), true),
+        (IpAddr::V4(Ipv4Addr::new(203, 0, 113, 1)), false),
+        (IpAddr::V6(Ipv6Addr::new(0xfd12, 0, 0, 0, 0, 0, 0, 1)), true),
+        (IpAddr::V6(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1)), true),
+        (
+            IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)),
+            false,
+        ),
+    ];
+
+    for (addr, private) in tests.iter() {
+        let mut sd = OFFER_SDP.parse::<SessionDescription>()?;
+        sd.set_all_connection_addresses(IpAddr::V4(Ipv4Addr::new(203, 0, 113, 2)));
+        assert!(!sd.has_private_addresses());
+        sd.media_descriptions[0].attributes.push(Attribute::new(
+            "candidate".to_owned(),
+            Some(format!("4 1 udp 2122260223 {} 9 typ host", addr)),
+        ));
+        assert_eq!(sd.has_private_addresses(), *private, "{}", addr);
+    }
+
+    Ok(())
+}
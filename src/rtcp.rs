@@ -0,0 +1,124 @@
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::{Attribute, ConnectionInformation, TypedAttribute};
+use super::error::Error;
+
+#[cfg(test)]
+mod rtcp_test;
+
+/// Rtcp is the "a=rtcp:" attribute, giving the RTCP port and optionally the
+/// address when they don't follow from the RTP ones.
+/// `a=rtcp:<port> [<nettype> <addrtype> <connection-address>]`
+/// <https://tools.ietf.org/html/rfc3605#section-2.1>
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rtcp {
+    /// port is the RTCP port.
+    pub port: u16,
+    /// connection_information is the address of the RTCP stream, if it differs
+    /// from the "c=" of the media description.
+    pub connection_information: Option<ConnectionInformation>,
+}
+
+impl fmt::Display for Rtcp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.port)?;
+        if let Some(connection_information) = &self.connection_information {
+            write!(f, " {}", connection_information)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Rtcp {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::SdpInvalidRtcp(value.to_owned());
+
+        let (port, connection_information) = match value.split_once(' ') {
+            Some((port, connection_information)) => (port, Some(connection_information)),
+            None => (value, None),
+        };
+        if port.is_empty() || !port.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let port = port.parse().map_err(|_| invalid())?;
+
+        // Unlike "c=", the address is mandatory here.
+        let connection_information = match connection_information {
+            Some(connection_information) => {
+                let connection_information = connection_information
+                    .parse::<ConnectionInformation>()
+                    .map_err(|_| invalid())?;
+                if connection_information.address.is_none() {
+                    return Err(invalid());
+                }
+                Some(connection_information)
+            }
+            None => None,
+        };
+
+        Ok(Rtcp {
+            port,
+            connection_information,
+        })
+    }
+}
+
+impl TypedAttribute for Rtcp {
+    const NAME: &'static str = "rtcp";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("RTCP port {}", self.port))
+    }
+}
+
+// impl_flag_attribute implements a value-less "a=<name>" attribute.
+macro_rules! impl_flag_attribute {
+    ($ty:ident, $name:expr) => {
+        impl fmt::Display for $ty {
+            fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+                Ok(())
+            }
+        }
+
+        impl FromStr for $ty {
+            type Err = Error;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                if !value.is_empty() {
+                    return Err(Error::SdpInvalidValue(value.to_owned()));
+                }
+                Ok($ty)
+            }
+        }
+
+        impl TypedAttribute for $ty {
+            const NAME: &'static str = $name;
+
+            fn to_attribute(&self) -> Attribute {
+                Attribute::new(Self::NAME.to_owned(), None)
+            }
+        }
+    };
+}
+
+/// RtcpMux is the "a=rtcp-mux" attribute, multiplexing RTP and RTCP on the
+/// RTP port.
+/// <https://tools.ietf.org/html/rfc5761#section-5.1.1>
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RtcpMux;
+
+/// RtcpMuxOnly is the "a=rtcp-mux-only" attribute, telling that the offerer
+/// will not fall back to a separate RTCP port. It must come with
+/// "a=rtcp-mux".
+/// <https://tools.ietf.org/html/rfc8858#section-4>
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RtcpMuxOnly;
+
+pub(crate) use impl_flag_attribute;
+
+impl_flag_attribute!(RtcpMux, "rtcp-mux");
+impl_flag_attribute!(RtcpMuxOnly, "rtcp-mux-only");
@@ -0,0 +1,69 @@
+use super::*;
+use crate::common_description::Attributes;
+
+#[test]
+fn test_rtcp() -> Result<(), Error> {
+    let tests = vec![
+        ("9 IN IP4 0.0.0.0", 9, Some("IN IP4 0.0.0.0")),
+        ("53020", 53020, None),
+        (
+            "53020 IN IP6 2001:db8::1",
+            53020,
+            Some("IN IP6 2001:db8::1"),
+        ),
+    ];
+
+    for (input, port, connection_information) in tests {
+        let rtcp = input.parse::<Rtcp>()?;
+        assert_eq!(rtcp.port, port);
+        assert_eq!(
+            rtcp.connection_information.map(|c| c.to_string()),
+            connection_information.map(str::to_owned)
+        );
+        assert_eq!(input.parse::<Rtcp>()?.to_string(), input);
+    }
+
+    for input in [
+        "",
+        "65536",
+        "-9",
+        "9 ",
+        "9 IN IP4",
+        "9 IN IP4 ::1",
+        "9 IN IP4 0.0.0.0 extra",
+    ] {
+        assert!(
+            matches!(input.parse::<Rtcp>(), Err(Error::SdpInvalidRtcp(_))),
+            "{:?}",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_rtcp_mux() -> Result<(), Error> {
+    let mut attributes = Attributes::default();
+    attributes.insert(RtcpMux);
+    attributes.insert(RtcpMuxOnly);
+
+    let keys: Vec<_> = attributes
+        .iter()
+        .map(|a| (a.key.as_str(), a.value.clone()))
+        .collect();
+    assert_eq!(keys, vec![("rtcp-mux", None), ("rtcp-mux-only", None)]);
+    assert_eq!(
+        attributes.get::<RtcpMux>().collect::<Result<Vec<_>, _>>()?,
+        vec![RtcpMux]
+    );
+    assert_eq!(
+        attributes
+            .get::<RtcpMuxOnly>()
+            .collect::<Result<Vec<_>, _>>()?,
+        vec![RtcpMuxOnly]
+    );
+    assert!("1".parse::<RtcpMux>().is_err());
+
+    Ok(())
+}
@@ -0,0 +1,191 @@
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::TypedAttribute;
+use super::error::Error;
+use super::rtpmap::MAX_PAYLOAD_TYPE;
+use super::util::token::is_token;
+
+#[cfg(test)]
+mod rtcpfb_test;
+
+/// PayloadTypeRef is the payload type an "a=rtcp-fb:" attribute applies to,
+/// either a single one or all of them with "*".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PayloadTypeRef {
+    /// All is "*", every payload type of the media description.
+    All,
+    /// Pt is a single payload type.
+    Pt(u8),
+}
+
+impl PayloadTypeRef {
+    /// applies_to reports whether the feedback applies to the payload type.
+    pub const fn applies_to(self, payload_type: u8) -> bool {
+        match self {
+            PayloadTypeRef::All => true,
+            PayloadTypeRef::Pt(pt) => pt == payload_type,
+        }
+    }
+}
+
+impl fmt::Display for PayloadTypeRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PayloadTypeRef::All => write!(f, "*"),
+            PayloadTypeRef::Pt(pt) => write!(f, "{}", pt),
+        }
+    }
+}
+
+impl FromStr for PayloadTypeRef {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value == "*" {
+            return Ok(PayloadTypeRef::All);
+        }
+        if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(Error::SdpInvalidRtcpFeedback(value.to_owned()));
+        }
+        match value.parse::<u8>() {
+            Ok(pt) if pt <= MAX_PAYLOAD_TYPE => Ok(PayloadTypeRef::Pt(pt)),
+            _ => Err(Error::SdpInvalidRtcpFeedback(value.to_owned())),
+        }
+    }
+}
+
+/// RtcpFeedbackType is the feedback message of an "a=rtcp-fb:" attribute.
+/// The ones used by WebRTC have their own variants, others are kept as
+/// Other with their parameter, if any.
+/// <https://tools.ietf.org/html/rfc4585#section-4.2>
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RtcpFeedbackType {
+    /// Generic NACK.
+    Nack,
+    /// Picture Loss Indication.
+    NackPli,
+    /// Full Intra Request.
+    /// <https://tools.ietf.org/html/rfc5104#section-7.1>
+    CcmFir,
+    /// Receiver Estimated Maximum Bitrate.
+    /// <https://tools.ietf.org/html/draft-alvestrand-rmcat-remb-03>
+    GoogRemb,
+    /// Transport wide congestion control.
+    /// <https://tools.ietf.org/html/draft-holmer-rmcat-transport-wide-cc-extensions-01>
+    TransportCc,
+    /// Other is any other feedback type, kept as written.
+    Other {
+        /// typ is the feedback type.
+        typ: String,
+        /// param is the feedback parameter, if given.
+        param: Option<String>,
+    },
+}
+
+impl fmt::Display for RtcpFeedbackType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RtcpFeedbackType::Nack => write!(f, "nack"),
+            RtcpFeedbackType::NackPli => write!(f, "nack pli"),
+            RtcpFeedbackType::CcmFir => write!(f, "ccm fir"),
+            RtcpFeedbackType::GoogRemb => write!(f, "goog-remb"),
+            RtcpFeedbackType::TransportCc => write!(f, "transport-cc"),
+            RtcpFeedbackType::Other { typ, param: None } => write!(f, "{}", typ),
+            RtcpFeedbackType::Other {
+                typ,
+                param: Some(param),
+            } => write!(f, "{} {}", typ, param),
+        }
+    }
+}
+
+impl FromStr for RtcpFeedbackType {
+    type Err = Error;
+
+    /// rtcp-fb-val = rtcp-fb-id [SP rtcp-fb-param]
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (typ, param) = match value.split_once(' ') {
+            Some((typ, param)) => (typ, Some(param)),
+            None => (value, None),
+        };
+        if !is_token(typ)
+            || param.is_some_and(|p| p.is_empty() || p.starts_with(' ') || p.ends_with(' '))
+        {
+            return Err(Error::SdpInvalidRtcpFeedback(value.to_owned()));
+        }
+
+        Ok(match (typ, param) {
+            ("nack", None) => RtcpFeedbackType::Nack,
+            ("nack", Some("pli")) => RtcpFeedbackType::NackPli,
+            ("ccm", Some("fir")) => RtcpFeedbackType::CcmFir,
+            ("goog-remb", None) => RtcpFeedbackType::GoogRemb,
+            ("transport-cc", None) => RtcpFeedbackType::TransportCc,
+            _ => RtcpFeedbackType::Other {
+                typ: typ.to_owned(),
+                param: param.map(str::to_owned),
+            },
+        })
+    }
+}
+
+/// RtcpFeedback is an "a=rtcp-fb:" attribute, announcing an RTCP feedback
+/// message for a payload type.
+/// `a=rtcp-fb:<payload type|*> <type> [<param>]`
+/// <https://tools.ietf.org/html/rfc4585#section-4.2>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::rtcpfb::{PayloadTypeRef, RtcpFeedback, RtcpFeedbackType};
+///
+/// # fn main() -> Result<(), Error> {
+/// let feedback = "96 nack pli".parse::<RtcpFeedback>()?;
+/// assert_eq!(feedback.payload_type, PayloadTypeRef::Pt(96));
+/// assert_eq!(feedback.feedback, RtcpFeedbackType::NackPli);
+/// assert!(feedback.payload_type.applies_to(96));
+/// assert!(PayloadTypeRef::All.applies_to(97));
+/// assert_eq!(feedback.to_string(), "96 nack pli");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RtcpFeedback {
+    /// payload_type tells which payload types the feedback is for.
+    pub payload_type: PayloadTypeRef,
+    /// feedback is the type of feedback.
+    pub feedback: RtcpFeedbackType,
+}
+
+impl fmt::Display for RtcpFeedback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.payload_type, self.feedback)
+    }
+}
+
+impl FromStr for RtcpFeedback {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::SdpInvalidRtcpFeedback(value.to_owned());
+
+        let (payload_type, feedback) = value.split_once(' ').ok_or_else(invalid)?;
+        Ok(RtcpFeedback {
+            payload_type: payload_type.parse().map_err(|_| invalid())?,
+            feedback: feedback.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl TypedAttribute for RtcpFeedback {
+    const NAME: &'static str = "rtcp-fb";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!(
+            "RTCP feedback for payload type {}",
+            self.payload_type
+        ))
+    }
+}
@@ -0,0 +1,82 @@
+use super::*;
+
+#[test]
+fn test_rtcp_feedback() -> Result<(), Error> {
+    let tests = vec![
+        ("96 nack", PayloadTypeRef::Pt(96), RtcpFeedbackType::Nack),
+        (
+            "96 nack pli",
+            PayloadTypeRef::Pt(96),
+            RtcpFeedbackType::NackPli,
+        ),
+        (
+            "96 ccm fir",
+            PayloadTypeRef::Pt(96),
+            RtcpFeedbackType::CcmFir,
+        ),
+        (
+            "0 goog-remb",
+            PayloadTypeRef::Pt(0),
+            RtcpFeedbackType::GoogRemb,
+        ),
+        (
+            "* transport-cc",
+            PayloadTypeRef::All,
+            RtcpFeedbackType::TransportCc,
+        ),
+        (
+            "127 ccm tmmbr smaxpr=120",
+            PayloadTypeRef::Pt(127),
+            RtcpFeedbackType::Other {
+                typ: "ccm".to_owned(),
+                param: Some("tmmbr smaxpr=120".to_owned()),
+            },
+        ),
+        (
+            "* trr-int",
+            PayloadTypeRef::All,
+            RtcpFeedbackType::Other {
+                typ: "trr-int".to_owned(),
+                param: None,
+            },
+        ),
+        (
+            "96 nack sli",
+            PayloadTypeRef::Pt(96),
+            RtcpFeedbackType::Other {
+                typ: "nack".to_owned(),
+                param: Some("sli".to_owned()),
+            },
+        ),
+    ];
+
+    for (input, payload_type, feedback) in tests {
+        let rtcp_feedback = input.parse::<RtcpFeedback>()?;
+        assert_eq!(rtcp_feedback.payload_type, payload_type, "{}", input);
+        assert_eq!(rtcp_feedback.feedback, feedback, "{}", input);
+        assert_eq!(rtcp_feedback.to_string(), input);
+    }
+
+    for input in [
+        "",
+        "96",
+        "96 ",
+        "128 nack",
+        "-1 nack",
+        "** nack",
+        "96  nack",
+        "96 nack ",
+        "96 na\"ck",
+    ] {
+        assert!(
+            matches!(
+                input.parse::<RtcpFeedback>(),
+                Err(Error::SdpInvalidRtcpFeedback(_))
+            ),
+            "{:?}",
+            input
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::TypedAttribute;
+use super::error::Error;
+use super::util::token::is_token;
+
+#[cfg(test)]
+mod rtpmap_test;
+
+/// Payload types above 127 would collide with the RTP marker bit.
+/// <https://tools.ietf.org/html/rfc3551#section-3>
+pub const MAX_PAYLOAD_TYPE: u8 = 127;
+
+/// RtpMap maps an RTP payload type to an encoding.
+/// `a=rtpmap:<payload type> <encoding name>/<clock rate>[/<encoding parameters>]`
+/// <https://tools.ietf.org/html/rfc4566#section-6>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::rtpmap::RtpMap;
+///
+/// # fn main() -> Result<(), Error> {
+/// let rtpmap = "111 opus/48000/2".parse::<RtpMap>()?;
+/// assert_eq!(rtpmap.payload_type, 111);
+/// assert!(rtpmap.is_encoding("OPUS"));
+/// assert_eq!(rtpmap.channels, Some(2));
+/// assert_eq!(rtpmap.to_string(), "111 opus/48000/2");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RtpMap {
+    /// payload_type is the RTP payload type.
+    pub payload_type: u8,
+    /// encoding_name is the name of the encoding, e.g. "opus".
+    pub encoding_name: String,
+    /// clock_rate is the RTP clock rate in Hz.
+    pub clock_rate: u32,
+    /// channels is the number of audio channels, if given.
+    pub channels: Option<u8>,
+}
+
+impl RtpMap {
+    /// is_encoding compares the encoding name case-insensitively, as MIME
+    /// subtypes are case-insensitive.
+    pub fn is_encoding(&self, name: &str) -> bool {
+        self.encoding_name.eq_ignore_ascii_case(name)
+    }
+}
+
+impl fmt::Display for RtpMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}/{}",
+            self.payload_type, self.encoding_name, self.clock_rate
+        )?;
+        if let Some(channels) = self.channels {
+            write!(f, "/{}", channels)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RtpMap {
+    type Err = Error;
+
+    /// from_str parses the value of an "a=rtpmap:" attribute, i.e. without
+    /// the "rtpmap:" prefix.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::SdpInvalidRtpMap(value.to_owned());
+
+        let (payload_type, encoding) = value.split_once(' ').ok_or_else(invalid)?;
+        let payload_type = payload_type.parse::<u8>().map_err(|_| invalid())?;
+        if payload_type > MAX_PAYLOAD_TYPE {
+            return Err(invalid());
+        }
+
+        let mut parts = encoding.split('/');
+        let encoding_name = parts.next().unwrap_or_default();
+        if !is_token(encoding_name) {
+            return Err(invalid());
+        }
+        let clock_rate = parts
+            .next()
+            .ok_or_else(invalid)?
+            .parse::<u32>()
+            .map_err(|_| invalid())?;
+        if clock_rate == 0 {
+            return Err(invalid());
+        }
+        let channels = match parts.next() {
+            Some(channels) => Some(channels.parse::<u8>().map_err(|_| invalid())?),
+            None => None,
+        };
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+
+        Ok(RtpMap {
+            payload_type,
+            encoding_name: encoding_name.to_owned(),
+            clock_rate,
+            channels,
+        })
+    }
+}
+
+impl TypedAttribute for RtpMap {
+    const NAME: &'static str = "rtpmap";
+
+    fn describe(&self) -> Option<String> {
+        let mut description = format!(
+            "payload type {}: {}, {} Hz",
+            self.payload_type, self.encoding_name, self.clock_rate
+        );
+        if let Some(channels) = self.channels {
+            description += &format!(", {} channels", channels);
+        }
+        Some(description)
+    }
+}
@@ -0,0 +1,57 @@
+use super::*;
+
+#[test]
+fn test_rtpmap_from_str() -> Result<(), Error> {
+    let passingtests = [
+        ("111 opus/48000/2", 111, "opus", 48000, Some(2)),
+        ("96 VP8/90000", 96, "VP8", 90000, None),
+        (
+            "126 telephone-event/8000",
+            126,
+            "telephone-event",
+            8000,
+            None,
+        ),
+    ];
+
+    for (input, payload_type, encoding_name, clock_rate, channels) in passingtests {
+        let rtpmap = input.parse::<RtpMap>()?;
+        assert_eq!(rtpmap.payload_type, payload_type, "{}", input);
+        assert_eq!(rtpmap.encoding_name, encoding_name, "{}", input);
+        assert_eq!(rtpmap.clock_rate, clock_rate, "{}", input);
+        assert_eq!(rtpmap.channels, channels, "{}", input);
+        assert_eq!(rtpmap.to_string(), input);
+    }
+
+    let failingtests = [
+        "128 opus/48000/2",
+        "300 opus/48000/2",
+        "111 opus",
+        "111 opus 48000",
+        "111 opus/0/2",
+        "111 /48000",
+        "111 opus/48000/2/1",
+        "opus/48000",
+    ];
+
+    for input in failingtests {
+        assert!(
+            matches!(input.parse::<RtpMap>(), Err(Error::SdpInvalidRtpMap(_))),
+            "expected `{}` to be rejected",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_rtpmap_is_encoding() -> Result<(), Error> {
+    let rtpmap = "96 VP8/90000".parse::<RtpMap>()?;
+    assert!(rtpmap.is_encoding("vp8"));
+    assert!(rtpmap.is_encoding("VP8"));
+    assert!(!rtpmap.is_encoding("VP9"));
+    assert_eq!(rtpmap.encoding_name, "VP8");
+
+    Ok(())
+}
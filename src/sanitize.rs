@@ -0,0 +1,428 @@
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use super::alloc_prelude::*;
+use super::candidate::{Candidate, CandidateAddress, RemoteCandidates};
+use super::common_description::{
+    Address, Attribute, Attributes, ConnectionInformation, EncryptionKey, TypedAttribute,
+};
+use super::crypto::Crypto;
+use super::fingerprint::Fingerprint;
+use super::ice::{IcePwd, IceUfrag};
+use super::keymgmt::KeyMgmt;
+use super::msid::{Msid, MsidSemantic};
+use super::session_description::{Origin, SessionDescription};
+use super::ssrc::Ssrc;
+use super::visit::{Level, SecretRedactor, VisitorMut};
+
+#[cfg(test)]
+mod sanitize_test;
+
+/// SanitizeStrategy tells what SessionDescription::sanitized does with the
+/// values of a category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SanitizeStrategy {
+    /// Keep leaves the values as they are.
+    Keep,
+    /// Remove drops the lines carrying the values. Values in lines which
+    /// can't be dropped, like the username of "o=", get the placeholder.
+    Remove,
+    /// Placeholder replaces every value with the same fixed one.
+    Placeholder,
+    /// Pseudonym replaces each distinct value with a distinct pseudonym, so
+    /// that lines referring to the same value still do so. Pseudonyms are
+    /// numbered in order of first appearance rather than derived from the
+    /// values, so they reveal nothing about them, and are the same for
+    /// equal inputs.
+    Pseudonym,
+}
+
+/// SanitizePolicy selects what SessionDescription::sanitized does with each
+/// category of sensitive values. The default removes everything which
+/// identifies a peer or could be used to attack a session, keeping
+/// fingerprints, which are public.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SanitizePolicy {
+    /// secrets are the "k=" keys, the ICE passwords and the keys of
+    /// "a=crypto:" and "a=key-mgmt:". They are never pseudonymized; Pseudonym
+    /// works like Placeholder, which zeroes them keeping their length.
+    pub secrets: SanitizeStrategy,
+    /// fingerprints are the certificate fingerprints of "a=fingerprint:".
+    pub fingerprints: SanitizeStrategy,
+    /// addresses are the IP addresses of "o=", "c=", "a=rtcp:",
+    /// "a=candidate:" and "a=remote-candidates:", replaced with addresses of
+    /// the documentation ranges of the same family. Multicast addresses stay
+    /// multicast, and the unspecified addresses are kept. Remove drops the
+    /// candidates with IP addresses.
+    /// <https://tools.ietf.org/html/rfc5737>
+    /// <https://tools.ietf.org/html/rfc3849>
+    /// <https://tools.ietf.org/html/rfc6676>
+    pub addresses: SanitizeStrategy,
+    /// fqdns are the domain names where addresses may be, e.g. mDNS
+    /// candidates. ".local" names stay ".local", others are replaced with
+    /// names under example.com. Remove drops the candidates with domain
+    /// names.
+    pub fqdns: SanitizeStrategy,
+    /// ice_ufrag are the ICE username fragments of "a=ice-ufrag:" and of the
+    /// "ufrag" candidate extension.
+    pub ice_ufrag: SanitizeStrategy,
+    /// cnames are the RTCP CNAMEs of "a=ssrc:".
+    pub cnames: SanitizeStrategy,
+    /// stream_ids are the stream and track ids of "a=msid:",
+    /// "a=msid-semantic:" and the legacy msid, mslabel and label "a=ssrc:"
+    /// attributes.
+    pub stream_ids: SanitizeStrategy,
+    /// username is the username of "o=".
+    pub username: SanitizeStrategy,
+}
+
+impl Default for SanitizePolicy {
+    fn default() -> Self {
+        SanitizePolicy {
+            secrets: SanitizeStrategy::Placeholder,
+            fingerprints: SanitizeStrategy::Keep,
+            addresses: SanitizeStrategy::Pseudonym,
+            fqdns: SanitizeStrategy::Pseudonym,
+            ice_ufrag: SanitizeStrategy::Pseudonym,
+            cnames: SanitizeStrategy::Pseudonym,
+            stream_ids: SanitizeStrategy::Pseudonym,
+            username: SanitizeStrategy::Placeholder,
+        }
+    }
+}
+
+impl SessionDescription {
+    /// sanitized returns a copy of the description with the sensitive values
+    /// replaced or removed as selected by policy, e.g. for logging. The
+    /// result still parses, and the replacements are valid values of their
+    /// fields.
+    pub fn sanitized(&self, policy: &SanitizePolicy) -> SessionDescription {
+        let mut sd = self.clone();
+
+        remove_lines(&mut sd.attributes, policy);
+        if policy.secrets == SanitizeStrategy::Remove {
+            sd.encryption_key = None;
+        }
+        for md in &mut sd.media_descriptions {
+            remove_lines(&mut md.attributes, policy);
+            if policy.secrets == SanitizeStrategy::Remove {
+                md.encryption_key = None;
+            }
+        }
+
+        let mut sanitizer = Sanitizer {
+            policy,
+            pseudonyms: Pseudonyms::default(),
+        };
+        sd.rewrite_connection_addresses(|connection_information| {
+            let address = connection_information.address.as_ref()?;
+            let new = match address.address.parse::<IpAddr>() {
+                Ok(ip) => Address {
+                    address: sanitizer.ip(ip).to_string(),
+                    ..address.clone()
+                },
+                Err(_) => {
+                    let fqdn = sanitizer.fqdn(&address.address);
+                    Address::fqdn(&connection_information.address_type, &fqdn).ok()?
+                }
+            };
+            Some(ConnectionInformation {
+                address: Some(new),
+                ..connection_information.clone()
+            })
+        });
+        sd.walk_mut(&mut sanitizer);
+        sd
+    }
+}
+
+/// remove_lines drops the attributes of the categories to remove.
+fn remove_lines(attributes: &mut Attributes, policy: &SanitizePolicy) {
+    let remove = |strategy: SanitizeStrategy| strategy == SanitizeStrategy::Remove;
+    let is_ip = |address: &CandidateAddress| matches!(address, CandidateAddress::Ip(_));
+    attributes.0.retain(|attribute| {
+        if IcePwd::matches(attribute) || KeyMgmt::matches(attribute) || Crypto::matches(attribute) {
+            !remove(policy.secrets)
+        } else if <Fingerprint as TypedAttribute>::matches(attribute) {
+            !remove(policy.fingerprints)
+        } else if IceUfrag::matches(attribute) {
+            !remove(policy.ice_ufrag)
+        } else if Msid::matches(attribute) {
+            !remove(policy.stream_ids)
+        } else if Candidate::matches(attribute) {
+            Candidate::from_attribute(attribute).map_or(true, |candidate| {
+                let removed = if is_ip(&candidate.address) {
+                    policy.addresses
+                } else {
+                    policy.fqdns
+                };
+                !remove(removed)
+            })
+        } else if RemoteCandidates::matches(attribute) {
+            RemoteCandidates::from_attribute(attribute).map_or(true, |remote_candidates| {
+                !remote_candidates.candidates.iter().any(|c| {
+                    if is_ip(&c.address) {
+                        remove(policy.addresses)
+                    } else {
+                        remove(policy.fqdns)
+                    }
+                })
+            })
+        } else if Ssrc::matches(attribute) {
+            Ssrc::from_attribute(attribute).map_or(true, |ssrc| match ssrc.attribute.as_str() {
+                "cname" => !remove(policy.cnames),
+                "msid" | "mslabel" | "label" => !remove(policy.stream_ids),
+                _ => true,
+            })
+        } else {
+            true
+        }
+    });
+}
+
+/// Category is a kind of value with pseudonyms of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Category {
+    Ipv4,
+    Ipv4Multicast,
+    Ipv6,
+    Ipv6Multicast,
+    Fqdn,
+    IceUfrag,
+    Cname,
+    StreamId,
+    TrackId,
+    Username,
+    Fingerprint,
+}
+
+/// Pseudonyms numbers the distinct values of each category, from 1, in order
+/// of first appearance.
+#[derive(Default)]
+struct Pseudonyms {
+    seen: Vec<(Category, String)>,
+}
+
+impl Pseudonyms {
+    fn number(&mut self, category: Category, value: &str) -> usize {
+        let mut number = 0;
+        for (c, v) in &self.seen {
+            if *c == category {
+                number += 1;
+                if v == value {
+                    return number;
+                }
+            }
+        }
+        self.seen.push((category, value.to_owned()));
+        number + 1
+    }
+}
+
+struct Sanitizer<'a> {
+    policy: &'a SanitizePolicy,
+    pseudonyms: Pseudonyms,
+}
+
+impl Sanitizer<'_> {
+    /// text replaces a value which is free-form but for a few characters. The
+    /// placeholder is used as the prefix of pseudonyms too.
+    fn text(
+        &mut self,
+        strategy: SanitizeStrategy,
+        category: Category,
+        value: &str,
+        placeholder: &str,
+    ) -> String {
+        match strategy {
+            SanitizeStrategy::Keep => value.to_owned(),
+            SanitizeStrategy::Remove | SanitizeStrategy::Placeholder => placeholder.to_owned(),
+            SanitizeStrategy::Pseudonym => {
+                format!("{}{}", placeholder, self.pseudonyms.number(category, value))
+            }
+        }
+    }
+
+    fn ip(&mut self, ip: IpAddr) -> IpAddr {
+        if ip.is_unspecified() {
+            return ip;
+        }
+        let category = match ip {
+            IpAddr::V4(ip) if ip.is_multicast() => Category::Ipv4Multicast,
+            IpAddr::V4(_) => Category::Ipv4,
+            IpAddr::V6(ip) if ip.is_multicast() => Category::Ipv6Multicast,
+            IpAddr::V6(_) => Category::Ipv6,
+        };
+        let number = match self.policy.addresses {
+            SanitizeStrategy::Keep => return ip,
+            SanitizeStrategy::Remove | SanitizeStrategy::Placeholder => 1,
+            SanitizeStrategy::Pseudonym => self.pseudonyms.number(category, &ip.to_string()),
+        };
+        documentation_address(category, number)
+    }
+
+    fn fqdn(&mut self, fqdn: &str) -> String {
+        let (name, domain) = if fqdn.ends_with(".local") {
+            (fqdn.trim_end_matches(".local"), "local")
+        } else {
+            (fqdn, "example.com")
+        };
+        let host = self.text(self.policy.fqdns, Category::Fqdn, name, "host");
+        if host == name {
+            return fqdn.to_owned();
+        }
+        format!("{}.{}", host, domain)
+    }
+}
+
+/// documentation_address returns the address with the given number, from 1,
+/// in the documentation ranges of the category. Numbers past the end of the
+/// ranges wrap around.
+fn documentation_address(category: Category, number: usize) -> IpAddr {
+    let index = number - 1;
+    match category {
+        Category::Ipv4 => {
+            // 192.0.2.0/24, 198.51.100.0/24 and 203.0.113.0/24, without the
+            // network and broadcast addresses.
+            const NETWORKS: [[u8; 3]; 3] = [[192, 0, 2], [198, 51, 100], [203, 0, 113]];
+            let [a, b, c] = NETWORKS[index / 254 % NETWORKS.len()];
+            IpAddr::V4(Ipv4Addr::new(a, b, c, (index % 254) as u8 + 1))
+        }
+        // 233.252.0.0/24
+        Category::Ipv4Multicast => IpAddr::V4(Ipv4Addr::new(233, 252, 0, (index % 255) as u8 + 1)),
+        // 2001:db8::/32
+        Category::Ipv6 => IpAddr::V6(Ipv6Addr::from(0x2001_0db8_u128 << 96 | (index as u128 + 1))),
+        // ff0e::db8:0:0/96
+        _ => IpAddr::V6(Ipv6Addr::from(
+            0xff0e_u128 << 112 | 0x0db8_u128 << 32 | (index as u128 + 1),
+        )),
+    }
+}
+
+impl VisitorMut for Sanitizer<'_> {
+    fn visit_origin(&mut self, origin: &mut Origin) {
+        let username = origin.username.to_string();
+        let username = self.text(self.policy.username, Category::Username, &username, "user");
+        // "-" is the placeholder for no username.
+        let username = if username == "user" { "-" } else { &username };
+        if let Ok(username) = username.parse() {
+            origin.username = username;
+        }
+    }
+
+    fn visit_key(&mut self, level: &Level, key: &mut EncryptionKey) {
+        if self.policy.secrets != SanitizeStrategy::Keep {
+            SecretRedactor.visit_key(level, key);
+        }
+    }
+
+    fn visit_attribute(&mut self, level: &Level, attribute: &mut Attribute) {
+        if self.policy.secrets != SanitizeStrategy::Keep {
+            SecretRedactor.visit_attribute(level, attribute);
+        }
+
+        if IceUfrag::matches(attribute) {
+            let ufrag = attribute.value().unwrap_or_default().to_owned();
+            let ufrag = self.text(self.policy.ice_ufrag, Category::IceUfrag, &ufrag, "ufrag");
+            if let Ok(ufrag) = IceUfrag::try_new(&ufrag) {
+                *attribute = ufrag.to_attribute();
+            }
+        } else if Candidate::matches(attribute) && self.policy.ice_ufrag != SanitizeStrategy::Keep {
+            if let Ok(mut candidate) = Candidate::from_attribute(attribute) {
+                let strategy = self.policy.ice_ufrag;
+                if strategy == SanitizeStrategy::Remove {
+                    candidate.extensions.retain(|(name, _)| name != "ufrag");
+                }
+                for (name, value) in &mut candidate.extensions {
+                    if name == "ufrag" {
+                        *value = self.text(strategy, Category::IceUfrag, value, "ufrag");
+                    }
+                }
+                *attribute = candidate.to_attribute();
+            }
+        } else if <Fingerprint as TypedAttribute>::matches(attribute)
+            && self.policy.fingerprints != SanitizeStrategy::Keep
+        {
+            if let Ok(fingerprint) = Fingerprint::from_attribute(attribute) {
+                *attribute = self.fingerprint(fingerprint).to_attribute();
+            }
+        } else if Ssrc::matches(attribute)
+            && (self.policy.cnames != SanitizeStrategy::Keep
+                || self.policy.stream_ids != SanitizeStrategy::Keep)
+        {
+            if let Ok(mut ssrc) = Ssrc::from_attribute(attribute) {
+                let value = ssrc.value.take().unwrap_or_default();
+                ssrc.value = Some(match ssrc.attribute.as_str() {
+                    "cname" => self.text(self.policy.cnames, Category::Cname, &value, "cname"),
+                    "msid" => {
+                        let (stream_id, track_id) = value.split_once(' ').unwrap_or((&value, ""));
+                        let stream_id = self.stream_id(stream_id);
+                        if track_id.is_empty() {
+                            stream_id
+                        } else {
+                            format!("{} {}", stream_id, self.track_id(track_id))
+                        }
+                    }
+                    "mslabel" => self.stream_id(&value),
+                    "label" => self.track_id(&value),
+                    _ => value,
+                })
+                .filter(|value| !value.is_empty());
+                *attribute = ssrc.to_attribute();
+            }
+        } else if Msid::matches(attribute) && self.policy.stream_ids != SanitizeStrategy::Keep {
+            if let Ok(mut msid) = Msid::from_attribute(attribute) {
+                msid.stream_id = msid.stream_id.map(|id| self.stream_id(&id));
+                msid.track_id = msid.track_id.map(|id| self.track_id(&id));
+                *attribute = msid.to_attribute();
+            }
+        } else if MsidSemantic::matches(attribute)
+            && self.policy.stream_ids != SanitizeStrategy::Keep
+        {
+            if let Ok(mut semantic) = MsidSemantic::from_attribute(attribute) {
+                if self.policy.stream_ids == SanitizeStrategy::Remove {
+                    semantic.stream_ids.retain(|id| id == "*");
+                }
+                for id in &mut semantic.stream_ids {
+                    *id = self.stream_id(id);
+                }
+                *attribute = semantic.to_attribute();
+            }
+        }
+    }
+}
+
+impl Sanitizer<'_> {
+    fn stream_id(&mut self, id: &str) -> String {
+        // "-" and "*" stand for no stream and all streams.
+        if id == "-" || id == "*" {
+            return id.to_owned();
+        }
+        self.text(self.policy.stream_ids, Category::StreamId, id, "stream")
+    }
+
+    fn track_id(&mut self, id: &str) -> String {
+        self.text(self.policy.stream_ids, Category::TrackId, id, "track")
+    }
+
+    fn fingerprint(&mut self, fingerprint: Fingerprint) -> Fingerprint {
+        let number = match self.policy.fingerprints {
+            SanitizeStrategy::Keep | SanitizeStrategy::Remove => return fingerprint,
+            SanitizeStrategy::Placeholder => 0,
+            SanitizeStrategy::Pseudonym => {
+                let value = fingerprint.to_string();
+                self.pseudonyms.number(Category::Fingerprint, &value)
+            }
+        };
+        // The number goes into the last bytes of an otherwise zero digest.
+        let len = fingerprint.value().len();
+        let mut value = vec![0; len];
+        for (byte, number_byte) in value
+            .iter_mut()
+            .rev()
+            .zip((number as u64).to_le_bytes().iter())
+        {
+            *byte = *number_byte;
+        }
+        Fingerprint::try_new(fingerprint.hash_function().clone(), value).unwrap_or(fingerprint)
+    }
+}
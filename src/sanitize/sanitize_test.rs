@@ -0,0 +1,186 @@
+use super::*;
+use crate::diagnostic::Severity;
+use crate::error::Error;
+
+const OFFER_SDP: &str = "v=0\r\n\
+o=alice 4611731400430051336 2 IN IP4 192.168.1.20\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0 1\r\n\
+a=msid-semantic: WMS 3c0c4a3b-stream\r\n\
+a=fingerprint:sha-256 19:E2:1C:3B:4B:9F:81:E6:B8:5C:F4:A5:A8:D8:73:04:BB:05:2F:70:9F:04:A9:0E:05:E9:26:33:E8:70:88:A2\r\n\
+m=audio 50000 UDP/TLS/RTP/SAVPF 111\r\n\
+c=IN IP4 192.168.1.20\r\n\
+a=rtcp:50001 IN IP4 192.168.1.20\r\n\
+a=candidate:1 1 udp 2122260223 192.168.1.20 50000 typ host generation 0 ufrag EsAw\r\n\
+a=candidate:2 1 udp 1686052607 203.0.113.7 50000 typ srflx raddr 192.168.1.20 rport 50000 ufrag EsAw\r\n\
+a=candidate:3 1 udp 2122260223 4c0ff2bd-3e60-4d9a-8d2e-3d5a2a6c1e8b.local 50002 typ host\r\n\
+a=ice-ufrag:EsAw\r\n\
+a=ice-pwd:P2uYro0UCOQ4zxjKXaWCBui1\r\n\
+a=mid:0\r\n\
+a=msid:3c0c4a3b-stream 8f3e9c21-audio\r\n\
+a=rtpmap:111 opus/48000/2\r\n\
+a=ssrc:1001 cname:Yk0BqBuFWnSdTSyW\r\n\
+a=ssrc:1001 msid:3c0c4a3b-stream 8f3e9c21-audio\r\n\
+m=video 50004 UDP/TLS/RTP/SAVPF 96\r\n\
+c=IN IP6 fd12:3456::20\r\n\
+a=ice-ufrag:EsAw\r\n\
+a=ice-pwd:P2uYro0UCOQ4zxjKXaWCBui1\r\n\
+a=mid:1\r\n\
+a=msid:3c0c4a3b-stream 1d7a2f66-video\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+a=ssrc:2002 cname:Yk0BqBuFWnSdTSyW\r\n\
+a=crypto:1 AES_CM_128_HMAC_SHA1_80 inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR|2^20|1:4\r\n";
+
+const ORIGINAL: [&str; 10] = [
+    "alice",
+    "192.168.1.20",
+    "203.0.113.7",
+    "fd12:3456::20",
+    "4c0ff2bd",
+    "EsAw",
+    "P2uYro0UCOQ4zxjKXaWCBui1",
+    "Yk0BqBuFWnSdTSyW",
+    "3c0c4a3b",
+    "PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR",
+];
+
+fn assert_valid(sd: &SessionDescription) -> Result<(), Error> {
+    let reparsed = sd.marshal().parse::<SessionDescription>()?;
+    assert_eq!(&reparsed, sd);
+    let errors = reparsed
+        .validate()
+        .into_iter()
+        .filter(|d| d.severity == Severity::Error)
+        .collect::<Vec<_>>();
+    assert!(errors.is_empty(), "{:?}", errors);
+    Ok(())
+}
+
+#[test]
+fn test_sanitized_default() -> Result<(), Error> {
+    let sd = OFFER_SDP.parse::<SessionDescription>()?;
+    let sanitized = sd.sanitized(&SanitizePolicy::default());
+    let text = sanitized.marshal();
+
+    for original in ORIGINAL.iter() {
+        assert!(!text.contains(original), "{} in {}", original, text);
+    }
+    // The original is left alone.
+    assert_eq!(sd.marshal(), OFFER_SDP);
+
+    // Mids, payload types and fingerprints are untouched.
+    assert!(text.contains("a=group:BUNDLE 0 1\r\n"), "{}", text);
+    assert!(text.contains("a=mid:0\r\n"), "{}", text);
+    assert!(text.contains("a=mid:1\r\n"), "{}", text);
+    assert!(text.contains("m=audio 50000 UDP/TLS/RTP/SAVPF 111\r\n"));
+    assert!(text.contains("a=rtpmap:96 VP8/90000\r\n"), "{}", text);
+    assert!(text.contains("a=fingerprint:sha-256 19:E2:1C"), "{}", text);
+
+    // Equal values get the same pseudonym, distinct values distinct ones.
+    assert!(text.contains("o=- 4611731400430051336 2 IN IP4 192.0.2.1\r\n"));
+    assert!(
+        text.contains("a=rtcp:50001 IN IP4 192.0.2.1\r\n"),
+        "{}",
+        text
+    );
+    assert!(
+        text.contains(
+            "a=candidate:2 1 udp 1686052607 192.0.2.2 50000 typ srflx raddr 192.0.2.1 rport 50000 ufrag ufrag1\r\n"
+        ),
+        "{}",
+        text
+    );
+    assert!(text.contains(" host1.local 50002 typ host\r\n"), "{}", text);
+    assert!(text.contains("c=IN IP6 2001:db8::1\r\n"), "{}", text);
+    assert_eq!(text.matches("a=ice-ufrag:ufrag1\r\n").count(), 2);
+    assert_eq!(
+        text.matches("a=ice-pwd:000000000000000000000000\r\n")
+            .count(),
+        2
+    );
+    assert!(text.contains("a=msid-semantic:WMS stream1\r\n"), "{}", text);
+    assert!(text.contains("a=msid:stream1 track1\r\n"), "{}", text);
+    assert!(text.contains("a=msid:stream1 track2\r\n"), "{}", text);
+    assert!(
+        text.contains("a=ssrc:1001 msid:stream1 track1\r\n"),
+        "{}",
+        text
+    );
+    assert_eq!(text.matches("cname:cname1\r\n").count(), 2);
+
+    assert_valid(&sanitized)
+}
+
+#[test]
+fn test_sanitized_placeholder() -> Result<(), Error> {
+    let sd = OFFER_SDP.parse::<SessionDescription>()?;
+    let policy = SanitizePolicy {
+        fingerprints: SanitizeStrategy::Placeholder,
+        addresses: SanitizeStrategy::Placeholder,
+        fqdns: SanitizeStrategy::Placeholder,
+        ice_ufrag: SanitizeStrategy::Placeholder,
+        cnames: SanitizeStrategy::Placeholder,
+        stream_ids: SanitizeStrategy::Placeholder,
+        ..SanitizePolicy::default()
+    };
+    let sanitized = sd.sanitized(&policy);
+    let text = sanitized.marshal();
+
+    for original in ORIGINAL.iter() {
+        assert!(!text.contains(original), "{} in {}", original, text);
+    }
+    assert!(!text.contains("192.0.2.2"), "{}", text);
+    assert!(text.contains("raddr 192.0.2.1"), "{}", text);
+    assert!(text.contains("a=msid:stream track\r\n"), "{}", text);
+    assert!(text.contains("a=fingerprint:sha-256 00:00:00"), "{}", text);
+
+    assert_valid(&sanitized)
+}
+
+#[test]
+fn test_sanitized_remove() -> Result<(), Error> {
+    let sd = OFFER_SDP.parse::<SessionDescription>()?;
+    let policy = SanitizePolicy {
+        secrets: SanitizeStrategy::Remove,
+        addresses: SanitizeStrategy::Remove,
+        fqdns: SanitizeStrategy::Remove,
+        ice_ufrag: SanitizeStrategy::Remove,
+        cnames: SanitizeStrategy::Remove,
+        stream_ids: SanitizeStrategy::Remove,
+        username: SanitizeStrategy::Remove,
+        ..SanitizePolicy::default()
+    };
+    let sanitized = sd.sanitized(&policy);
+    let text = sanitized.marshal();
+
+    for original in ORIGINAL.iter() {
+        assert!(!text.contains(original), "{} in {}", original, text);
+    }
+    for removed in ["a=candidate", "a=ice-", "a=msid:", "a=ssrc", "a=crypto"].iter() {
+        assert!(!text.contains(removed), "{} in {}", removed, text);
+    }
+    // Connection addresses can't be removed.
+    assert!(text.contains("c=IN IP4 192.0.2.1\r\n"), "{}", text);
+    assert!(text.contains("a=msid-semantic:WMS\r\n"), "{}", text);
+    assert!(text.contains("a=fingerprint:sha-256 19:E2:1C"), "{}", text);
+
+    assert_valid(&sanitized)
+}
+
+#[test]
+fn test_sanitized_keep() -> Result<(), Error> {
+    let sd = OFFER_SDP.parse::<SessionDescription>()?;
+    let policy = SanitizePolicy {
+        secrets: SanitizeStrategy::Keep,
+        fingerprints: SanitizeStrategy::Keep,
+        addresses: SanitizeStrategy::Keep,
+        fqdns: SanitizeStrategy::Keep,
+        ice_ufrag: SanitizeStrategy::Keep,
+        cnames: SanitizeStrategy::Keep,
+        stream_ids: SanitizeStrategy::Keep,
+        username: SanitizeStrategy::Keep,
+    };
+    assert_eq!(sd.sanitized(&policy), sd);
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::TypedAttribute;
+use super::error::Error;
+use super::util::token::is_token;
+
+#[cfg(test)]
+mod sctp_test;
+
+/// DATACHANNEL_FORMAT is the "m=" format and sctpmap application of WebRTC
+/// data channels.
+pub const DATACHANNEL_FORMAT: &str = "webrtc-datachannel";
+
+/// SctpPort is the "a=sctp-port:" attribute, giving the SCTP port of a
+/// datachannel media description. Zero is not a usable port.
+/// <https://tools.ietf.org/html/rfc8841#section-5>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SctpPort(pub u16);
+
+impl SctpPort {
+    /// new creates an SCTP port, rejecting zero.
+    pub fn new(port: u16) -> Result<Self, Error> {
+        if port == 0 {
+            return Err(Error::SdpInvalidSctpPort(port.to_string()));
+        }
+        Ok(SctpPort(port))
+    }
+
+    /// new_unchecked creates an SCTP port without rejecting zero, which the
+    /// caller has to ensure. It allows declaring ports as constants.
+    pub const fn new_unchecked(port: u16) -> Self {
+        SctpPort(port)
+    }
+}
+
+impl fmt::Display for SctpPort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for SctpPort {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match parse_digits::<u16>(value) {
+            Some(port) if port != 0 => Ok(SctpPort(port)),
+            _ => Err(Error::SdpInvalidSctpPort(value.to_owned())),
+        }
+    }
+}
+
+impl TypedAttribute for SctpPort {
+    const NAME: &'static str = "sctp-port";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("SCTP port {}", self.0))
+    }
+}
+
+/// MaxMessageSize is the "a=max-message-size:" attribute, the largest
+/// message in bytes the endpoint can receive. Zero means no limit.
+/// <https://tools.ietf.org/html/rfc8841#section-6>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MaxMessageSize(pub u64);
+
+impl MaxMessageSize {
+    /// UNLIMITED is the "0" of a peer which can receive messages of any size.
+    pub const UNLIMITED: MaxMessageSize = MaxMessageSize(0);
+
+    /// is_unlimited reports whether messages of any size can be received.
+    pub const fn is_unlimited(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl fmt::Display for MaxMessageSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for MaxMessageSize {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        parse_digits(value)
+            .map(MaxMessageSize)
+            .ok_or_else(|| Error::SdpInvalidMaxMessageSize(value.to_owned()))
+    }
+}
+
+impl TypedAttribute for MaxMessageSize {
+    const NAME: &'static str = "max-message-size";
+
+    fn describe(&self) -> Option<String> {
+        Some(match self.0 {
+            0 => "no message size limit".to_owned(),
+            size => format!("messages up to {} bytes", size),
+        })
+    }
+}
+
+/// SctpMap is the legacy "a=sctpmap:" attribute of older datachannel offers,
+/// where the "m=" format is the SCTP port.
+/// `a=sctpmap:<port> <app> [<max streams>]`
+/// <https://tools.ietf.org/html/draft-ietf-mmusic-sctp-sdp-05#section-5>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SctpMap {
+    /// port is the SCTP port.
+    pub port: u16,
+    /// app is the application protocol, e.g. "webrtc-datachannel".
+    pub app: String,
+    /// max_streams is the number of incoming streams, if given.
+    pub max_streams: Option<u32>,
+}
+
+impl fmt::Display for SctpMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.port, self.app)?;
+        if let Some(max_streams) = self.max_streams {
+            write!(f, " {}", max_streams)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SctpMap {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::SdpInvalidSctpMap(value.to_owned());
+
+        let mut fields = value.split(' ');
+        let port = fields
+            .next()
+            .and_then(parse_digits::<u16>)
+            .filter(|port| *port != 0)
+            .ok_or_else(invalid)?;
+        let app = fields.next().unwrap_or_default();
+        if !is_token(app) {
+            return Err(invalid());
+        }
+        let max_streams = match fields.next() {
+            Some(max_streams) => Some(parse_digits(max_streams).ok_or_else(invalid)?),
+            None => None,
+        };
+        if fields.next().is_some() {
+            return Err(invalid());
+        }
+
+        Ok(SctpMap {
+            port,
+            app: app.to_owned(),
+            max_streams,
+        })
+    }
+}
+
+impl TypedAttribute for SctpMap {
+    const NAME: &'static str = "sctpmap";
+}
+
+fn parse_digits<T: FromStr>(value: &str) -> Option<T> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
@@ -0,0 +1,74 @@
+use super::*;
+
+#[test]
+fn test_sctp_port() -> Result<(), Error> {
+    for input in ["5000", "1", "65535"] {
+        assert_eq!(input.parse::<SctpPort>()?.to_string(), input);
+    }
+    for input in ["", "0", "65536", "-1", "+5000", "5000 "] {
+        assert!(
+            matches!(input.parse::<SctpPort>(), Err(Error::SdpInvalidSctpPort(_))),
+            "{:?}",
+            input
+        );
+    }
+
+    assert_eq!(SctpPort::new(5000)?, SctpPort::new_unchecked(5000));
+    assert!(matches!(
+        SctpPort::new(0),
+        Err(Error::SdpInvalidSctpPort(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_max_message_size() -> Result<(), Error> {
+    assert_eq!("262144".parse::<MaxMessageSize>()?, MaxMessageSize(262144));
+    assert!("0".parse::<MaxMessageSize>()?.is_unlimited());
+    assert!(!MaxMessageSize(1073741823).is_unlimited());
+    for input in ["", "-1", "1.5", "18446744073709551616"] {
+        assert!(
+            matches!(
+                input.parse::<MaxMessageSize>(),
+                Err(Error::SdpInvalidMaxMessageSize(_))
+            ),
+            "{:?}",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_sctpmap() -> Result<(), Error> {
+    let sctpmap = "5000 webrtc-datachannel 1024".parse::<SctpMap>()?;
+    assert_eq!(
+        sctpmap,
+        SctpMap {
+            port: 5000,
+            app: DATACHANNEL_FORMAT.to_owned(),
+            max_streams: Some(1024),
+        }
+    );
+    for input in ["5000 webrtc-datachannel 1024", "5000 webrtc-datachannel"] {
+        assert_eq!(input.parse::<SctpMap>()?.to_string(), input);
+    }
+    for input in [
+        "",
+        "5000",
+        "0 webrtc-datachannel",
+        "5000 webrtc-datachannel x",
+        "5000 webrtc-datachannel 1024 1",
+        "5000  webrtc-datachannel",
+    ] {
+        assert!(
+            matches!(input.parse::<SctpMap>(), Err(Error::SdpInvalidSctpMap(_))),
+            "{:?}",
+            input
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,413 @@
+use core::convert::{TryFrom, TryInto};
+
+use sdp::description::common as sdp_common;
+use sdp::description::media as sdp_media;
+use sdp::description::session as sdp_session;
+use url::Url;
+
+use super::alloc_prelude::*;
+use super::common_description::{
+    validate_network_address, Address, Attribute, Attributes, Bandwidth, ConnectionInformation,
+    EncryptionKey, Information, ADDRESS_TYPE_IP4, ADDRESS_TYPE_IP6,
+};
+use super::error::Error;
+use super::media_description::{validate_protocol, MediaDescription, MediaName, Port};
+use super::session_description::{
+    EmailAddress, Origin, PhoneNumber, RepeatTime, SessionDescription, SessionName,
+    TimeDescription, TimeZone, TimeZones, Timing, Uri, Username, Version,
+};
+use super::util::token::validate_token;
+use super::util::LineEnding;
+
+#[cfg(test)]
+mod sdp_support_test;
+
+/// lossy_text converts "s=" and "i=" text to the String the sdp crate keeps
+/// it in, replacing what is not UTF-8 with U+FFFD.
+fn lossy_text(text: &[u8]) -> String {
+    String::from_utf8_lossy(text).into_owned()
+}
+
+/// to_isize converts a number for a field the sdp crate keeps as isize,
+/// saturating on targets where it does not fit.
+fn to_isize<T: TryInto<isize>>(value: T) -> isize {
+    value.try_into().unwrap_or(isize::MAX)
+}
+
+/// try_collect converts every value, stopping at the first error.
+fn try_collect<T, U: TryFrom<T, Error = Error>>(values: Vec<T>) -> Result<Vec<U>, Error> {
+    values.into_iter().map(U::try_from).collect()
+}
+
+impl From<Origin> for sdp_session::Origin {
+    fn from(origin: Origin) -> Self {
+        sdp_session::Origin {
+            username: origin.username.as_str().to_owned(),
+            session_id: origin.session_id,
+            session_version: origin.session_version,
+            network_type: origin.network_type.to_string(),
+            address_type: origin.address_type,
+            unicast_address: origin.unicast_address,
+        }
+    }
+}
+
+impl TryFrom<sdp_session::Origin> for Origin {
+    type Error = Error;
+
+    /// The fields are checked like parsing an "o=" line does, which the sdp
+    /// crate leaves to its parser.
+    fn try_from(origin: sdp_session::Origin) -> Result<Self, Self::Error> {
+        validate_network_address(
+            &origin.network_type,
+            &origin.address_type,
+            Some(&origin.unicast_address),
+        )?;
+
+        Ok(Origin {
+            username: Username::try_new(&origin.username)?,
+            session_id: origin.session_id,
+            session_version: origin.session_version,
+            network_type: origin.network_type.parse()?,
+            address_type: origin.address_type,
+            unicast_address: origin.unicast_address,
+        })
+    }
+}
+
+impl From<Address> for sdp_common::Address {
+    fn from(address: Address) -> Self {
+        sdp_common::Address {
+            address: address.address,
+            ttl: address.ttl.map(isize::from),
+            range: address.range.map(to_isize),
+        }
+    }
+}
+
+impl TryFrom<sdp_common::Address> for Address {
+    type Error = Error;
+
+    /// The parser of the sdp crate leaves the TTL and the number of addresses
+    /// in the address, so the address is read from how it is written out.
+    /// Its type is taken to be "IP6" if it contains a ":", and "IP4"
+    /// otherwise.
+    fn try_from(address: sdp_common::Address) -> Result<Self, Self::Error> {
+        let address_type = if address.address.contains(':') {
+            ADDRESS_TYPE_IP6
+        } else {
+            ADDRESS_TYPE_IP4
+        };
+        Address::parse(address_type, &address.to_string())
+    }
+}
+
+impl From<ConnectionInformation> for sdp_common::ConnectionInformation {
+    fn from(connection: ConnectionInformation) -> Self {
+        sdp_common::ConnectionInformation {
+            network_type: connection.network_type.to_string(),
+            address_type: connection.address_type,
+            address: connection.address.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<sdp_common::ConnectionInformation> for ConnectionInformation {
+    type Error = Error;
+
+    /// The fields are checked like parsing a "c=" line does, from how the sdp
+    /// crate writes them out.
+    fn try_from(connection: sdp_common::ConnectionInformation) -> Result<Self, Self::Error> {
+        connection.to_string().parse()
+    }
+}
+
+impl From<Bandwidth> for sdp_common::Bandwidth {
+    fn from(bandwidth: Bandwidth) -> Self {
+        sdp_common::Bandwidth {
+            experimental: bandwidth.experimental,
+            bandwidth_type: bandwidth.bandwidth_type.as_str().to_owned(),
+            bandwidth: bandwidth.bandwidth,
+        }
+    }
+}
+
+impl TryFrom<sdp_common::Bandwidth> for Bandwidth {
+    type Error = Error;
+
+    /// See Bandwidth::new.
+    fn try_from(bandwidth: sdp_common::Bandwidth) -> Result<Self, Self::Error> {
+        let bandwidth_type = if bandwidth.experimental {
+            format!("X-{}", bandwidth.bandwidth_type)
+        } else {
+            bandwidth.bandwidth_type
+        };
+        Bandwidth::new(&bandwidth_type, bandwidth.bandwidth)
+    }
+}
+
+impl From<Timing> for sdp_session::Timing {
+    fn from(timing: Timing) -> Self {
+        sdp_session::Timing {
+            start_time: timing.start_time,
+            stop_time: timing.stop_time,
+        }
+    }
+}
+
+impl TryFrom<sdp_session::Timing> for Timing {
+    type Error = Error;
+
+    /// See Timing::new.
+    fn try_from(timing: sdp_session::Timing) -> Result<Self, Self::Error> {
+        Timing::new(timing.start_time, timing.stop_time)
+    }
+}
+
+impl From<RepeatTime> for sdp_session::RepeatTime {
+    fn from(repeat_time: RepeatTime) -> Self {
+        sdp_session::RepeatTime {
+            interval: repeat_time.interval,
+            duration: repeat_time.duration,
+            offsets: repeat_time.offsets,
+        }
+    }
+}
+
+impl TryFrom<sdp_session::RepeatTime> for RepeatTime {
+    type Error = Error;
+
+    /// See RepeatTime::new.
+    fn try_from(repeat_time: sdp_session::RepeatTime) -> Result<Self, Self::Error> {
+        RepeatTime::new(
+            repeat_time.interval,
+            repeat_time.duration,
+            repeat_time.offsets,
+        )
+    }
+}
+
+impl From<TimeDescription> for sdp_session::TimeDescription {
+    fn from(time_description: TimeDescription) -> Self {
+        sdp_session::TimeDescription {
+            timing: time_description.timing.into(),
+            repeat_times: time_description
+                .repeat_times
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<sdp_session::TimeDescription> for TimeDescription {
+    type Error = Error;
+
+    fn try_from(time_description: sdp_session::TimeDescription) -> Result<Self, Self::Error> {
+        Ok(TimeDescription {
+            timing: time_description.timing.try_into()?,
+            repeat_times: try_collect(time_description.repeat_times)?,
+        })
+    }
+}
+
+impl From<TimeZone> for sdp_session::TimeZone {
+    fn from(time_zone: TimeZone) -> Self {
+        sdp_session::TimeZone {
+            adjustment_time: time_zone.adjustment_time,
+            offset: time_zone.offset,
+        }
+    }
+}
+
+impl From<sdp_session::TimeZone> for TimeZone {
+    fn from(time_zone: sdp_session::TimeZone) -> Self {
+        TimeZone {
+            adjustment_time: time_zone.adjustment_time,
+            offset: time_zone.offset,
+        }
+    }
+}
+
+/// Attributes are converted as their raw name and value; the typed ones are
+/// read from them with Attributes::get as usual.
+impl From<Attribute> for sdp_common::Attribute {
+    fn from(attribute: Attribute) -> Self {
+        sdp_common::Attribute {
+            key: attribute.key.as_str().to_owned(),
+            value: attribute.value.map(|value| value.as_str().to_owned()),
+        }
+    }
+}
+
+impl TryFrom<sdp_common::Attribute> for Attribute {
+    type Error = Error;
+
+    /// See Attribute::try_new.
+    fn try_from(attribute: sdp_common::Attribute) -> Result<Self, Self::Error> {
+        Attribute::try_new(&attribute.key, attribute.value.as_deref())
+    }
+}
+
+impl From<MediaName> for sdp_media::MediaName {
+    fn from(media_name: MediaName) -> Self {
+        sdp_media::MediaName {
+            media: media_name.media,
+            port: sdp_media::RangedPort {
+                value: to_isize(media_name.port.value),
+                range: media_name.port.count.map(|count| to_isize(count.get())),
+            },
+            protos: media_name.protos,
+            formats: media_name.formats,
+        }
+    }
+}
+
+impl TryFrom<sdp_media::MediaName> for MediaName {
+    type Error = Error;
+
+    /// The fields are checked like parsing an "m=" line does.
+    fn try_from(media_name: sdp_media::MediaName) -> Result<Self, Self::Error> {
+        validate_token(&media_name.media)?;
+        let port = media_name.port.to_string().parse::<Port>()?;
+        validate_protocol(&media_name.protos.join("/"))?;
+        if media_name.formats.is_empty() {
+            return Err(Error::SdpInvalidSyntax(format!("`m={}`", media_name)));
+        }
+        for format in &media_name.formats {
+            validate_token(format)?;
+        }
+
+        Ok(MediaName {
+            media: media_name.media,
+            port,
+            protos: media_name.protos,
+            formats: media_name.formats,
+        })
+    }
+}
+
+/// Unknown lines are dropped, the sdp crate has no place for them, and a
+/// title which is not UTF-8 gets U+FFFD for the bytes which are not.
+impl From<MediaDescription> for sdp_media::MediaDescription {
+    fn from(media: MediaDescription) -> Self {
+        sdp_media::MediaDescription {
+            media_name: media.media_name.into(),
+            media_title: media.media_title.map(|title| lossy_text(title.as_bytes())),
+            connection_information: media.connection_information.map(Into::into),
+            bandwidth: media.bandwidth.into_iter().map(Into::into).collect(),
+            encryption_key: media.encryption_key.map(|key| key.to_string()),
+            attributes: media.attributes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<sdp_media::MediaDescription> for MediaDescription {
+    type Error = Error;
+
+    /// The sdp crate does not check the fields it holds, so this fails for
+    /// those which could not have been parsed from an "m=" section.
+    fn try_from(media: sdp_media::MediaDescription) -> Result<Self, Self::Error> {
+        Ok(MediaDescription {
+            media_name: media.media_name.try_into()?,
+            media_title: media
+                .media_title
+                .as_deref()
+                .map(Information::try_new)
+                .transpose()?,
+            connection_information: media
+                .connection_information
+                .map(ConnectionInformation::try_from)
+                .transpose()?,
+            bandwidth: try_collect(media.bandwidth)?,
+            encryption_key: media
+                .encryption_key
+                .as_deref()
+                .map(EncryptionKey::try_new)
+                .transpose()?,
+            attributes: Attributes(try_collect(media.attributes)?),
+            unknown_lines: vec![],
+        })
+    }
+}
+
+/// The conversion is lossy where the sdp crate keeps less:
+///
+/// - unknown lines are dropped,
+/// - the line ending is lost, the sdp crate always writes CRLF,
+/// - the URI is written back in the normalized form of its Url,
+/// - text in "s=" and "i=" which is not UTF-8 gets U+FFFD for the bytes
+///   which are not.
+///
+/// Everything else, including every attribute, is written back by
+/// sdp::SessionDescription::marshal as it was.
+impl From<SessionDescription> for sdp_session::SessionDescription {
+    fn from(sd: SessionDescription) -> Self {
+        sdp_session::SessionDescription {
+            version: to_isize(sd.version.get()),
+            origin: sd.origin.into(),
+            session_name: lossy_text(sd.session_name.as_bytes()),
+            session_information: sd
+                .session_information
+                .map(|information| lossy_text(information.as_bytes())),
+            uri: sd.uri.map(Url::from),
+            email_address: sd.email_address.map(|email| email.to_string()),
+            phone_number: sd.phone_number.map(|phone| phone.to_string()),
+            connection_information: sd.connection_information.map(Into::into),
+            bandwidth: sd.bandwidth.into_iter().map(Into::into).collect(),
+            time_descriptions: sd.time_descriptions.into_iter().map(Into::into).collect(),
+            time_zones: sd.time_zones.0.into_iter().map(Into::into).collect(),
+            encryption_key: sd.encryption_key.map(|key| key.to_string()),
+            attributes: sd.attributes.into_iter().map(Into::into).collect(),
+            media_descriptions: sd.media_descriptions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<sdp_session::SessionDescription> for SessionDescription {
+    type Error = Error;
+
+    /// The sdp crate does not check the fields it holds, so this fails for
+    /// those which could not have been parsed, like an e-mail address
+    /// without an "@". The line ending is CRLF, which is what the sdp crate
+    /// writes.
+    fn try_from(sd: sdp_session::SessionDescription) -> Result<Self, Self::Error> {
+        Ok(SessionDescription {
+            version: sd.version.to_string().parse::<Version>()?,
+            origin: sd.origin.try_into()?,
+            session_name: SessionName::try_new(&sd.session_name)?,
+            session_information: sd
+                .session_information
+                .as_deref()
+                .map(Information::try_new)
+                .transpose()?,
+            uri: sd.uri.map(Uri::from),
+            email_address: sd
+                .email_address
+                .as_deref()
+                .map(str::parse::<EmailAddress>)
+                .transpose()?,
+            phone_number: sd
+                .phone_number
+                .as_deref()
+                .map(str::parse::<PhoneNumber>)
+                .transpose()?,
+            connection_information: sd
+                .connection_information
+                .map(ConnectionInformation::try_from)
+                .transpose()?,
+            bandwidth: try_collect(sd.bandwidth)?,
+            time_descriptions: try_collect(sd.time_descriptions)?,
+            time_zones: TimeZones(sd.time_zones.into_iter().map(Into::into).collect()),
+            encryption_key: sd
+                .encryption_key
+                .as_deref()
+                .map(EncryptionKey::try_new)
+                .transpose()?,
+            attributes: Attributes(try_collect(sd.attributes)?),
+            media_descriptions: try_collect(sd.media_descriptions)?,
+            unknown_lines: vec![],
+            line_ending: LineEnding::CrLf,
+        })
+    }
+}
@@ -0,0 +1,166 @@
+use super::*;
+
+use std::io::Cursor;
+
+use crate::common_description::{NetType, UnknownLine};
+
+const SESSION: &str = "v=0\r\n\
+o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
+s=SDP Seminar\r\n\
+i=A Seminar on the session description protocol\r\n\
+u=http://www.example.com/seminars/sdp.pdf\r\n\
+e=j.doe@example.com (Jane Doe)\r\n\
+p=+1 617 555-6011\r\n\
+c=IN IP4 224.2.17.12/127\r\n\
+b=X-YZ:128\r\n\
+t=2873397496 2873404696\r\n\
+r=604800 3600 0 90000\r\n\
+z=2882844526 -3600 2898848070 0\r\n\
+k=clear:secret\r\n\
+a=recvonly\r\n\
+m=audio 49170/2 RTP/AVP 0\r\n\
+i=Audio\r\n\
+b=AS:64\r\n\
+a=rtpmap:0 PCMU/8000\r\n\
+m=video 51372 RTP/AVP 99\r\n\
+a=rtpmap:99 h263-1998/90000\r\n";
+
+fn unmarshal(value: &str) -> sdp::SessionDescription {
+    sdp::SessionDescription::unmarshal(&mut Cursor::new(value.as_bytes()))
+        .expect("valid description")
+}
+
+#[test]
+fn test_session_description_round_trip() -> Result<(), Error> {
+    let sd = SESSION.parse::<SessionDescription>()?;
+    let upstream = sdp_session::SessionDescription::from(sd.clone());
+    assert_eq!(upstream.marshal(), SESSION);
+    assert_eq!(SessionDescription::try_from(upstream)?, sd);
+
+    Ok(())
+}
+
+#[test]
+fn test_session_description_fields() -> Result<(), Error> {
+    let sd = SessionDescription::try_from(unmarshal(SESSION))?;
+    assert_eq!(sd.origin.username, "jdoe");
+    assert_eq!(sd.origin.network_type, NetType::In);
+    assert_eq!(sd.session_name.as_str(), Some("SDP Seminar"));
+    assert_eq!(
+        sd.email_address.as_ref().map(|e| e.address.as_str()),
+        Some("j.doe@example.com")
+    );
+    let address = sd
+        .connection_information
+        .as_ref()
+        .and_then(|c| c.address.as_ref())
+        .expect("address");
+    assert_eq!(address.ttl, Some(127));
+    assert!(sd.bandwidth[0].experimental);
+    assert_eq!(
+        sd.time_descriptions[0].repeat_times[0].offsets,
+        vec![0, 90000]
+    );
+    assert_eq!(sd.time_zones.0.len(), 2);
+    assert!(sd.attributes.has("recvonly"));
+
+    let media = &sd.media_descriptions[0];
+    assert_eq!(media.media_name.port.count.map(|c| c.get()), Some(2));
+    assert_eq!(
+        media.media_title.as_ref().and_then(|t| t.as_str()),
+        Some("Audio")
+    );
+    assert_eq!(media.bandwidth[0].bandwidth, 64);
+    assert_eq!(
+        media.attributes.get_first("rtpmap").and_then(|a| a.value()),
+        Some("0 PCMU/8000")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_session_description_lossy() -> Result<(), Error> {
+    let mut sd = SESSION
+        .replace("\r\n", "\n")
+        .parse::<SessionDescription>()?;
+    sd.session_name = SessionName::from_bytes(b"Caf\xe9")?;
+    sd.unknown_lines.push(UnknownLine {
+        line_type: 'y',
+        value: "unknown".to_owned(),
+        position: 3,
+    });
+
+    let upstream = sdp_session::SessionDescription::from(sd);
+    assert_eq!(upstream.session_name, "Caf\u{fffd}");
+    let text = upstream.marshal();
+    assert!(text.starts_with("v=0\r\n"));
+    assert!(!text.contains("y=unknown"));
+
+    let sd = SessionDescription::try_from(upstream)?;
+    assert_eq!(sd.line_ending, LineEnding::CrLf);
+    assert!(sd.unknown_lines.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_try_from_validates() {
+    let origin = |username: &str, address_type: &str| sdp_session::Origin {
+        username: username.to_owned(),
+        session_id: 1,
+        session_version: 1,
+        network_type: "IN".to_owned(),
+        address_type: address_type.to_owned(),
+        unicast_address: "127.0.0.1".to_owned(),
+    };
+    assert!(Origin::try_from(origin("-", "IP4")).is_ok());
+    assert!(Origin::try_from(origin("j doe", "IP4")).is_err());
+    assert!(Origin::try_from(origin("-", "IP6")).is_err());
+
+    let address = |ttl, range| sdp_common::Address {
+        address: "224.2.17.12".to_owned(),
+        ttl,
+        range,
+    };
+    assert!(Address::try_from(address(Some(256), None)).is_err());
+    assert!(Address::try_from(address(Some(127), Some(0))).is_err());
+    let connection = sdp_common::ConnectionInformation {
+        network_type: "IN".to_owned(),
+        address_type: "IP4".to_owned(),
+        address: Some(address(None, None)),
+    };
+    assert!(matches!(
+        ConnectionInformation::try_from(connection),
+        Err(Error::SdpInvalidConnectionAddress(_))
+    ));
+
+    let timing = sdp_session::Timing {
+        start_time: 300,
+        stop_time: 100,
+    };
+    assert!(Timing::try_from(timing).is_err());
+
+    let attribute = sdp_common::Attribute {
+        key: "bad name".to_owned(),
+        value: None,
+    };
+    assert!(Attribute::try_from(attribute).is_err());
+
+    let media_name = |value, formats: &[&str]| sdp_media::MediaName {
+        media: "audio".to_owned(),
+        port: sdp_media::RangedPort { value, range: None },
+        protos: vec!["RTP".to_owned(), "AVP".to_owned()],
+        formats: formats.iter().map(|f| f.to_string()).collect(),
+    };
+    assert!(MediaName::try_from(media_name(9, &["0"])).is_ok());
+    assert!(MediaName::try_from(media_name(-1, &["0"])).is_err());
+    assert!(MediaName::try_from(media_name(9, &[])).is_err());
+
+    let mut sd = unmarshal(SESSION);
+    sd.email_address = Some("j.doe".to_owned());
+    assert!(matches!(
+        SessionDescription::try_from(sd),
+        Err(Error::SdpEmailMissingAt(_))
+    ));
+}
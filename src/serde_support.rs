@@ -0,0 +1,98 @@
+// Serialize/Deserialize implementations, enabled by the "serde" feature.
+//
+// Every type is (de)serialized in its SDP text form, i.e. the value of the
+// corresponding line without the "<type>=" prefix, so that a JSON dump of a
+// session description reads like the SDP it came from. Deserialization goes
+// through the same FromStr implementations as the parser, which means that
+// all validation runs on untrusted input as well.
+//
+// Encryption keys ("k=") are redacted when serializing a whole
+// SessionDescription unless the "expose-secrets" feature is enabled.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use super::common_description::*;
+use super::media_description::*;
+use super::session_description::*;
+use super::visit::redact_encryption_key;
+
+#[cfg(test)]
+mod serde_support_test;
+
+struct FromStrVisitor<T>(std::marker::PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for FromStrVisitor<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "an SDP field value")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+        value.parse::<T>().map_err(E::custom)
+    }
+}
+
+macro_rules! impl_serde_via_str {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserializer.deserialize_str(FromStrVisitor(std::marker::PhantomData))
+                }
+            }
+        )*
+    };
+}
+
+impl_serde_via_str!(
+    Origin,
+    Uri,
+    EmailAddress,
+    PhoneNumber,
+    ConnectionInformation,
+    Bandwidth,
+    Timing,
+    RepeatTime,
+    TimeZones,
+    Attribute,
+    MediaType,
+    Protocol,
+    Port,
+    MediaName,
+);
+
+impl Serialize for SessionDescription {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if cfg!(feature = "expose-secrets") {
+            return serializer.collect_str(self);
+        }
+
+        let mut sd = self.clone();
+        sd.encryption_key = sd.encryption_key.map(redact_encryption_key);
+        for md in &mut sd.media_descriptions {
+            md.encryption_key = md.encryption_key.take().map(redact_encryption_key);
+        }
+        serializer.collect_str(&sd)
+    }
+}
+
+impl<'de> Deserialize<'de> for SessionDescription {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(FromStrVisitor(std::marker::PhantomData))
+    }
+}
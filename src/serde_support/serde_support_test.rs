@@ -0,0 +1,55 @@
+use super::*;
+
+use serde::de::value::{Error as ValueError, StrDeserializer};
+use serde::de::IntoDeserializer;
+
+use crate::error::Error;
+
+fn from_str<'a, T: Deserialize<'a>>(value: &'a str) -> Result<T, ValueError> {
+    let deserializer: StrDeserializer<'a, ValueError> = value.into_deserializer();
+    T::deserialize(deserializer)
+}
+
+#[test]
+fn test_deserialize() -> Result<(), ValueError> {
+    let origin: Origin = from_str("jdoe 2890844526 2890842807 IN IP4 10.47.16.5")?;
+    assert_eq!(origin.username, "jdoe");
+    assert_eq!(origin.session_id, 2890844526);
+
+    let bandwidth: Bandwidth = from_str("X-YZ:128")?;
+    assert!(bandwidth.experimental);
+    assert_eq!(bandwidth.bandwidth, 128);
+
+    let timing: Timing = from_str("2873397496 2873404696")?;
+    assert_eq!(timing.stop_time, 2873404696);
+
+    let media_name: MediaName = from_str("audio 9 UDP/TLS/RTP/SAVPF 111")?;
+    assert_eq!(media_name.protocol().ok(), Some(Protocol::UdpTlsRtpSavpf));
+
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_validates() {
+    assert!(from_str::<Origin>("jdoe 1 1 IN IP4").is_err());
+    assert!(from_str::<ConnectionInformation>("IN IP4 ::1").is_err());
+    assert!(from_str::<Timing>("300 100").is_err());
+    assert!(from_str::<Attribute>("bad name:value").is_err());
+    assert!(from_str::<SessionDescription>("v=0\r\n").is_err());
+}
+
+#[test]
+fn test_redact_encryption_key() -> Result<(), Error> {
+    let tests = [
+        ("clear:secret", "clear:redacted"),
+        ("base64:c2VjcmV0", "base64:redacted"),
+        ("uri:https://example.com/key", "uri:https://example.com/key"),
+        ("prompt", "prompt"),
+    ];
+
+    for (key, expected) in tests {
+        assert_eq!(redact_encryption_key(key.parse()?).to_string(), expected);
+    }
+
+    Ok(())
+}
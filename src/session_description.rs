@@ -1,18 +1,39 @@
-use std::collections::HashMap;
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use core::convert::TryFrom;
+use core::fmt;
+use core::net::IpAddr;
+use core::ops::{Add, Mul, Neg, Sub};
+use core::str::FromStr;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
 use std::time::{SystemTime, UNIX_EPOCH};
-use std::{fmt, io};
 
 use url::Url;
 
+use super::alloc_prelude::*;
+use super::basic::check_basic_attributes;
+use super::charset::Charset;
 use super::common_description::*;
+use super::content::ContentValue;
+use super::diagnostic::{Diagnostic, DiagnosticKind};
 use super::error::Error;
+use super::extmap::{ExtMap, ExtmapAllowMixed};
+use super::group::{Group, GroupSemantics};
+use super::ice::{IcePwd, IceUfrag};
+use super::keymgmt::KeyMgmt;
 use super::media_description::*;
+use super::msid::{MsidSemantic, MSID_SEMANTIC_WMS};
+#[cfg(feature = "std")]
+use super::parser::lexer::Fields;
 use super::util::*;
 
 #[cfg(test)]
 mod session_description_test;
 
-// Constants for SDP attributes used in JSEP
+/// Constants for SDP attributes used in JSEP
 const ATTR_KEY_IDENTITY: &str = "identity";
 const ATTR_KEY_GROUP: &str = "group";
 const ATTR_KEY_SSRC: &str = "ssrc";
@@ -30,26 +51,119 @@ const ATTR_KEY_SEND_ONLY: &str = "sendonly";
 const ATTR_KEY_SEND_RECV: &str = "sendrecv";
 const ATTR_KEY_EXT_MAP: &str = "extmap";
 
-// Constants for semantic tokens used in JSEP
+/// Constants for semantic tokens used in JSEP
 const SEMANTIC_TOKEN_LIP_SYNCHRONIZATION: &str = "LS";
 const SEMANTIC_TOKEN_FLOW_IDENTIFICATION: &str = "FID";
 const SEMANTIC_TOKEN_FORWARD_ERROR_CORRECTION: &str = "FEC";
 const SEMANTIC_TOKEN_WEB_RTCMEDIA_STREAMS: &str = "WMS";
 
-// Version describes the value provided by the "v=" field which gives
-// the version of the Session Description Protocol.
-pub type Version = isize;
+/// Version describes the value provided by the "v=" field which gives
+/// the version of the Session Description Protocol. Only version 0 exists.
+/// <https://tools.ietf.org/html/rfc4566#section-5.1>
+///
+/// The grammar allows any number of digits, so "v=00" is accepted as version
+/// 0 and serialized back as "v=0". A well-formed version other than 0 is
+/// reported as SdpUnsupportedVersion so it can be told apart from garbage.
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::session_description::Version;
+///
+/// # fn main() -> Result<(), Error> {
+/// assert_eq!("00".parse::<Version>()?, Version::V0);
+/// assert_eq!(Version::V0.to_string(), "0");
+/// assert!(matches!(
+///     "1".parse::<Version>(),
+///     Err(Error::SdpUnsupportedVersion { found: 1 })
+/// ));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Version(u64);
+
+impl Version {
+    /// V0 is version 0, the only version there is.
+    pub const V0: Version = Version(0);
+
+    /// get returns the version number.
+    pub const fn get(&self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Version {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(Error::SdpInvalidSyntax(format!("`v={}`", value)));
+        }
+        match value.parse::<u64>() {
+            Ok(0) => Ok(Version::V0),
+            Ok(found) => Err(Error::SdpUnsupportedVersion { found }),
+            Err(_) => Err(Error::SdpInvalidSyntax(format!("`v={}`", value))),
+        }
+    }
+}
 
-// Origin defines the structure for the "o=" field which provides the
-// originator of the session plus a session identifier and version number.
-#[derive(Debug, Default)]
+/// Origin defines the structure for the "o=" field which provides the
+/// originator of the session plus a session identifier and version number.
+/// <https://tools.ietf.org/html/rfc4566#section-5.2>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::common_description::NetType;
+/// use webrtc_rs_sdp::session_description::{Origin, Username};
+///
+/// # fn main() -> Result<(), Error> {
+/// let mut origin = "jdoe 2890844526 2890842807 IN IP4 10.47.16.5".parse::<Origin>()?;
+/// assert_eq!(origin.username, "jdoe");
+/// assert_eq!(origin.session_id, 2890844526);
+/// origin.bump_version()?;
+/// assert_eq!(
+///     origin.to_string(),
+///     "jdoe 2890844526 2890842808 IN IP4 10.47.16.5"
+/// );
+///
+/// let origin = Origin {
+///     username: Username::try_new("-")?,
+///     session_id: 1,
+///     session_version: 1,
+///     network_type: NetType::In,
+///     address_type: "IP6".to_owned(),
+///     unicast_address: "::1".to_owned(),
+/// };
+/// assert_eq!(origin.to_string(), "- 1 1 IN IP6 ::1");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct Origin {
-    username: String,
-    session_id: u64,
-    session_version: u64,
-    network_type: String,
-    address_type: String,
-    unicast_address: String,
+    /// username is the `<username>` of the originating host.
+    pub username: Username,
+    /// session_id is the `<sess-id>`, which together with the other fields but
+    /// the version makes a globally unique identifier for the session.
+    pub session_id: u64,
+    /// session_version is the `<sess-version>`, which is increased whenever the
+    /// description is modified.
+    pub session_version: u64,
+    /// network_type is the `<nettype>`, "IN" for the Internet.
+    pub network_type: NetType,
+    /// address_type is the `<addrtype>`, "IP4" or "IP6" for the Internet.
+    pub address_type: String,
+    /// unicast_address is the `<unicast-address>` of the machine which created
+    /// the session, as an IP address or a domain name.
+    pub unicast_address: String,
 }
 
 impl fmt::Display for Origin {
@@ -67,180 +181,2419 @@ impl fmt::Display for Origin {
     }
 }
 
+impl FromStr for Origin {
+    type Err = Error;
+
+    /// `o=<username> <sess-id> <sess-version> <nettype> <addrtype> <unicast-address>`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(OriginRef::parse(value)?.to_owned())
+    }
+}
+
+/// Username is the `<username>` of the "o=" field: the user's login on the
+/// originating host, or "-" if the host does not support the concept of user
+/// IDs. It must not contain whitespace or control characters.
+/// <https://tools.ietf.org/html/rfc4566#section-5.2>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::session_description::Username;
+///
+/// # fn main() -> Result<(), Error> {
+/// let username = Username::try_new("jdoe")?;
+/// assert_eq!(username.as_str(), "jdoe");
+/// assert_eq!("-".parse::<Username>()?.to_string(), "-");
+/// assert!(Username::try_new("j doe").is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Username(String);
+
+impl Username {
+    /// try_new creates a username, rejecting empty ones and ones containing
+    /// whitespace or control characters.
+    pub fn try_new(value: &str) -> Result<Self, Error> {
+        validate_username(value)?;
+        Ok(Username(value.to_owned()))
+    }
+
+    /// as_str returns the username.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+fn validate_username(value: &str) -> Result<(), Error> {
+    if value.is_empty() || value.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(Error::SdpInvalidUsername(value.to_owned()));
+    }
+    Ok(())
+}
+
+impl_string_newtype!(Username(String), validate_username);
+
+/// OriginRef is the borrowed form of Origin.
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::session_description::OriginRef;
+///
+/// # fn main() -> Result<(), Error> {
+/// let text = "- 4611731400430051336 2 IN IP4 127.0.0.1";
+/// let origin = OriginRef::parse(text)?;
+/// assert_eq!(origin.unicast_address, "127.0.0.1");
+/// assert_eq!(origin.to_owned().to_string(), text);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OriginRef<'a> {
+    /// username is the `<username>`, see Origin::username.
+    pub username: &'a str,
+    /// session_id is the `<sess-id>`, see Origin::session_id.
+    pub session_id: u64,
+    /// session_version is the `<sess-version>`, see Origin::session_version.
+    pub session_version: u64,
+    /// network_type is the `<nettype>`, see Origin::network_type.
+    pub network_type: &'a str,
+    /// address_type is the `<addrtype>`, see Origin::address_type.
+    pub address_type: &'a str,
+    /// unicast_address is the `<unicast-address>`, see
+    /// Origin::unicast_address.
+    pub unicast_address: &'a str,
+}
+
+impl<'a> OriginRef<'a> {
+    /// `o=<username> <sess-id> <sess-version> <nettype> <addrtype> <unicast-address>`
+    pub fn parse(value: &'a str) -> Result<Self, Error> {
+        let mut fields = [""; 6];
+        let mut count = 0;
+        for field in value.split_whitespace() {
+            if count < fields.len() {
+                fields[count] = field;
+            }
+            count += 1;
+        }
+        if count != fields.len() {
+            return Err(Error::SdpInvalidSyntax(format!("`o={}`", value)));
+        }
+
+        validate_username(fields[0])?;
+        let session_id = fields[1].parse::<u64>()?;
+        let session_version = fields[2].parse::<u64>()?;
+
+        validate_network_address(fields[3], fields[4], Some(fields[5]))?;
+
+        Ok(OriginRef {
+            username: fields[0],
+            session_id,
+            session_version,
+            network_type: fields[3],
+            address_type: fields[4],
+            unicast_address: fields[5],
+        })
+    }
+
+    /// to_owned copies the origin into an Origin.
+    pub fn to_owned(&self) -> Origin {
+        Origin {
+            username: Username(self.username.to_owned()),
+            session_id: self.session_id,
+            session_version: self.session_version,
+            network_type: NetType::from_token(self.network_type),
+            address_type: self.address_type.to_owned(),
+            unicast_address: self.unicast_address.to_owned(),
+        }
+    }
+}
+
 impl Origin {
+    /// new creates an origin with all fields empty, to be filled in before it
+    /// is written out. See new_for_host for one that is ready to use.
     pub fn new() -> Self {
         Origin {
-            username: "".to_owned(),
+            username: Username::default(),
             session_id: 0,
             session_version: 0,
-            network_type: "".to_owned(),
+            network_type: NetType::default(),
             address_type: "".to_owned(),
             unicast_address: "".to_owned(),
         }
     }
+
+    /// new_for_host creates an origin for a new session on the given host,
+    /// with no username, a random `<sess-id>` and an NTP based `<sess-version>`.
+    #[cfg(feature = "std")]
+    pub fn new_for_host(addr: IpAddr) -> Self {
+        let address_type = match addr {
+            IpAddr::V4(_) => ADDRESS_TYPE_IP4,
+            IpAddr::V6(_) => ADDRESS_TYPE_IP6,
+        };
+        Origin {
+            username: Username("-".to_owned()),
+            session_id: new_session_id(),
+            session_version: ntp_now_secs(),
+            network_type: NetType::In,
+            address_type: address_type.to_owned(),
+            unicast_address: addr.to_string(),
+        }
+    }
+
+    /// bump_version increments `<sess-version>`, as required whenever a
+    /// modified description is offered in the same session.
+    /// <https://tools.ietf.org/html/rfc3264#section-8>
+    pub fn bump_version(&mut self) -> Result<(), Error> {
+        self.session_version = self
+            .session_version
+            .checked_add(1)
+            .ok_or(Error::SdpSessionVersionOverflow)?;
+        Ok(())
+    }
+
+    /// is_same_session reports whether both origins identify the same session,
+    /// i.e. all fields but `<sess-version>` are equal.
+    /// <https://tools.ietf.org/html/rfc4566#section-5.2>
+    pub fn is_same_session(&self, other: &Origin) -> bool {
+        self.username == other.username
+            && self.session_id == other.session_id
+            && self.network_type == other.network_type
+            && self.address_type == other.address_type
+            && self.unicast_address == other.unicast_address
+    }
+
+    /// marshal_len_hint returns the length of the marshaled origin.
+    pub fn marshal_len_hint(&self) -> usize {
+        self.username.0.len()
+            + decimal_len(self.session_id)
+            + decimal_len(self.session_version)
+            + self.network_type.as_str().len()
+            + self.address_type.len()
+            + self.unicast_address.len()
+            + 5
+    }
+}
+
+/// SessionName describes a structured representations for the "s=" field
+/// and is the textual session name. It may be any text without line breaks
+/// or NUL characters, in the charset given by the "a=charset:" attribute, so
+/// the raw bytes are kept and decoded on demand.
+/// <https://tools.ietf.org/html/rfc4566#section-5.3>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::charset::Charset;
+/// use webrtc_rs_sdp::session_description::SessionName;
+///
+/// # fn main() -> Result<(), Error> {
+/// let name = "SDP Seminar".parse::<SessionName>()?;
+/// assert_eq!(name.as_str(), Some("SDP Seminar"));
+/// assert_eq!(name.to_string(), "SDP Seminar");
+///
+/// let latin1 = SessionName::from_bytes(b"Caf\xe9")?;
+/// assert_eq!(latin1.as_str(), None);
+/// assert_eq!(latin1.decode_with(&Charset::Iso8859_1), "Café");
+///
+/// assert!(SessionName::try_new("SDP\r\nSeminar").is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default, Clone, PartialEq, Eq, Hash)]
+pub struct SessionName(Vec<u8>);
+
+impl SessionName {
+    /// try_new creates a session name from UTF-8 text, rejecting line breaks
+    /// and NUL characters.
+    pub fn try_new(value: &str) -> Result<Self, Error> {
+        SessionName::from_bytes(value.as_bytes())
+    }
+
+    /// from_bytes creates a session name from text in any charset.
+    pub fn from_bytes(value: &[u8]) -> Result<Self, Error> {
+        validate_text(value)?;
+        Ok(SessionName(value.to_vec()))
+    }
+
+    /// as_str returns the name if it is UTF-8.
+    pub fn as_str(&self) -> Option<&str> {
+        core::str::from_utf8(&self.0).ok()
+    }
+
+    /// as_bytes returns the name in its charset.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// decode_with decodes the name in the given charset, see
+    /// SessionDescription::charset.
+    pub fn decode_with(&self, charset: &Charset) -> Cow<'_, str> {
+        charset.decode(&self.0)
+    }
+}
+
+impl_string_newtype!(SessionName(Vec<u8>), validate_text);
+
+/// Uri describes the "u=" field which is a pointer to additional information
+/// about the session. The text it was parsed from is kept so that serializing
+/// reproduces it verbatim instead of the normalized form of the Url.
+/// <https://tools.ietf.org/html/rfc4566#section-5.5>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::session_description::Uri;
+///
+/// # fn main() -> Result<(), Error> {
+/// let uri = "http://www.example.com/seminars/sdp.pdf".parse::<Uri>()?;
+/// assert_eq!(uri.as_url().host_str(), Some("www.example.com"));
+/// assert_eq!(uri.to_string(), "http://www.example.com/seminars/sdp.pdf");
+/// assert!("/seminars/sdp.pdf".parse::<Uri>().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Uri {
+    url: Url,
+    raw: String,
+}
+
+impl fmt::Display for Uri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+impl FromStr for Uri {
+    type Err = Error;
+
+    /// Only absolute URIs are accepted, relative references are rejected by
+    /// the underlying Url parser.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.contains(|c: char| c.is_whitespace() || c.is_control()) {
+            return Err(Error::SdpInvalidValue(value.to_owned()));
+        }
+
+        Ok(Uri {
+            url: Url::parse(value)?,
+            raw: value.to_owned(),
+        })
+    }
+}
+
+impl From<Url> for Uri {
+    fn from(url: Url) -> Self {
+        Uri {
+            raw: url.to_string(),
+            url,
+        }
+    }
+}
+
+impl From<Uri> for Url {
+    fn from(uri: Uri) -> Self {
+        uri.url
+    }
+}
+
+impl Uri {
+    /// as_url returns the parsed URI.
+    pub fn as_url(&self) -> &Url {
+        &self.url
+    }
+}
+
+/// EmailAddress describes a structured representations for the "e=" line
+/// which specifies email contact information for the person responsible for
+/// the conference.
+/// <https://tools.ietf.org/html/rfc4566#section-5.6>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::session_description::EmailAddress;
+///
+/// # fn main() -> Result<(), Error> {
+/// let email = "Jane Doe <j.doe@example.com>".parse::<EmailAddress>()?;
+/// assert_eq!(email.address, "j.doe@example.com");
+/// assert_eq!(email.display_name.as_deref(), Some("Jane Doe"));
+/// assert_eq!(email.to_string(), "j.doe@example.com (Jane Doe)");
+///
+/// let email = EmailAddress::try_new("j.doe@example.com", None)?;
+/// assert_eq!(email.to_string(), "j.doe@example.com");
+/// assert!(EmailAddress::try_new("j.doe", None).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailAddress {
+    /// address is the email address.
+    pub address: String,
+    /// display_name is the name of the person the address belongs to.
+    pub display_name: Option<String>,
+}
+
+impl fmt::Display for EmailAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(display_name) = &self.display_name {
+            write!(f, "{} ({})", self.address, display_name)
+        } else {
+            write!(f, "{}", self.address)
+        }
+    }
+}
+
+impl FromStr for EmailAddress {
+    type Err = Error;
+
+    /// Accepts both "j.doe@example.com (Jane Doe)" and
+    /// "Jane Doe <j.doe@example.com>".
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.contains(['\r', '\n']) {
+            return Err(Error::SdpEmailInvalidChar(value.to_owned()));
+        }
+        let (address, display_name) = split_display_name(value)?;
+        EmailAddress::try_new(address, display_name)
+    }
+}
+
+impl EmailAddress {
+    /// try_new validates that the address contains exactly one "@" and no
+    /// whitespace, and that neither part contains line breaks.
+    pub fn try_new(address: &str, display_name: Option<&str>) -> Result<Self, Error> {
+        if address.contains(|c: char| c.is_whitespace() || c.is_control()) {
+            return Err(Error::SdpEmailInvalidChar(address.to_owned()));
+        }
+        if address.matches('@').count() != 1 {
+            return Err(Error::SdpEmailMissingAt(address.to_owned()));
+        }
+        if let Some(display_name) = display_name {
+            if display_name.contains(|c: char| c.is_control()) {
+                return Err(Error::SdpEmailInvalidChar(display_name.to_owned()));
+            }
+            if display_name.contains(['(', ')', '<', '>']) {
+                return Err(Error::SdpUnbalancedDelimiters(display_name.to_owned()));
+            }
+        }
+
+        Ok(EmailAddress {
+            address: address.to_owned(),
+            display_name: display_name.map(|n| n.to_owned()),
+        })
+    }
+}
+
+/// PhoneNumber describes a structured representations for the "p=" line
+/// specify phone contact information for the person responsible for the
+/// conference.
+/// <https://tools.ietf.org/html/rfc4566#section-5.6>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::session_description::PhoneNumber;
+///
+/// # fn main() -> Result<(), Error> {
+/// let phone = "Jane Doe <+1 617 555-6011>".parse::<PhoneNumber>()?;
+/// assert_eq!(phone.number, "+1 617 555-6011");
+/// assert_eq!(phone.to_string(), "+1 617 555-6011 (Jane Doe)");
+///
+/// let phone = PhoneNumber::new("+44-20-7946-0000", None)?;
+/// assert_eq!(phone.to_string(), "+44-20-7946-0000");
+/// assert!(PhoneNumber::new("617 555-6011", None).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhoneNumber {
+    /// number is the phone number in international format, starting with "+".
+    pub number: String,
+    /// display_name is the name of the person the number belongs to.
+    pub display_name: Option<String>,
+}
+
+impl fmt::Display for PhoneNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(display_name) = &self.display_name {
+            write!(f, "{} ({})", self.number, display_name)
+        } else {
+            write!(f, "{}", self.number)
+        }
+    }
 }
 
-// SessionName describes a structured representations for the "s=" field
-// and is the textual session name.
-pub type SessionName = String;
+impl FromStr for PhoneNumber {
+    type Err = Error;
 
-// EmailAddress describes a structured representations for the "e=" line
-// which specifies email contact information for the person responsible for
-// the conference.
-pub type EmailAddress = String;
+    /// Accepts both "+1 617 555-6011 (Jane Doe)" and
+    /// "Jane Doe <+1 617 555-6011>".
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (number, display_name) = split_display_name(value)?;
+        PhoneNumber::new(number, display_name)
+    }
+}
+
+impl PhoneNumber {
+    /// new validates that the number starts with "+" followed by digits,
+    /// spaces and hyphens only, and that the display name can be quoted in
+    /// parentheses.
+    pub fn new(number: &str, display_name: Option<&str>) -> Result<Self, Error> {
+        let digits = match number.strip_prefix('+') {
+            Some(digits) => digits,
+            None => return Err(Error::SdpInvalidPhoneNumber(number.to_owned())),
+        };
+        if !digits.bytes().any(|b| b.is_ascii_digit())
+            || !digits
+                .bytes()
+                .all(|b| b.is_ascii_digit() || b == b' ' || b == b'-')
+        {
+            return Err(Error::SdpInvalidPhoneNumber(number.to_owned()));
+        }
+        if let Some(display_name) = display_name {
+            if display_name.contains(|c: char| c.is_control()) {
+                return Err(Error::SdpInvalidPhoneNumber(display_name.to_owned()));
+            }
+            if display_name.contains(['(', ')', '<', '>']) {
+                return Err(Error::SdpUnbalancedDelimiters(display_name.to_owned()));
+            }
+        }
 
-// PhoneNumber describes a structured representations for the "p=" line
-// specify phone contact information for the person responsible for the
-// conference.
-pub type PhoneNumber = String;
+        Ok(PhoneNumber {
+            number: number.to_owned(),
+            display_name: display_name.map(|n| n.to_owned()),
+        })
+    }
+}
 
-// TimeZone defines the structured object for "z=" line which describes
-// repeated sessions scheduling.
-#[derive(Debug, Default)]
+/// TimeZone defines the structured object for "z=" line which describes
+/// repeated sessions scheduling.
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::session_description::TimeZone;
+///
+/// # fn main() -> Result<(), Error> {
+/// let time_zone = "2882844526 -1h".parse::<TimeZone>()?;
+/// assert_eq!(time_zone, TimeZone::new(2882844526, -3600));
+/// assert_eq!(time_zone.to_string(), "2882844526 -3600");
+/// assert_eq!(time_zone.to_compact_string(), "2882844526 -1h");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct TimeZone {
-    adjustment_time: u64,
-    offset: i64,
+    /// adjustment_time is the NTP time at which the offset starts to apply.
+    pub adjustment_time: u64,
+    /// offset is the number of seconds added to the times of repeated sessions
+    /// from the adjustment time on.
+    pub offset: i64,
 }
 
 impl fmt::Display for TimeZone {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{} {}", self.adjustment_time, self.offset)
     }
-}
+}
+
+impl FromStr for TimeZone {
+    type Err = Error;
+
+    /// `<adjustment time> <offset>`, with the offset in seconds or the typed
+    /// time shorthand.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut fields = value.split_whitespace();
+        match (fields.next(), fields.next(), fields.next()) {
+            (Some(adjustment_time), Some(offset), None) => Ok(TimeZone::new(
+                adjustment_time.parse::<u64>()?,
+                parse_time_units(offset)?,
+            )),
+            _ => Err(Error::SdpInvalidSyntax(format!("`z={}`", value))),
+        }
+    }
+}
+
+impl TimeZone {
+    /// new creates a time zone adjustment.
+    pub const fn new(adjustment_time: u64, offset: i64) -> Self {
+        TimeZone {
+            adjustment_time,
+            offset,
+        }
+    }
+
+    /// adjusted_from returns the time at which the adjustment starts to apply.
+    pub const fn adjusted_from(&self) -> Time {
+        Time(self.adjustment_time)
+    }
+
+    /// adjustment returns the offset added from the adjustment time on.
+    pub const fn adjustment(&self) -> Offset {
+        Offset(self.offset)
+    }
+
+    /// to_compact_string formats the time zone like Display but writes the
+    /// offset using the typed time shorthand (e.g. "2882844526 -1h").
+    pub fn to_compact_string(&self) -> String {
+        format!(
+            "{} {}",
+            self.adjustment_time,
+            format_time_units(self.offset)
+        )
+    }
+}
+
+/// TimeZones is the list of adjustment time and offset pairs carried by a
+/// single "z=" line.
+/// <https://tools.ietf.org/html/rfc4566#section-5.11>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::session_description::{TimeZone, TimeZones};
+///
+/// # fn main() -> Result<(), Error> {
+/// let mut time_zones = "2882844526 -1h 2898848070 0".parse::<TimeZones>()?;
+/// assert_eq!(time_zones.len(), 2);
+/// time_zones.push(TimeZone {
+///     adjustment_time: 2914570800,
+///     offset: -3600,
+/// });
+/// assert_eq!(
+///     time_zones.to_string(),
+///     "2882844526 -3600 2898848070 0 2914570800 -3600"
+/// );
+/// assert_eq!(
+///     time_zones.to_compact_string(),
+///     "2882844526 -1h 2898848070 0 2914570800 -1h"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TimeZones(pub Vec<TimeZone>);
+
+impl fmt::Display for TimeZones {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, time_zone) in self.0.iter().enumerate() {
+            let separator = if i == 0 { "" } else { " " };
+            write!(f, "{}{}", separator, time_zone)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TimeZones {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        // These fields are transimitted in pairs
+        // z=<adjustment time> <offset> <adjustment time> <offset> ....
+        // so we are making sure that there are actually multiple of 2 total.
+        let fields: Vec<&str> = value.split_whitespace().collect();
+        if fields.is_empty() || !fields.len().is_multiple_of(2) {
+            return Err(Error::SdpInvalidSyntax(format!("`z={}`", value)));
+        }
+
+        let mut time_zones = vec![];
+        for pair in fields.chunks(2) {
+            time_zones.push(TimeZone::new(
+                pair[0].parse::<u64>()?,
+                parse_time_units(pair[1])?,
+            ));
+        }
+
+        Ok(TimeZones(time_zones))
+    }
+}
+
+impl From<Vec<TimeZone>> for TimeZones {
+    fn from(time_zones: Vec<TimeZone>) -> Self {
+        TimeZones(time_zones)
+    }
+}
+
+impl TimeZones {
+    /// len returns the number of adjustments.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// is_empty reports whether there are no adjustments.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// iter iterates over the adjustments in order.
+    pub fn iter(&self) -> core::slice::Iter<'_, TimeZone> {
+        self.0.iter()
+    }
+
+    /// push appends an adjustment.
+    pub fn push(&mut self, time_zone: TimeZone) {
+        self.0.push(time_zone);
+    }
+
+    /// to_compact_string formats the list using the typed time shorthand for
+    /// the offsets, e.g. "2882844526 -1h 2898848070 0".
+    pub fn to_compact_string(&self) -> String {
+        let mut fields = vec![];
+        for time_zone in &self.0 {
+            fields.push(time_zone.to_compact_string());
+        }
+        fields.join(" ")
+    }
+}
+
+impl Extend<TimeZone> for TimeZones {
+    fn extend<I: IntoIterator<Item = TimeZone>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+
+impl IntoIterator for TimeZones {
+    type Item = TimeZone;
+    type IntoIter = alloc::vec::IntoIter<TimeZone>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a TimeZones {
+    type Item = &'a TimeZone;
+    type IntoIter = core::slice::Iter<'a, TimeZone>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// time_zone_offset_at returns the adjustment in effect at the given time,
+/// which is the offset of the latest "z=" entry whose adjustment time is not
+/// after it.
+fn time_zone_offset_at(time_zones: &TimeZones, time: i128) -> i128 {
+    let mut offset = 0;
+    let mut latest = None;
+    for time_zone in time_zones {
+        let adjustment_time = i128::from(time_zone.adjustment_time);
+        if adjustment_time <= time && latest.is_none_or(|l| adjustment_time >= l) {
+            latest = Some(adjustment_time);
+            offset = i128::from(time_zone.offset);
+        }
+    }
+    offset
+}
+
+/// TimeZoneSegment is a stretch of unadjusted time between two "z="
+/// adjustment times, during which every repetition is moved by the same
+/// offset. None stands for the start and the end of time.
+#[derive(Debug, Clone, Copy)]
+struct TimeZoneSegment {
+    start: Option<i128>,
+    end: Option<i128>,
+    offset: i128,
+}
+
+impl TimeZoneSegment {
+    /// repetitions returns the first and, unless the segment never ends, the
+    /// last repetition of a repeat offset whose unadjusted start falls in the
+    /// segment.
+    fn repetitions(&self, base: i128, interval: i128) -> Option<(i128, Option<i128>)> {
+        let first = match self.start {
+            Some(start) => -(base - start).div_euclid(interval),
+            None => 0,
+        }
+        .max(0);
+        let last = self.end.map(|end| (end - 1 - base).div_euclid(interval));
+        if last.is_some_and(|last| last < first) {
+            return None;
+        }
+        Some((first, last))
+    }
+}
+
+/// time_zone_segments splits the time line at every "z=" adjustment time, so
+/// that repetitions can be looked up per segment instead of one by one. Each
+/// segment is found by scanning the time zones again rather than sorting a
+/// copy of them, as there are rarely more than a couple.
+fn time_zone_segments(time_zones: &TimeZones) -> impl Iterator<Item = TimeZoneSegment> + '_ {
+    let next_adjustment_time = move |after: Option<i128>| {
+        time_zones
+            .iter()
+            .map(|tz| i128::from(tz.adjustment_time))
+            .filter(|&time| after.is_none_or(|after| time > after))
+            .min()
+    };
+    let first = TimeZoneSegment {
+        start: None,
+        end: next_adjustment_time(None),
+        offset: 0,
+    };
+    core::iter::successors(Some(first), move |segment| {
+        let start = segment.end?;
+        Some(TimeZoneSegment {
+            start: Some(start),
+            end: next_adjustment_time(Some(start)),
+            offset: time_zone_offset_at(time_zones, start),
+        })
+    })
+}
+
+/// first_repetition_from returns the adjusted start of the first repetition
+/// of a repeat offset starting at or after time.
+fn first_repetition_from(
+    base: i128,
+    interval: i128,
+    time: i128,
+    time_zones: &TimeZones,
+) -> Option<i128> {
+    time_zone_segments(time_zones)
+        .filter_map(|segment| {
+            let (first, last) = segment.repetitions(base, interval)?;
+            let k = (-(base + segment.offset - time).div_euclid(interval)).max(first);
+            if last.is_some_and(|last| k > last) {
+                return None;
+            }
+            Some(base + k * interval + segment.offset)
+        })
+        .min()
+}
+
+/// last_repetition_until returns the adjusted start of the last repetition of
+/// a repeat offset starting at or before time.
+fn last_repetition_until(
+    base: i128,
+    interval: i128,
+    time: i128,
+    time_zones: &TimeZones,
+) -> Option<i128> {
+    time_zone_segments(time_zones)
+        .filter_map(|segment| {
+            let (first, last) = segment.repetitions(base, interval)?;
+            let k = (time - base - segment.offset).div_euclid(interval);
+            let k = last.map_or(k, |last| k.min(last));
+            if k < first {
+                return None;
+            }
+            Some(base + k * interval + segment.offset)
+        })
+        .max()
+}
+
+/// TimeDescription describes "t=", "r=" fields of the session description
+/// which are used to specify the start and stop times for a session as well as
+/// repeat intervals and durations for the scheduled session.
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::session_description::TimeDescription;
+///
+/// # fn main() -> Result<(), Error> {
+/// let time_description =
+///     TimeDescription::from_lines(&["t=3034423619 3042462419", "r=7d 1h 0 25h"])?;
+/// assert_eq!(time_description.timing.to_string(), "3034423619 3042462419");
+/// assert_eq!(
+///     time_description.repeat_times[0].to_string(),
+///     "604800 3600 0 90000"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TimeDescription {
+    /// `t=<start-time> <stop-time>`
+    /// <https://tools.ietf.org/html/rfc4566#section-5.9>
+    pub timing: Timing,
+
+    /// `r=<repeat interval> <active duration> <offsets from start-time>`
+    /// <https://tools.ietf.org/html/rfc4566#section-5.10>
+    pub repeat_times: Vec<RepeatTime>,
+}
+
+impl TimeDescription {
+    /// from_lines builds a TimeDescription from a "t=" line followed by zero or
+    /// more "r=" lines. The "t=" and "r=" prefixes are optional.
+    pub fn from_lines(lines: &[&str]) -> Result<Self, Error> {
+        let (first, rest) = match lines.split_first() {
+            Some(split) => split,
+            None => return Err(Error::SdpEmptyTimeDescription),
+        };
+
+        let timing = first.trim().trim_start_matches("t=").parse::<Timing>()?;
+        let mut repeat_times = vec![];
+        for line in rest {
+            let line = line.trim();
+            if !line.starts_with("r=") {
+                return Err(Error::SdpInvalidSyntax(format!("`{}`", line)));
+            }
+            repeat_times.push(line.trim_start_matches("r=").parse::<RepeatTime>()?);
+        }
+
+        Ok(TimeDescription {
+            timing,
+            repeat_times,
+        })
+    }
+
+    /// write_to writes the "t=" line and its "r=" lines to w.
+    pub fn write_to<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        write!(w, "t={}{}", self.timing, END_LINE)?;
+        for repeat_time in &self.repeat_times {
+            write!(w, "r={}{}", repeat_time, END_LINE)?;
+        }
+        Ok(())
+    }
+
+    /// marshal_len_hint returns the length of the lines written by write_to.
+    pub fn marshal_len_hint(&self) -> usize {
+        let timing = decimal_len(self.timing.start_time) + 1 + decimal_len(self.timing.stop_time);
+        line_len(timing)
+            + self
+                .repeat_times
+                .iter()
+                .map(|r| {
+                    let offsets = r.offsets.iter().map(|&o| 1 + signed_decimal_len(o));
+                    line_len(
+                        signed_decimal_len(r.interval)
+                            + 1
+                            + signed_decimal_len(r.duration)
+                            + offsets.sum::<usize>(),
+                    )
+                })
+                .sum::<usize>()
+    }
+
+    /// is_active_at reports whether the session is active at the given NTP
+    /// time, expanding the "r=" lines and applying the "z=" adjustments.
+    /// Repeat times without a positive active duration never activate the
+    /// session.
+    /// <https://tools.ietf.org/html/rfc4566#section-5.10>
+    /// <https://tools.ietf.org/html/rfc4566#section-5.11>
+    pub fn is_active_at(&self, time: u64, time_zones: &TimeZones) -> bool {
+        if !self.timing.active_at(time) {
+            return false;
+        }
+
+        let repeat_times = self.effective_repeat_times();
+        if repeat_times.is_empty() {
+            return true;
+        }
+
+        let time = i128::from(time);
+        for repeat_time in repeat_times {
+            let interval = i128::from(repeat_time.interval);
+            let duration = i128::from(repeat_time.duration);
+            if duration <= 0 {
+                continue;
+            }
+            for offset in &repeat_time.offsets {
+                let base = i128::from(self.timing.start_time) + i128::from(*offset);
+                for segment in time_zone_segments(time_zones) {
+                    let (first, last) = match segment.repetitions(base, interval) {
+                        Some(range) => range,
+                        None => continue,
+                    };
+                    // Only the last repetition of the segment starting at or
+                    // before time can cover it, the earlier ones end sooner.
+                    let k = (time - base - segment.offset).div_euclid(interval);
+                    let k = last.map_or(k, |last| k.min(last));
+                    if k < first {
+                        continue;
+                    }
+                    if let Some((start, end)) =
+                        self.occurrence(base, interval, duration, k, time_zones)
+                    {
+                        if start <= time && time < end {
+                            return true;
+                        }
+                    }
+                }
+            }
+        }
+
+        false
+    }
+
+    /// next_active_interval returns the earliest activation interval starting
+    /// at or after the given NTP time as a (start, end) pair. The end of an
+    /// unbounded session without repeats is u64::MAX.
+    pub fn next_active_interval(&self, after: u64, time_zones: &TimeZones) -> Option<(u64, u64)> {
+        let repeat_times = self.effective_repeat_times();
+        if repeat_times.is_empty() {
+            if self.timing.start_time < after {
+                return None;
+            }
+            let end = if self.timing.is_unbounded() {
+                u64::MAX
+            } else {
+                self.timing.stop_time
+            };
+            return Some((self.timing.start_time, end));
+        }
+
+        let after = i128::from(after);
+        let mut next: Option<(i128, i128)> = None;
+        for repeat_time in repeat_times {
+            let interval = i128::from(repeat_time.interval);
+            let duration = i128::from(repeat_time.duration);
+            if duration <= 0 {
+                continue;
+            }
+            for offset in &repeat_time.offsets {
+                let base = i128::from(self.timing.start_time) + i128::from(*offset);
+                for segment in time_zone_segments(time_zones) {
+                    let (first, last) = match segment.repetitions(base, interval) {
+                        Some(range) => range,
+                        None => continue,
+                    };
+                    // The first repetition of the segment starting at or
+                    // after after.
+                    let k = (-(base + segment.offset - after).div_euclid(interval)).max(first);
+                    if last.is_some_and(|last| k > last) {
+                        continue;
+                    }
+                    if let Some((start, end)) =
+                        self.occurrence(base, interval, duration, k, time_zones)
+                    {
+                        if next.is_none_or(|(s, _)| start < s) {
+                            next = Some((start, end));
+                        }
+                    }
+                }
+            }
+        }
+
+        next.and_then(|(start, end)| {
+            Some((
+                u64::try_from(start).ok()?,
+                u64::try_from(end).unwrap_or(u64::MAX),
+            ))
+        })
+    }
+
+    /// occurrences returns the activation intervals of the session as
+    /// (start, end) pairs of NTP times in chronological order, expanding
+    /// every offset of every "r=" line and applying the "z=" adjustments.
+    /// Intervals are clipped to the "t=" times, and several ones starting at
+    /// the same time are merged. Repeats of an unbounded session only end
+    /// with the NTP range, so callers should limit the iteration, e.g. with
+    /// take or take_while. Repeat times without a positive active duration
+    /// yield nothing.
+    ///
+    /// The iterator only borrows the time description and the time zones,
+    /// and looks up the next repetition of every offset on each step instead
+    /// of keeping a cursor per offset, so it doesn't allocate.
+    /// <https://tools.ietf.org/html/rfc4566#section-5.10>
+    pub fn occurrences<'a>(&'a self, time_zones: &'a TimeZones) -> Occurrences<'a> {
+        let timing = &self.timing;
+        let start = i128::from(timing.start_time);
+        let end = if timing.is_unbounded() {
+            i128::from(u64::MAX)
+        } else {
+            i128::from(timing.stop_time)
+        };
+
+        let single = if self.repeat_times.iter().all(|r| r.interval <= 0) && start < end {
+            Some((timing.start_time, end as u64))
+        } else {
+            None
+        };
+
+        Occurrences {
+            repeat_times: &self.repeat_times,
+            time_zones,
+            start,
+            end,
+            from: start,
+            single,
+        }
+    }
+
+    /// Repeat times with a non-positive interval are treated as no repeats.
+    fn effective_repeat_times(&self) -> Vec<&RepeatTime> {
+        self.repeat_times
+            .iter()
+            .filter(|r| r.interval > 0)
+            .collect()
+    }
+
+    /// occurrence computes the k-th activation of a single repeat offset, with
+    /// the time zone adjustment applied and clipped by the "t=" stop time.
+    fn occurrence(
+        &self,
+        base: i128,
+        interval: i128,
+        duration: i128,
+        k: i128,
+        time_zones: &TimeZones,
+    ) -> Option<(i128, i128)> {
+        let unadjusted = base + k * interval;
+        let start = unadjusted + time_zone_offset_at(time_zones, unadjusted);
+        if start < 0 || start > i128::from(u64::MAX) {
+            return None;
+        }
+        if !self.timing.is_unbounded() && start > i128::from(self.timing.stop_time) {
+            return None;
+        }
+        Some((start, start + duration))
+    }
+}
+
+/// Occurrences is the iterator returned by TimeDescription::occurrences.
+#[derive(Debug, Clone)]
+pub struct Occurrences<'a> {
+    repeat_times: &'a [RepeatTime],
+    time_zones: &'a TimeZones,
+    /// start and end are the "t=" times intervals are clipped to.
+    start: i128,
+    end: i128,
+    /// from is the earliest start of the next interval.
+    from: i128,
+    /// single is the only interval of a session without repeats.
+    single: Option<(u64, u64)>,
+}
+
+impl Occurrences<'_> {
+    /// next_repetition returns the first repetition of a repeat offset which
+    /// starts at or after from once clipped to start and end, and isn't empty.
+    /// The time zone adjustments are assumed to be smaller than the repeat
+    /// interval, so that repetitions stay in order.
+    fn next_repetition(&self, base: i128, interval: i128, duration: i128) -> Option<(i128, i128)> {
+        if self.from <= self.start {
+            // A repetition in progress at the start time is clipped to it.
+            if let Some(start) = last_repetition_until(base, interval, self.start, self.time_zones)
+            {
+                if start + duration > self.start {
+                    return Some((self.start, (start + duration).min(self.end)));
+                }
+            }
+        }
+
+        let from = self.from.max(self.start + 1);
+        let start = first_repetition_from(base, interval, from, self.time_zones)?;
+        if start >= self.end {
+            return None;
+        }
+        Some((start, (start + duration).min(self.end)))
+    }
+}
+
+impl Iterator for Occurrences<'_> {
+    type Item = (u64, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(single) = self.single.take() {
+            return Some(single);
+        }
+        if self.start >= self.end {
+            return None;
+        }
+
+        let mut next: Option<(i128, i128)> = None;
+        let repeat_times = self
+            .repeat_times
+            .iter()
+            .filter(|r| r.interval > 0 && r.duration > 0);
+        for repeat_time in repeat_times {
+            let interval = i128::from(repeat_time.interval);
+            let duration = i128::from(repeat_time.duration);
+            for offset in &repeat_time.offsets {
+                let base = self.start + i128::from(*offset);
+                let (start, end) = match self.next_repetition(base, interval, duration) {
+                    Some(repetition) => repetition,
+                    None => continue,
+                };
+                next = match next {
+                    Some((s, e)) if s < start => Some((s, e)),
+                    Some((s, e)) if s == start => Some((s, e.max(end))),
+                    _ => Some((start, end)),
+                };
+            }
+        }
+
+        let (start, end) = next?;
+        self.from = start + 1;
+        // Both are clipped to the "t=" times, which are u64.
+        Some((start as u64, end as u64))
+    }
+}
+
+impl core::iter::FusedIterator for Occurrences<'_> {}
+
+/// Time is an NTP time in seconds, as used by "t=" and "z=".
+/// <https://tools.ietf.org/html/rfc4566#section-5.9>
+///
+/// # Examples
+///
+/// ```
+/// use webrtc_rs_sdp::session_description::{Offset, Time};
+///
+/// let time = Time::from_secs(3034423619);
+/// assert_eq!((time + Offset::from_secs(-3600)).as_secs(), 3034420019);
+/// assert_eq!(Time::from_secs(0).checked_add_offset(Offset::from_secs(-1)), None);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Time(pub u64);
+
+/// Offset is a signed number of seconds, as used by "r=" and "z=".
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use core::convert::TryFrom;
+/// use core::time::Duration;
+/// use webrtc_rs_sdp::session_description::Offset;
+///
+/// # fn main() -> Result<(), Error> {
+/// let hour = Offset::try_from(Duration::from_secs(3600))?;
+/// assert_eq!((hour * 25 - hour).as_secs(), 86400);
+/// assert!((-hour).is_negative());
+/// assert_eq!(Offset::MAX.checked_add(hour), None);
+/// assert!(Offset::try_from(Duration::from_millis(1500)).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Offset(pub i64);
+
+impl Time {
+    /// from_secs creates a time from a number of seconds since the NTP epoch.
+    pub const fn from_secs(secs: u64) -> Self {
+        Time(secs)
+    }
+
+    /// as_secs returns the number of seconds since the NTP epoch.
+    pub const fn as_secs(self) -> u64 {
+        self.0
+    }
+
+    /// is_zero reports whether the time is zero, which "t=" uses for
+    /// unbounded and permanent sessions.
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    /// iz_zero is the former, misspelled name of is_zero.
+    #[deprecated(note = "use Time::is_zero")]
+    pub const fn iz_zero(self) -> bool {
+        self.is_zero()
+    }
+
+    /// from_unix_secs converts seconds since the UNIX epoch, or returns None
+    /// if the NTP time does not fit into u64.
+    pub fn from_unix_secs(unix_secs: u64) -> Option<Time> {
+        unix_to_ntp_secs(unix_secs).map(Time)
+    }
+
+    /// to_unix_secs returns the seconds since the UNIX epoch, or None if the
+    /// time predates 1970.
+    pub fn to_unix_secs(self) -> Option<u64> {
+        ntp_to_unix_secs(self.0)
+    }
+
+    /// from_system_time converts a SystemTime, truncating any sub-second
+    /// part. Times before 1970 fail, like system_time_to_ntp_secs.
+    #[cfg(feature = "std")]
+    pub fn from_system_time(time: SystemTime) -> Result<Time, Error> {
+        system_time_to_ntp_secs(time).map(Time)
+    }
+
+    /// to_system_time converts the time into a SystemTime, or returns None if
+    /// it predates 1970.
+    #[cfg(feature = "std")]
+    pub fn to_system_time(self) -> Option<SystemTime> {
+        ntp_secs_to_system_time(self.0)
+    }
+
+    /// checked_add_offset returns the time moved by the offset, or None if it
+    /// falls before the NTP epoch or after u64::MAX.
+    pub const fn checked_add_offset(self, offset: Offset) -> Option<Time> {
+        let secs = if offset.0 < 0 {
+            self.0.checked_sub(offset.0.unsigned_abs())
+        } else {
+            self.0.checked_add(offset.0 as u64)
+        };
+        match secs {
+            Some(secs) => Some(Time(secs)),
+            None => None,
+        }
+    }
+
+    /// checked_sub_offset returns the time moved back by the offset, or None
+    /// if it falls before the NTP epoch or after u64::MAX.
+    pub const fn checked_sub_offset(self, offset: Offset) -> Option<Time> {
+        match offset.checked_neg() {
+            Some(offset) => self.checked_add_offset(offset),
+            // -i64::MIN is i64::MAX + 1.
+            None => match self.0.checked_add(offset.0.unsigned_abs()) {
+                Some(secs) => Some(Time(secs)),
+                None => None,
+            },
+        }
+    }
+
+    /// checked_add_duration returns the time moved forward by the duration,
+    /// or None if it overflows. Like Offset::try_from, durations with a
+    /// fractional second give None, as SDP times are whole seconds.
+    pub fn checked_add_duration(self, duration: Duration) -> Option<Time> {
+        if duration.subsec_nanos() != 0 {
+            return None;
+        }
+        self.0.checked_add(duration.as_secs()).map(Time)
+    }
+}
+
+/// Adding panics on overflow, like adding a Duration to an Instant; use
+/// Time::checked_add_offset to handle it.
+impl Add<Offset> for Time {
+    type Output = Time;
+
+    fn add(self, offset: Offset) -> Time {
+        self.checked_add_offset(offset)
+            .expect("overflow when adding offset to time")
+    }
+}
+
+impl Sub<Offset> for Time {
+    type Output = Time;
+
+    fn sub(self, offset: Offset) -> Time {
+        self.checked_sub_offset(offset)
+            .expect("overflow when subtracting offset from time")
+    }
+}
+
+/// Adding panics on overflow or a fractional second; use
+/// Time::checked_add_duration to handle them.
+impl Add<Duration> for Time {
+    type Output = Time;
+
+    fn add(self, duration: Duration) -> Time {
+        self.checked_add_duration(duration)
+            .expect("overflow when adding duration to time")
+    }
+}
+
+impl Offset {
+    /// ZERO is the offset of no time at all.
+    pub const ZERO: Offset = Offset(0);
+    /// MIN is the most negative offset.
+    pub const MIN: Offset = Offset(i64::MIN);
+    /// MAX is the most positive offset.
+    pub const MAX: Offset = Offset(i64::MAX);
+
+    /// from_secs creates an offset of the given number of seconds.
+    pub const fn from_secs(secs: i64) -> Self {
+        Offset(secs)
+    }
+
+    /// from_std_lossy converts a Duration, dropping any fractional second and
+    /// saturating at Offset::MAX. Use Offset::try_from to reject those
+    /// instead.
+    pub const fn from_std_lossy(duration: Duration) -> Self {
+        let secs = duration.as_secs();
+        if secs > i64::MAX as u64 {
+            Offset::MAX
+        } else {
+            Offset(secs as i64)
+        }
+    }
+
+    /// to_std converts the offset into a Duration, or None if it is negative.
+    pub const fn to_std(self) -> Option<Duration> {
+        if self.0 < 0 {
+            None
+        } else {
+            Some(Duration::from_secs(self.0 as u64))
+        }
+    }
+
+    /// is_zero reports whether the offset is zero.
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    /// is_negative reports whether the offset goes back in time.
+    pub const fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    /// as_secs returns the number of seconds of the offset.
+    pub const fn as_secs(self) -> i64 {
+        self.0
+    }
+
+    /// checked_add adds two offsets, or returns None on overflow.
+    pub const fn checked_add(self, other: Offset) -> Option<Offset> {
+        match self.0.checked_add(other.0) {
+            Some(secs) => Some(Offset(secs)),
+            None => None,
+        }
+    }
+
+    /// checked_sub subtracts an offset, or returns None on overflow.
+    pub const fn checked_sub(self, other: Offset) -> Option<Offset> {
+        match self.0.checked_sub(other.0) {
+            Some(secs) => Some(Offset(secs)),
+            None => None,
+        }
+    }
+
+    /// checked_mul multiplies the offset, e.g. a repeat interval by the
+    /// number of the repetition.
+    pub const fn checked_mul(self, factor: u32) -> Option<Offset> {
+        match self.0.checked_mul(factor as i64) {
+            Some(secs) => Some(Offset(secs)),
+            None => None,
+        }
+    }
+
+    /// checked_neg returns None for Offset(i64::MIN), whose negation doesn't
+    /// fit.
+    pub const fn checked_neg(self) -> Option<Offset> {
+        match self.0.checked_neg() {
+            Some(secs) => Some(Offset(secs)),
+            None => None,
+        }
+    }
+}
+
+/// The operators panic on overflow, like those of the integer types in
+/// debug builds; use the checked methods to handle it.
+impl Add for Offset {
+    type Output = Offset;
+
+    fn add(self, other: Offset) -> Offset {
+        self.checked_add(other)
+            .expect("overflow when adding offsets")
+    }
+}
+
+impl Sub for Offset {
+    type Output = Offset;
+
+    fn sub(self, other: Offset) -> Offset {
+        self.checked_sub(other)
+            .expect("overflow when subtracting offsets")
+    }
+}
+
+impl Mul<u32> for Offset {
+    type Output = Offset;
+
+    fn mul(self, factor: u32) -> Offset {
+        self.checked_mul(factor)
+            .expect("overflow when multiplying offset")
+    }
+}
+
+impl Neg for Offset {
+    type Output = Offset;
+
+    fn neg(self) -> Offset {
+        self.checked_neg().expect("overflow when negating offset")
+    }
+}
+
+/// Fails with SdpNonWholeSeconds if the duration has a fractional second, as
+/// SDP offsets are whole seconds, and with SdpInvalidValue if it is more than
+/// i64::MAX seconds. Offset::from_std_lossy truncates instead.
+impl TryFrom<Duration> for Offset {
+    type Error = Error;
+
+    fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+        if duration.subsec_nanos() != 0 {
+            return Err(Error::SdpNonWholeSeconds(format!("{:?}", duration)));
+        }
+        i64::try_from(duration.as_secs())
+            .map(Offset)
+            .map_err(|_| Error::SdpInvalidValue(format!("{:?}", duration)))
+    }
+}
+
+/// Timing defines the "t=" field's structured representation for the start and
+/// stop times.
+/// <https://tools.ietf.org/html/rfc4566#section-5.9>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::session_description::Timing;
+///
+/// # fn main() -> Result<(), Error> {
+/// let timing = "3034423619 3042462419".parse::<Timing>()?;
+/// assert_eq!(timing.duration(), Some(8038800));
+/// assert!(timing.active_at(3034423620));
+/// assert_eq!(Timing::unbounded_from(3034423619).to_string(), "3034423619 0");
+/// assert_eq!(Timing::permanent().to_string(), "0 0");
+/// assert!(Timing::new(2, 1).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Timing {
+    /// start_time is the NTP time the session starts at, or 0 for a permanent
+    /// session.
+    pub start_time: u64,
+    /// stop_time is the NTP time the session ends at, or 0 for a session which
+    /// doesn't end.
+    pub stop_time: u64,
+}
+
+impl fmt::Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.start_time, self.stop_time)
+    }
+}
+
+impl FromStr for Timing {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut fields = value.split_whitespace();
+        match (fields.next(), fields.next(), fields.next()) {
+            (Some(start_time), Some(stop_time), None) => {
+                Timing::new(start_time.parse::<u64>()?, stop_time.parse::<u64>()?)
+            }
+            _ => Err(Error::SdpInvalidSyntax(format!("`t={}`", value))),
+        }
+    }
+}
+
+impl Timing {
+    /// PERMANENT is the "t=0 0" timing of a permanent session.
+    pub const PERMANENT: Timing = Timing {
+        start_time: 0,
+        stop_time: 0,
+    };
+
+    /// new creates a Timing, rejecting a stop time before the start time. A
+    /// stop time of zero means the session is unbounded.
+    /// <https://tools.ietf.org/html/rfc4566#section-5.9>
+    pub fn new(start_time: u64, stop_time: u64) -> Result<Self, Error> {
+        if stop_time != 0 && stop_time < start_time {
+            return Err(Error::SdpInvalidTiming {
+                start: start_time,
+                stop: stop_time,
+            });
+        }
+
+        Ok(Timing {
+            start_time,
+            stop_time,
+        })
+    }
+
+    /// permanent creates the "t=0 0" timing of a permanent session.
+    pub const fn permanent() -> Self {
+        Timing::PERMANENT
+    }
+
+    /// unbounded_from creates a timing which starts at the given NTP time and
+    /// never ends.
+    pub const fn unbounded_from(start_time: u64) -> Self {
+        Timing {
+            start_time,
+            stop_time: 0,
+        }
+    }
+
+    /// bounded creates a timing active between the given NTP times.
+    pub fn bounded(start_time: u64, stop_time: u64) -> Result<Self, Error> {
+        Timing::new(start_time, stop_time)
+    }
+
+    /// start returns the time the session starts at.
+    pub const fn start(&self) -> Time {
+        Time(self.start_time)
+    }
+
+    /// stop returns the time the session ends at, or None if it is unbounded.
+    pub const fn stop(&self) -> Option<Time> {
+        if self.is_unbounded() {
+            None
+        } else {
+            Some(Time(self.stop_time))
+        }
+    }
+
+    /// is_permanent reports whether this is the "t=0 0" timing of a permanent
+    /// session.
+    pub const fn is_permanent(&self) -> bool {
+        self.start_time == 0 && self.stop_time == 0
+    }
+
+    /// is_unbounded reports whether the session never ends.
+    pub const fn is_unbounded(&self) -> bool {
+        self.stop_time == 0
+    }
+
+    /// active_at reports whether the session is active at the given NTP time.
+    pub const fn active_at(&self, time: u64) -> bool {
+        time >= self.start_time && (self.is_unbounded() || time <= self.stop_time)
+    }
+
+    /// duration returns the number of seconds the session is active for, or
+    /// None for unbounded sessions and for timings built with a stop time
+    /// before the start time.
+    pub const fn duration(&self) -> Option<u64> {
+        if self.is_unbounded() {
+            None
+        } else {
+            self.stop_time.checked_sub(self.start_time)
+        }
+    }
+}
+
+/// RepeatTime describes the "r=" fields of the session description which
+/// represents the intervals and durations for repeated scheduled sessions.
+/// <https://tools.ietf.org/html/rfc4566#section-5.10>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::session_description::RepeatTime;
+///
+/// # fn main() -> Result<(), Error> {
+/// let repeat_time = "7d 1h 0 25h".parse::<RepeatTime>()?;
+/// assert_eq!(repeat_time.offsets(), &[0, 90000]);
+/// assert_eq!(repeat_time.to_string(), "604800 3600 0 90000");
+/// assert_eq!(repeat_time.to_compact_string(), "7d 1h 0 25h");
+///
+/// let repeat_time = RepeatTime::new(86400, 3600, vec![0])?;
+/// assert_eq!(repeat_time.to_string(), "86400 3600 0");
+/// assert!(RepeatTime::new(86400, 3600, vec![]).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RepeatTime {
+    /// interval is the repeat interval in seconds.
+    pub interval: i64,
+    /// duration is the active duration in seconds.
+    pub duration: i64,
+    /// offsets are the offsets from the start time in seconds.
+    pub offsets: Vec<i64>,
+}
+
+impl fmt::Display for RepeatTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.interval, self.duration)?;
+        for value in &self.offsets {
+            write!(f, " {}", value)?;
+        }
+        Ok(())
+    }
+}
+
+impl RepeatTime {
+    /// new creates a RepeatTime. The repeat interval must be positive and at
+    /// least one offset from the start time is required.
+    /// <https://tools.ietf.org/html/rfc4566#section-5.10>
+    pub fn new<I: IntoIterator<Item = i64>>(
+        interval: i64,
+        duration: i64,
+        offsets: I,
+    ) -> Result<Self, Error> {
+        if interval <= 0 {
+            return Err(Error::SdpInvalidRepeatTime(format!(
+                "repeat interval must be positive, got {}",
+                interval
+            )));
+        }
+
+        let offsets: Vec<i64> = offsets.into_iter().collect();
+        if offsets.is_empty() {
+            return Err(Error::SdpInvalidRepeatTime(
+                "at least one offset is required".to_owned(),
+            ));
+        }
+
+        Ok(RepeatTime {
+            interval,
+            duration,
+            offsets,
+        })
+    }
+
+    /// push_offset appends an offset from the start time.
+    pub fn push_offset(&mut self, offset: i64) {
+        self.offsets.push(offset);
+    }
+
+    /// interval returns the repeat interval in seconds.
+    pub fn interval(&self) -> i64 {
+        self.interval
+    }
+
+    /// duration returns the active duration in seconds.
+    pub fn duration(&self) -> i64 {
+        self.duration
+    }
+
+    /// offsets returns the offsets from the start time in seconds.
+    pub fn offsets(&self) -> &[i64] {
+        &self.offsets
+    }
+
+    /// repeat_interval returns the repeat interval as an Offset.
+    pub const fn repeat_interval(&self) -> Offset {
+        Offset(self.interval)
+    }
+
+    /// active_duration returns the active duration as an Offset.
+    pub const fn active_duration(&self) -> Offset {
+        Offset(self.duration)
+    }
+
+    /// offsets_from_start returns the offsets from the start time as Offsets.
+    pub fn offsets_from_start(&self) -> impl Iterator<Item = Offset> + '_ {
+        self.offsets.iter().map(|&offset| Offset(offset))
+    }
+
+    /// to_compact_string formats the repeat time like Display but writes every
+    /// value using the typed time shorthand recommended by RFC 4566, e.g.
+    /// "7d 1h 0 25h" instead of "604800 3600 0 90000".
+    /// <https://tools.ietf.org/html/rfc4566#section-5.10>
+    pub fn to_compact_string(&self) -> String {
+        let mut fields = vec![];
+        fields.push(format_time_units(self.interval));
+        fields.push(format_time_units(self.duration));
+        for value in &self.offsets {
+            fields.push(format_time_units(*value));
+        }
+        fields.join(" ")
+    }
+}
+
+impl FromStr for RepeatTime {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        // r=<repeat interval> <active duration> <offsets from start-time>
+        let fields: Vec<&str> = value.split_whitespace().collect();
+        if fields.len() < 3 {
+            return Err(Error::SdpInvalidSyntax(format!(
+                "`r={}` -- expected a repeat interval, an active duration and at least one offset",
+                value
+            )));
+        }
+
+        let interval = parse_time_units(fields[0])?;
+        let duration = parse_time_units(fields[1])?;
+        let mut offsets = vec![];
+        for field in fields.iter().skip(2) {
+            offsets.push(parse_time_units(field)?);
+        }
+
+        RepeatTime::new(interval, duration, offsets)
+    }
+}
+
+impl Extend<i64> for RepeatTime {
+    fn extend<I: IntoIterator<Item = i64>>(&mut self, iter: I) {
+        self.offsets.extend(iter);
+    }
+}
+
+impl<'a> IntoIterator for &'a RepeatTime {
+    type Item = &'a i64;
+    type IntoIter = core::slice::Iter<'a, i64>;
+
+    /// Iterates over the offsets from the start time.
+    fn into_iter(self) -> Self::IntoIter {
+        self.offsets.iter()
+    }
+}
+
+/// SessionDescription is a a well-defined format for conveying sufficient
+/// information to discover and participate in a multimedia session.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SessionDescription {
+    /// v=0
+    /// <https://tools.ietf.org/html/rfc4566#section-5.1>
+    pub version: Version,
+
+    /// `o=<username> <sess-id> <sess-version> <nettype> <addrtype> <unicast-address>`
+    /// <https://tools.ietf.org/html/rfc4566#section-5.2>
+    pub origin: Origin,
+
+    /// `s=<session name>`
+    /// <https://tools.ietf.org/html/rfc4566#section-5.3>
+    pub session_name: SessionName,
+
+    /// `i=<session description>`
+    /// <https://tools.ietf.org/html/rfc4566#section-5.4>
+    pub session_information: Option<Information>,
+
+    /// `u=<uri>`
+    /// <https://tools.ietf.org/html/rfc4566#section-5.5>
+    pub uri: Option<Uri>,
+
+    /// `e=<email-address>`
+    /// <https://tools.ietf.org/html/rfc4566#section-5.6>
+    pub email_address: Option<EmailAddress>,
+
+    /// `p=<phone-number>`
+    /// <https://tools.ietf.org/html/rfc4566#section-5.6>
+    pub phone_number: Option<PhoneNumber>,
+
+    /// `c=<nettype> <addrtype> <connection-address>`
+    /// <https://tools.ietf.org/html/rfc4566#section-5.7>
+    pub connection_information: Option<ConnectionInformation>,
+
+    /// `b=<bwtype>:<bandwidth>`
+    /// <https://tools.ietf.org/html/rfc4566#section-5.8>
+    pub bandwidth: Vec<Bandwidth>,
+
+    /// <https://tools.ietf.org/html/rfc4566#section-5.9>
+    /// <https://tools.ietf.org/html/rfc4566#section-5.10>
+    pub time_descriptions: Vec<TimeDescription>,
+
+    /// `z=<adjustment time> <offset> <adjustment time> <offset> ...`
+    /// <https://tools.ietf.org/html/rfc4566#section-5.11>
+    pub time_zones: TimeZones,
+
+    /// `k=<method>`
+    /// `k=<method>:<encryption key>`
+    /// <https://tools.ietf.org/html/rfc4566#section-5.12>
+    pub encryption_key: Option<EncryptionKey>,
+
+    /// `a=<attribute>`
+    /// `a=<attribute>:<value>`
+    /// <https://tools.ietf.org/html/rfc4566#section-5.13>
+    pub attributes: Attributes,
+
+    /// <https://tools.ietf.org/html/rfc4566#section-5.14>
+    pub media_descriptions: Vec<MediaDescription>,
+
+    /// unknown_lines are the session-level lines of unknown types, if they
+    /// were kept by the parser.
+    pub unknown_lines: Vec<UnknownLine>,
+
+    /// line_ending is the line break written after every line. The parsers
+    /// set it to the one ending the first line of their input, so that a
+    /// description using LF alone is written back unchanged; set it to
+    /// LineEnding::CrLf to normalize it.
+    pub line_ending: LineEnding,
+}
+
+impl fmt::Display for SessionDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_to(f)
+    }
+}
+
+impl FromStr for SessionDescription {
+    type Err = Error;
+
+    #[cfg(feature = "std")]
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        SessionDescription::unmarshal(&mut io::Cursor::new(value.as_bytes()))
+    }
+
+    /// Without std, the borrowed parser walks the same state table.
+    #[cfg(not(feature = "std"))]
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        SessionDescriptionRef::parse(value).map(|sd| sd.to_owned())
+    }
+}
+
+/// SessionDescriptionRef is the borrowed form of SessionDescription returned
+/// by SessionDescription::parse_borrowed. The text fields, origin, attributes
+/// and media names are slices of the parsed input; numeric fields and the
+/// rarely used "u=", "e=", "p=" and "b=" lines are kept in their owned form.
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::session_description::SessionDescription;
+///
+/// # fn main() -> Result<(), Error> {
+/// let text = "v=0\r\n\
+/// o=- 0 1 IN IP4 127.0.0.1\r\n\
+/// s=-\r\n\
+/// t=0 0\r\n\
+/// a=ice-lite\r\n";
+/// let sd = SessionDescription::parse_borrowed(text)?;
+/// assert_eq!(sd.origin.unicast_address, "127.0.0.1");
+/// assert_eq!(sd.attributes[0].key, "ice-lite");
+/// assert_eq!(sd.to_owned().marshal(), text);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SessionDescriptionRef<'a> {
+    /// version is the "v=" field, see SessionDescription::version.
+    pub version: Version,
+    /// origin is the "o=" field, see SessionDescription::origin.
+    pub origin: OriginRef<'a>,
+    /// session_name is the "s=" field, see SessionDescription::session_name.
+    pub session_name: &'a str,
+    /// session_information is the "i=" field, see
+    /// SessionDescription::session_information.
+    pub session_information: Option<&'a str>,
+    /// uri is the "u=" field, see SessionDescription::uri.
+    pub uri: Option<Uri>,
+    /// email_address is the "e=" field, see SessionDescription::email_address.
+    pub email_address: Option<EmailAddress>,
+    /// phone_number is the "p=" field, see SessionDescription::phone_number.
+    pub phone_number: Option<PhoneNumber>,
+    /// connection_information is the "c=" field, see
+    /// SessionDescription::connection_information.
+    pub connection_information: Option<ConnectionInformationRef<'a>>,
+    /// bandwidth are the "b=" fields, see SessionDescription::bandwidth.
+    pub bandwidth: Vec<Bandwidth>,
+    /// time_descriptions are the "t=" and "r=" fields, see
+    /// SessionDescription::time_descriptions.
+    pub time_descriptions: Vec<TimeDescription>,
+    /// time_zones is the "z=" field, see SessionDescription::time_zones.
+    pub time_zones: TimeZones,
+    /// encryption_key is the "k=" field, see
+    /// SessionDescription::encryption_key.
+    pub encryption_key: Option<EncryptionKey>,
+    /// attributes are the "a=" fields, see SessionDescription::attributes.
+    pub attributes: Vec<AttributeRef<'a>>,
+    /// media_descriptions are the media descriptions, see
+    /// SessionDescription::media_descriptions.
+    pub media_descriptions: Vec<MediaDescriptionRef<'a>>,
+    /// line_ending is the line break of the first line, see
+    /// SessionDescription::line_ending.
+    pub line_ending: LineEnding,
+}
+
+impl<'a> SessionDescriptionRef<'a> {
+    /// parse is the borrowed counterpart of SessionDescription::unmarshal. It
+    /// walks the same state table (see the comment on unmarshal) and runs the
+    /// same field validation, reporting errors at the same positions.
+    pub fn parse(value: &'a str) -> Result<Self, Error> {
+        let mut desc = SessionDescriptionRef {
+            line_ending: LineEnding::of(value.as_bytes()),
+            ..Default::default()
+        };
+        let mut state = 1;
+        let mut newlines = 0;
+        let mut rest = value;
+
+        loop {
+            // Skip line breaks between fields like Fields does
+            let trimmed = rest.trim_start_matches(['\r', '\n']);
+            newlines += rest[..rest.len() - trimmed.len()].matches('\n').count();
+            rest = trimmed;
+
+            let line = newlines + 1;
+            let (field, next, ends_line) = match rest.split_once('\n') {
+                Some((field, next)) => (field, next, true),
+                None => (rest, "", false),
+            };
+            let line_type = field.chars().next();
+            let at = |err| Error::SdpParse {
+                line,
+                line_type,
+                source: Box::new(err),
+            };
+
+            if field.is_empty() {
+                // s9 and s11-s16 are the states in which the input may end
+                return match state {
+                    9 | 11..=16 => Ok(desc),
+                    _ => Err(at(Error::SdpInvalidSyntax(String::new()))),
+                };
+            }
+
+            let (key, value) = match field.find('=') {
+                Some(1) => (
+                    field.as_bytes()[0],
+                    field_value(field.as_bytes()[0], &field[2..]),
+                ),
+                _ => return Err(at(Error::SdpInvalidSyntax(format!("{:?}", field)))),
+            };
+
+            state = desc.unmarshal_field(state, key, value).map_err(at)?;
+
+            if ends_line {
+                newlines += 1;
+            }
+            rest = next;
+        }
+    }
+
+    /// unmarshal_field stores a single "`<key>`=`<value>`" field and returns the
+    /// next state.
+    fn unmarshal_field(&mut self, state: u8, key: u8, value: &'a str) -> Result<u8, Error> {
+        let in_media = state >= 12;
+        let next = match (key, state) {
+            (b'v', 1) => {
+                self.version = value.parse::<Version>()?;
+                2
+            }
+            (b'o', 2) => {
+                self.origin = OriginRef::parse(value)?;
+                3
+            }
+            (b's', 3) => {
+                validate_no_line_breaks(value)?;
+                self.session_name = value;
+                4
+            }
+            (b'i', 4) => {
+                validate_no_line_breaks(value)?;
+                self.session_information = Some(value);
+                7
+            }
+            (b'u', 4 | 7) => {
+                self.uri = Some(value.parse::<Uri>()?);
+                10
+            }
+            (b'e', 4 | 7 | 10) => {
+                self.email_address = Some(value.parse::<EmailAddress>()?);
+                6
+            }
+            (b'p', 4 | 6 | 7 | 10) => {
+                self.phone_number = Some(value.parse::<PhoneNumber>()?);
+                8
+            }
+            (b'c', 4 | 6 | 7 | 8 | 10) => {
+                self.connection_information = Some(ConnectionInformationRef::parse(value)?);
+                5
+            }
+            (b'b', 4..=8 | 10) => {
+                self.bandwidth.push(value.parse::<Bandwidth>()?);
+                5
+            }
+            (b't', 4..=10) => {
+                self.time_descriptions.push(TimeDescription {
+                    timing: value.parse::<Timing>()?,
+                    repeat_times: vec![],
+                });
+                9
+            }
+            (b'r', 9) => {
+                let repeat_time = value.parse::<RepeatTime>()?;
+                match self.time_descriptions.last_mut() {
+                    Some(latest_time_desc) => latest_time_desc.repeat_times.push(repeat_time),
+                    None => return Err(Error::SdpEmptyTimeDescription),
+                }
+                9
+            }
+            (b'z', 9) => {
+                self.time_zones.extend(value.parse::<TimeZones>()?);
+                13
+            }
+            (b'k', 9 | 13) => {
+                self.encryption_key = Some(value.parse::<EncryptionKey>()?);
+                11
+            }
+            (b'k', _) if !in_media && self.encryption_key.is_some() => {
+                return Err(Error::SdpDuplicateField("k=".to_owned()))
+            }
+            (b'a', 9 | 11 | 13) => {
+                self.attributes.push(AttributeRef::parse_line(value)?);
+                11
+            }
+            (b'm', 9 | 11..=16) => {
+                self.media_descriptions.push(MediaDescriptionRef {
+                    media_name: MediaNameRef::parse(value)?,
+                    ..Default::default()
+                });
+                12
+            }
+            (b'i' | b'c' | b'b' | b'k' | b'a', _) if in_media => {
+                let latest_media_desc = self
+                    .media_descriptions
+                    .last_mut()
+                    .ok_or(Error::SdpEmptyTimeDescription)?;
+                match key {
+                    b'i' => {
+                        validate_no_line_breaks(value)?;
+                        latest_media_desc.media_title = Some(value);
+                        16
+                    }
+                    b'c' => {
+                        latest_media_desc.connection_information =
+                            Some(ConnectionInformationRef::parse(value)?);
+                        15
+                    }
+                    b'b' => {
+                        latest_media_desc
+                            .bandwidth
+                            .push(value.parse::<Bandwidth>()?);
+                        15
+                    }
+                    b'k' if latest_media_desc.encryption_key.is_some() => {
+                        return Err(Error::SdpDuplicateField("k=".to_owned()))
+                    }
+                    b'k' => {
+                        latest_media_desc.encryption_key = Some(value.parse::<EncryptionKey>()?);
+                        14
+                    }
+                    _ => {
+                        latest_media_desc
+                            .attributes
+                            .push(AttributeRef::parse_line(value)?);
+                        14
+                    }
+                }
+            }
+            _ => return Err(Error::SdpInvalidSyntax(format!("{}=", key as char))),
+        };
+
+        Ok(next)
+    }
+
+    /// to_owned copies the description into a SessionDescription.
+    pub fn to_owned(&self) -> SessionDescription {
+        SessionDescription {
+            version: self.version,
+            origin: self.origin.to_owned(),
+            session_name: SessionName(self.session_name.as_bytes().to_vec()),
+            session_information: self
+                .session_information
+                .map(|i| Information(i.as_bytes().to_vec())),
+            uri: self.uri.clone(),
+            email_address: self.email_address.clone(),
+            phone_number: self.phone_number.clone(),
+            connection_information: self.connection_information.as_ref().map(|c| c.to_owned()),
+            bandwidth: self.bandwidth.clone(),
+            time_descriptions: self.time_descriptions.clone(),
+            time_zones: self.time_zones.clone(),
+            encryption_key: self.encryption_key.clone(),
+            attributes: self
+                .attributes
+                .iter()
+                .map(|a| a.to_owned())
+                .collect::<Vec<_>>()
+                .into(),
+            media_descriptions: self
+                .media_descriptions
+                .iter()
+                .map(|m| m.to_owned())
+                .collect(),
+            unknown_lines: vec![],
+            line_ending: self.line_ending,
+        }
+    }
+}
+
+/// Reset cleans the SessionDescription, and sets all fields back to their default values
+impl SessionDescription {
+    /// new creates a SessionDescription from its mandatory fields: "o=", "s="
+    /// and the first "t=" block. "v=" is always 0. Optional fields, further
+    /// time descriptions, attributes and media descriptions can be filled in
+    /// afterwards.
+    /// <https://tools.ietf.org/html/rfc4566#section-5>
+    pub fn new(
+        origin: Origin,
+        session_name: SessionName,
+        time_description: TimeDescription,
+    ) -> Self {
+        SessionDescription {
+            version: Version::V0,
+            origin,
+            session_name,
+            time_descriptions: vec![time_description],
+            ..Default::default()
+        }
+    }
+
+    /// charset returns the charset of the text fields given by the session
+    /// level "a=charset:" attribute, UTF-8 if there is none or it is
+    /// malformed. The text fields are decoded lazily with it, so the attribute
+    /// may come after them.
+    /// <https://tools.ietf.org/html/rfc4566#section-6>
+    pub fn charset(&self) -> Charset {
+        self.attributes
+            .get::<Charset>()
+            .next()
+            .and_then(Result::ok)
+            .unwrap_or_default()
+    }
+
+    /// total_bandwidth returns the bandwidth of the given modifier for the
+    /// whole session, in the unit of the modifier: the session level "b="
+    /// field if there is one, or else the sum of the media level ones. It is
+    /// None if a media description which isn't rejected lacks the modifier,
+    /// as the total would be incomplete, or if there are none.
+    pub fn total_bandwidth(&self, kind: BandwidthKind) -> Option<u64> {
+        match self.total_bandwidth_partial(kind) {
+            (total, 0) if self.has_bandwidth(kind) => Some(total),
+            _ => None,
+        }
+    }
 
-// TimeDescription describes "t=", "r=" fields of the session description
-// which are used to specify the start and stop times for a session as well as
-// repeat intervals and durations for the scheduled session.
-#[derive(Debug, Default)]
-pub struct TimeDescription {
-    // t=<start-time> <stop-time>
-    // https://tools.ietf.org/html/rfc4566#section-5.9
-    timing: Timing,
+    /// total_bandwidth_partial is total_bandwidth, summing the media level
+    /// fields which exist. It also returns the number of media descriptions
+    /// which aren't rejected and lack the modifier.
+    pub fn total_bandwidth_partial(&self, kind: BandwidthKind) -> (u64, usize) {
+        if let Some(bandwidth) = self.bandwidth.iter().find(|b| b.kind() == Some(kind)) {
+            return (bandwidth.bandwidth, 0);
+        }
 
-    // r=<repeat interval> <active duration> <offsets from start-time>
-    // https://tools.ietf.org/html/rfc4566#section-5.10
-    repeat_times: Vec<RepeatTime>,
-}
+        let mut total = 0u64;
+        let mut missing = 0;
+        for media_description in self
+            .media_descriptions
+            .iter()
+            .filter(|md| !md.is_rejected())
+        {
+            match media_description.bandwidth(kind) {
+                Some(bandwidth) => total = total.saturating_add(bandwidth),
+                None => missing += 1,
+            }
+        }
+        (total, missing)
+    }
 
-// Timing defines the "t=" field's structured representation for the start and
-// stop times.
-#[derive(Debug, Default)]
-pub struct Timing {
-    start_time: u64,
-    stop_time: u64,
-}
+    fn has_bandwidth(&self, kind: BandwidthKind) -> bool {
+        self.bandwidth.iter().any(|b| b.kind() == Some(kind))
+            || self
+                .media_descriptions
+                .iter()
+                .any(|md| !md.is_rejected() && md.bandwidth(kind).is_some())
+    }
 
-impl fmt::Display for Timing {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {}", self.start_time, self.stop_time)
+    /// ice_credentials_for returns the ICE credentials in effect for the media
+    /// description with the given index. Media level attributes override the
+    /// session level ones; the ufrag and pwd are taken from the same level.
+    /// <https://tools.ietf.org/html/rfc8839#section-5.4>
+    pub fn ice_credentials_for(&self, media_index: usize) -> Option<(IceUfrag, IcePwd)> {
+        self.media_descriptions
+            .get(media_index)
+            .and_then(|md| ice_credentials(&md.attributes))
+            .or_else(|| ice_credentials(&self.attributes))
     }
-}
 
-// RepeatTime describes the "r=" fields of the session description which
-// represents the intervals and durations for repeated scheduled sessions.
-#[derive(Debug, Default)]
-pub struct RepeatTime {
-    interval: i64,
-    duration: i64,
-    offsets: Vec<i64>,
-}
+    /// ice_credentials returns the ICE credentials in effect for the first
+    /// media description, or the session level ones if there is no media.
+    pub fn ice_credentials(&self) -> Option<(IceUfrag, IcePwd)> {
+        self.ice_credentials_for(0)
+    }
 
-impl fmt::Display for RepeatTime {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut fields = vec![];
-        fields.push(format!("{}", self.interval));
-        fields.push(format!("{}", self.duration));
-        for value in &self.offsets {
-            fields.push(format!("{}", value));
+    /// rotate_ice_credentials replaces the ICE credentials at every level
+    /// they are present, e.g. for an ICE restart. If there are none, they are
+    /// added at session level.
+    pub fn rotate_ice_credentials(&mut self, ufrag: IceUfrag, pwd: IcePwd) {
+        let mut rotated = false;
+        let levels = core::iter::once(&mut self.attributes).chain(
+            self.media_descriptions
+                .iter_mut()
+                .map(|md| &mut md.attributes),
+        );
+        for attributes in levels {
+            if has_ice_credentials(attributes) {
+                attributes.replace(ufrag.clone());
+                attributes.replace(pwd.clone());
+                rotated = true;
+            }
+        }
+        if !rotated {
+            self.attributes.replace(ufrag);
+            self.attributes.replace(pwd);
         }
-        write!(f, "{}", fields.join(" "))
     }
-}
 
-// SessionDescription is a a well-defined format for conveying sufficient
-// information to discover and participate in a multimedia session.
-#[derive(Debug, Default)]
-pub struct SessionDescription {
-    // v=0
-    // https://tools.ietf.org/html/rfc4566#section-5.1
-    pub version: Version,
+    /// media_by_label returns the media descriptions with the given
+    /// "a=label:", in order. Labels need not be unique, so there may be more
+    /// than one.
+    /// <https://tools.ietf.org/html/rfc4574#section-4>
+    pub fn media_by_label<'a>(
+        &'a self,
+        label: &'a str,
+    ) -> impl Iterator<Item = &'a MediaDescription> + 'a {
+        self.media_descriptions
+            .iter()
+            .filter(move |md| md.label().is_some_and(|l| l.as_str() == label))
+    }
 
-    // o=<username> <sess-id> <sess-version> <nettype> <addrtype> <unicast-address>
-    // https://tools.ietf.org/html/rfc4566#section-5.2
-    pub origin: Origin,
+    /// media_with_content returns the media descriptions whose "a=content:"
+    /// has the given value, in order.
+    /// <https://tools.ietf.org/html/rfc4796#section-5>
+    pub fn media_with_content(
+        &self,
+        value: ContentValue,
+    ) -> impl Iterator<Item = &MediaDescription> + '_ {
+        self.media_descriptions
+            .iter()
+            .filter(move |md| md.content().is_some_and(|c| c.contains(&value)))
+    }
 
-    // s=<session name>
-    // https://tools.ietf.org/html/rfc4566#section-5.3
-    pub session_name: SessionName,
+    /// groups returns the well-formed "a=group:" attributes.
+    pub fn groups(&self) -> Vec<Group> {
+        self.attributes
+            .get::<Group>()
+            .filter_map(Result::ok)
+            .collect()
+    }
 
-    // i=<session description>
-    // https://tools.ietf.org/html/rfc4566#section-5.4
-    pub session_information: Option<Information>,
+    /// key_mgmt returns the well-formed session level "a=key-mgmt:"
+    /// attributes, in order of preference.
+    pub fn key_mgmt(&self) -> Vec<KeyMgmt> {
+        self.attributes
+            .get::<KeyMgmt>()
+            .filter_map(Result::ok)
+            .collect()
+    }
 
-    // u=<uri>
-    // https://tools.ietf.org/html/rfc4566#section-5.5
-    pub uri: Option<Url>,
+    /// effective_key_mgmt returns the "a=key-mgmt:" attributes in effect for
+    /// the media description with the given index, in order of preference:
+    /// its own ones if it has any, the session level ones otherwise.
+    /// <https://tools.ietf.org/html/rfc4567#section-3.1>
+    pub fn effective_key_mgmt(&self, media_index: usize) -> Vec<KeyMgmt> {
+        match self.media_descriptions.get(media_index) {
+            Some(md) if md.attributes.has(KeyMgmt::NAME) => md.key_mgmt(),
+            Some(_) => self.key_mgmt(),
+            None => vec![],
+        }
+    }
 
-    // e=<email-address>
-    // https://tools.ietf.org/html/rfc4566#section-5.6
-    pub email_address: Option<EmailAddress>,
+    /// extmap_allow_mixed returns true if "a=extmap-allow-mixed" is present at
+    /// session level.
+    pub fn extmap_allow_mixed(&self) -> bool {
+        self.attributes.get::<ExtmapAllowMixed>().any(|a| a.is_ok())
+    }
 
-    // p=<phone-number>
-    // https://tools.ietf.org/html/rfc4566#section-5.6
-    pub phone_number: Option<PhoneNumber>,
+    /// bundle_groups returns the "a=group:BUNDLE" groups.
+    /// <https://tools.ietf.org/html/rfc9143>
+    pub fn bundle_groups(&self) -> Vec<Group> {
+        let mut groups = self.groups();
+        groups.retain(|g| g.semantics == GroupSemantics::Bundle);
+        groups
+    }
 
-    // c=<nettype> <addrtype> <connection-address>
-    // https://tools.ietf.org/html/rfc4566#section-5.7
-    pub connection_information: Option<ConnectionInformation>,
+    /// is_bundled returns true if the mid is in a BUNDLE group.
+    pub fn is_bundled(&self, mid: &str) -> bool {
+        self.bundle_groups().iter().any(|g| g.contains(mid))
+    }
 
-    // b=<bwtype>:<bandwidth>
-    // https://tools.ietf.org/html/rfc4566#section-5.8
-    pub bandwidth: Vec<Bandwidth>,
+    /// reject_media rejects the media description with the given mid, see
+    /// MediaDescription::reject, and removes the mid from the BUNDLE groups.
+    /// Groups left empty are removed.
+    /// <https://tools.ietf.org/html/rfc9143#section-7.3.3>
+    pub fn reject_media(&mut self, mid: &str) -> Result<(), Error> {
+        let media_description = self
+            .media_descriptions
+            .iter_mut()
+            .find(|md| md.mid().is_some_and(|m| m.as_str() == mid))
+            .ok_or_else(|| Error::SdpMidNotFound(mid.to_owned()))?;
+        media_description.reject();
+
+        self.attributes.0.retain_mut(|attribute| {
+            if !Group::matches(attribute) {
+                return true;
+            }
+            let mut group = match Group::from_attribute(attribute) {
+                Ok(group) if group.semantics == GroupSemantics::Bundle => group,
+                _ => return true,
+            };
+            group.mids.retain(|m| m.as_str() != mid);
+            *attribute = group.to_attribute();
+            !group.mids.is_empty()
+        });
 
-    // https://tools.ietf.org/html/rfc4566#section-5.9
-    // https://tools.ietf.org/html/rfc4566#section-5.10
-    pub time_descriptions: Vec<TimeDescription>,
+        Ok(())
+    }
 
-    // z=<adjustment time> <offset> <adjustment time> <offset> ...
-    // https://tools.ietf.org/html/rfc4566#section-5.11
-    pub time_zones: Vec<TimeZone>,
+    /// set_default_destination sets the default destination of the media
+    /// description with the given mid, see
+    /// MediaDescription::set_default_destination. Media descriptions bundled
+    /// with it share its transport, so the other ones of its BUNDLE group are
+    /// rewritten the same way, except for those with port zero, which are
+    /// bundle-only or rejected. A media level "c=" is only added where the
+    /// session level one doesn't match.
+    /// <https://tools.ietf.org/html/rfc9143#section-7.5.1>
+    pub fn set_default_destination(
+        &mut self,
+        mid: &str,
+        addr: IpAddr,
+        rtp_port: u16,
+        rtcp_port: Option<u16>,
+    ) -> Result<(), Error> {
+        if !self
+            .media_descriptions
+            .iter()
+            .any(|md| md.mid().is_some_and(|m| m.as_str() == mid))
+        {
+            return Err(Error::SdpMidNotFound(mid.to_owned()));
+        }
 
-    // k=<method>
-    // k=<method>:<encryption key>
-    // https://tools.ietf.org/html/rfc4566#section-5.12
-    pub encryption_key: Option<EncryptionKey>,
+        let bundle = self.bundle_groups().into_iter().find(|g| g.contains(mid));
+        let session_connection = self.connection_information.clone();
+        for md in &mut self.media_descriptions {
+            let md_mid = match md.mid() {
+                Some(md_mid) => md_mid,
+                None => continue,
+            };
+            let bundled =
+                !md.is_rejected() && bundle.as_ref().is_some_and(|g| g.contains(md_mid.as_str()));
+            if md_mid.as_str() == mid || bundled {
+                md.rewrite_default_destination(
+                    addr,
+                    rtp_port,
+                    rtcp_port,
+                    session_connection.as_ref(),
+                );
+            }
+        }
+        Ok(())
+    }
 
-    // a=<attribute>
-    // a=<attribute>:<value>
-    // https://tools.ietf.org/html/rfc4566#section-5.13
-    pub attributes: Vec<Attribute>,
+    /// wms_stream_ids returns the stream ids of the legacy
+    /// "a=msid-semantic:WMS" attribute, or None if there is none.
+    pub fn wms_stream_ids(&self) -> Option<Vec<String>> {
+        self.wms().map(|semantic| semantic.stream_ids)
+    }
 
-    // https://tools.ietf.org/html/rfc4566#section-5.14
-    pub media_descriptions: Vec<MediaDescription>,
-}
+    fn wms(&self) -> Option<MsidSemantic> {
+        self.attributes
+            .get::<MsidSemantic>()
+            .filter_map(Result::ok)
+            .find(|semantic| semantic.semantic == MSID_SEMANTIC_WMS)
+    }
+
+    /// prepare_reoffer bumps the session version of the "o=" line ahead of
+    /// offering this description again, leaving everything else intact.
+    pub fn prepare_reoffer(&mut self) -> Result<(), Error> {
+        self.origin.bump_version()
+    }
 
-// Reset cleans the SessionDescription, and sets all fields back to their default values
-impl SessionDescription {
     // API to match draft-ietf-rtcweb-jsep
     // Move to webrtc or its own package?
 
-    // NewJSEPSessionDescription creates a new SessionDescription with
-    // some settings that are required by the JSEP spec.
+    /// NewJSEPSessionDescription creates a new SessionDescription with
+    /// some settings that are required by the JSEP spec.
+    #[cfg(feature = "std")]
     pub fn new_jsep_session_description(identity: bool) -> Self {
         let d = SessionDescription {
-            version: 0,
+            version: Version::V0,
             origin: Origin {
-                username: "-".to_string(),
+                username: Username("-".to_owned()),
                 session_id: new_session_id(),
                 session_version: SystemTime::now()
                     .duration_since(UNIX_EPOCH)
                     .expect("Time went backwards")
                     .subsec_nanos() as u64,
-                network_type: "IN".to_string(),
+                network_type: NetType::In,
                 address_type: "IP4".to_string(),
                 unicast_address: "0.0.0.0".to_string(),
             },
-            session_name: "-".to_string(),
+            session_name: SessionName(b"-".to_vec()),
             session_information: None,
             uri: None,
             email_address: None,
@@ -254,10 +2607,12 @@ impl SessionDescription {
                 },
                 repeat_times: vec![],
             }],
-            time_zones: vec![],
+            time_zones: TimeZones::default(),
             encryption_key: None,
-            attributes: vec![], // TODO: implement trickle ICE
+            attributes: Attributes::default(), // TODO: implement trickle ICE
             media_descriptions: vec![],
+            unknown_lines: vec![],
+            line_ending: LineEnding::CrLf,
         };
 
         if identity {
@@ -267,31 +2622,31 @@ impl SessionDescription {
         }
     }
 
-    // WithPropertyAttribute adds a property attribute 'a=key' to the session description
+    /// WithPropertyAttribute adds a property attribute 'a=key' to the session description
     pub fn with_property_attribute(mut self, key: String) -> Self {
         self.attributes.push(Attribute::new(key, None));
         self
     }
 
-    // WithValueAttribute adds a value attribute 'a=key:value' to the session description
+    /// WithValueAttribute adds a value attribute 'a=key:value' to the session description
     pub fn with_value_attribute(mut self, key: String, value: String) -> Self {
         self.attributes.push(Attribute::new(key, Some(value)));
         self
     }
 
-    // WithFingerprint adds a fingerprint to the session description
+    /// WithFingerprint adds a fingerprint to the session description
     pub fn with_fingerprint(self, algorithm: String, value: String) -> Self {
         self.with_value_attribute("fingerprint".to_string(), algorithm + " " + value.as_str())
     }
 
-    // WithMedia adds a media description to the session description
+    /// WithMedia adds a media description to the session description
     pub fn with_media(mut self, md: MediaDescription) -> Self {
         self.media_descriptions.push(md);
         self
     }
 
-    fn build_codec_map(&self) -> HashMap<u8, Codec> {
-        let mut codecs: HashMap<u8, Codec> = HashMap::new();
+    fn build_codec_map(&self) -> BTreeMap<u8, Codec> {
+        let mut codecs: BTreeMap<u8, Codec> = BTreeMap::new();
 
         for m in &self.media_descriptions {
             for a in &m.attributes {
@@ -315,7 +2670,7 @@ impl SessionDescription {
         codecs
     }
 
-    // get_codec_for_payload_type scans the SessionDescription for the given payload type and returns the codec
+    /// get_codec_for_payload_type scans the SessionDescription for the given payload type and returns the codec
     pub fn get_codec_for_payload_type(&self, payload_type: u8) -> Result<Codec, Error> {
         let codecs = self.build_codec_map();
 
@@ -326,8 +2681,8 @@ impl SessionDescription {
         }
     }
 
-    // get_payload_type_for_codec scans the SessionDescription for a codec that matches the provided codec
-    // as closely as possible and returns its payload type
+    /// get_payload_type_for_codec scans the SessionDescription for a codec that matches the provided codec
+    /// as closely as possible and returns its payload type
     pub fn get_payload_type_for_codec(&self, wanted: &Codec) -> Result<u8, Error> {
         let codecs = self.build_codec_map();
 
@@ -339,993 +2694,841 @@ impl SessionDescription {
 
         Err(Error::CodecNotFound)
     }
-    // Marshal takes a SDP struct to text
-    // https://tools.ietf.org/html/rfc4566#section-5
-    // Session description
-    //    v=  (protocol version)
-    //    o=  (originator and session identifier)
-    //    s=  (session name)
-    //    i=* (session information)
-    //    u=* (URI of description)
-    //    e=* (email address)
-    //    p=* (phone number)
-    //    c=* (connection information -- not required if included in
-    //         all media)
-    //    b=* (zero or more bandwidth information lines)
-    //    One or more time descriptions ("t=" and "r=" lines; see below)
-    //    z=* (time zone adjustments)
-    //    k=* (encryption key)
-    //    a=* (zero or more session attribute lines)
-    //    Zero or more media descriptions
-    //
-    // Time description
-    //    t=  (time the session is active)
-    //    r=* (zero or more repeat times)
-    //
-    // Media description, if present
-    //    m=  (media name and transport address)
-    //    i=* (media title)
-    //    c=* (connection information -- optional if included at
-    //         session level)
-    //    b=* (zero or more bandwidth information lines)
-    //    k=* (encryption key)
-    //    a=* (zero or more media attribute lines)
+    /// Marshal takes a SDP struct to text
+    /// <https://tools.ietf.org/html/rfc4566#section-5>
+    /// Session description
+    ///    v=  (protocol version)
+    ///    o=  (originator and session identifier)
+    ///    s=  (session name)
+    ///    i=* (session information)
+    ///    u=* (URI of description)
+    ///    e=* (email address)
+    ///    p=* (phone number)
+    ///    c=* (connection information -- not required if included in
+    ///         all media)
+    ///    b=* (zero or more bandwidth information lines)
+    ///    One or more time descriptions ("t=" and "r=" lines; see below)
+    ///    z=* (time zone adjustments)
+    ///    k=* (encryption key)
+    ///    a=* (zero or more session attribute lines)
+    ///    Zero or more media descriptions
+    ///
+    /// Time description
+    ///    t=  (time the session is active)
+    ///    r=* (zero or more repeat times)
+    ///
+    /// Media description, if present
+    ///    m=  (media name and transport address)
+    ///    i=* (media title)
+    ///    c=* (connection information -- optional if included at
+    ///         session level)
+    ///    b=* (zero or more bandwidth information lines)
+    ///    k=* (encryption key)
+    ///    a=* (zero or more media attribute lines)
     pub fn marshal(&self) -> String {
         let mut result = String::new();
+        self.marshal_into(&mut result);
+        result
+    }
+
+    /// marshal_into appends the marshaled session description to buf. The
+    /// length given by marshal_len_hint is reserved first, so that the fields
+    /// are written without growing buf again.
+    pub fn marshal_into(&self, buf: &mut String) {
+        buf.reserve(self.marshal_len_hint());
+        // Writing to a String never fails.
+        let _ = self.write_to(buf);
+    }
 
-        result += key_value_build("v=", Some(&self.version.to_string())).as_str();
-        result += key_value_build("o=", Some(&self.origin.to_string())).as_str();
-        result += key_value_build("s=", Some(&self.session_name)).as_str();
+    /// write_to writes the marshaled session description to w, field by
+    /// field, without intermediate strings.
+    pub fn write_to<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        match self.line_ending {
+            LineEnding::CrLf => self.write_lines_to(w),
+            LineEnding::Lf => self.write_lines_to(&mut LfWriter(w)),
+        }
+    }
 
-        result += key_value_build("i=", self.session_information.as_ref()).as_str();
+    fn write_lines_to<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        let mut session = UnknownLineWriter::new(w, &self.unknown_lines);
+        self.write_fields_to(&mut session)?;
+        session.finish()?;
+        for media_description in &self.media_descriptions {
+            media_description.write_to(w)?;
+        }
+        Ok(())
+    }
 
+    fn write_fields_to<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        write!(w, "v={}{}", self.version, END_LINE)?;
+        write!(w, "o={}{}", self.origin, END_LINE)?;
+        write!(w, "s={}{}", self.session_name, END_LINE)?;
+        if let Some(session_information) = &self.session_information {
+            write!(w, "i={}{}", session_information, END_LINE)?;
+        }
         if let Some(uri) = &self.uri {
-            result += key_value_build("u=", Some(&format!("{}", uri))).as_str();
+            write!(w, "u={}{}", uri, END_LINE)?;
+        }
+        if let Some(email_address) = &self.email_address {
+            write!(w, "e={}{}", email_address, END_LINE)?;
+        }
+        if let Some(phone_number) = &self.phone_number {
+            write!(w, "p={}{}", phone_number, END_LINE)?;
         }
-        result += key_value_build("e=", self.email_address.as_ref()).as_str();
-        result += key_value_build("p=", self.phone_number.as_ref()).as_str();
         if let Some(connection_information) = &self.connection_information {
-            result += key_value_build("c=", Some(&connection_information.to_string())).as_str();
+            write!(w, "c={}{}", connection_information, END_LINE)?;
         }
-
         for bandwidth in &self.bandwidth {
-            result += key_value_build("b=", Some(&bandwidth.to_string())).as_str();
+            write!(w, "b={}{}", bandwidth, END_LINE)?;
         }
         for time_description in &self.time_descriptions {
-            result += key_value_build("t=", Some(&time_description.timing.to_string())).as_str();
-            for repeat_time in &time_description.repeat_times {
-                result += key_value_build("r=", Some(&repeat_time.to_string())).as_str();
-            }
+            time_description.write_to(w)?;
         }
         if !self.time_zones.is_empty() {
-            let mut time_zones = vec![];
-            for time_zone in &self.time_zones {
-                time_zones.push(time_zone.to_string());
-            }
-            result += key_value_build("z=", Some(&time_zones.join(" "))).as_str();
+            write!(w, "z={}{}", self.time_zones, END_LINE)?;
         }
-        result += key_value_build("k=", self.encryption_key.as_ref()).as_str();
-        for attribute in &self.attributes {
-            result += key_value_build("a=", Some(&attribute.to_string())).as_str();
+        if let Some(encryption_key) = &self.encryption_key {
+            write!(w, "k={}{}", encryption_key, END_LINE)?;
         }
-
-        for media_description in &self.media_descriptions {
-            result +=
-                key_value_build("m=", Some(&media_description.media_name.to_string())).as_str();
-            result += key_value_build("i=", media_description.media_title.as_ref()).as_str();
-            if let Some(connection_information) = &media_description.connection_information {
-                result += key_value_build("c=", Some(&connection_information.to_string())).as_str();
-            }
-            for bandwidth in &media_description.bandwidth {
-                result += key_value_build("b=", Some(&bandwidth.to_string())).as_str();
-            }
-            result += key_value_build("k=", media_description.encryption_key.as_ref()).as_str();
-            for attribute in &media_description.attributes {
-                result += key_value_build("a=", Some(&attribute.to_string())).as_str();
-            }
+        for attribute in &self.attributes {
+            write!(w, "a={}{}", attribute, END_LINE)?;
         }
-
-        result
+        Ok(())
     }
 
-    // Unmarshal is the primary function that deserializes the session description
-    // message and stores it inside of a structured SessionDescription object.
-    //
-    // The States Transition Table describes the computation flow between functions
-    // (namely s1, s2, s3, ...) for a parsing procedure that complies with the
-    // specifications laid out by the rfc4566#section-5 as well as by JavaScript
-    // Session Establishment Protocol draft. Links:
-    // 		https://tools.ietf.org/html/rfc4566#section-5
-    // 		https://tools.ietf.org/html/draft-ietf-rtcweb-jsep-24
-    //
-    // https://tools.ietf.org/html/rfc4566#section-5
-    // Session description
-    //    v=  (protocol version)
-    //    o=  (originator and session identifier)
-    //    s=  (session name)
-    //    i=* (session information)
-    //    u=* (URI of description)
-    //    e=* (email address)
-    //    p=* (phone number)
-    //    c=* (connection information -- not required if included in
-    //         all media)
-    //    b=* (zero or more bandwidth information lines)
-    //    One or more time descriptions ("t=" and "r=" lines; see below)
-    //    z=* (time zone adjustments)
-    //    k=* (encryption key)
-    //    a=* (zero or more session attribute lines)
-    //    Zero or more media descriptions
-    //
-    // Time description
-    //    t=  (time the session is active)
-    //    r=* (zero or more repeat times)
-    //
-    // Media description, if present
-    //    m=  (media name and transport address)
-    //    i=* (media title)
-    //    c=* (connection information -- optional if included at
-    //         session level)
-    //    b=* (zero or more bandwidth information lines)
-    //    k=* (encryption key)
-    //    a=* (zero or more media attribute lines)
-    //
-    // In order to generate the following state table and draw subsequent
-    // deterministic finite-state automota ("DFA") the following regex was used to
-    // derive the DFA:
-    //    vosi?u?e?p?c?b*(tr*)+z?k?a*(mi?c?b*k?a*)*
-    // possible place and state to exit:
-    //                    **   * * *  ** * * * *
-    //                    99   1 1 1  11 1 1 1 1
-    //                         3 1 1  26 5 5 4 4
-    //
-    // Please pay close attention to the `k`, and `a` parsing states. In the table
-    // below in order to distinguish between the states belonging to the media
-    // description as opposed to the session description, the states are marked
-    // with an asterisk ("a*", "k*").
-    // +--------+----+-------+----+-----+----+-----+---+----+----+---+---+-----+---+---+----+---+----+
-    // | STATES | a* | a*,k* | a  | a,k | b  | b,c | e | i  | m  | o | p | r,t | s | t | u  | v | z  |
-    // +--------+----+-------+----+-----+----+-----+---+----+----+---+---+-----+---+---+----+---+----+
-    // |   s1   |    |       |    |     |    |     |   |    |    |   |   |     |   |   |    | 2 |    |
-    // |   s2   |    |       |    |     |    |     |   |    |    | 3 |   |     |   |   |    |   |    |
-    // |   s3   |    |       |    |     |    |     |   |    |    |   |   |     | 4 |   |    |   |    |
-    // |   s4   |    |       |    |     |    |   5 | 6 |  7 |    |   | 8 |     |   | 9 | 10 |   |    |
-    // |   s5   |    |       |    |     |  5 |     |   |    |    |   |   |     |   | 9 |    |   |    |
-    // |   s6   |    |       |    |     |    |   5 |   |    |    |   | 8 |     |   | 9 |    |   |    |
-    // |   s7   |    |       |    |     |    |   5 | 6 |    |    |   | 8 |     |   | 9 | 10 |   |    |
-    // |   s8   |    |       |    |     |    |   5 |   |    |    |   |   |     |   | 9 |    |   |    |
-    // |   s9   |    |       |    |  11 |    |     |   |    | 12 |   |   |   9 |   |   |    |   | 13 |
-    // |   s10  |    |       |    |     |    |   5 | 6 |    |    |   | 8 |     |   | 9 |    |   |    |
-    // |   s11  |    |       | 11 |     |    |     |   |    | 12 |   |   |     |   |   |    |   |    |
-    // |   s12  |    |    14 |    |     |    |  15 |   | 16 | 12 |   |   |     |   |   |    |   |    |
-    // |   s13  |    |       |    |  11 |    |     |   |    | 12 |   |   |     |   |   |    |   |    |
-    // |   s14  | 14 |       |    |     |    |     |   |    | 12 |   |   |     |   |   |    |   |    |
-    // |   s15  |    |    14 |    |     | 15 |     |   |    | 12 |   |   |     |   |   |    |   |    |
-    // |   s16  |    |    14 |    |     |    |  15 |   |    | 12 |   |   |     |   |   |    |   |    |
-    // +--------+----+-------+----+-----+----+-----+---+----+----+---+---+-----+---+---+----+---+----+
-    pub fn unmarshal<R: io::BufRead + io::Seek>(reader: &mut R) -> Result<Self, Error> {
-        let mut lexer = Lexer {
-            desc: SessionDescription {
-                version: 0,
-                origin: Origin::new(),
-                session_name: "".to_owned(),
-                session_information: None,
-                uri: None,
-                email_address: None,
-                phone_number: None,
-                connection_information: None,
-                bandwidth: vec![],
-                time_descriptions: vec![],
-                time_zones: vec![],
-                encryption_key: None,
-                attributes: vec![],
-                media_descriptions: vec![],
-            },
-            reader,
-        };
-
-        let mut state = Some(StateFn { f: s1 });
-        while let Some(s) = state {
-            state = (s.f)(&mut lexer)?;
-        }
-
-        Ok(lexer.desc)
+    /// line_count returns the number of session-level lines written by
+    /// write_to, without the unknown lines.
+    pub(crate) fn line_count(&self) -> usize {
+        3 + usize::from(self.session_information.is_some())
+            + usize::from(self.uri.is_some())
+            + usize::from(self.email_address.is_some())
+            + usize::from(self.phone_number.is_some())
+            + usize::from(self.connection_information.is_some())
+            + self.bandwidth.len()
+            + self
+                .time_descriptions
+                .iter()
+                .map(|t| 1 + t.repeat_times.len())
+                .sum::<usize>()
+            + usize::from(!self.time_zones.is_empty())
+            + usize::from(self.encryption_key.is_some())
+            + self.attributes.len()
     }
-}
-
-fn s1<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (key, _) = read_type(lexer.reader)?;
-    if &key == "v=" {
-        return Ok(Some(StateFn {
-            f: unmarshal_protocol_version,
-        }));
-    }
-
-    Err(Error::SdpInvalidSyntax(key))
-}
-
-fn s2<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (key, _) = read_type(lexer.reader)?;
-    if &key == "o=" {
-        return Ok(Some(StateFn {
-            f: unmarshal_origin,
-        }));
-    }
-
-    Err(Error::SdpInvalidSyntax(key))
-}
-
-fn s3<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (key, _) = read_type(lexer.reader)?;
-    if &key == "s=" {
-        return Ok(Some(StateFn {
-            f: unmarshal_session_name,
-        }));
-    }
-
-    Err(Error::SdpInvalidSyntax(key))
-}
-
-fn s4<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (key, _) = read_type(lexer.reader)?;
-    match key.as_str() {
-        "i=" => Ok(Some(StateFn {
-            f: unmarshal_session_information,
-        })),
-        "u=" => Ok(Some(StateFn { f: unmarshal_uri })),
-        "e=" => Ok(Some(StateFn { f: unmarshal_email })),
-        "p=" => Ok(Some(StateFn { f: unmarshal_phone })),
-        "c=" => Ok(Some(StateFn {
-            f: unmarshal_session_connection_information,
-        })),
-        "b=" => Ok(Some(StateFn {
-            f: unmarshal_session_bandwidth,
-        })),
-        "t=" => Ok(Some(StateFn {
-            f: unmarshal_timing,
-        })),
-        _ => Err(Error::SdpInvalidSyntax(key)),
-    }
-}
-
-fn s5<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (key, _) = read_type(lexer.reader)?;
-    match key.as_str() {
-        "b=" => Ok(Some(StateFn {
-            f: unmarshal_session_bandwidth,
-        })),
-        "t=" => Ok(Some(StateFn {
-            f: unmarshal_timing,
-        })),
-        _ => Err(Error::SdpInvalidSyntax(key)),
-    }
-}
-
-fn s6<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (key, _) = read_type(lexer.reader)?;
-    match key.as_str() {
-        "p=" => Ok(Some(StateFn { f: unmarshal_phone })),
-        "c=" => Ok(Some(StateFn {
-            f: unmarshal_session_connection_information,
-        })),
-        "b=" => Ok(Some(StateFn {
-            f: unmarshal_session_bandwidth,
-        })),
-        "t=" => Ok(Some(StateFn {
-            f: unmarshal_timing,
-        })),
-        _ => Err(Error::SdpInvalidSyntax(key)),
-    }
-}
-
-fn s7<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (key, _) = read_type(lexer.reader)?;
-    match key.as_str() {
-        "u=" => Ok(Some(StateFn { f: unmarshal_uri })),
-        "e=" => Ok(Some(StateFn { f: unmarshal_email })),
-        "p=" => Ok(Some(StateFn { f: unmarshal_phone })),
-        "c=" => Ok(Some(StateFn {
-            f: unmarshal_session_connection_information,
-        })),
-        "b=" => Ok(Some(StateFn {
-            f: unmarshal_session_bandwidth,
-        })),
-        "t=" => Ok(Some(StateFn {
-            f: unmarshal_timing,
-        })),
-        _ => Err(Error::SdpInvalidSyntax(key)),
-    }
-}
-
-fn s8<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (key, _) = read_type(lexer.reader)?;
-    match key.as_str() {
-        "c=" => Ok(Some(StateFn {
-            f: unmarshal_session_connection_information,
-        })),
-        "b=" => Ok(Some(StateFn {
-            f: unmarshal_session_bandwidth,
-        })),
-        "t=" => Ok(Some(StateFn {
-            f: unmarshal_timing,
-        })),
-        _ => Err(Error::SdpInvalidSyntax(key)),
-    }
-}
-
-fn s9<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (key, num_bytes) = read_type(lexer.reader)?;
-    if &key == "" && num_bytes == 0 {
-        return Ok(None);
-    }
-
-    match key.as_str() {
-        "z=" => Ok(Some(StateFn {
-            f: unmarshal_time_zones,
-        })),
-        "k=" => Ok(Some(StateFn {
-            f: unmarshal_session_encryption_key,
-        })),
-        "a=" => Ok(Some(StateFn {
-            f: unmarshal_session_attribute,
-        })),
-        "r=" => Ok(Some(StateFn {
-            f: unmarshal_repeat_times,
-        })),
-        "t=" => Ok(Some(StateFn {
-            f: unmarshal_timing,
-        })),
-        "m=" => Ok(Some(StateFn {
-            f: unmarshal_media_description,
-        })),
-        _ => Err(Error::SdpInvalidSyntax(key)),
-    }
-}
-
-fn s10<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (key, _) = read_type(lexer.reader)?;
-    match key.as_str() {
-        "e=" => Ok(Some(StateFn { f: unmarshal_email })),
-        "p=" => Ok(Some(StateFn { f: unmarshal_phone })),
-        "c=" => Ok(Some(StateFn {
-            f: unmarshal_session_connection_information,
-        })),
-        "b=" => Ok(Some(StateFn {
-            f: unmarshal_session_bandwidth,
-        })),
-        "t=" => Ok(Some(StateFn {
-            f: unmarshal_timing,
-        })),
-        _ => Err(Error::SdpInvalidSyntax(key)),
-    }
-}
-
-fn s11<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (key, num_bytes) = read_type(lexer.reader)?;
-    if &key == "" && num_bytes == 0 {
-        return Ok(None);
-    }
-
-    match key.as_str() {
-        "a=" => Ok(Some(StateFn {
-            f: unmarshal_session_attribute,
-        })),
-        "m=" => Ok(Some(StateFn {
-            f: unmarshal_media_description,
-        })),
-        _ => Err(Error::SdpInvalidSyntax(key)),
-    }
-}
-
-fn s12<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (key, num_bytes) = read_type(lexer.reader)?;
-    if &key == "" && num_bytes == 0 {
-        return Ok(None);
-    }
-
-    match key.as_str() {
-        "a=" => Ok(Some(StateFn {
-            f: unmarshal_media_attribute,
-        })),
-        "k=" => Ok(Some(StateFn {
-            f: unmarshal_media_encryption_key,
-        })),
-        "b=" => Ok(Some(StateFn {
-            f: unmarshal_media_bandwidth,
-        })),
-        "c=" => Ok(Some(StateFn {
-            f: unmarshal_media_connection_information,
-        })),
-        "i=" => Ok(Some(StateFn {
-            f: unmarshal_media_title,
-        })),
-        "m=" => Ok(Some(StateFn {
-            f: unmarshal_media_description,
-        })),
-        _ => Err(Error::SdpInvalidSyntax(key)),
-    }
-}
-
-fn s13<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (key, num_bytes) = read_type(lexer.reader)?;
-    if &key == "" && num_bytes == 0 {
-        return Ok(None);
-    }
-
-    match key.as_str() {
-        "a=" => Ok(Some(StateFn {
-            f: unmarshal_session_attribute,
-        })),
-        "k=" => Ok(Some(StateFn {
-            f: unmarshal_session_encryption_key,
-        })),
-        "m=" => Ok(Some(StateFn {
-            f: unmarshal_media_description,
-        })),
-        _ => Err(Error::SdpInvalidSyntax(key)),
-    }
-}
-
-fn s14<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (key, num_bytes) = read_type(lexer.reader)?;
-    if &key == "" && num_bytes == 0 {
-        return Ok(None);
-    }
-
-    match key.as_str() {
-        "a=" => Ok(Some(StateFn {
-            f: unmarshal_media_attribute,
-        })),
-        // Non-spec ordering
-        "k=" => Ok(Some(StateFn {
-            f: unmarshal_media_encryption_key,
-        })),
-        // Non-spec ordering
-        "b=" => Ok(Some(StateFn {
-            f: unmarshal_media_bandwidth,
-        })),
-        // Non-spec ordering
-        "c=" => Ok(Some(StateFn {
-            f: unmarshal_media_connection_information,
-        })),
-        // Non-spec ordering
-        "i=" => Ok(Some(StateFn {
-            f: unmarshal_media_title,
-        })),
-        "m=" => Ok(Some(StateFn {
-            f: unmarshal_media_description,
-        })),
-        _ => Err(Error::SdpInvalidSyntax(key)),
-    }
-}
-
-fn s15<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (key, num_bytes) = read_type(lexer.reader)?;
-    if &key == "" && num_bytes == 0 {
-        return Ok(None);
-    }
-
-    match key.as_str() {
-        "a=" => Ok(Some(StateFn {
-            f: unmarshal_media_attribute,
-        })),
-        "k=" => Ok(Some(StateFn {
-            f: unmarshal_media_encryption_key,
-        })),
-        "b=" => Ok(Some(StateFn {
-            f: unmarshal_media_bandwidth,
-        })),
-        "c=" => Ok(Some(StateFn {
-            f: unmarshal_media_connection_information,
-        })),
-        // Non-spec ordering
-        "i=" => Ok(Some(StateFn {
-            f: unmarshal_media_title,
-        })),
-        "m=" => Ok(Some(StateFn {
-            f: unmarshal_media_description,
-        })),
-        _ => Err(Error::SdpInvalidSyntax(key)),
-    }
-}
-
-fn s16<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (key, num_bytes) = read_type(lexer.reader)?;
-    if &key == "" && num_bytes == 0 {
-        return Ok(None);
-    }
-
-    match key.as_str() {
-        "a=" => Ok(Some(StateFn {
-            f: unmarshal_media_attribute,
-        })),
-        "k=" => Ok(Some(StateFn {
-            f: unmarshal_media_encryption_key,
-        })),
-        "c=" => Ok(Some(StateFn {
-            f: unmarshal_media_connection_information,
-        })),
-        "b=" => Ok(Some(StateFn {
-            f: unmarshal_media_bandwidth,
-        })),
-        // Non-spec ordering
-        "i=" => Ok(Some(StateFn {
-            f: unmarshal_media_title,
-        })),
-        "m=" => Ok(Some(StateFn {
-            f: unmarshal_media_description,
-        })),
-        _ => Err(Error::SdpInvalidSyntax(key)),
-    }
-}
-
-fn unmarshal_protocol_version<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
-
-    let version = value.parse::<u32>()?;
-
-    // As off the latest draft of the rfc this value is required to be 0.
-    // https://tools.ietf.org/html/draft-ietf-rtcweb-jsep-24#section-5.8.1
-    if version != 0 {
-        return Err(Error::SdpInvalidSyntax(value));
-    }
-
-    Ok(Some(StateFn { f: s2 }))
-}
-
-fn unmarshal_origin<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
-
-    let fields: Vec<&str> = value.split_whitespace().collect();
-    if fields.len() != 6 {
-        return Err(Error::SdpInvalidSyntax(format!("`o={}`", value)));
-    }
-
-    let session_id = fields[1].parse::<u64>()?;
-    let session_version = fields[2].parse::<u64>()?;
-
-    // Set according to currently registered with IANA
-    // https://tools.ietf.org/html/rfc4566#section-8.2.6
-    let i = index_of(fields[3], &["IN"]);
-    if i == -1 {
-        return Err(Error::SdpInvalidValue(fields[3].to_owned()));
-    }
-
-    // Set according to currently registered with IANA
-    // https://tools.ietf.org/html/rfc4566#section-8.2.7
-    let i = index_of(fields[4], &["IP4", "IP6"]);
-    if i == -1 {
-        return Err(Error::SdpInvalidValue(fields[4].to_owned()));
-    }
-
-    // TODO validated UnicastAddress
-
-    lexer.desc.origin = Origin {
-        username: fields[0].to_owned(),
-        session_id,
-        session_version,
-        network_type: fields[3].to_owned(),
-        address_type: fields[4].to_owned(),
-        unicast_address: fields[5].to_owned(),
-    };
-
-    Ok(Some(StateFn { f: s3 }))
-}
-
-fn unmarshal_session_name<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
-    lexer.desc.session_name = value;
-    Ok(Some(StateFn { f: s4 }))
-}
-
-fn unmarshal_session_information<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
-    lexer.desc.session_information = Some(value);
-    Ok(Some(StateFn { f: s7 }))
-}
-
-fn unmarshal_uri<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
-    lexer.desc.uri = Some(Url::parse(&value)?);
-    Ok(Some(StateFn { f: s10 }))
-}
-
-fn unmarshal_email<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
-    lexer.desc.email_address = Some(value);
-    Ok(Some(StateFn { f: s6 }))
-}
-
-fn unmarshal_phone<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
-    lexer.desc.phone_number = Some(value);
-    Ok(Some(StateFn { f: s8 }))
-}
-
-fn unmarshal_session_connection_information<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
-    lexer.desc.connection_information = unmarshal_connection_information(&value)?;
-    Ok(Some(StateFn { f: s5 }))
-}
 
-fn unmarshal_connection_information(value: &str) -> Result<Option<ConnectionInformation>, Error> {
-    let fields: Vec<&str> = value.split_whitespace().collect();
-    if fields.len() < 2 {
-        return Err(Error::SdpInvalidSyntax(format!("`c={}`", value)));
+    /// marshal_len_hint returns the length of the marshaled session
+    /// description. It is exact unless a text field isn't UTF-8.
+    pub fn marshal_len_hint(&self) -> usize {
+        let len = self.crlf_len();
+        match self.line_ending {
+            LineEnding::CrLf => len,
+            LineEnding::Lf => {
+                len - self.line_count()
+                    - self.unknown_lines.len()
+                    - self
+                        .media_descriptions
+                        .iter()
+                        .map(|md| md.line_count() + md.unknown_lines.len())
+                        .sum::<usize>()
+            }
+        }
     }
 
-    // Set according to currently registered with IANA
-    // https://tools.ietf.org/html/rfc4566#section-8.2.6
-    let i = index_of(fields[0], &["IN"]);
-    if i == -1 {
-        return Err(Error::SdpInvalidValue(fields[0].to_owned()));
+    /// crlf_len is marshal_len_hint with every line ending in CRLF.
+    fn crlf_len(&self) -> usize {
+        let display_name_len =
+            |display_name: &Option<String>| display_name.as_ref().map_or(0, |n| n.len() + 3);
+        let time_zones_len = self
+            .time_zones
+            .iter()
+            .map(|z| 1 + decimal_len(z.adjustment_time) + 1 + signed_decimal_len(z.offset))
+            .sum::<usize>();
+
+        line_len(decimal_len(self.version.get()))
+            + line_len(self.origin.marshal_len_hint())
+            + line_len(self.session_name.as_bytes().len())
+            + self
+                .session_information
+                .as_ref()
+                .map_or(0, |i| line_len(i.as_bytes().len()))
+            + self.uri.as_ref().map_or(0, |u| line_len(u.raw.len()))
+            + self.email_address.as_ref().map_or(0, |e| {
+                line_len(e.address.len() + display_name_len(&e.display_name))
+            })
+            + self.phone_number.as_ref().map_or(0, |p| {
+                line_len(p.number.len() + display_name_len(&p.display_name))
+            })
+            + self
+                .connection_information
+                .as_ref()
+                .map_or(0, |c| line_len(c.marshal_len_hint()))
+            + self
+                .bandwidth
+                .iter()
+                .map(|b| line_len(b.marshal_len_hint()))
+                .sum::<usize>()
+            + self
+                .time_descriptions
+                .iter()
+                .map(TimeDescription::marshal_len_hint)
+                .sum::<usize>()
+            + if self.time_zones.is_empty() {
+                0
+            } else {
+                line_len(time_zones_len - 1)
+            }
+            + self
+                .encryption_key
+                .as_ref()
+                .map_or(0, |k| line_len(k.marshal_len_hint()))
+            + self
+                .attributes
+                .iter()
+                .map(|a| line_len(a.marshal_len_hint()))
+                .sum::<usize>()
+            + self
+                .unknown_lines
+                .iter()
+                .map(|l| l.marshal_len_hint() + 2)
+                .sum::<usize>()
+            + self
+                .media_descriptions
+                .iter()
+                .map(MediaDescription::marshal_len_hint)
+                .sum::<usize>()
     }
 
-    // Set according to currently registered with IANA
-    // https://tools.ietf.org/html/rfc4566#section-8.2.7
-    let i = index_of(fields[1], &["IP4", "IP6"]);
-    if i == -1 {
-        return Err(Error::SdpInvalidValue(fields[1].to_owned()));
+    /// to_bytes returns the marshaled session description, with every line
+    /// terminated by its line_ending.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.marshal().into_bytes()
     }
 
-    let address = if fields.len() > 2 {
-        Some(Address {
-            address: fields[2].to_owned(),
-            ttl: None,
-            range: None,
-        })
-    } else {
-        None
-    };
+    /// validate runs a strict validation pass, meant to be used before sending
+    /// a description to picky endpoints; parsing itself is permissive. It
+    /// returns every finding, including those of MediaDescription::validate.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+
+        if let Err(err) = self.check_line_breaks() {
+            let field = match &err {
+                Error::SdpUnexpectedLineBreak(field) => field.as_str(),
+                _ => "",
+            };
+            diagnostics.push(Diagnostic::error(
+                DiagnosticKind::UnexpectedLineBreak,
+                field,
+                err.to_string(),
+            ));
+        }
 
-    Ok(Some(ConnectionInformation {
-        network_type: fields[0].to_owned(),
-        address_type: fields[1].to_owned(),
-        address,
-    }))
-}
+        if self.time_descriptions.is_empty() {
+            diagnostics.push(Diagnostic::error(
+                DiagnosticKind::MissingTiming,
+                "t=",
+                "no time description".to_owned(),
+            ));
+        }
 
-fn unmarshal_session_bandwidth<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
-    lexer.desc.bandwidth.push(unmarshal_bandwidth(&value)?);
-    Ok(Some(StateFn { f: s5 }))
-}
+        let ct_count = self
+            .bandwidth
+            .iter()
+            .filter(|b| !b.experimental && b.bandwidth_type == BANDWIDTH_TYPE_CT)
+            .count();
+        if ct_count > 1 {
+            diagnostics.push(Diagnostic::error(
+                DiagnosticKind::DuplicateConferenceTotalBandwidth,
+                "b=",
+                format!("b=CT appears {} times", ct_count),
+            ));
+        }
+        diagnostics.extend(check_duplicate_bandwidth(&self.bandwidth, true));
+        diagnostics.extend(check_basic_attributes(&self.attributes));
 
-fn unmarshal_bandwidth(value: &str) -> Result<Bandwidth, Error> {
-    let mut parts: Vec<&str> = value.split(':').collect();
-    if parts.len() != 2 {
-        return Err(Error::SdpInvalidSyntax(format!("`b={}`", value)));
-    }
+        if let Some(encryption_key) = &self.encryption_key {
+            diagnostics.push(check_encryption_key(encryption_key));
+            if self.attributes.has(KeyMgmt::NAME) {
+                diagnostics.push(encryption_key_with_key_mgmt());
+            }
+        }
 
-    let experimental = parts[0].starts_with("X-");
-    if experimental {
-        parts[0] = parts[0].trim_start_matches("X-");
-    } else {
-        // Set according to currently registered with IANA
-        // https://tools.ietf.org/html/rfc4566#section-5.8
-        let i = index_of(parts[0], &["CT", "AS"]);
-        if i == -1 {
-            return Err(Error::SdpInvalidValue(parts[0].to_owned()));
+        for (index, media_description) in self.media_descriptions.iter().enumerate() {
+            // Some stacks omit "c=" from rejected media descriptions.
+            if self.connection_information.is_none()
+                && media_description.connection_information.is_none()
+                && !media_description.is_rejected()
+            {
+                diagnostics.push(
+                    Diagnostic::error(
+                        DiagnosticKind::MissingConnectionInformation,
+                        "c=",
+                        "no c= field at session or media level".to_owned(),
+                    )
+                    .in_media(index),
+                );
+            }
+            diagnostics.extend(
+                media_description
+                    .validate()
+                    .into_iter()
+                    .map(|d| d.in_media(index)),
+            );
+            // Keys from different levels; the same level is checked above.
+            let media_key_mgmt = media_description.attributes.has(KeyMgmt::NAME);
+            let mixed_levels = match &media_description.encryption_key {
+                Some(_) => !media_key_mgmt && self.attributes.has(KeyMgmt::NAME),
+                None => self.encryption_key.is_some() && media_key_mgmt,
+            };
+            if mixed_levels {
+                diagnostics.push(encryption_key_with_key_mgmt().in_media(index));
+            }
         }
-    }
 
-    let bandwidth = parts[1].parse::<u64>()?;
+        diagnostics.extend(self.check_groups());
+        diagnostics.extend(self.check_two_byte_extmaps());
+
+        // RFC 8830 deprecates the session level line, so a stream missing
+        // from it is only a warning.
+        if let Some(wms) = self.wms() {
+            for (index, media_description) in self.media_descriptions.iter().enumerate() {
+                let stream_id = match media_description.msid().and_then(|m| m.stream_id) {
+                    Some(stream_id) => stream_id,
+                    None => continue,
+                };
+                if !wms.contains(&stream_id) {
+                    diagnostics.push(
+                        Diagnostic::warning(
+                            DiagnosticKind::MsidStreamNotInWms,
+                            "a=msid",
+                            format!("stream `{}` is not in msid-semantic WMS", stream_id),
+                        )
+                        .in_media(index),
+                    );
+                }
+            }
+        }
 
-    Ok(Bandwidth {
-        experimental,
-        bandwidth_type: parts[0].to_owned(),
-        bandwidth,
-    })
-}
+        diagnostics
+    }
 
-fn unmarshal_timing<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
+    /// check_two_byte_extmaps reports the extmaps needing a two-byte header
+    /// when mixed extmaps aren't allowed for their media description.
+    fn check_two_byte_extmaps(&self) -> Vec<Diagnostic> {
+        let check = |attributes: &Attributes| -> Vec<Diagnostic> {
+            attributes
+                .get::<ExtMap>()
+                .filter_map(Result::ok)
+                .filter(|e| e.id.requires_two_byte_header())
+                .map(|e| {
+                    Diagnostic::warning(
+                        DiagnosticKind::TwoByteExtMapWithoutAllowMixed,
+                        "a=extmap",
+                        format!("extmap id {} without extmap-allow-mixed", e.id),
+                    )
+                })
+                .collect()
+        };
 
-    let fields: Vec<&str> = value.split_whitespace().collect();
-    if fields.len() < 2 {
-        return Err(Error::SdpInvalidSyntax(format!("`t={}`", value)));
+        if self.extmap_allow_mixed() {
+            return vec![];
+        }
+        let mut diagnostics = vec![];
+        if self
+            .media_descriptions
+            .iter()
+            .any(|md| !md.extmap_allow_mixed())
+        {
+            diagnostics.extend(check(&self.attributes));
+        }
+        for (index, media_description) in self.media_descriptions.iter().enumerate() {
+            if !media_description.extmap_allow_mixed() {
+                diagnostics.extend(
+                    check(&media_description.attributes)
+                        .into_iter()
+                        .map(|d| d.in_media(index)),
+                );
+            }
+        }
+        diagnostics
     }
 
-    let start_time = fields[0].parse::<u64>()?;
-    let stop_time = fields[1].parse::<u64>()?;
-
-    lexer.desc.time_descriptions.push(TimeDescription {
-        timing: Timing {
-            start_time,
-            stop_time,
-        },
-        repeat_times: vec![],
-    });
+    /// check_groups cross-checks the mids of "a=group:" attributes and media
+    /// descriptions.
+    fn check_groups(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+        let groups = self.groups();
+        if groups.is_empty() {
+            return diagnostics;
+        }
 
-    Ok(Some(StateFn { f: s9 }))
-}
+        let mids: Vec<_> = self
+            .media_descriptions
+            .iter()
+            .map(MediaDescription::mid)
+            .collect();
+
+        for group in &groups {
+            for mid in &group.mids {
+                if !mids.contains(&Some(mid.clone())) {
+                    diagnostics.push(Diagnostic::error(
+                        DiagnosticKind::UnknownGroupMid,
+                        "a=group",
+                        format!("{} group refers to unknown mid `{}`", group.semantics, mid),
+                    ));
+                }
+            }
+        }
 
-fn unmarshal_repeat_times<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
+        // Rejected media descriptions leave their groups.
+        for (index, mid) in mids.iter().enumerate() {
+            if let Some(mid) = mid {
+                if !self.media_descriptions[index].is_rejected()
+                    && !groups.iter().any(|g| g.contains(mid.as_str()))
+                {
+                    diagnostics.push(
+                        Diagnostic::warning(
+                            DiagnosticKind::UngroupedMid,
+                            "a=mid",
+                            format!("mid `{}` is in no group", mid),
+                        )
+                        .in_media(index),
+                    );
+                }
+            }
+        }
 
-    let fields: Vec<&str> = value.split_whitespace().collect();
-    if fields.len() < 3 {
-        return Err(Error::SdpInvalidSyntax(format!("`r={}`", value)));
+        diagnostics
     }
 
-    if let Some(latest_time_desc) = lexer.desc.time_descriptions.last_mut() {
-        let interval = parse_time_units(fields[0])?;
-        let duration = parse_time_units(fields[1])?;
-        let mut offsets = vec![];
-        for field in fields.iter().skip(2) {
-            let offset = parse_time_units(field)?;
-            offsets.push(offset);
+    /// check_line_breaks checks that none of the free-text fields would break
+    /// the line structure when marshaled. The typed fields (URI, email, phone
+    /// and attributes parsed from text) already reject line breaks when they
+    /// are constructed, but plain strings set directly on the struct are not.
+    pub fn check_line_breaks(&self) -> Result<(), Error> {
+        check_line_value("o=", &self.origin.to_string())?;
+        check_line_value("s=", &self.session_name.to_string())?;
+        if let Some(session_information) = &self.session_information {
+            check_line_value("i=", &session_information.to_string())?;
+        }
+        if let Some(uri) = &self.uri {
+            check_line_value("u=", &uri.to_string())?;
+        }
+        if let Some(email_address) = &self.email_address {
+            check_line_value("e=", &email_address.to_string())?;
+        }
+        if let Some(phone_number) = &self.phone_number {
+            check_line_value("p=", &phone_number.to_string())?;
+        }
+        if let Some(connection_information) = &self.connection_information {
+            check_line_value("c=", &connection_information.to_string())?;
+        }
+        for bandwidth in &self.bandwidth {
+            check_line_value("b=", &bandwidth.to_string())?;
+        }
+        if let Some(encryption_key) = &self.encryption_key {
+            check_line_value("k=", &encryption_key.to_string())?;
+        }
+        for attribute in &self.attributes {
+            check_line_value("a=", &attribute.to_string())?;
         }
-        latest_time_desc.repeat_times.push(RepeatTime {
-            interval,
-            duration,
-            offsets,
-        });
-
-        Ok(Some(StateFn { f: s9 }))
-    } else {
-        Err(Error::SdpEmptyTimeDescription)
-    }
-}
 
-fn unmarshal_time_zones<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
+        for media_description in &self.media_descriptions {
+            check_line_value("m=", &media_description.media_name.to_string())?;
+            if let Some(media_title) = &media_description.media_title {
+                check_line_value("i=", &media_title.to_string())?;
+            }
+            if let Some(connection_information) = &media_description.connection_information {
+                check_line_value("c=", &connection_information.to_string())?;
+            }
+            for bandwidth in &media_description.bandwidth {
+                check_line_value("b=", &bandwidth.to_string())?;
+            }
+            if let Some(encryption_key) = &media_description.encryption_key {
+                check_line_value("k=", &encryption_key.to_string())?;
+            }
+            for attribute in &media_description.attributes {
+                check_line_value("a=", &attribute.to_string())?;
+            }
+        }
 
-    // These fields are transimitted in pairs
-    // z=<adjustment time> <offset> <adjustment time> <offset> ....
-    // so we are making sure that there are actually multiple of 2 total.
-    let fields: Vec<&str> = value.split_whitespace().collect();
-    if fields.len() % 2 != 0 {
-        return Err(Error::SdpInvalidSyntax(format!("`t={}`", value)));
+        Ok(())
     }
 
-    for i in (0..fields.len()).step_by(2) {
-        let adjustment_time = fields[i].parse::<u64>()?;
-        let offset = parse_time_units(fields[i + 1])?;
-
-        lexer.desc.time_zones.push(TimeZone {
-            adjustment_time,
-            offset,
-        });
+    /// parse_borrowed parses value like unmarshal, but without copying the
+    /// text fields out of it. Use SessionDescriptionRef::to_owned to get a
+    /// SessionDescription back.
+    pub fn parse_borrowed(value: &str) -> Result<SessionDescriptionRef<'_>, Error> {
+        SessionDescriptionRef::parse(value)
     }
 
-    Ok(Some(StateFn { f: s13 }))
-}
-
-fn unmarshal_session_encryption_key<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
-    lexer.desc.encryption_key = Some(value);
-    Ok(Some(StateFn { f: s11 }))
-}
-
-fn unmarshal_session_attribute<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
-
-    let fields: Vec<&str> = value.splitn(2, ':').collect();
-    let attribute = if fields.len() == 2 {
-        Attribute {
-            key: fields[0].to_owned(),
-            value: Some(fields[1].to_owned()),
-        }
-    } else {
-        Attribute {
-            key: fields[0].to_owned(),
-            value: None,
+    /// Unmarshal is the primary function that deserializes the session description
+    /// message and stores it inside of a structured SessionDescription object.
+    ///
+    /// The States Transition Table describes the computation flow between states
+    /// (namely s1, s2, s3, ...) for a parsing procedure that complies with the
+    /// specifications laid out by the rfc4566#section-5 as well as by JavaScript
+    /// Session Establishment Protocol draft. Links:
+    /// - <https://tools.ietf.org/html/rfc4566#section-5>
+    /// - <https://tools.ietf.org/html/draft-ietf-rtcweb-jsep-24>
+    ///
+    /// <https://tools.ietf.org/html/rfc4566#section-5>
+    /// Session description
+    ///    v=  (protocol version)
+    ///    o=  (originator and session identifier)
+    ///    s=  (session name)
+    ///    i=* (session information)
+    ///    u=* (URI of description)
+    ///    e=* (email address)
+    ///    p=* (phone number)
+    ///    c=* (connection information -- not required if included in
+    ///         all media)
+    ///    b=* (zero or more bandwidth information lines)
+    ///    One or more time descriptions ("t=" and "r=" lines; see below)
+    ///    z=* (time zone adjustments)
+    ///    k=* (encryption key)
+    ///    a=* (zero or more session attribute lines)
+    ///    Zero or more media descriptions
+    ///
+    /// Time description
+    ///    t=  (time the session is active)
+    ///    r=* (zero or more repeat times)
+    ///
+    /// Media description, if present
+    ///    m=  (media name and transport address)
+    ///    i=* (media title)
+    ///    c=* (connection information -- optional if included at
+    ///         session level)
+    ///    b=* (zero or more bandwidth information lines)
+    ///    k=* (encryption key)
+    ///    a=* (zero or more media attribute lines)
+    ///
+    /// In order to generate the following state table and draw subsequent
+    /// deterministic finite-state automota ("DFA") the following regex was used to
+    /// derive the DFA:
+    ///    vosi?u?e?p?c?b*(tr*)+z?k?a*(mi?c?b*k?a*)*
+    /// possible place and state to exit:
+    ///                    **   * * *  ** * * * *
+    ///                    99   1 1 1  11 1 1 1 1
+    ///                         3 1 1  26 5 5 4 4
+    ///
+    /// Please pay close attention to the `k`, and `a` parsing states. In the table
+    /// below in order to distinguish between the states belonging to the media
+    /// description as opposed to the session description, the states are marked
+    /// with an asterisk ("a*", "k*").
+    /// +--------+----+-------+----+-----+----+-----+---+----+----+---+---+-----+---+---+----+---+----+
+    /// | STATES | a* | a*,k* | a  | a,k | b  | b,c | e | i  | m  | o | p | r,t | s | t | u  | v | z  |
+    /// +--------+----+-------+----+-----+----+-----+---+----+----+---+---+-----+---+---+----+---+----+
+    /// |   s1   |    |       |    |     |    |     |   |    |    |   |   |     |   |   |    | 2 |    |
+    /// |   s2   |    |       |    |     |    |     |   |    |    | 3 |   |     |   |   |    |   |    |
+    /// |   s3   |    |       |    |     |    |     |   |    |    |   |   |     | 4 |   |    |   |    |
+    /// |   s4   |    |       |    |     |    |   5 | 6 |  7 |    |   | 8 |     |   | 9 | 10 |   |    |
+    /// |   s5   |    |       |    |     |  5 |     |   |    |    |   |   |     |   | 9 |    |   |    |
+    /// |   s6   |    |       |    |     |    |   5 |   |    |    |   | 8 |     |   | 9 |    |   |    |
+    /// |   s7   |    |       |    |     |    |   5 | 6 |    |    |   | 8 |     |   | 9 | 10 |   |    |
+    /// |   s8   |    |       |    |     |    |   5 |   |    |    |   |   |     |   | 9 |    |   |    |
+    /// |   s9   |    |       |    |  11 |    |     |   |    | 12 |   |   |   9 |   |   |    |   | 13 |
+    /// |   s10  |    |       |    |     |    |   5 | 6 |    |    |   | 8 |     |   | 9 |    |   |    |
+    /// |   s11  |    |       | 11 |     |    |     |   |    | 12 |   |   |     |   |   |    |   |    |
+    /// |   s12  |    |    14 |    |     |    |  15 |   | 16 | 12 |   |   |     |   |   |    |   |    |
+    /// |   s13  |    |       |    |  11 |    |     |   |    | 12 |   |   |     |   |   |    |   |    |
+    /// |   s14  | 14 |       |    |     |    |     |   |    | 12 |   |   |     |   |   |    |   |    |
+    /// |   s15  |    |    14 |    |     | 15 |     |   |    | 12 |   |   |     |   |   |    |   |    |
+    /// |   s16  |    |    14 |    |     |    |  15 |   |    | 12 |   |   |     |   |   |    |   |    |
+    /// +--------+----+-------+----+-----+----+-----+---+----+----+---+---+-----+---+---+----+---+----+
+    ///
+    /// The reader is read to its end first, and then split into fields in a
+    /// single pass.
+    #[cfg(feature = "std")]
+    pub fn unmarshal<R: io::BufRead + io::Seek>(reader: &mut R) -> Result<Self, Error> {
+        let at = |line, line_type, err| Error::SdpParse {
+            line,
+            line_type,
+            source: Box::new(err),
+        };
+        let mut input = Vec::new();
+        reader
+            .read_to_end(&mut input)
+            .map_err(|err| at(1, None, err.into()))?;
+
+        let mut desc = SessionDescription {
+            line_ending: LineEnding::of(&input),
+            ..Default::default()
+        };
+        let mut state = 1;
+        let mut fields = Fields::new(&input);
+        let mut line_type = None;
+        let mut line = 1;
+        for field in fields.by_ref() {
+            // The position stays at the previous field if the type isn't even
+            // UTF-8.
+            let key = match core::str::from_utf8(field.key) {
+                Ok(key) => key,
+                Err(_) => {
+                    let err = String::from_utf8(field.key.to_vec()).expect_err("invalid UTF-8");
+                    return Err(at(line, line_type, err.into()));
+                }
+            };
+            line = field.line;
+            line_type = key.chars().next();
+
+            let key = match field.key {
+                [key, b'='] if Self::accepts_field(state, *key) => *key,
+                [b'k', b'='] if state == 11 && desc.encryption_key.is_some() => {
+                    let err = Error::SdpDuplicateField(key.to_owned());
+                    return Err(at(line, line_type, err));
+                }
+                [_, _] => return Err(at(line, line_type, Error::SdpInvalidSyntax(key.to_owned()))),
+                _ => {
+                    let err = Error::SdpInvalidSyntax(format!("{:?}", key));
+                    return Err(at(line, line_type, err));
+                }
+            };
+
+            // Text fields are kept as bytes, everything else is decoded once
+            // here.
+            let next = if matches!(key, b's' | b'i') {
+                desc.unmarshal_field(state, key, field.value)
+            } else {
+                match core::str::from_utf8(field.value) {
+                    Ok(value) => {
+                        desc.unmarshal_decoded_field(state, key, field_value(key, value), &[])
+                    }
+                    Err(_) => Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "stream did not contain valid UTF-8",
+                    )
+                    .into()),
+                }
+            };
+            state = next.map_err(|err| at(line, line_type, err))?;
         }
-    };
-    lexer.desc.attributes.push(attribute);
-
-    Ok(Some(StateFn { f: s11 }))
-}
-
-fn unmarshal_media_description<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
 
-    let fields: Vec<&str> = value.split_whitespace().collect();
-    if fields.len() < 4 {
-        return Err(Error::SdpInvalidSyntax(format!("`m={}`", value)));
+        if Self::is_final_state(state) {
+            Ok(desc)
+        } else {
+            Err(at(
+                fields.line(),
+                None,
+                Error::SdpInvalidSyntax(String::new()),
+            ))
+        }
     }
 
-    // <media>
-    // Set according to currently registered with IANA
-    // https://tools.ietf.org/html/rfc4566#section-5.14
-    let i = index_of(
-        fields[0],
-        &["audio", "video", "text", "application", "message"],
-    );
-    if i == -1 {
-        return Err(Error::SdpInvalidValue(fields[0].to_owned()));
+    /// unmarshal_field stores a single "`<key>`=`<value>`" field and returns the
+    /// next state of the table above. It is the owned counterpart of
+    /// SessionDescriptionRef::unmarshal_field, for parsers which see one field
+    /// at a time. The untrimmed value must be UTF-8, except for the text
+    /// fields which are kept as bytes, see Charset.
+    pub(crate) fn unmarshal_field(
+        &mut self,
+        state: u8,
+        key: u8,
+        value: &[u8],
+    ) -> Result<u8, Error> {
+        let text = trim_text(value);
+        let value = match core::str::from_utf8(value) {
+            Ok(value) => field_value(key, value),
+            Err(_) if matches!(key, b's' | b'i') => "",
+            Err(_) => return Err(Error::SdpInvalidValue(format!("{:?}", value))),
+        };
+        self.unmarshal_decoded_field(state, key, value, text)
     }
 
-    // <port>
-    let parts: Vec<&str> = fields[1].split('/').collect();
-    let port_value = parts[0].parse::<u16>()? as isize;
-    let port_range = if parts.len() > 1 {
-        Some(parts[1].parse::<i32>()? as isize)
-    } else {
-        None
-    };
+    /// unmarshal_decoded_field is unmarshal_field with the value already
+    /// decoded and passed through field_value. text is the trimmed value of
+    /// the text fields, "s=" and "i=", which are only used as bytes.
+    fn unmarshal_decoded_field(
+        &mut self,
+        state: u8,
+        key: u8,
+        value: &str,
+        text: &[u8],
+    ) -> Result<u8, Error> {
+        let in_media = state >= 12;
+        let next = match (key, state) {
+            (b'v', 1) => {
+                self.version = value.parse::<Version>()?;
+                2
+            }
+            (b'o', 2) => {
+                self.origin = value.parse::<Origin>()?;
+                3
+            }
+            (b's', 3) => {
+                self.session_name = SessionName::from_bytes(text)?;
+                4
+            }
+            (b'i', 4) => {
+                self.session_information = Some(Information::from_bytes(text)?);
+                7
+            }
+            (b'u', 4 | 7) => {
+                self.uri = Some(value.parse::<Uri>()?);
+                10
+            }
+            (b'e', 4 | 7 | 10) => {
+                self.email_address = Some(value.parse::<EmailAddress>()?);
+                6
+            }
+            (b'p', 4 | 6 | 7 | 10) => {
+                self.phone_number = Some(value.parse::<PhoneNumber>()?);
+                8
+            }
+            (b'c', 4 | 6 | 7 | 8 | 10) => {
+                self.connection_information = unmarshal_connection_information(value)?;
+                5
+            }
+            (b'b', 4..=8 | 10) => {
+                self.bandwidth.push(value.parse::<Bandwidth>()?);
+                5
+            }
+            (b't', 4..=10) => {
+                self.time_descriptions.push(TimeDescription {
+                    timing: value.parse::<Timing>()?,
+                    repeat_times: vec![],
+                });
+                9
+            }
+            (b'r', 9) => {
+                let repeat_time = value.parse::<RepeatTime>()?;
+                match self.time_descriptions.last_mut() {
+                    Some(latest_time_desc) => latest_time_desc.repeat_times.push(repeat_time),
+                    None => return Err(Error::SdpEmptyTimeDescription),
+                }
+                9
+            }
+            (b'z', 9) => {
+                self.time_zones.extend(value.parse::<TimeZones>()?);
+                13
+            }
+            (b'k', 9 | 13) => {
+                self.encryption_key = Some(value.parse::<EncryptionKey>()?);
+                11
+            }
+            (b'k', _) if !in_media && self.encryption_key.is_some() => {
+                return Err(Error::SdpDuplicateField("k=".to_owned()))
+            }
+            (b'a', 9 | 11 | 13) => {
+                self.attributes
+                    .push(AttributeRef::parse_line(value)?.to_owned());
+                11
+            }
+            (b'm', 9 | 11..=16) => {
+                self.media_descriptions.push(MediaDescription {
+                    media_name: value.parse::<MediaName>()?,
+                    ..Default::default()
+                });
+                12
+            }
+            (b'i' | b'c' | b'b' | b'k' | b'a', _) if in_media => {
+                let latest_media_desc = self
+                    .media_descriptions
+                    .last_mut()
+                    .ok_or(Error::SdpEmptyTimeDescription)?;
+                match key {
+                    b'i' => {
+                        latest_media_desc.media_title = Some(Information::from_bytes(text)?);
+                        16
+                    }
+                    b'c' => {
+                        latest_media_desc.connection_information =
+                            unmarshal_connection_information(value)?;
+                        15
+                    }
+                    b'b' => {
+                        latest_media_desc
+                            .bandwidth
+                            .push(value.parse::<Bandwidth>()?);
+                        15
+                    }
+                    b'k' if latest_media_desc.encryption_key.is_some() => {
+                        return Err(Error::SdpDuplicateField("k=".to_owned()))
+                    }
+                    b'k' => {
+                        latest_media_desc.encryption_key = Some(value.parse::<EncryptionKey>()?);
+                        14
+                    }
+                    _ => {
+                        latest_media_desc
+                            .attributes
+                            .push(AttributeRef::parse_line(value)?.to_owned());
+                        14
+                    }
+                }
+            }
+            _ => return Err(Error::SdpInvalidSyntax(format!("{}=", key as char))),
+        };
 
-    // <proto>
-    // Set according to currently registered with IANA
-    // https://tools.ietf.org/html/rfc4566#section-5.14
-    let mut protos = vec![];
-    for proto in fields[2].split('/').collect::<Vec<&str>>() {
-        let i = index_of(
-            proto,
-            &[
-                "UDP", "RTP", "AVP", "SAVP", "SAVPF", "TLS", "DTLS", "SCTP", "AVPF",
-            ],
-        );
-        if i == -1 {
-            return Err(Error::SdpInvalidValue(fields[2].to_owned()));
-        }
-        protos.push(proto.to_owned());
+        Ok(next)
     }
 
-    // <fmt>...
-    let mut formats = vec![];
-    for field in fields.iter().skip(3) {
-        formats.push(field.to_string());
+    /// is_final_state returns true if the input may end in the given state of
+    /// the table above.
+    pub(crate) fn is_final_state(state: u8) -> bool {
+        matches!(state, 9 | 11..=16)
     }
 
-    lexer.desc.media_descriptions.push(MediaDescription {
-        media_name: MediaName {
-            media: fields[0].to_owned(),
-            port: RangedPort {
-                value: port_value,
-                range: port_range,
-            },
-            protos,
-            formats,
-        },
-        media_title: None,
-        connection_information: None,
-        bandwidth: vec![],
-        encryption_key: None,
-        attributes: vec![],
-    });
-
-    Ok(Some(StateFn { f: s12 }))
-}
-
-fn unmarshal_media_title<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
-
-    if let Some(latest_media_desc) = lexer.desc.media_descriptions.last_mut() {
-        latest_media_desc.media_title = Some(value);
-        Ok(Some(StateFn { f: s16 }))
-    } else {
-        Err(Error::SdpEmptyTimeDescription)
+    /// accepts_field returns true if a field of the given type may follow the
+    /// given state of the table above.
+    pub(crate) fn accepts_field(state: u8, key: u8) -> bool {
+        match (key, state) {
+            (b'v', 1) | (b'o', 2) | (b's', 3) | (b'i', 4) | (b'u', 4 | 7) => true,
+            (b'e', 4 | 7 | 10) | (b'p', 4 | 6 | 7 | 10) | (b'c', 4 | 6 | 7 | 8 | 10) => true,
+            (b'b', 4..=8 | 10) | (b't', 4..=10) | (b'r', 9) | (b'z', 9) | (b'k', 9 | 13) => true,
+            (b'a', 9 | 11 | 13) | (b'm', 9 | 11..=16) => true,
+            (b'i' | b'c' | b'b' | b'k' | b'a', _) => state >= 12,
+            _ => false,
+        }
     }
 }
 
-fn unmarshal_media_connection_information<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
-
-    if let Some(latest_media_desc) = lexer.desc.media_descriptions.last_mut() {
-        latest_media_desc.connection_information = unmarshal_connection_information(&value)?;
-        Ok(Some(StateFn { f: s15 }))
-    } else {
-        Err(Error::SdpEmptyTimeDescription)
-    }
+fn ice_credentials(attributes: &Attributes) -> Option<(IceUfrag, IcePwd)> {
+    let ufrag = attributes.get::<IceUfrag>().find_map(Result::ok)?;
+    let pwd = attributes.get::<IcePwd>().find_map(Result::ok)?;
+    Some((ufrag, pwd))
 }
 
-fn unmarshal_media_bandwidth<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
+fn has_ice_credentials(attributes: &Attributes) -> bool {
+    attributes
+        .iter()
+        .any(|a| IceUfrag::matches(a) || IcePwd::matches(a))
+}
 
-    if let Some(latest_media_desc) = lexer.desc.media_descriptions.last_mut() {
-        let bandwidth = unmarshal_bandwidth(&value)?;
-        latest_media_desc.bandwidth.push(bandwidth);
-        Ok(Some(StateFn { f: s15 }))
-    } else {
-        Err(Error::SdpEmptyTimeDescription)
+/// field_value returns the value of a field the way the fields are stored:
+/// trimmed, except for the value of "a=", which only loses the "\r" of its
+/// line break, see AttributeRef::parse_line.
+fn field_value(key: u8, value: &str) -> &str {
+    match key {
+        b'a' => value.strip_suffix('\r').unwrap_or(value),
+        _ => value.trim(),
     }
 }
 
-fn unmarshal_media_encryption_key<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
-
-    if let Some(latest_media_desc) = lexer.desc.media_descriptions.last_mut() {
-        latest_media_desc.encryption_key = Some(value);
-        Ok(Some(StateFn { f: s14 }))
-    } else {
-        Err(Error::SdpEmptyTimeDescription)
+fn check_line_value(key: &str, value: &str) -> Result<(), Error> {
+    if value.contains(['\r', '\n']) {
+        return Err(Error::SdpUnexpectedLineBreak(key.to_owned()));
     }
+    Ok(())
 }
 
-fn unmarshal_media_attribute<'a, R: io::BufRead + io::Seek>(
-    lexer: &mut Lexer<'a, R>,
-) -> Result<Option<StateFn<'a, R>>, Error> {
-    let (value, _) = read_value(lexer.reader)?;
+fn unmarshal_connection_information(value: &str) -> Result<Option<ConnectionInformation>, Error> {
+    Ok(Some(value.parse::<ConnectionInformation>()?))
+}
 
-    let fields: Vec<&str> = value.splitn(2, ':').collect();
-    let attribute = if fields.len() == 2 {
-        Attribute {
-            key: fields[0].to_owned(),
-            value: Some(fields[1].to_owned()),
-        }
-    } else {
-        Attribute {
-            key: fields[0].to_owned(),
-            value: None,
-        }
-    };
+/// format_time_units is the inverse of parse_time_units: it writes the value in
+/// the largest unit which divides it exactly. Zero is always written as "0".
+pub fn format_time_units(value: i64) -> String {
+    if value == 0 {
+        return "0".to_owned();
+    }
 
-    if let Some(latest_media_desc) = lexer.desc.media_descriptions.last_mut() {
-        latest_media_desc.attributes.push(attribute);
-        Ok(Some(StateFn { f: s14 }))
+    if value % 86400 == 0 {
+        format!("{}d", value / 86400)
+    } else if value % 3600 == 0 {
+        format!("{}h", value / 3600)
+    } else if value % 60 == 0 {
+        format!("{}m", value / 60)
     } else {
-        Err(Error::SdpEmptyTimeDescription)
+        format!("{}", value)
     }
 }
 
@@ -1336,14 +3539,16 @@ fn parse_time_units(value: &str) -> Result<i64, Error> {
     //      h - hours (3600 seconds)
     //      m - minutes (60 seconds)
     //      s - seconds (allowed for completeness)
-    let val = value.as_bytes();
-    let len = val.len();
-    let num = match val[len - 1] {
-        b'd' => value.trim_end_matches('d').parse::<i64>()? * 86400,
-        b'h' => value.trim_end_matches('h').parse::<i64>()? * 3600,
-        b'm' => value.trim_end_matches('m').parse::<i64>()? * 60,
-        _ => value.trim_end_matches('m').parse::<i64>()?,
+    let (num, multiplier) = match value.as_bytes().last() {
+        Some(b'd') => (&value[..value.len() - 1], 86400),
+        Some(b'h') => (&value[..value.len() - 1], 3600),
+        Some(b'm') => (&value[..value.len() - 1], 60),
+        Some(b's') => (&value[..value.len() - 1], 1),
+        Some(b'0'..=b'9') => (value, 1),
+        _ => return Err(Error::SdpInvalidValue(value.to_owned())),
     };
 
-    Ok(num)
+    num.parse::<i64>()?
+        .checked_mul(multiplier)
+        .ok_or_else(|| Error::SdpInvalidValue(value.to_owned()))
 }
@@ -1,8 +1,9 @@
 use super::*;
 
+#[cfg(feature = "std")]
 use std::io::Cursor;
 
-const CANONICAL_MARSHAL_SDP: &'static str = "v=0\r\n\
+const CANONICAL_MARSHAL_SDP: &str = "v=0\r\n\
      o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
      s=SDP Seminar\r\n\
      i=A Seminar on the session description protocol\r\n\
@@ -29,6 +30,7 @@ const CANONICAL_MARSHAL_SDP: &'static str = "v=0\r\n\
      a=rtpmap:99 h263-1998/90000\r\n";
 
 #[test]
+#[cfg(feature = "std")]
 fn test_unmarshal_marshal() -> Result<(), Error> {
     let input = CANONICAL_MARSHAL_SDP;
     let mut reader = Cursor::new(input.as_bytes());
@@ -42,22 +44,22 @@ fn test_unmarshal_marshal() -> Result<(), Error> {
 #[test]
 fn test_marshal() -> Result<(), Error> {
     let sd = SessionDescription {
-        version: 0,
+        version: Version::V0,
         origin: Origin {
-            username: "jdoe".to_string(),
+            username: "jdoe".parse()?,
             session_id: 2890844526,
             session_version: 2890842807,
-            network_type: "IN".to_string(),
+            network_type: NetType::In,
             address_type: "IP4".to_string(),
             unicast_address: "10.47.16.5".to_string(),
         },
-        session_name: "SDP Seminar".to_string(),
-        session_information: Some("A Seminar on the session description protocol".to_string()),
-        uri: Some(Url::parse("http://www.example.com/seminars/sdp.pdf")?),
-        email_address: Some("j.doe@example.com (Jane Doe)".to_string()),
-        phone_number: Some("+1 617 555-6011".to_string()),
+        session_name: "SDP Seminar".parse()?,
+        session_information: Some("A Seminar on the session description protocol".parse()?),
+        uri: Some(Url::parse("http://www.example.com/seminars/sdp.pdf")?.into()),
+        email_address: Some("j.doe@example.com (Jane Doe)".parse()?),
+        phone_number: Some("+1 617 555-6011".parse()?),
         connection_information: Some(ConnectionInformation {
-            network_type: "IN".to_string(),
+            network_type: NetType::In,
             address_type: "IP4".to_string(),
             address: Some(Address {
                 address: "224.2.17.12".to_string(),
@@ -68,12 +70,12 @@ fn test_marshal() -> Result<(), Error> {
         bandwidth: vec![
             Bandwidth {
                 experimental: true,
-                bandwidth_type: "YZ".to_string(),
+                bandwidth_type: "YZ".parse()?,
                 bandwidth: 128,
             },
             Bandwidth {
                 experimental: false,
-                bandwidth_type: "AS".to_string(),
+                bandwidth_type: "AS".parse()?,
                 bandwidth: 12345,
             },
         ],
@@ -106,29 +108,28 @@ fn test_marshal() -> Result<(), Error> {
                 adjustment_time: 2898848070,
                 offset: 0,
             },
-        ],
-        encryption_key: Some("prompt".to_string()),
+        ]
+        .into(),
+        encryption_key: Some("prompt".parse()?),
         attributes: vec![
             Attribute::new(
                 "candidate".to_string(),
                 Some("0 1 UDP 2113667327 203.0.113.1 54400 typ host".to_string()),
             ),
             Attribute::new("recvonly".to_string(), None),
-        ],
+        ]
+        .into(),
         media_descriptions: vec![
             MediaDescription {
                 media_name: MediaName {
                     media: "audio".to_string(),
-                    port: RangedPort {
-                        value: 49170,
-                        range: None,
-                    },
+                    port: Port::new(49170),
                     protos: vec!["RTP".to_string(), "AVP".to_string()],
                     formats: vec!["0".to_string()],
                 },
-                media_title: Some("Vivamus a posuere nisl".to_string()),
+                media_title: Some("Vivamus a posuere nisl".parse()?),
                 connection_information: Some(ConnectionInformation {
-                    network_type: "IN".to_string(),
+                    network_type: NetType::In,
                     address_type: "IP4".to_string(),
                     address: Some(Address {
                         address: "203.0.113.1".to_string(),
@@ -138,19 +139,17 @@ fn test_marshal() -> Result<(), Error> {
                 }),
                 bandwidth: vec![Bandwidth {
                     experimental: true,
-                    bandwidth_type: "YZ".to_string(),
+                    bandwidth_type: "YZ".parse()?,
                     bandwidth: 128,
                 }],
-                encryption_key: Some("prompt".to_string()),
-                attributes: vec![Attribute::new("sendrecv".to_string(), None)],
+                encryption_key: Some("prompt".parse()?),
+                attributes: vec![Attribute::new("sendrecv".to_string(), None)].into(),
+                unknown_lines: vec![],
             },
             MediaDescription {
                 media_name: MediaName {
                     media: "video".to_string(),
-                    port: RangedPort {
-                        value: 51372,
-                        range: None,
-                    },
+                    port: Port::new(51372),
                     protos: vec!["RTP".to_string(), "AVP".to_string()],
                     formats: vec!["99".to_string()],
                 },
@@ -161,14 +160,18 @@ fn test_marshal() -> Result<(), Error> {
                 attributes: vec![Attribute::new(
                     "rtpmap".to_string(),
                     Some("99 h263-1998/90000".to_string()),
-                )],
+                )]
+                .into(),
+                unknown_lines: vec![],
             },
         ],
+        unknown_lines: vec![],
+        line_ending: LineEnding::CrLf,
     };
 
     let actual = sd.marshal();
     assert!(
-        &actual == CANONICAL_MARSHAL_SDP,
+        actual == CANONICAL_MARSHAL_SDP,
         "error:\n\nEXPECTED:\n{}\nACTUAL:\n{}!!!!\n",
         CANONICAL_MARSHAL_SDP,
         actual
@@ -177,11 +180,11 @@ fn test_marshal() -> Result<(), Error> {
     Ok(())
 }
 
-const BASE_SDP: &'static str = "v=0\r\n\
+const BASE_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n";
 
-const SESSION_INFORMATION_SDP: &'static str = "v=0\r\n\
+const SESSION_INFORMATION_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 i=A Seminar on the session description protocol\r\n\
@@ -190,7 +193,7 @@ t=3034423619 3042462419\r\n";
 // https://tools.ietf.org/html/rfc4566#section-5
 // Parsers SHOULD be tolerant and also accept records terminated
 // with a single newline character.
-const SESSION_INFORMATION_SDPLFONLY: &'static str = "v=0\n\
+const SESSION_INFORMATION_SDPLFONLY: &str = "v=0\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\n\
 s=SDP Seminar\n\
 i=A Seminar on the session description protocol\n\
@@ -204,7 +207,7 @@ t=3034423619 3042462419\n";
 
 // Other SDP parsers (e.g. one in VLC media player) allow
 // empty lines.
-const SESSION_INFORMATION_SDPEXTRA_CRLF: &'static str = "v=0\r\n\
+const SESSION_INFORMATION_SDPEXTRA_CRLF: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 \r\n\
 s=SDP Seminar\r\n\
@@ -214,38 +217,38 @@ i=A Seminar on the session description protocol\r\n\
 t=3034423619 3042462419\r\n\
 \r\n";
 
-const URI_SDP: &'static str = "v=0\r\n\
+const URI_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 u=http://www.example.com/seminars/sdp.pdf\r\n\
 t=3034423619 3042462419\r\n";
 
-const EMAIL_ADDRESS_SDP: &'static str = "v=0\r\n\
+const EMAIL_ADDRESS_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 e=j.doe@example.com (Jane Doe)\r\n\
 t=3034423619 3042462419\r\n";
 
-const PHONE_NUMBER_SDP: &'static str = "v=0\r\n\
+const PHONE_NUMBER_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 p=+1 617 555-6011\r\n\
 t=3034423619 3042462419\r\n";
 
-const SESSION_CONNECTION_INFORMATION_SDP: &'static str = "v=0\r\n\
+const SESSION_CONNECTION_INFORMATION_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 c=IN IP4 224.2.17.12/127\r\n\
 t=3034423619 3042462419\r\n";
 
-const SESSION_BANDWIDTH_SDP: &'static str = "v=0\r\n\
+const SESSION_BANDWIDTH_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 b=X-YZ:128\r\n\
 b=AS:12345\r\n\
 t=3034423619 3042462419\r\n";
 
-const TIMING_SDP: &'static str = "v=0\r\n\
+const TIMING_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n";
@@ -253,21 +256,21 @@ t=2873397496 2873404696\r\n";
 // Short hand time notation is converted into NTP timestamp format in
 // seconds. Because of that unittest comparisons will fail as the same time
 // will be expressed in different units.
-const REPEAT_TIMES_SDP: &'static str = "v=0\r\n\
+const REPEAT_TIMES_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
 r=604800 3600 0 90000\r\n\
 r=3d 2h 0 21h\r\n";
 
-const REPEAT_TIMES_SDPEXPECTED: &'static str = "v=0\r\n\
+const REPEAT_TIMES_SDPEXPECTED: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
 r=604800 3600 0 90000\r\n\
 r=259200 7200 0 75600\r\n";
 
-const REPEAT_TIMES_SDPEXTRA_CRLF: &'static str = "v=0\r\n\
+const REPEAT_TIMES_SDPEXTRA_CRLF: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
@@ -277,58 +280,58 @@ r=259200 7200 0 75600\r\n\
 
 // The expected value looks a bit different for the same reason as mentioned
 // above regarding RepeatTimes.
-const TIME_ZONES_SDP: &'static str = "v=0\r\n\
+const TIME_ZONES_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
 r=2882844526 -1h 2898848070 0\r\n";
 
-const TIME_ZONES_SDPEXPECTED: &'static str = "v=0\r\n\
+const TIME_ZONES_SDPEXPECTED: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
 r=2882844526 -3600 2898848070 0\r\n";
 
-const TIME_ZONES_SDP2: &'static str = "v=0\r\n\
+const TIME_ZONES_SDP2: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
 z=2882844526 -3600 2898848070 0\r\n";
 
-const TIME_ZONES_SDP2EXTRA_CRLF: &'static str = "v=0\r\n\
+const TIME_ZONES_SDP2EXTRA_CRLF: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
 z=2882844526 -3600 2898848070 0\r\n\
 \r\n";
 
-const SESSION_ENCRYPTION_KEY_SDP: &'static str = "v=0\r\n\
+const SESSION_ENCRYPTION_KEY_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
 k=prompt\r\n";
 
-const SESSION_ENCRYPTION_KEY_SDPEXTRA_CRLF: &'static str = "v=0\r\n\
+const SESSION_ENCRYPTION_KEY_SDPEXTRA_CRLF: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
 k=prompt\r\n
 \r\n";
 
-const SESSION_ATTRIBUTES_SDP: &'static str = "v=0\r\n\
+const SESSION_ATTRIBUTES_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
 a=rtpmap:96 opus/48000\r\n";
 
-const MEDIA_NAME_SDP: &'static str = "v=0\r\n\
+const MEDIA_NAME_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
 m=video 51372 RTP/AVP 99\r\n\
 m=audio 54400 RTP/SAVPF 0 96\r\n";
 
-const MEDIA_NAME_SDPEXTRA_CRLF: &'static str = "v=0\r\n\
+const MEDIA_NAME_SDPEXTRA_CRLF: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
@@ -336,7 +339,7 @@ m=video 51372 RTP/AVP 99\r\n\
 m=audio 54400 RTP/SAVPF 0 96\r\n
 \r\n";
 
-const MEDIA_TITLE_SDP: &'static str = "v=0\r\n\
+const MEDIA_TITLE_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
@@ -344,7 +347,7 @@ m=video 51372 RTP/AVP 99\r\n\
 m=audio 54400 RTP/SAVPF 0 96\r\n\
 i=Vivamus a posuere nisl\r\n";
 
-const MEDIA_CONNECTION_INFORMATION_SDP: &'static str = "v=0\r\n\
+const MEDIA_CONNECTION_INFORMATION_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
@@ -352,7 +355,7 @@ m=video 51372 RTP/AVP 99\r\n\
 m=audio 54400 RTP/SAVPF 0 96\r\n\
 c=IN IP4 203.0.113.1\r\n";
 
-const MEDIA_CONNECTION_INFORMATION_SDPEXTRA_CRLF: &'static str = "v=0\r\n\
+const MEDIA_CONNECTION_INFORMATION_SDPEXTRA_CRLF: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
@@ -361,7 +364,7 @@ m=audio 54400 RTP/SAVPF 0 96\r\n\
 c=IN IP4 203.0.113.1\r\n\
 \r\n";
 
-const MEDIA_DESCRIPTION_OUT_OF_ORDER_SDP: &'static str = "v=0\r\n\
+const MEDIA_DESCRIPTION_OUT_OF_ORDER_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
@@ -372,7 +375,7 @@ a=candidate:0 1 UDP 2113667327 203.0.113.1 54400 typ host\r\n\
 c=IN IP4 203.0.113.1\r\n\
 i=Vivamus a posuere nisl\r\n";
 
-const MEDIA_DESCRIPTION_OUT_OF_ORDER_SDPACTUAL: &'static str = "v=0\r\n\
+const MEDIA_DESCRIPTION_OUT_OF_ORDER_SDPACTUAL: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
@@ -383,7 +386,7 @@ c=IN IP4 203.0.113.1\r\n\
 a=rtpmap:99 h263-1998/90000\r\n\
 a=candidate:0 1 UDP 2113667327 203.0.113.1 54400 typ host\r\n";
 
-const MEDIA_BANDWIDTH_SDP: &'static str = "v=0\r\n\
+const MEDIA_BANDWIDTH_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
@@ -392,7 +395,7 @@ m=audio 54400 RTP/SAVPF 0 96\r\n\
 b=X-YZ:128\r\n\
 b=AS:12345\r\n";
 
-const MEDIA_ENCRYPTION_KEY_SDP: &'static str = "v=0\r\n\
+const MEDIA_ENCRYPTION_KEY_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
@@ -400,7 +403,7 @@ m=video 51372 RTP/AVP 99\r\n\
 m=audio 54400 RTP/SAVPF 0 96\r\n\
 k=prompt\r\n";
 
-const MEDIA_ENCRYPTION_KEY_SDPEXTRA_CRLF: &'static str = "v=0\r\n\
+const MEDIA_ENCRYPTION_KEY_SDPEXTRA_CRLF: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
@@ -409,7 +412,7 @@ m=audio 54400 RTP/SAVPF 0 96\r\n\
 k=prompt\r\n\
 \r\n";
 
-const MEDIA_ATTRIBUTES_SDP: &'static str = "v=0\r\n\
+const MEDIA_ATTRIBUTES_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 t=2873397496 2873404696\r\n\
@@ -421,7 +424,7 @@ a=rtcp-fb:97 ccm fir\r\n\
 a=rtcp-fb:97 nack\r\n\
 a=rtcp-fb:97 nack pli\r\n";
 
-const CANONICAL_UNMARSHAL_SDP: &'static str = "v=0\r\n\
+const CANONICAL_UNMARSHAL_SDP: &str = "v=0\r\n\
 o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
 s=SDP Seminar\r\n\
 i=A Seminar on the session description protocol\r\n\
@@ -448,12 +451,13 @@ m=video 51372 RTP/AVP 99\r\n\
 a=rtpmap:99 h263-1998/90000\r\n";
 
 #[test]
+#[cfg(feature = "std")]
 fn test_round_trip() -> Result<(), Error> {
     let tests = vec![
         (
             "SessionInformationSDPLFOnly",
             SESSION_INFORMATION_SDPLFONLY,
-            Some(SESSION_INFORMATION_SDP),
+            None,
         ),
         (
             "SessionInformationSDPExtraCRLF",
@@ -518,6 +522,7 @@ fn test_round_trip() -> Result<(), Error> {
         ),
         ("MediaAttributes", MEDIA_ATTRIBUTES_SDP, None),
         ("CanonicalUnmarshal", CANONICAL_UNMARSHAL_SDP, None),
+        ("WebRTCOffer", WEBRTC_OFFER_SDP, None),
     ];
 
     for (name, sdp_str, expected) in tests {
@@ -531,7 +536,7 @@ fn test_round_trip() -> Result<(), Error> {
                 assert_eq!(actual.as_str(), sdp_str, "{}\n{}", name, sdp_str);
             }
         } else {
-            assert!(false, "{}\n{}", name, sdp_str);
+            panic!("{}\n{}", name, sdp_str);
         }
     }
 
@@ -539,6 +544,7 @@ fn test_round_trip() -> Result<(), Error> {
 }
 
 #[test]
+#[cfg(feature = "std")]
 fn test_unmarshal_repeat_times() -> Result<(), Error> {
     let mut reader = Cursor::new(REPEAT_TIMES_SDP.as_bytes());
     let sdp = SessionDescription::unmarshal(&mut reader)?;
@@ -548,6 +554,7 @@ fn test_unmarshal_repeat_times() -> Result<(), Error> {
 }
 
 #[test]
+#[cfg(feature = "std")]
 fn test_unmarshal_time_zones() -> Result<(), Error> {
     let mut reader = Cursor::new(TIME_ZONES_SDP.as_bytes());
     let sdp = SessionDescription::unmarshal(&mut reader)?;
@@ -557,6 +564,7 @@ fn test_unmarshal_time_zones() -> Result<(), Error> {
 }
 
 #[test]
+#[cfg(feature = "std")]
 fn test_unmarshal_non_nil_address() -> Result<(), Error> {
     let input = "v=0\r\no=0 0 0 IN IP4 0\r\ns=0\r\nc=IN IP4\r\nt=0 0\r\n";
     let mut reader = Cursor::new(input);
@@ -565,7 +573,2006 @@ fn test_unmarshal_non_nil_address() -> Result<(), Error> {
         let output = sdp.marshal();
         assert_eq!(output.as_str(), input);
     } else {
-        assert!(false, "{}", input);
+        panic!("{}", input);
+    }
+    Ok(())
+}
+
+const WEBRTC_OFFER_SDP: &str = "v=0\r\n\
+o=- 4611731400430051336 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0 1\r\n\
+a=msid-semantic: WMS stream\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=rtcp:9 IN IP4 0.0.0.0\r\n\
+a=ice-ufrag:ECpu\r\n\
+a=ice-pwd:ER8+ZPSzrdyH6tSjDEETE7di\r\n\
+a=fingerprint:sha-256 2F:4C:56:91:41:55:B4:1F:6E:6A:BF:64:4F:C5:1B:5A:30:23:98:36:E3:11:7E:58:04:A8:E6:73:7A:E4:D7:83\r\n\
+a=setup:actpass\r\n\
+a=mid:0\r\n\
+a=extmap:1 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r\n\
+a=sendrecv\r\n\
+a=msid:stream track-audio\r\n\
+a=rtcp-mux\r\n\
+a=rtpmap:111 opus/48000/2\r\n\
+a=fmtp:111 minptime=10;useinbandfec=1\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=mid:1\r\n\
+a=sendrecv\r\n\
+a=rtcp-mux\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+a=rtcp-fb:96 nack pli\r\n";
+
+#[test]
+fn test_new_session_description() -> Result<(), Error> {
+    let origin = Origin {
+        username: "-".parse()?,
+        session_id: 4611731400430051336,
+        session_version: 2,
+        network_type: NetType::In,
+        address_type: "IP4".to_string(),
+        unicast_address: "127.0.0.1".to_string(),
+    };
+    let time_description = TimeDescription {
+        timing: Timing {
+            start_time: 0,
+            stop_time: 0,
+        },
+        repeat_times: vec![],
+    };
+
+    let parsed = WEBRTC_OFFER_SDP.parse::<SessionDescription>()?;
+
+    let mut sd = SessionDescription::new(origin, "-".parse()?, time_description);
+    sd.attributes = parsed.attributes;
+    sd.media_descriptions = parsed.media_descriptions;
+
+    assert_eq!(sd.version, Version::V0);
+    assert_eq!(sd.to_string(), WEBRTC_OFFER_SDP);
+
+    Ok(())
+}
+
+#[test]
+fn test_time_description_from_lines() -> Result<(), Error> {
+    let td = TimeDescription::from_lines(&[
+        "t=3034423619 3042462419",
+        "r=7d 1h 0 25h",
+        "r=604800 3600 -1h 30s 90000",
+    ])?;
+    assert_eq!(td.timing.start_time, 3034423619);
+    assert_eq!(td.timing.stop_time, 3042462419);
+    assert_eq!(td.repeat_times.len(), 2);
+    assert_eq!(td.repeat_times[0].interval, 604800);
+    assert_eq!(td.repeat_times[0].duration, 3600);
+    assert_eq!(td.repeat_times[0].offsets, vec![0, 90000]);
+    assert_eq!(td.repeat_times[1].offsets, vec![-3600, 30, 90000]);
+
+    // Round trip keeps the semantics, typed times are expanded to seconds.
+    let lines = [
+        format!("t={}", td.timing),
+        format!("r={}", td.repeat_times[0]),
+        format!("r={}", td.repeat_times[1]),
+    ];
+    let lines: Vec<&str> = lines.iter().map(|l| l.as_str()).collect();
+    let reparsed = TimeDescription::from_lines(&lines)?;
+    assert_eq!(reparsed.repeat_times[0].to_string(), "604800 3600 0 90000");
+    assert_eq!(
+        reparsed.repeat_times[1].to_string(),
+        "604800 3600 -3600 30 90000"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_time_description_from_lines_errors() {
+    let failingtests: Vec<&[&str]> = vec![
+        &[],
+        &["t=0"],
+        &["t=0 0", "r=7d 1h"],
+        &["t=0 0", "r=7w 1h 0"],
+        &["t=0 0", "r=7d 1h x"],
+        &["t=0 0", "r=7d 1h "],
+        &["t=0 0", "z=2882844526 -1h"],
+    ];
+
+    for (i, lines) in failingtests.iter().enumerate() {
+        assert!(
+            TimeDescription::from_lines(lines).is_err(),
+            "{}: {:?}",
+            i,
+            lines
+        );
+    }
+}
+
+#[test]
+fn test_format_time_units() {
+    let tests = [
+        (0, "0"),
+        (30, "30"),
+        (60, "1m"),
+        (1500, "25m"),
+        (3600, "1h"),
+        (90000, "25h"),
+        (86400, "1d"),
+        (604800, "7d"),
+        (-3600, "-1h"),
+        (-90000, "-25h"),
+        (-61, "-61"),
+    ];
+
+    for (value, expected) in tests.iter() {
+        assert_eq!(format_time_units(*value), *expected, "{}", value);
+    }
+}
+
+#[test]
+fn test_compact_repeat_time_and_time_zone() -> Result<(), Error> {
+    // https://tools.ietf.org/html/rfc4566#section-5.10
+    let repeat_time = "604800 3600 0 90000".parse::<RepeatTime>()?;
+    assert_eq!(repeat_time.to_compact_string(), "7d 1h 0 25h");
+    assert_eq!(repeat_time.to_string(), "604800 3600 0 90000");
+    assert_eq!(
+        repeat_time
+            .to_compact_string()
+            .parse::<RepeatTime>()?
+            .to_string(),
+        repeat_time.to_string()
+    );
+
+    // https://tools.ietf.org/html/rfc4566#section-5.11
+    let time_zone = TimeZone {
+        adjustment_time: 2882844526,
+        offset: -3600,
+    };
+    assert_eq!(time_zone.to_compact_string(), "2882844526 -1h");
+
+    Ok(())
+}
+
+#[test]
+fn test_repeat_time_field_order() -> Result<(), Error> {
+    // r=<repeat interval> <active duration> <offsets from start-time>
+    // https://tools.ietf.org/html/rfc4566#section-5.10
+    let repeat_time = RepeatTime {
+        interval: 604800,
+        duration: 3600,
+        offsets: vec![0, 90000],
+    };
+    assert_eq!(repeat_time.to_string(), "604800 3600 0 90000");
+
+    let parsed = "604800 3600 0 90000".parse::<RepeatTime>()?;
+    assert_eq!(parsed.interval(), 604800);
+    assert_eq!(parsed.duration(), 3600);
+    assert_eq!(parsed.offsets(), &[0, 90000]);
+    assert_eq!(parsed.to_string(), "604800 3600 0 90000");
+
+    Ok(())
+}
+
+#[test]
+fn test_new_repeat_time() -> Result<(), Error> {
+    let mut repeat_time = RepeatTime::new(604800, 3600, vec![0])?;
+    assert_eq!(repeat_time.offsets(), &[0]);
+
+    repeat_time.push_offset(90000);
+    assert_eq!(repeat_time.to_string(), "604800 3600 0 90000");
+
+    repeat_time.extend(vec![172800, 259200]);
+    let offsets: Vec<i64> = (&repeat_time).into_iter().copied().collect();
+    assert_eq!(offsets, vec![0, 90000, 172800, 259200]);
+
+    assert!(RepeatTime::new(0, 3600, vec![0]).is_err());
+    assert!(RepeatTime::new(-1, 3600, vec![0]).is_err());
+    assert!(RepeatTime::new(604800, 3600, vec![]).is_err());
+    assert!("0 3600 0".parse::<RepeatTime>().is_err());
+    // Typed times which overflow when converted to seconds.
+    assert!("9999999999999999d 1h 0".parse::<RepeatTime>().is_err());
+    assert!("7d 1h 199999999999999999h".parse::<RepeatTime>().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_time_zones() -> Result<(), Error> {
+    // https://tools.ietf.org/html/rfc4566#section-5.11
+    let time_zones = "2882844526 -1h 2898848070 0".parse::<TimeZones>()?;
+    assert_eq!(time_zones.len(), 2);
+    assert!(!time_zones.is_empty());
+
+    let offsets: Vec<i64> = time_zones.iter().map(|tz| tz.offset).collect();
+    assert_eq!(offsets, vec![-3600, 0]);
+    assert_eq!(time_zones.to_string(), "2882844526 -3600 2898848070 0");
+    assert_eq!(
+        time_zones.to_compact_string(),
+        "2882844526 -1h 2898848070 0"
+    );
+    assert_eq!(
+        time_zones.to_string().parse::<TimeZones>()?.to_string(),
+        time_zones.to_string()
+    );
+
+    assert!("".parse::<TimeZones>().is_err());
+    assert!("2882844526".parse::<TimeZones>().is_err());
+    assert!("2882844526 -1h 2898848070".parse::<TimeZones>().is_err());
+    assert!("2882844526 -1x".parse::<TimeZones>().is_err());
+    assert!("2882844526 -9999999999999999d"
+        .parse::<TimeZones>()
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_uri() -> Result<(), Error> {
+    let passingtests = [
+        "http://www.example.com/seminars/sdp.pdf",
+        "https://example.com",
+        "https://example.com/a%20b?q=1&r=%7E#frag",
+        "HTTP://Example.COM/Path",
+    ];
+
+    for input in passingtests.iter() {
+        let uri = input.parse::<Uri>()?;
+        assert_eq!(uri.to_string(), *input);
+    }
+
+    let uri = "HTTP://Example.COM/Path".parse::<Uri>()?;
+    assert_eq!(uri.as_url().host_str(), Some("example.com"));
+    let url: Url = uri.into();
+    assert_eq!(url.scheme(), "http");
+
+    let failingtests = ["", "/seminars/sdp.pdf", "sdp.pdf", "http://a b.com/"];
+    for input in failingtests.iter() {
+        assert!(input.parse::<Uri>().is_err(), "{}", input);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_uri_position() -> Result<(), Error> {
+    let mut sd = SessionDescription::new(Origin::new(), "-".parse()?, TimeDescription::default());
+    sd.session_information = Some("info".parse()?);
+    sd.uri = Some("https://example.com".parse()?);
+    sd.email_address = Some("j.doe@example.com".parse()?);
+
+    let output = sd.marshal();
+    let i = output.find("i=info").unwrap();
+    let u = output.find("u=https://example.com\r\n").unwrap();
+    let e = output.find("e=j.doe@example.com").unwrap();
+    assert!(i < u && u < e, "{}", output);
+
+    Ok(())
+}
+
+#[test]
+fn test_phone_number() -> Result<(), Error> {
+    let passingtests = [
+        (
+            "+1 617 555-6011",
+            "+1 617 555-6011",
+            None,
+            "+1 617 555-6011",
+        ),
+        (
+            "+1 617 555-6011 (Jane Doe)",
+            "+1 617 555-6011",
+            Some("Jane Doe"),
+            "+1 617 555-6011 (Jane Doe)",
+        ),
+        (
+            "Jane Doe <+1 617 555-6011>",
+            "+1 617 555-6011",
+            Some("Jane Doe"),
+            "+1 617 555-6011 (Jane Doe)",
+        ),
+        (
+            "+44-20-7946-0958",
+            "+44-20-7946-0958",
+            None,
+            "+44-20-7946-0958",
+        ),
+    ];
+
+    for (input, number, display_name, output) in passingtests.iter() {
+        let phone = input.parse::<PhoneNumber>()?;
+        assert_eq!(phone.number, *number, "{}", input);
+        assert_eq!(phone.display_name.as_deref(), *display_name, "{}", input);
+        assert_eq!(phone.to_string(), *output, "{}", input);
+    }
+
+    let failingtests = [
+        "",
+        "1 617 555-6011",
+        "+",
+        "+1 617 555.6011",
+        "+1 617 555-6011 (Jane Doe",
+        "+1 617 555-6011 Jane Doe)",
+        "Jane Doe <+1 617 555-6011",
+        "+1 617 (555) 6011 (Jane)",
+    ];
+    for input in failingtests.iter() {
+        assert!(input.parse::<PhoneNumber>().is_err(), "{}", input);
+    }
+    assert!(matches!(
+        "+1 617 555-6011 (Jane Doe".parse::<PhoneNumber>(),
+        Err(Error::SdpUnbalancedDelimiters(_))
+    ));
+    for display_name in ["Jane (Doe)", "Jane Doe)", "Jane <Doe>", "Jane Doe>"] {
+        assert!(
+            matches!(
+                PhoneNumber::new("+1 617 555-6011", Some(display_name)),
+                Err(Error::SdpUnbalancedDelimiters(_))
+            ),
+            "{}",
+            display_name
+        );
+    }
+
+    let phone = PhoneNumber::new("+1 617 555-6011", Some("Jane Doe"))?;
+    assert_eq!(phone.to_string().parse::<PhoneNumber>()?, phone);
+
+    Ok(())
+}
+
+#[test]
+fn test_email_address() -> Result<(), Error> {
+    let passingtests = [
+        (
+            "j.doe@example.com",
+            "j.doe@example.com",
+            None,
+            "j.doe@example.com",
+        ),
+        (
+            "j.doe@example.com (Jane Doe)",
+            "j.doe@example.com",
+            Some("Jane Doe"),
+            "j.doe@example.com (Jane Doe)",
+        ),
+        (
+            "Jane Doe <j.doe@example.com>",
+            "j.doe@example.com",
+            Some("Jane Doe"),
+            "j.doe@example.com (Jane Doe)",
+        ),
+    ];
+
+    for (input, address, display_name, output) in passingtests.iter() {
+        let email = input.parse::<EmailAddress>()?;
+        assert_eq!(email.address, *address, "{}", input);
+        assert_eq!(email.display_name.as_deref(), *display_name, "{}", input);
+        assert_eq!(email.to_string(), *output, "{}", input);
+    }
+
+    assert!(matches!(
+        "j.doe.example.com".parse::<EmailAddress>(),
+        Err(Error::SdpEmailMissingAt(_))
+    ));
+    assert!(matches!(
+        "j@doe@example.com".parse::<EmailAddress>(),
+        Err(Error::SdpEmailMissingAt(_))
+    ));
+    assert!(matches!(
+        "j.doe@example.com\r\nk=clear:secret".parse::<EmailAddress>(),
+        Err(Error::SdpEmailInvalidChar(_))
+    ));
+    assert!(matches!(
+        "j doe@example.com".parse::<EmailAddress>(),
+        Err(Error::SdpEmailInvalidChar(_))
+    ));
+    assert!(matches!(
+        "Jane Doe <j.doe@example.com".parse::<EmailAddress>(),
+        Err(Error::SdpUnbalancedDelimiters(_))
+    ));
+    assert!(matches!(
+        EmailAddress::try_new("j.doe@example.com", Some("Jane\nDoe")),
+        Err(Error::SdpEmailInvalidChar(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_timing() -> Result<(), Error> {
+    let permanent = Timing::permanent();
+    assert!(permanent.is_permanent());
+    assert!(permanent.active_at(0));
+    assert!(permanent.active_at(u64::MAX));
+    assert_eq!(permanent.duration(), None);
+    assert_eq!(permanent.to_string(), "0 0");
+
+    let unbounded = Timing::unbounded_from(3034423619);
+    assert!(!unbounded.is_permanent());
+    assert!(unbounded.is_unbounded());
+    assert!(!unbounded.active_at(3034423618));
+    assert!(unbounded.active_at(3034423619));
+    assert!(unbounded.active_at(u64::MAX));
+    assert_eq!(unbounded.duration(), None);
+
+    let bounded = Timing::bounded(2873397496, 2873404696)?;
+    assert!(!bounded.active_at(2873397495));
+    assert!(bounded.active_at(2873397496));
+    assert!(bounded.active_at(2873404696));
+    assert!(!bounded.active_at(2873404697));
+    assert_eq!(bounded.duration(), Some(7200));
+
+    let equal = Timing::new(2873397496, 2873397496)?;
+    assert_eq!(equal.duration(), Some(0));
+    assert!(equal.active_at(2873397496));
+
+    assert!(matches!(
+        Timing::new(300, 100),
+        Err(Error::SdpInvalidTiming {
+            start: 300,
+            stop: 100
+        })
+    ));
+    assert!("300 100".parse::<Timing>().is_err());
+
+    // The fields are public, so an inverted timing can still be built.
+    let inverted = Timing {
+        start_time: 300,
+        stop_time: 100,
+    };
+    assert_eq!(inverted.duration(), None);
+    assert!(!inverted.active_at(200));
+
+    Ok(())
+}
+
+#[test]
+fn test_time_description_activity() -> Result<(), Error> {
+    // Weekly one hour lecture repeated for three months, see the examples in
+    // https://tools.ietf.org/html/rfc4566#section-5.10
+    let start = 3034423619;
+    let stop = 3042462419;
+    let td = TimeDescription::from_lines(&["t=3034423619 3042462419", "r=7d 1h 0 25h"])?;
+    let no_zones = TimeZones::default();
+
+    assert!(td.is_active_at(start, &no_zones));
+    assert!(td.is_active_at(start + 3599, &no_zones));
+    assert!(!td.is_active_at(start + 3600, &no_zones));
+    assert!(td.is_active_at(start + 90000, &no_zones));
+    assert!(td.is_active_at(start + 604800 + 90000 + 10, &no_zones));
+    assert!(!td.is_active_at(start - 1, &no_zones));
+    assert!(!td.is_active_at(stop + 1, &no_zones));
+
+    assert_eq!(
+        td.next_active_interval(start, &no_zones),
+        Some((start, start + 3600))
+    );
+    assert_eq!(
+        td.next_active_interval(start + 1, &no_zones),
+        Some((start + 90000, start + 90000 + 3600))
+    );
+    assert_eq!(
+        td.next_active_interval(start + 90001, &no_zones),
+        Some((start + 604800, start + 604800 + 3600))
+    );
+    assert_eq!(td.next_active_interval(stop, &no_zones), None);
+
+    // Daylight saving: one hour earlier after the adjustment time.
+    let adjustment = start + 2 * 604800;
+    let zones: TimeZones = vec![TimeZone {
+        adjustment_time: adjustment,
+        offset: -3600,
+    }]
+    .into();
+    assert!(td.is_active_at(start + 604800, &zones));
+    assert!(!td.is_active_at(start + 2 * 604800 + 1800, &zones));
+    assert!(td.is_active_at(start + 2 * 604800 - 1800, &zones));
+    assert_eq!(
+        td.next_active_interval(start + 604800 + 90001, &zones),
+        Some((start + 2 * 604800 - 3600, start + 2 * 604800))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_time_description_activity_edge_cases() -> Result<(), Error> {
+    let no_zones = TimeZones::default();
+
+    // Unbounded without repeats.
+    let td = TimeDescription::from_lines(&["t=3034423619 0"])?;
+    assert!(td.is_active_at(u64::MAX, &no_zones));
+    assert_eq!(
+        td.next_active_interval(3034423619, &no_zones),
+        Some((3034423619, u64::MAX))
+    );
+    assert_eq!(td.next_active_interval(3034423620, &no_zones), None);
+
+    // Unbounded with repeats near the end of the NTP range must not overflow.
+    let td = TimeDescription::from_lines(&["t=3034423619 0", "r=1d 1h 0"])?;
+    assert!(td.is_active_at(3034423619 + 86400 * 1_000_000, &no_zones));
+    assert!(!td.is_active_at(u64::MAX, &no_zones));
+    assert!(td.next_active_interval(u64::MAX - 10, &no_zones).is_none());
+
+    // A zero repeat interval is treated as no repeats.
+    let td = TimeDescription {
+        timing: Timing::bounded(100, 200)?,
+        repeat_times: vec![RepeatTime {
+            interval: 0,
+            duration: 10,
+            offsets: vec![0],
+        }],
+    };
+    assert!(td.is_active_at(150, &no_zones));
+    assert_eq!(td.next_active_interval(0, &no_zones), Some((100, 200)));
+
+    // Overlapping occurrences.
+    let td = TimeDescription::from_lines(&["t=1000 0", "r=100 150 0 10"])?;
+    assert!(td.is_active_at(1205, &no_zones));
+    assert_eq!(td.next_active_interval(1101, &no_zones), Some((1110, 1260)));
+
+    // Repeat times without a positive duration never activate the session.
+    let td = TimeDescription::from_lines(&["t=0 0", "r=1 0 0"])?;
+    assert!(!td.is_active_at(u64::MAX / 2, &no_zones));
+    assert_eq!(td.next_active_interval(0, &no_zones), None);
+    let td = TimeDescription {
+        timing: Timing::bounded(100, 200)?,
+        repeat_times: vec![RepeatTime {
+            interval: 10,
+            duration: -5,
+            offsets: vec![0],
+        }],
+    };
+    assert!(!td.is_active_at(150, &no_zones));
+    assert_eq!(td.next_active_interval(0, &no_zones), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_time_description_activity_large_time_zone_offsets() -> Result<(), Error> {
+    // Every repetition is moved by far more than the repeat interval.
+    let zones: TimeZones = vec![TimeZone {
+        adjustment_time: 0,
+        offset: 1_000_000_000_000,
+    }]
+    .into();
+    let td = TimeDescription::from_lines(&["t=1000 0", "r=10 5 0"])?;
+    assert!(!td.is_active_at(1000, &zones));
+    assert!(td.is_active_at(1_000_000_001_000, &zones));
+    assert!(!td.is_active_at(1_000_000_001_005, &zones));
+    assert_eq!(
+        td.next_active_interval(0, &zones),
+        Some((1_000_000_001_000, 1_000_000_001_005))
+    );
+
+    let zones: TimeZones = vec![
+        TimeZone {
+            adjustment_time: 5000,
+            offset: -1_000_000_000_000,
+        },
+        TimeZone {
+            adjustment_time: 6000,
+            offset: 0,
+        },
+    ]
+    .into();
+    // Repetitions between the adjustments would start before the NTP epoch.
+    assert!(td.is_active_at(4990, &zones));
+    assert!(!td.is_active_at(5000, &zones));
+    assert_eq!(td.next_active_interval(4991, &zones), Some((6000, 6005)));
+
+    let td = TimeDescription::from_lines(&["t=0 0", "r=1 0 0"])?;
+    assert!(!td.is_active_at(1_000_000_000_000, &zones));
+    assert_eq!(td.next_active_interval(0, &zones), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_time_description_occurrences() -> Result<(), Error> {
+    let start = 3034423619;
+    let stop = 3042462419;
+    let no_zones = TimeZones::default();
+
+    let td = TimeDescription::from_lines(&["t=3034423619 3042462419", "r=7d 1h 0 25h"])?;
+    let occurrences: Vec<_> = td.occurrences(&no_zones).collect();
+    assert_eq!(occurrences.len(), 28);
+    assert_eq!(
+        &occurrences[..3],
+        &[
+            (start, start + 3600),
+            (start + 90000, start + 90000 + 3600),
+            (start + 604800, start + 604800 + 3600),
+        ]
+    );
+    assert!(occurrences.iter().all(|&(_, end)| end <= stop));
+
+    // Daylight saving: one hour earlier after the adjustment time.
+    let zones: TimeZones = vec![TimeZone {
+        adjustment_time: start + 2 * 604800,
+        offset: -3600,
+    }]
+    .into();
+    let occurrences: Vec<_> = td.occurrences(&zones).skip(4).take(2).collect();
+    assert_eq!(
+        occurrences,
+        vec![
+            (start + 2 * 604800 - 3600, start + 2 * 604800),
+            (
+                start + 2 * 604800 + 90000 - 3600,
+                start + 2 * 604800 + 90000
+            ),
+        ]
+    );
+
+    // Offsets of several repeat lines starting together are merged.
+    let td = TimeDescription::from_lines(&["t=1000 2000", "r=100 10 0", "r=200 30 0"])?;
+    let occurrences: Vec<_> = td.occurrences(&no_zones).take(3).collect();
+    assert_eq!(occurrences, vec![(1000, 1030), (1100, 1110), (1200, 1230)]);
+
+    // Without repeats the session is active once, clipped to its times.
+    let td = TimeDescription::from_lines(&["t=1000 2000"])?;
+    assert_eq!(
+        td.occurrences(&no_zones).collect::<Vec<_>>(),
+        vec![(1000, 2000)]
+    );
+    let td = TimeDescription::from_lines(&["t=3034423619 0"])?;
+    assert_eq!(
+        td.occurrences(&no_zones).collect::<Vec<_>>(),
+        vec![(3034423619, u64::MAX)]
+    );
+
+    // Unbounded repeats end with the NTP range without overflowing.
+    let td = TimeDescription {
+        timing: Timing::unbounded_from(u64::MAX - 250),
+        repeat_times: vec![RepeatTime {
+            interval: 100,
+            duration: 80,
+            offsets: vec![0],
+        }],
+    };
+    let mut occurrences = td.occurrences(&no_zones);
+    assert_eq!(occurrences.next(), Some((u64::MAX - 250, u64::MAX - 170)));
+    assert_eq!(occurrences.next(), Some((u64::MAX - 150, u64::MAX - 70)));
+    assert_eq!(occurrences.next(), Some((u64::MAX - 50, u64::MAX)));
+    assert_eq!(occurrences.next(), None);
+    assert_eq!(occurrences.next(), None);
+
+    // Repeat times without a positive duration yield nothing, without
+    // walking the repetitions up to the end of the NTP range.
+    let td = TimeDescription::from_lines(&["t=0 0", "r=1 0 0"])?;
+    assert_eq!(td.occurrences(&no_zones).next(), None);
+    let td = TimeDescription::from_lines(&["t=1000 0", "r=1 0 0", "r=100 10 0"])?;
+    assert_eq!(
+        td.occurrences(&no_zones).take(2).collect::<Vec<_>>(),
+        vec![(1000, 1010), (1100, 1110)]
+    );
+
+    // Repetitions moved before the start time by a large "z=" offset are
+    // jumped over.
+    let zones: TimeZones = vec![
+        TimeZone {
+            adjustment_time: 0,
+            offset: -1_000_000_000_000,
+        },
+        TimeZone {
+            adjustment_time: 1_000_000_000_000,
+            offset: 0,
+        },
+    ]
+    .into();
+    let td = TimeDescription::from_lines(&["t=1000 0", "r=1 1 0"])?;
+    assert_eq!(
+        td.occurrences(&zones).next(),
+        Some((1_000_000_000_000, 1_000_000_000_001))
+    );
+
+    // A repetition in progress at the start time is clipped to it, and
+    // overlapping ones starting together are merged.
+    let td = TimeDescription::from_lines(&["t=1000 0", "r=100 250 -350"])?;
+    assert_eq!(
+        td.occurrences(&no_zones).take(2).collect::<Vec<_>>(),
+        vec![(1000, 1200), (1050, 1300)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_time_description_occurrences_random() -> Result<(), Error> {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(3);
+    for _ in 0..200 {
+        let start = rng.gen_range(0..1_000_000);
+        let timing = if rng.gen_bool(0.3) {
+            Timing::unbounded_from(start)
+        } else {
+            Timing::bounded(start, start + rng.gen_range(1..100_000))?
+        };
+        let mut repeat_times = vec![];
+        for _ in 0..rng.gen_range(0..3) {
+            repeat_times.push(RepeatTime {
+                interval: rng.gen_range(7_200..20_000),
+                duration: rng.gen_range(1..10_000),
+                offsets: (0..rng.gen_range(1..4))
+                    .map(|_| rng.gen_range(0..30_000))
+                    .collect(),
+            });
+        }
+        let zones: TimeZones = (0..rng.gen_range(0..3))
+            .map(|i| TimeZone {
+                adjustment_time: start + 50_000 * (i + 1),
+                offset: rng.gen_range(-3_600..3_600),
+            })
+            .collect::<Vec<_>>()
+            .into();
+        let td = TimeDescription {
+            timing,
+            repeat_times,
+        };
+
+        let mut previous = None;
+        for (occurrence_start, occurrence_end) in td.occurrences(&zones).take(50) {
+            assert!(occurrence_start < occurrence_end, "{:?}", td);
+            assert!(previous < Some(occurrence_start), "{:?}", td);
+            let midpoint = occurrence_start + (occurrence_end - occurrence_start) / 2;
+            assert!(
+                td.is_active_at(midpoint, &zones),
+                "{:?} at {}",
+                td,
+                midpoint
+            );
+            previous = Some(occurrence_start);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_marshal_into() -> Result<(), Error> {
+    let tests = vec![
+        ("Canonical", CANONICAL_UNMARSHAL_SDP),
+        ("URI", URI_SDP),
+        ("EmailAddress", EMAIL_ADDRESS_SDP),
+        ("PhoneNumber", PHONE_NUMBER_SDP),
+        ("RepeatTimes", REPEAT_TIMES_SDPEXPECTED),
+        ("TimeZones", TIME_ZONES_SDPEXPECTED),
+        ("SessionEncryptionKey", SESSION_ENCRYPTION_KEY_SDP),
+        ("MediaEncryptionKey", MEDIA_ENCRYPTION_KEY_SDP),
+    ];
+
+    for (name, input) in tests {
+        let sd = input.parse::<SessionDescription>()?;
+        let expected = sd.to_string();
+        assert_eq!(sd.marshal_len_hint(), expected.len(), "{}", name);
+
+        let mut buf = "prefix\r\n".to_owned();
+        sd.marshal_into(&mut buf);
+        assert_eq!(buf, format!("prefix\r\n{}", expected), "{}", name);
+
+        let mut media = String::new();
+        for md in &sd.media_descriptions {
+            assert_eq!(md.marshal_len_hint(), md.to_string().len(), "{}", name);
+            md.marshal_into(&mut media);
+        }
+        assert!(expected.ends_with(&media), "{}", name);
+    }
+
+    let origin = "jdoe 2890844526 2890842807 IN IP4 10.47.16.5".parse::<Origin>()?;
+    assert_eq!(origin.marshal_len_hint(), origin.to_string().len());
+    assert_eq!(format!("[{:>6}]", origin.username), "[  jdoe]");
+    assert_eq!(format!("[{:<6}]", SessionName::try_new("-")?), "[-     ]");
+
+    Ok(())
+}
+
+#[test]
+fn test_unmarshal_encryption_keys() -> Result<(), Error> {
+    // One "k=" at session level, and one in each media description, which
+    // are written back where they belong.
+    const SDP: &str = "v=0\r\n\
+o=- 0 0 IN IP4 0.0.0.0\r\n\
+s=-\r\n\
+t=0 0\r\n\
+k=prompt\r\n\
+a=recvonly\r\n\
+m=audio 9 RTP/AVP 0\r\n\
+b=AS:64\r\n\
+k=uri:https://example.com/key\r\n\
+a=rtpmap:0 PCMU/8000\r\n\
+m=video 9 RTP/AVP 96\r\n\
+k=prompt\r\n";
+
+    let sd = SDP.parse::<SessionDescription>()?;
+    assert_eq!(sd.encryption_key, Some(EncryptionKey::Prompt));
+    assert_eq!(
+        sd.media_descriptions[0].encryption_key,
+        Some("uri:https://example.com/key".parse()?)
+    );
+    assert_eq!(
+        sd.media_descriptions[1].encryption_key,
+        Some(EncryptionKey::Prompt)
+    );
+    assert_eq!(sd.marshal(), SDP);
+    assert_eq!(SessionDescription::parse_borrowed(SDP)?.to_owned(), sd);
+
+    // A second "k=" at the same level is rejected.
+    let tests = [
+        (
+            "v=0\r\no=- 0 0 IN IP4 0.0.0.0\r\ns=-\r\nt=0 0\r\nk=prompt\r\nk=prompt\r\n",
+            6,
+        ),
+        (
+            "v=0\r\no=- 0 0 IN IP4 0.0.0.0\r\ns=-\r\nt=0 0\r\nk=prompt\r\na=recvonly\r\nk=prompt\r\n",
+            7,
+        ),
+        (
+            "v=0\r\no=- 0 0 IN IP4 0.0.0.0\r\ns=-\r\nt=0 0\r\nm=audio 9 RTP/AVP 0\r\nk=prompt\r\na=sendrecv\r\nk=clear:secret\r\n",
+            8,
+        ),
+    ];
+    for &(input, line) in tests.iter() {
+        for result in [
+            input.parse::<SessionDescription>(),
+            SessionDescription::parse_borrowed(input).map(|sd| sd.to_owned()),
+        ] {
+            match result {
+                Err(Error::SdpParse {
+                    line: l,
+                    line_type: Some('k'),
+                    source,
+                }) => {
+                    assert_eq!(l, line, "{:?}", input);
+                    assert!(
+                        matches!(*source, Error::SdpDuplicateField(ref key) if key == "k="),
+                        "{:?}: {}",
+                        input,
+                        source
+                    );
+                }
+                other => panic!("unexpected result for {:?}: {:?}", input, other),
+            }
+        }
     }
+
+    Ok(())
+}
+
+#[test]
+fn test_unmarshal_error_position() {
+    let tests = [
+        // out of order: s= before o=
+        (
+            "v=0\r\ns=-\r\no=- 0 0 IN IP4 0.0.0.0\r\nt=0 0\r\n",
+            2,
+            Some('s'),
+        ),
+        // unknown line type
+        (
+            "v=0\r\no=- 0 0 IN IP4 0.0.0.0\r\ns=-\r\nt=0 0\r\nx=foo\r\n",
+            5,
+            Some('x'),
+        ),
+        // invalid value, with bare line endings
+        (
+            "v=0\no=- 0 0 IN IP4 0.0.0.0\ns=-\nt=0 0\nm=audio 70000 RTP/AVP 0\n",
+            5,
+            Some('m'),
+        ),
+        // invalid origin after a blank line
+        ("v=0\r\n\r\no=- x 0 IN IP4 0.0.0.0\r\n", 3, Some('o')),
+        // missing time description at the end of input
+        ("v=0\r\no=- 0 0 IN IP4 0.0.0.0\r\ns=-\r\n", 4, None),
+    ];
+
+    for (input, line, line_type) in tests.iter() {
+        match input.parse::<SessionDescription>() {
+            Err(Error::SdpParse {
+                line: l,
+                line_type: t,
+                ..
+            }) => {
+                assert_eq!((l, t), (*line, *line_type), "{:?}", input);
+            }
+            other => panic!("unexpected result for {:?}: {:?}", input, other),
+        }
+    }
+}
+
+#[test]
+fn test_round_trip_structure() -> Result<(), Error> {
+    let corpus = [
+        SESSION_INFORMATION_SDP,
+        URI_SDP,
+        EMAIL_ADDRESS_SDP,
+        PHONE_NUMBER_SDP,
+        REPEAT_TIMES_SDPEXPECTED,
+        SESSION_CONNECTION_INFORMATION_SDP,
+        SESSION_BANDWIDTH_SDP,
+        SESSION_ENCRYPTION_KEY_SDP,
+        SESSION_ATTRIBUTES_SDP,
+        TIME_ZONES_SDP2,
+        MEDIA_NAME_SDP,
+        MEDIA_TITLE_SDP,
+        MEDIA_CONNECTION_INFORMATION_SDP,
+        MEDIA_BANDWIDTH_SDP,
+        MEDIA_ENCRYPTION_KEY_SDP,
+        MEDIA_ATTRIBUTES_SDP,
+        CANONICAL_UNMARSHAL_SDP,
+        WEBRTC_OFFER_SDP,
+    ];
+
+    for sdp_str in corpus.iter() {
+        let parsed = sdp_str.parse::<SessionDescription>()?;
+        parsed.check_line_breaks()?;
+
+        let bytes = parsed.to_bytes();
+        assert_eq!(bytes, parsed.to_string().into_bytes());
+        let text = String::from_utf8(bytes)?;
+        assert!(text.ends_with("\r\n"));
+        assert_eq!(
+            text.matches('\n').count(),
+            text.matches("\r\n").count(),
+            "{}",
+            text
+        );
+
+        let reparsed = text.parse::<SessionDescription>()?;
+        assert_eq!(reparsed, parsed, "{}", sdp_str);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_line_breaks() -> Result<(), Error> {
+    let mut sd = CANONICAL_UNMARSHAL_SDP.parse::<SessionDescription>()?;
+    sd.check_line_breaks()?;
+
+    assert!(matches!(
+        "two\r\nlines".parse::<Information>(),
+        Err(Error::SdpInvalidCharacter(_))
+    ));
+
+    sd.media_descriptions[0].attributes.push(Attribute::new(
+        "tool".to_owned(),
+        Some("x\na=injected".to_owned()),
+    ));
+    assert!(matches!(
+        sd.check_line_breaks(),
+        Err(Error::SdpUnexpectedLineBreak(ref key)) if key == "a="
+    ));
+    // The "k=prompt" fields of the description get warnings of their own.
+    let diagnostics = sd
+        .validate()
+        .into_iter()
+        .filter(|d| d.kind != DiagnosticKind::DeprecatedEncryptionKey)
+        .collect::<Vec<_>>();
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].kind, DiagnosticKind::UnexpectedLineBreak);
+    assert_eq!(diagnostics[0].field, "a=");
+    // The value isn't a valid "a=tool" either.
+    assert_eq!(diagnostics[1].kind, DiagnosticKind::MalformedAttribute);
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_borrowed() -> Result<(), Error> {
+    let corpus = [
+        SESSION_INFORMATION_SDP,
+        SESSION_INFORMATION_SDPLFONLY,
+        SESSION_INFORMATION_SDPEXTRA_CRLF,
+        URI_SDP,
+        EMAIL_ADDRESS_SDP,
+        PHONE_NUMBER_SDP,
+        REPEAT_TIMES_SDPEXTRA_CRLF,
+        SESSION_CONNECTION_INFORMATION_SDP,
+        SESSION_BANDWIDTH_SDP,
+        SESSION_ENCRYPTION_KEY_SDPEXTRA_CRLF,
+        SESSION_ATTRIBUTES_SDP,
+        TIME_ZONES_SDP2EXTRA_CRLF,
+        MEDIA_NAME_SDPEXTRA_CRLF,
+        MEDIA_TITLE_SDP,
+        MEDIA_CONNECTION_INFORMATION_SDPEXTRA_CRLF,
+        MEDIA_DESCRIPTION_OUT_OF_ORDER_SDP,
+        MEDIA_BANDWIDTH_SDP,
+        MEDIA_ENCRYPTION_KEY_SDPEXTRA_CRLF,
+        MEDIA_ATTRIBUTES_SDP,
+        CANONICAL_UNMARSHAL_SDP,
+        WEBRTC_OFFER_SDP,
+    ];
+
+    for sdp_str in corpus.iter() {
+        let owned = sdp_str.parse::<SessionDescription>()?;
+        let borrowed = SessionDescription::parse_borrowed(sdp_str)?;
+        assert_eq!(borrowed.to_owned(), owned, "{}", sdp_str);
+    }
+
+    let borrowed = SessionDescription::parse_borrowed(WEBRTC_OFFER_SDP)?;
+    let input = WEBRTC_OFFER_SDP.as_bytes().as_ptr_range();
+    let points_into_input = |s: &str| input.contains(&s.as_ptr());
+    assert!(points_into_input(borrowed.origin.username));
+    assert!(points_into_input(borrowed.session_name));
+    for md in &borrowed.media_descriptions {
+        assert!(points_into_input(md.media_name.media));
+        for attribute in &md.attributes {
+            assert!(points_into_input(attribute.key));
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_borrowed_errors() {
+    let tests = [
+        "",
+        "x=0\r\n",
+        "v=1\r\n",
+        "v=0\r\ns=-\r\n",
+        "v=0\r\no=- 0 0 IN IP4 0.0.0.0\r\ns=-\r\n",
+        "v=0\r\no=- 0 0 IN IP4 0.0.0.0\r\ns=-\r\nt=0 0\r\nx=foo\r\n",
+        "v=0\no=- 0 0 IN IP4 0.0.0.0\ns=-\nt=0 0\nm=audio 70000 RTP/AVP 0\n",
+        "v=0\r\n\r\no=- x 0 IN IP4 0.0.0.0\r\n",
+        "v=0\r\no=- 0 0 IN IP4 0.0.0.0\r\ns=-\r\nt=0 0\r\nmissing separator\r\n",
+        "v=0\r\no=- 0 0 IN IP4 0.0.0.0\r\ns=-\r\nt=0 0\r\na=bad name\r\n",
+        "v=0\r\no=- 0 0 IN IP4 0.0.0.0\r\ns=-\r\nt=0 0\r\nm=audio 9 RTP/AVP 0\r\nc=IN IP4 ::1\r\n",
+        "v=0\r\no=- 0 0 IN IP4 0.0.0.0\r\ns=-\r\nt=0 0\r\na=recvonly\r\nk=prompt\r\n",
+        "v=0\r\njunk\r\no=- 0 0 IN IP4 0.0.0.0\r\n",
+        "v=0\njunk\no=- 0 0 IN IP4 0.0.0.0\n",
+    ];
+
+    for input in tests.iter() {
+        let owned = match input.parse::<SessionDescription>() {
+            Err(Error::SdpParse {
+                line,
+                line_type,
+                source,
+            }) => (line, line_type, source.to_string()),
+            other => panic!("unexpected result for {:?}: {:?}", input, other),
+        };
+        let borrowed = match SessionDescription::parse_borrowed(input) {
+            Err(Error::SdpParse {
+                line,
+                line_type,
+                source,
+            }) => (line, line_type, source.to_string()),
+            other => panic!("unexpected result for {:?}: {:?}", input, other),
+        };
+        assert_eq!(borrowed, owned, "{:?}", input);
+    }
+}
+
+#[test]
+fn test_username() -> Result<(), Error> {
+    let username = "alice".parse::<Username>()?;
+    assert_eq!(username, "alice");
+    assert_eq!(Username::try_from("-")?.as_str(), "-");
+    assert_eq!(Username::try_from("bob".to_owned())?.to_string(), "bob");
+
+    let failingtests = [
+        "",
+        "alice bob",
+        "alice\r\nm=audio 0",
+        "alice\n",
+        "al\u{7}ice",
+    ];
+    for input in failingtests.iter() {
+        assert!(
+            matches!(input.parse::<Username>(), Err(Error::SdpInvalidUsername(_))),
+            "{:?}",
+            input
+        );
+        assert!(Username::try_from(*input).is_err(), "{:?}", input);
+        assert!(
+            Username::try_from(input.to_string()).is_err(),
+            "{:?}",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_session_name() -> Result<(), Error> {
+    let session_name = "SDP Seminar".parse::<SessionName>()?;
+    assert_eq!(session_name, "SDP Seminar");
+    assert_eq!(SessionName::try_from("-")?.as_str(), Some("-"));
+    assert_eq!(SessionName::try_from(" ".to_owned())?.to_string(), " ");
+
+    let failingtests = [
+        "Seminar\r\nc=IN IP4 10.0.0.1",
+        "Seminar\n",
+        "Seminar\r",
+        "Sem\0inar",
+    ];
+    for input in failingtests.iter() {
+        assert!(
+            matches!(
+                input.parse::<SessionName>(),
+                Err(Error::SdpInvalidCharacter(_))
+            ),
+            "{:?}",
+            input
+        );
+        assert!(SessionName::try_from(*input).is_err(), "{:?}", input);
+        assert!(
+            SessionName::try_from(input.to_string()).is_err(),
+            "{:?}",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_string_types_reject_line_breaks() {
+    for c in ['\r', '\n', '\0'].iter() {
+        let text = format!("Seminar{}c=IN IP4 10.0.0.1", c);
+        assert!(text.parse::<Information>().is_err(), "{:?}", text);
+        assert!(text.parse::<SessionName>().is_err(), "{:?}", text);
+        assert!(Information::try_from(text.clone()).is_err(), "{:?}", text);
+
+        let key = format!("clear:secret{}a=recvonly", c);
+        assert!(key.parse::<EncryptionKey>().is_err(), "{:?}", key);
+        assert!(EncryptionKey::try_from(key.as_str()).is_err(), "{:?}", key);
+
+        let username = format!("alice{}m=audio", c);
+        assert!(username.parse::<Username>().is_err(), "{:?}", username);
+
+        let email = format!("j.doe@example.com{}k=prompt", c);
+        assert!(email.parse::<EmailAddress>().is_err(), "{:?}", email);
+        assert!(
+            EmailAddress::try_new("j.doe@example.com", Some(&format!("Jane{}Doe", c))).is_err(),
+            "{:?}",
+            c
+        );
+
+        let phone = format!("+1 617 555-6011{}k=prompt", c);
+        assert!(phone.parse::<PhoneNumber>().is_err(), "{:?}", phone);
+
+        let uri = format!("http://www.example.com/{}k=prompt", c);
+        assert!(uri.parse::<Uri>().is_err(), "{:?}", uri);
+    }
+}
+
+#[test]
+fn test_reoffer_keeps_session_identity() -> Result<(), Error> {
+    let mut sd = CANONICAL_MARSHAL_SDP.parse::<SessionDescription>()?;
+    let original = sd.clone();
+
+    sd.prepare_reoffer()?;
+    assert_eq!(
+        sd.origin.session_version,
+        original.origin.session_version + 1
+    );
+    assert_eq!(sd.origin.session_id, original.origin.session_id);
+    assert!(sd.origin.is_same_session(&original.origin));
+    assert_ne!(sd.origin, original.origin);
+
+    sd.origin.session_version = original.origin.session_version;
+    assert_eq!(sd, original);
+
+    let mut moved = original.origin.clone();
+    moved.unicast_address = "10.47.16.6".to_owned();
+    assert!(!moved.is_same_session(&original.origin));
+
+    let mut restarted = original.origin.clone();
+    restarted.session_id += 1;
+    assert!(!restarted.is_same_session(&original.origin));
+
+    Ok(())
+}
+
+#[test]
+fn test_bump_version_overflow() {
+    let mut origin = Origin {
+        session_version: u64::MAX,
+        ..Origin::new()
+    };
+    assert!(matches!(
+        origin.bump_version(),
+        Err(Error::SdpSessionVersionOverflow)
+    ));
+    assert_eq!(origin.session_version, u64::MAX);
+}
+
+#[test]
+fn test_origin_round_trip() -> Result<(), Error> {
+    let tests = [
+        (
+            "- 4611731400430051336 2 IN IP4 127.0.0.1",
+            ("-", 4611731400430051336, 2, "IN", "IP4", "127.0.0.1"),
+        ),
+        (
+            "jdoe 2890844526 2890842807 IN IP6 2001:db8::1",
+            ("jdoe", 2890844526, 2890842807, "IN", "IP6", "2001:db8::1"),
+        ),
+        (
+            "- 0 0 IN IP4 host.example.com",
+            ("-", 0, 0, "IN", "IP4", "host.example.com"),
+        ),
+    ];
+
+    for (value, expected) in tests.iter() {
+        let origin = value.parse::<Origin>()?;
+        let (username, session_id, session_version, network_type, address_type, address) =
+            *expected;
+        assert_eq!(origin.username, username);
+        assert_eq!(origin.session_id, session_id);
+        assert_eq!(origin.session_version, session_version);
+        assert_eq!(origin.network_type.as_str(), network_type);
+        assert_eq!(origin.address_type, address_type);
+        assert_eq!(origin.unicast_address, address);
+        assert_eq!(origin.to_string(), *value);
+
+        let sdp = format!("v=0\r\no={}\r\ns=-\r\nt=0 0\r\n", value);
+        assert_eq!(sdp.parse::<SessionDescription>()?.marshal(), sdp);
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_origin_new_for_host() -> Result<(), Error> {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    let origin = Origin::new_for_host(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)));
+    assert_eq!(origin.to_string().parse::<Origin>()?, origin);
+    assert_eq!(origin.username, "-");
+    assert_eq!(origin.session_id >> 63, 0);
+    assert!(origin.session_version > NTP_UNIX_OFFSET);
+    assert_eq!(origin.address_type, "IP4");
+    assert_eq!(origin.unicast_address, "192.0.2.1");
+
+    let origin = Origin::new_for_host(IpAddr::V6(Ipv6Addr::LOCALHOST));
+    assert_eq!(origin.network_type, NetType::In);
+    assert_eq!(origin.address_type, "IP6");
+    assert_eq!(origin.unicast_address, "::1");
+    assert!(!origin.is_same_session(&Origin::new_for_host(IpAddr::V6(Ipv6Addr::LOCALHOST))));
+
+    Ok(())
+}
+
+#[test]
+fn test_protocol_version() -> Result<(), Error> {
+    assert_eq!("0".parse::<Version>()?, Version::V0);
+    assert_eq!(Version::V0.to_string(), "0");
+    // 1*DIGIT allows leading zeros; "v=00" is version 0.
+    assert_eq!("00".parse::<Version>()?, Version::V0);
+
+    assert!(matches!(
+        "1".parse::<Version>(),
+        Err(Error::SdpUnsupportedVersion { found: 1 })
+    ));
+    for garbage in ["", "x", "+0", "-1", "0.1", "99999999999999999999"] {
+        assert!(
+            matches!(garbage.parse::<Version>(), Err(Error::SdpInvalidSyntax(_))),
+            "{:?}",
+            garbage
+        );
+    }
+
+    let rest = "o=- 0 0 IN IP4 0.0.0.0\r\ns=-\r\nt=0 0\r\n";
+    let sd = format!("v=00\r\n{}", rest).parse::<SessionDescription>()?;
+    assert_eq!(sd.version, Version::V0);
+    assert_eq!(sd.marshal(), format!("v=0\r\n{}", rest));
+
+    let unsupported = format!("v=1\r\n{}", rest);
+    for result in [
+        unsupported.parse::<SessionDescription>().map(|_| ()),
+        SessionDescription::parse_borrowed(&unsupported).map(|_| ()),
+    ] {
+        match result {
+            Err(Error::SdpParse {
+                line: 1, source, ..
+            }) => assert!(
+                matches!(*source, Error::SdpUnsupportedVersion { found: 1 }),
+                "{}",
+                source
+            ),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    // The first line must be "v=".
+    for missing in [rest.to_owned(), format!("\r\n{}", rest)] {
+        assert!(missing.parse::<SessionDescription>().is_err());
+        assert!(SessionDescription::parse_borrowed(&missing).is_err());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_diagnostics() -> Result<(), Error> {
+    use crate::diagnostic::Severity;
+
+    const VALID_SDP: &str = "v=0\r\n\
+o=- 0 0 IN IP4 0.0.0.0\r\n\
+s=-\r\n\
+c=IN IP4 0.0.0.0\r\n\
+b=CT:1000\r\n\
+t=0 0\r\n\
+m=audio 9 RTP/AVP 0 96\r\n\
+a=rtpmap:96 opus/48000/2\r\n\
+a=ptime:20\r\n\
+a=maxptime:120\r\n";
+
+    let valid = VALID_SDP.parse::<SessionDescription>()?;
+    assert_eq!(valid.validate(), vec![]);
+
+    let kinds = |sd: &SessionDescription| -> Vec<(DiagnosticKind, Severity, Option<usize>)> {
+        sd.validate()
+            .into_iter()
+            .map(|d| (d.kind, d.severity, d.media_index))
+            .collect()
+    };
+
+    let mut sd = valid.clone();
+    sd.connection_information = None;
+    assert_eq!(
+        kinds(&sd),
+        vec![(
+            DiagnosticKind::MissingConnectionInformation,
+            Severity::Error,
+            Some(0)
+        )]
+    );
+    sd.media_descriptions[0].connection_information = valid.connection_information.clone();
+    assert_eq!(kinds(&sd), vec![]);
+
+    let mut sd = valid.clone();
+    sd.time_descriptions.clear();
+    assert_eq!(
+        kinds(&sd),
+        vec![(DiagnosticKind::MissingTiming, Severity::Error, None)]
+    );
+
+    let mut sd = valid.clone();
+    sd.bandwidth.push("CT:2000".parse()?);
+    assert_eq!(
+        kinds(&sd),
+        vec![(
+            DiagnosticKind::DuplicateConferenceTotalBandwidth,
+            Severity::Error,
+            None
+        )]
+    );
+
+    let mut sd = valid.clone();
+    sd.bandwidth.push("AS:128".parse()?);
+    sd.bandwidth.push("AS:256".parse()?);
+    sd.media_descriptions[0]
+        .bandwidth
+        .push("TIAS:64000".parse()?);
+    sd.media_descriptions[0]
+        .bandwidth
+        .push("TIAS:96000".parse()?);
+    assert_eq!(
+        kinds(&sd),
+        vec![
+            (DiagnosticKind::DuplicateBandwidth, Severity::Error, None),
+            (DiagnosticKind::DuplicateBandwidth, Severity::Error, Some(0)),
+        ]
+    );
+
+    let mut sd = valid.clone();
+    sd.encryption_key = Some("clear:secret".parse()?);
+    assert_eq!(
+        kinds(&sd),
+        vec![(
+            DiagnosticKind::InsecureEncryptionKey,
+            Severity::Warning,
+            None
+        )]
+    );
+    let mut sd = valid.clone();
+    sd.media_descriptions[0].encryption_key = Some("base64:c2VjcmV0".parse()?);
+    assert_eq!(
+        kinds(&sd),
+        vec![(
+            DiagnosticKind::InsecureEncryptionKey,
+            Severity::Warning,
+            Some(0)
+        )]
+    );
+    // Any other method is still NOT RECOMMENDED, at either level.
+    let mut sd = valid.clone();
+    sd.encryption_key = Some("prompt".parse()?);
+    sd.media_descriptions[0].encryption_key = Some("uri:https://example.com/key".parse()?);
+    assert_eq!(
+        kinds(&sd),
+        vec![
+            (
+                DiagnosticKind::DeprecatedEncryptionKey,
+                Severity::Warning,
+                None
+            ),
+            (
+                DiagnosticKind::DeprecatedEncryptionKey,
+                Severity::Warning,
+                Some(0)
+            ),
+        ]
+    );
+
+    let mut sd = valid.clone();
+    sd.media_descriptions[0]
+        .media_name
+        .formats
+        .push("97".to_owned());
+    assert_eq!(
+        kinds(&sd),
+        vec![(DiagnosticKind::MissingRtpMap, Severity::Error, Some(0))]
+    );
+
+    let mut sd = valid.clone();
+    sd.media_descriptions[0].set_ptime("150".parse()?);
+    assert_eq!(
+        kinds(&sd),
+        vec![(
+            DiagnosticKind::PTimeExceedsMaxPTime,
+            Severity::Warning,
+            Some(0)
+        )]
+    );
+    let diagnostics = sd.validate();
+    assert!(diagnostics.iter().all(|d| d.severity != Severity::Error));
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "warning: media 0: a=ptime: ptime 150 exceeds maxptime 120"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_ice_credentials() -> Result<(), Error> {
+    const SESSION_LEVEL_SDP: &str = "v=0\r\n\
+o=- 0 0 IN IP4 0.0.0.0\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=ice-ufrag:sess\r\n\
+a=ice-pwd:sessionpasswordsession1\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96\r\n";
+    const MEDIA_LEVEL_SDP: &str = "v=0\r\n\
+o=- 0 0 IN IP4 0.0.0.0\r\n\
+s=-\r\n\
+t=0 0\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+a=ice-ufrag:aud1\r\n\
+a=ice-pwd:audiopasswordaudiopass1\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96\r\n\
+a=ice-ufrag:vid1\r\n\
+a=ice-pwd:videopasswordvideopass1\r\n";
+    const MIXED_SDP: &str = "v=0\r\n\
+o=- 0 0 IN IP4 0.0.0.0\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=ice-ufrag:sess\r\n\
+a=ice-pwd:sessionpasswordsession1\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96\r\n\
+a=ice-ufrag:vid1\r\n\
+a=ice-pwd:videopasswordvideopass1\r\n";
+
+    let ufrag_of = |sd: &SessionDescription, index: usize| {
+        sd.ice_credentials_for(index)
+            .map(|(ufrag, _)| ufrag.to_string())
+    };
+
+    let mut sd = SESSION_LEVEL_SDP.parse::<SessionDescription>()?;
+    let (ufrag, pwd) = sd.ice_credentials().expect("session credentials");
+    assert_eq!(ufrag.as_str(), "sess");
+    assert_eq!(pwd.expose(), "sessionpasswordsession1");
+    assert_eq!(ufrag_of(&sd, 1).as_deref(), Some("sess"));
+
+    sd.rotate_ice_credentials("new1".parse()?, "newpasswordnewpassword1".parse()?);
+    assert_eq!(ufrag_of(&sd, 0).as_deref(), Some("new1"));
+    assert_eq!(sd.attributes.get::<IceUfrag>().count(), 1);
+    assert!(sd
+        .media_descriptions
+        .iter()
+        .all(|md| md.attributes.is_empty()));
+
+    let mut sd = MEDIA_LEVEL_SDP.parse::<SessionDescription>()?;
+    assert_eq!(ufrag_of(&sd, 0).as_deref(), Some("aud1"));
+    assert_eq!(ufrag_of(&sd, 1).as_deref(), Some("vid1"));
+    assert_eq!(ufrag_of(&sd, 2), None);
+
+    sd.rotate_ice_credentials("new1".parse()?, "newpasswordnewpassword1".parse()?);
+    assert!(sd.attributes.is_empty());
+    assert_eq!(ufrag_of(&sd, 0).as_deref(), Some("new1"));
+    assert_eq!(ufrag_of(&sd, 1).as_deref(), Some("new1"));
+
+    let mut sd = MIXED_SDP.parse::<SessionDescription>()?;
+    assert_eq!(ufrag_of(&sd, 0).as_deref(), Some("sess"));
+    assert_eq!(ufrag_of(&sd, 1).as_deref(), Some("vid1"));
+
+    sd.rotate_ice_credentials("new1".parse()?, "newpasswordnewpassword1".parse()?);
+    assert_eq!(sd.attributes.get::<IcePwd>().count(), 1);
+    assert!(sd.media_descriptions[0].attributes.is_empty());
+    assert_eq!(
+        sd.media_descriptions[1]
+            .attributes
+            .get::<IceUfrag>()
+            .count(),
+        1
+    );
+    assert_eq!(ufrag_of(&sd, 0).as_deref(), Some("new1"));
+    assert_eq!(ufrag_of(&sd, 1).as_deref(), Some("new1"));
+
+    let mut sd = SessionDescription::default();
+    assert!(sd.ice_credentials().is_none());
+    sd.rotate_ice_credentials("new1".parse()?, "newpasswordnewpassword1".parse()?);
+    assert_eq!(ufrag_of(&sd, 0).as_deref(), Some("new1"));
+
+    Ok(())
+}
+
+#[test]
+fn test_simulcast_offer() -> Result<(), Error> {
+    const SIMULCAST_OFFER_SDP: &str = "v=0\r\n\
+o=- 3496123564421094463 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96 97\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=mid:0\r\n\
+a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=extmap:10 urn:ietf:params:rtp-hdrext:sdes:rtp-stream-id\r\n\
+a=extmap:11 urn:ietf:params:rtp-hdrext:sdes:repaired-rtp-stream-id\r\n\
+a=sendonly\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+a=rtpmap:97 rtx/90000\r\n\
+a=fmtp:97 apt=96\r\n\
+a=rid:q send\r\n\
+a=rid:h send\r\n\
+a=rid:f send pt=96;max-width=1280;max-height=720\r\n\
+a=simulcast:send q;h;f\r\n";
+
+    let sd = SIMULCAST_OFFER_SDP.parse::<SessionDescription>()?;
+    let md = &sd.media_descriptions[0];
+    let simulcast = md.simulcast().unwrap();
+    assert_eq!(
+        simulcast
+            .send
+            .iter()
+            .map(|alternatives| alternatives[0].id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["q", "h", "f"]
+    );
+    assert_eq!(md.rids()[2].restriction("max-width"), Some("1280"));
+    assert!(sd.validate().is_empty());
+    assert_eq!(sd.marshal(), SIMULCAST_OFFER_SDP);
+
+    Ok(())
+}
+
+#[test]
+fn test_set_default_destination() -> Result<(), Error> {
+    use crate::candidate::{Candidate, RemoteCandidate, RemoteCandidates};
+    use crate::diagnostic::Severity;
+    use crate::rtcp::Rtcp;
+    use core::net::Ipv4Addr;
+
+    const TRICKLE_OFFER_SDP: &str = "v=0\r\n\
+o=- 4611731400430051336 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+c=IN IP4 0.0.0.0\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0 1\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+a=mid:0\r\n\
+a=rtcp:9 IN IP4 0.0.0.0\r\n\
+a=rtpmap:111 opus/48000/2\r\n\
+m=video 0 UDP/TLS/RTP/SAVPF 96\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=bundle-only\r\n\
+a=mid:1\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=mid:2\r\n\
+a=rtpmap:96 VP8/90000\r\n";
+
+    let selected = "1 1 udp 1518280447 47.61.61.61 36768 typ srflx raddr 192.168.0.196 rport 36768"
+        .parse::<Candidate>()?;
+    let addr = IpAddr::V4(Ipv4Addr::new(47, 61, 61, 61));
+
+    let mut sd = TRICKLE_OFFER_SDP.parse::<SessionDescription>()?;
+    sd.set_default_destination("0", addr, selected.port, Some(36770))?;
+    sd.media_descriptions[0]
+        .attributes
+        .insert(RemoteCandidates {
+            candidates: vec![RemoteCandidate::from(&selected)],
+        });
+
+    let audio = &sd.media_descriptions[0];
+    assert_eq!(audio.media_name.port.value, 36768);
+    assert_eq!(
+        audio.connection_information.as_ref().map(|c| c.to_string()),
+        Some("IN IP4 47.61.61.61".to_owned())
+    );
+    assert_eq!(
+        audio.rtcp_address(),
+        Some(Rtcp {
+            port: 36770,
+            connection_information: audio.connection_information.clone(),
+        })
+    );
+    assert_eq!(
+        audio.remote_candidates().map(|r| r.to_string()),
+        Some("1 47.61.61.61 36768".to_owned())
+    );
+    // The bundle-only section keeps port zero; the unbundled one is not
+    // touched.
+    assert_eq!(sd.media_descriptions[1].media_name.port.value, 0);
+    assert_eq!(
+        sd.media_descriptions[1]
+            .connection_information
+            .as_ref()
+            .map(|c| c.to_string()),
+        Some("IN IP4 0.0.0.0".to_owned())
+    );
+    assert_eq!(sd.media_descriptions[2].media_name.port.value, 9);
+    assert!(
+        sd.validate().iter().all(|d| d.severity != Severity::Error),
+        "{:?}",
+        sd.validate()
+    );
+
+    let text = sd.marshal();
+    assert!(
+        text.contains(
+            "m=audio 36768 UDP/TLS/RTP/SAVPF 111\r\n\
+c=IN IP4 47.61.61.61\r\n\
+a=mid:0\r\n\
+a=rtcp:36770\r\n"
+        ),
+        "{}",
+        text
+    );
+    assert_eq!(text.parse::<SessionDescription>()?, sd);
+
+    // Once the bundled section is no longer bundle-only, it follows along.
+    // The RTCP port is the default one, so "a=rtcp:" goes away.
+    sd.media_descriptions[1].media_name.port.value = 9;
+    sd.set_default_destination("1", addr, 36768, Some(36769))?;
+    for md in &sd.media_descriptions[..2] {
+        assert_eq!(md.media_name.port.value, 36768);
+        assert_eq!(
+            md.connection_information,
+            Some(ConnectionInformation::from(addr))
+        );
+        assert!(md.attributes.get::<Rtcp>().next().is_none());
+    }
+    assert_eq!(sd.media_descriptions[2].media_name.port.value, 9);
+
+    // A media level "c=" is only added where the session level one differs.
+    sd.connection_information = Some(ConnectionInformation::from(addr));
+    sd.media_descriptions[2].connection_information = None;
+    sd.set_default_destination("2", addr, 40000, None)?;
+    assert_eq!(sd.media_descriptions[2].connection_information, None);
+    assert_eq!(sd.media_descriptions[2].media_name.port.value, 40000);
+
+    assert!(matches!(
+        sd.set_default_destination("3", addr, 40000, None),
+        Err(Error::SdpMidNotFound(ref mid)) if mid == "3"
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_total_bandwidth() -> Result<(), Error> {
+    const SDP: &str = "v=0\r\n\
+o=- 0 0 IN IP4 0.0.0.0\r\n\
+s=-\r\n\
+c=IN IP4 0.0.0.0\r\n\
+b=CT:1000\r\n\
+b=TIAS:900000\r\n\
+t=0 0\r\n\
+m=audio 9 RTP/AVP 0\r\n\
+b=AS:64\r\n\
+b=TIAS:48000\r\n\
+m=video 9 RTP/AVP 96\r\n\
+b=AS:512\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+m=video 0 RTP/AVP 96\r\n\
+a=rtpmap:96 VP8/90000\r\n";
+
+    let mut sd = SDP.parse::<SessionDescription>()?;
+
+    // CT and TIAS are given at session level, AS only in the media
+    // descriptions. The rejected one is left out.
+    assert_eq!(sd.total_bandwidth(BandwidthKind::Ct), Some(1000));
+    assert_eq!(sd.total_bandwidth(BandwidthKind::Tias), Some(900000));
+    assert_eq!(sd.total_bandwidth(BandwidthKind::As), Some(576));
+    assert_eq!(sd.total_bandwidth_partial(BandwidthKind::As), (576, 0));
+
+    // Without the session level TIAS, the video lacks one.
+    sd.bandwidth
+        .retain(|b| b.kind() != Some(BandwidthKind::Tias));
+    assert_eq!(sd.total_bandwidth(BandwidthKind::Tias), None);
+    assert_eq!(sd.total_bandwidth_partial(BandwidthKind::Tias), (48000, 1));
+
+    sd.media_descriptions[1].set_bandwidth(Bandwidth::tias_from_bps(480000));
+    assert_eq!(sd.total_bandwidth(BandwidthKind::Tias), Some(528000));
+
+    // CT isn't given for any media description.
+    sd.bandwidth.clear();
+    assert_eq!(sd.total_bandwidth(BandwidthKind::Ct), None);
+    assert_eq!(sd.total_bandwidth_partial(BandwidthKind::Ct), (0, 2));
+
+    // Experimental modifiers are not the registered ones.
+    sd.media_descriptions[0].bandwidth = vec!["X-AS:64".parse()?];
+    assert_eq!(sd.total_bandwidth_partial(BandwidthKind::As), (512, 1));
+
+    Ok(())
+}
+
+#[test]
+fn test_time_checked_add_offset() {
+    let tests = [
+        (0, 0, Some(0)),
+        (0, 1, Some(1)),
+        (0, -1, None),
+        (1, -1, Some(0)),
+        (u64::MAX, 0, Some(u64::MAX)),
+        (u64::MAX, 1, None),
+        (u64::MAX, -1, Some(u64::MAX - 1)),
+        (u64::MAX, i64::MIN, Some(u64::MAX - (1 << 63))),
+        (1 << 63, i64::MIN, Some(0)),
+        ((1 << 63) - 1, i64::MIN, None),
+        (0, i64::MAX, Some(i64::MAX as u64)),
+        (u64::MAX - i64::MAX as u64, i64::MAX, Some(u64::MAX)),
+        (u64::MAX - i64::MAX as u64 + 1, i64::MAX, None),
+    ];
+    for &(time, offset, expected) in tests.iter() {
+        assert_eq!(
+            Time(time).checked_add_offset(Offset(offset)),
+            expected.map(Time),
+            "{} + {}",
+            time,
+            offset
+        );
+    }
+}
+
+#[test]
+fn test_time_checked_add_duration() {
+    use core::time::Duration;
+
+    assert_eq!(
+        Time(1).checked_add_duration(Duration::from_secs(2)),
+        Some(Time(3))
+    );
+    assert_eq!(
+        Time(u64::MAX - 1).checked_add_duration(Duration::from_secs(1)),
+        Some(Time(u64::MAX))
+    );
+    assert_eq!(
+        Time(u64::MAX).checked_add_duration(Duration::from_secs(1)),
+        None
+    );
+    assert_eq!(
+        Time(0).checked_add_duration(Duration::from_secs(u64::MAX)),
+        Some(Time(u64::MAX))
+    );
+    assert_eq!(
+        Time(0).checked_add_duration(Duration::from_millis(1500)),
+        None
+    );
+
+    assert_eq!(Time(10) + Offset(-10), Time(0));
+    assert_eq!(Time(10) + Duration::from_secs(5), Time(15));
+}
+
+#[test]
+#[should_panic(expected = "overflow when adding offset to time")]
+fn test_time_add_offset_overflow() {
+    let _ = Time(0) + Offset(-1);
+}
+
+#[test]
+#[should_panic(expected = "overflow when subtracting offset from time")]
+fn test_time_sub_offset_overflow() {
+    let _ = Time(0) - Offset(1);
+}
+
+#[test]
+#[should_panic(expected = "overflow when adding duration to time")]
+fn test_time_add_duration_overflow() {
+    let _ = Time(u64::MAX) + core::time::Duration::from_secs(1);
+}
+
+#[test]
+fn test_time_unix_conversion() {
+    assert_eq!(Time::from_unix_secs(0), Some(Time(NTP_UNIX_OFFSET)));
+    assert_eq!(Time(NTP_UNIX_OFFSET).to_unix_secs(), Some(0));
+    assert_eq!(Time(NTP_UNIX_OFFSET - 1).to_unix_secs(), None);
+    assert_eq!(Time(0).to_unix_secs(), None);
+    assert_eq!(
+        Time::from_unix_secs(u64::MAX - NTP_UNIX_OFFSET),
+        Some(Time(u64::MAX))
+    );
+    assert_eq!(Time::from_unix_secs(u64::MAX - NTP_UNIX_OFFSET + 1), None);
+
+    // t=2873397496 2873404696 from RFC 4566 is 1991-01-20 21:58:16 UTC.
+    assert_eq!(Time(2873397496).to_unix_secs(), Some(664408696));
+    assert_eq!(Time::from_unix_secs(664408696), Some(Time(2873397496)));
+
+    assert!(Time(0).is_zero());
+    assert!(!Time(1).is_zero());
+    #[allow(deprecated)]
+    {
+        assert!(Time(0).iz_zero());
+    }
+    assert!(Time(2873397496) < Time(2873404696));
+}
+
+#[test]
+fn test_typed_time_accessors() -> Result<(), Error> {
+    let timing = Timing::new(2873397496, 2873404696)?;
+    assert_eq!(timing.start(), Time(2873397496));
+    assert_eq!(timing.stop(), Some(Time(2873404696)));
+    assert_eq!(Timing::unbounded_from(2873397496).stop(), None);
+    assert_eq!(Timing::permanent().start(), Time(0));
+
+    let time_zone = "2882844526 -1h".parse::<TimeZone>()?;
+    assert_eq!(time_zone.adjusted_from(), Time(2882844526));
+    assert_eq!(time_zone.adjustment(), Offset(-3600));
+    assert_eq!(
+        time_zone.adjusted_from() + time_zone.adjustment(),
+        Time(2882840926)
+    );
+
+    let repeat_time = "7d 1h 0 25h".parse::<RepeatTime>()?;
+    assert_eq!(repeat_time.repeat_interval(), Offset(604800));
+    assert_eq!(repeat_time.active_duration(), Offset(3600));
+    assert_eq!(
+        repeat_time.offsets_from_start().collect::<Vec<_>>(),
+        vec![Offset(0), Offset(90000)]
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_time_system_time_conversion() -> Result<(), Error> {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    assert_eq!(Time::from_system_time(UNIX_EPOCH)?, Time(NTP_UNIX_OFFSET));
+    assert!(Time::from_system_time(UNIX_EPOCH - Duration::from_secs(1)).is_err());
+    assert_eq!(
+        Time::from_system_time(UNIX_EPOCH + Duration::from_millis(664408696500))?,
+        Time(2873397496)
+    );
+
+    assert_eq!(Time(NTP_UNIX_OFFSET).to_system_time(), Some(UNIX_EPOCH));
+    assert_eq!(Time(NTP_UNIX_OFFSET - 1).to_system_time(), None);
+    assert_eq!(
+        Time(2873397496).to_system_time(),
+        Some(UNIX_EPOCH + Duration::from_secs(664408696))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_offset_checked_ops() -> Result<(), Error> {
+    use core::time::Duration;
+
+    assert_eq!(Offset(1).checked_add(Offset(-1)), Some(Offset(0)));
+    assert_eq!(Offset(i64::MAX).checked_add(Offset(1)), None);
+    assert_eq!(Offset(i64::MIN).checked_add(Offset(-1)), None);
+    assert_eq!(
+        Offset(i64::MIN).checked_add(Offset(i64::MAX)),
+        Some(Offset(-1))
+    );
+
+    assert_eq!(Offset(0).checked_neg(), Some(Offset(0)));
+    assert_eq!(Offset(-1).checked_neg(), Some(Offset(1)));
+    assert_eq!(Offset(i64::MAX).checked_neg(), Some(Offset(-i64::MAX)));
+    assert_eq!(Offset(i64::MIN).checked_neg(), None);
+
+    assert_eq!(Offset::from_secs(-3600).as_secs(), -3600);
+    assert_eq!(Offset::try_from(Duration::from_secs(0))?, Offset(0));
+    assert_eq!(
+        Offset::try_from(Duration::from_secs(i64::MAX as u64))?,
+        Offset(i64::MAX)
+    );
+    assert!(matches!(
+        Offset::try_from(Duration::from_secs(i64::MAX as u64 + 1)),
+        Err(Error::SdpInvalidValue(_))
+    ));
+    assert!(matches!(
+        Offset::try_from(Duration::from_millis(1)),
+        Err(Error::SdpNonWholeSeconds(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_offset_arithmetic() {
+    assert_eq!(Offset(7) + Offset(-2), Offset(5));
+    assert_eq!(Offset(7) - Offset(9), Offset(-2));
+    assert_eq!(Offset(3600) * 3, Offset(10800));
+    assert_eq!(-Offset(60), Offset(-60));
+    assert_eq!(Offset(-5).checked_mul(0), Some(Offset::ZERO));
+
+    assert_eq!(Offset::MAX.checked_mul(1), Some(Offset::MAX));
+    assert_eq!(Offset::MAX.checked_mul(2), None);
+    assert_eq!(Offset::MIN.checked_mul(2), None);
+    assert_eq!(
+        Offset(-1).checked_mul(u32::MAX),
+        Some(Offset(-(u32::MAX as i64)))
+    );
+    assert_eq!(Offset::MIN.checked_sub(Offset(1)), None);
+    assert_eq!(Offset::MAX.checked_sub(Offset(-1)), None);
+    assert_eq!(Offset(0).checked_sub(Offset::MAX), Some(Offset(-i64::MAX)));
+
+    assert_eq!(Time(100) - Offset(40), Time(60));
+    assert_eq!(Time(100) - Offset(-40), Time(140));
+    assert_eq!(Time(0).checked_sub_offset(Offset(1)), None);
+    assert_eq!(Time(u64::MAX).checked_sub_offset(Offset::MIN), None);
+    assert_eq!(Time(0).checked_sub_offset(Offset::MIN), Some(Time(1 << 63)));
+
+    // "r=7d 1h 0 25h": the third repetition starts two intervals later.
+    let interval = Offset(604800);
+    assert_eq!(Time(3034423619) + interval * 2, Time(3035633219));
+
+    assert!(Offset::ZERO.is_zero());
+    assert!(Offset(-1).is_negative());
+    assert!(!Offset(1).is_negative());
+}
+
+#[test]
+#[should_panic(expected = "overflow when multiplying offset")]
+fn test_offset_mul_overflow() {
+    let _ = Offset::MAX * 2;
+}
+
+#[test]
+#[should_panic(expected = "overflow when negating offset")]
+fn test_offset_neg_overflow() {
+    let _ = -Offset::MIN;
+}
+
+#[test]
+fn test_offset_std_conversions() -> Result<(), Error> {
+    use core::time::Duration;
+
+    for secs in [0, 1, 86400, i64::MAX] {
+        let offset = Offset(secs);
+        let duration = offset.to_std().unwrap();
+        assert_eq!(duration, Duration::from_secs(secs as u64));
+        assert_eq!(Offset::try_from(duration)?, offset);
+        assert_eq!(Offset::from_std_lossy(duration), offset);
+    }
+    assert_eq!(Offset(-1).to_std(), None);
+
+    // The lossy conversion truncates and saturates explicitly.
+    assert_eq!(
+        Offset::from_std_lossy(Duration::from_millis(2999)),
+        Offset(2)
+    );
+    assert_eq!(Offset::from_std_lossy(Duration::MAX), Offset::MAX);
+
     Ok(())
 }
@@ -0,0 +1,149 @@
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::TypedAttribute;
+use super::error::Error;
+
+#[cfg(test)]
+mod setup_test;
+
+/// Setup is the "a=setup:" attribute, which decides which endpoint opens the
+/// connection. WebRTC uses it to pick the DTLS client and server roles.
+/// <https://tools.ietf.org/html/rfc4145#section-4>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::setup::Setup;
+///
+/// # fn main() -> Result<(), Error> {
+/// assert_eq!("actpass".parse::<Setup>()?, Setup::ActPass);
+/// assert_eq!(Setup::Active.to_string(), "active");
+/// assert!("server".parse::<Setup>().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Setup {
+    /// Active initiates the connection, "active".
+    Active,
+    /// Passive accepts the connection, "passive".
+    Passive,
+    /// ActPass may do either, "actpass", and is what offers use.
+    ActPass,
+    /// HoldConn doesn't establish the connection for now, "holdconn".
+    HoldConn,
+}
+
+impl Setup {
+    /// answer_for returns the setup an answerer should use for the offered
+    /// one, preferring active for actpass offers. DTLS answers must be active
+    /// or passive, so a holdconn offer, which can only be answered with
+    /// holdconn, is rejected.
+    /// <https://tools.ietf.org/html/rfc5763#section-5>
+    pub fn answer_for(offer: Setup) -> Result<Setup, Error> {
+        let answer = match offer {
+            Setup::ActPass | Setup::Passive => Setup::Active,
+            Setup::Active => Setup::Passive,
+            Setup::HoldConn => Setup::HoldConn,
+        };
+        Setup::check_answer(offer, answer)?;
+        Ok(answer)
+    }
+
+    /// check_answer reports whether a received answer is a valid DTLS answer
+    /// to the offer.
+    pub fn check_answer(offer: Setup, answer: Setup) -> Result<(), Error> {
+        match (offer, answer) {
+            (Setup::ActPass, Setup::Active)
+            | (Setup::ActPass, Setup::Passive)
+            | (Setup::Active, Setup::Passive)
+            | (Setup::Passive, Setup::Active) => Ok(()),
+            _ => Err(Error::SdpIncompatibleSetup {
+                offer: offer.to_string(),
+                answer: answer.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Setup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Setup::Active => "active",
+            Setup::Passive => "passive",
+            Setup::ActPass => "actpass",
+            Setup::HoldConn => "holdconn",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for Setup {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "active" => Ok(Setup::Active),
+            "passive" => Ok(Setup::Passive),
+            "actpass" => Ok(Setup::ActPass),
+            "holdconn" => Ok(Setup::HoldConn),
+            _ => Err(Error::SdpInvalidSetup(value.to_owned())),
+        }
+    }
+}
+
+impl TypedAttribute for Setup {
+    const NAME: &'static str = "setup";
+
+    fn describe(&self) -> Option<String> {
+        Some(
+            match self {
+                Setup::Active => "DTLS client",
+                Setup::Passive => "DTLS server",
+                Setup::ActPass => "DTLS client or server",
+                Setup::HoldConn => "no connection yet",
+            }
+            .to_owned(),
+        )
+    }
+}
+
+/// Connection is the "a=connection:" attribute, telling whether a new
+/// connection is needed or the existing one is reused.
+/// <https://tools.ietf.org/html/rfc4145#section-5>
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Connection {
+    /// New asks for a new connection, "new".
+    New,
+    /// Existing keeps using the existing connection, "existing".
+    Existing,
+}
+
+impl fmt::Display for Connection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Connection::New => "new",
+            Connection::Existing => "existing",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for Connection {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "new" => Ok(Connection::New),
+            "existing" => Ok(Connection::Existing),
+            _ => Err(Error::SdpInvalidConnection(value.to_owned())),
+        }
+    }
+}
+
+impl TypedAttribute for Connection {
+    const NAME: &'static str = "connection";
+}
@@ -0,0 +1,82 @@
+use super::*;
+
+#[test]
+fn test_setup_from_str() -> Result<(), Error> {
+    for input in ["active", "passive", "actpass", "holdconn"] {
+        assert_eq!(input.parse::<Setup>()?.to_string(), input);
+    }
+    for input in ["", "Active", "act pass", "server"] {
+        assert!(
+            matches!(input.parse::<Setup>(), Err(Error::SdpInvalidSetup(_))),
+            "{}",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_connection_from_str() -> Result<(), Error> {
+    for input in ["new", "existing"] {
+        assert_eq!(input.parse::<Connection>()?.to_string(), input);
+    }
+    for input in ["", "New", "old"] {
+        assert!(
+            matches!(
+                input.parse::<Connection>(),
+                Err(Error::SdpInvalidConnection(_))
+            ),
+            "{}",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_setup_answer_for() {
+    let tests = vec![
+        (Setup::ActPass, Some(Setup::Active)),
+        (Setup::Active, Some(Setup::Passive)),
+        (Setup::Passive, Some(Setup::Active)),
+        (Setup::HoldConn, None),
+    ];
+
+    for (offer, expected) in tests {
+        assert_eq!(Setup::answer_for(offer).ok(), expected, "{}", offer);
+    }
+}
+
+#[test]
+fn test_setup_check_answer() {
+    use Setup::*;
+
+    let tests = vec![
+        (ActPass, Active, true),
+        (ActPass, Passive, true),
+        (ActPass, ActPass, false),
+        (ActPass, HoldConn, false),
+        (Active, Active, false),
+        (Active, Passive, true),
+        (Active, ActPass, false),
+        (Active, HoldConn, false),
+        (Passive, Active, true),
+        (Passive, Passive, false),
+        (Passive, ActPass, false),
+        (Passive, HoldConn, false),
+        (HoldConn, Active, false),
+        (HoldConn, Passive, false),
+        (HoldConn, ActPass, false),
+        (HoldConn, HoldConn, false),
+    ];
+
+    for (offer, answer, valid) in tests {
+        let result = Setup::check_answer(offer, answer);
+        assert_eq!(result.is_ok(), valid, "offer {} answer {}", offer, answer);
+        if !valid {
+            assert!(matches!(result, Err(Error::SdpIncompatibleSetup { .. })));
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::TypedAttribute;
+use super::error::Error;
+use super::rtpmap::MAX_PAYLOAD_TYPE;
+
+#[cfg(test)]
+mod simulcast_test;
+
+/// RidDirection tells whether a RID applies to sent or received streams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RidDirection {
+    /// Send is "send".
+    Send,
+    /// Recv is "recv".
+    Recv,
+}
+
+impl fmt::Display for RidDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RidDirection::Send => write!(f, "send"),
+            RidDirection::Recv => write!(f, "recv"),
+        }
+    }
+}
+
+impl FromStr for RidDirection {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "send" => Ok(RidDirection::Send),
+            "recv" => Ok(RidDirection::Recv),
+            _ => Err(Error::SdpInvalidRid(value.to_owned())),
+        }
+    }
+}
+
+/// Rid is an "a=rid:" attribute, describing the restrictions of an RTP
+/// stream identified by its RID.
+/// `a=rid:<rid-id> <direction> [pt=<fmt>,...;<restriction>=<value>;...]`
+/// <https://tools.ietf.org/html/rfc8851#section-10>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::simulcast::{Rid, RidDirection};
+///
+/// # fn main() -> Result<(), Error> {
+/// let rid = "hi send pt=96;max-width=1280".parse::<Rid>()?;
+/// assert_eq!(rid.direction, RidDirection::Send);
+/// assert_eq!(rid.payload_types, vec![96]);
+/// assert_eq!(rid.restriction("max-width"), Some("1280"));
+/// assert_eq!(rid.to_string(), "hi send pt=96;max-width=1280");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rid {
+    /// id is the RID, which the RTP streams carry in their header extension.
+    pub id: String,
+    /// direction tells whether the RID is for sent or received streams.
+    pub direction: RidDirection,
+    /// payload_types are the formats from "pt=", empty if not given.
+    pub payload_types: Vec<u8>,
+    /// restrictions are the other parameters, e.g. "max-width=1280", in their
+    /// original order.
+    pub restrictions: Vec<(String, Option<String>)>,
+}
+
+impl Rid {
+    /// restriction returns the value of the restriction with the name.
+    pub fn restriction(&self, key: &str) -> Option<&str> {
+        self.restrictions
+            .iter()
+            .find(|(k, _)| k == key)
+            .and_then(|(_, v)| v.as_deref())
+    }
+}
+
+impl fmt::Display for Rid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.id, self.direction)?;
+
+        let mut params = vec![];
+        if !self.payload_types.is_empty() {
+            let payload_types: Vec<_> = self.payload_types.iter().map(u8::to_string).collect();
+            params.push(format!("pt={}", payload_types.join(",")));
+        }
+        for (key, value) in &self.restrictions {
+            match value {
+                Some(value) => params.push(format!("{}={}", key, value)),
+                None => params.push(key.clone()),
+            }
+        }
+        if !params.is_empty() {
+            write!(f, " {}", params.join(";"))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Rid {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::SdpInvalidRid(value.to_owned());
+
+        let mut fields = value.splitn(3, ' ');
+        let id = fields.next().unwrap_or_default();
+        if !is_rid_id(id) {
+            return Err(invalid());
+        }
+        let direction = fields
+            .next()
+            .ok_or_else(invalid)?
+            .parse()
+            .map_err(|_| invalid())?;
+
+        let mut payload_types = vec![];
+        let mut restrictions = vec![];
+        if let Some(params) = fields.next() {
+            for (i, param) in params.split(';').enumerate() {
+                let (key, param_value) = match param.split_once('=') {
+                    Some((key, value)) => (key, Some(value)),
+                    None => (param, None),
+                };
+                // The payload type list can only come first.
+                if key == "pt" && i == 0 {
+                    for pt in param_value.ok_or_else(invalid)?.split(',') {
+                        payload_types.push(parse_payload_type(pt).ok_or_else(invalid)?);
+                    }
+                    continue;
+                }
+                let is_key_char = |c: char| c.is_ascii_alphanumeric() || c == '-';
+                if key.is_empty()
+                    || key == "pt"
+                    || !key.chars().all(is_key_char)
+                    || param_value.is_some_and(|v| v.is_empty() || v.contains(' '))
+                {
+                    return Err(invalid());
+                }
+                restrictions.push((key.to_owned(), param_value.map(str::to_owned)));
+            }
+        }
+
+        Ok(Rid {
+            id: id.to_owned(),
+            direction,
+            payload_types,
+            restrictions,
+        })
+    }
+}
+
+impl TypedAttribute for Rid {
+    const NAME: &'static str = "rid";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("{} RTP stream `{}`", self.direction, self.id))
+    }
+}
+
+/// SimulcastRid refers to a RID from "a=simulcast:". A paused stream is
+/// prefixed with "~".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulcastRid {
+    /// id is the RID.
+    pub id: String,
+    /// paused tells whether the stream is paused.
+    pub paused: bool,
+}
+
+impl fmt::Display for SimulcastRid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.paused {
+            write!(f, "~")?;
+        }
+        write!(f, "{}", self.id)
+    }
+}
+
+impl FromStr for SimulcastRid {
+    type Err = Error;
+
+    /// sc-id = [sc-id-paused] rid-id
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (id, paused) = match value.strip_prefix('~') {
+            Some(id) => (id, true),
+            None => (value, false),
+        };
+        if !is_rid_id(id) {
+            return Err(Error::SdpInvalidSimulcast(value.to_owned()));
+        }
+        Ok(SimulcastRid {
+            id: id.to_owned(),
+            paused,
+        })
+    }
+}
+
+/// Simulcast is an "a=simulcast:" attribute. Each of the send and recv lists
+/// holds one entry per simulcast stream, and each stream the alternative
+/// RIDs it may use, in their original order. Send is written before recv.
+/// `a=simulcast:send <rid>[,<rid>];<rid> recv <rid>`
+/// <https://tools.ietf.org/html/rfc8853#section-5.1>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::simulcast::Simulcast;
+///
+/// # fn main() -> Result<(), Error> {
+/// let simulcast = "send hi;mid;~lo".parse::<Simulcast>()?;
+/// assert_eq!(simulcast.send.len(), 3);
+/// assert!(simulcast.send[2][0].paused);
+/// assert_eq!(simulcast.to_string(), "send hi;mid;~lo");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Simulcast {
+    /// send are the sent streams; each holds the alternative RIDs of one
+    /// stream.
+    pub send: Vec<Vec<SimulcastRid>>,
+    /// recv are the received streams; each holds the alternative RIDs of one
+    /// stream.
+    pub recv: Vec<Vec<SimulcastRid>>,
+}
+
+impl Simulcast {
+    /// rids returns every RID referenced, with the direction of its list.
+    pub fn rids(&self) -> impl Iterator<Item = (RidDirection, &SimulcastRid)> {
+        let send = self.send.iter().flatten().map(|r| (RidDirection::Send, r));
+        let recv = self.recv.iter().flatten().map(|r| (RidDirection::Recv, r));
+        send.chain(recv)
+    }
+}
+
+impl fmt::Display for Simulcast {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lists = vec![];
+        for (direction, streams) in [
+            (RidDirection::Send, &self.send),
+            (RidDirection::Recv, &self.recv),
+        ] {
+            if streams.is_empty() {
+                continue;
+            }
+            let streams: Vec<_> = streams
+                .iter()
+                .map(|alternatives| {
+                    let alternatives: Vec<_> =
+                        alternatives.iter().map(SimulcastRid::to_string).collect();
+                    alternatives.join(",")
+                })
+                .collect();
+            lists.push(format!("{} {}", direction, streams.join(";")));
+        }
+        write!(f, "{}", lists.join(" "))
+    }
+}
+
+impl FromStr for Simulcast {
+    type Err = Error;
+
+    /// sc-value = ( sc-send [SP sc-recv] ) / ( sc-recv [SP sc-send] )
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::SdpInvalidSimulcast(value.to_owned());
+
+        let fields: Vec<_> = value.split(' ').collect();
+        if fields.len() != 2 && fields.len() != 4 {
+            return Err(invalid());
+        }
+
+        let mut simulcast = Simulcast::default();
+        for pair in fields.chunks(2) {
+            let list = match pair[0].parse().map_err(|_| invalid())? {
+                RidDirection::Send => &mut simulcast.send,
+                RidDirection::Recv => &mut simulcast.recv,
+            };
+            if !list.is_empty() {
+                return Err(invalid());
+            }
+            for stream in pair[1].split(';') {
+                let alternatives = stream
+                    .split(',')
+                    .map(|rid| rid.parse::<SimulcastRid>().map_err(|_| invalid()))
+                    .collect::<Result<_, _>>()?;
+                list.push(alternatives);
+            }
+        }
+
+        Ok(simulcast)
+    }
+}
+
+impl TypedAttribute for Simulcast {
+    const NAME: &'static str = "simulcast";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!(
+            "simulcast, {} send and {} receive streams",
+            self.send.len(),
+            self.recv.len()
+        ))
+    }
+}
+
+/// rid-id = 1*255(alpha-numeric / "-" / "_")
+fn is_rid_id(value: &str) -> bool {
+    (1..=255).contains(&value.len())
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn parse_payload_type(value: &str) -> Option<u8> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok().filter(|pt| *pt <= MAX_PAYLOAD_TYPE)
+}
@@ -0,0 +1,110 @@
+use super::*;
+
+#[test]
+fn test_rid() -> Result<(), Error> {
+    let rid = "f send pt=96,97;max-width=1280;max-height=720;max-fps=30".parse::<Rid>()?;
+    assert_eq!(rid.id, "f");
+    assert_eq!(rid.direction, RidDirection::Send);
+    assert_eq!(rid.payload_types, vec![96, 97]);
+    assert_eq!(
+        rid.restrictions
+            .iter()
+            .map(|(k, _)| k.as_str())
+            .collect::<Vec<_>>(),
+        vec!["max-width", "max-height", "max-fps"]
+    );
+    assert_eq!(rid.restriction("max-width"), Some("1280"));
+    assert_eq!(rid.restriction("max-br"), None);
+
+    for input in [
+        "q send",
+        "hi_res-1 recv",
+        "f send pt=96",
+        "f recv max-width=640;depend=h,q",
+        "f send max-br=100000;flag",
+    ] {
+        assert_eq!(input.parse::<Rid>()?.to_string(), input);
+    }
+
+    for input in [
+        "".to_owned(),
+        "f".to_owned(),
+        "f both".to_owned(),
+        "f~ send".to_owned(),
+        "f send ".to_owned(),
+        "f send pt=".to_owned(),
+        "f send pt=96,".to_owned(),
+        "f send pt=128".to_owned(),
+        "f send max-width=640;pt=96".to_owned(),
+        "f send max-width=".to_owned(),
+        "f send max-width=640;;".to_owned(),
+        format!("{} send", "r".repeat(256)),
+    ] {
+        assert!(
+            matches!(input.parse::<Rid>(), Err(Error::SdpInvalidRid(_))),
+            "{:?}",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_simulcast() -> Result<(), Error> {
+    let simulcast = "send f;h;~q".parse::<Simulcast>()?;
+    assert_eq!(simulcast.send.len(), 3);
+    assert!(simulcast.recv.is_empty());
+    assert_eq!(
+        simulcast
+            .rids()
+            .map(|(direction, rid)| (direction, rid.id.as_str(), rid.paused))
+            .collect::<Vec<_>>(),
+        vec![
+            (RidDirection::Send, "f", false),
+            (RidDirection::Send, "h", false),
+            (RidDirection::Send, "q", true),
+        ]
+    );
+
+    let simulcast = "send 2,1;3 recv ~4".parse::<Simulcast>()?;
+    assert_eq!(
+        simulcast.send[0]
+            .iter()
+            .map(|r| r.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["2", "1"]
+    );
+    assert!(simulcast.recv[0][0].paused);
+
+    for input in ["send f;h;~q", "recv 1", "send 2,1;3 recv ~4"] {
+        assert_eq!(input.parse::<Simulcast>()?.to_string(), input);
+    }
+    assert_eq!(
+        "recv 4 send 1".parse::<Simulcast>()?.to_string(),
+        "send 1 recv 4"
+    );
+
+    for input in [
+        "",
+        "send",
+        "send f;;h",
+        "send f,",
+        "send ~",
+        "send f recv",
+        "send f send h",
+        "both f",
+        "send f  recv h",
+    ] {
+        assert!(
+            matches!(
+                input.parse::<Simulcast>(),
+                Err(Error::SdpInvalidSimulcast(_))
+            ),
+            "{:?}",
+            input
+        );
+    }
+
+    Ok(())
+}
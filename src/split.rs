@@ -0,0 +1,356 @@
+use core::ops::RangeInclusive;
+
+use super::alloc_prelude::*;
+use super::common_description::{Attributes, TypedAttribute};
+use super::error::Error;
+use super::extmap::ExtMapId;
+use super::group::{Group, GroupSemantics, Mid};
+use super::media_description::MediaDescription;
+use super::resolve::inherited_name;
+use super::session_description::{
+    Origin, SessionDescription, SessionName, TimeDescription, TimeZones, Version,
+};
+#[cfg(feature = "std")]
+use super::util::{new_session_id, ntp_now_secs};
+
+#[cfg(test)]
+mod split_test;
+
+/// OriginPolicy tells SessionDescription::extract_media which "o=" field
+/// the extracted description gets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OriginPolicy {
+    /// Copy keeps the "o=" field of the original description.
+    Copy,
+    /// Fresh starts a new session: the username and address are kept, the
+    /// `<sess-id>` is random and the `<sess-version>` NTP based, as made by
+    /// Origin::new_for_host.
+    #[cfg(feature = "std")]
+    Fresh,
+}
+
+/// SessionTemplate has the session level fields of the descriptions made by
+/// SessionDescription::compose.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionTemplate {
+    /// origin is the "o=" field.
+    pub origin: Origin,
+    /// session_name is the "s=" field.
+    pub session_name: SessionName,
+    /// time_descriptions are the "t=" and "r=" fields.
+    pub time_descriptions: Vec<TimeDescription>,
+    /// time_zones is the "z=" field.
+    pub time_zones: TimeZones,
+    /// attributes are the session level attributes. Groups among them are
+    /// replaced by the BUNDLE group of the composed media descriptions.
+    pub attributes: Attributes,
+}
+
+impl SessionTemplate {
+    /// new creates a template from the mandatory fields, see
+    /// SessionDescription::new.
+    pub fn new(
+        origin: Origin,
+        session_name: SessionName,
+        time_description: TimeDescription,
+    ) -> Self {
+        SessionTemplate {
+            origin,
+            session_name,
+            time_descriptions: vec![time_description],
+            time_zones: TimeZones::default(),
+            attributes: Attributes::default(),
+        }
+    }
+}
+
+/// A template of a description has its "o=", "s=", "t=", "r=" and "z="
+/// fields, and those session level attributes which are neither inherited by
+/// the media descriptions nor groups, e.g. "a=msid-semantic:".
+impl From<&SessionDescription> for SessionTemplate {
+    fn from(description: &SessionDescription) -> Self {
+        let attributes = description
+            .attributes
+            .iter()
+            .filter(|a| inherited_name(a).is_none() && !Group::matches(a))
+            .cloned()
+            .collect::<Vec<_>>();
+        SessionTemplate {
+            origin: description.origin.clone(),
+            session_name: description.session_name.clone(),
+            time_descriptions: description.time_descriptions.clone(),
+            time_zones: description.time_zones.clone(),
+            attributes: attributes.into(),
+        }
+    }
+}
+
+/// Composition is the result of SessionDescription::compose.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Composition {
+    /// description is the composed session description.
+    pub description: SessionDescription,
+    /// remappings are the renumberings made to resolve conflicts between the
+    /// media descriptions, in the order they were made.
+    pub remappings: Vec<Remapping>,
+}
+
+/// Remapping is a payload type or extmap id of a media description which
+/// SessionDescription::compose renumbered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Remapping {
+    /// PayloadType is a payload type renumbered with
+    /// MediaDescription::remap_payload_type.
+    PayloadType {
+        /// index is the index of the media description.
+        index: usize,
+        /// from is the original payload type.
+        from: u8,
+        /// to is the new payload type.
+        to: u8,
+    },
+    /// ExtMapId is an extmap id renumbered with
+    /// MediaDescription::remap_extmap_id.
+    ExtMapId {
+        /// index is the index of the media description.
+        index: usize,
+        /// from is the original id.
+        from: ExtMapId,
+        /// to is the new id.
+        to: ExtMapId,
+    },
+}
+
+impl SessionDescription {
+    /// extract_media makes a standalone description of the media description
+    /// with the given mid, e.g. to forward it to a peer on its own. It has the
+    /// "v=", "s=", "t=", "r=" and "z=" fields of this description, and the
+    /// "o=" field origin asks for. The session level "c=", "b=", "k=" and
+    /// attributes the media description inherits are pushed down into it, see
+    /// push_down_fields and push_down_attributes, so what ResolvedMedia
+    /// returns for it is unchanged. The other session level attributes are
+    /// kept, except for the groups, which are replaced by a BUNDLE group of
+    /// the mid alone unless the media description is rejected.
+    pub fn extract_media(&self, mid: &Mid, origin: OriginPolicy) -> Result<Self, Error> {
+        let media_description = self
+            .media_descriptions
+            .iter()
+            .find(|md| md.mid().as_ref() == Some(mid))
+            .ok_or_else(|| Error::SdpMidNotFound(mid.to_string()))?;
+
+        let mut extracted = SessionDescription {
+            version: self.version,
+            origin: self.origin.clone(),
+            session_name: self.session_name.clone(),
+            connection_information: self.connection_information.clone(),
+            bandwidth: self.bandwidth.clone(),
+            time_descriptions: self.time_descriptions.clone(),
+            time_zones: self.time_zones.clone(),
+            attributes: self.attributes.clone(),
+            media_descriptions: vec![media_description.clone()],
+            line_ending: self.line_ending,
+            ..Default::default()
+        };
+        match origin {
+            OriginPolicy::Copy => {}
+            #[cfg(feature = "std")]
+            OriginPolicy::Fresh => {
+                extracted.origin.session_id = new_session_id();
+                extracted.origin.session_version = ntp_now_secs();
+            }
+        }
+
+        extracted.push_down_fields();
+        extracted.push_down_attributes();
+        let md = &mut extracted.media_descriptions[0];
+        if md.encryption_key.is_none() {
+            md.encryption_key = self.encryption_key.clone();
+        }
+        let rejected = md.is_rejected();
+
+        extracted.attributes.remove_all::<Group>();
+        if !rejected {
+            let group = Group {
+                semantics: GroupSemantics::Bundle,
+                mids: vec![mid.clone()],
+            };
+            extracted.attributes.0.insert(0, group.to_attribute());
+        }
+
+        Ok(extracted)
+    }
+
+    /// compose is the inverse of extract_media: it puts media descriptions
+    /// into one description with the session level fields of the template,
+    /// and a BUNDLE group of the mids of those which are not rejected.
+    /// Bundled RTP media descriptions share the payload type and extmap id
+    /// spaces, so a payload type which an earlier media description uses for
+    /// another codec is renumbered to a free dynamic one, and an extmap id
+    /// which an earlier one uses for another extension to the id that one
+    /// uses for the extension, or else to a free id of the same header size.
+    /// The renumberings are listed in the result. Running out of free payload
+    /// types or ids is an error, as are duplicate mids.
+    /// <https://tools.ietf.org/html/rfc8843#section-9>
+    pub fn compose<I>(sections: I, template: &SessionTemplate) -> Result<Composition, Error>
+    where
+        I: IntoIterator<Item = MediaDescription>,
+    {
+        let mut attributes = template.attributes.clone();
+        attributes.remove_all::<Group>();
+        let mut description = SessionDescription {
+            version: Version::V0,
+            origin: template.origin.clone(),
+            session_name: template.session_name.clone(),
+            time_descriptions: template.time_descriptions.clone(),
+            time_zones: template.time_zones.clone(),
+            attributes,
+            ..Default::default()
+        };
+
+        let mut remappings = vec![];
+        let mut bundled_mids: Vec<Mid> = vec![];
+        for (index, mut md) in sections.into_iter().enumerate() {
+            if let Some(mid) = md.mid() {
+                if description
+                    .media_descriptions
+                    .iter()
+                    .any(|other| other.mid().as_ref() == Some(&mid))
+                {
+                    return Err(Error::SdpDuplicateMid(mid.to_string()));
+                }
+                if !md.is_rejected() {
+                    bundled_mids.push(mid);
+                }
+            }
+
+            if is_bundled_rtp(&md) {
+                let bundled: Vec<&MediaDescription> = description
+                    .media_descriptions
+                    .iter()
+                    .filter(|other| is_bundled_rtp(other))
+                    .collect();
+                for (from, to) in bundle_payload_types(&bundled, &mut md)? {
+                    remappings.push(Remapping::PayloadType { index, from, to });
+                }
+                for (from, to) in bundle_extmap_ids(&bundled, &mut md)? {
+                    remappings.push(Remapping::ExtMapId { index, from, to });
+                }
+            }
+            description.media_descriptions.push(md);
+        }
+
+        if !bundled_mids.is_empty() {
+            let group = Group {
+                semantics: GroupSemantics::Bundle,
+                mids: bundled_mids,
+            };
+            description.attributes.0.insert(0, group.to_attribute());
+        }
+
+        Ok(Composition {
+            description,
+            remappings,
+        })
+    }
+}
+
+/// is_bundled_rtp reports whether the media description shares the payload
+/// type and extmap id spaces of the BUNDLE group made by compose.
+fn is_bundled_rtp(md: &MediaDescription) -> bool {
+    !md.is_rejected() && md.media_name.protocol().is_ok_and(|p| p.is_rtp())
+}
+
+fn payload_types(md: &MediaDescription) -> impl Iterator<Item = u8> + '_ {
+    md.media_name
+        .formats
+        .iter()
+        .filter_map(|format| format.parse().ok())
+}
+
+/// has_other_codec reports whether other uses the payload type for another
+/// codec than md, judged by "a=rtpmap:" and "a=fmtp:".
+fn has_other_codec(other: &MediaDescription, md: &MediaDescription, payload_type: u8) -> bool {
+    let format = payload_type.to_string();
+    payload_types(other).any(|pt| pt == payload_type)
+        && (other.rtpmap_for(payload_type) != md.rtpmap_for(payload_type)
+            || other.fmtp_for(&format) != md.fmtp_for(&format))
+}
+
+/// bundle_payload_types renumbers the payload types of md which the bundled
+/// media descriptions use for other codecs. Renumbering one payload type may
+/// change the fmtp of another, e.g. the "apt=" of RTX, so conflicts are
+/// looked for again after each one. A renumbered payload type is unused by
+/// the bundled media descriptions and can't conflict again.
+fn bundle_payload_types(
+    bundled: &[&MediaDescription],
+    md: &mut MediaDescription,
+) -> Result<Vec<(u8, u8)>, Error> {
+    let mut remappings = vec![];
+    loop {
+        let conflict = payload_types(md)
+            .find(|pt| bundled.iter().any(|other| has_other_codec(other, md, *pt)));
+        let from = match conflict {
+            Some(from) => from,
+            None => return Ok(remappings),
+        };
+        let to = md
+            .free_dynamic_payload_types()
+            .find(|pt| {
+                !bundled
+                    .iter()
+                    .any(|other| payload_types(other).any(|p| p == *pt))
+            })
+            .ok_or(Error::SdpPayloadTypesExhausted(from))?;
+        md.remap_payload_type(from, to)?;
+        remappings.push((from, to));
+    }
+}
+
+/// bundle_extmap_ids renumbers the extmap ids of md which the bundled media
+/// descriptions use for other extensions.
+fn bundle_extmap_ids(
+    bundled: &[&MediaDescription],
+    md: &mut MediaDescription,
+) -> Result<Vec<(ExtMapId, ExtMapId)>, Error> {
+    let mut remappings = vec![];
+    loop {
+        let conflict = md.extmaps().find(|extmap| {
+            bundled
+                .iter()
+                .flat_map(|other| other.extmaps())
+                .any(|e| e.id == extmap.id && e.uri != extmap.uri)
+        });
+        let extmap = match conflict {
+            Some(extmap) => extmap,
+            None => return Ok(remappings),
+        };
+        let is_free = |id: &ExtMapId| {
+            !md.extmaps().any(|e| e.id == *id)
+                && !bundled
+                    .iter()
+                    .flat_map(|other| other.extmaps())
+                    .any(|e| e.id == *id && e.uri != extmap.uri)
+        };
+        let same_extension = bundled
+            .iter()
+            .flat_map(|other| other.extmaps())
+            .find(|e| e.uri == extmap.uri)
+            .map(|e| e.id)
+            .filter(&is_free);
+        let to = same_extension
+            .or_else(|| extmap_ids(extmap.id).map(ExtMapId).find(&is_free))
+            .ok_or(Error::SdpExtMapIdsExhausted(extmap.id.get()))?;
+        md.remap_extmap_id(extmap.id, to)?;
+        remappings.push((extmap.id, to));
+    }
+}
+
+/// extmap_ids returns the ids with the header size of id.
+/// <https://tools.ietf.org/html/rfc8285#section-4.2>
+fn extmap_ids(id: ExtMapId) -> RangeInclusive<u16> {
+    if id.requires_two_byte_header() {
+        16..=255
+    } else {
+        1..=14
+    }
+}
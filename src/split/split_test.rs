@@ -0,0 +1,257 @@
+use super::*;
+use crate::extmap::ExtMap;
+
+const OFFER: &str = "v=0\r\n\
+o=- 4611731400430051336 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+c=IN IP4 0.0.0.0\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0 1\r\n\
+a=msid-semantic:WMS stream\r\n\
+a=ice-ufrag:sess\r\n\
+a=ice-pwd:sessionpasswordsessionpassword\r\n\
+a=setup:actpass\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+a=mid:0\r\n\
+a=rtpmap:111 opus/48000/2\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96\r\n\
+a=mid:1\r\n\
+a=setup:active\r\n\
+a=rtpmap:96 VP8/90000\r\n";
+
+/// media parses the lines of a media description.
+fn media(lines: &str) -> Result<MediaDescription, Error> {
+    let sd = format!(
+        "v=0\r\no=- 0 0 IN IP4 127.0.0.1\r\ns=-\r\nt=0 0\r\n{}",
+        lines
+    )
+    .parse::<SessionDescription>()?;
+    Ok(sd.media_descriptions[0].clone())
+}
+
+fn template() -> Result<SessionTemplate, Error> {
+    Ok(SessionTemplate::new(
+        "- 1 1 IN IP4 127.0.0.1".parse()?,
+        "-".parse()?,
+        TimeDescription::default(),
+    ))
+}
+
+#[test]
+fn test_extract_media() -> Result<(), Error> {
+    let sd = OFFER.parse::<SessionDescription>()?;
+    let extracted = sd.extract_media(&Mid::try_new("1")?, OriginPolicy::Copy)?;
+    assert_eq!(
+        extracted.marshal(),
+        "v=0\r\n\
+o=- 4611731400430051336 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 1\r\n\
+a=msid-semantic:WMS stream\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=mid:1\r\n\
+a=setup:active\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+a=ice-ufrag:sess\r\n\
+a=ice-pwd:sessionpasswordsessionpassword\r\n"
+    );
+
+    assert!(matches!(
+        sd.extract_media(&Mid::try_new("2")?, OriginPolicy::Copy),
+        Err(Error::SdpMidNotFound(mid)) if mid == "2"
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_extract_rejected_media() -> Result<(), Error> {
+    let mut sd = OFFER.parse::<SessionDescription>()?;
+    sd.reject_media("0")?;
+    let extracted = sd.extract_media(&Mid::try_new("0")?, OriginPolicy::Copy)?;
+    assert!(extracted.groups().is_empty());
+    assert!(extracted.media_descriptions[0].is_rejected());
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_extract_media_fresh_origin() -> Result<(), Error> {
+    let sd = OFFER.parse::<SessionDescription>()?;
+    let extracted = sd.extract_media(&Mid::try_new("0")?, OriginPolicy::Fresh)?;
+    assert_eq!(extracted.origin.username, sd.origin.username);
+    assert_eq!(extracted.origin.unicast_address, sd.origin.unicast_address);
+    assert_ne!(extracted.origin.session_version, sd.origin.session_version);
+    assert!(extracted.origin.session_id < 1 << 63);
+
+    Ok(())
+}
+
+#[test]
+fn test_session_template_from_description() -> Result<(), Error> {
+    let sd = OFFER.parse::<SessionDescription>()?;
+    let template = SessionTemplate::from(&sd);
+    assert_eq!(template.origin, sd.origin);
+    let attributes: Vec<_> = template
+        .attributes
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(attributes, vec!["msid-semantic:WMS stream"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_compose_remaps_conflicts() -> Result<(), Error> {
+    let vp8 = media(
+        "m=video 9 UDP/TLS/RTP/SAVPF 96 97\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=mid:a\r\n\
+a=extmap:1 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=extmap:2 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+a=rtpmap:97 rtx/90000\r\n\
+a=fmtp:97 apt=96\r\n",
+    )?;
+    let h264 = media(
+        "m=video 9 UDP/TLS/RTP/SAVPF 96 97\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=mid:b\r\n\
+a=extmap:1 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time\r\n\
+a=extmap:3 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=rtpmap:96 H264/90000\r\n\
+a=rtpmap:97 rtx/90000\r\n\
+a=fmtp:97 apt=96\r\n",
+    )?;
+    // Data channels have no payload types, and rejected media descriptions
+    // are not bundled.
+    let data = media(
+        "m=application 9 UDP/DTLS/SCTP webrtc-datachannel\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=mid:c\r\n",
+    )?;
+    let rejected = media("m=video 0 UDP/TLS/RTP/SAVPF 96\r\na=mid:d\r\na=rtpmap:96 AV1/90000\r\n")?;
+
+    let composition = SessionDescription::compose(
+        vec![vp8.clone(), h264, data.clone(), rejected.clone()],
+        &template()?,
+    )?;
+    assert_eq!(
+        composition.remappings,
+        vec![
+            Remapping::PayloadType {
+                index: 1,
+                from: 96,
+                to: 98
+            },
+            Remapping::PayloadType {
+                index: 1,
+                from: 97,
+                to: 99
+            },
+            // The id the first media description uses for the extension.
+            Remapping::ExtMapId {
+                index: 1,
+                from: ExtMapId::try_new(1)?,
+                to: ExtMapId::try_new(2)?,
+            },
+        ]
+    );
+
+    let sd = &composition.description;
+    assert_eq!(sd.bundle_groups().len(), 1);
+    assert_eq!(
+        sd.bundle_groups()[0].to_string(),
+        "BUNDLE a b c",
+        "{}",
+        sd.marshal()
+    );
+    assert_eq!(sd.media_descriptions[0], vp8);
+    assert_eq!(sd.media_descriptions[2], data);
+    assert_eq!(sd.media_descriptions[3], rejected);
+
+    let h264 = &sd.media_descriptions[1];
+    assert_eq!(h264.media_name.formats, vec!["98", "99"]);
+    assert_eq!(
+        h264.fmtp_for("99").map(|f| f.to_string()),
+        Some("99 apt=98".to_owned())
+    );
+    let extmaps: Vec<_> = h264.extmaps().map(|e| e.to_string()).collect();
+    assert_eq!(
+        extmaps,
+        vec![
+            "2 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time",
+            "3 urn:ietf:params:rtp-hdrext:sdes:mid",
+        ]
+    );
+    assert!(sd.validate().is_empty(), "{:?}", sd.validate());
+
+    Ok(())
+}
+
+#[test]
+fn test_compose_extmap_id_of_same_size() -> Result<(), Error> {
+    let first = media(
+        "m=audio 9 RTP/AVP 0\r\na=mid:a\r\na=extmap:16 urn:example:one\r\na=extmap:17 urn:example:two\r\n",
+    )?;
+    let second = media("m=audio 9 RTP/AVP 0\r\na=mid:b\r\na=extmap:16 urn:example:three\r\n")?;
+    let composition = SessionDescription::compose(vec![first, second], &template()?)?;
+    assert_eq!(
+        composition.remappings,
+        vec![Remapping::ExtMapId {
+            index: 1,
+            from: ExtMapId::try_new(16)?,
+            to: ExtMapId::try_new(18)?,
+        }]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_compose_duplicate_mid() -> Result<(), Error> {
+    let audio = media("m=audio 9 RTP/AVP 0\r\na=mid:0\r\n")?;
+    assert!(matches!(
+        SessionDescription::compose(vec![audio.clone(), audio], &template()?),
+        Err(Error::SdpDuplicateMid(mid)) if mid == "0"
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_compose_exhausted() -> Result<(), Error> {
+    // The first media description takes every dynamic payload type and
+    // every one-byte extmap id.
+    let mut full = media("m=video 9 RTP/AVP 96\r\na=mid:0\r\n")?;
+    full.media_name.formats.clear();
+    for payload_type in (96..=127).chain(35..=63) {
+        full = full.with_codec(payload_type, "VP8".to_owned(), 90000, 0, String::new());
+    }
+    for id in 1..=14 {
+        full = full.with_extmap(format!("{} urn:example:{}", id, id).parse::<ExtMap>()?);
+    }
+
+    let h264 = media("m=video 9 RTP/AVP 96\r\na=mid:1\r\na=rtpmap:96 H264/90000\r\n")?;
+    assert!(matches!(
+        SessionDescription::compose(vec![full.clone(), h264], &template()?),
+        Err(Error::SdpPayloadTypesExhausted(96))
+    ));
+
+    let extmap = media(
+        "m=video 9 RTP/AVP 96\r\n\
+a=mid:1\r\n\
+a=extmap:1 urn:example:other\r\n\
+a=rtpmap:96 VP8/90000\r\n",
+    )?;
+    assert!(matches!(
+        SessionDescription::compose(vec![full, extmap], &template()?),
+        Err(Error::SdpExtMapIdsExhausted(1))
+    ));
+
+    Ok(())
+}
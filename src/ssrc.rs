@@ -0,0 +1,260 @@
+use core::fmt;
+use core::str::FromStr;
+
+use super::alloc_prelude::*;
+use super::common_description::TypedAttribute;
+use super::error::Error;
+use super::util::token::is_token;
+
+#[cfg(test)]
+mod ssrc_test;
+
+/// SSRC_ATTRIBUTE_CNAME is the canonical end-point identifier of a source.
+pub const SSRC_ATTRIBUTE_CNAME: &str = "cname";
+/// SSRC_ATTRIBUTE_MSID is the legacy media stream id of a source.
+pub const SSRC_ATTRIBUTE_MSID: &str = "msid";
+/// SSRC_ATTRIBUTE_MSLABEL is the legacy media stream label of a source.
+pub const SSRC_ATTRIBUTE_MSLABEL: &str = "mslabel";
+/// SSRC_ATTRIBUTE_LABEL is the legacy track label of a source.
+pub const SSRC_ATTRIBUTE_LABEL: &str = "label";
+
+/// Ssrc is an "a=ssrc:" attribute, describing one property of an RTP
+/// source.
+/// `a=ssrc:<ssrc-id> <attribute>`
+/// `a=ssrc:<ssrc-id> <attribute>:<value>`
+/// <https://tools.ietf.org/html/rfc5576#section-4.1>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::ssrc::Ssrc;
+///
+/// # fn main() -> Result<(), Error> {
+/// let ssrc = "3735928559 cname:user@example.com".parse::<Ssrc>()?;
+/// assert_eq!(ssrc.id, 0xdeadbeef);
+/// assert_eq!(ssrc.value.as_deref(), Some("user@example.com"));
+/// assert_eq!(ssrc.to_string(), "3735928559 cname:user@example.com");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ssrc {
+    /// id is the SSRC of the source.
+    pub id: u32,
+    /// attribute is the name of the source attribute, e.g. "cname".
+    pub attribute: String,
+    /// value is the value of the source attribute, if it has one.
+    pub value: Option<String>,
+}
+
+impl fmt::Display for Ssrc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.id, self.attribute)?;
+        if let Some(value) = &self.value {
+            write!(f, ":{}", value)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Ssrc {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::SdpInvalidSsrc(value.to_owned());
+
+        let (id, attribute) = value.split_once(' ').ok_or_else(invalid)?;
+        let id = parse_ssrc_id(id).ok_or_else(invalid)?;
+        let (attribute, attribute_value) = match attribute.split_once(':') {
+            Some((attribute, value)) => (attribute, Some(value.to_owned())),
+            None => (attribute, None),
+        };
+        if !is_token(attribute) {
+            return Err(invalid());
+        }
+
+        Ok(Ssrc {
+            id,
+            attribute: attribute.to_owned(),
+            value: attribute_value,
+        })
+    }
+}
+
+impl TypedAttribute for Ssrc {
+    const NAME: &'static str = "ssrc";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("{} of SSRC {}", self.attribute, self.id))
+    }
+}
+
+/// SsrcSource collects the "a=ssrc:" attributes of one RTP source, in the
+/// order they appear.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SsrcSource {
+    /// id is the SSRC of the source.
+    pub id: u32,
+    /// attributes are the names and values of the source attributes.
+    pub attributes: Vec<(String, Option<String>)>,
+}
+
+impl SsrcSource {
+    /// attribute returns the value of the first attribute with the name.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .and_then(|(_, v)| v.as_deref())
+    }
+
+    /// cname returns the "cname" of the source.
+    pub fn cname(&self) -> Option<&str> {
+        self.attribute(SSRC_ATTRIBUTE_CNAME)
+    }
+
+    /// msid returns the legacy "msid" of the source.
+    pub fn msid(&self) -> Option<&str> {
+        self.attribute(SSRC_ATTRIBUTE_MSID)
+    }
+
+    /// mslabel returns the legacy "mslabel" of the source.
+    pub fn mslabel(&self) -> Option<&str> {
+        self.attribute(SSRC_ATTRIBUTE_MSLABEL)
+    }
+
+    /// label returns the legacy "label" of the source.
+    pub fn label(&self) -> Option<&str> {
+        self.attribute(SSRC_ATTRIBUTE_LABEL)
+    }
+
+    /// to_ssrcs turns the source back into its "a=ssrc:" attributes.
+    pub fn to_ssrcs(&self) -> Vec<Ssrc> {
+        self.attributes
+            .iter()
+            .map(|(attribute, value)| Ssrc {
+                id: self.id,
+                attribute: attribute.clone(),
+                value: value.clone(),
+            })
+            .collect()
+    }
+}
+
+/// SsrcGroupSemantics tells how the sources of an "a=ssrc-group:" relate.
+/// <https://tools.ietf.org/html/rfc5576#section-4.2>
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SsrcGroupSemantics {
+    /// Fid is flow identification, e.g. a source and its RTX source.
+    /// <https://tools.ietf.org/html/rfc5888#section-8>
+    Fid,
+    /// <https://tools.ietf.org/html/rfc5956#section-4.3>
+    Fec,
+    /// FecFr is forward error correction framework, "FEC-FR".
+    /// <https://tools.ietf.org/html/rfc5956#section-4.1>
+    FecFr,
+    /// Sim is simulcast, as used by Chrome.
+    Sim,
+    /// Other is any other semantics, kept as written.
+    Other(String),
+}
+
+impl fmt::Display for SsrcGroupSemantics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            SsrcGroupSemantics::Fid => "FID",
+            SsrcGroupSemantics::Fec => "FEC",
+            SsrcGroupSemantics::FecFr => "FEC-FR",
+            SsrcGroupSemantics::Sim => "SIM",
+            SsrcGroupSemantics::Other(token) => token,
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for SsrcGroupSemantics {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(match value {
+            "FID" => SsrcGroupSemantics::Fid,
+            "FEC" => SsrcGroupSemantics::Fec,
+            "FEC-FR" => SsrcGroupSemantics::FecFr,
+            "SIM" => SsrcGroupSemantics::Sim,
+            _ if is_token(value) => SsrcGroupSemantics::Other(value.to_owned()),
+            _ => return Err(Error::SdpInvalidSsrcGroup(value.to_owned())),
+        })
+    }
+}
+
+/// SsrcGroup is an "a=ssrc-group:" attribute.
+/// `a=ssrc-group:<semantics> <ssrc-id> ...`
+/// <https://tools.ietf.org/html/rfc5576#section-4.2>
+///
+/// # Examples
+///
+/// ```
+/// # use webrtc_rs_sdp::Error;
+/// use webrtc_rs_sdp::ssrc::{SsrcGroup, SsrcGroupSemantics};
+///
+/// # fn main() -> Result<(), Error> {
+/// let group = "FID 1 2".parse::<SsrcGroup>()?;
+/// assert_eq!(group.semantics, SsrcGroupSemantics::Fid);
+/// assert_eq!(group.ssrcs, vec![1, 2]);
+/// assert_eq!(group.to_string(), "FID 1 2");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SsrcGroup {
+    /// semantics tells how the sources relate.
+    pub semantics: SsrcGroupSemantics,
+    /// ssrcs are the SSRCs of the sources.
+    pub ssrcs: Vec<u32>,
+}
+
+impl fmt::Display for SsrcGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.semantics)?;
+        for ssrc in &self.ssrcs {
+            write!(f, " {}", ssrc)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SsrcGroup {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::SdpInvalidSsrcGroup(value.to_owned());
+
+        let mut fields = value.split(' ');
+        let semantics = fields.next().unwrap_or_default().parse()?;
+        let ssrcs = fields
+            .map(|id| parse_ssrc_id(id).ok_or_else(invalid))
+            .collect::<Result<Vec<_>, _>>()?;
+        if ssrcs.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(SsrcGroup { semantics, ssrcs })
+    }
+}
+
+impl TypedAttribute for SsrcGroup {
+    const NAME: &'static str = "ssrc-group";
+
+    fn describe(&self) -> Option<String> {
+        Some(format!("{} SSRC group", self.semantics))
+    }
+}
+
+/// ssrc-id = integer ; 0 .. 2**32 - 1
+fn parse_ssrc_id(value: &str) -> Option<u32> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
@@ -0,0 +1,83 @@
+use super::*;
+
+#[test]
+fn test_ssrc() -> Result<(), Error> {
+    let tests = vec![
+        (
+            "1001286040 cname:2VZ7vUaxZQxyB6sR",
+            1001286040,
+            "cname",
+            Some("2VZ7vUaxZQxyB6sR"),
+        ),
+        (
+            "1001286040 msid:3HjkHtYq 6e4ab2a4-a1a3",
+            1001286040,
+            "msid",
+            Some("3HjkHtYq 6e4ab2a4-a1a3"),
+        ),
+        ("4294967295 label:a:b", 4294967295, "label", Some("a:b")),
+        ("0 previous-ssrc", 0, "previous-ssrc", None),
+        ("1 cname:", 1, "cname", Some("")),
+    ];
+
+    for (input, id, attribute, value) in tests {
+        let ssrc = input.parse::<Ssrc>()?;
+        assert_eq!(ssrc.id, id, "{}", input);
+        assert_eq!(ssrc.attribute, attribute);
+        assert_eq!(ssrc.value.as_deref(), value);
+        assert_eq!(ssrc.to_string(), input);
+    }
+
+    for input in [
+        "",
+        "1001286040",
+        "4294967296 cname:x",
+        "-1 cname:x",
+        "+1 cname:x",
+        "0x10 cname:x",
+        "1  cname:x",
+        "1 :x",
+    ] {
+        assert!(
+            matches!(input.parse::<Ssrc>(), Err(Error::SdpInvalidSsrc(_))),
+            "{:?}",
+            input
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_ssrc_group() -> Result<(), Error> {
+    let tests = vec![
+        ("FID 2231627014 632943048", SsrcGroupSemantics::Fid),
+        ("FEC 1 2", SsrcGroupSemantics::Fec),
+        ("FEC-FR 1 2", SsrcGroupSemantics::FecFr),
+        ("SIM 1 2 3", SsrcGroupSemantics::Sim),
+        ("DUP 1 2", SsrcGroupSemantics::Other("DUP".to_owned())),
+    ];
+
+    for (input, semantics) in tests {
+        let group = input.parse::<SsrcGroup>()?;
+        assert_eq!(group.semantics, semantics, "{}", input);
+        assert_eq!(group.to_string(), input);
+    }
+    assert_eq!(
+        "FID 2231627014 632943048".parse::<SsrcGroup>()?.ssrcs,
+        vec![2231627014, 632943048]
+    );
+
+    for input in ["", "FID", "FID 1 x", "FID 1  2", "F/D 1 2"] {
+        assert!(
+            matches!(
+                input.parse::<SsrcGroup>(),
+                Err(Error::SdpInvalidSsrcGroup(_))
+            ),
+            "{:?}",
+            input
+        );
+    }
+
+    Ok(())
+}
@@ -1,29 +1,40 @@
+/// token has the "token" rule of the SDP grammar.
+pub mod token;
+
 #[cfg(test)]
 mod util_test;
 
-use std::collections::HashMap;
-use std::{fmt, io};
+use alloc::collections::BTreeMap;
+use core::fmt;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::io::{self, SeekFrom};
+#[cfg(feature = "std")]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use super::alloc_prelude::*;
 use super::error::Error;
 use super::session_description::SessionDescription;
-use std::io::SeekFrom;
 
+/// END_LINE ends every line of a session description, unless its
+/// line_ending says otherwise.
 pub const END_LINE: &str = "\r\n";
+/// ATTRIBUTE_KEY is the type prefix of attribute lines.
 pub const ATTRIBUTE_KEY: &str = "a=";
 
-// ConnectionRole indicates which of the end points should initiate the connection establishment
-#[derive(Debug)]
+/// ConnectionRole indicates which of the end points should initiate the connection establishment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConnectionRole {
-    // ConnectionRoleActive indicates the endpoint will initiate an outgoing connection.
+    /// ConnectionRoleActive indicates the endpoint will initiate an outgoing connection.
     ConnectionRoleActive = 1,
 
-    // ConnectionRolePassive indicates the endpoint will accept an incoming connection.
+    /// ConnectionRolePassive indicates the endpoint will accept an incoming connection.
     ConnectionRolePassive = 2,
 
-    // ConnectionRoleActpass indicates the endpoint is willing to accept an incoming connection or to initiate an outgoing connection.
+    /// ConnectionRoleActpass indicates the endpoint is willing to accept an incoming connection or to initiate an outgoing connection.
     ConnectionRoleActpass = 3,
 
-    // ConnectionRoleHoldconn indicates the endpoint does not want the connection to be established for the time being.
+    /// ConnectionRoleHoldconn indicates the endpoint does not want the connection to be established for the time being.
     ConnectionRoleHoldconn = 4,
 }
 
@@ -40,7 +51,68 @@ impl fmt::Display for ConnectionRole {
     }
 }
 
-pub(crate) fn new_session_id() -> u64 {
+impl FromStr for ConnectionRole {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "active" => Ok(ConnectionRole::ConnectionRoleActive),
+            "passive" => Ok(ConnectionRole::ConnectionRolePassive),
+            "actpass" => Ok(ConnectionRole::ConnectionRoleActpass),
+            "holdconn" => Ok(ConnectionRole::ConnectionRoleHoldconn),
+            _ => Err(Error::SdpInvalidSetup(value.to_owned())),
+        }
+    }
+}
+
+/// NTP_UNIX_OFFSET is the number of seconds between the NTP epoch
+/// (1900-01-01) and the UNIX epoch (1970-01-01). SDP times are NTP seconds.
+/// <https://tools.ietf.org/html/rfc4566#section-5.9>
+pub const NTP_UNIX_OFFSET: u64 = 2208988800;
+
+/// unix_to_ntp_secs converts seconds since the UNIX epoch into NTP seconds.
+/// Returns None if the result does not fit into u64.
+pub fn unix_to_ntp_secs(unix_secs: u64) -> Option<u64> {
+    unix_secs.checked_add(NTP_UNIX_OFFSET)
+}
+
+/// ntp_to_unix_secs converts NTP seconds into seconds since the UNIX epoch.
+/// Returns None if the NTP value predates 1970.
+pub fn ntp_to_unix_secs(ntp_secs: u64) -> Option<u64> {
+    ntp_secs.checked_sub(NTP_UNIX_OFFSET)
+}
+
+/// system_time_to_ntp_secs converts a SystemTime into whole NTP seconds,
+/// truncating any sub-second part.
+#[cfg(feature = "std")]
+pub fn system_time_to_ntp_secs(time: SystemTime) -> Result<u64, Error> {
+    let since_epoch = time
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| Error::SdpInvalidValue("system time predates the UNIX epoch".to_owned()))?;
+    unix_to_ntp_secs(since_epoch.as_secs())
+        .ok_or_else(|| Error::SdpInvalidValue(format!("{:?}", time)))
+}
+
+/// ntp_secs_to_system_time converts NTP seconds into a SystemTime. Returns None
+/// if the NTP value predates 1970.
+#[cfg(feature = "std")]
+pub fn ntp_secs_to_system_time(ntp_secs: u64) -> Option<SystemTime> {
+    ntp_to_unix_secs(ntp_secs).and_then(|secs| UNIX_EPOCH.checked_add(Duration::from_secs(secs)))
+}
+
+/// ntp_now_secs returns the current time in NTP seconds, which RFC 4566
+/// suggests for `<sess-id>` and `<sess-version>`. A system clock set before 1970
+/// yields the NTP time of the UNIX epoch instead of panicking.
+/// <https://tools.ietf.org/html/rfc4566#section-5.2>
+#[cfg(feature = "std")]
+pub fn ntp_now_secs() -> u64 {
+    system_time_to_ntp_secs(SystemTime::now()).unwrap_or(NTP_UNIX_OFFSET)
+}
+
+/// new_session_id returns a random `<sess-id>`. The highest bit is always zero
+/// so that implementations parsing it as a signed 64-bit value accept it.
+#[cfg(feature = "std")]
+pub fn new_session_id() -> u64 {
     // https://tools.ietf.org/html/draft-ietf-rtcweb-jsep-26#section-5.2.1
     // Session ID is recommended to be constructed by generating a 64-bit
     // quantity with the highest bit set to zero and the remaining 63-bits
@@ -49,7 +121,7 @@ pub(crate) fn new_session_id() -> u64 {
     rand::random::<u64>() & c
 }
 
-// Codec represents a codec
+/// Codec represents a codec
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct Codec {
     payload_type: u8,
@@ -152,21 +224,21 @@ pub(crate) fn parse_rtcp_fb(rtcp_fb: &str) -> Result<Codec, Error> {
     })
 }
 
-pub(crate) fn merge_codecs(mut codec: Codec, codecs: &mut HashMap<u8, Codec>) {
+pub(crate) fn merge_codecs(mut codec: Codec, codecs: &mut BTreeMap<u8, Codec>) {
     if let Some(saved_codec) = codecs.get_mut(&codec.payload_type) {
         if saved_codec.payload_type == 0 {
             saved_codec.payload_type = codec.payload_type
         }
-        if &saved_codec.name == "" {
+        if saved_codec.name.is_empty() {
             saved_codec.name = codec.name
         }
         if saved_codec.clock_rate == 0 {
             saved_codec.clock_rate = codec.clock_rate
         }
-        if &saved_codec.encoding_parameters == "" {
+        if saved_codec.encoding_parameters.is_empty() {
             saved_codec.encoding_parameters = codec.encoding_parameters
         }
-        if &saved_codec.fmtp == "" {
+        if saved_codec.fmtp.is_empty() {
             saved_codec.fmtp = codec.fmtp
         }
         saved_codec.rtcp_feedback.append(&mut codec.rtcp_feedback);
@@ -198,65 +270,158 @@ fn equivalent_fmtp(want: &str, got: &str) -> bool {
 }
 
 pub(crate) fn codecs_match(wanted: &Codec, got: &Codec) -> bool {
-    if &wanted.name != "" && wanted.name.to_lowercase() != got.name.to_lowercase() {
+    if !wanted.name.is_empty() && wanted.name.to_lowercase() != got.name.to_lowercase() {
         return false;
     }
     if wanted.clock_rate != 0 && wanted.clock_rate != got.clock_rate {
         return false;
     }
-    if &wanted.encoding_parameters != "" && wanted.encoding_parameters != got.encoding_parameters {
+    if !wanted.encoding_parameters.is_empty()
+        && wanted.encoding_parameters != got.encoding_parameters
+    {
         return false;
     }
-    if &wanted.fmtp != "" && !equivalent_fmtp(&wanted.fmtp, &got.fmtp) {
+    if !wanted.fmtp.is_empty() && !equivalent_fmtp(&wanted.fmtp, &got.fmtp) {
         return false;
     }
 
     true
 }
 
+/// Lexer reads a session description field by field for
+/// SessionDescription::unmarshal.
+#[cfg(feature = "std")]
 pub struct Lexer<'a, R: io::BufRead + io::Seek> {
+    /// desc is the description read so far.
     pub desc: SessionDescription,
+    /// reader is the text being read.
     pub reader: &'a mut R,
+    /// 1-based number of the line holding the field being parsed
+    pub line: usize,
+    /// type character of the field being parsed, if one has been read
+    pub line_type: Option<char>,
+    /// number of line breaks consumed so far
+    pub newlines: usize,
 }
 
+#[cfg(feature = "std")]
+impl<'a, R: io::BufRead + io::Seek> Lexer<'a, R> {
+    /// new creates a lexer at the start of reader.
+    pub fn new(reader: &'a mut R) -> Self {
+        Lexer {
+            desc: SessionDescription::default(),
+            reader,
+            line: 1,
+            line_type: None,
+            newlines: 0,
+        }
+    }
+
+    /// read_type reads the "`<type>`=" prefix of the next field, keeping track
+    /// of the line it is on.
+    pub fn read_type(&mut self) -> Result<(String, usize), Error> {
+        let (key, num_bytes, newlines) = read_type_counted(self.reader)?;
+        self.newlines += newlines;
+        self.line = self.newlines + 1;
+        self.line_type = key.chars().next();
+        check_type(key, num_bytes)
+    }
+
+    /// read_value reads the remainder of the current field.
+    pub fn read_value(&mut self) -> Result<(String, usize), Error> {
+        let mut value = String::new();
+        let num_bytes = self.reader.read_line(&mut value)?;
+        if value.ends_with('\n') {
+            self.newlines += 1;
+        }
+        Ok((value.trim().to_string(), num_bytes))
+    }
+
+    /// read_text_value reads the remainder of a text field, which may be in
+    /// another charset than UTF-8.
+    pub fn read_text_value(&mut self) -> Result<(Vec<u8>, usize), Error> {
+        let mut value = Vec::new();
+        let num_bytes = self.reader.read_until(b'\n', &mut value)?;
+        if value.ends_with(b"\n") {
+            self.newlines += 1;
+        }
+        Ok((trim_text(&value).to_vec(), num_bytes))
+    }
+
+    /// error_at attaches the position of the field being parsed to err.
+    pub fn error_at(&self, err: Error) -> Error {
+        Error::SdpParse {
+            line: self.line,
+            line_type: self.line_type,
+            source: Box::new(err),
+        }
+    }
+}
+
+/// StateFnType is a state of the parser: it reads a field and returns the
+/// next state, or None at the end.
+#[cfg(feature = "std")]
 pub type StateFnType<'a, R> = fn(&mut Lexer<'a, R>) -> Result<Option<StateFn<'a, R>>, Error>;
 
+/// StateFn wraps a StateFnType, which can't refer to itself.
+#[cfg(feature = "std")]
 pub struct StateFn<'a, R: io::BufRead + io::Seek> {
+    /// f is the state.
     pub f: StateFnType<'a, R>,
 }
 
+/// read_type reads the "`<type>`=" prefix of the next field.
+#[cfg(feature = "std")]
 pub fn read_type<R: io::BufRead + io::Seek>(reader: &mut R) -> Result<(String, usize), Error> {
+    let (key, num_bytes, _) = read_type_counted(reader)?;
+    check_type(key, num_bytes)
+}
+
+/// read_type_counted reads everything up to and including the next "=",
+/// returning it along with the number of line breaks skipped before it.
+#[cfg(feature = "std")]
+fn read_type_counted<R: io::BufRead + io::Seek>(
+    reader: &mut R,
+) -> Result<(String, usize, usize), Error> {
+    let mut newlines = 0;
     loop {
         let mut b = [0; 1];
         if reader.read_exact(&mut b).is_err() {
-            return Ok(("".to_owned(), 0));
+            return Ok(("".to_owned(), 0, newlines));
         }
 
-        if b[0] == b'\n' || b[0] == b'\r' {
+        if b[0] == b'\n' {
+            newlines += 1;
+            continue;
+        }
+        if b[0] == b'\r' {
             continue;
         }
         reader.seek(SeekFrom::Current(-1))?;
 
         let mut buf = vec![];
         let num_bytes = reader.read_until(b'=', &mut buf)?;
-        if num_bytes == 0 {
-            return Ok(("".to_owned(), num_bytes));
-        }
+        return Ok((String::from_utf8(buf)?, num_bytes, newlines));
+    }
+}
 
-        let key = String::from_utf8(buf)?;
-        match key.len() {
-            2 => return Ok((key, num_bytes)),
-            _ => return Err(Error::SdpInvalidSyntax(format!("{:?}", key))),
-        }
+#[cfg(feature = "std")]
+fn check_type(key: String, num_bytes: usize) -> Result<(String, usize), Error> {
+    match key.len() {
+        0 | 2 => Ok((key, num_bytes)),
+        _ => Err(Error::SdpInvalidSyntax(format!("{:?}", key))),
     }
 }
 
+/// read_value reads the remainder of the current field.
+#[cfg(feature = "std")]
 pub fn read_value<R: io::BufRead + io::Seek>(reader: &mut R) -> Result<(String, usize), Error> {
     let mut value = String::new();
     let num_bytes = reader.read_line(&mut value)?;
     Ok((value.trim().to_string(), num_bytes))
 }
 
+/// index_of returns the position of element in data, or -1.
 pub fn index_of(element: &str, data: &[&str]) -> i32 {
     for (k, &v) in data.iter().enumerate() {
         if element == v {
@@ -266,6 +431,8 @@ pub fn index_of(element: &str, data: &[&str]) -> i32 {
     -1
 }
 
+/// key_value_build writes a "`<key><value>`" line, or nothing without a
+/// value.
 pub fn key_value_build(key: &str, value: Option<&String>) -> String {
     if let Some(val) = value {
         format!("{}{}{}", key, val, END_LINE)
@@ -273,3 +440,323 @@ pub fn key_value_build(key: &str, value: Option<&String>) -> String {
         "".to_string()
     }
 }
+
+/// decimal_len returns the number of digits of value, for the
+/// marshal_len_hint functions.
+pub(crate) fn decimal_len(value: u64) -> usize {
+    value
+        .checked_ilog10()
+        .map_or(1, |exponent| exponent as usize + 1)
+}
+
+/// signed_decimal_len is decimal_len for signed values, counting the sign.
+pub(crate) fn signed_decimal_len(value: i64) -> usize {
+    decimal_len(value.unsigned_abs()) + usize::from(value < 0)
+}
+
+/// LineEnding is the line break written after every line of a session
+/// description. RFC 4566 requires CRLF, but descriptions are also exchanged
+/// with LF alone, and the parsers keep the one of their input so that it is
+/// written back unchanged.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// CrLf is "\r\n".
+    #[default]
+    CrLf,
+    /// Lf is "\n".
+    Lf,
+}
+
+impl LineEnding {
+    /// as_str returns the line break.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::CrLf => END_LINE,
+            LineEnding::Lf => "\n",
+        }
+    }
+
+    /// of returns the line ending of the first line of input. Line breaks
+    /// before it are skipped like the parsers do, and input without any
+    /// line break is taken as CRLF.
+    pub(crate) fn of(input: &[u8]) -> LineEnding {
+        let start = input
+            .iter()
+            .take_while(|&&b| b == b'\r' || b == b'\n')
+            .count();
+        match input[start..].iter().position(|&b| b == b'\n') {
+            Some(end) if input[start + end - 1] != b'\r' => LineEnding::Lf,
+            _ => LineEnding::CrLf,
+        }
+    }
+}
+
+impl fmt::Display for LineEnding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// LfWriter passes everything written to it on to a fmt::Write, with "\n" in
+/// place of every END_LINE.
+pub(crate) struct LfWriter<'a, W>(pub(crate) &'a mut W);
+
+impl<W: fmt::Write> fmt::Write for LfWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut parts = s.split(END_LINE);
+        if let Some(first) = parts.next() {
+            self.0.write_str(first)?;
+        }
+        for part in parts {
+            self.0.write_str("\n")?;
+            self.0.write_str(part)?;
+        }
+        Ok(())
+    }
+}
+
+/// LenCounter is a fmt::Write which only counts the bytes written to it.
+#[derive(Debug, Default)]
+pub(crate) struct LenCounter(pub(crate) usize);
+
+impl fmt::Write for LenCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+/// line_len returns the length of a "`<type>`=`<value>`" line of which the value
+/// has the given length, including the line break.
+pub(crate) fn line_len(value_len: usize) -> usize {
+    2 + value_len + END_LINE.len()
+}
+
+/// validate_no_line_breaks rejects text which contains CR, LF or NUL. Every
+/// free-text field ends up on a line of its own when marshaled, so a line
+/// break would let the value inject arbitrary fields into the description.
+pub(crate) fn validate_no_line_breaks(value: &str) -> Result<(), Error> {
+    if value.contains(['\r', '\n', '\0']) {
+        return Err(Error::SdpInvalidCharacter(value.to_owned()));
+    }
+    Ok(())
+}
+
+/// validate_text is validate_no_line_breaks for text fields, which may be in
+/// another charset than UTF-8.
+pub(crate) fn validate_text(value: &[u8]) -> Result<(), Error> {
+    if value.iter().any(|b| matches!(b, b'\r' | b'\n' | b'\0')) {
+        return Err(Error::SdpInvalidCharacter(
+            String::from_utf8_lossy(value).into_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// trim_text trims a text field like str::trim does, or only ASCII whitespace
+/// if it isn't UTF-8.
+pub(crate) fn trim_text(value: &[u8]) -> &[u8] {
+    match core::str::from_utf8(value) {
+        Ok(value) => value.trim().as_bytes(),
+        Err(_) => value.trim_ascii(),
+    }
+}
+
+/// split_display_name splits the value of an "e=" or "p=" field into the
+/// address part and an optional display name. Both forms allowed by RFC 4566
+/// are recognized: `<address> (<name>)` and `<name> <<address>>`.
+/// <https://tools.ietf.org/html/rfc4566#section-5.6>
+pub(crate) fn split_display_name(value: &str) -> Result<(&str, Option<&str>), Error> {
+    let value = value.trim();
+    let count = |c: char| value.matches(c).count();
+    let (parens, brackets) = ((count('('), count(')')), (count('<'), count('>')));
+    if parens.0 != parens.1 || brackets.0 != brackets.1 || parens.0 > 1 || brackets.0 > 1 {
+        return Err(Error::SdpUnbalancedDelimiters(value.to_owned()));
+    }
+
+    if parens.0 == 1 {
+        let open = value.find('(').unwrap_or_default();
+        if brackets.0 != 0 || !value.ends_with(')') || open == 0 {
+            return Err(Error::SdpUnbalancedDelimiters(value.to_owned()));
+        }
+        let name = value[open + 1..value.len() - 1].trim();
+        return Ok((value[..open].trim(), Some(name).filter(|n| !n.is_empty())));
+    }
+
+    if brackets.0 == 1 {
+        let open = value.find('<').unwrap_or_default();
+        if !value.ends_with('>') {
+            return Err(Error::SdpUnbalancedDelimiters(value.to_owned()));
+        }
+        let name = value[..open].trim();
+        return Ok((
+            value[open + 1..value.len() - 1].trim(),
+            Some(name).filter(|n| !n.is_empty()),
+        ));
+    }
+
+    Ok((value, None))
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// base64_encode encodes data as padded base64 in the standard alphabet.
+/// <https://tools.ietf.org/html/rfc4648#section-4>
+pub(crate) fn base64_encode(data: &[u8]) -> String {
+    let mut output = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                output.push(BASE64_ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+/// base64_decode decodes canonical padded base64 in the standard alphabet. It
+/// returns None for anything else, including encodings where the unused bits
+/// of the last character are not zero.
+pub(crate) fn base64_decode(value: &str) -> Option<Vec<u8>> {
+    let bytes = value.as_bytes();
+    if !bytes.len().is_multiple_of(4) {
+        return None;
+    }
+    let padding = bytes.iter().rev().take_while(|&&b| b == b'=').count();
+    if padding > 2 {
+        return None;
+    }
+
+    let mut output = Vec::with_capacity(bytes.len() / 4 * 3);
+    for (i, chunk) in bytes.chunks(4).enumerate() {
+        let last = (i + 1) * 4 == bytes.len();
+        let len = if last { 4 - padding } else { 4 };
+        let mut n = 0u32;
+        for (j, &b) in chunk.iter().enumerate() {
+            let sextet = if j < len {
+                BASE64_ALPHABET.iter().position(|&c| c == b)? as u32
+            } else {
+                0
+            };
+            n = (n << 6) | sextet;
+        }
+        let decoded = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+        let count = len * 6 / 8;
+        // The bits beyond the last decoded byte must be zero.
+        if n & (0xff_ffff >> (count * 8)) != 0 {
+            return None;
+        }
+        output.extend_from_slice(&decoded[..count]);
+    }
+    Some(output)
+}
+
+/// impl_string_newtype implements Display, FromStr, TryFrom<&str>,
+/// TryFrom<String> and the comparisons with str for a newtype around a
+/// String, or around a Vec<u8> for text in any charset. Every conversion goes
+/// through the given validation function.
+macro_rules! impl_string_newtype {
+    ($ty:ident(Vec<u8>), $validate:path) => {
+        impl core::fmt::Debug for $ty {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_tuple(stringify!($ty))
+                    .field(&String::from_utf8_lossy(&self.0))
+                    .finish()
+            }
+        }
+
+        /// Text which isn't UTF-8 is displayed with replacement characters;
+        /// use decode_with or as_bytes to get it in its charset.
+        impl core::fmt::Display for $ty {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.pad(&String::from_utf8_lossy(&self.0))
+            }
+        }
+
+        impl core::convert::TryFrom<String> for $ty {
+            type Error = $crate::error::Error;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                $validate(value.as_bytes())?;
+                Ok($ty(value.into_bytes()))
+            }
+        }
+
+        impl PartialEq<str> for $ty {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other.as_bytes()
+            }
+        }
+
+        impl PartialEq<&str> for $ty {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == other.as_bytes()
+            }
+        }
+
+        $crate::util::impl_string_newtype!(@from_str $ty);
+    };
+    ($ty:ident(String), $validate:path) => {
+        $crate::util::impl_string_newtype!(secret $ty(String), $validate);
+
+        impl PartialEq<str> for $ty {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $ty {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+    // A secret such as IcePwd gets the conversions without the comparisons
+    // with str, which wouldn't take constant time.
+    (secret $ty:ident(String), $validate:path) => {
+        impl core::fmt::Display for $ty {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.pad(&self.0)
+            }
+        }
+
+        impl core::convert::TryFrom<String> for $ty {
+            type Error = $crate::error::Error;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                $validate(&value)?;
+                Ok($ty(value))
+            }
+        }
+
+        $crate::util::impl_string_newtype!(@from_str $ty);
+    };
+    (@from_str $ty:ident) => {
+        impl core::str::FromStr for $ty {
+            type Err = $crate::error::Error;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                <$ty as core::convert::TryFrom<String>>::try_from(value.to_owned())
+            }
+        }
+
+        impl core::convert::TryFrom<&str> for $ty {
+            type Error = $crate::error::Error;
+
+            fn try_from(value: &str) -> Result<Self, Self::Error> {
+                <$ty as core::convert::TryFrom<String>>::try_from(value.to_owned())
+            }
+        }
+    };
+}
+
+pub(crate) use impl_string_newtype;
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod token_test;
+
+use crate::alloc_prelude::*;
+use crate::error::Error;
+use crate::util::impl_string_newtype;
+
+/// is_token_char reports whether b may appear in a "token" as defined by the
+/// SDP grammar:
+///
+///   token-char = %x21 / %x23-27 / %x2A-2B / %x2D-2E / %x30-39
+///                / %x41-5A / %x5E-7E
+///
+/// That is letters, digits and "!#$%&'*+-.^_`{|}~". Note that "{" and "}" are
+/// token characters in SDP, unlike in HTTP.
+/// <https://tools.ietf.org/html/rfc4566#section-9>
+pub const fn is_token_char(b: u8) -> bool {
+    matches!(b, 0x21 | 0x23..=0x27 | 0x2a..=0x2b | 0x2d..=0x2e | 0x30..=0x39 | 0x41..=0x5a | 0x5e..=0x7e)
+}
+
+/// is_token reports whether value is a non-empty sequence of token characters.
+pub fn is_token(value: &str) -> bool {
+    !value.is_empty() && value.bytes().all(is_token_char)
+}
+
+/// validate_token checks that value is a token, returning SdpInvalidValue
+/// otherwise.
+pub fn validate_token(value: &str) -> Result<(), Error> {
+    if !is_token(value) {
+        return Err(Error::SdpInvalidValue(value.to_owned()));
+    }
+    Ok(())
+}
+
+/// Token is a string which has been checked to be a "token", so it can be
+/// written into any token-typed field without breaking the description.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Token(String);
+
+impl Token {
+    /// try_new creates a token, rejecting values which are not tokens.
+    pub fn try_new(value: &str) -> Result<Self, Error> {
+        validate_token(value)?;
+        Ok(Token(value.to_owned()))
+    }
+
+    /// as_str returns the token.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl_string_newtype!(Token(String), validate_token);
+
+impl AsRef<str> for Token {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<Token> for String {
+    fn from(token: Token) -> Self {
+        token.0
+    }
+}
@@ -0,0 +1,67 @@
+use super::*;
+
+use core::convert::TryFrom;
+
+const _: () = assert!(is_token_char(b'~') && !is_token_char(b'"'));
+
+// abnf_token_char spells out the token-char rule of RFC 4566 section 9 as a
+// list of characters, independently of the ranges used by is_token_char.
+fn abnf_token_char(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`{|}~".contains(&b)
+}
+
+#[test]
+fn test_is_token_char() {
+    for b in 0..=u8::MAX {
+        assert_eq!(is_token_char(b), abnf_token_char(b), "{:#04x}", b);
+    }
+    for b in [
+        b' ', b'"', b'(', b')', b',', b'/', b':', b';', b'<', b'=', b'>', b'?', b'@',
+    ] {
+        assert!(!is_token_char(b), "{:?}", b as char);
+    }
+}
+
+#[test]
+fn test_is_token() {
+    for b in 0..=u8::MAX {
+        match String::from_utf8(vec![b'a', b, b'z']) {
+            Ok(value) => assert_eq!(is_token(&value), abnf_token_char(b), "{:?}", value),
+            // bytes above 0x7f are never token characters
+            Err(_) => assert!(!is_token_char(b)),
+        }
+    }
+
+    let passingtests = ["AS", "X-cap-max", "1.0", "x{1}", "~"];
+    for input in passingtests.iter() {
+        assert!(is_token(input), "{:?}", input);
+    }
+    let failingtests = ["", "RTP/AVP", "a b", "a\r\nb", "\u{e9}", "a:b"];
+    for input in failingtests.iter() {
+        assert!(!is_token(input), "{:?}", input);
+        assert!(
+            matches!(validate_token(input), Err(Error::SdpInvalidValue(_))),
+            "{:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_token() -> Result<(), Error> {
+    let token = "X-cap-max".parse::<Token>()?;
+    assert_eq!(token, "X-cap-max");
+    assert_eq!(token.as_str(), "X-cap-max");
+    assert_eq!(token.to_string(), "X-cap-max");
+    assert_eq!(Token::try_from("{~}")?.as_ref(), "{~}");
+    assert_eq!(
+        String::from(Token::try_from("BUNDLE".to_owned())?),
+        "BUNDLE"
+    );
+
+    assert!(Token::try_new("").is_err());
+    assert!(Token::try_from("a b").is_err());
+    assert!(Token::try_from("a\nb".to_owned()).is_err());
+
+    Ok(())
+}
@@ -4,15 +4,12 @@ use crate::media_description::*;
 use crate::session_description::*;
 
 fn get_test_session_description() -> SessionDescription {
-    return SessionDescription{
+    SessionDescription{
         media_descriptions: vec![
             MediaDescription {
                 media_name: MediaName {
                     media: "video".to_string(),
-                    port: RangedPort {
-                        value: 51372,
-                        range: None,
-                    },
+                    port: Port::new(51372),
                     protos: vec!["RTP".to_string(), "AVP".to_string()],
                     formats: vec!["120".to_string(), "121".to_string(), "126".to_string(), "97".to_string()],
                 },
@@ -28,12 +25,13 @@ fn get_test_session_description() -> SessionDescription {
                     Attribute::new("rtcp-fb:97 ccm fir".to_string(), None),
                     Attribute::new("rtcp-fb:97 nack".to_string(), None),
                     Attribute::new("rtcp-fb:97 nack pli".to_string(), None),
-                ],
+                ]
+                .into(),
                 ..Default::default()
             },
 		],
         ..Default::default()
-	};
+	}
 }
 
 #[test]
@@ -150,6 +148,7 @@ fn test_get_codec_for_payload_type() -> Result<(), Error> {
 }
 
 #[test]
+#[cfg(feature = "std")]
 fn test_new_session_id() -> Result<(), Error> {
     let mut min = 0x7FFFFFFFFFFFFFFFu64;
     let mut max = 0u64;
@@ -157,7 +156,7 @@ fn test_new_session_id() -> Result<(), Error> {
         let r = new_session_id();
 
         if r > (1 << 63) - 1 {
-            assert!(false, "Session ID must be less than 2**64-1, got {}", r)
+            panic!("Session ID must be less than 2**64-1, got {}", r)
         }
         if r < min {
             min = r
@@ -167,11 +166,76 @@ fn test_new_session_id() -> Result<(), Error> {
         }
     }
     if min > 0x1000000000000000 {
-        assert!(false, "Value around lower boundary was not generated")
+        panic!("Value around lower boundary was not generated")
     }
     if max < 0x7000000000000000 {
-        assert!(false, "Value around upper boundary was not generated")
+        panic!("Value around upper boundary was not generated")
     }
 
     Ok(())
 }
+
+#[test]
+fn test_ntp_unix_conversion() {
+    assert_eq!(unix_to_ntp_secs(0), Some(NTP_UNIX_OFFSET));
+    assert_eq!(ntp_to_unix_secs(NTP_UNIX_OFFSET), Some(0));
+    assert_eq!(ntp_to_unix_secs(NTP_UNIX_OFFSET - 1), None);
+    assert_eq!(ntp_to_unix_secs(0), None);
+    assert_eq!(unix_to_ntp_secs(u64::MAX), None);
+    assert_eq!(unix_to_ntp_secs(u64::MAX - NTP_UNIX_OFFSET), Some(u64::MAX));
+
+    // t=2873397496 2873404696 from RFC 4566 is 1991-01-20 21:58:16 UTC.
+    assert_eq!(ntp_to_unix_secs(2873397496), Some(664408696));
+    assert_eq!(unix_to_ntp_secs(664408696), Some(2873397496));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_ntp_system_time_conversion() -> Result<(), Error> {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    assert_eq!(system_time_to_ntp_secs(UNIX_EPOCH)?, NTP_UNIX_OFFSET);
+    assert!(system_time_to_ntp_secs(UNIX_EPOCH - Duration::from_secs(1)).is_err());
+
+    let time = UNIX_EPOCH + Duration::from_millis(664408696500);
+    assert_eq!(system_time_to_ntp_secs(time)?, 2873397496);
+    assert_eq!(
+        ntp_secs_to_system_time(2873397496),
+        Some(UNIX_EPOCH + Duration::from_secs(664408696))
+    );
+    assert_eq!(ntp_secs_to_system_time(0), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_base64() {
+    let tests: [(&[u8], &str); 6] = [
+        (b"", ""),
+        (b"f", "Zg=="),
+        (b"fo", "Zm8="),
+        (b"foo", "Zm9v"),
+        (b"foob", "Zm9vYg=="),
+        (&[0x00, 0xff, 0xfe, 0x80, 0x7f], "AP/+gH8="),
+    ];
+
+    for (data, encoded) in tests.iter() {
+        assert_eq!(base64_encode(data), *encoded);
+        assert_eq!(base64_decode(encoded).as_deref(), Some(*data));
+    }
+
+    for invalid in [
+        "Zg=", "Zg", "Z===", "Zh==", "Zm9=", "Zm-v", "Zg==Zg==", "=Zg=",
+    ] {
+        assert!(base64_decode(invalid).is_none(), "{}", invalid);
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_ntp_now_secs() {
+    let first = ntp_now_secs();
+    let second = ntp_now_secs();
+    assert!(second >= first);
+    assert!(first > NTP_UNIX_OFFSET);
+}
@@ -0,0 +1,560 @@
+use super::alloc_prelude::*;
+use super::common_description::{
+    Attribute, Bandwidth, ConnectionInformation, EncryptionKey, Information, TypedAttribute,
+    UnknownLine,
+};
+use super::crypto::{Crypto, KeyParam};
+use super::group::Mid;
+use super::ice::IcePwd;
+use super::keymgmt::KeyMgmt;
+use super::media_description::{MediaDescription, MediaName};
+use super::session_description::{
+    EmailAddress, Origin, PhoneNumber, RepeatTime, SessionDescription, SessionName, TimeZones,
+    Timing, Uri, Version,
+};
+
+#[cfg(test)]
+mod visit_test;
+
+/// Level tells whether a line is part of the session level or of a media
+/// description.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Level {
+    /// Session is the session level, before the first "m=" line.
+    Session,
+    /// Media is the media description with the given index. mid is its
+    /// "a=mid:" as of the start of the media description.
+    Media {
+        /// index is the position of the media description.
+        index: usize,
+        /// mid is the mid of the media description, if it has one.
+        mid: Option<Mid>,
+    },
+}
+
+/// Visitor is called for every line of a session description by
+/// SessionDescription::walk, in the order they are serialized. Every method
+/// does nothing by default, so a visitor only implements the lines it cares
+/// about.
+pub trait Visitor {
+    /// visit_version is called for "v=".
+    fn visit_version(&mut self, _version: &Version) {}
+    /// visit_origin is called for "o=".
+    fn visit_origin(&mut self, _origin: &Origin) {}
+    /// visit_session_name is called for "s=".
+    fn visit_session_name(&mut self, _session_name: &SessionName) {}
+    /// visit_information is called for "i=", the session information or the
+    /// media title.
+    fn visit_information(&mut self, _level: &Level, _information: &Information) {}
+    /// visit_uri is called for "u=".
+    fn visit_uri(&mut self, _uri: &Uri) {}
+    /// visit_email_address is called for "e=".
+    fn visit_email_address(&mut self, _email_address: &EmailAddress) {}
+    /// visit_phone_number is called for "p=".
+    fn visit_phone_number(&mut self, _phone_number: &PhoneNumber) {}
+    /// visit_connection is called for "c=".
+    fn visit_connection(&mut self, _level: &Level, _connection: &ConnectionInformation) {}
+    /// visit_bandwidth is called for each "b=".
+    fn visit_bandwidth(&mut self, _level: &Level, _bandwidth: &Bandwidth) {}
+    /// visit_timing is called for each "t=".
+    fn visit_timing(&mut self, _timing: &Timing) {}
+    /// visit_repeat_time is called for each "r=", after the "t=" it belongs
+    /// to.
+    fn visit_repeat_time(&mut self, _repeat_time: &RepeatTime) {}
+    /// visit_time_zones is called for "z=".
+    fn visit_time_zones(&mut self, _time_zones: &TimeZones) {}
+    /// visit_key is called for "k=".
+    fn visit_key(&mut self, _level: &Level, _key: &EncryptionKey) {}
+    /// visit_attribute is called for each "a=".
+    fn visit_attribute(&mut self, _level: &Level, _attribute: &Attribute) {}
+    /// visit_unknown_line is called for each line of an unknown type, at the
+    /// position it is serialized at.
+    fn visit_unknown_line(&mut self, _level: &Level, _unknown_line: &UnknownLine) {}
+    /// visit_media_start is called when a media description starts, before
+    /// any of its lines.
+    fn visit_media_start(&mut self, _level: &Level, _media_description: &MediaDescription) {}
+    /// visit_media_name is called for "m=".
+    fn visit_media_name(&mut self, _level: &Level, _media_name: &MediaName) {}
+    /// visit_media_end is called after the last line of a media description.
+    fn visit_media_end(&mut self, _level: &Level) {}
+}
+
+/// VisitorMut is Visitor with mutable access, for
+/// SessionDescription::walk_mut. Changes made by a method are seen by the
+/// methods called later, e.g. visit_media_start may change the lines of the
+/// media description before they are visited.
+pub trait VisitorMut {
+    /// visit_version is called for "v=".
+    fn visit_version(&mut self, _version: &mut Version) {}
+    /// visit_origin is called for "o=".
+    fn visit_origin(&mut self, _origin: &mut Origin) {}
+    /// visit_session_name is called for "s=".
+    fn visit_session_name(&mut self, _session_name: &mut SessionName) {}
+    /// visit_information is called for "i=", the session information or the
+    /// media title.
+    fn visit_information(&mut self, _level: &Level, _information: &mut Information) {}
+    /// visit_uri is called for "u=".
+    fn visit_uri(&mut self, _uri: &mut Uri) {}
+    /// visit_email_address is called for "e=".
+    fn visit_email_address(&mut self, _email_address: &mut EmailAddress) {}
+    /// visit_phone_number is called for "p=".
+    fn visit_phone_number(&mut self, _phone_number: &mut PhoneNumber) {}
+    /// visit_connection is called for "c=".
+    fn visit_connection(&mut self, _level: &Level, _connection: &mut ConnectionInformation) {}
+    /// visit_bandwidth is called for each "b=".
+    fn visit_bandwidth(&mut self, _level: &Level, _bandwidth: &mut Bandwidth) {}
+    /// visit_timing is called for each "t=".
+    fn visit_timing(&mut self, _timing: &mut Timing) {}
+    /// visit_repeat_time is called for each "r=", after the "t=" it belongs
+    /// to.
+    fn visit_repeat_time(&mut self, _repeat_time: &mut RepeatTime) {}
+    /// visit_time_zones is called for "z=".
+    fn visit_time_zones(&mut self, _time_zones: &mut TimeZones) {}
+    /// visit_key is called for "k=".
+    fn visit_key(&mut self, _level: &Level, _key: &mut EncryptionKey) {}
+    /// visit_attribute is called for each "a=".
+    fn visit_attribute(&mut self, _level: &Level, _attribute: &mut Attribute) {}
+    /// visit_unknown_line is called for each line of an unknown type, at the
+    /// position it is serialized at.
+    fn visit_unknown_line(&mut self, _level: &Level, _unknown_line: &mut UnknownLine) {}
+    /// visit_media_start is called when a media description starts, before
+    /// any of its lines.
+    fn visit_media_start(&mut self, _level: &Level, _media_description: &mut MediaDescription) {}
+    /// visit_media_name is called for "m=".
+    fn visit_media_name(&mut self, _level: &Level, _media_name: &mut MediaName) {}
+    /// visit_media_end is called after the last line of a media description.
+    fn visit_media_end(&mut self, _level: &Level) {}
+}
+
+/// UnknownLines interleaves the unknown lines of a session or media
+/// description with its other lines, like UnknownLineWriter.
+struct UnknownLines<'a> {
+    unknown_lines: &'a [UnknownLine],
+    lines: usize,
+}
+
+impl<'a> UnknownLines<'a> {
+    /// line visits the unknown lines which come before the next line.
+    fn line<V: Visitor + ?Sized>(&mut self, visitor: &mut V, level: &Level) {
+        self.visit_until(visitor, level, self.lines);
+        self.lines += 1;
+    }
+
+    /// finish visits the unknown lines after the last line.
+    fn finish<V: Visitor + ?Sized>(mut self, visitor: &mut V, level: &Level) {
+        self.visit_until(visitor, level, usize::MAX);
+    }
+
+    fn visit_until<V: Visitor + ?Sized>(
+        &mut self,
+        visitor: &mut V,
+        level: &Level,
+        position: usize,
+    ) {
+        while let Some((unknown_line, rest)) = self.unknown_lines.split_first() {
+            if unknown_line.position > position {
+                break;
+            }
+            visitor.visit_unknown_line(level, unknown_line);
+            self.unknown_lines = rest;
+        }
+    }
+}
+
+/// UnknownLinesMut is UnknownLines for VisitorMut.
+struct UnknownLinesMut<'a> {
+    unknown_lines: &'a mut [UnknownLine],
+    lines: usize,
+}
+
+impl<'a> UnknownLinesMut<'a> {
+    fn line<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V, level: &Level) {
+        self.visit_until(visitor, level, self.lines);
+        self.lines += 1;
+    }
+
+    fn finish<V: VisitorMut + ?Sized>(mut self, visitor: &mut V, level: &Level) {
+        self.visit_until(visitor, level, usize::MAX);
+    }
+
+    fn visit_until<V: VisitorMut + ?Sized>(
+        &mut self,
+        visitor: &mut V,
+        level: &Level,
+        position: usize,
+    ) {
+        while self
+            .unknown_lines
+            .first()
+            .is_some_and(|l| l.position <= position)
+        {
+            let (unknown_line, rest) =
+                match core::mem::take(&mut self.unknown_lines).split_first_mut() {
+                    Some(split) => split,
+                    None => break,
+                };
+            visitor.visit_unknown_line(level, unknown_line);
+            self.unknown_lines = rest;
+        }
+    }
+}
+
+impl SessionDescription {
+    /// walk calls the visitor for every line of the description, in the
+    /// order marshal writes them.
+    pub fn walk<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        let level = Level::Session;
+        let mut unknown = UnknownLines {
+            unknown_lines: &self.unknown_lines,
+            lines: 0,
+        };
+        unknown.line(visitor, &level);
+        visitor.visit_version(&self.version);
+        unknown.line(visitor, &level);
+        visitor.visit_origin(&self.origin);
+        unknown.line(visitor, &level);
+        visitor.visit_session_name(&self.session_name);
+        if let Some(session_information) = &self.session_information {
+            unknown.line(visitor, &level);
+            visitor.visit_information(&level, session_information);
+        }
+        if let Some(uri) = &self.uri {
+            unknown.line(visitor, &level);
+            visitor.visit_uri(uri);
+        }
+        if let Some(email_address) = &self.email_address {
+            unknown.line(visitor, &level);
+            visitor.visit_email_address(email_address);
+        }
+        if let Some(phone_number) = &self.phone_number {
+            unknown.line(visitor, &level);
+            visitor.visit_phone_number(phone_number);
+        }
+        if let Some(connection_information) = &self.connection_information {
+            unknown.line(visitor, &level);
+            visitor.visit_connection(&level, connection_information);
+        }
+        for bandwidth in &self.bandwidth {
+            unknown.line(visitor, &level);
+            visitor.visit_bandwidth(&level, bandwidth);
+        }
+        for time_description in &self.time_descriptions {
+            unknown.line(visitor, &level);
+            visitor.visit_timing(&time_description.timing);
+            for repeat_time in &time_description.repeat_times {
+                unknown.line(visitor, &level);
+                visitor.visit_repeat_time(repeat_time);
+            }
+        }
+        if !self.time_zones.is_empty() {
+            unknown.line(visitor, &level);
+            visitor.visit_time_zones(&self.time_zones);
+        }
+        if let Some(encryption_key) = &self.encryption_key {
+            unknown.line(visitor, &level);
+            visitor.visit_key(&level, encryption_key);
+        }
+        for attribute in &self.attributes {
+            unknown.line(visitor, &level);
+            visitor.visit_attribute(&level, attribute);
+        }
+        unknown.finish(visitor, &level);
+
+        for (index, media_description) in self.media_descriptions.iter().enumerate() {
+            media_description.walk(index, visitor);
+        }
+    }
+
+    /// walk_mut is walk with mutable access to the lines, for visitors which
+    /// transform the description.
+    pub fn walk_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        let level = Level::Session;
+        let mut unknown = UnknownLinesMut {
+            unknown_lines: &mut self.unknown_lines,
+            lines: 0,
+        };
+        unknown.line(visitor, &level);
+        visitor.visit_version(&mut self.version);
+        unknown.line(visitor, &level);
+        visitor.visit_origin(&mut self.origin);
+        unknown.line(visitor, &level);
+        visitor.visit_session_name(&mut self.session_name);
+        if let Some(session_information) = &mut self.session_information {
+            unknown.line(visitor, &level);
+            visitor.visit_information(&level, session_information);
+        }
+        if let Some(uri) = &mut self.uri {
+            unknown.line(visitor, &level);
+            visitor.visit_uri(uri);
+        }
+        if let Some(email_address) = &mut self.email_address {
+            unknown.line(visitor, &level);
+            visitor.visit_email_address(email_address);
+        }
+        if let Some(phone_number) = &mut self.phone_number {
+            unknown.line(visitor, &level);
+            visitor.visit_phone_number(phone_number);
+        }
+        if let Some(connection_information) = &mut self.connection_information {
+            unknown.line(visitor, &level);
+            visitor.visit_connection(&level, connection_information);
+        }
+        for bandwidth in &mut self.bandwidth {
+            unknown.line(visitor, &level);
+            visitor.visit_bandwidth(&level, bandwidth);
+        }
+        for time_description in &mut self.time_descriptions {
+            unknown.line(visitor, &level);
+            visitor.visit_timing(&mut time_description.timing);
+            for repeat_time in &mut time_description.repeat_times {
+                unknown.line(visitor, &level);
+                visitor.visit_repeat_time(repeat_time);
+            }
+        }
+        if !self.time_zones.is_empty() {
+            unknown.line(visitor, &level);
+            visitor.visit_time_zones(&mut self.time_zones);
+        }
+        if let Some(encryption_key) = &mut self.encryption_key {
+            unknown.line(visitor, &level);
+            visitor.visit_key(&level, encryption_key);
+        }
+        for attribute in self.attributes.0.iter_mut() {
+            unknown.line(visitor, &level);
+            visitor.visit_attribute(&level, attribute);
+        }
+        unknown.finish(visitor, &level);
+
+        for (index, media_description) in self.media_descriptions.iter_mut().enumerate() {
+            media_description.walk_mut(index, visitor);
+        }
+    }
+}
+
+impl MediaDescription {
+    fn walk<V: Visitor + ?Sized>(&self, index: usize, visitor: &mut V) {
+        let level = Level::Media {
+            index,
+            mid: self.mid(),
+        };
+        visitor.visit_media_start(&level, self);
+        let mut unknown = UnknownLines {
+            unknown_lines: &self.unknown_lines,
+            lines: 0,
+        };
+        unknown.line(visitor, &level);
+        visitor.visit_media_name(&level, &self.media_name);
+        if let Some(media_title) = &self.media_title {
+            unknown.line(visitor, &level);
+            visitor.visit_information(&level, media_title);
+        }
+        if let Some(connection_information) = &self.connection_information {
+            unknown.line(visitor, &level);
+            visitor.visit_connection(&level, connection_information);
+        }
+        for bandwidth in &self.bandwidth {
+            unknown.line(visitor, &level);
+            visitor.visit_bandwidth(&level, bandwidth);
+        }
+        if let Some(encryption_key) = &self.encryption_key {
+            unknown.line(visitor, &level);
+            visitor.visit_key(&level, encryption_key);
+        }
+        for attribute in &self.attributes {
+            unknown.line(visitor, &level);
+            visitor.visit_attribute(&level, attribute);
+        }
+        unknown.finish(visitor, &level);
+        visitor.visit_media_end(&level);
+    }
+
+    fn walk_mut<V: VisitorMut + ?Sized>(&mut self, index: usize, visitor: &mut V) {
+        let level = Level::Media {
+            index,
+            mid: self.mid(),
+        };
+        visitor.visit_media_start(&level, self);
+        let mut unknown = UnknownLinesMut {
+            unknown_lines: &mut self.unknown_lines,
+            lines: 0,
+        };
+        unknown.line(visitor, &level);
+        visitor.visit_media_name(&level, &mut self.media_name);
+        if let Some(media_title) = &mut self.media_title {
+            unknown.line(visitor, &level);
+            visitor.visit_information(&level, media_title);
+        }
+        if let Some(connection_information) = &mut self.connection_information {
+            unknown.line(visitor, &level);
+            visitor.visit_connection(&level, connection_information);
+        }
+        for bandwidth in &mut self.bandwidth {
+            unknown.line(visitor, &level);
+            visitor.visit_bandwidth(&level, bandwidth);
+        }
+        if let Some(encryption_key) = &mut self.encryption_key {
+            unknown.line(visitor, &level);
+            visitor.visit_key(&level, encryption_key);
+        }
+        for attribute in self.attributes.0.iter_mut() {
+            unknown.line(visitor, &level);
+            visitor.visit_attribute(&level, attribute);
+        }
+        unknown.finish(visitor, &level);
+        visitor.visit_media_end(&level);
+    }
+}
+
+/// LineCounter is a Visitor counting the lines of a session description,
+/// and how many of them are attributes.
+///
+/// ```
+/// # use webrtc_rs_sdp::prelude::*;
+/// # fn main() -> Result<(), Error> {
+/// let sd = "v=0\r\n\
+///           o=- 0 0 IN IP4 127.0.0.1\r\n\
+///           s=-\r\n\
+///           t=0 0\r\n\
+///           m=audio 9 RTP/AVP 0\r\n\
+///           a=sendrecv\r\n"
+///     .parse::<SessionDescription>()?;
+/// let mut counter = LineCounter::default();
+/// sd.walk(&mut counter);
+/// assert_eq!(counter.lines, 6);
+/// assert_eq!(counter.attributes, 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LineCounter {
+    /// lines is the number of lines visited.
+    pub lines: usize,
+    /// attributes is the number of "a=" lines visited.
+    pub attributes: usize,
+}
+
+impl Visitor for LineCounter {
+    fn visit_version(&mut self, _version: &Version) {
+        self.lines += 1;
+    }
+    fn visit_origin(&mut self, _origin: &Origin) {
+        self.lines += 1;
+    }
+    fn visit_session_name(&mut self, _session_name: &SessionName) {
+        self.lines += 1;
+    }
+    fn visit_information(&mut self, _level: &Level, _information: &Information) {
+        self.lines += 1;
+    }
+    fn visit_uri(&mut self, _uri: &Uri) {
+        self.lines += 1;
+    }
+    fn visit_email_address(&mut self, _email_address: &EmailAddress) {
+        self.lines += 1;
+    }
+    fn visit_phone_number(&mut self, _phone_number: &PhoneNumber) {
+        self.lines += 1;
+    }
+    fn visit_connection(&mut self, _level: &Level, _connection: &ConnectionInformation) {
+        self.lines += 1;
+    }
+    fn visit_bandwidth(&mut self, _level: &Level, _bandwidth: &Bandwidth) {
+        self.lines += 1;
+    }
+    fn visit_timing(&mut self, _timing: &Timing) {
+        self.lines += 1;
+    }
+    fn visit_repeat_time(&mut self, _repeat_time: &RepeatTime) {
+        self.lines += 1;
+    }
+    fn visit_time_zones(&mut self, _time_zones: &TimeZones) {
+        self.lines += 1;
+    }
+    fn visit_key(&mut self, _level: &Level, _key: &EncryptionKey) {
+        self.lines += 1;
+    }
+    fn visit_attribute(&mut self, _level: &Level, _attribute: &Attribute) {
+        self.lines += 1;
+        self.attributes += 1;
+    }
+    fn visit_unknown_line(&mut self, _level: &Level, _unknown_line: &UnknownLine) {
+        self.lines += 1;
+    }
+    fn visit_media_name(&mut self, _level: &Level, _media_name: &MediaName) {
+        self.lines += 1;
+    }
+}
+
+/// REDACTED_KEY replaces the key material of a "k=" line.
+pub(crate) const REDACTED_KEY: &str = "redacted";
+
+/// redact_encryption_key keeps the method of an encryption key and drops the
+/// key itself, e.g. "clear:secret" becomes "clear:redacted". A "uri:" key
+/// only locates the key and is kept.
+pub(crate) fn redact_encryption_key(key: EncryptionKey) -> EncryptionKey {
+    match key {
+        EncryptionKey::Clear(_) => EncryptionKey::Clear(REDACTED_KEY.to_owned()),
+        EncryptionKey::Base64(_) => EncryptionKey::Base64(REDACTED_KEY.to_owned()),
+        key => key,
+    }
+}
+
+/// SecretRedactor is a VisitorMut removing the secrets of a session
+/// description, e.g. before logging it: the keys of "k=", "a=crypto:" and
+/// "a=key-mgmt:" and the ICE passwords. Unlike SessionDescription::annotate
+/// it keeps the description valid: "k=" keys become "redacted", and the other
+/// secrets are zeroed, keeping their length. Malformed attributes of these
+/// types, which might still hold a secret, lose their value.
+///
+/// ```
+/// # use webrtc_rs_sdp::prelude::*;
+/// # fn main() -> Result<(), Error> {
+/// let mut sd = "v=0\r\n\
+///               o=- 0 0 IN IP4 127.0.0.1\r\n\
+///               s=-\r\n\
+///               t=0 0\r\n\
+///               k=clear:secret\r\n"
+///     .parse::<SessionDescription>()?;
+/// sd.walk_mut(&mut SecretRedactor);
+/// assert!(sd.marshal().contains("k=clear:redacted\r\n"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SecretRedactor;
+
+impl VisitorMut for SecretRedactor {
+    fn visit_key(&mut self, _level: &Level, key: &mut EncryptionKey) {
+        *key = redact_encryption_key(key.clone());
+    }
+
+    fn visit_attribute(&mut self, _level: &Level, attribute: &mut Attribute) {
+        let redacted = if IcePwd::matches(attribute) {
+            IcePwd::from_attribute(attribute)
+                .ok()
+                .and_then(|pwd| IcePwd::try_new(&"0".repeat(pwd.expose().len())).ok())
+                .map(|pwd| pwd.to_attribute())
+        } else if KeyMgmt::matches(attribute) {
+            KeyMgmt::from_attribute(attribute)
+                .ok()
+                .and_then(|key_mgmt| {
+                    let zeroes = vec![0; key_mgmt.expose_data().len()];
+                    KeyMgmt::new(key_mgmt.protocol_id(), zeroes).ok()
+                })
+                .map(|key_mgmt| key_mgmt.to_attribute())
+        } else if Crypto::matches(attribute) {
+            Crypto::from_attribute(attribute).ok().map(|mut crypto| {
+                for key_param in &mut crypto.key_params {
+                    let mut zeroed = KeyParam::inline(vec![0; key_param.expose_key_salt().len()]);
+                    zeroed.lifetime = key_param.lifetime;
+                    zeroed.mki = key_param.mki;
+                    *key_param = zeroed;
+                }
+                crypto.to_attribute()
+            })
+        } else {
+            return;
+        };
+        *attribute = redacted.unwrap_or_else(|| Attribute::new(attribute.key.to_string(), None));
+    }
+}
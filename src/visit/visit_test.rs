@@ -0,0 +1,232 @@
+use super::*;
+use crate::error::Error;
+
+const SDP: &str = "v=0\r\n\
+o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
+s=SDP Seminar\r\n\
+i=A Seminar\r\n\
+u=http://www.example.com/seminars/sdp.pdf\r\n\
+e=j.doe@example.com (Jane Doe)\r\n\
+p=+1 617 555-6011\r\n\
+c=IN IP4 224.2.17.12/127\r\n\
+b=AS:256\r\n\
+t=2873397496 2873404696\r\n\
+r=604800 3600 0 90000\r\n\
+z=2882844526 -3600 2898848070 0\r\n\
+k=clear:secret\r\n\
+a=recvonly\r\n\
+a=ice-pwd:asd88fgpdd777uzjYhagZg\r\n\
+m=audio 49170 RTP/SAVP 0\r\n\
+i=Vivamus\r\n\
+c=IN IP4 203.0.113.1\r\n\
+b=AS:64\r\n\
+k=base64:c2VjcmV0\r\n\
+a=mid:audio\r\n\
+a=crypto:1 AES_CM_128_HMAC_SHA1_80 inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR|2^20|1:4\r\n\
+a=key-mgmt:mikey AQAFgM0XflABAAAAAAAAAAAAAAsAyONQ6gAAAAAGEEoo2pee4hp2UaDX8ZE22YwKAQAGVbCSvFsY7g==\r\n\
+m=video 51372 RTP/AVP 99\r\n\
+a=rtpmap:99 h263-1998/90000\r\n";
+
+/// Recorder writes each visited line back as SDP text.
+#[derive(Default)]
+struct Recorder {
+    lines: Vec<String>,
+    media: Vec<Level>,
+}
+
+impl Visitor for Recorder {
+    fn visit_version(&mut self, version: &Version) {
+        self.lines.push(format!("v={}", version));
+    }
+    fn visit_origin(&mut self, origin: &Origin) {
+        self.lines.push(format!("o={}", origin));
+    }
+    fn visit_session_name(&mut self, session_name: &SessionName) {
+        self.lines.push(format!("s={}", session_name));
+    }
+    fn visit_information(&mut self, _level: &Level, information: &Information) {
+        self.lines.push(format!("i={}", information));
+    }
+    fn visit_uri(&mut self, uri: &Uri) {
+        self.lines.push(format!("u={}", uri));
+    }
+    fn visit_email_address(&mut self, email_address: &EmailAddress) {
+        self.lines.push(format!("e={}", email_address));
+    }
+    fn visit_phone_number(&mut self, phone_number: &PhoneNumber) {
+        self.lines.push(format!("p={}", phone_number));
+    }
+    fn visit_connection(&mut self, _level: &Level, connection: &ConnectionInformation) {
+        self.lines.push(format!("c={}", connection));
+    }
+    fn visit_bandwidth(&mut self, _level: &Level, bandwidth: &Bandwidth) {
+        self.lines.push(format!("b={}", bandwidth));
+    }
+    fn visit_timing(&mut self, timing: &Timing) {
+        self.lines.push(format!("t={}", timing));
+    }
+    fn visit_repeat_time(&mut self, repeat_time: &RepeatTime) {
+        self.lines.push(format!("r={}", repeat_time));
+    }
+    fn visit_time_zones(&mut self, time_zones: &TimeZones) {
+        self.lines.push(format!("z={}", time_zones));
+    }
+    fn visit_key(&mut self, _level: &Level, key: &EncryptionKey) {
+        self.lines.push(format!("k={}", key));
+    }
+    fn visit_attribute(&mut self, _level: &Level, attribute: &Attribute) {
+        self.lines.push(format!("a={}", attribute));
+    }
+    fn visit_unknown_line(&mut self, _level: &Level, unknown_line: &UnknownLine) {
+        self.lines.push(unknown_line.to_string());
+    }
+    fn visit_media_start(&mut self, level: &Level, _media_description: &MediaDescription) {
+        self.media.push(level.clone());
+    }
+    fn visit_media_name(&mut self, _level: &Level, media_name: &MediaName) {
+        self.lines.push(format!("m={}", media_name));
+    }
+}
+
+fn serialized_lines(sd: &SessionDescription) -> Vec<String> {
+    sd.marshal()
+        .split("\r\n")
+        .filter(|line| !line.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+#[test]
+fn test_walk_order() -> Result<(), Error> {
+    let mut sd = SDP.parse::<SessionDescription>()?;
+    sd.unknown_lines.push(UnknownLine {
+        line_type: 'y',
+        value: "first".to_owned(),
+        position: 0,
+    });
+    sd.unknown_lines.push(UnknownLine {
+        line_type: 'y',
+        value: "after-origin".to_owned(),
+        position: 2,
+    });
+    sd.media_descriptions[1].unknown_lines.push(UnknownLine {
+        line_type: 'y',
+        value: "last".to_owned(),
+        position: usize::MAX,
+    });
+
+    let mut recorder = Recorder::default();
+    sd.walk(&mut recorder);
+    assert_eq!(recorder.lines, serialized_lines(&sd));
+    assert_eq!(
+        recorder.media,
+        vec![
+            Level::Media {
+                index: 0,
+                mid: Some(Mid::try_new("audio")?),
+            },
+            Level::Media {
+                index: 1,
+                mid: None,
+            },
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_line_counter() -> Result<(), Error> {
+    let sd = SDP.parse::<SessionDescription>()?;
+    let mut counter = LineCounter::default();
+    sd.walk(&mut counter);
+    assert_eq!(counter.lines, serialized_lines(&sd).len());
+    assert_eq!(counter.attributes, 6);
+    Ok(())
+}
+
+#[test]
+fn test_secret_redactor() -> Result<(), Error> {
+    let mut sd = SDP.parse::<SessionDescription>()?;
+    sd.walk_mut(&mut SecretRedactor);
+    let text = sd.marshal();
+
+    assert!(text.contains("k=clear:redacted\r\n"), "{}", text);
+    assert!(text.contains("k=base64:redacted\r\n"), "{}", text);
+    assert!(
+        text.contains("a=ice-pwd:0000000000000000000000\r\n"),
+        "{}",
+        text
+    );
+    assert!(
+        text.contains(
+            "a=crypto:1 AES_CM_128_HMAC_SHA1_80 inline:AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA|2^20|1:4\r\n"
+        ),
+        "{}",
+        text
+    );
+    assert!(!text.contains("c2VjcmV0"), "{}", text);
+    assert!(!text.contains("asd88fgpdd777uzjYhagZg"), "{}", text);
+    assert!(
+        !text.contains("PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR"),
+        "{}",
+        text
+    );
+    assert!(!text.contains("AQAFgM0X"), "{}", text);
+
+    // The redacted description is still valid, and the rest is unchanged.
+    let redacted = text.parse::<SessionDescription>()?;
+    assert_eq!(redacted.key_mgmt().len(), 0);
+    assert_eq!(redacted.media_descriptions[0].key_mgmt().len(), 1);
+    assert_eq!(redacted.origin, SDP.parse::<SessionDescription>()?.origin);
+    assert_eq!(
+        serialized_lines(&redacted).len(),
+        serialized_lines(&sd).len()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_secret_redactor_malformed() -> Result<(), Error> {
+    let mut sd = SessionDescription::default();
+    sd.attributes.push(Attribute::new(
+        "ice-pwd".to_owned(),
+        Some("short".to_owned()),
+    ));
+    sd.walk_mut(&mut SecretRedactor);
+    assert_eq!(
+        sd.attributes.0,
+        vec![Attribute::new("ice-pwd".to_owned(), None)]
+    );
+    Ok(())
+}
+
+/// Anonymizer is a transformation written as a visitor: it replaces every
+/// connection address.
+struct Anonymizer;
+
+impl VisitorMut for Anonymizer {
+    fn visit_connection(&mut self, _level: &Level, connection: &mut ConnectionInformation) {
+        *connection = "IN IP4 192.0.2.1".parse().expect("valid connection");
+    }
+}
+
+#[test]
+fn test_walk_mut() -> Result<(), Error> {
+    let mut sd = SDP.parse::<SessionDescription>()?;
+    sd.walk_mut(&mut Anonymizer);
+    assert_eq!(
+        sd.connection_information.as_ref().map(ToString::to_string),
+        Some("IN IP4 192.0.2.1".to_owned())
+    );
+    assert_eq!(
+        sd.media_descriptions[0]
+            .connection_information
+            .as_ref()
+            .map(ToString::to_string),
+        Some("IN IP4 192.0.2.1".to_owned())
+    );
+    assert_eq!(sd.media_descriptions[1].connection_information, None);
+    Ok(())
+}
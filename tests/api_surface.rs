@@ -0,0 +1,243 @@
+// Goes once through every public constructor and every parse and display
+// pair, the way a user of the crate would, so that the public surface as a
+// whole stays usable from outside the crate.
+#![cfg(feature = "std")]
+
+use std::fmt::{Debug, Display};
+use std::net::{IpAddr, Ipv4Addr};
+use std::str::FromStr;
+
+use webrtc_rs_sdp::basic::{
+    ConferenceType, FrameRate, Lang, LanguageTag, Orient, Quality, SdpLang, Tool,
+};
+use webrtc_rs_sdp::candidate::{Candidate, CandidateAddress, CandidateType, TcpType, Transport};
+use webrtc_rs_sdp::charset::Charset;
+use webrtc_rs_sdp::common_description::{
+    Address, Attribute, Bandwidth, BandwidthType, ConnectionInformation, EncryptionKey,
+    Information, NetType,
+};
+use webrtc_rs_sdp::content::{Content, ContentValue, Label};
+use webrtc_rs_sdp::crypto::{Crypto, CryptoSuite, KeyParam, Lifetime, Mki};
+use webrtc_rs_sdp::direction::Direction;
+use webrtc_rs_sdp::extmap::{ExtMap, ExtMapId};
+use webrtc_rs_sdp::fingerprint::{Fingerprint, HashFunction};
+use webrtc_rs_sdp::fmtp::Fmtp;
+use webrtc_rs_sdp::group::{Group, GroupSemantics, Mid};
+use webrtc_rs_sdp::ice::{IceOptions, IcePwd, IceUfrag};
+use webrtc_rs_sdp::keymgmt::KeyMgmt;
+use webrtc_rs_sdp::maxprate::MaxPRate;
+use webrtc_rs_sdp::media_description::{MediaName, MediaType, Port, Protocol};
+use webrtc_rs_sdp::msid::{Msid, MsidSemantic};
+use webrtc_rs_sdp::rtcp::Rtcp;
+use webrtc_rs_sdp::rtcpfb::{PayloadTypeRef, RtcpFeedback, RtcpFeedbackType};
+use webrtc_rs_sdp::rtpmap::RtpMap;
+use webrtc_rs_sdp::sctp::{MaxMessageSize, SctpMap, SctpPort};
+use webrtc_rs_sdp::session_description::{
+    EmailAddress, Origin, PhoneNumber, RepeatTime, SessionName, TimeZone, TimeZones, Timing, Uri,
+    Username, Version,
+};
+use webrtc_rs_sdp::setup::{Connection, Setup};
+use webrtc_rs_sdp::simulcast::{Rid, RidDirection, Simulcast};
+use webrtc_rs_sdp::ssrc::{Ssrc, SsrcGroup, SsrcGroupSemantics};
+use webrtc_rs_sdp::util::token::Token;
+use webrtc_rs_sdp::{Error, MediaDescription, SessionDescription};
+
+/// roundtrip parses value as T and checks that it is displayed as written.
+fn roundtrip<T>(value: &str) -> Result<T, Error>
+where
+    T: FromStr<Err = Error> + Display + Debug,
+{
+    let parsed = value.parse::<T>()?;
+    assert_eq!(parsed.to_string(), value, "{:?}", parsed);
+    Ok(parsed)
+}
+
+#[test]
+fn test_session_fields() -> Result<(), Error> {
+    roundtrip::<Version>("0")?;
+    roundtrip::<Origin>("- 4611731400430051336 2 IN IP4 127.0.0.1")?;
+    roundtrip::<Username>("jdoe")?;
+    roundtrip::<SessionName>("Call")?;
+    roundtrip::<Uri>("http://www.example.com/seminars/sdp.pdf")?;
+    roundtrip::<EmailAddress>("j.doe@example.com (Jane Doe)")?;
+    roundtrip::<PhoneNumber>("+1 617 555-6011")?;
+    roundtrip::<TimeZone>("2882844526 -3600")?;
+    roundtrip::<TimeZones>("2882844526 -3600 2898848070 0")?;
+    roundtrip::<Timing>("2873397496 2873404696")?;
+    roundtrip::<RepeatTime>("604800 3600 0 90000")?;
+
+    assert_eq!(Username::try_new("jdoe")?.to_string(), "jdoe");
+    assert!(Username::try_new("j doe").is_err());
+    assert_eq!(SessionName::try_new("Call")?.to_string(), "Call");
+    assert_eq!(
+        EmailAddress::try_new("j.doe@example.com", None)?.to_string(),
+        "j.doe@example.com"
+    );
+    assert_eq!(
+        PhoneNumber::new("+1 617 555-6011", None)?.to_string(),
+        "+1 617 555-6011"
+    );
+    assert_eq!(
+        TimeZone::new(2882844526, -3600).to_string(),
+        "2882844526 -3600"
+    );
+    assert_eq!(Timing::new(0, 0)?.to_string(), "0 0");
+    let origin = Origin::new_for_host(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    assert_eq!(origin.to_string().parse::<Origin>()?, origin);
+
+    Ok(())
+}
+
+#[test]
+fn test_common_fields() -> Result<(), Error> {
+    roundtrip::<Information>("A Seminar on the session description protocol")?;
+    roundtrip::<ConnectionInformation>("IN IP4 224.2.36.42/127")?;
+    roundtrip::<BandwidthType>("AS")?;
+    roundtrip::<NetType>("IN")?;
+    roundtrip::<NetType>("ATM")?;
+    roundtrip::<Bandwidth>("AS:256")?;
+    roundtrip::<EncryptionKey>("prompt")?;
+    roundtrip::<Attribute>("rtpmap:96 VP8/90000")?;
+
+    assert_eq!(Information::try_new("A Seminar")?.to_string(), "A Seminar");
+    let address = Address::from(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    assert_eq!(address.to_string(), "127.0.0.1");
+    assert_eq!(
+        ConnectionInformation::try_new(NetType::In, "IP4", Some(address))?.to_string(),
+        "IN IP4 127.0.0.1"
+    );
+    assert!(ConnectionInformation::try_new(
+        NetType::In,
+        "IP6",
+        Some(Address::from(IpAddr::V4(Ipv4Addr::LOCALHOST)))
+    )
+    .is_err());
+    assert_eq!(Bandwidth::new("AS", 256)?.to_string(), "AS:256");
+    assert_eq!(EncryptionKey::try_new("prompt")?.to_string(), "prompt");
+    assert_eq!(
+        Attribute::new("recvonly".to_owned(), None).to_string(),
+        "recvonly"
+    );
+    assert_eq!(Attribute::try_new("mid", Some("0"))?.to_string(), "mid:0");
+    assert!(Attribute::try_new("mid\r\n", None).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_media_fields() -> Result<(), Error> {
+    roundtrip::<MediaType>("audio")?;
+    roundtrip::<Protocol>("UDP/TLS/RTP/SAVPF")?;
+    roundtrip::<MediaName>("audio 9 UDP/TLS/RTP/SAVPF 111")?;
+    assert_eq!("9".parse::<Port>()?, Port::new(9));
+
+    let md = MediaDescription::new("audio".to_owned(), vec![]);
+    assert_eq!(md.media_name.media, "audio");
+
+    Ok(())
+}
+
+#[test]
+fn test_attributes() -> Result<(), Error> {
+    roundtrip::<Tool>("libsdp 1.2")?;
+    roundtrip::<ConferenceType>("meeting")?;
+    roundtrip::<Orient>("portrait")?;
+    roundtrip::<FrameRate>("29.97")?;
+    roundtrip::<Quality>("10")?;
+    roundtrip::<LanguageTag>("de-CH")?;
+    roundtrip::<Lang>("en")?;
+    roundtrip::<SdpLang>("en")?;
+    roundtrip::<Transport>("udp")?;
+    roundtrip::<CandidateType>("host")?;
+    roundtrip::<TcpType>("passive")?;
+    roundtrip::<CandidateAddress>("192.0.2.1")?;
+    roundtrip::<Candidate>("1 1 udp 2122260223 192.0.2.1 54321 typ host")?;
+    roundtrip::<Charset>("ISO-8859-1")?;
+    roundtrip::<Label>("1")?;
+    roundtrip::<ContentValue>("main")?;
+    roundtrip::<Content>("main,sl")?;
+    roundtrip::<CryptoSuite>("AES_CM_128_HMAC_SHA1_80")?;
+    roundtrip::<Lifetime>("2^20")?;
+    roundtrip::<Mki>("1:4")?;
+    roundtrip::<KeyParam>("inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR|2^20|1:4")?;
+    roundtrip::<Crypto>(
+        "1 AES_CM_128_HMAC_SHA1_80 inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR",
+    )?;
+    roundtrip::<Direction>("sendrecv")?;
+    roundtrip::<ExtMapId>("1")?;
+    roundtrip::<ExtMap>("1 urn:ietf:params:rtp-hdrext:ssrc-audio-level")?;
+    roundtrip::<HashFunction>("sha-256")?;
+    roundtrip::<Fmtp>("111 minptime=10;useinbandfec=1")?;
+    roundtrip::<Mid>("0")?;
+    roundtrip::<GroupSemantics>("BUNDLE")?;
+    roundtrip::<Group>("BUNDLE 0 1")?;
+    roundtrip::<IceUfrag>("8hhY")?;
+    roundtrip::<IcePwd>("asd88fgpdd777uzjYhagZg")?;
+    roundtrip::<IceOptions>("trickle")?;
+    roundtrip::<KeyMgmt>("mikey AQAFgM0XflABAAAAAAAAAAAAAAsAyONQ6gAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA")?;
+    roundtrip::<MaxPRate>("15.5")?;
+    roundtrip::<Msid>("stream track")?;
+    roundtrip::<MsidSemantic>("WMS stream")?;
+    roundtrip::<Rtcp>("53020 IN IP4 126.16.64.4")?;
+    roundtrip::<PayloadTypeRef>("*")?;
+    roundtrip::<RtcpFeedbackType>("nack")?;
+    roundtrip::<RtcpFeedback>("96 nack pli")?;
+    roundtrip::<RtpMap>("111 opus/48000/2")?;
+    roundtrip::<SctpPort>("5000")?;
+    roundtrip::<MaxMessageSize>("262144")?;
+    roundtrip::<SctpMap>("5000 webrtc-datachannel 1024")?;
+    roundtrip::<Setup>("actpass")?;
+    roundtrip::<Connection>("new")?;
+    roundtrip::<RidDirection>("send")?;
+    roundtrip::<Rid>("hi send pt=96;max-width=1280")?;
+    roundtrip::<Simulcast>("send hi;~lo")?;
+    roundtrip::<Ssrc>("1 cname:stream")?;
+    roundtrip::<SsrcGroupSemantics>("FID")?;
+    roundtrip::<SsrcGroup>("FID 1 2")?;
+    roundtrip::<Token>("token")?;
+
+    assert_eq!(Tool::new("libsdp")?.to_string(), "libsdp");
+    assert_eq!(Quality::new(5)?.to_string(), "5");
+    assert_eq!(FrameRate::from_fps(30).to_string(), "30");
+    assert_eq!(Label::try_new("1")?.to_string(), "1");
+    assert_eq!(ExtMapId::try_new(1)?.to_string(), "1");
+    assert_eq!(
+        Fingerprint::try_new(HashFunction::Sha1, vec![0xab; 20])?
+            .to_string()
+            .parse::<Fingerprint>()?
+            .value()
+            .len(),
+        20
+    );
+    assert_eq!(Fmtp::try_new("96", "apt=100")?.to_string(), "96 apt=100");
+    assert_eq!(Mid::try_new("0")?.to_string(), "0");
+    assert_eq!(IceUfrag::try_new("8hhY")?.to_string(), "8hhY");
+    assert_eq!(
+        IcePwd::try_new("asd88fgpdd777uzjYhagZg")?.to_string(),
+        "asd88fgpdd777uzjYhagZg"
+    );
+    assert!(KeyMgmt::new("mikey", b"key".to_vec())?.is_mikey());
+    assert_eq!(MaxPRate::new(155, 1)?.to_string(), "15.5");
+    assert_eq!(SctpPort::new(5000)?.to_string(), "5000");
+    assert_eq!(Token::try_new("token")?.to_string(), "token");
+
+    Ok(())
+}
+
+#[test]
+fn test_descriptions() -> Result<(), Error> {
+    let sdp = "v=0\r\n\
+o=- 4611731400430051336 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=rtpmap:111 opus/48000/2\r\n";
+    let sd = roundtrip::<SessionDescription>(sdp)?;
+    assert_eq!(sd.marshal(), sdp);
+
+    let sd = SessionDescription::new_jsep_session_description(false);
+    assert!(sd.marshal().starts_with("v=0\r\n"));
+
+    Ok(())
+}
@@ -0,0 +1,459 @@
+// Parses the offers of the major browsers, which use most of what WebRTC
+// puts into session descriptions, and checks what the crate makes of them.
+#![cfg(feature = "std")]
+
+use webrtc_rs_sdp::prelude::*;
+
+const CHROME_OFFER_SDP: &str = "v=0\r
+o=- 4611731400430051336 2 IN IP4 127.0.0.1\r
+s=-\r
+t=0 0\r
+a=group:BUNDLE 0 1 2\r
+a=extmap-allow-mixed\r
+a=msid-semantic: WMS 3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc\r
+m=audio 9 UDP/TLS/RTP/SAVPF 111 63 9 0 8 13 110 126\r
+c=IN IP4 0.0.0.0\r
+a=rtcp:9 IN IP4 0.0.0.0\r
+a=ice-ufrag:Gh2c\r
+a=ice-pwd:3zUiRr5gHbS0aBkPQVvvDbQy\r
+a=ice-options:trickle\r
+a=fingerprint:sha-256 8F:5A:1C:0E:65:3B:7D:14:0C:57:3A:31:DD:5D:80:D7:1E:70:F5:87:6C:5C:31:E2:0A:EE:D8:B8:6B:63:17:3E\r
+a=setup:actpass\r
+a=mid:0\r
+a=extmap:1 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r
+a=extmap:2 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time\r
+a=extmap:3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01\r
+a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r
+a=sendrecv\r
+a=msid:3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc 6e4ab2a4-a1a3-4a66-90c5-9b1c5d7b5ed1\r
+a=rtcp-mux\r
+a=rtpmap:111 opus/48000/2\r
+a=rtcp-fb:111 transport-cc\r
+a=fmtp:111 minptime=10;useinbandfec=1\r
+a=rtpmap:63 red/48000/2\r
+a=fmtp:63 111/111\r
+a=rtpmap:9 G722/8000\r
+a=rtpmap:0 PCMU/8000\r
+a=rtpmap:8 PCMA/8000\r
+a=rtpmap:13 CN/8000\r
+a=rtpmap:110 telephone-event/48000\r
+a=rtpmap:126 telephone-event/8000\r
+a=ssrc:1001286040 cname:2VZ7vUaxZQxyB6sR\r
+a=ssrc:1001286040 msid:3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc 6e4ab2a4-a1a3-4a66-90c5-9b1c5d7b5ed1\r
+m=video 9 UDP/TLS/RTP/SAVPF 96 97 102 103\r
+c=IN IP4 0.0.0.0\r
+a=rtcp:9 IN IP4 0.0.0.0\r
+a=ice-ufrag:Gh2c\r
+a=ice-pwd:3zUiRr5gHbS0aBkPQVvvDbQy\r
+a=ice-options:trickle\r
+a=fingerprint:sha-256 8F:5A:1C:0E:65:3B:7D:14:0C:57:3A:31:DD:5D:80:D7:1E:70:F5:87:6C:5C:31:E2:0A:EE:D8:B8:6B:63:17:3E\r
+a=setup:actpass\r
+a=mid:1\r
+a=extmap:14 urn:ietf:params:rtp-hdrext:toffset\r
+a=extmap:2 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time\r
+a=extmap:13 urn:3gpp:video-orientation\r
+a=sendrecv\r
+a=msid:3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc 1c4d8e2b-6a5f-4f87-8e0b-2f3cd3a0c3b4\r
+a=rtcp-mux\r
+a=rtcp-rsize\r
+a=rtpmap:96 VP8/90000\r
+a=rtcp-fb:96 goog-remb\r
+a=rtcp-fb:96 transport-cc\r
+a=rtcp-fb:96 ccm fir\r
+a=rtcp-fb:96 nack\r
+a=rtcp-fb:96 nack pli\r
+a=rtpmap:97 rtx/90000\r
+a=fmtp:97 apt=96\r
+a=rtpmap:102 H264/90000\r
+a=rtcp-fb:102 nack pli\r
+a=fmtp:102 level-asymmetry-allowed=1;packetization-mode=1;profile-level-id=42001f\r
+a=rtpmap:103 rtx/90000\r
+a=fmtp:103 apt=102\r
+a=ssrc-group:FID 2231627014 632943048\r
+a=ssrc:2231627014 cname:2VZ7vUaxZQxyB6sR\r
+a=ssrc:632943048 cname:2VZ7vUaxZQxyB6sR\r
+m=application 9 UDP/DTLS/SCTP webrtc-datachannel\r
+c=IN IP4 0.0.0.0\r
+a=ice-ufrag:Gh2c\r
+a=ice-pwd:3zUiRr5gHbS0aBkPQVvvDbQy\r
+a=ice-options:trickle\r
+a=fingerprint:sha-256 8F:5A:1C:0E:65:3B:7D:14:0C:57:3A:31:DD:5D:80:D7:1E:70:F5:87:6C:5C:31:E2:0A:EE:D8:B8:6B:63:17:3E\r
+a=setup:actpass\r
+a=mid:2\r
+a=sctp-port:5000\r
+a=max-message-size:262144\r
+";
+
+const FIREFOX_OFFER_SDP: &str = "v=0\r
+o=mozilla...THIS_IS_SDPARTA-99.0 7178811405463316015 0 IN IP4 0.0.0.0\r
+s=-\r
+t=0 0\r
+a=fingerprint:sha-256 4E:2D:3A:6B:8C:1F:90:55:AB:CD:12:34:56:78:9A:BC:DE:F0:11:22:33:44:55:66:77:88:99:AA:BB:CC:DD:EE\r
+a=group:BUNDLE 0 1\r
+a=ice-options:trickle\r
+a=msid-semantic:WMS *\r
+m=audio 9 UDP/TLS/RTP/SAVPF 109 9 0 8 101\r
+c=IN IP4 0.0.0.0\r
+a=sendrecv\r
+a=extmap:1 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r
+a=extmap:2/recvonly urn:ietf:params:rtp-hdrext:csrc-audio-level\r
+a=extmap:3 urn:ietf:params:rtp-hdrext:sdes:mid\r
+a=fmtp:109 maxplaybackrate=48000;stereo=1;useinbandfec=1\r
+a=fmtp:101 0-15\r
+a=ice-pwd:b0bd6a4f8c1a4a7a3f7b7ca2b0b8d7a1\r
+a=ice-ufrag:5a2d1f8b\r
+a=mid:0\r
+a=msid:{1bc87a5a-0e4e-4a31-9d39-3c0a7bd3e1f3} {9a0c4a1e-52f8-4e3c-b0ea-8a7b3c1b2d4f}\r
+a=rtcp-mux\r
+a=rtpmap:109 opus/48000/2\r
+a=rtpmap:9 G722/8000/1\r
+a=rtpmap:0 PCMU/8000\r
+a=rtpmap:8 PCMA/8000\r
+a=rtpmap:101 telephone-event/8000/1\r
+a=setup:actpass\r
+a=ssrc:2655508255 cname:{4e7b3c2a-1d5f-4b8e-9a6c-0f1e2d3c4b5a}\r
+m=video 9 UDP/TLS/RTP/SAVPF 120 124 121 125 126 127 97 98\r
+c=IN IP4 0.0.0.0\r
+a=sendrecv\r
+a=extmap:3 urn:ietf:params:rtp-hdrext:sdes:mid\r
+a=extmap:4 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time\r
+a=extmap:5 urn:ietf:params:rtp-hdrext:toffset\r
+a=extmap:6/recvonly http://www.webrtc.org/experiments/rtp-hdrext/playout-delay\r
+a=extmap:7 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01\r
+a=fmtp:126 profile-level-id=42e01f;level-asymmetry-allowed=1;packetization-mode=1\r
+a=fmtp:97 profile-level-id=42e01f;level-asymmetry-allowed=1\r
+a=fmtp:120 max-fs=12288;max-fr=60\r
+a=fmtp:124 apt=120\r
+a=fmtp:121 max-fs=12288;max-fr=60\r
+a=fmtp:125 apt=121\r
+a=fmtp:127 apt=126\r
+a=fmtp:98 apt=97\r
+a=ice-pwd:b0bd6a4f8c1a4a7a3f7b7ca2b0b8d7a1\r
+a=ice-ufrag:5a2d1f8b\r
+a=mid:1\r
+a=msid:{1bc87a5a-0e4e-4a31-9d39-3c0a7bd3e1f3} {2f7c1e3b-8d4a-4b6c-a5e9-1c3b5d7f9a0e}\r
+a=rtcp-fb:120 nack\r
+a=rtcp-fb:120 nack pli\r
+a=rtcp-fb:120 ccm fir\r
+a=rtcp-fb:120 goog-remb\r
+a=rtcp-fb:120 transport-cc\r
+a=rtcp-mux\r
+a=rtcp-rsize\r
+a=rtpmap:120 VP8/90000\r
+a=rtpmap:124 rtx/90000\r
+a=rtpmap:121 VP9/90000\r
+a=rtpmap:125 rtx/90000\r
+a=rtpmap:126 H264/90000\r
+a=rtpmap:127 rtx/90000\r
+a=rtpmap:97 H264/90000\r
+a=rtpmap:98 rtx/90000\r
+a=setup:actpass\r
+a=ssrc:3735928559 cname:{4e7b3c2a-1d5f-4b8e-9a6c-0f1e2d3c4b5a}\r
+a=ssrc:3735928560 cname:{4e7b3c2a-1d5f-4b8e-9a6c-0f1e2d3c4b5a}\r
+a=ssrc-group:FID 3735928559 3735928560\r
+";
+
+const BROWSER_OFFERS: &[(&str, &str)] =
+    &[("chrome", CHROME_OFFER_SDP), ("firefox", FIREFOX_OFFER_SDP)];
+
+#[test]
+fn test_browser_offers() -> Result<(), Error> {
+    let sd = CHROME_OFFER_SDP.parse::<SessionDescription>()?;
+    assert_eq!(sd.origin.session_id, 4611731400430051336);
+    assert_eq!(
+        sd.attributes.get_first("group").and_then(|a| a.value()),
+        Some("BUNDLE 0 1 2")
+    );
+    assert_eq!(sd.media_descriptions.len(), 3);
+    assert_eq!(
+        sd.media_descriptions[1]
+            .attributes
+            .get_all("rtcp-fb")
+            .count(),
+        6
+    );
+    assert_eq!(
+        sd.media_descriptions[2].media_name.protocol()?,
+        Protocol::UdpDtlsSctp
+    );
+    assert_eq!(sd.marshal(), CHROME_OFFER_SDP);
+
+    let sd = FIREFOX_OFFER_SDP.parse::<SessionDescription>()?;
+    assert_eq!(sd.origin.username, "mozilla...THIS_IS_SDPARTA-99.0");
+    assert_eq!(sd.media_descriptions.len(), 2);
+    assert_eq!(
+        sd.media_descriptions[1]
+            .attributes
+            .get_all("rtpmap")
+            .count(),
+        8
+    );
+    assert_eq!(sd.marshal(), FIREFOX_OFFER_SDP);
+
+    Ok(())
+}
+
+#[test]
+fn test_browser_offers_round_trip() -> Result<(), Error> {
+    for &(name, offer) in BROWSER_OFFERS {
+        let sd = offer.parse::<SessionDescription>()?;
+        assert_eq!(sd.to_bytes(), offer.as_bytes(), "{}", name);
+        assert_eq!(sd.marshal_len_hint(), offer.len(), "{}", name);
+        assert!(sd.check_line_breaks().is_ok(), "{}", name);
+
+        let mut buf = "prefix\r\n".to_owned();
+        sd.marshal_into(&mut buf);
+        assert_eq!(buf, format!("prefix\r\n{}", offer), "{}", name);
+        for md in &sd.media_descriptions {
+            assert_eq!(md.marshal_len_hint(), md.to_string().len(), "{}", name);
+        }
+
+        let borrowed = SessionDescription::parse_borrowed(offer)?;
+        assert_eq!(borrowed.to_owned(), sd, "{}", name);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_browser_offers_line_endings() -> Result<(), Error> {
+    // Bare "\n" line breaks are written back, unless they are normalized.
+    let bare = CHROME_OFFER_SDP.replace("\r\n", "\n");
+    let mut sd = bare.parse::<SessionDescription>()?;
+    assert_eq!(sd.line_ending, LineEnding::Lf);
+    assert_eq!(sd.marshal(), bare);
+    assert_eq!(sd.marshal_len_hint(), bare.len());
+    let borrowed = SessionDescription::parse_borrowed(&bare)?;
+    assert_eq!(borrowed.to_owned().marshal(), bare);
+    sd.line_ending = LineEnding::CrLf;
+    assert_eq!(sd.marshal(), CHROME_OFFER_SDP);
+
+    let padded = CHROME_OFFER_SDP.replace("\r\n", " \t\r\n");
+    let sd = padded.parse::<SessionDescription>()?;
+    assert_eq!(sd.marshal(), CHROME_OFFER_SDP);
+
+    Ok(())
+}
+
+#[test]
+fn test_bundle_groups() -> Result<(), Error> {
+    let mut sd = CHROME_OFFER_SDP.parse::<SessionDescription>()?;
+
+    let groups = sd.bundle_groups();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].to_string(), "BUNDLE 0 1 2");
+    for (md, mid) in sd.media_descriptions.iter().zip(&groups[0].mids) {
+        assert_eq!(md.mid().as_ref(), Some(mid));
+        assert!(sd.is_bundled(mid.as_str()));
+    }
+    assert!(!sd.is_bundled("3"));
+    assert!(sd.validate().is_empty());
+
+    sd.media_descriptions[2].set_mid("data".parse()?);
+    let diagnostics: Vec<_> = sd
+        .validate()
+        .into_iter()
+        .map(|d| (d.kind, d.media_index))
+        .collect();
+    assert_eq!(
+        diagnostics,
+        vec![
+            (DiagnosticKind::UnknownGroupMid, None),
+            (DiagnosticKind::UngroupedMid, Some(2)),
+        ]
+    );
+
+    sd.media_descriptions[2].set_mid("an-overly-long-mid".parse()?);
+    assert!(sd
+        .validate()
+        .iter()
+        .any(|d| d.kind == DiagnosticKind::LongMid && d.media_index == Some(2)));
+
+    Ok(())
+}
+
+#[test]
+fn test_two_byte_extmaps() -> Result<(), Error> {
+    let two_byte_kinds = |sd: &SessionDescription| -> Vec<_> {
+        sd.validate()
+            .into_iter()
+            .filter(|d| d.kind == DiagnosticKind::TwoByteExtMapWithoutAllowMixed)
+            .map(|d| d.media_index)
+            .collect()
+    };
+
+    let mut sd = CHROME_OFFER_SDP.parse::<SessionDescription>()?;
+    assert!(sd.extmap_allow_mixed());
+    sd.media_descriptions[0].attributes.push(Attribute::new(
+        "extmap".to_owned(),
+        Some("16 urn:example:a".to_owned()),
+    ));
+    sd.attributes.push(Attribute::new(
+        "extmap".to_owned(),
+        Some("4096 urn:example:b".to_owned()),
+    ));
+    assert!(two_byte_kinds(&sd).is_empty());
+
+    sd.attributes.remove_all::<ExtmapAllowMixed>();
+    assert_eq!(two_byte_kinds(&sd), vec![None, Some(0)]);
+
+    // Allowing it in every media description covers the session level.
+    for md in &mut sd.media_descriptions {
+        md.attributes.insert(ExtmapAllowMixed);
+    }
+    assert!(two_byte_kinds(&sd).is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_msid() -> Result<(), Error> {
+    let chrome = CHROME_OFFER_SDP.parse::<SessionDescription>()?;
+    assert_eq!(
+        chrome.wms_stream_ids(),
+        Some(vec!["3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc".to_owned()])
+    );
+    let msid = chrome.media_descriptions[1].msid().unwrap();
+    assert_eq!(
+        msid.stream_id.as_deref(),
+        Some("3HjkHtYq4PQwe0wbqQT5mnKnrMqG2S8xZpbc")
+    );
+    assert_eq!(
+        msid.track_id.as_deref(),
+        Some("1c4d8e2b-6a5f-4f87-8e0b-2f3cd3a0c3b4")
+    );
+    assert_eq!(chrome.media_descriptions[2].msid(), None);
+    assert!(chrome.validate().is_empty());
+
+    let firefox = FIREFOX_OFFER_SDP.parse::<SessionDescription>()?;
+    assert_eq!(firefox.wms_stream_ids(), Some(vec!["*".to_owned()]));
+    assert!(firefox
+        .validate()
+        .iter()
+        .all(|d| d.kind != DiagnosticKind::MsidStreamNotInWms));
+
+    // Modern offers only carry per-media msid.
+    let mut modern = chrome.clone();
+    modern.attributes.remove_all::<MsidSemantic>();
+    assert_eq!(modern.wms_stream_ids(), None);
+    assert!(modern.validate().is_empty());
+
+    // Older Chrome listed every stream at session level.
+    let mut legacy = chrome;
+    legacy
+        .attributes
+        .replace("WMS other".parse::<MsidSemantic>()?);
+    let diagnostics: Vec<_> = legacy
+        .validate()
+        .into_iter()
+        .map(|d| (d.kind, d.severity, d.media_index))
+        .collect();
+    assert_eq!(
+        diagnostics,
+        vec![
+            (
+                DiagnosticKind::MsidStreamNotInWms,
+                Severity::Warning,
+                Some(0)
+            ),
+            (
+                DiagnosticKind::MsidStreamNotInWms,
+                Severity::Warning,
+                Some(1)
+            ),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_datachannel() -> Result<(), Error> {
+    const LEGACY_DATACHANNEL_SDP: &str = "v=0\r\n\
+o=- 0 0 IN IP4 0.0.0.0\r\n\
+s=-\r\n\
+t=0 0\r\n\
+m=application 9 DTLS/SCTP 5000\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=sctpmap:5000 webrtc-datachannel 1024\r\n\
+m=application 9 DTLS/SCTP 5001\r\n\
+c=IN IP4 0.0.0.0\r\n";
+
+    let chrome = CHROME_OFFER_SDP.parse::<SessionDescription>()?;
+    let datachannel = &chrome.media_descriptions[2];
+    assert!(datachannel.is_datachannel());
+    assert_eq!(datachannel.sctp_port(), Some(5000));
+    assert_eq!(
+        datachannel
+            .attributes
+            .get::<MaxMessageSize>()
+            .next()
+            .transpose()?,
+        Some(MaxMessageSize(262144))
+    );
+    assert!(!chrome.media_descriptions[0].is_datachannel());
+    assert_eq!(chrome.media_descriptions[0].sctp_port(), None);
+
+    let legacy = LEGACY_DATACHANNEL_SDP.parse::<SessionDescription>()?;
+    assert!(legacy.media_descriptions[0].is_datachannel());
+    assert_eq!(legacy.media_descriptions[0].sctp_port(), Some(5000));
+    // Without sctpmap, a legacy section can't be told apart from other
+    // SCTP applications.
+    assert!(!legacy.media_descriptions[1].is_datachannel());
+
+    let mut md = legacy.media_descriptions[0].clone();
+    md.media_name.formats = vec!["5002".to_owned()];
+    md.attributes.remove_all::<SctpMap>();
+    md.attributes.insert(SctpMap {
+        port: 5002,
+        app: "webrtc-datachannel".to_owned(),
+        max_streams: None,
+    });
+    assert_eq!(md.sctp_port(), Some(5002));
+    md.attributes.insert(SctpPort(5003));
+    assert_eq!(md.sctp_port(), Some(5003));
+
+    Ok(())
+}
+
+#[test]
+fn test_reject_media() -> Result<(), Error> {
+    let mut sd = CHROME_OFFER_SDP.parse::<SessionDescription>()?;
+    sd.reject_media("1")?;
+
+    assert_eq!(sd.bundle_groups()[0].to_string(), "BUNDLE 0 2");
+    assert!(!sd.is_bundled("1"));
+
+    let md = &sd.media_descriptions[1];
+    assert!(md.is_rejected());
+    assert_eq!(md.media_name.to_string(), "video 0 UDP/TLS/RTP/SAVPF 96");
+    assert_eq!(md.mid().map(|m| m.to_string()), Some("1".to_owned()));
+    assert_eq!(md.direction(), Some(Direction::Inactive));
+    assert!(md.ssrcs().is_empty());
+    assert!(md.attribute("ice-ufrag").is_none());
+    assert_eq!(
+        md.attributes
+            .iter()
+            .map(|a| a.to_string())
+            .collect::<Vec<_>>(),
+        vec!["mid:1", "rtpmap:96 VP8/90000", "inactive"]
+    );
+    assert!(!sd.media_descriptions[0].is_rejected());
+    assert!(sd.validate().is_empty());
+
+    // The rejected section survives a round trip, even without "c=".
+    sd.media_descriptions[1].connection_information = None;
+    let reparsed = sd.marshal().parse::<SessionDescription>()?;
+    assert_eq!(reparsed, sd);
+    assert!(reparsed.validate().is_empty());
+
+    sd.reject_media("0")?;
+    sd.reject_media("2")?;
+    assert!(sd.bundle_groups().is_empty());
+    assert!(matches!(
+        sd.reject_media("3"),
+        Err(Error::SdpMidNotFound(ref mid)) if mid == "3"
+    ));
+
+    Ok(())
+}
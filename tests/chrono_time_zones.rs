@@ -0,0 +1,81 @@
+// Builds the "z=" line of the New York lecture example of RFC 4566 from the
+// daylight saving transitions of its time zone, and checks that the weekly
+// lecture keeps its local time across them.
+// <https://tools.ietf.org/html/rfc4566#section-5.11>
+#![cfg(feature = "chrono")]
+
+use chrono::{DateTime, TimeZone as _, Utc};
+use webrtc_rs_sdp::session_description::{Offset, SessionDescription, Time, TimeZones, Timing};
+use webrtc_rs_sdp::Error;
+
+fn utc(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, hour, 0, 0)
+        .single()
+        .expect("valid date")
+}
+
+fn ntp(datetime: DateTime<Utc>) -> u64 {
+    Time::from_datetime(datetime).map_or(0, Time::as_secs)
+}
+
+#[test]
+fn test_new_york_lecture() -> Result<(), Error> {
+    let eastern_standard = Offset::from_secs(-5 * 3600);
+    let eastern_daylight = Offset::from_secs(-4 * 3600);
+    // The adjustment times of the example, which the RFC uses as the
+    // daylight saving transitions.
+    let spring = Time::from_secs(2882844526).to_datetime().expect("in range");
+    let autumn = Time::from_secs(2898848070).to_datetime().expect("in range");
+    let transitions = [
+        (utc(1990, 10, 28, 6), eastern_standard),
+        (spring, eastern_daylight),
+        (autumn, eastern_standard),
+    ];
+
+    // Every Sunday at 10h, New York time, for an hour, during 1991.
+    let timing = Timing::new(ntp(utc(1991, 1, 20, 15)), ntp(utc(1991, 12, 31, 0)))?;
+    let time_zones = TimeZones::from_transitions(&timing, &transitions)?;
+    assert_eq!(
+        format!("z={}", time_zones.to_compact_string()),
+        "z=2882844526 -1h 2898848070 0"
+    );
+
+    let sd = format!(
+        "v=0\r\n\
+         o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
+         s=Lecture\r\n\
+         t={} {}\r\n\
+         r=7d 1h 0\r\n\
+         z={}\r\n",
+        timing.start_time,
+        timing.stop_time,
+        time_zones.to_compact_string()
+    )
+    .parse::<SessionDescription>()?;
+    assert_eq!(sd.time_zones, time_zones);
+
+    // 15h UTC in standard time, 14h UTC in daylight saving time.
+    assert_eq!(
+        sd.occurrences_between(utc(1991, 5, 1, 0), utc(1991, 5, 20, 0)),
+        vec![
+            (utc(1991, 5, 5, 15), utc(1991, 5, 5, 16)),
+            (utc(1991, 5, 12, 14), utc(1991, 5, 12, 15)),
+            (utc(1991, 5, 19, 14), utc(1991, 5, 19, 15)),
+        ]
+    );
+    assert_eq!(
+        sd.occurrences_between(utc(1991, 11, 10, 0), utc(1991, 11, 18, 0)),
+        vec![
+            (utc(1991, 11, 10, 14), utc(1991, 11, 10, 15)),
+            (utc(1991, 11, 17, 15), utc(1991, 11, 17, 16)),
+        ]
+    );
+
+    // An open ended lecture series can't have its transitions listed.
+    assert!(matches!(
+        TimeZones::from_transitions(&Timing::new(timing.start_time, 0)?, &transitions),
+        Err(Error::SdpUnboundedSession)
+    ));
+
+    Ok(())
+}
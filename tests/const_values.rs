@@ -0,0 +1,38 @@
+// Checks that well-known values can be declared as constants and statics
+// outside the crate; this fails to compile otherwise.
+
+use webrtc_rs_sdp::prelude::*;
+
+static PERMANENT: Timing = Timing::PERMANENT;
+const LECTURE: Timing = Timing {
+    start_time: 3034423619,
+    stop_time: 3042462419,
+};
+
+// PAYLOAD_TABLE pairs static RTP payload types with their packet time.
+// https://tools.ietf.org/html/rfc3551#section-6
+static PAYLOAD_TABLE: [(u8, PTime); 3] = [(0, PTime(20)), (8, PTime(20)), (9, PTime(20))];
+
+const DATACHANNEL_PORT: SctpPort = SctpPort::new_unchecked(5000);
+const AUDIO_LEVEL_ID: ExtMapId = ExtMapId::new_unchecked(1);
+const MEDIA_PORT: Port = Port::new(9);
+
+const _: () = assert!(Timing::PERMANENT.is_permanent() && !LECTURE.is_unbounded());
+
+#[test]
+fn test_const_values() -> Result<(), Error> {
+    assert_eq!(PERMANENT, Timing::permanent());
+    assert_eq!(PERMANENT.to_string(), "0 0");
+    assert_eq!(LECTURE.duration(), Some(8038800));
+    assert!(LECTURE.active_at(3034423619));
+
+    assert!(PAYLOAD_TABLE
+        .iter()
+        .all(|(_, ptime)| ptime.as_millis() == 20));
+    assert_eq!(DATACHANNEL_PORT, "5000".parse()?);
+    assert_eq!(AUDIO_LEVEL_ID, ExtMapId::try_new(1)?);
+    assert_eq!(MEDIA_PORT.rtcp_port(), Some(10));
+    assert!(MaxMessageSize::UNLIMITED.is_unlimited());
+
+    Ok(())
+}
@@ -0,0 +1,257 @@
+// Every public type written as an SDP field or token parses back from its
+// own Display output and rejects malformed input. New types with a Display
+// and a FromStr belong here as well.
+
+use std::convert::TryFrom;
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+use webrtc_rs_sdp::candidate::{CandidateAddress, CandidateType, TcpType, Transport};
+use webrtc_rs_sdp::prelude::*;
+use webrtc_rs_sdp::util::token::Token;
+use webrtc_rs_sdp::util::ConnectionRole;
+
+fn check<T>(valid: &[&str], invalid: &[&str])
+where
+    T: FromStr + Display + PartialEq + Debug,
+    T::Err: Debug,
+{
+    let name = core::any::type_name::<T>();
+    for value in valid {
+        let parsed = value
+            .parse::<T>()
+            .unwrap_or_else(|err| panic!("{}: {:?}: {:?}", name, value, err));
+        let text = parsed.to_string();
+        let reparsed = text
+            .parse::<T>()
+            .unwrap_or_else(|err| panic!("{}: {:?}: {:?}", name, text, err));
+        assert_eq!(reparsed, parsed, "{}: {:?}", name, value);
+    }
+    assert!(!invalid.is_empty(), "{}: no invalid input", name);
+    for value in invalid {
+        assert!(value.parse::<T>().is_err(), "{}: {:?}", name, value);
+    }
+}
+
+// check_try_from checks that TryFrom<&str> and TryFrom<String> agree with
+// FromStr.
+fn check_try_from<T>(valid: &[&str], invalid: &[&str])
+where
+    T: FromStr + for<'a> TryFrom<&'a str> + TryFrom<String> + PartialEq + Debug,
+{
+    let name = core::any::type_name::<T>();
+    for value in valid {
+        let parsed = value.parse::<T>().ok();
+        assert!(parsed.is_some(), "{}: {:?}", name, value);
+        assert!(T::try_from(*value).ok() == parsed, "{}: {:?}", name, value);
+        assert!(
+            T::try_from((*value).to_owned()).ok() == parsed,
+            "{}: {:?}",
+            name,
+            value
+        );
+    }
+    for value in invalid {
+        assert!(T::try_from(*value).is_err(), "{}: {:?}", name, value);
+        assert!(
+            T::try_from((*value).to_owned()).is_err(),
+            "{}: {:?}",
+            name,
+            value
+        );
+    }
+}
+
+#[test]
+fn test_from_str_session_fields() {
+    check::<Version>(&["0"], &["1", "", "x"]);
+    check::<Origin>(
+        &[
+            "- 4611731400430051336 2 IN IP4 127.0.0.1",
+            "jdoe 1 1 IN IP6 ::1",
+        ],
+        &["- 1 1 IN IP4", "- x 1 IN IP4 127.0.0.1"],
+    );
+    check::<Username>(&["-", "jdoe"], &["", "j doe"]);
+    check::<SessionName>(&["-", "SDP Seminar"], &["a\r\nb"]);
+    check::<Information>(
+        &["A Seminar on the session description protocol"],
+        &["a\nb"],
+    );
+    check::<Uri>(
+        &["http://www.example.com/seminars/sdp.pdf"],
+        &["not a uri", "/relative"],
+    );
+    check::<EmailAddress>(
+        &[
+            "j.doe@example.com (Jane Doe)",
+            "Jane Doe <j.doe@example.com>",
+        ],
+        &["j.doe", "j.doe@example.com\r\nk=clear:x"],
+    );
+    check::<PhoneNumber>(&["+1 617 555-6011", "Jane Doe <+1 617 555-6011>"], &["617"]);
+    check::<ConnectionInformation>(
+        &["IN IP4 224.2.36.42/127/3", "IN IP6 ::1"],
+        &["IN IP6 10.47.16.5", "IN"],
+    );
+    check::<Address>(
+        &[
+            "224.2.1.1/127/3",
+            "ff15::103/3",
+            "192.0.2.1",
+            "host.example.com",
+        ],
+        &["224.2.1.1", "192.0.2.1/127", "", "::1/2"],
+    );
+    check::<Bandwidth>(&["AS:128", "TIAS:64000", "X-YZ:1"], &["AS", "AS:x", ":1"]);
+    check::<BandwidthType>(&["AS", "YZ"], &["", "A S"]);
+    check::<BandwidthKind>(&["CT", "AS", "TIAS", "RR", "RS"], &["X-AS", "YZ", "as"]);
+    check::<Timing>(&["0 0", "3034423619 3042462419"], &["1", "2 1"]);
+    check::<RepeatTime>(
+        &["604800 3600 0 90000", "7d 1h 0 25h"],
+        &["604800", "x 1 0"],
+    );
+    check::<TimeZone>(&["2882844526 -1h"], &["2882844526"]);
+    check::<TimeZones>(&["2882844526 -1h 2898848070 0"], &["2882844526", ""]);
+    check::<EncryptionKey>(
+        &[
+            "prompt",
+            "clear:secret",
+            "base64:c2VjcmV0",
+            "uri:https://example.com/key",
+        ],
+        &["secret", "base64:!"],
+    );
+    check::<MediaName>(
+        &["audio 9 UDP/TLS/RTP/SAVPF 111", "video 49170/2 RTP/AVP 31"],
+        &["audio 9", "audio x RTP/AVP 0"],
+    );
+    check::<MediaType>(&["audio", "application"], &["", "au dio"]);
+    check::<Port>(&["9", "49170/2"], &["x", "70000", "9/x"]);
+    check::<Protocol>(&["RTP/AVP", "UDP/DTLS/SCTP"], &["", "RTP AVP"]);
+    check::<Attribute>(&["sendrecv", "rtpmap:111 opus/48000/2"], &["", "bad name"]);
+    check::<Token>(&["AS", "UDP"], &["", "a b"]);
+    check::<SessionDescription>(
+        &["v=0\r\no=- 1 1 IN IP4 0.0.0.0\r\ns=-\r\nt=0 0\r\n"],
+        &["", "v=0\r\n"],
+    );
+}
+
+#[test]
+fn test_from_str_attributes() {
+    check::<Direction>(&["sendrecv", "inactive"], &["send"]);
+    check::<Rtcp>(&["9 IN IP4 0.0.0.0", "53020"], &["x"]);
+    check::<RtpMap>(
+        &["111 opus/48000/2", "96 VP8/90000"],
+        &["111", "x opus/48000"],
+    );
+    check::<Fmtp>(&["111 minptime=10;useinbandfec=1"], &[""]);
+    check::<RtcpFeedback>(&["96 nack pli", "* transport-cc"], &["96", "x nack"]);
+    check::<RtcpFeedbackType>(&["nack", "transport-cc"], &[""]);
+    check::<PayloadTypeRef>(&["96", "*"], &["x"]);
+    check::<ExtMap>(
+        &[
+            "1 urn:ietf:params:rtp-hdrext:ssrc-audio-level",
+            "2/sendonly urn:x",
+        ],
+        &["0 urn:x", "1"],
+    );
+    check::<ExtMapId>(&["1", "255"], &["0", "256"]);
+    check::<KnownExtension>(&["urn:ietf:params:rtp-hdrext:sdes:mid"], &["urn:x"]);
+    check::<Fingerprint>(
+        &["sha-256 19:E2:1C:3B:4B:9F:81:E6:B8:5C:F4:A5:A8:D8:73:04:BB:05:2F:70:9F:04:A9:0E:05:E9:26:33:E8:70:88:A2"],
+        &["sha-256", "sha-256 zz"],
+    );
+    check::<HashFunction>(&["sha-256", "sha-1"], &[""]);
+    check::<Setup>(&["actpass", "active", "passive", "holdconn"], &["server"]);
+    check::<Connection>(&["new", "existing"], &["old"]);
+    check::<ConnectionRole>(&["actpass", "active", "passive", "holdconn"], &["server"]);
+    check::<IceUfrag>(&["EsAw"], &["abc", "a b c d"]);
+    check::<IcePwd>(&["P2uYro0UCOQ4zxjKXaWCBui1"], &["short"]);
+    check::<IceOptions>(&["trickle", "trickle ice2"], &[""]);
+    check::<Candidate>(
+        &[
+            "1 1 udp 2122260223 192.168.0.196 46243 typ host generation 0",
+            "2 1 tcp 1518280447 47.61.61.61 9 typ srflx raddr 192.168.0.196 rport 0 tcptype active",
+        ],
+        &["1 1 udp", "1 0 udp 1 192.0.2.1 9 typ host"],
+    );
+    check::<CandidateAddress>(&["192.0.2.1", "::1", "4c0ff2bd.local"], &[""]);
+    check::<CandidateType>(&["host", "srflx", "prflx", "relay"], &["x"]);
+    check::<TcpType>(&["active", "passive", "so"], &["x"]);
+    check::<Transport>(&["udp", "tcp"], &[""]);
+    check::<RemoteCandidate>(&["1 192.0.2.3 45664"], &["1 192.0.2.3", "0 192.0.2.3 1"]);
+    check::<RemoteCandidates>(&["1 192.0.2.3 45664 2 192.0.2.3 45665"], &["", "1 2"]);
+    check::<Mid>(&["0", "audio"], &["", "a b"]);
+    check::<Group>(&["BUNDLE 0 1", "LS"], &[""]);
+    check::<GroupSemantics>(&["BUNDLE", "FID"], &[""]);
+    check::<Msid>(&["stream track", "stream"], &["", "a b c"]);
+    check::<MsidSemantic>(&["WMS stream", "WMS *"], &[""]);
+    check::<Ssrc>(
+        &["1001 cname:Yk0BqBuFWnSdTSyW", "1001 msid:stream track"],
+        &["x cname:a", ""],
+    );
+    check::<SsrcGroup>(&["FID 1001 1002"], &["FID x", ""]);
+    check::<SsrcGroupSemantics>(&["FID", "SIM"], &[""]);
+    check::<Rid>(&["q send", "h recv pt=96;max-width=1280"], &["q", "q both"]);
+    check::<RidDirection>(&["send", "recv"], &["both"]);
+    check::<Simulcast>(
+        &["send hi;mid;~lo", "send a recv b,c"],
+        &["send", "send a send b"],
+    );
+    check::<SimulcastRid>(&["hi", "~lo"], &["", "~", "a;b"]);
+    check::<SctpPort>(&["5000"], &["x", "0"]);
+    check::<MaxMessageSize>(&["262144", "0"], &["x", "-1"]);
+    check::<SctpMap>(
+        &["5000 webrtc-datachannel 1024"],
+        &["5000", "x webrtc-datachannel"],
+    );
+    check::<Crypto>(
+        &["1 AES_CM_128_HMAC_SHA1_80 inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR|2^20|1:4"],
+        &[
+            "1 AES_CM_128_HMAC_SHA1_80",
+            "x AES_CM_128_HMAC_SHA1_80 inline:a",
+        ],
+    );
+    check::<CryptoSuite>(&["AES_CM_128_HMAC_SHA1_80"], &[""]);
+    check::<KeyParam>(
+        &["inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR|2^20|1:4"],
+        &["PS1u"],
+    );
+    check::<Lifetime>(&["2^20", "1048576"], &["2^", "x"]);
+    check::<Mki>(&["1:4"], &["1", "x:4"]);
+    check::<KeyMgmt>(&["mikey AQAFgM0XflABAAAAAAAAAAAAAAsA"], &["mikey", ""]);
+    check::<Label>(&["1"], &["", "a b"]);
+    check::<Content>(&["main", "slides,speaker"], &[""]);
+    check::<ContentValue>(&["main", "x-ext"], &[""]);
+    check::<MaxPRate>(&["20", "12.5"], &["x", "-1"]);
+}
+
+#[test]
+fn test_from_str_basic_attributes() {
+    check::<Tool>(&["libwebrtc 1.0"], &["", "a\nb"]);
+    check::<ConferenceType>(&["broadcast", "meeting"], &[""]);
+    check::<Charset>(&["ISO-8859-1", "UTF-8"], &[""]);
+    check::<SdpLang>(&["en", "de-CH"], &["", "en us"]);
+    check::<Lang>(&["en", "de-CH"], &["", "en us"]);
+    check::<LanguageTag>(&["en", "de-CH"], &["", "en us"]);
+    check::<Orient>(&["portrait", "landscape", "seascape"], &["upside-down"]);
+    check::<FrameRate>(&["30", "29.97"], &["x", "-1"]);
+    check::<Quality>(&["0", "10"], &["11", "x"]);
+}
+
+#[test]
+fn test_try_from_str() {
+    check_try_from::<Token>(&["AS"], &["", "a b"]);
+    check_try_from::<Username>(&["jdoe"], &["", "j doe"]);
+    check_try_from::<SessionName>(&["-"], &["a\r\nb"]);
+    check_try_from::<Information>(&["A Seminar"], &["a\nb"]);
+    check_try_from::<BandwidthType>(&["AS"], &["", "A S"]);
+    check_try_from::<EncryptionKey>(&["prompt", "clear:secret"], &["secret"]);
+    check_try_from::<Mid>(&["0"], &["", "a b"]);
+    check_try_from::<Label>(&["1"], &["", "a b"]);
+    check_try_from::<Tool>(&["libwebrtc 1.0"], &["", "a\nb"]);
+    check_try_from::<LanguageTag>(&["de-CH"], &["", "en us"]);
+    check_try_from::<IceUfrag>(&["EsAw"], &["abc"]);
+    check_try_from::<IcePwd>(&["P2uYro0UCOQ4zxjKXaWCBui1"], &["short"]);
+}
@@ -0,0 +1,221 @@
+// Feeds mutated copies of valid fields and session descriptions to the
+// parsers. The seed is fixed, so failures are reproducible; the cargo-fuzz
+// targets in fuzz/ explore further.
+#![cfg(feature = "std")]
+
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use webrtc_rs_sdp::prelude::*;
+
+const ITERATIONS: usize = 5000;
+
+const FIELDS: &[&str] = &[
+    "jdoe 2890844526 2890842807 IN IP4 10.47.16.5",
+    "AS:128",
+    "2873397496 2873404696",
+    "604800 3600 0 90000",
+    "7d 1h 0 25h",
+    "2882844526 -1h 2898848070 0",
+    "IN IP4 224.2.17.12/127/3",
+    "IN IP6 ff15::101/3",
+    "1 urn:ietf:params:rtp-hdrext:sdes:mid",
+    "2/sendrecv urn:ietf:params:rtp-hdrext:toffset ext",
+    "842083199 1 udp 2122260223 192.168.1.2 53187 typ host generation 0",
+    "1 1 tcp 1518280447 192.0.2.1 9 typ srflx raddr 10.0.0.1 rport 9 tcptype active",
+    "96 VP8/90000",
+    "111 opus/48000/2",
+    "96 minptime=10;useinbandfec=1",
+    "audio 49170/2 RTP/AVP 0 96",
+    "sha-256 4A:AD:B9:B1:3F:82:18:3B:54:02:12:DF:3E:5D:49:6B:19:E5:7C:AB",
+    "1234 cname:foo",
+    "FID 1 2",
+    "stream track",
+    " WMS a b",
+    "* nack pli",
+    "hi send pt=96,97;max-width=1280",
+    "send 1,~2;3 recv 4",
+    "5000 webrtc-datachannel 1024",
+    "53020 IN IP4 126.16.64.4",
+    "BUNDLE 0 1",
+    "clear:secret",
+    "20.5",
+    "http://www.example.com/seminars/sdp.pdf",
+    "j.doe@example.com (Jane Doe)",
+    "+1 617 555-6011",
+];
+
+const SESSION_DESCRIPTIONS: &[&str] = &[
+    "v=0\r\n\
+     o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
+     s=SDP Seminar\r\n\
+     i=A Seminar\r\n\
+     u=http://www.example.com/seminars/sdp.pdf\r\n\
+     e=j.doe@example.com (Jane Doe)\r\n\
+     p=+1 617 555-6011\r\n\
+     c=IN IP4 224.2.17.12/127\r\n\
+     b=X-YZ:128\r\n\
+     t=2873397496 2873404696\r\n\
+     r=7d 1h 0 25h\r\n\
+     z=2882844526 -1h 2898848070 0\r\n\
+     k=clear:abc\r\n\
+     a=recvonly\r\n\
+     m=audio 49170 RTP/AVP 0\r\n\
+     i=Vivamus\r\n\
+     c=IN IP4 203.0.113.1\r\n\
+     b=AS:64\r\n\
+     k=prompt\r\n\
+     a=rtpmap:0 PCMU/8000\r\n\
+     m=video 51372/2 RTP/AVP 99\r\n\
+     a=rtpmap:99 h263-1998/90000\r\n",
+    "v=0\n\
+     o=- 0 0 IN IP6 ::1\n\
+     s=-\n\
+     t=0 0\n\
+     a=group:BUNDLE 0\n\
+     m=application 9 UDP/DTLS/SCTP webrtc-datachannel\n\
+     a=mid:0\n\
+     a=sctp-port:5000\n",
+];
+
+const TOKENS: &[&str] = &[
+    "",
+    " ",
+    "/",
+    ":",
+    ";",
+    ",",
+    "=",
+    "-",
+    "~",
+    "*",
+    "0",
+    "9",
+    "65535",
+    "65536",
+    "4294967296",
+    "18446744073709551616",
+    "9999999999d",
+    "99999999999999999h",
+    "-9223372036854775808",
+    "\u{e9}",
+    "\u{1f600}",
+    "\r",
+    "\n",
+    "\0",
+    "IP4",
+    "IP6",
+    "IN",
+    "typ",
+    "raddr",
+    "rport",
+    ".",
+    "::",
+    "[",
+    "]",
+    "(",
+    ")",
+    "<",
+    ">",
+    "@",
+];
+
+// mutate inserts, replaces or cuts off tokens at random positions.
+fn mutate(rng: &mut StdRng, seed: &str) -> String {
+    let mut s = seed.to_owned();
+    for _ in 0..rng.gen_range(1..4) {
+        let mut at = rng.gen_range(0..=s.len());
+        while !s.is_char_boundary(at) {
+            at -= 1;
+        }
+        match rng.gen_range(0..3) {
+            0 => s.insert_str(at, TOKENS[rng.gen_range(0..TOKENS.len())]),
+            1 => s.truncate(at),
+            _ => {
+                let mut end = rng.gen_range(at..=s.len());
+                while !s.is_char_boundary(end) {
+                    end -= 1;
+                }
+                s.replace_range(at..end.max(at), TOKENS[rng.gen_range(0..TOKENS.len())]);
+            }
+        }
+    }
+    s
+}
+
+// check parses input, which may fail but must not panic, and makes sure
+// that a parsed value survives a round trip through its text form.
+fn check<T>(input: &str)
+where
+    T: FromStr + ToString + PartialEq + Debug,
+    T::Err: Debug,
+{
+    if let Ok(value) = input.parse::<T>() {
+        let text = value.to_string();
+        match text.parse::<T>() {
+            Ok(reparsed) => assert_eq!(reparsed, value, "{:?} -> {:?}", input, text),
+            Err(err) => panic!("{:?} -> {:?}: {:?}", input, text, err),
+        }
+    }
+}
+
+#[test]
+fn test_field_parsers_fuzz() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..ITERATIONS {
+        let seed = FIELDS[rng.gen_range(0..FIELDS.len())];
+        let input = mutate(&mut rng, seed);
+        check::<Origin>(&input);
+        check::<Bandwidth>(&input);
+        check::<Timing>(&input);
+        check::<RepeatTime>(&input);
+        check::<TimeZones>(&input);
+        check::<ConnectionInformation>(&input);
+        check::<EncryptionKey>(&input);
+        check::<Uri>(&input);
+        check::<EmailAddress>(&input);
+        check::<PhoneNumber>(&input);
+        check::<Attribute>(&input);
+        check::<MediaName>(&input);
+        check::<ExtMap>(&input);
+        check::<Candidate>(&input);
+        check::<RtpMap>(&input);
+        check::<Fmtp>(&input);
+        check::<Fingerprint>(&input);
+        check::<Ssrc>(&input);
+        check::<SsrcGroup>(&input);
+        check::<Msid>(&input);
+        check::<MsidSemantic>(&input);
+        check::<RtcpFeedback>(&input);
+        check::<Rid>(&input);
+        check::<Simulcast>(&input);
+        check::<SctpMap>(&input);
+        check::<Rtcp>(&input);
+        check::<Group>(&input);
+        check::<MaxPRate>(&input);
+        check::<PTime>(&input);
+        check::<IceOptions>(&input);
+    }
+}
+
+#[test]
+fn test_session_description_parsers_fuzz() {
+    let mut rng = StdRng::seed_from_u64(2);
+    for _ in 0..ITERATIONS {
+        let seed = SESSION_DESCRIPTIONS[rng.gen_range(0..SESSION_DESCRIPTIONS.len())];
+        let input = mutate(&mut rng, seed);
+        check::<SessionDescription>(&input);
+
+        // All entry points accept and reject the same input.
+        let owned = input.parse::<SessionDescription>().ok();
+        let borrowed = SessionDescription::parse_borrowed(&input)
+            .ok()
+            .map(|sd| sd.to_owned());
+        let streamed = SessionDescription::from_reader(input.as_bytes()).ok();
+        assert_eq!(borrowed, owned, "{:?}", input);
+        assert_eq!(streamed, owned, "{:?}", input);
+    }
+}
@@ -0,0 +1,128 @@
+// Measures the memory retained by parsed descriptions with and without an
+// Interner. It is a test of its own so that the counting allocator only
+// sees this one.
+#![cfg(feature = "std")]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use webrtc_rs_sdp::{Interner, ParseOptions, Parser, SessionDescription};
+
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const COPIES: usize = 1000;
+
+const OFFER_SDP: &str = "v=0\r\n\
+o=- 4215775240449105457 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0 1\r\n\
+a=extmap-allow-mixed\r\n\
+a=msid-semantic: WMS stream\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111 63 9 0 8 13 110 126\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=rtcp:9 IN IP4 0.0.0.0\r\n\
+a=ice-ufrag:Fh4x\r\n\
+a=ice-pwd:ocWVKZqIVjYLhdKnTmpXprYq\r\n\
+a=ice-options:trickle\r\n\
+a=fingerprint:sha-256 3D:6A:A3:9B:6B:7D:10:9E:2F:46:0A:42:A7:B9:1A:5E:D8:32:0B:FB:A6:B0:4A:3E:C8:52:3C:F2:55:E6:07:6C\r\n\
+a=setup:actpass\r\n\
+a=mid:0\r\n\
+a=extmap:1 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r\n\
+a=extmap:2 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time\r\n\
+a=extmap:3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01\r\n\
+a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=sendrecv\r\n\
+a=msid:stream audio\r\n\
+a=rtcp-mux\r\n\
+a=rtpmap:111 opus/48000/2\r\n\
+a=rtcp-fb:111 transport-cc\r\n\
+a=fmtp:111 minptime=10;useinbandfec=1\r\n\
+a=rtpmap:63 red/48000/2\r\n\
+a=fmtp:63 111/111\r\n\
+a=rtpmap:9 G722/8000\r\n\
+a=rtpmap:0 PCMU/8000\r\n\
+a=rtpmap:8 PCMA/8000\r\n\
+a=rtpmap:13 CN/8000\r\n\
+a=rtpmap:110 telephone-event/48000\r\n\
+a=rtpmap:126 telephone-event/8000\r\n\
+a=ssrc:3735928559 cname:Rk5XdB9ygEkqUpJo\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96 97\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=rtcp:9 IN IP4 0.0.0.0\r\n\
+a=ice-ufrag:Fh4x\r\n\
+a=ice-pwd:ocWVKZqIVjYLhdKnTmpXprYq\r\n\
+a=ice-options:trickle\r\n\
+a=fingerprint:sha-256 3D:6A:A3:9B:6B:7D:10:9E:2F:46:0A:42:A7:B9:1A:5E:D8:32:0B:FB:A6:B0:4A:3E:C8:52:3C:F2:55:E6:07:6C\r\n\
+a=setup:actpass\r\n\
+a=mid:1\r\n\
+a=extmap:14 urn:ietf:params:rtp-hdrext:toffset\r\n\
+a=extmap:2 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time\r\n\
+a=extmap:13 urn:3gpp:video-orientation\r\n\
+a=extmap:3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01\r\n\
+a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=sendrecv\r\n\
+a=msid:stream video\r\n\
+a=rtcp-mux\r\n\
+a=rtcp-rsize\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+a=rtcp-fb:96 goog-remb\r\n\
+a=rtcp-fb:96 transport-cc\r\n\
+a=rtcp-fb:96 ccm fir\r\n\
+a=rtcp-fb:96 nack\r\n\
+a=rtcp-fb:96 nack pli\r\n\
+a=rtpmap:97 rtx/90000\r\n\
+a=fmtp:97 apt=96\r\n\
+a=ssrc-group:FID 2231627014 632943048\r\n\
+a=ssrc:2231627014 cname:Rk5XdB9ygEkqUpJo\r\n\
+a=ssrc:632943048 cname:Rk5XdB9ygEkqUpJo\r\n";
+
+// retained returns the bytes still allocated after parsing COPIES copies
+// of the offer, which are kept alive until the measurement.
+fn retained(options: ParseOptions) -> usize {
+    let mut parser = Parser::new().with_options(options);
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    let descriptions = (0..COPIES)
+        .map(|_| parser.parse(OFFER_SDP.as_bytes()))
+        .collect::<Result<Vec<SessionDescription>, _>>()
+        .expect("valid offer");
+    let after = ALLOCATED.load(Ordering::Relaxed);
+    drop(descriptions);
+    after - before
+}
+
+#[test]
+fn test_interning_memory() {
+    let plain = retained(ParseOptions::default());
+    // The interner is part of what is retained.
+    let interned = retained(ParseOptions {
+        interner: Some(Interner::new()),
+        ..Default::default()
+    });
+
+    // Most of what remains is the attribute lists themselves and the values
+    // unique to each endpoint, like the ICE credentials and SSRCs.
+    assert!(
+        interned * 5 < plain * 4,
+        "interning saved too little: {} vs {} bytes",
+        interned,
+        plain
+    );
+}
@@ -0,0 +1,43 @@
+// A no_std library using the SDP types with alloc only.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use webrtc_rs_sdp::prelude::*;
+
+// rtpmaps returns the rtpmaps of every media description of the offer.
+pub fn rtpmaps(offer: &str) -> Result<Vec<RtpMap>, Error> {
+    let sd = offer.parse::<SessionDescription>()?;
+    Ok(sd
+        .media_descriptions
+        .iter()
+        .flat_map(|md| md.attributes.get::<RtpMap>().filter_map(Result::ok))
+        .collect())
+}
+
+// origin_line returns the "o=" value of a session on the given host.
+pub fn origin_line(host: core::net::Ipv4Addr, session_id: u64) -> Result<String, Error> {
+    let origin = Origin {
+        username: Username::try_new("-")?,
+        session_id,
+        session_version: 1,
+        network_type: NetType::In,
+        address_type: "IP4".to_string(),
+        unicast_address: host.to_string(),
+    };
+    Ok(origin.to_string())
+}
+
+// timing_line returns the "t=" value of a bounded session.
+pub fn timing_line(start: u64, stop: u64) -> Result<String, Error> {
+    Ok(Timing::new(start, stop)?.to_string())
+}
+
+// bandwidth returns the value of a "b=AS:" line in kilobits per second.
+pub fn bandwidth(value: &str) -> Result<u64, Error> {
+    Ok(value.parse::<Bandwidth>()?.bandwidth)
+}
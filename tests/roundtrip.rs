@@ -0,0 +1,422 @@
+// Descriptions which are parsed and marshaled again come out byte for byte
+// the same, including attributes this crate has no type for and their exact
+// whitespace, so that they can be forwarded after changing only what needs
+// to be changed.
+#![cfg(feature = "std")]
+
+use webrtc_rs_sdp::prelude::*;
+
+const CHROME_OFFER: &str = "v=0\r\n\
+o=- 4215775240449105457 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0 1 2\r\n\
+a=extmap-allow-mixed\r\n\
+a=msid-semantic: WMS 0c8e9c87-1fc4-4b64-b4f4-39c1a4b5e1c7\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111 63 9 0 8 13 110 126\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=rtcp:9 IN IP4 0.0.0.0\r\n\
+a=ice-ufrag:Fh4x\r\n\
+a=ice-pwd:ocWVKZqIVjYLhdKnTmpXprYq\r\n\
+a=ice-options:trickle\r\n\
+a=fingerprint:sha-256 3D:6A:A3:9B:6B:7D:10:9E:2F:46:0A:42:A7:B9:1A:5E:D8:32:0B:FB:A6:B0:4A:3E:C8:52:3C:F2:55:E6:07:6C\r\n\
+a=setup:actpass\r\n\
+a=mid:0\r\n\
+a=extmap:1 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r\n\
+a=extmap:2 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time\r\n\
+a=extmap:3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01\r\n\
+a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=sendrecv\r\n\
+a=msid:0c8e9c87-1fc4-4b64-b4f4-39c1a4b5e1c7 7d3b2a11-46f1-4c4e-9a56-0c1f0e1f9b3e\r\n\
+a=rtcp-mux\r\n\
+a=rtcp-rsize\r\n\
+a=rtpmap:111 opus/48000/2\r\n\
+a=rtcp-fb:111 transport-cc\r\n\
+a=fmtp:111 minptime=10;useinbandfec=1\r\n\
+a=rtpmap:63 red/48000/2\r\n\
+a=fmtp:63 111/111\r\n\
+a=rtpmap:9 G722/8000\r\n\
+a=rtpmap:0 PCMU/8000\r\n\
+a=rtpmap:8 PCMA/8000\r\n\
+a=rtpmap:13 CN/8000\r\n\
+a=rtpmap:110 telephone-event/48000\r\n\
+a=rtpmap:126 telephone-event/8000\r\n\
+a=ssrc:3735928559 cname:Rk5XdB9ygEkqUpJo\r\n\
+a=ssrc:3735928559 msid:0c8e9c87-1fc4-4b64-b4f4-39c1a4b5e1c7 7d3b2a11-46f1-4c4e-9a56-0c1f0e1f9b3e\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96 97 102 103\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=rtcp:9 IN IP4 0.0.0.0\r\n\
+a=ice-ufrag:Fh4x\r\n\
+a=ice-pwd:ocWVKZqIVjYLhdKnTmpXprYq\r\n\
+a=ice-options:trickle\r\n\
+a=fingerprint:sha-256 3D:6A:A3:9B:6B:7D:10:9E:2F:46:0A:42:A7:B9:1A:5E:D8:32:0B:FB:A6:B0:4A:3E:C8:52:3C:F2:55:E6:07:6C\r\n\
+a=setup:actpass\r\n\
+a=mid:1\r\n\
+a=extmap:14 urn:ietf:params:rtp-hdrext:toffset\r\n\
+a=extmap:2 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time\r\n\
+a=extmap:13 urn:3gpp:video-orientation\r\n\
+a=extmap:3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01\r\n\
+a=extmap:5 http://www.webrtc.org/experiments/rtp-hdrext/playout-delay\r\n\
+a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=extmap:10 urn:ietf:params:rtp-hdrext:sdes:rtp-stream-id\r\n\
+a=extmap:11 urn:ietf:params:rtp-hdrext:sdes:repaired-rtp-stream-id\r\n\
+a=sendrecv\r\n\
+a=msid:0c8e9c87-1fc4-4b64-b4f4-39c1a4b5e1c7 b1e2f6f4-3a0b-4f4e-8d0c-6a2e3f1d9c7b\r\n\
+a=rtcp-mux\r\n\
+a=rtcp-rsize\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+a=rtcp-fb:96 goog-remb\r\n\
+a=rtcp-fb:96 transport-cc\r\n\
+a=rtcp-fb:96 ccm fir\r\n\
+a=rtcp-fb:96 nack\r\n\
+a=rtcp-fb:96 nack pli\r\n\
+a=rtpmap:97 rtx/90000\r\n\
+a=fmtp:97 apt=96\r\n\
+a=rtpmap:102 H264/90000\r\n\
+a=rtcp-fb:102 goog-remb\r\n\
+a=rtcp-fb:102 transport-cc\r\n\
+a=rtcp-fb:102 ccm fir\r\n\
+a=rtcp-fb:102 nack\r\n\
+a=rtcp-fb:102 nack pli\r\n\
+a=fmtp:102 level-asymmetry-allowed=1;packetization-mode=1;profile-level-id=42001f\r\n\
+a=rtpmap:103 rtx/90000\r\n\
+a=fmtp:103 apt=102\r\n\
+a=ssrc-group:FID 2271440394 1381524561\r\n\
+a=ssrc:2271440394 cname:Rk5XdB9ygEkqUpJo\r\n\
+a=ssrc:2271440394 msid:0c8e9c87-1fc4-4b64-b4f4-39c1a4b5e1c7 b1e2f6f4-3a0b-4f4e-8d0c-6a2e3f1d9c7b\r\n\
+a=ssrc:1381524561 cname:Rk5XdB9ygEkqUpJo\r\n\
+a=ssrc:1381524561 msid:0c8e9c87-1fc4-4b64-b4f4-39c1a4b5e1c7 b1e2f6f4-3a0b-4f4e-8d0c-6a2e3f1d9c7b\r\n\
+m=application 9 UDP/DTLS/SCTP webrtc-datachannel\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=ice-ufrag:Fh4x\r\n\
+a=ice-pwd:ocWVKZqIVjYLhdKnTmpXprYq\r\n\
+a=ice-options:trickle\r\n\
+a=fingerprint:sha-256 3D:6A:A3:9B:6B:7D:10:9E:2F:46:0A:42:A7:B9:1A:5E:D8:32:0B:FB:A6:B0:4A:3E:C8:52:3C:F2:55:E6:07:6C\r\n\
+a=setup:actpass\r\n\
+a=mid:2\r\n\
+a=sctp-port:5000\r\n\
+a=max-message-size:262144\r\n";
+
+const FIREFOX_OFFER: &str = "v=0\r\n\
+o=mozilla...THIS_IS_SDPARTA-99.0 5107232427436433046 0 IN IP4 0.0.0.0\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=fingerprint:sha-256 B8:4E:2C:5C:0D:DE:58:0F:1F:06:29:0C:D5:61:C3:6E:1C:55:D8:03:B0:A4:0A:6C:AD:CF:05:90:B5:4C:DC:E7\r\n\
+a=group:BUNDLE 0 1\r\n\
+a=ice-options:trickle\r\n\
+a=msid-semantic:WMS *\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 109 9 0 8 101\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=sendrecv\r\n\
+a=extmap:1 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r\n\
+a=extmap:2/recvonly urn:ietf:params:rtp-hdrext:csrc-audio-level\r\n\
+a=extmap:3 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=fmtp:109 maxplaybackrate=48000;stereo=1;useinbandfec=1\r\n\
+a=fmtp:101 0-15\r\n\
+a=ice-pwd:4b3b7a4e8b3b0f0e5bb3c1c19e2c2f51\r\n\
+a=ice-ufrag:0fd9b2f4\r\n\
+a=mid:0\r\n\
+a=msid:{3b1a0c0e-5d2f-4b8e-9a51-1f2e3d4c5b6a} {c6a5b4d3-e2f1-4a09-8b7c-6d5e4f3a2b1c}\r\n\
+a=rtcp-mux\r\n\
+a=rtpmap:109 opus/48000/2\r\n\
+a=rtpmap:9 G722/8000/1\r\n\
+a=rtpmap:0 PCMU/8000\r\n\
+a=rtpmap:8 PCMA/8000\r\n\
+a=rtpmap:101 telephone-event/8000/1\r\n\
+a=setup:actpass\r\n\
+a=ssrc:1587036425 cname:{9e4f2b1c-3d5a-4c6b-8e7f-0a1b2c3d4e5f}\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 120 124 121 125 126 127 97 98\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=sendrecv\r\n\
+a=extmap:3 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=extmap:4 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time\r\n\
+a=extmap:5 urn:ietf:params:rtp-hdrext:toffset\r\n\
+a=extmap:6/recvonly http://www.webrtc.org/experiments/rtp-hdrext/playout-delay\r\n\
+a=extmap:7 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01\r\n\
+a=fmtp:126 profile-level-id=42e01f;level-asymmetry-allowed=1;packetization-mode=1\r\n\
+a=fmtp:97 profile-level-id=42e01f;level-asymmetry-allowed=1\r\n\
+a=fmtp:120 max-fs=12288;max-fr=60\r\n\
+a=fmtp:124 apt=120\r\n\
+a=fmtp:121 max-fs=12288;max-fr=60\r\n\
+a=fmtp:125 apt=121\r\n\
+a=fmtp:127 apt=126\r\n\
+a=fmtp:98 apt=97\r\n\
+a=ice-pwd:4b3b7a4e8b3b0f0e5bb3c1c19e2c2f51\r\n\
+a=ice-ufrag:0fd9b2f4\r\n\
+a=mid:1\r\n\
+a=msid:{3b1a0c0e-5d2f-4b8e-9a51-1f2e3d4c5b6a} {0f1e2d3c-4b5a-4968-8776-655443322110}\r\n\
+a=rtcp-fb:120 nack\r\n\
+a=rtcp-fb:120 nack pli\r\n\
+a=rtcp-fb:120 ccm fir\r\n\
+a=rtcp-fb:120 goog-remb\r\n\
+a=rtcp-fb:120 transport-cc\r\n\
+a=rtcp-fb:126 nack\r\n\
+a=rtcp-fb:126 nack pli\r\n\
+a=rtcp-fb:126 ccm fir\r\n\
+a=rtcp-fb:126 goog-remb\r\n\
+a=rtcp-fb:126 transport-cc\r\n\
+a=rtcp-mux\r\n\
+a=rtcp-rsize\r\n\
+a=rtpmap:120 VP8/90000\r\n\
+a=rtpmap:124 rtx/90000\r\n\
+a=rtpmap:121 VP9/90000\r\n\
+a=rtpmap:125 rtx/90000\r\n\
+a=rtpmap:126 H264/90000\r\n\
+a=rtpmap:127 rtx/90000\r\n\
+a=rtpmap:97 H264/90000\r\n\
+a=rtpmap:98 rtx/90000\r\n\
+a=setup:actpass\r\n\
+a=ssrc:2951367018 cname:{9e4f2b1c-3d5a-4c6b-8e7f-0a1b2c3d4e5f}\r\n\
+a=ssrc:3429164934 cname:{9e4f2b1c-3d5a-4c6b-8e7f-0a1b2c3d4e5f}\r\n\
+a=ssrc-group:FID 2951367018 3429164934\r\n";
+
+const SAFARI_OFFER: &str = "v=0\r\n\
+o=- 6829436457286470254 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0 1\r\n\
+a=extmap-allow-mixed\r\n\
+a=msid-semantic: WMS 5f3c2f6e-6a1b-4c3d-9e8f-7a6b5c4d3e2f\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111 9 0 8 13 110 126\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=rtcp:9 IN IP4 0.0.0.0\r\n\
+a=ice-ufrag:c3nq\r\n\
+a=ice-pwd:3h6bF9TyMmVGdqjOc4yuVBRn\r\n\
+a=ice-options:trickle\r\n\
+a=fingerprint:sha-256 97:A2:0B:0A:2E:4A:CD:5B:0D:B7:C1:46:4D:4E:07:8D:63:63:C1:1A:31:25:7E:71:56:96:5E:C9:15:9B:A6:6B\r\n\
+a=setup:actpass\r\n\
+a=mid:0\r\n\
+a=extmap:1 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r\n\
+a=extmap:2 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time\r\n\
+a=extmap:3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01\r\n\
+a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=sendrecv\r\n\
+a=msid:5f3c2f6e-6a1b-4c3d-9e8f-7a6b5c4d3e2f 1c2d3e4f-5a6b-4c7d-8e9f-0a1b2c3d4e5f\r\n\
+a=rtcp-mux\r\n\
+a=rtpmap:111 opus/48000/2\r\n\
+a=rtcp-fb:111 transport-cc\r\n\
+a=fmtp:111 minptime=10;useinbandfec=1\r\n\
+a=rtpmap:9 G722/8000\r\n\
+a=rtpmap:0 PCMU/8000\r\n\
+a=rtpmap:8 PCMA/8000\r\n\
+a=rtpmap:13 CN/8000\r\n\
+a=rtpmap:110 telephone-event/48000\r\n\
+a=rtpmap:126 telephone-event/8000\r\n\
+a=ssrc:1263581929 cname:9fXyVz0a1b2C3d4E\r\n\
+a=ssrc:1263581929 msid:5f3c2f6e-6a1b-4c3d-9e8f-7a6b5c4d3e2f 1c2d3e4f-5a6b-4c7d-8e9f-0a1b2c3d4e5f\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96 97 98 99 100 101 127 125\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=rtcp:9 IN IP4 0.0.0.0\r\n\
+a=ice-ufrag:c3nq\r\n\
+a=ice-pwd:3h6bF9TyMmVGdqjOc4yuVBRn\r\n\
+a=ice-options:trickle\r\n\
+a=fingerprint:sha-256 97:A2:0B:0A:2E:4A:CD:5B:0D:B7:C1:46:4D:4E:07:8D:63:63:C1:1A:31:25:7E:71:56:96:5E:C9:15:9B:A6:6B\r\n\
+a=setup:actpass\r\n\
+a=mid:1\r\n\
+a=extmap:14 urn:ietf:params:rtp-hdrext:toffset\r\n\
+a=extmap:2 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time\r\n\
+a=extmap:13 urn:3gpp:video-orientation\r\n\
+a=extmap:3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01\r\n\
+a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=sendrecv\r\n\
+a=msid:5f3c2f6e-6a1b-4c3d-9e8f-7a6b5c4d3e2f 2d3e4f5a-6b7c-4d8e-9f0a-1b2c3d4e5f6a\r\n\
+a=rtcp-mux\r\n\
+a=rtcp-rsize\r\n\
+a=rtpmap:96 H264/90000\r\n\
+a=rtcp-fb:96 goog-remb\r\n\
+a=rtcp-fb:96 transport-cc\r\n\
+a=rtcp-fb:96 ccm fir\r\n\
+a=rtcp-fb:96 nack\r\n\
+a=rtcp-fb:96 nack pli\r\n\
+a=fmtp:96 level-asymmetry-allowed=1;packetization-mode=1;profile-level-id=640c1f\r\n\
+a=rtpmap:97 rtx/90000\r\n\
+a=fmtp:97 apt=96\r\n\
+a=rtpmap:98 H264/90000\r\n\
+a=rtcp-fb:98 goog-remb\r\n\
+a=rtcp-fb:98 transport-cc\r\n\
+a=rtcp-fb:98 ccm fir\r\n\
+a=rtcp-fb:98 nack\r\n\
+a=rtcp-fb:98 nack pli\r\n\
+a=fmtp:98 level-asymmetry-allowed=1;packetization-mode=1;profile-level-id=42e01f\r\n\
+a=rtpmap:99 rtx/90000\r\n\
+a=fmtp:99 apt=98\r\n\
+a=rtpmap:100 VP8/90000\r\n\
+a=rtcp-fb:100 goog-remb\r\n\
+a=rtcp-fb:100 transport-cc\r\n\
+a=rtcp-fb:100 ccm fir\r\n\
+a=rtcp-fb:100 nack\r\n\
+a=rtcp-fb:100 nack pli\r\n\
+a=rtpmap:101 rtx/90000\r\n\
+a=fmtp:101 apt=100\r\n\
+a=rtpmap:127 red/90000\r\n\
+a=rtpmap:125 rtx/90000\r\n\
+a=fmtp:125 apt=127\r\n\
+a=ssrc-group:FID 1837522386 3054208934\r\n\
+a=ssrc:1837522386 cname:9fXyVz0a1b2C3d4E\r\n\
+a=ssrc:1837522386 msid:5f3c2f6e-6a1b-4c3d-9e8f-7a6b5c4d3e2f 2d3e4f5a-6b7c-4d8e-9f0a-1b2c3d4e5f6a\r\n\
+a=ssrc:3054208934 cname:9fXyVz0a1b2C3d4E\r\n\
+a=ssrc:3054208934 msid:5f3c2f6e-6a1b-4c3d-9e8f-7a6b5c4d3e2f 2d3e4f5a-6b7c-4d8e-9f0a-1b2c3d4e5f6a\r\n";
+
+const BROWSER_OFFERS: &[(&str, &str)] = &[
+    ("chrome", CHROME_OFFER),
+    ("firefox", FIREFOX_OFFER),
+    ("safari", SAFARI_OFFER),
+];
+
+// PROPRIETARY_OFFER has attributes and lines no implementation defines,
+// with unusual whitespace.
+const PROPRIETARY_OFFER: &str = "v=0\r\n\
+o=- 1 1 IN IP4 10.0.0.1\r\n\
+x=vendor session line\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=x-vendor-session:  two spaces\r\n\
+a=x-flag\r\n\
+y=before media\r\n\
+m=audio 5004 RTP/AVP 0\r\n\
+a=x-vendor-media:a:b:c\r\n\
+z=ignored by RFC 4566 parsers: this is a time zone line\r\n\
+a=sendrecv\r\n\
+w=first\r\n\
+w=second\r\n";
+
+#[test]
+fn test_roundtrip_browser_offers() -> Result<(), Error> {
+    for &(name, offer) in BROWSER_OFFERS {
+        let sd = offer.parse::<SessionDescription>()?;
+        assert_eq!(sd.marshal(), offer, "{}: FromStr", name);
+        assert_eq!(sd.marshal_len_hint(), offer.len(), "{}: length", name);
+
+        let borrowed = SessionDescription::parse_borrowed(offer)?;
+        assert_eq!(borrowed.to_owned().marshal(), offer, "{}: borrowed", name);
+
+        let streamed = SessionDescription::from_reader(offer.as_bytes())?;
+        assert_eq!(streamed.marshal(), offer, "{}: reader", name);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_roundtrip_modified() -> Result<(), Error> {
+    for &(name, offer) in BROWSER_OFFERS {
+        let mut sd = offer.parse::<SessionDescription>()?;
+        sd.media_descriptions[0].set_direction(Direction::RecvOnly);
+        if let Some(md) = sd.media_descriptions.last_mut() {
+            md.attributes
+                .push(Attribute::try_new("x-sfu-layer", Some(" 2"))?);
+        }
+
+        // Only the changed lines differ, the others are left as they were.
+        let expected = offer.replacen("a=sendrecv\r\n", "a=recvonly\r\n", 1);
+        let marshaled = sd.marshal();
+        let (before, after) = marshaled.split_at(expected.len());
+        assert_eq!(before, expected, "{}: untouched lines", name);
+        assert_eq!(after, "a=x-sfu-layer: 2\r\n", "{}: added line", name);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_roundtrip_unknown_lines() -> Result<(), Error> {
+    let mut parser = Parser::new().with_options(ParseOptions {
+        unknown_line_policy: UnknownLinePolicy::Keep,
+        ..Default::default()
+    });
+
+    // z= is a known line type out of place, which isn't kept.
+    let offer = PROPRIETARY_OFFER.replace(
+        "z=ignored by RFC 4566 parsers: this is a time zone line\r\n",
+        "",
+    );
+    let sd = parser.parse(offer.as_bytes())?;
+    assert_eq!(sd.marshal(), offer);
+    assert_eq!(sd.marshal_len_hint(), offer.len());
+    assert_eq!(
+        sd.attributes
+            .get_first("x-vendor-session")
+            .and_then(Attribute::value),
+        Some("  two spaces")
+    );
+
+    // An attribute added to a media description goes after the unknown
+    // lines at its end.
+    let mut sd = sd;
+    sd.media_descriptions[0]
+        .attributes
+        .push(Attribute::try_new("x-added", None)?);
+    assert!(sd
+        .marshal()
+        .ends_with("a=sendrecv\r\nw=first\r\nw=second\r\na=x-added\r\n"));
+
+    assert!(parser.parse(PROPRIETARY_OFFER.as_bytes()).is_err());
+
+    Ok(())
+}
+
+// PADDED_OFFER has an attribute with whitespace around its value.
+const PADDED_OFFER: &str = "v=0\r\n\
+o=- 1 1 IN IP4 10.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+m=audio 5004 RTP/AVP 0\r\n\
+a=foo:  bar \r\n\
+a=sendrecv\r\n";
+
+#[test]
+fn test_roundtrip_attribute_whitespace() -> Result<(), Error> {
+    let sd = PADDED_OFFER.parse::<SessionDescription>()?;
+    let foo = sd.media_descriptions[0].attributes.get_first("foo");
+    assert_eq!(foo.and_then(Attribute::value), Some("  bar "));
+    assert_eq!(sd.marshal(), PADDED_OFFER, "FromStr");
+    assert_eq!(sd.marshal_len_hint(), PADDED_OFFER.len());
+
+    let borrowed = SessionDescription::parse_borrowed(PADDED_OFFER)?;
+    assert_eq!(borrowed.to_owned().marshal(), PADDED_OFFER, "borrowed");
+
+    let streamed = SessionDescription::from_reader(PADDED_OFFER.as_bytes())?;
+    assert_eq!(streamed.marshal(), PADDED_OFFER, "reader");
+
+    // The values of typed attributes and property attributes are trimmed.
+    let padded = PADDED_OFFER.replace("a=sendrecv\r\n", "a=sendrecv \r\na=mid:0 \r\n");
+    let sd = padded.parse::<SessionDescription>()?;
+    assert_eq!(
+        sd.marshal(),
+        PADDED_OFFER.replace("a=sendrecv\r\n", "a=sendrecv\r\na=mid:0\r\n")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_roundtrip_line_feeds() -> Result<(), Error> {
+    for &(name, offer) in BROWSER_OFFERS {
+        let offer = offer.replace("\r\n", "\n");
+        let sd = offer.parse::<SessionDescription>()?;
+        assert_eq!(sd.marshal(), offer, "{}: FromStr", name);
+        assert_eq!(sd.marshal_len_hint(), offer.len(), "{}: length", name);
+
+        let borrowed = SessionDescription::parse_borrowed(&offer)?;
+        assert_eq!(borrowed.to_owned().marshal(), offer, "{}: borrowed", name);
+
+        let streamed = SessionDescription::from_reader(offer.as_bytes())?;
+        assert_eq!(streamed.marshal(), offer, "{}: reader", name);
+
+        // A changed description keeps the line endings.
+        let mut sd = sd;
+        sd.media_descriptions[0].set_direction(Direction::RecvOnly);
+        let expected = offer.replacen("a=sendrecv\n", "a=recvonly\n", 1);
+        assert_eq!(sd.marshal(), expected, "{}: modified", name);
+    }
+
+    let mut parser = Parser::new().with_options(ParseOptions {
+        normalize_line_endings: true,
+        ..Default::default()
+    });
+    let sd = parser.parse(CHROME_OFFER.replace("\r\n", "\n").as_bytes())?;
+    assert_eq!(sd.line_ending, LineEnding::CrLf);
+    assert_eq!(sd.marshal(), CHROME_OFFER);
+
+    Ok(())
+}
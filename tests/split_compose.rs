@@ -0,0 +1,130 @@
+// Splits a browser offer into one description per media description, the
+// way an SFU forwards them to different consumers, puts them together again
+// and checks that every media description means what it meant in the offer.
+
+use webrtc_rs_sdp::direction::Direction;
+use webrtc_rs_sdp::extmap::{ExtMap, ExtmapAllowMixed};
+use webrtc_rs_sdp::fingerprint::Fingerprint;
+use webrtc_rs_sdp::group::Mid;
+use webrtc_rs_sdp::ice::{IcePwd, IceUfrag};
+use webrtc_rs_sdp::rtpmap::RtpMap;
+use webrtc_rs_sdp::setup::Setup;
+use webrtc_rs_sdp::split::{OriginPolicy, SessionTemplate};
+use webrtc_rs_sdp::{
+    BandwidthKind, ConnectionInformation, Error, MediaDescription, MediaName, ResolvedMedia,
+    SessionDescription,
+};
+
+const OFFER: &str = "v=0\r\n\
+o=- 4611731400430051336 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+c=IN IP4 0.0.0.0\r\n\
+b=AS:2000\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0 1 2\r\n\
+a=extmap-allow-mixed\r\n\
+a=msid-semantic:WMS stream\r\n\
+a=ice-ufrag:EsAw\r\n\
+a=ice-pwd:P2uYro0UCOQ4zxjKXaWCBui1\r\n\
+a=fingerprint:sha-256 19:E2:1C:3B:4B:9F:81:E6:B8:5C:F4:A5:A8:D8:73:04:BB:05:2F:70:9F:04:A9:0E:05:E9:26:33:E8:70:88:A2\r\n\
+a=setup:actpass\r\n\
+a=sendrecv\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111 0\r\n\
+b=AS:64\r\n\
+a=mid:0\r\n\
+a=msid:stream audio\r\n\
+a=rtcp-mux\r\n\
+a=extmap:1 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r\n\
+a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=rtpmap:111 opus/48000/2\r\n\
+a=fmtp:111 minptime=10;useinbandfec=1\r\n\
+a=rtpmap:0 PCMU/8000\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96 97\r\n\
+a=mid:1\r\n\
+a=sendonly\r\n\
+a=msid:stream video\r\n\
+a=rtcp-mux\r\n\
+a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=extmap:16 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+a=rtcp-fb:96 nack\r\n\
+a=rtpmap:97 rtx/90000\r\n\
+a=fmtp:97 apt=96\r\n\
+m=application 9 UDP/DTLS/SCTP webrtc-datachannel\r\n\
+c=IN IP4 192.0.2.1\r\n\
+a=mid:2\r\n\
+a=setup:active\r\n\
+a=sctp-port:5000\r\n";
+
+/// Resolved is what a media description means, as seen through ResolvedMedia.
+#[derive(Debug, PartialEq)]
+struct Resolved {
+    media_name: MediaName,
+    mid: Option<Mid>,
+    connection: Option<ConnectionInformation>,
+    bandwidth: Option<u64>,
+    direction: Direction,
+    ice_credentials: Option<(IceUfrag, IcePwd)>,
+    fingerprint: Option<Fingerprint>,
+    setup: Option<Setup>,
+    extmap_allow_mixed: bool,
+    extmaps: Vec<ExtMap>,
+    rtpmaps: Vec<RtpMap>,
+}
+
+impl From<ResolvedMedia<'_>> for Resolved {
+    fn from(media: ResolvedMedia<'_>) -> Self {
+        Resolved {
+            media_name: media.media().media_name.clone(),
+            mid: media.media().mid(),
+            connection: media.connection().cloned(),
+            bandwidth: media.bandwidth(BandwidthKind::As),
+            direction: media.direction(),
+            ice_credentials: media.ice_credentials(),
+            fingerprint: media.fingerprint(),
+            setup: media.attribute(),
+            extmap_allow_mixed: media.attribute::<ExtmapAllowMixed>().is_some(),
+            extmaps: media.attributes(),
+            rtpmaps: media.attributes(),
+        }
+    }
+}
+
+fn resolved(sd: &SessionDescription) -> Vec<Resolved> {
+    sd.resolved_media().map(Resolved::from).collect()
+}
+
+#[test]
+fn test_split_and_compose() -> Result<(), Error> {
+    let offer = OFFER.parse::<SessionDescription>()?;
+
+    let mut sections: Vec<MediaDescription> = vec![];
+    for mid in ["0", "1", "2"] {
+        let extracted = offer.extract_media(&Mid::try_new(mid)?, OriginPolicy::Copy)?;
+        // Each part stands on its own.
+        assert_eq!(extracted.media_descriptions.len(), 1);
+        assert_eq!(
+            extracted.bundle_groups()[0].to_string(),
+            format!("BUNDLE {}", mid)
+        );
+        assert!(
+            extracted.validate().is_empty(),
+            "{:?}",
+            extracted.validate()
+        );
+        let part = extracted.marshal().parse::<SessionDescription>()?;
+        assert_eq!(resolved(&part)[..], resolved(&offer)[sections.len()..][..1]);
+        sections.extend(part.media_descriptions);
+    }
+
+    let composition = SessionDescription::compose(sections, &SessionTemplate::from(&offer))?;
+    assert!(composition.remappings.is_empty());
+    let composed = &composition.description;
+    assert_eq!(composed.origin, offer.origin);
+    assert_eq!(composed.bundle_groups(), offer.bundle_groups());
+    assert_eq!(composed.wms_stream_ids(), offer.wms_stream_ids());
+    assert_eq!(resolved(composed), resolved(&offer));
+    assert!(composed.validate().is_empty(), "{:?}", composed.validate());
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+// Takes an offer the way code still using the sdp crate of webrtc-rs has it,
+// changes it with this crate and hands it back, which is how a code base
+// moves over one part at a time.
+#![cfg(feature = "webrtc-compat")]
+
+use std::convert::TryFrom;
+use std::io::Cursor;
+
+use webrtc_rs_sdp::prelude::*;
+
+const OFFER: &str = "v=0\r\n\
+o=- 4611731400430051336 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0 1\r\n\
+a=extmap-allow-mixed\r\n\
+a=msid-semantic: WMS stream\r\n\
+a=fingerprint:sha-256 19:E2:1C:3B:4B:9F:81:E6:B8:5C:F4:A5:A8:D8:73:04:BB:05:2F:70:9F:04:A9:0E:05:E9:26:33:E8:70:88:A2\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111 0\r\n\
+c=IN IP4 0.0.0.0\r\n\
+b=AS:64\r\n\
+a=rtcp:9 IN IP4 0.0.0.0\r\n\
+a=ice-ufrag:EsAw\r\n\
+a=ice-pwd:P2uYro0UCOQ4zxjKXaWCBui1\r\n\
+a=setup:actpass\r\n\
+a=mid:0\r\n\
+a=extmap:1 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r\n\
+a=sendrecv\r\n\
+a=msid:stream audio\r\n\
+a=rtcp-mux\r\n\
+a=rtpmap:111 opus/48000/2\r\n\
+a=fmtp:111 minptime=10;useinbandfec=1\r\n\
+a=rtpmap:0 PCMU/8000\r\n\
+a=ssrc:1001 cname:stream\r\n\
+a=candidate:1 1 udp 2113937151 192.0.2.1 54400 typ host\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96 97\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=ice-ufrag:EsAw\r\n\
+a=ice-pwd:P2uYro0UCOQ4zxjKXaWCBui1\r\n\
+a=setup:actpass\r\n\
+a=mid:1\r\n\
+a=sendrecv\r\n\
+a=msid:stream video\r\n\
+a=rtcp-mux\r\n\
+a=rtcp-rsize\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+a=rtcp-fb:96 nack pli\r\n\
+a=rtpmap:97 rtx/90000\r\n\
+a=fmtp:97 apt=96\r\n\
+a=ssrc-group:FID 2001 2002\r\n";
+
+fn unmarshal(value: &str) -> sdp::SessionDescription {
+    sdp::SessionDescription::unmarshal(&mut Cursor::new(value.as_bytes()))
+        .expect("the sdp crate parses the offer")
+}
+
+#[test]
+fn test_round_trip_through_sdp() -> Result<(), Error> {
+    let sd = SessionDescription::try_from(unmarshal(OFFER))?;
+    assert_eq!(sd.marshal(), OFFER);
+    assert_eq!(sdp::SessionDescription::from(sd).marshal(), OFFER);
+
+    Ok(())
+}
+
+#[test]
+fn test_change_direction_for_sdp() -> Result<(), Error> {
+    let mut sd = SessionDescription::try_from(unmarshal(OFFER))?;
+    sd.media_descriptions[1].set_direction(Direction::RecvOnly);
+
+    let upstream = sdp::SessionDescription::from(sd);
+    let marshaled = upstream.marshal();
+    assert_eq!(
+        marshaled,
+        OFFER.replace("a=mid:1\r\na=sendrecv\r\n", "a=mid:1\r\na=recvonly\r\n")
+    );
+    assert_eq!(
+        upstream.media_descriptions[1].attribute("recvonly"),
+        Some(None)
+    );
+    assert_eq!(upstream.media_descriptions[1].attribute("sendrecv"), None);
+    assert_eq!(
+        upstream.media_descriptions[0].attribute("sendrecv"),
+        Some(None)
+    );
+
+    // What the sdp crate writes parses back to the same description.
+    let reparsed = SessionDescription::try_from(unmarshal(&marshaled))?;
+    assert_eq!(
+        reparsed.media_descriptions[1].direction(),
+        Some(Direction::RecvOnly)
+    );
+    assert_eq!(reparsed.marshal(), marshaled);
+
+    Ok(())
+}